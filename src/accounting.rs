@@ -0,0 +1,342 @@
+//! Cash counting and accounting of accepted media.
+//!
+//! A [CashAccounting] follows the device events, and counts every note stacked with a
+//! `Vend Valid` event by currency and denomination, using the [EscrowData] of the preceding
+//! `Escrow` event. Counts are kept for the current session, and for the cashbox contents, and can
+//! be serialized, so hosts can persist them between runs.
+//!
+//! Tallies share the `key=value` record format of [AuditCounters](crate::AuditCounters).
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::counters::{parse_records, write_record};
+use crate::{Currency, CurrencyCode, Error, EscrowData, EscrowEvent, EventCode, Message, Result};
+
+const KEY_SESSION_PREFIX: &str = "session.";
+const KEY_CASHBOX_PREFIX: &str = "cashbox.";
+const KEY_NOTE_PREFIX: &str = "note.";
+const KEY_TICKETS: &str = "tickets";
+
+/// Represents the per-denomination counts of accepted media.
+///
+/// Notes are counted per ISO 4217 currency code and denomination value.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Tally {
+    notes: BTreeMap<(String, u64), u64>,
+    tickets: u64,
+}
+
+impl Tally {
+    /// Creates a new, empty [Tally].
+    pub const fn new() -> Self {
+        Self {
+            notes: BTreeMap::new(),
+            tickets: 0,
+        }
+    }
+
+    /// Gets the number of counted notes of the provided currency and denomination value.
+    pub fn count(&self, code: CurrencyCode, value: u64) -> u64 {
+        self.notes
+            .get(&(<&str>::from(code).into(), value))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Gets the total number of counted notes.
+    pub fn note_count(&self) -> u64 {
+        self.notes
+            .values()
+            .fold(0u64, |acc, &count| acc.saturating_add(count))
+    }
+
+    /// Gets the number of counted tickets.
+    pub const fn tickets(&self) -> u64 {
+        self.tickets
+    }
+
+    /// Gets the total value of counted notes of the provided [CurrencyCode].
+    pub fn total(&self, code: CurrencyCode) -> u64 {
+        let code = <&str>::from(code);
+
+        self.notes
+            .iter()
+            .filter(|((c, _), _)| c == code)
+            .fold(0u64, |acc, ((_, value), &count)| {
+                acc.saturating_add(value.saturating_mul(count))
+            })
+    }
+
+    /// Gets an iterator over the note counts, as `(currency code, denomination value, count)`.
+    pub fn counts(&self) -> impl Iterator<Item = (&str, u64, u64)> {
+        self.notes
+            .iter()
+            .map(|((code, value), count)| (code.as_str(), *value, *count))
+    }
+
+    /// Gets whether nothing was counted.
+    pub fn is_empty(&self) -> bool {
+        self.notes.is_empty() && self.tickets == 0
+    }
+
+    /// Records an accepted note.
+    pub fn record_note(&mut self, currency: &Currency) {
+        let count = self
+            .notes
+            .entry((
+                <&str>::from(currency.code()).into(),
+                currency.denomination().value(),
+            ))
+            .or_default();
+        *count = count.saturating_add(1);
+    }
+
+    /// Records an accepted ticket.
+    pub fn record_ticket(&mut self) {
+        self.tickets = self.tickets.saturating_add(1);
+    }
+
+    /// Resets all counts to zero.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    fn write_records(&self, prefix: &str, out: &mut String) {
+        write_record(out, format_args!("{prefix}{KEY_TICKETS}"), self.tickets);
+        for ((code, value), count) in self.notes.iter() {
+            write_record(
+                out,
+                format_args!("{prefix}{KEY_NOTE_PREFIX}{code}.{value}"),
+                *count,
+            );
+        }
+    }
+
+    fn set_record(&mut self, key: &str, val: u64) -> bool {
+        if key == KEY_TICKETS {
+            self.tickets = val;
+            return true;
+        }
+
+        let note = key
+            .strip_prefix(KEY_NOTE_PREFIX)
+            .and_then(|note| note.split_once('.'))
+            .and_then(|(code, value)| Some((code, value.parse::<u64>().ok()?)));
+
+        match note {
+            Some((code, value)) => {
+                self.notes.insert((code.to_ascii_uppercase(), value), val);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl fmt::Display for Tally {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""tickets": {}, "#, self.tickets)?;
+        write!(f, r#""notes": ["#)?;
+        for (i, ((code, value), count)) in self.notes.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(
+                f,
+                r#"{{"code": "{code}", "value": {value}, "count": {count}}}"#
+            )?;
+        }
+        write!(f, "]}}")
+    }
+}
+
+/// Counts the media accepted by a device, for the current session and the cashbox.
+///
+/// Pass every device event to [on_event](Self::on_event). Resent `Vend Valid` events are counted
+/// once, since each `Escrow` event is consumed by the first `Vend Valid` that follows.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CashAccounting {
+    session: Tally,
+    cashbox: Tally,
+    escrow: Option<EscrowData>,
+}
+
+impl CashAccounting {
+    /// Creates a new, empty [CashAccounting].
+    pub const fn new() -> Self {
+        Self {
+            session: Tally::new(),
+            cashbox: Tally::new(),
+            escrow: None,
+        }
+    }
+
+    /// Gets a reference to the [Tally] of the current session.
+    pub const fn session(&self) -> &Tally {
+        &self.session
+    }
+
+    /// Gets a reference to the [Tally] of the cashbox contents.
+    pub const fn cashbox(&self) -> &Tally {
+        &self.cashbox
+    }
+
+    /// Processes a device event [Message].
+    ///
+    /// Returns the counted [EscrowData] for a `Vend Valid` event, and `None` for all other
+    /// events.
+    pub fn on_event(&mut self, event: &Message) -> Option<EscrowData> {
+        match event.data().message_code().event_code() {
+            Ok(EventCode::Escrow) => {
+                self.escrow = EscrowEvent::try_from(event).ok().map(|e| e.data().clone());
+                None
+            }
+            Ok(EventCode::VendValid) => {
+                let escrow = self.escrow.take()?;
+                self.record(&escrow);
+                Some(escrow)
+            }
+            Ok(EventCode::Returned | EventCode::Rejected | EventCode::AcceptorRejected) => {
+                self.escrow = None;
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// Records accepted [EscrowData], in both the session and cashbox tallies.
+    ///
+    /// Media other than notes and tickets are not counted.
+    pub fn record(&mut self, escrow: &EscrowData) {
+        match escrow {
+            EscrowData::Currency(currency) => {
+                self.session.record_note(currency);
+                self.cashbox.record_note(currency);
+            }
+            EscrowData::Ticket(_) => {
+                self.session.record_ticket();
+                self.cashbox.record_ticket();
+            }
+            media => log::debug!("not counting media: {}", media.media()),
+        }
+    }
+
+    /// Ends the current session, returning its [Tally], and starting a new, empty one.
+    pub fn end_session(&mut self) -> Tally {
+        std::mem::take(&mut self.session)
+    }
+
+    /// Resets the cashbox [Tally], e.g. after a cashbox exchange, returning the previous counts.
+    pub fn reset_cashbox(&mut self) -> Tally {
+        std::mem::take(&mut self.cashbox)
+    }
+
+    /// Serializes the session and cashbox tallies into `key=value` lines.
+    pub fn to_records(&self) -> String {
+        let mut out = String::new();
+
+        self.session.write_records(KEY_SESSION_PREFIX, &mut out);
+        self.cashbox.write_records(KEY_CASHBOX_PREFIX, &mut out);
+
+        out
+    }
+
+    /// Parses `key=value` lines into a [CashAccounting].
+    ///
+    /// Blank lines and unknown keys are ignored, so newer records remain readable.
+    pub fn from_records(records: &str) -> Result<Self> {
+        let mut accounting = Self::new();
+
+        parse_records(records, Error::Accounting, |key, val| {
+            let known = if let Some(key) = key.strip_prefix(KEY_SESSION_PREFIX) {
+                accounting.session.set_record(key, val)
+            } else if let Some(key) = key.strip_prefix(KEY_CASHBOX_PREFIX) {
+                accounting.cashbox.set_record(key, val)
+            } else {
+                false
+            };
+
+            if !known {
+                log::debug!("ignoring unknown accounting key: {key}");
+            }
+        })?;
+
+        Ok(accounting)
+    }
+}
+
+impl fmt::Display for CashAccounting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""session": {}, "#, self.session)?;
+        write!(f, r#""cashbox": {}"#, self.cashbox)?;
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockDevice;
+    use crate::{event_ack, Denomination, IdleRequest, StackRequest};
+
+    fn next_event(device: &MockDevice) -> Result<Message> {
+        let event = device.pending_event().expect("no pending event");
+        device.acknowledge_event(&event_ack(&event))?;
+        Ok(event)
+    }
+
+    #[test]
+    fn test_cash_accounting() -> Result<()> {
+        let device = MockDevice::new();
+        let mut accounting = CashAccounting::new();
+
+        next_event(&device)?;
+        device.handle_request(&IdleRequest::new().into())?;
+        next_event(&device)?;
+
+        for value in [20, 5, 20] {
+            assert!(device.insert_note(
+                Currency::new()
+                    .with_code(CurrencyCode::USD)
+                    .with_denomination(Denomination::from_value(value))
+            ));
+            accounting.on_event(&next_event(&device)?);
+            device.handle_request(&StackRequest::new().into())?;
+
+            let vend = next_event(&device)?;
+            assert!(accounting.on_event(&vend).is_some());
+            // a resent `Vend Valid` is not counted twice
+            assert!(accounting.on_event(&vend).is_none());
+
+            next_event(&device)?;
+            next_event(&device)?;
+        }
+
+        assert_eq!(accounting.session().count(CurrencyCode::USD, 20), 2);
+        assert_eq!(accounting.session().note_count(), 3);
+        assert_eq!(accounting.cashbox().total(CurrencyCode::USD), 45);
+
+        let session = accounting.end_session();
+        assert_eq!(session.total(CurrencyCode::USD), 45);
+        assert!(accounting.session().is_empty());
+
+        let records = accounting.to_records();
+        assert_eq!(
+            records,
+            "session.tickets=0\ncashbox.tickets=0\ncashbox.note.USD.5=1\ncashbox.note.USD.20=2\n"
+        );
+        assert_eq!(CashAccounting::from_records(records.as_str())?, accounting);
+        assert!(matches!(
+            CashAccounting::from_records("cashbox.tickets=x"),
+            Err(Error::Accounting(_))
+        ));
+
+        assert_eq!(accounting.reset_cashbox().note_count(), 3);
+        assert!(accounting.cashbox().is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,318 @@
+//! Structured audit trail of device state changes.
+//!
+//! An [AuditRecorder] derives [AuditRecord]s from device events, and passes them to an
+//! [EventLog] sink, so applications can stream device-side audit trails to external compliance
+//! systems.
+
+use std::sync::{Arc, Mutex};
+use std::{fmt, time};
+
+use crate::fleet::FailoverEvent;
+use crate::{EscrowData, EscrowEvent, EventCode, InterlockCondition, Message, RequestCode};
+
+/// Represents a structured audit record of a device state change.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AuditRecord {
+    /// A deposit was accepted for stacking (`Vend Valid`), with the escrowed data.
+    DepositCompleted(EscrowData),
+    /// A setting was changed by an acknowledged request, with the request code and setting data.
+    ConfigChanged((RequestCode, Vec<u8>)),
+    /// A fault was raised by the event code.
+    FaultRaised(EventCode),
+    /// A previously raised fault was cleared.
+    FaultCleared(EventCode),
+    /// The cashbox was removed from the device.
+    CashboxRemoved,
+    /// An [Interlock](crate::Interlock) condition was engaged.
+    InterlockEngaged(InterlockCondition),
+    /// An [Interlock](crate::Interlock) condition was released.
+    InterlockReleased(InterlockCondition),
+    /// The active acceptor of a [FailoverCoordinator](crate::fleet::FailoverCoordinator) switched.
+    Failover(FailoverEvent),
+}
+
+impl fmt::Display for AuditRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DepositCompleted(data) => write!(f, r#"{{"deposit_completed": {data}}}"#),
+            Self::ConfigChanged((code, data)) => {
+                write!(
+                    f,
+                    r#"{{"config_changed": {{"code": {code}, "data": {data:?}}}}}"#
+                )
+            }
+            Self::FaultRaised(code) => write!(f, r#"{{"fault_raised": {code}}}"#),
+            Self::FaultCleared(code) => write!(f, r#"{{"fault_cleared": {code}}}"#),
+            Self::CashboxRemoved => write!(f, r#"{{"cashbox_removed": null}}"#),
+            Self::InterlockEngaged(cond) => write!(f, r#"{{"interlock_engaged": {cond}}}"#),
+            Self::InterlockReleased(cond) => write!(f, r#"{{"interlock_released": {cond}}}"#),
+            Self::Failover(event) => write!(f, r#"{{"failover": {event}}}"#),
+        }
+    }
+}
+
+/// Represents a timestamped [AuditRecord].
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuditEntry {
+    timestamp: time::SystemTime,
+    record: AuditRecord,
+}
+
+impl AuditEntry {
+    /// Creates a new [AuditEntry], timestamped with the current system time.
+    pub fn new(record: AuditRecord) -> Self {
+        Self::create(time::SystemTime::now(), record)
+    }
+
+    /// Creates a new [AuditEntry] from the provided parameters.
+    pub const fn create(timestamp: time::SystemTime, record: AuditRecord) -> Self {
+        Self { timestamp, record }
+    }
+
+    /// Gets the time the [AuditRecord] was made.
+    pub const fn timestamp(&self) -> time::SystemTime {
+        self.timestamp
+    }
+
+    /// Gets a reference to the [AuditRecord].
+    pub const fn record(&self) -> &AuditRecord {
+        &self.record
+    }
+}
+
+impl fmt::Display for AuditEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let timestamp_ms = self
+            .timestamp
+            .duration_since(time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+
+        write!(f, "{{")?;
+        write!(f, r#""timestamp_ms": {timestamp_ms}, "#)?;
+        write!(f, r#""record": {}"#, self.record)?;
+        write!(f, "}}")
+    }
+}
+
+/// Receives structured [AuditEntry] records.
+///
+/// Implementations should not block the caller for long, e.g. by queueing entries for delivery
+/// to the compliance system on a separate thread.
+pub trait EventLog: Send + Sync {
+    /// Called with each new [AuditEntry].
+    fn record(&self, entry: &AuditEntry);
+}
+
+impl<L: EventLog + ?Sized> EventLog for &L {
+    fn record(&self, entry: &AuditEntry) {
+        (**self).record(entry)
+    }
+}
+
+impl<L: EventLog + ?Sized> EventLog for Arc<L> {
+    fn record(&self, entry: &AuditEntry) {
+        (**self).record(entry)
+    }
+}
+
+/// Represents a volatile [EventLog], useful for tests.
+#[derive(Debug, Default)]
+pub struct MemoryEventLog {
+    entries: Mutex<Vec<AuditEntry>>,
+}
+
+impl MemoryEventLog {
+    /// Creates a new, empty [MemoryEventLog].
+    pub const fn new() -> Self {
+        Self {
+            entries: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Gets a copy of the recorded [AuditEntry] list, oldest first.
+    pub fn entries(&self) -> Vec<AuditEntry> {
+        self.entries
+            .lock()
+            .map(|entries| entries.clone())
+            .unwrap_or_default()
+    }
+
+    /// Gets a copy of the recorded [AuditRecord] list, oldest first.
+    pub fn records(&self) -> Vec<AuditRecord> {
+        self.entries()
+            .into_iter()
+            .map(|entry| entry.record)
+            .collect()
+    }
+}
+
+impl EventLog for MemoryEventLog {
+    fn record(&self, entry: &AuditEntry) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.push(entry.clone());
+        }
+    }
+}
+
+/// Derives [AuditRecord]s from device events, and passes them to an [EventLog].
+///
+/// - `Vend Valid` after an `Escrow` records [AuditRecord::DepositCompleted], once per deposit
+/// - failure and operation error events record [AuditRecord::FaultRaised], once per fault
+/// - `Clear`, `Idle`, and `Inhibit` events after a fault record [AuditRecord::FaultCleared]
+///
+/// Host-side changes are recorded with [on_config_changed](Self::on_config_changed) and
+/// [on_cashbox_removed](Self::on_cashbox_removed).
+#[derive(Debug)]
+pub struct AuditRecorder<L: EventLog> {
+    log: L,
+    escrow: Option<EscrowData>,
+    fault: Option<EventCode>,
+}
+
+impl<L: EventLog> AuditRecorder<L> {
+    /// Creates a new [AuditRecorder] with the provided [EventLog].
+    pub const fn new(log: L) -> Self {
+        Self {
+            log,
+            escrow: None,
+            fault: None,
+        }
+    }
+
+    /// Gets a reference to the [EventLog].
+    pub const fn log(&self) -> &L {
+        &self.log
+    }
+
+    /// Gets the currently raised fault, if any.
+    pub const fn fault(&self) -> Option<EventCode> {
+        self.fault
+    }
+
+    /// Records an [AuditRecord] to the [EventLog].
+    pub fn record(&self, record: AuditRecord) {
+        self.log.record(&AuditEntry::new(record));
+    }
+
+    /// Derives an [AuditRecord] from a device event [Message], and records it.
+    ///
+    /// Returns `None` if the event changes no audited state.
+    pub fn on_event(&mut self, event: &Message) -> Option<AuditRecord> {
+        let record = match event.data().message_code().event_code().ok()? {
+            EventCode::Escrow => {
+                self.escrow = EscrowEvent::try_from(event)
+                    .ok()
+                    .map(|evt| evt.data().clone());
+                None
+            }
+            EventCode::VendValid => self.escrow.take().map(AuditRecord::DepositCompleted),
+            EventCode::Returned | EventCode::Rejected | EventCode::AcceptorRejected => {
+                self.escrow = None;
+                None
+            }
+            code @ (EventCode::Failure
+            | EventCode::OperationError
+            | EventCode::AcceptorFailure
+            | EventCode::AcceptorOperationError) => {
+                if self.fault.replace(code) == Some(code) {
+                    None
+                } else {
+                    Some(AuditRecord::FaultRaised(code))
+                }
+            }
+            EventCode::Clear | EventCode::AcceptorClear | EventCode::Idle | EventCode::Inhibit => {
+                self.fault.take().map(AuditRecord::FaultCleared)
+            }
+            _ => None,
+        }?;
+
+        self.record(record.clone());
+
+        Some(record)
+    }
+
+    /// Records an [AuditRecord::ConfigChanged] for an acknowledged setting request [Message].
+    pub fn on_config_changed(&self, request: &Message) -> Option<AuditRecord> {
+        let code = request.data().message_code().request_code().ok()?;
+        let record = AuditRecord::ConfigChanged((code, request.data().additional().into()));
+
+        self.record(record.clone());
+
+        Some(record)
+    }
+
+    /// Records an [AuditRecord::CashboxRemoved].
+    pub fn on_cashbox_removed(&self) {
+        self.record(AuditRecord::CashboxRemoved);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Currency, CurrencyCode, Denomination, EventType, InhibitDirection, MessageCode,
+        MessageData, MessageType, Result,
+    };
+
+    fn event(code: EventCode) -> Message {
+        Message::new().with_data(
+            MessageData::new()
+                .with_message_type(MessageType::Event(EventType::Sequence0))
+                .with_message_code(MessageCode::Event(code)),
+        )
+    }
+
+    #[test]
+    fn test_audit_recorder() -> Result<()> {
+        let log = Arc::new(MemoryEventLog::new());
+        let mut recorder = AuditRecorder::new(Arc::clone(&log));
+
+        let data = EscrowData::Currency(
+            Currency::new()
+                .with_code(CurrencyCode::JPY)
+                .with_denomination(Denomination::from_value(1000)),
+        );
+        let escrow = Message::from(EscrowEvent::create(EventType::Sequence0, data.clone()));
+
+        assert!(recorder.on_event(&escrow).is_none());
+        assert_eq!(
+            recorder.on_event(&event(EventCode::VendValid)),
+            Some(AuditRecord::DepositCompleted(data))
+        );
+        // resent `Vend Valid` events complete no further deposits
+        assert!(recorder.on_event(&event(EventCode::VendValid)).is_none());
+
+        recorder.on_event(&escrow);
+        recorder.on_event(&event(EventCode::Returned));
+        assert!(recorder.on_event(&event(EventCode::VendValid)).is_none());
+
+        recorder.on_event(&event(EventCode::Failure));
+        recorder.on_event(&event(EventCode::Failure));
+        assert_eq!(recorder.fault(), Some(EventCode::Failure));
+        recorder.on_event(&event(EventCode::Idle));
+        assert!(recorder.fault().is_none());
+
+        let req = Message::from(
+            crate::DirectionDisableRequest::new()
+                .with_mode(crate::DirectionDisableMode::Set)
+                .with_direction(InhibitDirection::create(0xf)),
+        );
+        recorder.on_config_changed(&req);
+        recorder.on_cashbox_removed();
+
+        assert_eq!(log.records().len(), 5);
+        assert_eq!(
+            log.records()[1..],
+            [
+                AuditRecord::FaultRaised(EventCode::Failure),
+                AuditRecord::FaultCleared(EventCode::Failure),
+                AuditRecord::ConfigChanged((RequestCode::DirectionDisable, vec![0xf])),
+                AuditRecord::CashboxRemoved,
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,459 @@
+use std::fmt;
+
+use crate::{Error, EventCode, Message, MessageType, RequestType, Response, ResponseCode, Result};
+
+/// Represents a single named request in a [ConfigPipeline].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfigStep {
+    name: String,
+    request: Message,
+}
+
+impl ConfigStep {
+    /// Creates a new [ConfigStep] from the provided parameters.
+    pub fn create<M: Into<Message>>(name: &str, request: M) -> Self {
+        Self {
+            name: name.into(),
+            request: request.into(),
+        }
+    }
+
+    /// Gets the name of the [ConfigStep].
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Gets a reference to the request [Message] of the [ConfigStep].
+    pub const fn request(&self) -> &Message {
+        &self.request
+    }
+
+    /// Gets the request [Message] that reads back the setting of a `SetFeature` [ConfigStep].
+    ///
+    /// Returns `None` for other request types.
+    pub fn verify_request(&self) -> Option<Message> {
+        match self.request.data().message_type() {
+            MessageType::Request(RequestType::SetFeature) => Some(
+                Message::new().with_data(
+                    self.request
+                        .data()
+                        .clone()
+                        .with_message_type(MessageType::Request(RequestType::Status))
+                        .with_additional(&[]),
+                ),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Reads back the setting of the [ConfigStep] through the polling function.
+    ///
+    /// Returns whether the device still holds the setting, or `None` if the [ConfigStep] is not
+    /// verifiable.
+    pub fn verify<F>(&self, mut poll: F) -> Result<Option<bool>>
+    where
+        F: FnMut(&Message) -> Result<Message>,
+    {
+        match self.verify_request() {
+            Some(req) => {
+                let res = Response::try_from(poll(&req)?)?;
+
+                Ok(Some(
+                    res.code() == ResponseCode::Ack
+                        && res.additional() == self.request.data().additional(),
+                ))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+impl fmt::Display for ConfigStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""name": "{}", "#, self.name)?;
+        write!(f, r#""request": {}"#, self.request)?;
+        write!(f, "}}")
+    }
+}
+
+/// Represents an ordered list of configuration requests (UID, denominations, near-full, etc.)
+/// sent to the device after `Power Up`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConfigPipeline {
+    steps: Vec<ConfigStep>,
+}
+
+impl ConfigPipeline {
+    /// Creates a new, empty [ConfigPipeline].
+    pub const fn new() -> Self {
+        Self { steps: Vec::new() }
+    }
+
+    /// Gets a reference to the list of [ConfigStep]s.
+    pub fn steps(&self) -> &[ConfigStep] {
+        self.steps.as_ref()
+    }
+
+    /// Gets the number of [ConfigStep]s.
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Gets whether the [ConfigPipeline] is empty.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Appends a request to the [ConfigPipeline].
+    pub fn push_step<M: Into<Message>>(&mut self, name: &str, request: M) {
+        self.steps.push(ConfigStep::create(name, request));
+    }
+
+    /// Builder function that appends a request to the [ConfigPipeline].
+    pub fn with_step<M: Into<Message>>(mut self, name: &str, request: M) -> Self {
+        self.push_step(name, request);
+        self
+    }
+
+    /// Runs every [ConfigStep] in order through the polling function.
+    ///
+    /// Stops at the first step that fails, or is not acknowledged by the device.
+    pub fn run<F>(&self, mut poll: F) -> Result<()>
+    where
+        F: FnMut(&Message) -> Result<Message>,
+    {
+        for step in self.steps.iter() {
+            let res = poll(step.request())
+                .and_then(|res| Response::try_from(&res))
+                .and_then(|res| match res.code() {
+                    ResponseCode::Ack => Ok(()),
+                    code => Err(Error::InvalidResponseCode(code.into())),
+                });
+
+            if let Err(err) = res {
+                log::warn!("configuration step {} failed: {err}", step.name());
+                return Err(err);
+            }
+
+            log::debug!("configuration step {} complete", step.name());
+        }
+
+        Ok(())
+    }
+
+    /// Reads back the setting of every verifiable [ConfigStep] through the polling function.
+    ///
+    /// Returns whether the device still holds every setting. A pipeline without verifiable steps
+    /// is never considered retained.
+    pub fn verify<F>(&self, mut poll: F) -> Result<bool>
+    where
+        F: FnMut(&Message) -> Result<Message>,
+    {
+        let mut verified = 0usize;
+
+        for step in self.steps.iter() {
+            match step.verify(&mut poll)? {
+                Some(true) => verified += 1,
+                Some(false) => {
+                    log::debug!("configuration step {} not retained", step.name());
+                    return Ok(false);
+                }
+                None => (),
+            }
+        }
+
+        Ok(verified > 0)
+    }
+}
+
+/// Represents whether the device retained its `SetFeature` configuration across a `Power Up`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ConfigPersistence {
+    retained: bool,
+}
+
+impl ConfigPersistence {
+    /// Creates a new [ConfigPersistence] from the provided parameters.
+    pub const fn create(retained: bool) -> Self {
+        Self { retained }
+    }
+
+    /// Gets whether the device retained its configuration.
+    pub const fn retained(&self) -> bool {
+        self.retained
+    }
+}
+
+impl fmt::Display for ConfigPersistence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#"{{"retained": {}}}"#, self.retained)
+    }
+}
+
+/// Represents the notification that the device was reconfigured after a `Power Up`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Reconfigured {
+    power_up: EventCode,
+    steps: usize,
+    count: u64,
+    persistence: Option<ConfigPersistence>,
+}
+
+impl Reconfigured {
+    /// Gets the `Power Up` [EventCode] that triggered the reconfiguration.
+    pub const fn power_up(&self) -> EventCode {
+        self.power_up
+    }
+
+    /// Gets the number of configuration steps that were applied.
+    ///
+    /// No steps are applied when the device retained its configuration.
+    pub const fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Gets the number of reconfigurations since the [AutoConfigurator] was created.
+    pub const fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Gets the detected [ConfigPersistence], if the configuration was verified.
+    pub const fn persistence(&self) -> Option<ConfigPersistence> {
+        self.persistence
+    }
+}
+
+impl fmt::Display for Reconfigured {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""power_up": {}, "#, self.power_up)?;
+        write!(f, r#""steps": {}, "#, self.steps)?;
+        write!(f, r#""count": {}, "#, self.count)?;
+        match self.persistence {
+            Some(persistence) => write!(f, r#""persistence": {persistence}"#)?,
+            None => write!(f, r#""persistence": null"#)?,
+        }
+        write!(f, "}}")
+    }
+}
+
+/// Re-applies a [ConfigPipeline] after every `Power Up` event.
+///
+/// A device reboot (expected, or mid-session) loses all `SetFeature` state. The
+/// [AutoConfigurator] marks the device unavailable when it sees any `Power Up` variant, and only
+/// marks it available again once the whole pipeline has been acknowledged.
+///
+/// Some devices retain their configuration across a `Reset` or power cycle. With
+/// [persistence verification](Self::with_verify_persistence) enabled, the [AutoConfigurator]
+/// reads back the `SetFeature` settings after every `Power Up` but the first, and only re-applies
+/// the pipeline when the device lost them.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AutoConfigurator {
+    pipeline: ConfigPipeline,
+    available: bool,
+    count: u64,
+    verify_persistence: bool,
+    persistence: Option<ConfigPersistence>,
+}
+
+impl AutoConfigurator {
+    /// Creates a new [AutoConfigurator] for the provided [ConfigPipeline].
+    ///
+    /// The device is unavailable until the first `Power Up` is processed.
+    pub const fn new(pipeline: ConfigPipeline) -> Self {
+        Self {
+            pipeline,
+            available: false,
+            count: 0,
+            verify_persistence: false,
+            persistence: None,
+        }
+    }
+
+    /// Gets a reference to the [ConfigPipeline].
+    pub const fn pipeline(&self) -> &ConfigPipeline {
+        &self.pipeline
+    }
+
+    /// Gets whether the device is configured, and available for use.
+    pub const fn is_available(&self) -> bool {
+        self.available
+    }
+
+    /// Gets the number of successful reconfigurations.
+    pub const fn reconfigurations(&self) -> u64 {
+        self.count
+    }
+
+    /// Gets whether configuration persistence is verified after a `Power Up`.
+    pub const fn verify_persistence(&self) -> bool {
+        self.verify_persistence
+    }
+
+    /// Sets whether configuration persistence is verified after a `Power Up`.
+    pub fn set_verify_persistence(&mut self, val: bool) {
+        self.verify_persistence = val;
+    }
+
+    /// Builder function that sets whether configuration persistence is verified after a
+    /// `Power Up`.
+    pub fn with_verify_persistence(mut self, val: bool) -> Self {
+        self.set_verify_persistence(val);
+        self
+    }
+
+    /// Gets the last detected [ConfigPersistence], if any.
+    pub const fn persistence(&self) -> Option<ConfigPersistence> {
+        self.persistence
+    }
+
+    /// Processes a device [Message], running the [ConfigPipeline] if it is a `Power Up` event.
+    ///
+    /// Returns [Reconfigured] when the pipeline completes, and `None` for other messages.
+    pub fn on_message<F>(&mut self, message: &Message, poll: F) -> Result<Option<Reconfigured>>
+    where
+        F: FnMut(&Message) -> Result<Message>,
+    {
+        match message.data().message_code().event_code() {
+            Ok(code) if code.is_power_up() => self.on_power_up(code, poll).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Runs the [ConfigPipeline] in response to a `Power Up` event.
+    pub fn on_power_up<F>(&mut self, power_up: EventCode, mut poll: F) -> Result<Reconfigured>
+    where
+        F: FnMut(&Message) -> Result<Message>,
+    {
+        if self.available {
+            log::warn!("unexpected {power_up} event, reconfiguring device");
+        }
+
+        self.available = false;
+
+        let persistence = if self.verify_persistence && self.count > 0 {
+            let retained = self.pipeline.verify(&mut poll).unwrap_or_else(|err| {
+                log::warn!("unable to verify configuration persistence: {err}");
+                false
+            });
+            log::info!("configuration retained after {power_up}: {retained}");
+
+            Some(ConfigPersistence::create(retained))
+        } else {
+            None
+        };
+        self.persistence = persistence.or(self.persistence);
+
+        let steps = if persistence.map(|p| p.retained()).unwrap_or(false) {
+            0
+        } else {
+            self.pipeline.run(&mut poll)?;
+            self.pipeline.len()
+        };
+
+        self.available = true;
+        self.count = self.count.saturating_add(1);
+
+        Ok(Reconfigured {
+            power_up,
+            steps,
+            count: self.count,
+            persistence,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventType, MessageCode, MessageData, MessageType, UidRequest};
+
+    fn event(code: EventCode) -> Message {
+        Message::new().with_data(
+            MessageData::new()
+                .with_message_type(MessageType::Event(EventType::Sequence0))
+                .with_message_code(MessageCode::Event(code)),
+        )
+    }
+
+    fn reply(req: &Message, code: ResponseCode) -> Result<Message> {
+        Ok(Message::new().with_data(req.data().clone().with_additional(&[code.into()])))
+    }
+
+    #[test]
+    fn test_autoconfigurator() -> Result<()> {
+        let pipeline = ConfigPipeline::new()
+            .with_step("uid", UidRequest::new_set(1))
+            .with_step("uid_check", UidRequest::new_get());
+        let mut config = AutoConfigurator::new(pipeline);
+
+        let mut sent = 0;
+        let mut ack = |req: &Message| {
+            sent += 1;
+            reply(req, ResponseCode::Ack)
+        };
+
+        assert_eq!(config.on_message(&event(EventCode::Idle), &mut ack)?, None);
+        assert!(!config.is_available());
+
+        let res = config.on_message(&event(EventCode::PowerUp), &mut ack)?;
+        assert_eq!(res.map(|r| (r.steps(), r.count())), Some((2, 1)));
+        assert!(config.is_available());
+
+        // a mid-session reboot that fails to reconfigure leaves the device unavailable
+        let res = config.on_message(&event(EventCode::PowerUpStacker), |req| {
+            reply(req, ResponseCode::Nak)
+        });
+        assert_eq!(
+            res,
+            Err(Error::InvalidResponseCode(ResponseCode::Nak.into()))
+        );
+        assert!(!config.is_available());
+
+        let res = config.on_message(&event(EventCode::PowerUpAcceptor), &mut ack)?;
+        assert_eq!(res.map(|r| r.power_up()), Some(EventCode::PowerUpAcceptor));
+        assert_eq!(config.reconfigurations(), 2);
+        assert!(config.is_available());
+
+        assert_eq!(sent, 4);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_config_persistence() -> Result<()> {
+        let pipeline = ConfigPipeline::new().with_step("uid", UidRequest::new_set(1));
+        let mut config = AutoConfigurator::new(pipeline).with_verify_persistence(true);
+
+        let uid = std::cell::Cell::new(0);
+        let device = |req: &Message| {
+            let additional = match req.data().message_type() {
+                MessageType::Request(RequestType::SetFeature) => {
+                    uid.set(req.data().additional()[0]);
+                    vec![ResponseCode::Ack.into()]
+                }
+                _ => vec![ResponseCode::Ack.into(), uid.get()],
+            };
+            Ok(Message::new().with_data(req.data().clone().with_additional(&additional)))
+        };
+
+        let res = config.on_power_up(EventCode::PowerUp, device)?;
+        assert_eq!((res.steps(), res.persistence()), (1, None));
+
+        // the device kept the UID across the reset
+        let res = config.on_power_up(EventCode::PowerUp, device)?;
+        assert_eq!(res.steps(), 0);
+        assert_eq!(res.persistence(), Some(ConfigPersistence::create(true)));
+
+        // the device lost the UID across the power cycle, and is reconfigured
+        uid.set(0);
+        let res = config.on_power_up(EventCode::PowerUp, device)?;
+        assert_eq!(res.steps(), 1);
+        assert_eq!(config.persistence(), Some(ConfigPersistence::create(false)));
+        assert_eq!(uid.get(), 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,232 @@
+use core::fmt;
+
+use crate::{Error, Result};
+
+/// Represents the length of [BarCodeSettings].
+pub const BAR_CODE_SETTINGS_LEN: usize = 2;
+/// Represents the minimum number of characters of an accepted barcode.
+pub const MIN_BAR_CODE_DIGITS: u8 = 6;
+/// Represents the maximum number of characters of an accepted barcode.
+pub const MAX_BAR_CODE_DIGITS: u8 = 32;
+
+const INTERLEAVED_2_OF_5: u8 = 0x01;
+const CODE_128: u8 = 0x02;
+const CODE_39: u8 = 0x03;
+
+/// Represents the barcode symbology accepted by the device.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BarCodeType {
+    /// Interleaved 2 of 5, numeric characters encoded in pairs.
+    #[default]
+    Interleaved2of5 = INTERLEAVED_2_OF_5,
+    /// Code 128, full ASCII.
+    Code128 = CODE_128,
+    /// Code 39, upper-case alphanumeric.
+    Code39 = CODE_39,
+}
+
+impl BarCodeType {
+    /// Creates a new [BarCodeType].
+    pub const fn new() -> Self {
+        Self::Interleaved2of5
+    }
+
+    /// Gets whether the [BarCodeType] supports the number of characters.
+    ///
+    /// Interleaved 2 of 5 encodes characters in pairs, so only even counts are supported.
+    pub const fn supports_digits(&self, digits: u8) -> bool {
+        let in_range = digits >= MIN_BAR_CODE_DIGITS && digits <= MAX_BAR_CODE_DIGITS;
+
+        match self {
+            Self::Interleaved2of5 => in_range && digits.is_multiple_of(2),
+            Self::Code128 | Self::Code39 => in_range,
+        }
+    }
+}
+
+impl TryFrom<u8> for BarCodeType {
+    type Error = Error;
+
+    fn try_from(val: u8) -> Result<Self> {
+        match val {
+            INTERLEAVED_2_OF_5 => Ok(Self::Interleaved2of5),
+            CODE_128 => Ok(Self::Code128),
+            CODE_39 => Ok(Self::Code39),
+            _ => Err(Error::InvalidBarCodeType(val)),
+        }
+    }
+}
+
+impl From<BarCodeType> for u8 {
+    fn from(val: BarCodeType) -> Self {
+        val as u8
+    }
+}
+
+impl From<BarCodeType> for &'static str {
+    fn from(val: BarCodeType) -> Self {
+        match val {
+            BarCodeType::Interleaved2of5 => "interleaved 2 of 5",
+            BarCodeType::Code128 => "code 128",
+            BarCodeType::Code39 => "code 39",
+        }
+    }
+}
+
+impl From<&BarCodeType> for &'static str {
+    fn from(val: &BarCodeType) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for BarCodeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
+
+/// Represents the barcode ticket settings of the device.
+///
+/// ## Format
+///
+/// Field  | Barcode type | Digits
+/// -------|--------------|-------
+/// Length | 1 byte       | 1 byte
+///
+/// # Example
+///
+/// ```
+/// use jcm::{BarCodeSettings, BarCodeType};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// let settings = BarCodeSettings::create(BarCodeType::Interleaved2of5, 18)?;
+///
+/// assert_eq!(settings.into_bytes(), [0x01, 18]);
+/// assert!(BarCodeSettings::create(BarCodeType::Interleaved2of5, 17).is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BarCodeSettings {
+    bar_code_type: BarCodeType,
+    digits: u8,
+}
+
+impl BarCodeSettings {
+    /// Creates a new [BarCodeSettings], for 18-digit Interleaved 2 of 5 tickets.
+    pub const fn new() -> Self {
+        Self {
+            bar_code_type: BarCodeType::Interleaved2of5,
+            digits: 18,
+        }
+    }
+
+    /// Creates a new [BarCodeSettings] from the provided parameters.
+    ///
+    /// Returns an error if the [BarCodeType] does not support the number of characters.
+    pub const fn create(bar_code_type: BarCodeType, digits: u8) -> Result<Self> {
+        if bar_code_type.supports_digits(digits) {
+            Ok(Self {
+                bar_code_type,
+                digits,
+            })
+        } else {
+            Err(Error::InvalidBarCodeDigits((bar_code_type as u8, digits)))
+        }
+    }
+
+    /// Gets the [BarCodeType].
+    pub const fn bar_code_type(&self) -> BarCodeType {
+        self.bar_code_type
+    }
+
+    /// Gets the number of barcode characters.
+    pub const fn digits(&self) -> u8 {
+        self.digits
+    }
+
+    /// Gets the byte length of the [BarCodeSettings].
+    pub const fn len() -> usize {
+        BAR_CODE_SETTINGS_LEN
+    }
+
+    /// Attempts to convert a byte buffer into [BarCodeSettings].
+    pub fn from_bytes(buf: &[u8]) -> Result<Self> {
+        match buf {
+            [bar_code_type, digits, ..] => Self::create((*bar_code_type).try_into()?, *digits),
+            _ => Err(Error::InvalidBarCodeSettingsLen((
+                buf.len(),
+                BAR_CODE_SETTINGS_LEN,
+            ))),
+        }
+    }
+
+    /// Converts the [BarCodeSettings] into a byte array.
+    pub const fn into_bytes(self) -> [u8; BAR_CODE_SETTINGS_LEN] {
+        [self.bar_code_type as u8, self.digits]
+    }
+}
+
+impl Default for BarCodeSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TryFrom<&[u8]> for BarCodeSettings {
+    type Error = Error;
+
+    fn try_from(val: &[u8]) -> Result<Self> {
+        Self::from_bytes(val)
+    }
+}
+
+impl fmt::Display for BarCodeSettings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""bar_code_type": {}, "#, self.bar_code_type)?;
+        write!(f, r#""digits": {}"#, self.digits)?;
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bar_code_settings() -> Result<()> {
+        let settings = BarCodeSettings::from_bytes(&[CODE_128, 12])?;
+        assert_eq!(settings.bar_code_type(), BarCodeType::Code128);
+        assert_eq!(settings.digits(), 12);
+        assert_eq!(settings.into_bytes(), [CODE_128, 12]);
+        assert_eq!(
+            settings.to_string(),
+            r#"{"bar_code_type": "code 128", "digits": 12}"#
+        );
+
+        assert!(BarCodeType::Code39.supports_digits(MAX_BAR_CODE_DIGITS));
+        assert!(!BarCodeType::Code39.supports_digits(MAX_BAR_CODE_DIGITS + 1));
+        assert!(!BarCodeType::Code128.supports_digits(MIN_BAR_CODE_DIGITS - 1));
+        assert!(!BarCodeType::Interleaved2of5.supports_digits(19));
+
+        assert_eq!(
+            BarCodeSettings::from_bytes(&[0x7f, 18]),
+            Err(Error::InvalidBarCodeType(0x7f))
+        );
+        assert_eq!(
+            BarCodeSettings::from_bytes(&[INTERLEAVED_2_OF_5, 7]),
+            Err(Error::InvalidBarCodeDigits((INTERLEAVED_2_OF_5, 7)))
+        );
+        assert_eq!(
+            BarCodeSettings::from_bytes(&[CODE_39]),
+            Err(Error::InvalidBarCodeSettingsLen((1, BAR_CODE_SETTINGS_LEN)))
+        );
+
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
-use std::fmt;
+use core::fmt;
 
-use crate::{Error, Result};
+use crate::{Error, EventCode, Result};
 
 const INIT_STATE: u8 = 1;
 const INHIBITED_STATE: u8 = 2;
@@ -9,9 +9,39 @@ const ESCROWED_STATE: u8 = 4;
 const VEND_VALID_STATE: u8 = 5;
 const RESERVED_STATE: u8 = 0xff;
 
+/// Represents the allowed [BillAcceptorState] transitions as `(from, to)` pairs.
+///
+/// Remaining in the same state (e.g. repeated `Idle` events) is always allowed, and is not listed.
+pub const BILL_ACCEPTOR_TRANSITIONS: [(BillAcceptorState, BillAcceptorState); 14] = [
+    (
+        BillAcceptorState::Initializing,
+        BillAcceptorState::Inhibited,
+    ),
+    (BillAcceptorState::Initializing, BillAcceptorState::Idle),
+    (
+        BillAcceptorState::Inhibited,
+        BillAcceptorState::Initializing,
+    ),
+    (BillAcceptorState::Inhibited, BillAcceptorState::Idle),
+    (BillAcceptorState::Idle, BillAcceptorState::Initializing),
+    (BillAcceptorState::Idle, BillAcceptorState::Inhibited),
+    (BillAcceptorState::Idle, BillAcceptorState::Escrowed),
+    (BillAcceptorState::Escrowed, BillAcceptorState::Initializing),
+    (BillAcceptorState::Escrowed, BillAcceptorState::Inhibited),
+    (BillAcceptorState::Escrowed, BillAcceptorState::Idle),
+    (BillAcceptorState::Escrowed, BillAcceptorState::VendValid),
+    (
+        BillAcceptorState::VendValid,
+        BillAcceptorState::Initializing,
+    ),
+    (BillAcceptorState::VendValid, BillAcceptorState::Inhibited),
+    (BillAcceptorState::VendValid, BillAcceptorState::Idle),
+];
+
 /// Represents the state of the bill acceptor.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BillAcceptorState {
     Initializing = INIT_STATE,
     Inhibited = INHIBITED_STATE,
@@ -38,6 +68,51 @@ impl BillAcceptorState {
             _ => Self::Reserved,
         }
     }
+
+    /// Infallible conversion from an [EventCode] into a [BillAcceptorState].
+    ///
+    /// Returns [BillAcceptorState::Reserved] for events that do not announce a state.
+    pub const fn from_event_code(code: EventCode) -> Self {
+        match code {
+            EventCode::PowerUp
+            | EventCode::PowerUpAcceptor
+            | EventCode::PowerUpStacker
+            | EventCode::PowerUpAcceptorAccepting
+            | EventCode::PowerUpStackerAccepting => Self::Initializing,
+            EventCode::Inhibit => Self::Inhibited,
+            EventCode::Idle => Self::Idle,
+            EventCode::Escrow => Self::Escrowed,
+            EventCode::VendValid => Self::VendValid,
+            _ => Self::Reserved,
+        }
+    }
+
+    /// Gets whether the device may move from the `from` state to the `to` state.
+    ///
+    /// See [BILL_ACCEPTOR_TRANSITIONS] for the full table.
+    pub const fn can_transition(from: Self, to: Self) -> bool {
+        if from as u8 == to as u8 {
+            !matches!(from, Self::Reserved)
+        } else {
+            let mut i = 0;
+            while i < BILL_ACCEPTOR_TRANSITIONS.len() {
+                let (f, t) = BILL_ACCEPTOR_TRANSITIONS[i];
+                if f as u8 == from as u8 && t as u8 == to as u8 {
+                    return true;
+                }
+                i += 1;
+            }
+            false
+        }
+    }
+
+    /// Gets the list of states reachable from the current state.
+    pub fn next_states(&self) -> impl Iterator<Item = BillAcceptorState> + '_ {
+        BILL_ACCEPTOR_TRANSITIONS
+            .iter()
+            .filter(move |(from, _)| from == self)
+            .map(|(_, to)| *to)
+    }
 }
 
 impl Default for BillAcceptorState {
@@ -103,7 +178,7 @@ mod tests {
             BillAcceptorState::VendValid,
         ];
 
-        for (raw, exp) in raw_denom.into_iter().zip(expected.into_iter()) {
+        for (raw, exp) in raw_denom.into_iter().zip(expected) {
             assert_eq!(BillAcceptorState::try_from(raw), Ok(exp));
             assert_eq!(BillAcceptorState::from_u8(raw), exp);
         }
@@ -116,4 +191,30 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_bill_acceptor_state_transitions() {
+        use BillAcceptorState::*;
+
+        for (from, to) in BILL_ACCEPTOR_TRANSITIONS {
+            assert!(BillAcceptorState::can_transition(from, to));
+        }
+
+        for state in [Initializing, Inhibited, Idle, Escrowed, VendValid] {
+            assert!(BillAcceptorState::can_transition(state, state));
+            assert!(BillAcceptorState::can_transition(state, Initializing));
+            assert!(!BillAcceptorState::can_transition(state, Reserved));
+            assert!(!BillAcceptorState::can_transition(Reserved, state));
+        }
+
+        assert!(!BillAcceptorState::can_transition(Inhibited, Escrowed));
+        assert!(!BillAcceptorState::can_transition(Idle, VendValid));
+        assert!(!BillAcceptorState::can_transition(VendValid, Escrowed));
+        assert!(!BillAcceptorState::can_transition(Initializing, VendValid));
+
+        assert_eq!(
+            Idle.next_states().collect::<Vec<_>>(),
+            [Initializing, Inhibited, Escrowed]
+        );
+    }
 }
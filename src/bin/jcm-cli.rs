@@ -0,0 +1,33 @@
+//! Runs a single command against an attached JCM USB device.
+//!
+//! See the [jcm::cli] module for the supported commands.
+
+use std::io;
+use std::process::ExitCode;
+
+use jcm::cli::{CliArgs, CliCommand, CliSession, CLI_USAGE};
+
+fn main() -> ExitCode {
+    let args = match CliArgs::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{err}\n\n{CLI_USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    if args.command() == &CliCommand::Help {
+        println!("{CLI_USAGE}");
+        return ExitCode::SUCCESS;
+    }
+
+    match CliSession::open_usb(&args)
+        .and_then(|session| session.run(args.command(), &mut io::stdout().lock()))
+    {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
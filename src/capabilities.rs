@@ -0,0 +1,209 @@
+use std::fmt;
+
+use crate::{
+    DeviceInfo, Feature, FeatureSet, FirmwareVersion, Message, ModelName, NearFullData,
+    NearFullRequest, NearFullResponse, ProductFamily, RequestCode, ResponseCode, Result,
+};
+
+/// Represents the optional features supported by a device.
+///
+/// Query the capabilities once at startup, with [query](Self::query), so applications can branch
+/// on the supported features instead of sending requests the device refuses.
+///
+/// The [ProductFamily] comes from the model name, the recycler and note image support from the
+/// firmware revision (see [FeatureSet]), and the `Near Full` support from probing the current
+/// `Near Full` setting.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Capabilities {
+    model_name: ModelName,
+    firmware_version: FirmwareVersion,
+    family: ProductFamily,
+    features: FeatureSet,
+    max_denominations: usize,
+    near_full: Option<NearFullData>,
+}
+
+impl Capabilities {
+    /// Creates a new [Capabilities] from the [DeviceInfo], and the `Near Full` setting, if
+    /// supported.
+    pub fn create(info: &DeviceInfo, near_full: Option<NearFullData>) -> Self {
+        Self {
+            model_name: info.model_name().clone(),
+            firmware_version: info.firmware_version().clone(),
+            family: info.model_name().into(),
+            features: FeatureSet::for_firmware(info.firmware_version()),
+            max_denominations: info.currency_assign().len(),
+            near_full,
+        }
+    }
+
+    /// Queries the `ModelName`, `Version`, `CurrencyAssign`, and `Near Full` settings of the
+    /// device through the polling function.
+    ///
+    /// Fails if the identity requests are not acknowledged, see [DeviceInfo::query]. A refused
+    /// `Near Full` request marks the feature as unsupported.
+    pub fn query<F>(serial: &str, mut poll: F) -> Result<Self>
+    where
+        F: FnMut(&Message) -> Result<Message>,
+    {
+        let info = DeviceInfo::query(serial, &mut poll)?;
+        let near_full = Self::probe_near_full(poll);
+
+        Ok(Self::create(&info, near_full))
+    }
+
+    fn probe_near_full<F>(mut poll: F) -> Option<NearFullData>
+    where
+        F: FnMut(&Message) -> Result<Message>,
+    {
+        match poll(&NearFullRequest::new().into()).and_then(NearFullResponse::try_from) {
+            Ok(res) if res.code() == ResponseCode::Ack => res.data(),
+            Ok(res) => {
+                log::debug!("Near Full not supported, response code: {}", res.code());
+                None
+            }
+            Err(err) => {
+                log::debug!("Near Full not supported: {err}");
+                None
+            }
+        }
+    }
+
+    /// Gets a reference to the [ModelName] of the device.
+    pub const fn model_name(&self) -> &ModelName {
+        &self.model_name
+    }
+
+    /// Gets a reference to the [FirmwareVersion] of the device.
+    pub const fn firmware_version(&self) -> &FirmwareVersion {
+        &self.firmware_version
+    }
+
+    /// Gets the [ProductFamily] of the device.
+    pub const fn family(&self) -> ProductFamily {
+        self.family
+    }
+
+    /// Gets the [FeatureSet] enabled by the device firmware.
+    pub const fn features(&self) -> FeatureSet {
+        self.features
+    }
+
+    /// Gets whether the device supports recycler collection and dispense.
+    pub const fn has_recycler(&self) -> bool {
+        self.features.contains(Feature::Recycler)
+    }
+
+    /// Gets whether the device supports note image retrieval.
+    pub const fn supports_note_image(&self) -> bool {
+        self.features.contains(Feature::NoteImage)
+    }
+
+    /// Gets whether the device supports security mode configuration.
+    pub const fn supports_security_mode(&self) -> bool {
+        self.features.contains(Feature::SecurityMode)
+    }
+
+    /// Gets whether the device supports the `Near Full` settings.
+    pub const fn supports_near_full(&self) -> bool {
+        self.near_full.is_some()
+    }
+
+    /// Gets the `Near Full` settings read while probing the device, if supported.
+    pub const fn near_full(&self) -> Option<NearFullData> {
+        self.near_full
+    }
+
+    /// Gets the number of denominations assigned on the device.
+    pub const fn max_denominations(&self) -> usize {
+        self.max_denominations
+    }
+
+    /// Gets whether the device supports the request.
+    pub fn supports(&self, code: RequestCode) -> bool {
+        match code {
+            RequestCode::NearFull => self.supports_near_full(),
+            code => self.features.check(code).is_ok(),
+        }
+    }
+}
+
+impl fmt::Display for Capabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""model_name": {}, "#, self.model_name)?;
+        write!(f, r#""firmware_version": {}, "#, self.firmware_version)?;
+        write!(f, r#""family": {}, "#, self.family)?;
+        write!(f, r#""features": {}, "#, self.features)?;
+        write!(f, r#""max_denominations": {}, "#, self.max_denominations)?;
+        match self.near_full.as_ref() {
+            Some(near_full) => write!(f, r#""near_full": {near_full}"#)?,
+            None => write!(f, r#""near_full": null"#)?,
+        }
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ModelNameResponse, VersionResponse};
+
+    #[test]
+    fn test_capabilities() -> Result<()> {
+        let poll = |version: &'static str, near_full: ResponseCode| {
+            move |req: &Message| -> Result<Message> {
+                match req.data().message_code().request_code()? {
+                    RequestCode::ModelName => Ok(ModelNameResponse::new()
+                        .with_code(ResponseCode::Ack)
+                        .with_model_name(ModelName::from_string("iVIZION"))
+                        .into()),
+                    RequestCode::Version => Ok(VersionResponse::new()
+                        .with_code(ResponseCode::Ack)
+                        .with_firmware_version(FirmwareVersion::new().with_version(version))
+                        .into()),
+                    RequestCode::NearFull => Ok(Message::new().with_data(
+                        req.data().clone().with_additional(
+                            &NearFullResponse::new()
+                                .with_code(near_full)
+                                .with_data(NearFullData::new())
+                                .into_bytes(),
+                        ),
+                    )),
+                    _ => Ok(
+                        Message::new().with_data(req.data().clone().with_additional(&[
+                            u8::from(ResponseCode::Ack),
+                            0,
+                            b'U',
+                            b'S',
+                            b'D',
+                            1,
+                            0,
+                        ])),
+                    ),
+                }
+            }
+        };
+
+        let caps = Capabilities::query("A000001", poll("V2.03", ResponseCode::Ack))?;
+
+        assert_eq!(caps.family(), ProductFamily::IVizion);
+        assert!(caps.has_recycler());
+        assert!(caps.supports_note_image());
+        assert!(!caps.supports_security_mode());
+        assert!(caps.supports_near_full());
+        assert_eq!(caps.max_denominations(), 1);
+        assert!(caps.supports(RequestCode::RecyclerDispense));
+        assert!(caps.supports(RequestCode::Status));
+
+        let caps = Capabilities::query("A000001", poll("V1.05", ResponseCode::Nak))?;
+
+        assert!(!caps.has_recycler());
+        assert!(!caps.supports_note_image());
+        assert!(!caps.supports_near_full());
+        assert!(!caps.supports(RequestCode::NearFull));
+        assert!(!caps.supports(RequestCode::NoteDataInfo));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,262 @@
+use std::{fmt, time};
+
+use crate::{
+    poll_typed, AuditCounters, Clock, Error, FunctionStatus, IdleRequest, InhibitRequest, Message,
+    ResponseCode, Result, StatusRequest,
+};
+
+/// Represents the default interval between `Status` polls while waiting on the operator.
+pub const CASHBOX_POLL_INTERVAL: time::Duration = time::Duration::from_millis(500);
+/// Represents the default time the operator has for each cashbox exchange step.
+pub const CASHBOX_STEP_TIMEOUT: time::Duration = time::Duration::from_secs(300);
+
+/// Represents the outcome of a [CashboxExchange], for reconciliation.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CashboxExchangeReport {
+    counters: AuditCounters,
+    removal_wait: time::Duration,
+    insertion_wait: time::Duration,
+    near_full_cleared: bool,
+}
+
+impl CashboxExchangeReport {
+    /// Gets the [AuditCounters] snapshot, taken when the cashbox was removed.
+    pub const fn counters(&self) -> &AuditCounters {
+        &self.counters
+    }
+
+    /// Gets the time from inhibiting the device until the cashbox was removed.
+    pub const fn removal_wait(&self) -> time::Duration {
+        self.removal_wait
+    }
+
+    /// Gets the time from removing the cashbox until it was inserted again.
+    pub const fn insertion_wait(&self) -> time::Duration {
+        self.insertion_wait
+    }
+
+    /// Gets whether the device cleared its `Near Full` and `Full` status after the exchange.
+    pub const fn near_full_cleared(&self) -> bool {
+        self.near_full_cleared
+    }
+}
+
+impl fmt::Display for CashboxExchangeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""accepted": {}, "#, self.counters.accepted())?;
+        write!(f, r#""tickets": {}, "#, self.counters.tickets())?;
+        write!(f, r#""totals": {{"#)?;
+        for (i, (code, total)) in self.counters.totals().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, r#""{code}": {total}"#)?;
+        }
+        write!(f, "}}, ")?;
+        write!(
+            f,
+            r#""removal_wait_ms": {}, "#,
+            self.removal_wait.as_millis()
+        )?;
+        write!(
+            f,
+            r#""insertion_wait_ms": {}, "#,
+            self.insertion_wait.as_millis()
+        )?;
+        write!(f, r#""near_full_cleared": {}"#, self.near_full_cleared)?;
+        write!(f, "}}")
+    }
+}
+
+/// Guides the host through a cashbox exchange (soft count):
+///
+/// - inhibits the device
+/// - waits for the cashbox to be removed
+/// - takes a snapshot of the [AuditCounters]
+/// - waits for the cashbox to be inserted again
+/// - verifies the device cleared its `Near Full` and `Full` status
+/// - re-enables the device
+///
+/// Cashbox presence is read from the `Status` unit statuses, polled on the [Clock].
+#[derive(Debug)]
+pub struct CashboxExchange<'c, C: Clock + ?Sized> {
+    clock: &'c C,
+    poll_interval: time::Duration,
+    step_timeout: time::Duration,
+}
+
+impl<'c, C: Clock + ?Sized> CashboxExchange<'c, C> {
+    /// Creates a new [CashboxExchange] using the provided [Clock].
+    pub const fn new(clock: &'c C) -> Self {
+        Self {
+            clock,
+            poll_interval: CASHBOX_POLL_INTERVAL,
+            step_timeout: CASHBOX_STEP_TIMEOUT,
+        }
+    }
+
+    /// Gets the interval between `Status` polls.
+    pub const fn poll_interval(&self) -> time::Duration {
+        self.poll_interval
+    }
+
+    /// Builder function that sets the interval between `Status` polls.
+    pub fn with_poll_interval(mut self, val: time::Duration) -> Self {
+        self.poll_interval = val;
+        self
+    }
+
+    /// Gets the time the operator has for each exchange step.
+    pub const fn step_timeout(&self) -> time::Duration {
+        self.step_timeout
+    }
+
+    /// Builder function that sets the time the operator has for each exchange step.
+    pub fn with_step_timeout(mut self, val: time::Duration) -> Self {
+        self.step_timeout = val;
+        self
+    }
+
+    /// Runs the cashbox exchange through the polling function.
+    ///
+    /// The device is left inhibited if a step fails, or times out with [Error::Timeout].
+    pub fn run<P>(&self, counters: &AuditCounters, mut poll: P) -> Result<CashboxExchangeReport>
+    where
+        P: FnMut(&Message) -> Result<Message>,
+    {
+        ack(poll(&InhibitRequest::new().into())?)?;
+        log::info!("cashbox exchange: device inhibited, waiting for cashbox removal");
+
+        let removal_wait = self.wait_for("cashbox removal", &mut poll, |units| {
+            units.contains(&FunctionStatus::BoxRemoved)
+        })?;
+        let counters = counters.clone();
+        log::info!("cashbox exchange: cashbox removed, waiting for cashbox insertion");
+
+        let mut near_full_cleared = false;
+        let insertion_wait = self.wait_for("cashbox insertion", &mut poll, |units| {
+            near_full_cleared = !units
+                .iter()
+                .any(|s| matches!(s, FunctionStatus::NearFull | FunctionStatus::Full));
+            !units.contains(&FunctionStatus::BoxRemoved)
+        })?;
+
+        if !near_full_cleared {
+            log::warn!("cashbox exchange: device still reports near full after the exchange");
+        }
+
+        ack(poll(&IdleRequest::new().into())?)?;
+        log::info!("cashbox exchange: device re-enabled");
+
+        Ok(CashboxExchangeReport {
+            counters,
+            removal_wait,
+            insertion_wait,
+            near_full_cleared,
+        })
+    }
+
+    fn wait_for<P, F>(&self, step: &str, poll: &mut P, mut done: F) -> Result<time::Duration>
+    where
+        P: FnMut(&Message) -> Result<Message>,
+        F: FnMut(&[FunctionStatus]) -> bool,
+    {
+        let start = self.clock.now();
+
+        loop {
+            let res = poll_typed(StatusRequest::new(), &mut *poll)?;
+            let units = res
+                .unit_status()
+                .iter()
+                .map(|s| s.function_status())
+                .collect::<Vec<_>>();
+
+            if done(units.as_ref()) {
+                return Ok(self.clock.elapsed(start));
+            }
+
+            if self.clock.elapsed(start) >= self.step_timeout {
+                return Err(Error::Timeout(format!("cashbox exchange: {step}")));
+            }
+
+            self.clock.sleep(self.poll_interval);
+        }
+    }
+}
+
+fn ack(res: Message) -> Result<()> {
+    match res
+        .data()
+        .additional()
+        .first()
+        .copied()
+        .map(ResponseCode::from_u8)
+    {
+        Some(ResponseCode::Ack) => Ok(()),
+        Some(code) => Err(Error::InvalidResponseCode(code.into())),
+        None => Err(Error::InvalidResponseLen((0, 1))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DeviceStatus, RequestCode, SimulatedClock, UnitNumber, UnitStatus};
+
+    #[test]
+    fn test_cashbox_exchange() -> Result<()> {
+        let clock = SimulatedClock::new();
+        let interval = time::Duration::from_secs(1);
+        let exchange = CashboxExchange::new(&clock).with_poll_interval(interval);
+
+        let mut counters = AuditCounters::new();
+        counters.record_ticket();
+
+        // near full, removed, removed, inserted
+        let mut statuses = [
+            FunctionStatus::NearFull,
+            FunctionStatus::BoxRemoved,
+            FunctionStatus::BoxRemoved,
+            FunctionStatus::Normal,
+        ]
+        .into_iter();
+        let mut requests = Vec::new();
+
+        let report = exchange.run(&counters, |req| {
+            let code = req.data().message_code().request_code()?;
+            requests.push(code);
+
+            let mut additional = vec![ResponseCode::Ack.into()];
+            if code == RequestCode::Status {
+                let unit = UnitStatus::new()
+                    .with_unit_number(UnitNumber::from_u8(1))
+                    .with_function_status(statuses.next().unwrap_or_default());
+                additional.push((DeviceStatus::len() + UnitStatus::len()) as u8);
+                additional.extend(DeviceStatus::new().to_bytes());
+                additional.extend(unit.to_bytes());
+            }
+
+            Ok(Message::new().with_data(req.data().clone().with_additional(&additional)))
+        })?;
+
+        assert_eq!(requests.first(), Some(&RequestCode::Inhibit));
+        assert_eq!(requests.last(), Some(&RequestCode::Idle));
+        assert_eq!(report.counters(), &counters);
+        assert_eq!(report.removal_wait(), interval);
+        assert_eq!(report.insertion_wait(), interval);
+        assert!(report.near_full_cleared());
+
+        let timeout = CashboxExchange::new(&clock).with_step_timeout(interval);
+        assert!(matches!(
+            timeout.run(&counters, |req| Ok(Message::new().with_data(
+                req.data()
+                    .clone()
+                    .with_additional(&[ResponseCode::Ack.into(), 4, 0, 0, 1, 0])
+            ))),
+            Err(Error::Timeout(_))
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,521 @@
+use std::{fmt, time};
+
+use crate::{
+    poll_typed, CashBoxSizeRequest, Clock, Error, EventCode, InhibitRequest, Message, ResponseCode,
+    Result,
+};
+
+/// Represents the default interval between `Cash Box Size` polls.
+pub const CASHBOX_MONITOR_INTERVAL: time::Duration = time::Duration::from_secs(60);
+/// Represents the default fill level, in percent, that triggers the near-full warning.
+pub const CASHBOX_NEAR_FULL_PERCENT: u8 = 90;
+/// Represents the default fill level, in percent, that triggers the full stop.
+pub const CASHBOX_FULL_PERCENT: u8 = 100;
+
+/// Represents a callback invoked when the cashbox crosses a fill threshold.
+pub type CashboxCallback = Box<dyn FnMut(CashboxLevel) + Send>;
+
+/// Represents the fill level of the cashbox, in notes.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CashboxLevel {
+    capacity: u16,
+    count: u16,
+}
+
+impl CashboxLevel {
+    /// Creates a new, empty [CashboxLevel] of unknown capacity.
+    pub const fn new() -> Self {
+        Self {
+            capacity: 0,
+            count: 0,
+        }
+    }
+
+    /// Creates a new [CashboxLevel] from the provided parameters.
+    pub const fn create(capacity: u16, count: u16) -> Self {
+        Self { capacity, count }
+    }
+
+    /// Gets the number of notes the cashbox holds, zero if unknown.
+    pub const fn capacity(&self) -> u16 {
+        self.capacity
+    }
+
+    /// Gets the number of notes in the cashbox.
+    pub const fn count(&self) -> u16 {
+        self.count
+    }
+
+    /// Gets the number of notes the cashbox can still hold.
+    pub const fn remaining(&self) -> u16 {
+        self.capacity.saturating_sub(self.count)
+    }
+
+    /// Gets the fill level in percent, saturating at 100.
+    ///
+    /// Returns zero while the capacity is unknown.
+    pub const fn percent(&self) -> u8 {
+        if self.capacity == 0 {
+            0
+        } else if self.count >= self.capacity {
+            100
+        } else {
+            ((self.count as u32 * 100) / self.capacity as u32) as u8
+        }
+    }
+}
+
+impl fmt::Display for CashboxLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""capacity": {}, "#, self.capacity)?;
+        write!(f, r#""count": {}, "#, self.count)?;
+        write!(f, r#""percent": {}"#, self.percent())?;
+        write!(f, "}}")
+    }
+}
+
+/// Represents the fill state of the cashbox.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CashboxFill {
+    /// The cashbox is below the near-full threshold.
+    #[default]
+    Normal,
+    /// The cashbox reached the near-full threshold.
+    NearFull,
+    /// The cashbox reached the full threshold.
+    Full,
+}
+
+impl From<CashboxFill> for &'static str {
+    fn from(val: CashboxFill) -> Self {
+        match val {
+            CashboxFill::Normal => "normal",
+            CashboxFill::NearFull => "near full",
+            CashboxFill::Full => "full",
+        }
+    }
+}
+
+impl From<&CashboxFill> for &'static str {
+    fn from(val: &CashboxFill) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for CashboxFill {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
+
+/// Monitors the cashbox fill level, and invokes callbacks at the configured thresholds.
+///
+/// The level is read with periodic `Cash Box Size` requests, and the count is advanced on each
+/// `Vend Valid` event in between, so devices that only report the capacity are still tracked.
+///
+/// Callbacks are invoked once when the level crosses a threshold upwards. With
+/// [auto_inhibit](Self::auto_inhibit), the device is inhibited on the next
+/// [tick](Self::tick) after the full threshold is reached. Call
+/// [on_exchanged](Self::on_exchanged) once the cashbox is emptied.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::{Arc, Mutex};
+///
+/// use jcm::{CashBoxSizeResponse, CashboxFill, CashboxMonitor, Message, ResponseCode, SystemClock};
+///
+/// # fn main() -> jcm::Result<()> {
+/// let warned = Arc::new(Mutex::new(None));
+/// let cb_warned = Arc::clone(&warned);
+///
+/// let mut monitor = CashboxMonitor::new(SystemClock::new())
+///     .with_near_full_callback(move |level| *cb_warned.lock().unwrap() = Some(level));
+///
+/// let poll = |req: &Message| -> jcm::Result<Message> {
+///     let res = CashBoxSizeResponse::new()
+///         .with_code(ResponseCode::Ack)
+///         .with_capacity(500)
+///         .with_count(460);
+///     Ok(Message::new().with_data(req.data().clone().with_additional(&res.into_bytes())))
+/// };
+///
+/// assert_eq!(monitor.tick(poll).transpose()?, Some(CashboxFill::NearFull));
+/// assert_eq!(warned.lock().unwrap().map(|l| l.percent()), Some(92));
+/// # Ok(())
+/// # }
+/// ```
+pub struct CashboxMonitor<C: Clock> {
+    clock: C,
+    interval: time::Duration,
+    last_poll: Option<time::Duration>,
+    near_full_percent: u8,
+    full_percent: u8,
+    auto_inhibit: bool,
+    level: CashboxLevel,
+    fill: CashboxFill,
+    inhibit_pending: bool,
+    on_near_full: Option<CashboxCallback>,
+    on_full: Option<CashboxCallback>,
+}
+
+impl<C: Clock> CashboxMonitor<C> {
+    /// Creates a new [CashboxMonitor] using the provided [Clock].
+    pub fn new(clock: C) -> Self {
+        Self {
+            clock,
+            interval: CASHBOX_MONITOR_INTERVAL,
+            last_poll: None,
+            near_full_percent: CASHBOX_NEAR_FULL_PERCENT,
+            full_percent: CASHBOX_FULL_PERCENT,
+            auto_inhibit: false,
+            level: CashboxLevel::new(),
+            fill: CashboxFill::Normal,
+            inhibit_pending: false,
+            on_near_full: None,
+            on_full: None,
+        }
+    }
+
+    /// Gets the interval between `Cash Box Size` polls.
+    pub const fn interval(&self) -> time::Duration {
+        self.interval
+    }
+
+    /// Sets the interval between `Cash Box Size` polls.
+    pub fn set_interval(&mut self, interval: time::Duration) {
+        self.interval = interval;
+    }
+
+    /// Builder function that sets the interval between `Cash Box Size` polls.
+    pub fn with_interval(mut self, interval: time::Duration) -> Self {
+        self.set_interval(interval);
+        self
+    }
+
+    /// Gets the near-full threshold, in percent.
+    pub const fn near_full_percent(&self) -> u8 {
+        self.near_full_percent
+    }
+
+    /// Sets the near-full threshold, in percent.
+    pub fn set_near_full_percent(&mut self, percent: u8) {
+        self.near_full_percent = percent.min(100);
+    }
+
+    /// Builder function that sets the near-full threshold, in percent.
+    pub fn with_near_full_percent(mut self, percent: u8) -> Self {
+        self.set_near_full_percent(percent);
+        self
+    }
+
+    /// Gets the full threshold, in percent.
+    pub const fn full_percent(&self) -> u8 {
+        self.full_percent
+    }
+
+    /// Sets the full threshold, in percent.
+    pub fn set_full_percent(&mut self, percent: u8) {
+        self.full_percent = percent.min(100);
+    }
+
+    /// Builder function that sets the full threshold, in percent.
+    pub fn with_full_percent(mut self, percent: u8) -> Self {
+        self.set_full_percent(percent);
+        self
+    }
+
+    /// Gets whether the device is inhibited when the cashbox is full.
+    pub const fn auto_inhibit(&self) -> bool {
+        self.auto_inhibit
+    }
+
+    /// Sets whether the device is inhibited when the cashbox is full.
+    pub fn set_auto_inhibit(&mut self, auto_inhibit: bool) {
+        self.auto_inhibit = auto_inhibit;
+    }
+
+    /// Builder function that sets whether the device is inhibited when the cashbox is full.
+    pub fn with_auto_inhibit(mut self, auto_inhibit: bool) -> Self {
+        self.set_auto_inhibit(auto_inhibit);
+        self
+    }
+
+    /// Sets the callback invoked when the cashbox reaches the near-full threshold.
+    pub fn set_near_full_callback<F>(&mut self, callback: F)
+    where
+        F: FnMut(CashboxLevel) + Send + 'static,
+    {
+        self.on_near_full = Some(Box::new(callback));
+    }
+
+    /// Builder function that sets the callback invoked when the cashbox reaches the near-full
+    /// threshold.
+    pub fn with_near_full_callback<F>(mut self, callback: F) -> Self
+    where
+        F: FnMut(CashboxLevel) + Send + 'static,
+    {
+        self.set_near_full_callback(callback);
+        self
+    }
+
+    /// Sets the callback invoked when the cashbox reaches the full threshold.
+    pub fn set_full_callback<F>(&mut self, callback: F)
+    where
+        F: FnMut(CashboxLevel) + Send + 'static,
+    {
+        self.on_full = Some(Box::new(callback));
+    }
+
+    /// Builder function that sets the callback invoked when the cashbox reaches the full
+    /// threshold.
+    pub fn with_full_callback<F>(mut self, callback: F) -> Self
+    where
+        F: FnMut(CashboxLevel) + Send + 'static,
+    {
+        self.set_full_callback(callback);
+        self
+    }
+
+    /// Gets the last known [CashboxLevel].
+    pub const fn level(&self) -> CashboxLevel {
+        self.level
+    }
+
+    /// Gets the current [CashboxFill] state.
+    pub const fn fill(&self) -> CashboxFill {
+        self.fill
+    }
+
+    /// Records a device event, counting each stacked note.
+    pub fn on_event(&mut self, event: &Message) -> CashboxFill {
+        if let Ok(EventCode::VendValid) = event.data().message_code().event_code() {
+            let count = self.level.count.saturating_add(1);
+            self.update(CashboxLevel::create(self.level.capacity, count));
+        }
+
+        self.fill
+    }
+
+    /// Records an emptied cashbox, resetting the count and fill state.
+    pub fn on_exchanged(&mut self) {
+        self.level = CashboxLevel::create(self.level.capacity, 0);
+        self.fill = CashboxFill::Normal;
+        self.inhibit_pending = false;
+    }
+
+    /// Updates the [CashboxLevel], invoking the callbacks for any crossed threshold.
+    pub fn update(&mut self, level: CashboxLevel) -> CashboxFill {
+        self.level = level;
+
+        let percent = level.percent();
+        let fill = if level.capacity == 0 {
+            CashboxFill::Normal
+        } else if percent >= self.full_percent {
+            CashboxFill::Full
+        } else if percent >= self.near_full_percent {
+            CashboxFill::NearFull
+        } else {
+            CashboxFill::Normal
+        };
+
+        if fill > self.fill {
+            if let Some(callback) = self.on_near_full.as_mut() {
+                if self.fill < CashboxFill::NearFull {
+                    callback(level);
+                }
+            }
+
+            if fill == CashboxFill::Full {
+                log::warn!("cashbox full: {level}");
+
+                if let Some(callback) = self.on_full.as_mut() {
+                    callback(level);
+                }
+
+                self.inhibit_pending = self.auto_inhibit;
+            }
+        }
+
+        self.fill = fill;
+        fill
+    }
+
+    /// Gets whether a `Cash Box Size` poll, or an automatic inhibit, is due.
+    pub fn is_due(&self) -> bool {
+        match self.last_poll {
+            Some(last) => self.inhibit_pending || self.clock.elapsed(last) >= self.interval,
+            None => true,
+        }
+    }
+
+    /// Polls the cashbox level through the polling function, if due.
+    ///
+    /// With [auto_inhibit](Self::auto_inhibit), a full cashbox also sends an `Inhibit` request.
+    ///
+    /// Returns `None` if no poll was due.
+    pub fn tick<F>(&mut self, mut poll: F) -> Option<Result<CashboxFill>>
+    where
+        F: FnMut(&Message) -> Result<Message>,
+    {
+        if !self.is_due() {
+            return None;
+        }
+
+        let res = self.poll_level(&mut poll).and_then(|fill| {
+            if self.inhibit_pending {
+                let res = poll_typed(InhibitRequest::new(), &mut poll)?;
+                Self::check_code(res.code())?;
+
+                log::info!("cashbox monitor: device inhibited, cashbox full");
+                self.inhibit_pending = false;
+            }
+
+            Ok(fill)
+        });
+
+        if let Err(err) = res.as_ref() {
+            log::warn!("cashbox monitor poll failed: {err}");
+        }
+
+        Some(res)
+    }
+
+    fn poll_level<F>(&mut self, poll: &mut F) -> Result<CashboxFill>
+    where
+        F: FnMut(&Message) -> Result<Message>,
+    {
+        if self.inhibit_pending && self.last_poll.is_some() {
+            return Ok(self.fill);
+        }
+
+        self.last_poll = Some(self.clock.now());
+
+        let res = poll_typed(CashBoxSizeRequest::new(), &mut *poll)?;
+        Self::check_code(res.code())?;
+
+        let capacity = res.capacity().unwrap_or(self.level.capacity);
+        let count = res.count().unwrap_or(self.level.count);
+
+        Ok(self.update(CashboxLevel::create(capacity, count)))
+    }
+
+    fn check_code(code: ResponseCode) -> Result<()> {
+        match code {
+            ResponseCode::Ack => Ok(()),
+            code => Err(Error::InvalidResponseCode(code.into())),
+        }
+    }
+}
+
+impl<C: Clock> fmt::Debug for CashboxMonitor<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CashboxMonitor")
+            .field("interval", &self.interval)
+            .field("near_full_percent", &self.near_full_percent)
+            .field("full_percent", &self.full_percent)
+            .field("auto_inhibit", &self.auto_inhibit)
+            .field("level", &self.level)
+            .field("fill", &self.fill)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C: Clock> fmt::Display for CashboxMonitor<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""interval_ms": {}, "#, self.interval.as_millis())?;
+        write!(f, r#""near_full_percent": {}, "#, self.near_full_percent)?;
+        write!(f, r#""full_percent": {}, "#, self.full_percent)?;
+        write!(f, r#""auto_inhibit": {}, "#, self.auto_inhibit)?;
+        write!(f, r#""level": {}, "#, self.level)?;
+        write!(f, r#""fill": {}"#, self.fill)?;
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use super::*;
+    use crate::{
+        CashBoxSizeResponse, EventType, MessageCode, MessageData, MessageType, RequestCode,
+        SimulatedClock,
+    };
+
+    #[test]
+    fn test_cashbox_monitor() -> Result<()> {
+        let clock = SimulatedClock::new();
+        let interval = time::Duration::from_secs(1);
+        let (near_full, full) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+        let (cb_near_full, cb_full) = (Arc::clone(&near_full), Arc::clone(&full));
+
+        let mut monitor = CashboxMonitor::new(&clock)
+            .with_interval(interval)
+            .with_near_full_percent(80)
+            .with_auto_inhibit(true)
+            .with_near_full_callback(move |_| {
+                cb_near_full.fetch_add(1, Ordering::SeqCst);
+            })
+            .with_full_callback(move |_| {
+                cb_full.fetch_add(1, Ordering::SeqCst);
+            });
+
+        // the device only reports the capacity
+        let mut inhibits = 0;
+        let mut poll = |req: &Message| -> Result<Message> {
+            let additional = match req.data().message_code().request_code()? {
+                RequestCode::CashBoxSize => CashBoxSizeResponse::new()
+                    .with_code(ResponseCode::Ack)
+                    .with_capacity(10)
+                    .into_bytes(),
+                _ => {
+                    inhibits += 1;
+                    vec![ResponseCode::Ack.into()]
+                }
+            };
+            Ok(Message::new().with_data(req.data().clone().with_additional(&additional)))
+        };
+
+        assert_eq!(monitor.tick(&mut poll), Some(Ok(CashboxFill::Normal)));
+        assert!(monitor.tick(&mut poll).is_none());
+
+        let vend = Message::new().with_data(
+            MessageData::new()
+                .with_message_type(MessageType::Event(EventType::Sequence1))
+                .with_message_code(MessageCode::Event(EventCode::VendValid)),
+        );
+        for _ in 0..8 {
+            monitor.on_event(&vend);
+        }
+        assert_eq!(monitor.fill(), CashboxFill::NearFull);
+        assert_eq!(near_full.load(Ordering::SeqCst), 1);
+
+        monitor.on_event(&vend);
+        assert_eq!(monitor.on_event(&vend), CashboxFill::Full);
+        assert_eq!(full.load(Ordering::SeqCst), 1);
+        assert_eq!(near_full.load(Ordering::SeqCst), 1);
+
+        // the inhibit is sent without waiting for the poll interval
+        assert_eq!(monitor.tick(&mut poll), Some(Ok(CashboxFill::Full)));
+        assert!(monitor.tick(&mut poll).is_none());
+
+        monitor.on_exchanged();
+        assert_eq!(monitor.level(), CashboxLevel::create(10, 0));
+        clock.advance(interval);
+        assert_eq!(monitor.tick(&mut poll), Some(Ok(CashboxFill::Normal)));
+
+        assert_eq!(inhibits, 1);
+
+        Ok(())
+    }
+}
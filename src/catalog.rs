@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::{EventCode, EventCodeDetails, FailureCode, RejectCode, RejectCodeDetails};
+
+/// Represents the language tag of the default [EnglishCatalog].
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// Provides human-readable text for device codes in a single language.
+///
+/// Implementations return `None` for codes they do not translate, and the [CatalogRegistry]
+/// falls back to the [EnglishCatalog].
+pub trait MessageCatalog: Send + Sync {
+    /// Gets the language tag of the [MessageCatalog], e.g. `en`, `de`, `pt-BR`.
+    fn language(&self) -> &str;
+
+    /// Gets the text for a [RejectCode].
+    fn reject_code(&self, code: RejectCode) -> Option<&str>;
+
+    /// Gets the text for a [FailureCode].
+    fn failure_code(&self, code: FailureCode) -> Option<&str>;
+
+    /// Gets the text for an [EventCode].
+    fn event_code(&self, code: EventCode) -> Option<&str>;
+}
+
+/// Represents the built-in English [MessageCatalog].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct EnglishCatalog;
+
+impl EnglishCatalog {
+    /// Creates a new [EnglishCatalog].
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl MessageCatalog for EnglishCatalog {
+    fn language(&self) -> &str {
+        DEFAULT_LANGUAGE
+    }
+
+    fn reject_code(&self, code: RejectCode) -> Option<&str> {
+        Some(RejectCodeDetails(code).into())
+    }
+
+    fn failure_code(&self, code: FailureCode) -> Option<&str> {
+        Some(code.into())
+    }
+
+    fn event_code(&self, code: EventCode) -> Option<&str> {
+        Some(EventCodeDetails(code).into())
+    }
+}
+
+/// Represents a user-provided [MessageCatalog] backed by lookup tables.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TextCatalog {
+    language: String,
+    reject_codes: HashMap<u8, String>,
+    failure_codes: HashMap<u8, String>,
+    event_codes: HashMap<u16, String>,
+}
+
+impl TextCatalog {
+    /// Creates a new, empty [TextCatalog] for the provided language tag.
+    pub fn new(language: &str) -> Self {
+        Self {
+            language: language.into(),
+            reject_codes: HashMap::new(),
+            failure_codes: HashMap::new(),
+            event_codes: HashMap::new(),
+        }
+    }
+
+    /// Sets the text for a [RejectCode].
+    pub fn set_reject_code(&mut self, code: RejectCode, text: &str) {
+        self.reject_codes.insert(code.into(), text.into());
+    }
+
+    /// Builder function that sets the text for a [RejectCode].
+    pub fn with_reject_code(mut self, code: RejectCode, text: &str) -> Self {
+        self.set_reject_code(code, text);
+        self
+    }
+
+    /// Sets the text for a [FailureCode].
+    pub fn set_failure_code(&mut self, code: FailureCode, text: &str) {
+        self.failure_codes.insert(code.into(), text.into());
+    }
+
+    /// Builder function that sets the text for a [FailureCode].
+    pub fn with_failure_code(mut self, code: FailureCode, text: &str) -> Self {
+        self.set_failure_code(code, text);
+        self
+    }
+
+    /// Sets the text for an [EventCode].
+    pub fn set_event_code(&mut self, code: EventCode, text: &str) {
+        self.event_codes.insert(code.into(), text.into());
+    }
+
+    /// Builder function that sets the text for an [EventCode].
+    pub fn with_event_code(mut self, code: EventCode, text: &str) -> Self {
+        self.set_event_code(code, text);
+        self
+    }
+}
+
+impl MessageCatalog for TextCatalog {
+    fn language(&self) -> &str {
+        self.language.as_str()
+    }
+
+    fn reject_code(&self, code: RejectCode) -> Option<&str> {
+        self.reject_codes.get(&u8::from(code)).map(String::as_str)
+    }
+
+    fn failure_code(&self, code: FailureCode) -> Option<&str> {
+        self.failure_codes.get(&u8::from(code)).map(String::as_str)
+    }
+
+    fn event_code(&self, code: EventCode) -> Option<&str> {
+        self.event_codes.get(&u16::from(code)).map(String::as_str)
+    }
+}
+
+/// Represents a set of [MessageCatalog]s keyed by language.
+///
+/// Lookups try the exact language tag, then the primary subtag (`pt-BR` -> `pt`), then English.
+#[derive(Clone)]
+pub struct CatalogRegistry {
+    catalogs: HashMap<String, Arc<dyn MessageCatalog>>,
+}
+
+impl CatalogRegistry {
+    /// Creates a new [CatalogRegistry] containing the [EnglishCatalog].
+    pub fn new() -> Self {
+        let mut catalogs: HashMap<String, Arc<dyn MessageCatalog>> = HashMap::new();
+        catalogs.insert(DEFAULT_LANGUAGE.into(), Arc::new(EnglishCatalog::new()));
+
+        Self { catalogs }
+    }
+
+    /// Registers a [MessageCatalog], replacing any catalog with the same language tag.
+    pub fn register<C: MessageCatalog + 'static>(&mut self, catalog: C) {
+        self.catalogs
+            .insert(catalog.language().to_ascii_lowercase(), Arc::new(catalog));
+    }
+
+    /// Builder function that registers a [MessageCatalog].
+    pub fn with_catalog<C: MessageCatalog + 'static>(mut self, catalog: C) -> Self {
+        self.register(catalog);
+        self
+    }
+
+    /// Gets an iterator over the registered language tags.
+    pub fn languages(&self) -> impl Iterator<Item = &str> {
+        self.catalogs.keys().map(String::as_str)
+    }
+
+    /// Gets the text for a [RejectCode] in the requested language.
+    pub fn reject_code(&self, language: &str, code: RejectCode) -> &str {
+        self.lookup(language, |c| c.reject_code(code))
+            .unwrap_or(RejectCodeDetails(code).into())
+    }
+
+    /// Gets the text for a [FailureCode] in the requested language.
+    pub fn failure_code(&self, language: &str, code: FailureCode) -> &str {
+        self.lookup(language, |c| c.failure_code(code))
+            .unwrap_or(code.into())
+    }
+
+    /// Gets the text for an [EventCode] in the requested language.
+    pub fn event_code(&self, language: &str, code: EventCode) -> &str {
+        self.lookup(language, |c| c.event_code(code))
+            .unwrap_or(EventCodeDetails(code).into())
+    }
+
+    fn lookup<'c, F>(&'c self, language: &str, f: F) -> Option<&'c str>
+    where
+        F: Fn(&'c dyn MessageCatalog) -> Option<&'c str>,
+    {
+        let language = language.to_ascii_lowercase();
+        let primary = language.split(['-', '_']).next().unwrap_or_default();
+
+        for lang in [language.as_str(), primary, DEFAULT_LANGUAGE] {
+            if let Some(text) = self.catalogs.get(lang).and_then(|c| f(c.as_ref())) {
+                return Some(text);
+            }
+        }
+
+        None
+    }
+}
+
+impl Default for CatalogRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalog_registry() {
+        let registry = CatalogRegistry::new().with_catalog(
+            TextCatalog::new("de")
+                .with_reject_code(RejectCode::Inhibited, "Banknote gesperrt")
+                .with_failure_code(FailureCode::Rom, "ROM-Fehler")
+                .with_event_code(EventCode::Idle, "Bereit"),
+        );
+
+        assert_eq!(
+            registry.reject_code("de", RejectCode::Inhibited),
+            "Banknote gesperrt"
+        );
+        assert_eq!(
+            registry.reject_code("de-AT", RejectCode::Inhibited),
+            "Banknote gesperrt"
+        );
+        assert_eq!(registry.failure_code("DE", FailureCode::Rom), "ROM-Fehler");
+        assert_eq!(registry.event_code("de", EventCode::Idle), "Bereit");
+
+        // untranslated codes, and unknown languages, fall back to English
+        assert_eq!(
+            registry.reject_code("de", RejectCode::NoteLength),
+            "invalid note length"
+        );
+        assert_eq!(registry.failure_code("fr", FailureCode::Rom), "ROM error");
+        assert_eq!(registry.event_code("ja", EventCode::Idle), "stand-by");
+
+        let mut langs: Vec<&str> = registry.languages().collect();
+        langs.sort();
+        assert_eq!(langs, ["de", "en"]);
+    }
+}
@@ -0,0 +1,553 @@
+//! Command-line access to a device, for field engineers poking at a unit without writing Rust.
+//!
+//! The `jcm-cli` binary, built with the `cli` feature, runs one [CliCommand] against the first
+//! attached JCM USB device:
+//!
+//! Command                         | Effect
+//! --------------------------------|----------------------------------------------------------
+//! `status`                        | prints the device status
+//! `reset`                         | resets the device
+//! `idle`                          | enables the device to accept notes
+//! `inhibit`                       | inhibits the device from accepting notes
+//! `denoms get`                    | prints the assigned and disabled denominations
+//! `denoms set all`                | enables all denominations
+//! `denoms set <code> <value>...`  | enables only the listed denominations
+//! `version`                       | prints the firmware version
+//! `serial [note]`                 | prints the USB serial number, or the note serial number
+//! `image dump <path>`             | writes the note image in escrow to a file
+//! `monitor [count]`               | prints device events, stopping after `count` events
+//! `debug`                         | prints a snapshot of the host internals
+//! `support-bundle <path>`         | writes a support bundle to a file
+//! `help`                          | prints the usage
+//!
+//! Options precede the command:
+//!
+//! Option               | Effect
+//! ---------------------|---------------------------------------------------------
+//! `--device <serial>`  | opens the device with the USB serial number
+//! `--uid <uid>`        | assigns the UID to the device, defaults to `1`
+//! `--retries <count>`  | retries each request `count` times, defaults to `3`
+//!
+//! ```
+//! use jcm::cli::{CliArgs, CliCommand};
+//!
+//! # fn main() -> jcm::Result<()> {
+//! let args = CliArgs::parse(["--uid", "2", "denoms", "set", "USD", "1", "5"])?;
+//! assert_eq!(args.uid(), 2);
+//! assert_eq!(args.command().to_string(), r#"{"denoms_set": ["1 USD", "5 USD"]}"#);
+//!
+//! assert_eq!(CliArgs::parse(["monitor"])?.command(), &CliCommand::Monitor(None));
+//! assert!(CliArgs::parse(["denoms", "toggle"]).is_err());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::usb::{self, DeviceIo, EventAckPolicy, UsbDeviceHandle, POLL_INTERVAL};
+use crate::{
+    CancelToken, Currency, CurrencyCode, DebugMonitor, Denomination, DenominationProfile,
+    DeviceInfo, DeviceInhibit, DeviceTransport, Error, ImageFetcher, Message, PollConfig,
+    RejectStats, ResetRequest, ResponseCode, Result, StatusRequest, SupportBundle, SystemClock,
+    UidRequest, VersionRequest,
+};
+
+/// Represents the default UID assigned to the device.
+pub const CLI_DEFAULT_UID: u8 = 1;
+/// Represents the default number of request retries.
+pub const CLI_DEFAULT_RETRIES: usize = 3;
+/// Represents the usage text printed by the `help` command.
+pub const CLI_USAGE: &str = "\
+usage: jcm-cli [--device <serial>] [--uid <uid>] [--retries <count>] <command>
+
+commands:
+    status                          print the device status
+    reset                           reset the device
+    idle                            enable the device to accept notes
+    inhibit                         inhibit the device from accepting notes
+    denoms get                      print the assigned and disabled denominations
+    denoms set all                  enable all denominations
+    denoms set <code> <value>...    enable only the listed denominations
+    version                         print the firmware version
+    serial [note]                   print the USB serial number, or the note serial number
+    image dump <path>               write the note image in escrow to a file
+    monitor [count]                 print device events, stopping after `count` events
+    debug                           print a snapshot of the host internals
+    support-bundle <path>           write a support bundle to a file
+    help                            print this message";
+
+const MONITOR_QUEUE_LEN: usize = 64;
+
+/// Represents a command run against the device.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CliCommand {
+    /// Prints the device status.
+    Status,
+    /// Resets the device.
+    Reset,
+    /// Enables the device to accept notes.
+    Idle,
+    /// Inhibits the device from accepting notes.
+    Inhibit,
+    /// Prints the assigned and disabled denominations.
+    DenomsGet,
+    /// Enables only the listed denominations, or all denominations if the list is empty.
+    DenomsSet(Vec<Currency>),
+    /// Prints the firmware version.
+    Version,
+    /// Prints the USB serial number of the device.
+    Serial,
+    /// Prints the serial number of the note in escrow.
+    NoteSerial,
+    /// Writes the note image in escrow to the file.
+    ImageDump(PathBuf),
+    /// Prints device events, optionally stopping after the number of events.
+    Monitor(Option<usize>),
+    /// Prints a [DebugState](crate::DebugState) snapshot of the host internals.
+    Debug,
+    /// Writes a [SupportBundle] to the file.
+    SupportBundle(PathBuf),
+    /// Prints the usage.
+    Help,
+}
+
+impl CliCommand {
+    /// Parses a [CliCommand] from its command-line arguments.
+    pub fn parse<S: AsRef<str>>(args: &[S]) -> Result<Self> {
+        let args: Vec<&str> = args.iter().map(|a| a.as_ref()).collect();
+        let cmd_err = || Error::InvalidCliCommand(args.join(" "));
+
+        match args.as_slice() {
+            ["status"] => Ok(Self::Status),
+            ["reset"] => Ok(Self::Reset),
+            ["idle"] => Ok(Self::Idle),
+            ["inhibit"] => Ok(Self::Inhibit),
+            ["denoms", "get"] => Ok(Self::DenomsGet),
+            ["denoms", "set", "all"] => Ok(Self::DenomsSet(Vec::new())),
+            ["denoms", "set", code, values @ ..] if !values.is_empty() => {
+                let code = match CurrencyCode::from(code.to_ascii_uppercase().as_str()) {
+                    CurrencyCode::XXX => return Err(cmd_err()),
+                    code => code,
+                };
+
+                values
+                    .iter()
+                    .map(|v| {
+                        let value = v.parse::<u64>().map_err(|_| cmd_err())?;
+                        let denomination = Denomination::from_value(value);
+
+                        if denomination.is_valid() && denomination.value() == value {
+                            Ok(Currency::new()
+                                .with_code(code)
+                                .with_denomination(denomination))
+                        } else {
+                            Err(cmd_err())
+                        }
+                    })
+                    .collect::<Result<Vec<Currency>>>()
+                    .map(Self::DenomsSet)
+            }
+            ["version"] => Ok(Self::Version),
+            ["serial"] => Ok(Self::Serial),
+            ["serial", "note"] => Ok(Self::NoteSerial),
+            ["image", "dump", path] => Ok(Self::ImageDump(PathBuf::from(path))),
+            ["monitor"] => Ok(Self::Monitor(None)),
+            ["monitor", count] => count
+                .parse::<usize>()
+                .map(|c| Self::Monitor(Some(c)))
+                .map_err(|_| cmd_err()),
+            ["debug"] => Ok(Self::Debug),
+            ["support-bundle", path] => Ok(Self::SupportBundle(PathBuf::from(path))),
+            ["help" | "--help" | "-h"] => Ok(Self::Help),
+            _ => Err(cmd_err()),
+        }
+    }
+}
+
+impl fmt::Display for CliCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Status => write!(f, r#""status""#),
+            Self::Reset => write!(f, r#""reset""#),
+            Self::Idle => write!(f, r#""idle""#),
+            Self::Inhibit => write!(f, r#""inhibit""#),
+            Self::DenomsGet => write!(f, r#""denoms_get""#),
+            Self::DenomsSet(enabled) => {
+                write!(f, r#"{{"denoms_set": ["#)?;
+                for (i, currency) in enabled.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{currency}")?;
+                }
+                write!(f, "]}}")
+            }
+            Self::Version => write!(f, r#""version""#),
+            Self::Serial => write!(f, r#""serial""#),
+            Self::NoteSerial => write!(f, r#""note_serial""#),
+            Self::ImageDump(path) => write!(f, r#"{{"image_dump": "{}"}}"#, path.display()),
+            Self::Monitor(None) => write!(f, r#""monitor""#),
+            Self::Monitor(Some(count)) => write!(f, r#"{{"monitor": {count}}}"#),
+            Self::Debug => write!(f, r#""debug""#),
+            Self::SupportBundle(path) => {
+                write!(f, r#"{{"support_bundle": "{}"}}"#, path.display())
+            }
+            Self::Help => write!(f, r#""help""#),
+        }
+    }
+}
+
+/// Represents the parsed command-line arguments of the `jcm-cli` binary.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CliArgs {
+    device: Option<String>,
+    uid: u8,
+    retries: usize,
+    command: CliCommand,
+}
+
+impl CliArgs {
+    /// Parses the [CliArgs] from the command-line arguments, excluding the program name.
+    pub fn parse<I, S>(args: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let args: Vec<String> = args.into_iter().map(|a| a.as_ref().into()).collect();
+        let opt_err = |opt: &str| Error::InvalidCliCommand(format!("missing or invalid {opt}"));
+
+        let mut device = None;
+        let mut uid = CLI_DEFAULT_UID;
+        let mut retries = CLI_DEFAULT_RETRIES;
+        let mut rest = args.as_slice();
+
+        loop {
+            match rest {
+                [opt, val, tail @ ..] if opt == "--device" => {
+                    device = Some(val.clone());
+                    rest = tail;
+                }
+                [opt, val, tail @ ..] if opt == "--uid" => {
+                    uid = val.parse().map_err(|_| opt_err(opt))?;
+                    rest = tail;
+                }
+                [opt, val, tail @ ..] if opt == "--retries" => {
+                    retries = val.parse().map_err(|_| opt_err(opt))?;
+                    rest = tail;
+                }
+                [opt] if opt.starts_with("--") && opt != "--help" => return Err(opt_err(opt)),
+                _ => break,
+            }
+        }
+
+        Ok(Self {
+            device,
+            uid,
+            retries,
+            command: CliCommand::parse(rest)?,
+        })
+    }
+
+    /// Gets the USB serial number of the device to open, if any.
+    pub fn device(&self) -> Option<&str> {
+        self.device.as_deref()
+    }
+
+    /// Gets the UID assigned to the device.
+    pub const fn uid(&self) -> u8 {
+        self.uid
+    }
+
+    /// Gets the number of request retries.
+    pub const fn retries(&self) -> usize {
+        self.retries
+    }
+
+    /// Gets a reference to the [CliCommand].
+    pub const fn command(&self) -> &CliCommand {
+        &self.command
+    }
+}
+
+/// Represents a connection to a device for running [CliCommand]s.
+///
+/// Opening the session starts a [DeviceIo] thread owning the device, acknowledges every device
+/// event, and assigns the UID to the device. Dropping the session stops the I/O thread.
+///
+/// The session records requests and events in a [DebugMonitor], and rejects in [RejectStats],
+/// for the [Debug](CliCommand::Debug) and [SupportBundle](CliCommand::SupportBundle) commands.
+pub struct CliSession {
+    io: DeviceIo,
+    stop: Arc<AtomicBool>,
+    event_recv: crossbeam::channel::Receiver<Message>,
+    monitor_recv: crossbeam::channel::Receiver<Message>,
+    debug: Arc<DebugMonitor>,
+    reject_stats: Arc<Mutex<RejectStats>>,
+    uid: u8,
+    retries: usize,
+    serial_number: Option<String>,
+}
+
+impl CliSession {
+    /// Opens a [CliSession] with the USB device selected by the [CliArgs].
+    pub fn open_usb(args: &CliArgs) -> Result<Self> {
+        let usb = match args.device() {
+            Some(serial) => UsbDeviceHandle::open_by_serial(serial)?,
+            None => UsbDeviceHandle::find_usb()?,
+        };
+        let serial_number = usb.serial_number().map(String::from);
+
+        Self::open(usb, args).map(|session| session.with_serial_number(serial_number))
+    }
+
+    /// Opens a [CliSession] with the [DeviceTransport].
+    pub fn open<T: DeviceTransport>(usb: T, args: &CliArgs) -> Result<Self> {
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let (event_send, event_recv) = crossbeam::channel::unbounded();
+        let (event_res_send, event_res_recv) = crossbeam::channel::unbounded();
+        let (monitor_send, monitor_recv) = crossbeam::channel::bounded(MONITOR_QUEUE_LEN);
+        let debug = Arc::new(DebugMonitor::new());
+        let reject_stats = Arc::new(Mutex::new(RejectStats::new()));
+
+        let config = PollConfig::new()
+            .with_retries(args.retries())
+            .with_response_timeout(usb::RESPONSE_TIMEOUT)
+            .with_retry_interval(POLL_INTERVAL);
+        let io = DeviceIo::spawn_observed(
+            usb,
+            event_send,
+            event_res_recv,
+            config,
+            SystemClock::new(),
+            Arc::clone(&debug),
+        );
+
+        let (event_debug, event_stats) = (Arc::clone(&debug), Arc::clone(&reject_stats));
+        usb::spawn_event_acknowledger(
+            Arc::clone(&stop),
+            event_recv.clone(),
+            event_res_send,
+            EventAckPolicy::Custom(Box::new(move |event| {
+                event_debug.on_event(event);
+                if let Ok(mut stats) = event_stats.lock() {
+                    stats.record_event(event);
+                }
+                // events are dropped while nothing is monitoring
+                monitor_send.try_send(event.clone()).ok();
+                Some(crate::event_ack(event))
+            })),
+        );
+
+        let session = Self {
+            io,
+            stop,
+            event_recv,
+            monitor_recv,
+            debug,
+            reject_stats,
+            uid: args.uid(),
+            retries: args.retries(),
+            serial_number: None,
+        };
+
+        // the device may not answer to the new UID yet, so send the request unmodified
+        let uid_req = Message::from(UidRequest::new_set(session.uid));
+        session.io.request(&uid_req)?;
+
+        Ok(session)
+    }
+
+    /// Sets the USB serial number printed by the [Serial](CliCommand::Serial) command, and
+    /// included in the [SupportBundle](CliCommand::SupportBundle).
+    pub fn with_serial_number(mut self, serial_number: Option<String>) -> Self {
+        self.serial_number = serial_number;
+        self
+    }
+
+    /// Gets the UID assigned to the device.
+    pub const fn uid(&self) -> u8 {
+        self.uid
+    }
+
+    /// Runs the [CliCommand], printing its result to the output.
+    pub fn run<W: Write>(&self, command: &CliCommand, out: &mut W) -> Result<()> {
+        let poll = |req: &Message| self.poll(req);
+
+        match command {
+            CliCommand::Status => {
+                let res = crate::poll_typed(StatusRequest::new(), poll)?;
+                Self::print(out, res)
+            }
+            CliCommand::Reset => {
+                let res = crate::poll_typed(ResetRequest::new(), poll)?;
+                Self::check_code(res.code())?;
+                Self::print(out, res)
+            }
+            CliCommand::Idle => {
+                crate::set_device_inhibit(self.uid, DeviceInhibit::Enabled, poll)?;
+                Self::print(out, DeviceInhibit::Enabled)
+            }
+            CliCommand::Inhibit => {
+                crate::set_device_inhibit(self.uid, DeviceInhibit::Inhibited, poll)?;
+                Self::print(out, DeviceInhibit::Inhibited)
+            }
+            CliCommand::DenomsGet => Self::print(out, DenominationProfile::query(poll)?),
+            CliCommand::DenomsSet(enabled) => {
+                let mut profile = DenominationProfile::query(poll)?;
+                if enabled.is_empty() {
+                    profile.enable_all();
+                } else {
+                    profile.only(enabled)?;
+                }
+                profile.apply(poll)?;
+                Self::print(out, profile)
+            }
+            CliCommand::Version => {
+                let res = crate::poll_typed(VersionRequest::new(), poll)?;
+                Self::check_code(res.code())?;
+                Self::print(out, res)
+            }
+            CliCommand::Serial => match self.serial_number.as_deref() {
+                Some(serial) => Self::print(out, serial),
+                None => Err(Error::Cli("no USB serial number for the device".into())),
+            },
+            CliCommand::NoteSerial => {
+                let serial = crate::read_note_serial(&CancelToken::new(), poll)?;
+                Self::print(out, serial.serial())
+            }
+            CliCommand::ImageDump(path) => {
+                let image = ImageFetcher::new().fetch_image(poll, |p| log::info!("{p}"))?;
+                if image.is_empty() {
+                    return Err(Error::Cli("no note image available".into()));
+                }
+
+                #[cfg(feature = "image")]
+                let data = match path.extension().and_then(|ext| ext.to_str()) {
+                    Some("png") => image.to_png()?,
+                    Some("bmp") => image.to_bmp()?,
+                    _ => image.data().into(),
+                };
+                #[cfg(not(feature = "image"))]
+                let data = image.data();
+
+                fs::write(path, data).map_err(|err| {
+                    Error::Cli(format!("unable to write {}: {err}", path.display()))
+                })?;
+                Self::print(out, image)
+            }
+            CliCommand::Monitor(count) => {
+                let count = count.unwrap_or(usize::MAX);
+                for event in self.monitor_recv.iter().take(count) {
+                    Self::print(out, event)?;
+                }
+                Ok(())
+            }
+            CliCommand::Debug => Self::print(
+                out,
+                self.debug.snapshot(&[
+                    ("events", self.event_recv.len()),
+                    ("requests", self.io.handle().queued()),
+                ]),
+            ),
+            CliCommand::SupportBundle(path) => {
+                let serial = self.serial_number.as_deref().unwrap_or("unknown");
+                let reject_stats = match self.reject_stats.lock() {
+                    Ok(stats) => stats.clone(),
+                    Err(err) => err.into_inner().clone(),
+                };
+
+                // query through the session, so requests carry the assigned UID
+                SupportBundle::new()
+                    .with_device_info(DeviceInfo::query(serial, poll)?)
+                    .with_reject_stats(reject_stats)
+                    .with_config("uid", self.uid)
+                    .with_config("retries", self.retries)
+                    .export(path)?;
+                Self::print(out, path.display())
+            }
+            CliCommand::Help => Self::print(out, CLI_USAGE),
+        }
+    }
+
+    fn poll(&self, request: &Message) -> Result<Message> {
+        let request = Message::new().with_data(request.data().clone().with_uid(self.uid));
+        self.io.request(&request)
+    }
+
+    fn print<W: Write, D: fmt::Display>(out: &mut W, val: D) -> Result<()> {
+        writeln!(out, "{val}").map_err(|err| Error::Cli(format!("error writing output: {err}")))
+    }
+
+    fn check_code(code: ResponseCode) -> Result<()> {
+        match code {
+            ResponseCode::Ack => Ok(()),
+            code => Err(Error::InvalidResponseCode(code.into())),
+        }
+    }
+}
+
+impl Drop for CliSession {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockDevice;
+
+    #[test]
+    fn test_cli_session() -> Result<()> {
+        let args = CliArgs::parse(["--uid", "3", "--retries", "1", "status"])?;
+        assert_eq!(args.command(), &CliCommand::Status);
+        assert!(CliArgs::parse(["--uid", "256", "status"]).is_err());
+        assert!(CliArgs::parse(["--retries"]).is_err());
+        assert!(CliArgs::parse(["image", "dump"]).is_err());
+
+        let session = CliSession::open(MockDevice::new(), &args)?;
+        assert_eq!(session.uid(), 3);
+
+        let mut out = Vec::new();
+        for command in [CliCommand::Idle, CliCommand::Inhibit, CliCommand::Status] {
+            session.run(&command, &mut out)?;
+        }
+        session.run(&CliCommand::Monitor(Some(1)), &mut out)?;
+
+        let out = String::from_utf8(out).unwrap_or_default();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], DeviceInhibit::Enabled.to_string());
+        assert_eq!(lines[1], DeviceInhibit::Inhibited.to_string());
+
+        assert!(matches!(
+            session.run(&CliCommand::Serial, &mut Vec::new()),
+            Err(Error::Cli(_))
+        ));
+
+        assert_eq!(CliArgs::parse(["debug"])?.command(), &CliCommand::Debug);
+        let mut out = Vec::new();
+        session.run(&CliCommand::Debug, &mut out)?;
+        let out = String::from_utf8(out).unwrap_or_default();
+        assert!(out.contains(r#""requests": 0"#));
+        // the monitored event was recorded before it was printed
+        assert!(out.contains(r#""PowerUp": "#));
+
+        let args = CliArgs::parse(["support-bundle", "bundle.json"])?;
+        assert_eq!(
+            args.command().to_string(),
+            r#"{"support_bundle": "bundle.json"}"#
+        );
+        assert!(CliArgs::parse(["support-bundle"]).is_err());
+        // the mock does not answer the device info requests
+        assert!(session.run(args.command(), &mut Vec::new()).is_err());
+
+        Ok(())
+    }
+}
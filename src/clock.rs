@@ -0,0 +1,189 @@
+#[cfg(target_has_atomic = "64")]
+use core::fmt;
+#[cfg(target_has_atomic = "64")]
+use core::sync::atomic::{AtomicU64, Ordering};
+use core::time;
+
+use alloc::sync::Arc;
+
+/// Represents a source of time used for timeouts, delays, and retry intervals.
+///
+/// Host-side helpers that wait on the device take a [Clock], so tests can substitute a
+/// [SimulatedClock] and run timeout logic instantly and deterministically.
+pub trait Clock: Send + Sync {
+    /// Gets the monotonic time elapsed since the [Clock] epoch.
+    fn now(&self) -> time::Duration;
+
+    /// Blocks the current thread (or advances virtual time) for the provided duration.
+    fn sleep(&self, dur: time::Duration);
+
+    /// Gets the time elapsed since an earlier [now](Self::now) reading.
+    fn elapsed(&self, since: time::Duration) -> time::Duration {
+        self.now().saturating_sub(since)
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> time::Duration {
+        (**self).now()
+    }
+
+    fn sleep(&self, dur: time::Duration) {
+        (**self).sleep(dur)
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for Arc<C> {
+    fn now(&self) -> time::Duration {
+        (**self).now()
+    }
+
+    fn sleep(&self, dur: time::Duration) {
+        (**self).sleep(dur)
+    }
+}
+
+/// Represents a [Clock] backed by the operating system monotonic clock.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SystemClock {
+    epoch: std::time::Instant,
+}
+
+#[cfg(feature = "std")]
+impl SystemClock {
+    /// Creates a new [SystemClock].
+    pub fn new() -> Self {
+        Self {
+            epoch: std::time::Instant::now(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now(&self) -> time::Duration {
+        self.epoch.elapsed()
+    }
+
+    fn sleep(&self, dur: time::Duration) {
+        std::thread::sleep(dur);
+    }
+}
+
+/// Represents a virtual [Clock] for deterministic simulation.
+///
+/// Time only moves when [sleep](Clock::sleep) or [advance](Self::advance) is called, so timeouts
+/// expire without waiting in real time.
+///
+/// Clones share the same virtual time source.
+#[cfg(target_has_atomic = "64")]
+#[derive(Clone, Debug, Default)]
+pub struct SimulatedClock {
+    nanos: Arc<AtomicU64>,
+}
+
+#[cfg(target_has_atomic = "64")]
+impl SimulatedClock {
+    /// Creates a new [SimulatedClock] starting at zero.
+    pub fn new() -> Self {
+        Self {
+            nanos: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Creates a new [SimulatedClock] starting at the provided time.
+    pub fn create(start: time::Duration) -> Self {
+        Self {
+            nanos: Arc::new(AtomicU64::new(duration_to_nanos(start))),
+        }
+    }
+
+    /// Advances the virtual time by the provided duration.
+    pub fn advance(&self, dur: time::Duration) {
+        self.nanos
+            .fetch_add(duration_to_nanos(dur), Ordering::SeqCst);
+    }
+
+    /// Sets the virtual time to the provided value.
+    ///
+    /// **NOTE** virtual time never moves backwards, earlier values are ignored.
+    pub fn set(&self, now: time::Duration) {
+        self.nanos
+            .fetch_max(duration_to_nanos(now), Ordering::SeqCst);
+    }
+}
+
+#[cfg(target_has_atomic = "64")]
+impl Clock for SimulatedClock {
+    fn now(&self) -> time::Duration {
+        time::Duration::from_nanos(self.nanos.load(Ordering::SeqCst))
+    }
+
+    fn sleep(&self, dur: time::Duration) {
+        self.advance(dur);
+        // let other simulated actors observe the new time
+        #[cfg(feature = "std")]
+        std::thread::yield_now();
+    }
+}
+
+#[cfg(target_has_atomic = "64")]
+impl fmt::Display for SimulatedClock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#"{{"now_ns": {}}}"#, self.now().as_nanos())
+    }
+}
+
+#[cfg(target_has_atomic = "64")]
+fn duration_to_nanos(dur: time::Duration) -> u64 {
+    u64::try_from(dur.as_nanos()).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn test_simulated_clock() {
+        let clock = SimulatedClock::new();
+        let shared = clock.clone();
+
+        assert_eq!(clock.now(), time::Duration::ZERO);
+
+        let real = Instant::now();
+
+        clock.sleep(time::Duration::from_secs(3600));
+        assert_eq!(shared.now(), time::Duration::from_secs(3600));
+
+        shared.advance(time::Duration::from_millis(500));
+        assert_eq!(
+            clock.elapsed(time::Duration::from_secs(3600)),
+            time::Duration::from_millis(500)
+        );
+
+        clock.set(time::Duration::from_secs(1));
+        assert_eq!(clock.now(), time::Duration::from_millis(3_600_500));
+
+        assert!(real.elapsed() < time::Duration::from_secs(1));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_system_clock() {
+        let clock = SystemClock::new();
+        let start = clock.now();
+
+        clock.sleep(time::Duration::from_millis(5));
+
+        assert!(clock.elapsed(start) >= time::Duration::from_millis(5));
+    }
+}
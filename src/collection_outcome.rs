@@ -0,0 +1,308 @@
+use core::{fmt, time};
+
+use crate::{
+    Clock, CollectMode, CollectRequest, Error, EventCode, Message, MessageType, Response,
+    ResponseCode, Result, UnitNumber,
+};
+
+/// Represents the default time to wait for the event reporting a note collection.
+pub const DEFAULT_COLLECT_TIMEOUT: time::Duration = time::Duration::from_secs(10);
+
+/// Represents the result of a note collection, regardless of which message reported it.
+///
+/// Collections are reported by three different message shapes:
+///
+/// - `Collected` event: notes left in the transport at `Power Up`
+/// - `AcceptorCollected` event: notes collected from the acceptor unit
+/// - `RecyclerCollect` response: notes collected from a recycler unit
+///
+/// When the device includes it, the unit that received the notes is reported in the first
+/// data byte.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CollectionOutcome {
+    mode: CollectMode,
+    unit: Option<UnitNumber>,
+    completed: bool,
+}
+
+impl CollectionOutcome {
+    /// Creates a new [CollectionOutcome] from the provided parameters.
+    pub const fn create(mode: CollectMode, unit: Option<UnitNumber>, completed: bool) -> Self {
+        Self {
+            mode,
+            unit,
+            completed,
+        }
+    }
+
+    /// Gets the [CollectMode] describing where the notes were collected from.
+    pub const fn mode(&self) -> CollectMode {
+        self.mode
+    }
+
+    /// Gets the [UnitNumber] that received the notes, if reported by the device.
+    pub const fn unit(&self) -> Option<UnitNumber> {
+        self.unit
+    }
+
+    /// Gets whether the collection completed successfully.
+    ///
+    /// Events always report a completed collection. Responses report completion with an `ACK`.
+    pub const fn completed(&self) -> bool {
+        self.completed
+    }
+
+    fn parse_unit(data: &[u8]) -> Option<UnitNumber> {
+        data.first()
+            .map(|&u| UnitNumber::from_u8(u))
+            .filter(UnitNumber::is_valid)
+    }
+}
+
+impl TryFrom<&Message> for CollectionOutcome {
+    type Error = Error;
+
+    fn try_from(val: &Message) -> Result<Self> {
+        let data = val.data();
+
+        match data.message_type() {
+            MessageType::Event(_) => {
+                let mode = match data.message_code().event_code()? {
+                    EventCode::Collected => CollectMode::PowerUp,
+                    EventCode::AcceptorCollected => CollectMode::Acceptor,
+                    code => {
+                        return Err(Error::InvalidEventCode(code.into()));
+                    }
+                };
+
+                Ok(Self::create(
+                    mode,
+                    Self::parse_unit(data.additional()),
+                    true,
+                ))
+            }
+            MessageType::Request(_) => {
+                let mode = CollectMode::try_from(data.message_code())?;
+                let res = Response::try_from(val)?;
+
+                Ok(Self::create(
+                    mode,
+                    Self::parse_unit(res.additional()),
+                    res.code() == ResponseCode::Ack,
+                ))
+            }
+            msg_type => Err(Error::InvalidMessageType(msg_type.into())),
+        }
+    }
+}
+
+impl TryFrom<Message> for CollectionOutcome {
+    type Error = Error;
+
+    fn try_from(val: Message) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
+impl fmt::Display for CollectionOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""mode": {}, "#, self.mode)?;
+        match self.unit {
+            Some(unit) => write!(f, r#""unit": {unit}, "#)?,
+            None => write!(f, r#""unit": null, "#)?,
+        }
+        write!(f, r#""completed": {}"#, self.completed)?;
+        write!(f, "}}")
+    }
+}
+
+/// Collects a note stranded in the device into the cashbox, e.g. a note left in the transport at
+/// `Power Up`.
+///
+/// Sends a `Collect` request for [CollectMode::PowerUp], or an `Acceptor Collect` request for
+/// [CollectMode::Acceptor], and waits for the `Collected` or `AcceptorCollected` event. The event
+/// function receives the next device event within the provided timeout, and is responsible for
+/// acknowledging it. Unrelated events are skipped.
+///
+/// Recycler collections are reported in the response, so [CollectMode::Recycler] returns without
+/// waiting for an event.
+pub fn collect_stranded_note<C, P, E>(
+    clock: &C,
+    mode: CollectMode,
+    timeout: time::Duration,
+    mut poll: P,
+    mut events: E,
+) -> Result<CollectionOutcome>
+where
+    C: Clock + ?Sized,
+    P: FnMut(&Message) -> Result<Message>,
+    E: FnMut(time::Duration) -> Result<Message>,
+{
+    let res = poll(&CollectRequest::create(mode).into())?;
+
+    match Response::try_from(&res)?.code() {
+        ResponseCode::Ack if mode == CollectMode::Recycler => {
+            return CollectionOutcome::try_from(res)
+        }
+        ResponseCode::Ack => (),
+        code => return Err(Error::InvalidResponseCode(code.into())),
+    }
+
+    let start = clock.now();
+
+    loop {
+        let remaining = timeout.saturating_sub(clock.elapsed(start));
+        if remaining.is_zero() {
+            return Err(Error::Timeout(format!(
+                "collect: waiting for the {mode} collection"
+            )));
+        }
+
+        let event = events(remaining)?;
+        if matches!(
+            event.data().message_code().event_code(),
+            Ok(EventCode::Collected | EventCode::AcceptorCollected)
+        ) {
+            let outcome = CollectionOutcome::try_from(event)?;
+            log::info!("stranded note collected: {outcome}");
+            return Ok(outcome);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventType, MessageCode, MessageData, RequestCode, RequestType, SimulatedClock};
+
+    fn message(msg_type: MessageType, msg_code: MessageCode, additional: &[u8]) -> Message {
+        Message::new().with_data(
+            MessageData::new()
+                .with_message_type(msg_type)
+                .with_message_code(msg_code)
+                .with_additional(additional),
+        )
+    }
+
+    #[test]
+    fn test_collection_outcome() -> Result<()> {
+        let event = MessageType::Event(EventType::Sequence0);
+        let operation = MessageType::Request(RequestType::Operation);
+        let unit = UnitNumber::from_u8(0x12);
+
+        let outcome = CollectionOutcome::try_from(message(
+            event,
+            MessageCode::Event(EventCode::Collected),
+            &[],
+        ))?;
+        assert_eq!(
+            outcome,
+            CollectionOutcome::create(CollectMode::PowerUp, None, true)
+        );
+
+        let outcome = CollectionOutcome::try_from(message(
+            event,
+            MessageCode::Event(EventCode::AcceptorCollected),
+            &[unit.into_u8()],
+        ))?;
+        assert_eq!(
+            outcome,
+            CollectionOutcome::create(CollectMode::Acceptor, Some(unit), true)
+        );
+
+        let outcome = CollectionOutcome::try_from(message(
+            operation,
+            MessageCode::Request(RequestCode::RecyclerCollect),
+            &[ResponseCode::Ack.into(), unit.into_u8()],
+        ))?;
+        assert_eq!(
+            outcome,
+            CollectionOutcome::create(CollectMode::Recycler, Some(unit), true)
+        );
+
+        let outcome = CollectionOutcome::try_from(message(
+            operation,
+            MessageCode::Request(RequestCode::RecyclerCollect),
+            &[ResponseCode::Nak.into()],
+        ))?;
+        assert!(!outcome.completed());
+
+        assert!(CollectionOutcome::try_from(message(
+            event,
+            MessageCode::Event(EventCode::Idle),
+            &[]
+        ))
+        .is_err());
+        assert!(CollectionOutcome::try_from(message(
+            operation,
+            MessageCode::Request(RequestCode::Stack),
+            &[ResponseCode::Ack.into()]
+        ))
+        .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_collect_stranded_note() -> Result<()> {
+        let clock = SimulatedClock::new();
+        let unit = UnitNumber::from_u8(0x12);
+        let mut sent = Vec::new();
+        let ack = |req: &Message| -> Result<Message> {
+            sent.push(req.data().message_code().request_code()?);
+            Ok(Message::new().with_data(
+                req.data()
+                    .clone()
+                    .with_additional(&[ResponseCode::Ack.into()]),
+            ))
+        };
+        let mut queue = vec![
+            message(
+                MessageType::Event(EventType::Sequence1),
+                MessageCode::Event(EventCode::AcceptorCollected),
+                &[unit.into_u8()],
+            ),
+            message(
+                MessageType::Event(EventType::Sequence0),
+                MessageCode::Event(EventCode::Idle),
+                &[],
+            ),
+        ];
+        let events = |_| queue.pop().ok_or(Error::Timeout("no event".into()));
+
+        let outcome = collect_stranded_note(
+            &clock,
+            CollectMode::Acceptor,
+            DEFAULT_COLLECT_TIMEOUT,
+            ack,
+            events,
+        )?;
+        assert_eq!(
+            outcome,
+            CollectionOutcome::create(CollectMode::Acceptor, Some(unit), true)
+        );
+        assert_eq!(sent, [RequestCode::AcceptorCollect]);
+
+        let nak = |req: &Message| -> Result<Message> {
+            Ok(Message::new().with_data(
+                req.data()
+                    .clone()
+                    .with_additional(&[ResponseCode::Nak.into()]),
+            ))
+        };
+        assert_eq!(
+            collect_stranded_note(
+                &clock,
+                CollectMode::PowerUp,
+                DEFAULT_COLLECT_TIMEOUT,
+                nak,
+                |_| Err(Error::Timeout("no event".into())),
+            ),
+            Err(Error::InvalidResponseCode(ResponseCode::Nak.into()))
+        );
+
+        Ok(())
+    }
+}
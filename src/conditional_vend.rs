@@ -0,0 +1,198 @@
+use core::fmt;
+
+use crate::{Denomination, Error, Result, DENOM_LEN};
+
+/// Represents the length of [ConditionalVendSettings].
+pub const CONDITIONAL_VEND_SETTINGS_LEN: usize = 1 + (2 * DENOM_LEN);
+
+const DISABLED: u8 = 0x00;
+const ENABLED: u8 = 0x01;
+
+/// Represents the `Conditional Vend` (conditional stacking) settings of the device.
+///
+/// When enabled, notes with a value within the lower and upper thresholds are held after
+/// `Vend Valid`, and the device sends a `Conditional Vend` event. The host then decides to
+/// stack or return the note.
+///
+/// ## Format
+///
+/// Field  | Enable | Lower threshold | Upper threshold
+/// -------|--------|-----------------|----------------
+/// Length | 1 byte | 2 bytes         | 2 bytes
+///
+/// Both thresholds use the [Denomination] encoding.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{ConditionalVendSettings, Denomination};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// let (lower, upper) = (Denomination::from_value(20), Denomination::from_value(100));
+/// let settings = ConditionalVendSettings::create(true, lower, upper)?;
+///
+/// assert!(settings.applies_to(Denomination::from_value(50)));
+/// assert!(!settings.applies_to(Denomination::from_value(10)));
+/// assert_eq!(settings.into_bytes(), [0x01, 0x14, 0x00, 0x64, 0x00]);
+/// # Ok(())
+/// # }
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConditionalVendSettings {
+    enabled: bool,
+    lower: Denomination,
+    upper: Denomination,
+}
+
+impl ConditionalVendSettings {
+    /// Creates a new, disabled [ConditionalVendSettings].
+    pub const fn new() -> Self {
+        Self {
+            enabled: false,
+            lower: Denomination::new(),
+            upper: Denomination::new(),
+        }
+    }
+
+    /// Creates a new [ConditionalVendSettings] from the provided parameters.
+    ///
+    /// Returns an error if the lower threshold is above the upper threshold.
+    pub fn create(enabled: bool, lower: Denomination, upper: Denomination) -> Result<Self> {
+        if lower.value() <= upper.value() {
+            Ok(Self {
+                enabled,
+                lower,
+                upper,
+            })
+        } else {
+            Err(Error::InvalidConditionalVendThresholds((
+                lower.value(),
+                upper.value(),
+            )))
+        }
+    }
+
+    /// Gets whether conditional stacking is enabled.
+    pub const fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Gets the lower threshold [Denomination].
+    pub const fn lower(&self) -> Denomination {
+        self.lower
+    }
+
+    /// Gets the upper threshold [Denomination].
+    pub const fn upper(&self) -> Denomination {
+        self.upper
+    }
+
+    /// Gets whether a note of the [Denomination] is conditionally stacked.
+    pub fn applies_to(&self, denomination: Denomination) -> bool {
+        let value = denomination.value();
+        self.enabled && value >= self.lower.value() && value <= self.upper.value()
+    }
+
+    /// Gets the byte length of the [ConditionalVendSettings].
+    pub const fn len() -> usize {
+        CONDITIONAL_VEND_SETTINGS_LEN
+    }
+
+    /// Attempts to convert a byte buffer into [ConditionalVendSettings].
+    pub fn from_bytes(buf: &[u8]) -> Result<Self> {
+        if buf.len() < CONDITIONAL_VEND_SETTINGS_LEN {
+            return Err(Error::InvalidConditionalVendSettingsLen((
+                buf.len(),
+                CONDITIONAL_VEND_SETTINGS_LEN,
+            )));
+        }
+
+        let enabled = match buf[0] {
+            DISABLED => false,
+            ENABLED => true,
+            flag => return Err(Error::InvalidConditionalVendFlag(flag)),
+        };
+
+        Self::create(
+            enabled,
+            Denomination::from_bytes(&buf[1..1 + DENOM_LEN]),
+            Denomination::from_bytes(&buf[1 + DENOM_LEN..CONDITIONAL_VEND_SETTINGS_LEN]),
+        )
+    }
+
+    /// Converts the [ConditionalVendSettings] into a byte array.
+    pub const fn into_bytes(self) -> [u8; CONDITIONAL_VEND_SETTINGS_LEN] {
+        let [lower_int, lower_exp] = self.lower.into_bytes();
+        let [upper_int, upper_exp] = self.upper.into_bytes();
+
+        [
+            if self.enabled { ENABLED } else { DISABLED },
+            lower_int,
+            lower_exp,
+            upper_int,
+            upper_exp,
+        ]
+    }
+}
+
+impl Default for ConditionalVendSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TryFrom<&[u8]> for ConditionalVendSettings {
+    type Error = Error;
+
+    fn try_from(val: &[u8]) -> Result<Self> {
+        Self::from_bytes(val)
+    }
+}
+
+impl fmt::Display for ConditionalVendSettings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""enabled": {}, "#, self.enabled)?;
+        write!(f, r#""lower": {}, "#, self.lower)?;
+        write!(f, r#""upper": {}"#, self.upper)?;
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conditional_vend_settings() -> Result<()> {
+        let settings = ConditionalVendSettings::from_bytes(&[ENABLED, 5, 0, 100, 0])?;
+        assert!(settings.enabled());
+        assert_eq!(settings.lower(), Denomination::from_value(5));
+        assert_eq!(settings.upper(), Denomination::from_value(100));
+        assert_eq!(settings.into_bytes(), [ENABLED, 5, 0, 100, 0]);
+
+        assert!(settings.applies_to(Denomination::from_value(100)));
+        assert!(!settings.applies_to(Denomination::from_value(1)));
+        assert!(!ConditionalVendSettings::new().applies_to(Denomination::new()));
+
+        assert_eq!(
+            ConditionalVendSettings::from_bytes(&[0x02, 5, 0, 1, 2]),
+            Err(Error::InvalidConditionalVendFlag(0x02))
+        );
+        assert_eq!(
+            ConditionalVendSettings::from_bytes(&[ENABLED, 1, 2, 5, 0]),
+            Err(Error::InvalidConditionalVendThresholds((100, 5)))
+        );
+        assert_eq!(
+            ConditionalVendSettings::from_bytes(&[ENABLED, 5, 0]),
+            Err(Error::InvalidConditionalVendSettingsLen((
+                3,
+                CONDITIONAL_VEND_SETTINGS_LEN
+            )))
+        );
+
+        Ok(())
+    }
+}
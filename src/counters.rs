@@ -0,0 +1,383 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::{fmt, fs};
+
+use crate::{Currency, CurrencyCode, Error, Result};
+
+#[cfg(feature = "sqlite")]
+mod sqlite_store;
+
+#[cfg(feature = "sqlite")]
+pub use sqlite_store::*;
+
+const KEY_ACCEPTED: &str = "accepted";
+const KEY_TICKETS: &str = "tickets";
+const KEY_REJECTED: &str = "rejected";
+const KEY_RETURNED: &str = "returned";
+const KEY_TOTAL_PREFIX: &str = "total.";
+
+/// Represents the audit/cash counters for a device.
+///
+/// Totals are tracked per ISO 4217 currency code, in whole denomination units.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuditCounters {
+    accepted: u64,
+    tickets: u64,
+    rejected: u64,
+    returned: u64,
+    totals: BTreeMap<String, u64>,
+}
+
+impl AuditCounters {
+    /// Creates a new, zeroed [AuditCounters].
+    pub const fn new() -> Self {
+        Self {
+            accepted: 0,
+            tickets: 0,
+            rejected: 0,
+            returned: 0,
+            totals: BTreeMap::new(),
+        }
+    }
+
+    /// Gets the number of accepted notes.
+    pub const fn accepted(&self) -> u64 {
+        self.accepted
+    }
+
+    /// Gets the number of accepted tickets.
+    pub const fn tickets(&self) -> u64 {
+        self.tickets
+    }
+
+    /// Gets the number of rejected notes/tickets.
+    pub const fn rejected(&self) -> u64 {
+        self.rejected
+    }
+
+    /// Gets the number of notes/tickets returned to the customer.
+    pub const fn returned(&self) -> u64 {
+        self.returned
+    }
+
+    /// Gets the accepted total for the provided [CurrencyCode].
+    pub fn total(&self, code: CurrencyCode) -> u64 {
+        self.totals
+            .get(<&str>::from(code))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Gets an iterator over the accepted totals, keyed by ISO 4217 currency code.
+    pub fn totals(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.totals.iter().map(|(k, v)| (k.as_str(), *v))
+    }
+
+    /// Records an accepted note.
+    pub fn record_note(&mut self, currency: &Currency) {
+        self.accepted = self.accepted.saturating_add(1);
+
+        let total = self
+            .totals
+            .entry(<&str>::from(currency.code()).into())
+            .or_default();
+        *total = total.saturating_add(currency.denomination().value());
+    }
+
+    /// Records an accepted ticket.
+    pub fn record_ticket(&mut self) {
+        self.tickets = self.tickets.saturating_add(1);
+    }
+
+    /// Records a rejected note/ticket.
+    pub fn record_rejected(&mut self) {
+        self.rejected = self.rejected.saturating_add(1);
+    }
+
+    /// Records a note/ticket returned to the customer.
+    pub fn record_returned(&mut self) {
+        self.returned = self.returned.saturating_add(1);
+    }
+
+    /// Resets all counters to zero, e.g. after a cashbox exchange.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Serializes the [AuditCounters] into `key=value` lines.
+    pub fn to_records(&self) -> String {
+        let mut out = String::new();
+
+        write_record(&mut out, KEY_ACCEPTED, self.accepted);
+        write_record(&mut out, KEY_TICKETS, self.tickets);
+        write_record(&mut out, KEY_REJECTED, self.rejected);
+        write_record(&mut out, KEY_RETURNED, self.returned);
+        for (code, total) in self.totals.iter() {
+            write_record(&mut out, format_args!("{KEY_TOTAL_PREFIX}{code}"), *total);
+        }
+
+        out
+    }
+
+    /// Parses `key=value` lines into [AuditCounters].
+    ///
+    /// Blank lines and unknown keys are ignored, so newer records remain readable.
+    pub fn from_records(records: &str) -> Result<Self> {
+        let mut counters = Self::new();
+
+        parse_records(records, Error::CountersStore, |key, val| match key {
+            KEY_ACCEPTED => counters.accepted = val,
+            KEY_TICKETS => counters.tickets = val,
+            KEY_REJECTED => counters.rejected = val,
+            KEY_RETURNED => counters.returned = val,
+            key => {
+                if let Some(code) = key.strip_prefix(KEY_TOTAL_PREFIX) {
+                    counters.totals.insert(code.to_ascii_uppercase(), val);
+                } else {
+                    log::debug!("ignoring unknown counter key: {key}");
+                }
+            }
+        })?;
+
+        Ok(counters)
+    }
+}
+
+/// Writes a `key=value` record line.
+pub(crate) fn write_record<K: fmt::Display>(out: &mut String, key: K, val: u64) {
+    out.push_str(format!("{key}={val}\n").as_str());
+}
+
+/// Parses `key=value` record lines, calling `f` with every trimmed key and value.
+///
+/// Blank lines are skipped. Malformed lines are reported with the `err` variant, so each record
+/// format keeps its own [Error].
+pub(crate) fn parse_records<F>(records: &str, err: fn(String) -> Error, mut f: F) -> Result<()>
+where
+    F: FnMut(&str, u64),
+{
+    for (i, record) in records.lines().enumerate() {
+        let record = record.trim();
+        if record.is_empty() {
+            continue;
+        }
+
+        let (key, val) = record
+            .split_once('=')
+            .ok_or_else(|| err(format!("invalid record, line: {}", i + 1)))?;
+        let val = val
+            .trim()
+            .parse::<u64>()
+            .map_err(|e| err(format!("invalid value, line: {}, error: {e}", i + 1)))?;
+
+        f(key.trim(), val);
+    }
+
+    Ok(())
+}
+
+impl fmt::Display for AuditCounters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""accepted": {}, "#, self.accepted)?;
+        write!(f, r#""tickets": {}, "#, self.tickets)?;
+        write!(f, r#""rejected": {}, "#, self.rejected)?;
+        write!(f, r#""returned": {}, "#, self.returned)?;
+        write!(f, r#""totals": {{"#)?;
+        for (i, (code, total)) in self.totals.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, r#""{code}": {total}"#)?;
+        }
+        write!(f, "}}}}")
+    }
+}
+
+/// Provides persistence for [AuditCounters], so totals survive restarts.
+pub trait CountersStore: Send {
+    /// Loads the stored [AuditCounters], returning zeroed counters if nothing is stored yet.
+    fn load(&mut self) -> Result<AuditCounters>;
+
+    /// Stores the [AuditCounters], replacing any previous value.
+    fn save(&mut self, counters: &AuditCounters) -> Result<()>;
+}
+
+/// Represents a volatile [CountersStore], useful for tests and hosts without storage.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryCountersStore {
+    counters: Option<AuditCounters>,
+}
+
+impl MemoryCountersStore {
+    /// Creates a new, empty [MemoryCountersStore].
+    pub const fn new() -> Self {
+        Self { counters: None }
+    }
+}
+
+impl CountersStore for MemoryCountersStore {
+    fn load(&mut self) -> Result<AuditCounters> {
+        Ok(self.counters.clone().unwrap_or_default())
+    }
+
+    fn save(&mut self, counters: &AuditCounters) -> Result<()> {
+        self.counters = Some(counters.clone());
+        Ok(())
+    }
+}
+
+/// Represents a file-backed reference [CountersStore].
+///
+/// Counters are stored as `key=value` lines, see `SqliteCountersStore` with the `sqlite` feature
+/// for a database-backed store. Saves write a temporary file, and rename it over
+/// the previous one, so an interrupted save never leaves a partially written file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FileCountersStore {
+    path: PathBuf,
+}
+
+impl FileCountersStore {
+    /// Creates a new [FileCountersStore] for the provided path.
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().into(),
+        }
+    }
+
+    /// Gets the path of the [FileCountersStore].
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+}
+
+impl CountersStore for FileCountersStore {
+    fn load(&mut self) -> Result<AuditCounters> {
+        match fs::read_to_string(&self.path) {
+            Ok(records) => AuditCounters::from_records(records.as_str()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(AuditCounters::new()),
+            Err(err) => Err(Error::CountersStore(format!(
+                "{}: {err}",
+                self.path.display()
+            ))),
+        }
+    }
+
+    fn save(&mut self, counters: &AuditCounters) -> Result<()> {
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+
+        fs::write(&tmp, counters.to_records())
+            .and_then(|_| fs::rename(&tmp, &self.path))
+            .map_err(|err| Error::CountersStore(format!("{}: {err}", self.path.display())))
+    }
+}
+
+/// Represents [AuditCounters] that are written through to a [CountersStore] on every update.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PersistentCounters<S: CountersStore> {
+    counters: AuditCounters,
+    store: S,
+}
+
+impl<S: CountersStore> PersistentCounters<S> {
+    /// Opens the [PersistentCounters], loading the current values from the [CountersStore].
+    pub fn open(mut store: S) -> Result<Self> {
+        Ok(Self {
+            counters: store.load()?,
+            store,
+        })
+    }
+
+    /// Gets a reference to the current [AuditCounters].
+    pub const fn counters(&self) -> &AuditCounters {
+        &self.counters
+    }
+
+    /// Gets a reference to the [CountersStore].
+    pub const fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Applies an update to the [AuditCounters], and saves the result.
+    ///
+    /// The in-memory counters are only changed if the save succeeds.
+    pub fn update<F: FnOnce(&mut AuditCounters)>(&mut self, f: F) -> Result<()> {
+        let mut counters = self.counters.clone();
+        f(&mut counters);
+
+        self.store.save(&counters)?;
+        self.counters = counters;
+
+        Ok(())
+    }
+
+    /// Consumes the [PersistentCounters], returning the [CountersStore].
+    pub fn into_store(self) -> S {
+        self.store
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Denomination;
+
+    fn note(code: CurrencyCode, value: u64) -> Currency {
+        Currency::new()
+            .with_code(code)
+            .with_denomination(Denomination::from_value(value))
+    }
+
+    #[test]
+    fn test_audit_counters_records() -> Result<()> {
+        let mut counters = AuditCounters::new();
+        counters.record_note(&note(CurrencyCode::USD, 20));
+        counters.record_note(&note(CurrencyCode::USD, 5));
+        counters.record_note(&note(CurrencyCode::JPY, 1000));
+        counters.record_ticket();
+        counters.record_rejected();
+
+        assert_eq!(counters.accepted(), 3);
+        assert_eq!(counters.total(CurrencyCode::USD), 25);
+        assert_eq!(counters.total(CurrencyCode::JPY), 1000);
+        assert_eq!(counters.total(CurrencyCode::EUR), 0);
+
+        let records = counters.to_records();
+        assert_eq!(
+            records,
+            "accepted=3\ntickets=1\nrejected=1\nreturned=0\ntotal.JPY=1000\ntotal.USD=25\n"
+        );
+        assert_eq!(AuditCounters::from_records(records.as_str())?, counters);
+
+        assert!(AuditCounters::from_records("accepted").is_err());
+        assert!(AuditCounters::from_records("accepted=x").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_file_counters_store() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("jcm-counters-{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut counters = PersistentCounters::open(FileCountersStore::new(&path))?;
+        assert_eq!(counters.counters(), &AuditCounters::new());
+
+        counters.update(|c| c.record_note(&note(CurrencyCode::USD, 100)))?;
+        counters.update(|c| c.record_returned())?;
+
+        // reopening simulates a host restart
+        let reopened = PersistentCounters::open(counters.into_store())?;
+        assert_eq!(reopened.counters().total(CurrencyCode::USD), 100);
+        assert_eq!(reopened.counters().returned(), 1);
+
+        fs::remove_file(&path).ok();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,209 @@
+use std::path::Path;
+
+use rusqlite::{params, Connection};
+
+use super::{AuditCounters, CountersStore, KEY_ACCEPTED, KEY_REJECTED, KEY_RETURNED, KEY_TICKETS};
+use crate::{Error, Result};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS counters (
+    name TEXT PRIMARY KEY NOT NULL,
+    value INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS currency_totals (
+    code TEXT PRIMARY KEY NOT NULL,
+    total INTEGER NOT NULL
+);";
+
+/// Represents a SQLite-backed reference [CountersStore].
+///
+/// Counters are stored in a `counters` table, keyed by counter name, and accepted totals in a
+/// `currency_totals` table, keyed by ISO 4217 currency code. Saves run in a single transaction,
+/// so an interrupted save never leaves a partial update.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{AuditCounters, CountersStore, SqliteCountersStore};
+///
+/// # fn main() -> jcm::Result<()> {
+/// let mut store = SqliteCountersStore::open_in_memory()?;
+/// assert_eq!(store.load()?, AuditCounters::new());
+///
+/// let mut counters = AuditCounters::new();
+/// counters.record_ticket();
+/// store.save(&counters)?;
+/// assert_eq!(store.load()?, counters);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SqliteCountersStore {
+    conn: Connection,
+}
+
+impl SqliteCountersStore {
+    /// Opens the [SqliteCountersStore] database at the provided path, creating it if needed.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        Connection::open(path)
+            .map_err(|err| Error::CountersStore(format!("{}: {err}", path.display())))
+            .and_then(Self::create)
+    }
+
+    /// Opens a volatile [SqliteCountersStore], e.g. for tests.
+    pub fn open_in_memory() -> Result<Self> {
+        Connection::open_in_memory()
+            .map_err(Self::sql_err)
+            .and_then(Self::create)
+    }
+
+    /// Creates a [SqliteCountersStore] from an open [Connection], creating the tables if needed.
+    pub fn create(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA).map_err(Self::sql_err)?;
+        Ok(Self { conn })
+    }
+
+    /// Gets a reference to the database [Connection].
+    pub const fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    fn sql_err(err: rusqlite::Error) -> Error {
+        Error::CountersStore(format!("SQLite error: {err}"))
+    }
+
+    fn to_sql(name: &str, val: u64) -> Result<i64> {
+        i64::try_from(val)
+            .map_err(|_| Error::CountersStore(format!("{name} out of range for SQLite: {val}")))
+    }
+
+    fn from_sql(name: &str, val: i64) -> Result<u64> {
+        u64::try_from(val)
+            .map_err(|_| Error::CountersStore(format!("invalid stored {name}: {val}")))
+    }
+}
+
+impl CountersStore for SqliteCountersStore {
+    fn load(&mut self) -> Result<AuditCounters> {
+        let mut counters = AuditCounters::new();
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT name, value FROM counters")
+            .map_err(Self::sql_err)?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+            })
+            .map_err(Self::sql_err)?;
+
+        for row in rows {
+            let (name, val) = row.map_err(Self::sql_err)?;
+            let val = Self::from_sql(name.as_str(), val)?;
+
+            match name.as_str() {
+                KEY_ACCEPTED => counters.accepted = val,
+                KEY_TICKETS => counters.tickets = val,
+                KEY_REJECTED => counters.rejected = val,
+                KEY_RETURNED => counters.returned = val,
+                name => log::debug!("ignoring unknown counter: {name}"),
+            }
+        }
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT code, total FROM currency_totals")
+            .map_err(Self::sql_err)?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+            })
+            .map_err(Self::sql_err)?;
+
+        for row in rows {
+            let (code, total) = row.map_err(Self::sql_err)?;
+            let total = Self::from_sql(code.as_str(), total)?;
+            counters.totals.insert(code.to_ascii_uppercase(), total);
+        }
+
+        Ok(counters)
+    }
+
+    fn save(&mut self, counters: &AuditCounters) -> Result<()> {
+        let tx = self.conn.transaction().map_err(Self::sql_err)?;
+
+        for (name, val) in [
+            (KEY_ACCEPTED, counters.accepted),
+            (KEY_TICKETS, counters.tickets),
+            (KEY_REJECTED, counters.rejected),
+            (KEY_RETURNED, counters.returned),
+        ] {
+            tx.execute(
+                "INSERT OR REPLACE INTO counters (name, value) VALUES (?1, ?2)",
+                params![name, Self::to_sql(name, val)?],
+            )
+            .map_err(Self::sql_err)?;
+        }
+
+        // totals are replaced, so currencies cleared by a reset do not linger
+        tx.execute("DELETE FROM currency_totals", [])
+            .map_err(Self::sql_err)?;
+        for (code, total) in counters.totals() {
+            tx.execute(
+                "INSERT INTO currency_totals (code, total) VALUES (?1, ?2)",
+                params![code, Self::to_sql(code, total)?],
+            )
+            .map_err(Self::sql_err)?;
+        }
+
+        tx.commit().map_err(Self::sql_err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Currency, CurrencyCode, Denomination, PersistentCounters};
+
+    fn note(code: CurrencyCode, value: u64) -> Currency {
+        Currency::new()
+            .with_code(code)
+            .with_denomination(Denomination::from_value(value))
+    }
+
+    #[test]
+    fn test_sqlite_counters_store() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("jcm-counters-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut counters = PersistentCounters::open(SqliteCountersStore::open(&path)?)?;
+        assert_eq!(counters.counters(), &AuditCounters::new());
+
+        counters.update(|c| c.record_note(&note(CurrencyCode::USD, 100)))?;
+        counters.update(|c| c.record_note(&note(CurrencyCode::JPY, 1000)))?;
+        counters.update(|c| c.record_rejected())?;
+        let expected = counters.counters().clone();
+
+        // reopening the database simulates a host restart
+        drop(counters);
+        let mut reopened = PersistentCounters::open(SqliteCountersStore::open(&path)?)?;
+        assert_eq!(reopened.counters(), &expected);
+        assert_eq!(reopened.counters().total(CurrencyCode::USD), 100);
+
+        // a reset clears the stored totals
+        reopened.update(|c| c.reset())?;
+        let mut store = reopened.into_store();
+        assert_eq!(store.load()?, AuditCounters::new());
+
+        let mut overflow = AuditCounters::new();
+        overflow.accepted = u64::MAX;
+        assert!(store.save(&overflow).is_err());
+        assert_eq!(store.load()?, AuditCounters::new());
+
+        drop(store);
+        std::fs::remove_file(&path).ok();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,345 @@
+use std::collections::VecDeque;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::{fmt, thread, time};
+
+use crate::{Error, Message, PollObserver, Result};
+
+/// Represents the default number of protocol messages kept by a [MessageRing].
+pub const DEFAULT_MESSAGE_RING_LEN: usize = 256;
+
+/// Represents the direction of a protocol message recorded in a [MessageRing].
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MessageDirection {
+    /// Message written by the host.
+    Sent,
+    /// Message read from the device.
+    Received,
+}
+
+impl From<MessageDirection> for &'static str {
+    fn from(val: MessageDirection) -> Self {
+        match val {
+            MessageDirection::Sent => "sent",
+            MessageDirection::Received => "received",
+        }
+    }
+}
+
+impl From<&MessageDirection> for &'static str {
+    fn from(val: &MessageDirection) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for MessageDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
+
+/// Represents a timestamped protocol [Message] recorded in a [MessageRing].
+#[derive(Clone, Debug, PartialEq)]
+pub struct MessageRecord {
+    timestamp: time::SystemTime,
+    direction: MessageDirection,
+    message: Message,
+}
+
+impl MessageRecord {
+    /// Creates a new [MessageRecord], timestamped with the current system time.
+    pub fn new(direction: MessageDirection, message: Message) -> Self {
+        Self::create(time::SystemTime::now(), direction, message)
+    }
+
+    /// Creates a new [MessageRecord] from the provided parameters.
+    pub const fn create(
+        timestamp: time::SystemTime,
+        direction: MessageDirection,
+        message: Message,
+    ) -> Self {
+        Self {
+            timestamp,
+            direction,
+            message,
+        }
+    }
+
+    /// Gets the time the [Message] was recorded.
+    pub const fn timestamp(&self) -> time::SystemTime {
+        self.timestamp
+    }
+
+    /// Gets the [MessageDirection].
+    pub const fn direction(&self) -> MessageDirection {
+        self.direction
+    }
+
+    /// Gets a reference to the recorded [Message].
+    pub const fn message(&self) -> &Message {
+        &self.message
+    }
+}
+
+impl fmt::Display for MessageRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let timestamp_ms = self
+            .timestamp
+            .duration_since(time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+
+        write!(f, "{{")?;
+        write!(f, r#""timestamp_ms": {timestamp_ms}, "#)?;
+        write!(f, r#""direction": {}, "#, self.direction)?;
+        write!(f, r#""message": {}"#, self.message)?;
+        write!(f, "}}")
+    }
+}
+
+/// Keeps the last protocol messages exchanged with a device, for post-mortem analysis.
+///
+/// The [MessageRing] implements [PollObserver], so passing it to the observed polling functions
+/// records every request and response; record device events with
+/// [record_received](Self::record_received). Clones share the same buffer. Once full, the oldest
+/// message is dropped for each new one.
+#[derive(Clone, Debug)]
+pub struct MessageRing {
+    records: Arc<Mutex<VecDeque<MessageRecord>>>,
+    capacity: usize,
+}
+
+impl MessageRing {
+    /// Creates a new [MessageRing] holding up to [DEFAULT_MESSAGE_RING_LEN] messages.
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_MESSAGE_RING_LEN)
+    }
+
+    /// Creates a new [MessageRing] holding up to `capacity` messages.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+
+        Self {
+            records: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    /// Gets the maximum number of messages kept by the [MessageRing].
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Gets the number of messages currently kept by the [MessageRing].
+    pub fn len(&self) -> usize {
+        self.records
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .len()
+    }
+
+    /// Gets whether the [MessageRing] is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Records a [Message] written by the host.
+    pub fn record_sent(&self, message: &Message) {
+        self.record(MessageRecord::new(MessageDirection::Sent, message.clone()));
+    }
+
+    /// Records a [Message] read from the device.
+    pub fn record_received(&self, message: &Message) {
+        self.record(MessageRecord::new(
+            MessageDirection::Received,
+            message.clone(),
+        ));
+    }
+
+    /// Records a [MessageRecord], dropping the oldest record if the [MessageRing] is full.
+    pub fn record(&self, record: MessageRecord) {
+        // a poisoned lock still holds the records worth flushing after a panic
+        let mut records = self.records.lock().unwrap_or_else(|err| err.into_inner());
+
+        if records.len() >= self.capacity {
+            records.pop_front();
+        }
+        records.push_back(record);
+    }
+
+    /// Gets a copy of the recorded messages, oldest first.
+    pub fn records(&self) -> Vec<MessageRecord> {
+        self.records
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Removes all recorded messages.
+    pub fn clear(&self) {
+        self.records
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .clear();
+    }
+
+    /// Writes the recorded messages, one per line, oldest first.
+    pub fn write_to<W: Write>(&self, out: &mut W) -> Result<()> {
+        for record in self.records() {
+            writeln!(out, "{record}")
+                .map_err(|err| Error::CrashLog(format!("error writing record: {err}")))?;
+        }
+
+        out.flush()
+            .map_err(|err| Error::CrashLog(format!("error flushing records: {err}")))
+    }
+
+    /// Appends the recorded messages to the file at `path`, creating it if needed.
+    pub fn flush_to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|err| Error::CrashLog(format!("unable to open {}: {err}", path.display())))?;
+
+        self.write_to(&mut file)
+    }
+}
+
+impl Default for MessageRing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PollObserver for MessageRing {
+    fn on_request_sent(&self, request: &Message, _attempt: usize) {
+        self.record_sent(request);
+    }
+
+    fn on_response(&self, _request: &Message, response: &Message, _attempt: usize) {
+        self.record_received(response);
+    }
+
+    fn on_unsolicited(&self, response: &Message) {
+        self.record_received(response);
+    }
+
+    fn on_status(&self, status: &Message) {
+        self.record_received(status);
+    }
+}
+
+/// Flushes a [MessageRing] to a crash log file on panic or abnormal exit.
+///
+/// Create the guard at the top of the device session, and call [finish](Self::finish) on a
+/// clean shutdown. If the guard is dropped while unwinding from a panic, or without having been
+/// finished (e.g. an early return on error), the recorded messages are appended to the crash
+/// log file.
+#[derive(Debug)]
+pub struct CrashLogGuard {
+    ring: MessageRing,
+    path: PathBuf,
+    finished: bool,
+}
+
+impl CrashLogGuard {
+    /// Creates a new [CrashLogGuard] flushing the [MessageRing] to the file at `path`.
+    pub fn new<P: Into<PathBuf>>(ring: MessageRing, path: P) -> Self {
+        Self {
+            ring,
+            path: path.into(),
+            finished: false,
+        }
+    }
+
+    /// Gets a reference to the guarded [MessageRing].
+    pub const fn ring(&self) -> &MessageRing {
+        &self.ring
+    }
+
+    /// Gets the path of the crash log file.
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    /// Marks a clean shutdown, so the [MessageRing] is not flushed on drop.
+    pub fn finish(mut self) {
+        self.finished = true;
+    }
+}
+
+impl Drop for CrashLogGuard {
+    fn drop(&mut self) {
+        if self.finished && !thread::panicking() {
+            return;
+        }
+
+        match self.ring.flush_to_file(&self.path) {
+            Ok(()) => log::warn!(
+                "abnormal exit, wrote {} protocol messages to {}",
+                self.ring.len(),
+                self.path.display()
+            ),
+            Err(err) => log::error!("abnormal exit, unable to write crash log: {err}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{StatusRequest, UidRequest};
+
+    #[test]
+    fn test_crash_log() -> Result<()> {
+        let ring = MessageRing::with_capacity(2);
+        let status = Message::from(StatusRequest::new());
+        let uid = Message::from(UidRequest::new());
+
+        ring.on_request_sent(&status, 0);
+        ring.on_response(&status, &status, 0);
+        ring.on_request_sent(&uid, 0);
+
+        let records = ring.records();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].direction(), MessageDirection::Received);
+        assert_eq!(records[1].message(), &uid);
+
+        let mut out = Vec::new();
+        ring.write_to(&mut out)?;
+        let out = String::from_utf8(out).unwrap_or_default();
+        assert_eq!(out.lines().count(), 2);
+        assert!(out.lines().all(|l| l.contains(r#""direction": "#)));
+
+        let path = std::env::temp_dir().join(format!("jcm-crash-{}.log", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        CrashLogGuard::new(ring.clone(), &path).finish();
+        assert!(!path.exists());
+
+        drop(CrashLogGuard::new(ring.clone(), &path));
+        let written = std::fs::read_to_string(&path).unwrap_or_default();
+        assert_eq!(written.lines().count(), 2);
+
+        let guard_ring = ring.clone();
+        let guard_path = path.clone();
+        let _ = thread::spawn(move || {
+            let _guard = CrashLogGuard::new(guard_ring, guard_path);
+            panic!("simulated crash");
+        })
+        .join();
+        let written = std::fs::read_to_string(&path).unwrap_or_default();
+        assert_eq!(written.lines().count(), 4);
+
+        std::fs::remove_file(&path).ok();
+
+        Ok(())
+    }
+}
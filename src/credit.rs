@@ -0,0 +1,388 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::{redact, Error, EscrowData, EscrowEvent, EventCode, EventType, Message, Result};
+
+/// Represents a credit notification for a note or ticket that reached `Vend Valid`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Credit {
+    id: u64,
+    data: Option<EscrowData>,
+}
+
+impl Credit {
+    /// Creates a new [Credit] from the provided parameters.
+    pub const fn create(id: u64, data: Option<EscrowData>) -> Self {
+        Self { id, data }
+    }
+
+    /// Gets the journal ID of the [Credit].
+    pub const fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Gets the escrowed [EscrowData] of the [Credit].
+    ///
+    /// Returns `None` if the `Escrow` event was not observed, e.g. after a host restart between
+    /// `Escrow` and `Vend Valid`.
+    pub const fn data(&self) -> Option<&EscrowData> {
+        self.data.as_ref()
+    }
+}
+
+impl fmt::Display for Credit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""id": {}, "#, self.id)?;
+        match self.data.as_ref() {
+            Some(data) => write!(f, r#""data": {data}"#)?,
+            None => write!(f, r#""data": null"#)?,
+        }
+        write!(f, "}}")
+    }
+}
+
+/// Represents a single record in a [CreditJournal].
+#[derive(Clone, Debug, PartialEq)]
+pub enum JournalEntry {
+    /// A credit was recorded, before delivery to the application.
+    Credit(Credit),
+    /// The application acknowledged the credit with the provided ID.
+    Ack(u64),
+}
+
+impl JournalEntry {
+    /// Converts the [JournalEntry] into a single-line record.
+    pub fn to_record(&self) -> String {
+        match self {
+            Self::Credit(credit) => {
+                let data = credit
+                    .data
+                    .as_ref()
+                    .map(|d| d.to_vec().iter().map(|b| format!("{b:02x}")).collect())
+                    .unwrap_or_else(|| String::from("-"));
+                format!("credit {} {data}", credit.id)
+            }
+            Self::Ack(id) => format!("ack {id}"),
+        }
+    }
+
+    /// Parses a single-line record into a [JournalEntry].
+    pub fn from_record(record: &str) -> Result<Self> {
+        let journal_err = || Error::Journal(format!("invalid record: {record}"));
+        let mut fields = record.split_whitespace();
+
+        let kind = fields.next().ok_or_else(journal_err)?;
+        let id = fields
+            .next()
+            .and_then(|f| f.parse::<u64>().ok())
+            .ok_or_else(journal_err)?;
+
+        match (kind, fields.next()) {
+            ("credit", Some("-")) => Ok(Self::Credit(Credit::create(id, None))),
+            ("credit", Some(hex)) if hex.is_ascii() && hex.len() % 2 == 0 => {
+                let bytes = (0..hex.len())
+                    .step_by(2)
+                    .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+                    .collect::<std::result::Result<Vec<u8>, _>>()
+                    .map_err(|_| journal_err())?;
+
+                Ok(Self::Credit(Credit::create(
+                    id,
+                    Some(EscrowData::try_from(bytes.as_slice())?),
+                )))
+            }
+            ("ack", None) => Ok(Self::Ack(id)),
+            _ => Err(journal_err()),
+        }
+    }
+}
+
+/// Provides durable storage for credit notifications.
+///
+/// Entries must be durable when [append](Self::append) returns, since the [CreditLedger] only
+/// delivers a credit after it has been journaled.
+pub trait CreditJournal: Send {
+    /// Appends an entry to the [CreditJournal].
+    fn append(&mut self, entry: &JournalEntry) -> Result<()>;
+
+    /// Reads all entries from the [CreditJournal], in append order.
+    fn entries(&mut self) -> Result<Vec<JournalEntry>>;
+}
+
+/// Represents a volatile [CreditJournal], useful for tests.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MemoryJournal {
+    entries: Vec<JournalEntry>,
+}
+
+impl MemoryJournal {
+    /// Creates a new, empty [MemoryJournal].
+    pub const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl CreditJournal for MemoryJournal {
+    fn append(&mut self, entry: &JournalEntry) -> Result<()> {
+        self.entries.push(entry.clone());
+        Ok(())
+    }
+
+    fn entries(&mut self) -> Result<Vec<JournalEntry>> {
+        Ok(self.entries.clone())
+    }
+}
+
+/// Represents an append-only, file-backed [CreditJournal].
+///
+/// Each entry is written as one line, and synced to disk before [append](CreditJournal::append)
+/// returns.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FileJournal {
+    path: PathBuf,
+}
+
+impl FileJournal {
+    /// Creates a new [FileJournal] for the provided path.
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().into(),
+        }
+    }
+
+    /// Gets the path of the [FileJournal].
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    fn io_err(&self, err: std::io::Error) -> Error {
+        Error::Journal(format!("{}: {err}", self.path.display()))
+    }
+}
+
+impl CreditJournal for FileJournal {
+    fn append(&mut self, entry: &JournalEntry) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|err| self.io_err(err))?;
+
+        writeln!(file, "{}", entry.to_record())
+            .and_then(|_| file.sync_data())
+            .map_err(|err| self.io_err(err))
+    }
+
+    fn entries(&mut self) -> Result<Vec<JournalEntry>> {
+        match fs::read_to_string(&self.path) {
+            Ok(records) => records
+                .lines()
+                .map(str::trim)
+                .filter(|r| !r.is_empty())
+                .map(JournalEntry::from_record)
+                .collect(),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(err) => Err(self.io_err(err)),
+        }
+    }
+}
+
+/// Provides exactly-once delivery of [Credit]s from `Vend Valid` events.
+///
+/// - Resent `Vend Valid` events (same event sequence, no intervening event) are de-duplicated.
+/// - Each [Credit] is journaled before it is returned to the application.
+/// - Credits the application has not [acknowledged](Self::acknowledge) are returned by
+///   [pending](Self::pending), including after a restart.
+#[derive(Debug)]
+pub struct CreditLedger<J: CreditJournal> {
+    journal: J,
+    pending: BTreeMap<u64, Credit>,
+    next_id: u64,
+    escrow: Option<EscrowData>,
+    last_event: Option<(EventType, EventCode)>,
+}
+
+impl<J: CreditJournal> CreditLedger<J> {
+    /// Opens the [CreditLedger], restoring unacknowledged credits from the [CreditJournal].
+    pub fn open(mut journal: J) -> Result<Self> {
+        let mut pending = BTreeMap::new();
+        let mut next_id = 0;
+
+        for entry in journal.entries()? {
+            match entry {
+                JournalEntry::Credit(credit) => {
+                    next_id = next_id.max(credit.id.saturating_add(1));
+                    pending.insert(credit.id, credit);
+                }
+                JournalEntry::Ack(id) => {
+                    pending.remove(&id);
+                }
+            }
+        }
+
+        if !pending.is_empty() {
+            log::info!("restored {} unacknowledged credits", pending.len());
+        }
+
+        Ok(Self {
+            journal,
+            pending,
+            next_id,
+            escrow: None,
+            last_event: None,
+        })
+    }
+
+    /// Processes a device event [Message].
+    ///
+    /// Returns a new [Credit] for the first delivery of a `Vend Valid` event, and `None` for
+    /// all other events, including resent `Vend Valid` events.
+    pub fn on_event(&mut self, event: &Message) -> Result<Option<Credit>> {
+        let data = event.data();
+        let (event_type, code) = match (
+            data.message_type().event_type(),
+            data.message_code().event_code(),
+        ) {
+            (Ok(t), Ok(c)) => (t, c),
+            _ => return Ok(None),
+        };
+
+        let duplicate = self.last_event == Some((event_type, code));
+        self.last_event = Some((event_type, code));
+
+        match code {
+            EventCode::Escrow => {
+                self.escrow = EscrowEvent::try_from(event).ok().map(|e| e.data().clone());
+                Ok(None)
+            }
+            EventCode::VendValid if duplicate => {
+                log::debug!("ignoring resent Vend Valid event: {}", redact(event));
+                Ok(None)
+            }
+            EventCode::VendValid => {
+                let credit = Credit::create(self.next_id, self.escrow.take());
+
+                self.journal.append(&JournalEntry::Credit(credit.clone()))?;
+                self.next_id = self.next_id.saturating_add(1);
+                self.pending.insert(credit.id, credit.clone());
+
+                Ok(Some(credit))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Acknowledges delivery of the [Credit] with the provided ID.
+    ///
+    /// Acknowledging an unknown, or already acknowledged, ID is a no-op.
+    pub fn acknowledge(&mut self, id: u64) -> Result<()> {
+        if self.pending.contains_key(&id) {
+            self.journal.append(&JournalEntry::Ack(id))?;
+            self.pending.remove(&id);
+        }
+
+        Ok(())
+    }
+
+    /// Gets an iterator over unacknowledged [Credit]s, in journal order.
+    pub fn pending(&self) -> impl Iterator<Item = &Credit> {
+        self.pending.values()
+    }
+
+    /// Consumes the [CreditLedger], returning the [CreditJournal].
+    pub fn into_journal(self) -> J {
+        self.journal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Currency, CurrencyCode, Denomination, MessageCode, MessageData, MessageType};
+
+    fn event(event_type: EventType, code: EventCode, additional: &[u8]) -> Message {
+        Message::new().with_data(
+            MessageData::new()
+                .with_message_type(MessageType::Event(event_type))
+                .with_message_code(MessageCode::Event(code))
+                .with_additional(additional),
+        )
+    }
+
+    fn escrow_data() -> EscrowData {
+        EscrowData::new_currency(
+            Currency::new()
+                .with_code(CurrencyCode::USD)
+                .with_denomination(Denomination::from_value(20)),
+        )
+    }
+
+    #[test]
+    fn test_credit_ledger() -> Result<()> {
+        let mut ledger = CreditLedger::open(MemoryJournal::new())?;
+
+        let escrow = event(
+            EventType::Sequence0,
+            EventCode::Escrow,
+            escrow_data().to_vec().as_ref(),
+        );
+        let vend = event(EventType::Sequence1, EventCode::VendValid, &[]);
+
+        assert_eq!(ledger.on_event(&escrow)?, None);
+
+        let credit = ledger.on_event(&vend)?.unwrap();
+        assert_eq!(credit.data(), Some(&escrow_data()));
+
+        // resent event is not credited twice
+        assert_eq!(ledger.on_event(&vend)?, None);
+        assert_eq!(ledger.pending().count(), 1);
+
+        // a restart before acknowledgement re-delivers the credit
+        let mut ledger = CreditLedger::open(ledger.into_journal())?;
+        assert_eq!(ledger.pending().collect::<Vec<_>>(), [&credit]);
+
+        ledger.acknowledge(credit.id())?;
+        ledger.acknowledge(credit.id())?;
+
+        let mut ledger = CreditLedger::open(ledger.into_journal())?;
+        assert_eq!(ledger.pending().count(), 0);
+
+        // new credits continue the journal ID sequence
+        ledger.on_event(&event(EventType::Sequence2, EventCode::Idle, &[]))?;
+        let next = ledger.on_event(&event(EventType::Sequence3, EventCode::VendValid, &[]))?;
+        assert_eq!(next, Some(Credit::create(credit.id() + 1, None)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_file_journal() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("jcm-journal-{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut journal = FileJournal::new(&path);
+        let entries = [
+            JournalEntry::Credit(Credit::create(0, Some(escrow_data()))),
+            JournalEntry::Credit(Credit::create(1, None)),
+            JournalEntry::Ack(0),
+        ];
+
+        for entry in entries.iter() {
+            journal.append(entry)?;
+        }
+        assert_eq!(journal.entries()?, entries);
+
+        assert!(JournalEntry::from_record("credit x -").is_err());
+        assert!(JournalEntry::from_record("ack 1 2").is_err());
+
+        fs::remove_file(&path).ok();
+
+        Ok(())
+    }
+}
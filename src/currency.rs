@@ -1,4 +1,6 @@
-use std::fmt;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
 
 pub use currency_iso4217::Currency as CurrencyCode;
 
@@ -7,9 +9,26 @@ use crate::{Denomination, Error, Result};
 /// Represents the length in bytes of the [Currency].
 pub const CURRENCY_LEN: usize = 5;
 
+/// Gets the number of ISO 4217 minor units (decimal places) of the [CurrencyCode].
+///
+/// Codes without minor units (e.g. precious metals, and `XXX`) return zero.
+pub const fn minor_units(code: CurrencyCode) -> u8 {
+    use CurrencyCode::*;
+
+    match code {
+        BHD | IQD | JOD | KWD | LYD | OMR | TND => 3,
+        CLF | UYW => 4,
+        BIF | CLP | DJF | GNF | ISK | JPY | KMF | KRW | PYG | RWF | UGX | UYI | VND | VUV | XAF
+        | XOF | XPF => 0,
+        XAG | XAU | XBA | XBB | XBC | XBD | XDR | XPD | XPT | XSU | XTS | XUA | XXX => 0,
+        _ => 2,
+    }
+}
+
 /// Represents device currency code and denomination.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Currency {
     code: CurrencyCode,
     denomination: Denomination,
@@ -56,6 +75,20 @@ impl Currency {
         self
     }
 
+    /// Gets the number of ISO 4217 minor units (decimal places) of the [CurrencyCode].
+    pub const fn minor_units(&self) -> u8 {
+        minor_units(self.code)
+    }
+
+    /// Gets the [Denomination] value in minor units, e.g. `2000` for `20 USD`.
+    ///
+    /// Saturates at [`u64::MAX`].
+    pub fn minor_value(&self) -> u64 {
+        self.denomination
+            .value()
+            .saturating_mul(10u64.saturating_pow(self.minor_units() as u32))
+    }
+
     /// Gets the length of the [Currency].
     pub const fn len() -> usize {
         CurrencyCode::LEN + Denomination::len()
@@ -130,6 +163,36 @@ impl fmt::Display for Currency {
     }
 }
 
+impl FromStr for Currency {
+    type Err = Error;
+
+    /// Parses a [Currency] from a code and value pair, in either order, e.g. `USD 100` or
+    /// `100 USD`.
+    fn from_str(val: &str) -> Result<Self> {
+        let err = || Error::InvalidCurrencyString(val.trim().into());
+        let fields: Vec<&str> = val.split_whitespace().collect();
+
+        let (code, value) = match fields.as_slice() {
+            [code, value] if value.parse::<u64>().is_ok() => (code, value),
+            [value, code] => (code, value),
+            _ => return Err(err()),
+        };
+
+        let code = match CurrencyCode::from(code.to_ascii_uppercase().as_str()) {
+            CurrencyCode::XXX => return Err(err()),
+            code => code,
+        };
+
+        let value = value.parse::<u64>().map_err(|_| err())?;
+        let denomination = Denomination::from_value(value);
+        if !denomination.is_valid() || denomination.value() != value {
+            return Err(err());
+        }
+
+        Ok(Self::new().with_code(code).with_denomination(denomination))
+    }
+}
+
 impl TryFrom<&[u8]> for Currency {
     type Error = Error;
 
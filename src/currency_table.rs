@@ -0,0 +1,418 @@
+use alloc::{string::ToString, vec::Vec};
+use core::fmt;
+
+use crate::{
+    Currency, CurrencyAssign, CurrencyAssignResponse, CurrencyCode, DenominationDisableRequest,
+    DenominationTable, DenominationTableEntry, Error, Message, RequestCode, RequestType, Response,
+    ResponseCode, Result,
+};
+
+/// Represents the `Currency Assign` table of a device, mapping bit numbers to denominations.
+///
+/// Denominations are looked up by [Currency], e.g. parsed from `"USD 100"`, or by exact value in
+/// ISO 4217 minor units, e.g. `10000` cents, so callers never compute bit numbers by hand.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{Currency, CurrencyAssign, CurrencyCode, CurrencyTable, Denomination};
+///
+/// # fn main() -> jcm::Result<()> {
+/// let usd = |bit, value| {
+///     CurrencyAssign::new().with_bit_number(bit).with_currency(
+///         Currency::new()
+///             .with_code(CurrencyCode::USD)
+///             .with_denomination(Denomination::from_value(value)),
+///     )
+/// };
+/// let table = CurrencyTable::from([usd(0, 20), usd(1, 100)].as_ref());
+///
+/// assert_eq!(table.bit_number(&"USD 100".parse()?), Some(1));
+/// assert_eq!(table.find_minor(CurrencyCode::USD, 2000), Some(&usd(0, 20)));
+///
+/// let req = table.disable_request(&["USD 100".parse()?])?;
+/// assert!(req.denominations()[0].is_disabled(1));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CurrencyTable(Vec<CurrencyAssign>);
+
+impl CurrencyTable {
+    /// Creates a new, empty [CurrencyTable].
+    pub const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Gets a reference to the list of [CurrencyAssign] entries.
+    pub fn entries(&self) -> &[CurrencyAssign] {
+        self.0.as_ref()
+    }
+
+    /// Gets the number of assigned denominations.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Gets whether the [CurrencyTable] is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Gets an iterator over the [CurrencyAssign] entries.
+    pub fn iter(&self) -> impl Iterator<Item = &CurrencyAssign> {
+        self.0.iter()
+    }
+
+    /// Gets the [Currency] assigned to the bit number, if any.
+    pub fn get(&self, bit_number: u8) -> Option<Currency> {
+        self.0
+            .iter()
+            .find(|assign| assign.bit_number() == bit_number)
+            .map(CurrencyAssign::currency)
+    }
+
+    /// Gets the bit number assigned to the [Currency], if any.
+    pub fn bit_number(&self, currency: &Currency) -> Option<u8> {
+        self.0
+            .iter()
+            .find(|assign| &assign.currency() == currency)
+            .map(CurrencyAssign::bit_number)
+    }
+
+    /// Finds the [CurrencyAssign] entry by the exact value in ISO 4217 minor units.
+    pub fn find_minor(&self, code: CurrencyCode, minor_value: u64) -> Option<&CurrencyAssign> {
+        self.0.iter().find(|assign| {
+            let currency = assign.currency();
+            currency.code() == code && currency.minor_value() == minor_value
+        })
+    }
+
+    /// Gets an iterator over the ISO 4217 currency codes in the table, in assignment order.
+    pub fn currency_codes(&self) -> impl Iterator<Item = CurrencyCode> + '_ {
+        self.0
+            .iter()
+            .map(|assign| assign.currency().code())
+            .enumerate()
+            .filter(|(i, code)| {
+                !self.0[..*i]
+                    .iter()
+                    .any(|assign| assign.currency().code() == *code)
+            })
+            .map(|(_, code)| code)
+    }
+
+    /// Creates a `Set` [DenominationDisableRequest] disabling the listed denominations, and
+    /// enabling all others.
+    ///
+    /// Fails with [Error::DenominationNotAssigned] if a denomination is missing from the table.
+    pub fn disable_request(&self, disabled: &[Currency]) -> Result<DenominationDisableRequest> {
+        if let Some(missing) = disabled
+            .iter()
+            .find(|currency| self.bit_number(currency).is_none())
+        {
+            return Err(Error::DenominationNotAssigned(missing.to_string()));
+        }
+
+        let mut table = DenominationTable::new();
+        for assign in self.0.iter() {
+            table.push(
+                DenominationTableEntry::from(assign)
+                    .with_disabled(disabled.contains(&assign.currency())),
+            );
+        }
+
+        table.to_denomination_disable_request()
+    }
+}
+
+impl From<&[CurrencyAssign]> for CurrencyTable {
+    fn from(val: &[CurrencyAssign]) -> Self {
+        Self(val.into())
+    }
+}
+
+impl From<&CurrencyAssignResponse> for CurrencyTable {
+    fn from(val: &CurrencyAssignResponse) -> Self {
+        val.currency_assign().into()
+    }
+}
+
+impl From<CurrencyAssignResponse> for CurrencyTable {
+    fn from(val: CurrencyAssignResponse) -> Self {
+        (&val).into()
+    }
+}
+
+impl fmt::Display for CurrencyTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[")?;
+        for (i, assign) in self.0.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{assign}")?;
+        }
+        write!(f, "]")
+    }
+}
+
+/// Represents the difference between two `Currency Assign` tables, e.g. before and after a
+/// firmware or banknote set update.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CurrencyTableChanged {
+    added: Vec<CurrencyAssign>,
+    removed: Vec<CurrencyAssign>,
+}
+
+impl CurrencyTableChanged {
+    /// Creates a new, empty [CurrencyTableChanged].
+    pub const fn new() -> Self {
+        Self {
+            added: Vec::new(),
+            removed: Vec::new(),
+        }
+    }
+
+    /// Compares the cached and the current `Currency Assign` tables.
+    ///
+    /// An entry whose bit number is assigned a different currency is reported as both removed
+    /// and added.
+    pub fn diff(cached: &[CurrencyAssign], current: &[CurrencyAssign]) -> Self {
+        Self {
+            added: current
+                .iter()
+                .filter(|assign| !cached.contains(assign))
+                .copied()
+                .collect(),
+            removed: cached
+                .iter()
+                .filter(|assign| !current.contains(assign))
+                .copied()
+                .collect(),
+        }
+    }
+
+    /// Gets the newly assigned entries.
+    pub fn added(&self) -> &[CurrencyAssign] {
+        self.added.as_ref()
+    }
+
+    /// Gets the entries no longer assigned.
+    pub fn removed(&self) -> &[CurrencyAssign] {
+        self.removed.as_ref()
+    }
+
+    /// Gets whether the tables are identical.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl fmt::Display for CurrencyTableChanged {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let write_list = |f: &mut fmt::Formatter<'_>, list: &[CurrencyAssign]| -> fmt::Result {
+            write!(f, "[")?;
+            for (i, assign) in list.iter().enumerate() {
+                if i != 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{assign}")?;
+            }
+            write!(f, "]")
+        };
+
+        write!(f, "{{")?;
+        write!(f, r#""added": "#)?;
+        write_list(f, self.added.as_ref())?;
+        write!(f, r#", "removed": "#)?;
+        write_list(f, self.removed.as_ref())?;
+        write!(f, "}}")
+    }
+}
+
+/// Blocks note acceptance after a `Currency Assign` table change, until the denomination
+/// policies are applied again.
+///
+/// Newly added denominations are enabled by default on most firmware, so resuming acceptance
+/// with the old policies would silently accept them. While a change is pending, the wrapped
+/// polling function refuses `Idle` requests. An acknowledged `Denomination Disable` set request
+/// through the wrapper, or an explicit call to [reapplied](Self::reapplied), clears the change.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CurrencyTableGuard {
+    pending: Option<CurrencyTableChanged>,
+}
+
+impl CurrencyTableGuard {
+    /// Creates a new [CurrencyTableGuard], with no pending change.
+    pub const fn new() -> Self {
+        Self { pending: None }
+    }
+
+    /// Gets the pending [CurrencyTableChanged], if any.
+    pub const fn pending(&self) -> Option<&CurrencyTableChanged> {
+        self.pending.as_ref()
+    }
+
+    /// Gets whether the denomination policies must be applied again before accepting notes.
+    pub const fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Records a [CurrencyTableChanged], ignoring empty changes.
+    pub fn on_change(&mut self, change: CurrencyTableChanged) {
+        if !change.is_empty() {
+            log::warn!(
+                "currency assign table changed, denomination policies must be re-applied: {change}"
+            );
+            self.pending = Some(change);
+        }
+    }
+
+    /// Marks the denomination policies as applied again, returning the cleared change.
+    pub fn reapplied(&mut self) -> Option<CurrencyTableChanged> {
+        self.pending.take()
+    }
+
+    /// Wraps a polling function, refusing `Idle` requests while a change is pending.
+    pub fn gate<'a, P>(&'a mut self, mut poll: P) -> impl FnMut(&Message) -> Result<Message> + 'a
+    where
+        P: FnMut(&Message) -> Result<Message> + 'a,
+    {
+        move |req: &Message| {
+            let data = req.data();
+            let code = data.message_code().request_code();
+
+            match (&self.pending, code) {
+                (Some(change), Ok(RequestCode::Idle)) => {
+                    log::warn!("refusing Idle request, denomination policies not re-applied");
+                    return Err(Error::CurrencyTableChanged(change.to_string()));
+                }
+                (Some(_), Ok(RequestCode::DenominationDisable))
+                    if data.message_type().request_type() == Ok(RequestType::SetFeature) =>
+                {
+                    let res = poll(req)?;
+
+                    if Response::try_from(&res).map(|r| r.code()) == Ok(ResponseCode::Ack) {
+                        log::info!("denomination policies re-applied");
+                        self.pending = None;
+                    }
+
+                    return Ok(res);
+                }
+                _ => (),
+            }
+
+            poll(req)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Currency, CurrencyCode, Denomination, DenominationDisableMode, DenominationDisableRequest,
+        IdleRequest,
+    };
+
+    fn assign(bit: u8, value: u64) -> CurrencyAssign {
+        CurrencyAssign::new().with_bit_number(bit).with_currency(
+            Currency::new()
+                .with_code(CurrencyCode::USD)
+                .with_denomination(Denomination::from_value(value)),
+        )
+    }
+
+    #[test]
+    fn test_currency_table() -> Result<()> {
+        let jpy = CurrencyAssign::new().with_bit_number(2).with_currency(
+            Currency::new()
+                .with_code(CurrencyCode::JPY)
+                .with_denomination(Denomination::from_value(1000)),
+        );
+        let table = CurrencyTable::from([assign(0, 20), assign(1, 100), jpy].as_ref());
+
+        assert_eq!(table.len(), 3);
+        assert_eq!(table.get(2), Some(jpy.currency()));
+        assert_eq!(table.get(3), None);
+        assert_eq!(table.bit_number(&"100 usd".parse()?), Some(1));
+        assert_eq!(
+            table.find_minor(CurrencyCode::USD, 10_000),
+            Some(&assign(1, 100))
+        );
+        assert_eq!(table.find_minor(CurrencyCode::JPY, 1000), Some(&jpy));
+        assert_eq!(table.find_minor(CurrencyCode::USD, 100), None);
+        assert_eq!(
+            table.currency_codes().collect::<Vec<CurrencyCode>>(),
+            [CurrencyCode::USD, CurrencyCode::JPY]
+        );
+
+        let req = table.disable_request(&["USD 100".parse()?, "JPY 1000".parse()?])?;
+        assert_eq!(req.mode(), DenominationDisableMode::Set);
+        let disabled: Vec<bool> = (0..3)
+            .map(|bit| req.denominations()[0].is_disabled(bit))
+            .collect();
+        assert_eq!(disabled, [false, true, true]);
+
+        assert_eq!(
+            table.disable_request(&["USD 50".parse()?]),
+            Err(Error::DenominationNotAssigned(r#""50 USD""#.into()))
+        );
+        assert!("USD".parse::<Currency>().is_err());
+        assert!("ABC 10".parse::<Currency>().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_currency_table_guard() -> Result<()> {
+        let cached = [assign(0, 1), assign(1, 5), assign(2, 10)];
+        let current = [assign(0, 1), assign(1, 5), assign(2, 20), assign(3, 50)];
+
+        let change = CurrencyTableChanged::diff(&cached, &current);
+        assert_eq!(change.added(), [assign(2, 20), assign(3, 50)]);
+        assert_eq!(change.removed(), [assign(2, 10)]);
+        assert!(CurrencyTableChanged::diff(&cached, &cached).is_empty());
+
+        let mut guard = CurrencyTableGuard::new();
+        guard.on_change(CurrencyTableChanged::new());
+        assert!(!guard.is_pending());
+
+        guard.on_change(change.clone());
+        assert_eq!(guard.pending(), Some(&change));
+
+        let ack = |req: &Message| -> Result<Message> {
+            Ok(Message::new().with_data(
+                req.data()
+                    .clone()
+                    .with_additional(&[ResponseCode::Ack.into()]),
+            ))
+        };
+
+        {
+            let mut poll = guard.gate(ack);
+
+            assert_eq!(
+                poll(&IdleRequest::new().into()),
+                Err(Error::CurrencyTableChanged(change.to_string()))
+            );
+            // reading the policies does not re-apply them
+            poll(&DenominationDisableRequest::new().into())?;
+            assert!(poll(&IdleRequest::new().into()).is_err());
+
+            poll(
+                &DenominationDisableRequest::new()
+                    .with_mode(DenominationDisableMode::Set)
+                    .into(),
+            )?;
+            assert!(poll(&IdleRequest::new().into()).is_ok());
+        }
+        assert!(!guard.is_pending());
+
+        guard.on_change(change.clone());
+        assert_eq!(guard.reapplied(), Some(change));
+
+        Ok(())
+    }
+}
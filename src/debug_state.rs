@@ -0,0 +1,309 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Mutex;
+
+use crate::{
+    redact, BillAcceptorState, ConfigPersistence, Error, EventCode, Interlock, InterlockCondition,
+    Message, PollObserver, Reconfigured, RequestCode, StateTracker,
+};
+
+/// Represents a request sent to the device, still waiting for its response.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct OutstandingRequest {
+    code: RequestCode,
+    attempt: usize,
+}
+
+impl OutstandingRequest {
+    /// Creates a new [OutstandingRequest] from the provided parameters.
+    pub const fn create(code: RequestCode, attempt: usize) -> Self {
+        Self { code, attempt }
+    }
+
+    /// Gets the [RequestCode] of the request.
+    pub const fn code(&self) -> RequestCode {
+        self.code
+    }
+
+    /// Gets the zero-based attempt of the request.
+    pub const fn attempt(&self) -> usize {
+        self.attempt
+    }
+}
+
+impl fmt::Display for OutstandingRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""code": {}, "#, self.code)?;
+        write!(f, r#""attempt": {}"#, self.attempt)?;
+        write!(f, "}}")
+    }
+}
+
+/// Represents a diagnostic snapshot of the host internals, formatted for bug reports.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DebugState {
+    state: BillAcceptorState,
+    outstanding: Vec<OutstandingRequest>,
+    queue_depths: BTreeMap<String, usize>,
+    last_events: Vec<(EventCode, Message)>,
+    state_diagnostics: usize,
+    reconnects: u64,
+    config_persistence: Option<ConfigPersistence>,
+    interlocks: Vec<InterlockCondition>,
+}
+
+impl DebugState {
+    /// Gets the tracked [BillAcceptorState].
+    pub const fn state(&self) -> BillAcceptorState {
+        self.state
+    }
+
+    /// Gets the list of [OutstandingRequest]s.
+    pub fn outstanding(&self) -> &[OutstandingRequest] {
+        self.outstanding.as_ref()
+    }
+
+    /// Gets the depth of the named queue, if recorded.
+    pub fn queue_depth(&self, name: &str) -> Option<usize> {
+        self.queue_depths.get(name).copied()
+    }
+
+    /// Gets the last event received for the [EventCode], if any.
+    pub fn last_event(&self, code: EventCode) -> Option<&Message> {
+        self.last_events
+            .iter()
+            .find(|(c, _)| *c == code)
+            .map(|(_, evt)| evt)
+    }
+
+    /// Gets the number of invalid state transitions recorded.
+    pub const fn state_diagnostics(&self) -> usize {
+        self.state_diagnostics
+    }
+
+    /// Gets the number of reconnects.
+    pub const fn reconnects(&self) -> u64 {
+        self.reconnects
+    }
+
+    /// Gets the last detected [ConfigPersistence], if any.
+    pub const fn config_persistence(&self) -> Option<ConfigPersistence> {
+        self.config_persistence
+    }
+
+    /// Gets the engaged [InterlockCondition]s.
+    pub fn interlocks(&self) -> &[InterlockCondition] {
+        self.interlocks.as_ref()
+    }
+}
+
+impl fmt::Display for DebugState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""state": {}, "#, self.state)?;
+        write!(f, r#""outstanding": ["#)?;
+        for (i, req) in self.outstanding.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{req}")?;
+        }
+        write!(f, r#"], "queue_depths": {{"#)?;
+        for (i, (name, depth)) in self.queue_depths.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, r#""{name}": {depth}"#)?;
+        }
+        write!(f, r#"}}, "last_events": {{"#)?;
+        for (i, (code, evt)) in self.last_events.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, r#""{}": {}"#, <&str>::from(code), redact(evt))?;
+        }
+        write!(f, "}}, ")?;
+        write!(f, r#""state_diagnostics": {}, "#, self.state_diagnostics)?;
+        write!(f, r#""reconnects": {}, "#, self.reconnects)?;
+        match self.config_persistence {
+            Some(persistence) => write!(f, r#""config_persistence": {persistence}"#)?,
+            None => write!(f, r#""config_persistence": null"#)?,
+        }
+        write!(f, r#", "interlocks": ["#)?;
+        for (i, cond) in self.interlocks.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{cond}")?;
+        }
+        write!(f, "]}}")
+    }
+}
+
+#[derive(Debug, Default)]
+struct DebugInner {
+    tracker: StateTracker,
+    outstanding: Vec<OutstandingRequest>,
+    last_events: Vec<(EventCode, Message)>,
+    reconnects: u64,
+    config_persistence: Option<ConfigPersistence>,
+}
+
+/// Collects the host internals reported in a [DebugState] snapshot.
+///
+/// Pass the [DebugMonitor] as the [PollObserver] of polled requests to track outstanding
+/// requests, and pass device events to [on_event](Self::on_event).
+#[derive(Debug, Default)]
+pub struct DebugMonitor {
+    inner: Mutex<DebugInner>,
+    interlock: Option<Interlock>,
+}
+
+impl DebugMonitor {
+    /// Creates a new [DebugMonitor].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder function that reports the engaged conditions of the [Interlock] in snapshots.
+    pub fn with_interlock(mut self, interlock: Interlock) -> Self {
+        self.interlock = Some(interlock);
+        self
+    }
+
+    /// Records a device event [Message].
+    pub fn on_event(&self, event: &Message) {
+        let code = match event.data().message_code().event_code() {
+            Ok(code) => code,
+            Err(_) => return,
+        };
+
+        self.with_inner(|inner| {
+            inner.tracker.on_event_code(code);
+
+            match inner.last_events.iter_mut().find(|(c, _)| *c == code) {
+                Some((_, evt)) => *evt = event.clone(),
+                None => inner.last_events.push((code, event.clone())),
+            }
+        });
+    }
+
+    /// Records a reconnect to the device.
+    pub fn on_reconnect(&self) {
+        self.with_inner(|inner| inner.reconnects = inner.reconnects.saturating_add(1));
+    }
+
+    /// Records a device reconfiguration after a `Power Up`.
+    pub fn on_reconfigured(&self, reconfigured: &Reconfigured) {
+        if let Some(persistence) = reconfigured.persistence() {
+            self.with_inner(|inner| inner.config_persistence = Some(persistence));
+        }
+    }
+
+    /// Takes a [DebugState] snapshot, including the provided named queue depths.
+    pub fn snapshot(&self, queue_depths: &[(&str, usize)]) -> DebugState {
+        self.with_inner(|inner| DebugState {
+            state: inner.tracker.state(),
+            outstanding: inner.outstanding.clone(),
+            queue_depths: queue_depths
+                .iter()
+                .map(|(name, depth)| ((*name).into(), *depth))
+                .collect(),
+            last_events: inner.last_events.clone(),
+            state_diagnostics: inner.tracker.diagnostics().count(),
+            reconnects: inner.reconnects,
+            config_persistence: inner.config_persistence,
+            interlocks: self
+                .interlock
+                .as_ref()
+                .map(Interlock::conditions)
+                .unwrap_or_default(),
+        })
+        .unwrap_or_default()
+    }
+
+    fn with_inner<T>(&self, f: impl FnOnce(&mut DebugInner) -> T) -> Option<T> {
+        self.inner.lock().ok().map(|mut inner| f(&mut inner))
+    }
+
+    fn remove_outstanding(&self, request: &Message) {
+        if let Ok(code) = request.data().message_code().request_code() {
+            self.with_inner(|inner| inner.outstanding.retain(|req| req.code() != code));
+        }
+    }
+}
+
+impl PollObserver for DebugMonitor {
+    fn on_request_sent(&self, request: &Message, attempt: usize) {
+        if let Ok(code) = request.data().message_code().request_code() {
+            self.with_inner(|inner| {
+                match inner.outstanding.iter_mut().find(|req| req.code() == code) {
+                    Some(req) => req.attempt = attempt,
+                    None => inner
+                        .outstanding
+                        .push(OutstandingRequest::create(code, attempt)),
+                }
+            });
+        }
+    }
+
+    fn on_response(&self, request: &Message, _response: &Message, _attempt: usize) {
+        self.remove_outstanding(request);
+    }
+
+    fn on_failure(&self, request: &Message, _err: &Error) {
+        self.remove_outstanding(request);
+    }
+
+    fn on_status(&self, status: &Message) {
+        self.with_inner(|inner| inner.tracker.on_message(status));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventType, MessageCode, MessageData, MessageType, StatusRequest};
+
+    #[test]
+    fn test_debug_state() {
+        let interlock = Interlock::new();
+        let monitor = DebugMonitor::new().with_interlock(interlock.clone());
+        let status = Message::from(StatusRequest::new());
+        let idle = Message::new().with_data(
+            MessageData::new()
+                .with_message_type(MessageType::Event(EventType::Sequence0))
+                .with_message_code(MessageCode::Event(EventCode::Idle)),
+        );
+
+        monitor.on_request_sent(&status, 0);
+        monitor.on_request_sent(&status, 1);
+        monitor.on_event(&idle);
+        monitor.on_reconnect();
+
+        let state = monitor.snapshot(&[("events", 3)]);
+        assert_eq!(state.state(), BillAcceptorState::Idle);
+        assert_eq!(
+            state.outstanding(),
+            [OutstandingRequest::create(RequestCode::Status, 1)]
+        );
+        assert_eq!(state.queue_depth("events"), Some(3));
+        assert_eq!(state.last_event(EventCode::Idle), Some(&idle));
+        assert_eq!(state.reconnects(), 1);
+        assert!(state
+            .to_string()
+            .contains(r#""queue_depths": {"events": 3}"#));
+
+        assert!(state.interlocks().is_empty());
+
+        monitor.on_response(&status, &status, 1);
+        assert!(monitor.snapshot(&[]).outstanding().is_empty());
+
+        interlock.engage(InterlockCondition::Tamper);
+        let state = monitor.snapshot(&[]);
+        assert_eq!(state.interlocks(), [InterlockCondition::Tamper]);
+        assert!(state.to_string().ends_with(r#""interlocks": ["tamper"]}"#));
+    }
+}
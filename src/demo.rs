@@ -0,0 +1,276 @@
+//! Scripted user flows for developing host UIs without hardware.
+//!
+//! A [DemoDriver] plays simple text commands against a [MockDevice], so front-end code polling
+//! the mock sees the same event stream as from a real device:
+//!
+//! Command                  | Effect
+//! -------------------------|-------------------------------------------
+//! `insert <value> [code]`  | customer inserts a note, `code` defaults to `USD`
+//! `jam`                    | the note transport jams
+//! `clear`                  | an operator clears the jam
+//! `quit`                   | stops the driver
+//!
+//! Commands are read from any [BufRead] source, e.g. stdin, or sent over a local channel. Empty
+//! lines and lines starting with `#` are ignored.
+//!
+//! ```
+//! use std::sync::Arc;
+//!
+//! use jcm::demo::{DemoCommand, DemoDriver};
+//! use jcm::mock::MockDevice;
+//! use jcm::{EventCode, IdleRequest};
+//!
+//! # fn main() -> jcm::Result<()> {
+//! let device = Arc::new(MockDevice::new());
+//! device.acknowledge_event(&jcm::event_ack(&device.pending_event().unwrap()))?;
+//! device.handle_request(&IdleRequest::new().into())?;
+//! device.acknowledge_event(&jcm::event_ack(&device.pending_event().unwrap()))?;
+//!
+//! let driver = DemoDriver::new(Arc::clone(&device));
+//! assert_eq!(driver.run("# kiosk flow\ninsert 20 USD\n".as_bytes())?, 1);
+//!
+//! let escrow = device.pending_event().unwrap();
+//! assert_eq!(escrow.data().message_code().event_code(), Ok(EventCode::Escrow));
+//!
+//! assert_eq!("jam".parse::<DemoCommand>()?, DemoCommand::Jam);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io::{self, BufRead};
+use std::str::FromStr;
+use std::sync::{mpsc, Arc};
+use std::{fmt, thread};
+
+use crate::mock::MockDevice;
+use crate::{Currency, CurrencyCode, Denomination, Error, FailureCode, Result};
+
+/// Represents the failure reported by a simulated jam.
+pub const DEMO_JAM_FAILURE: FailureCode = FailureCode::TransportMotor;
+
+/// Represents a scripted user action on a [MockDevice].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DemoCommand {
+    /// A customer inserts a note.
+    Insert(Currency),
+    /// The note transport jams.
+    Jam,
+    /// An operator clears the jam.
+    Clear,
+    /// Stops the [DemoDriver].
+    Quit,
+}
+
+impl DemoCommand {
+    /// Parses a [DemoCommand] from a line of text.
+    pub fn parse(line: &str) -> Result<Self> {
+        let cmd_err = || Error::InvalidDemoCommand(line.trim().into());
+        let mut fields = line.split_whitespace();
+
+        match fields.next().map(str::to_ascii_lowercase).as_deref() {
+            Some("insert") => {
+                let value = fields
+                    .next()
+                    .and_then(|v| v.parse::<u64>().ok())
+                    .ok_or_else(cmd_err)?;
+
+                let denomination = Denomination::from_value(value);
+                if !denomination.is_valid() || denomination.value() != value {
+                    return Err(cmd_err());
+                }
+
+                let code = match fields.next().map(str::to_ascii_uppercase) {
+                    None => CurrencyCode::USD,
+                    Some(code) => match CurrencyCode::from(code.as_str()) {
+                        CurrencyCode::XXX => return Err(cmd_err()),
+                        code => code,
+                    },
+                };
+
+                match fields.next() {
+                    None => Ok(Self::Insert(
+                        Currency::new()
+                            .with_code(code)
+                            .with_denomination(denomination),
+                    )),
+                    Some(_) => Err(cmd_err()),
+                }
+            }
+            Some("jam") if fields.next().is_none() => Ok(Self::Jam),
+            Some("clear") if fields.next().is_none() => Ok(Self::Clear),
+            Some("quit" | "exit") if fields.next().is_none() => Ok(Self::Quit),
+            _ => Err(cmd_err()),
+        }
+    }
+}
+
+impl FromStr for DemoCommand {
+    type Err = Error;
+
+    fn from_str(val: &str) -> Result<Self> {
+        Self::parse(val)
+    }
+}
+
+impl fmt::Display for DemoCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Insert(currency) => write!(f, r#"{{"insert": {currency}}}"#),
+            Self::Jam => write!(f, r#""jam""#),
+            Self::Clear => write!(f, r#""clear""#),
+            Self::Quit => write!(f, r#""quit""#),
+        }
+    }
+}
+
+/// Drives a shared [MockDevice] from [DemoCommand]s.
+#[derive(Clone, Debug)]
+pub struct DemoDriver {
+    device: Arc<MockDevice>,
+}
+
+impl DemoDriver {
+    /// Creates a new [DemoDriver] for the [MockDevice].
+    pub const fn new(device: Arc<MockDevice>) -> Self {
+        Self { device }
+    }
+
+    /// Gets a reference to the driven [MockDevice].
+    pub fn device(&self) -> &MockDevice {
+        self.device.as_ref()
+    }
+
+    /// Applies a [DemoCommand] to the [MockDevice].
+    ///
+    /// Returns `false` if the device refused the action, e.g. inserting a note while inhibited,
+    /// or for [Quit](DemoCommand::Quit).
+    pub fn execute(&self, command: &DemoCommand) -> bool {
+        let applied = match command {
+            DemoCommand::Insert(currency) => self.device.insert_note(*currency),
+            DemoCommand::Jam => self.device.jam(DEMO_JAM_FAILURE),
+            DemoCommand::Clear => self.device.clear_jam(),
+            DemoCommand::Quit => false,
+        };
+
+        if applied {
+            log::info!("demo command applied: {command}");
+        } else {
+            log::warn!(
+                "demo command refused: {command}, status: {}",
+                self.device.status()
+            );
+        }
+
+        applied
+    }
+
+    /// Reads and applies commands, one per line, until the end of input or a
+    /// [Quit](DemoCommand::Quit) command.
+    ///
+    /// Invalid commands are logged and skipped. Returns the number of applied commands.
+    pub fn run<R: BufRead>(&self, input: R) -> Result<usize> {
+        let mut applied = 0;
+
+        for line in input.lines() {
+            let line = line.map_err(|err| Error::Demo(format!("error reading command: {err}")))?;
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            match DemoCommand::parse(line) {
+                Ok(DemoCommand::Quit) => break,
+                Ok(command) => applied += usize::from(self.execute(&command)),
+                Err(err) => log::warn!("{err}"),
+            }
+        }
+
+        Ok(applied)
+    }
+
+    /// Spawns a thread applying commands read from stdin.
+    pub fn spawn_stdin(self) -> thread::JoinHandle<Result<usize>> {
+        thread::spawn(move || self.run(io::stdin().lock()))
+    }
+
+    /// Spawns a thread applying commands sent over the returned channel.
+    ///
+    /// The thread stops on a [Quit](DemoCommand::Quit) command, or once all senders are dropped,
+    /// returning the number of applied commands.
+    pub fn spawn_channel(self) -> (mpsc::Sender<DemoCommand>, thread::JoinHandle<usize>) {
+        let (tx, rx) = mpsc::channel::<DemoCommand>();
+
+        let handle = thread::spawn(move || {
+            rx.iter()
+                .take_while(|command| command != &DemoCommand::Quit)
+                .filter(|command| self.execute(command))
+                .count()
+        });
+
+        (tx, handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{event_ack, EventCode, IdleRequest, MajorMinorStatus, Message};
+
+    fn ack_next(device: &MockDevice) -> Result<EventCode> {
+        let event: Message = device.pending_event().unwrap();
+        device.acknowledge_event(&event_ack(&event))?;
+        event.data().message_code().event_code()
+    }
+
+    #[test]
+    fn test_demo_driver() -> Result<()> {
+        assert_eq!(
+            DemoCommand::parse("Insert 1000 jpy")?,
+            DemoCommand::Insert(
+                Currency::new()
+                    .with_code(CurrencyCode::JPY)
+                    .with_denomination(Denomination::from_value(1000))
+            )
+        );
+        for invalid in [
+            "",
+            "insert",
+            "insert ten",
+            "insert 10 ZZZ",
+            "insert 10 USD 2",
+            "jam 2",
+        ] {
+            assert_eq!(
+                DemoCommand::parse(invalid),
+                Err(Error::InvalidDemoCommand(invalid.into()))
+            );
+        }
+
+        let device = Arc::new(MockDevice::new());
+        ack_next(&device)?;
+        device.handle_request(&IdleRequest::new().into())?;
+        ack_next(&device)?;
+
+        let driver = DemoDriver::new(Arc::clone(&device));
+        let script = "insert 10\nbogus\njam\nclear\nquit\ninsert 5\n";
+        assert_eq!(driver.run(script.as_bytes())?, 3);
+
+        assert_eq!(ack_next(&device)?, EventCode::Escrow);
+        assert_eq!(ack_next(&device)?, EventCode::AcceptorFailure);
+        assert_eq!(ack_next(&device)?, EventCode::AcceptorClear);
+        assert_eq!(device.pending_event(), None);
+
+        let (tx, handle) = driver.spawn_channel();
+        tx.send(DemoCommand::Clear).ok();
+        tx.send(DemoCommand::Jam).ok();
+        drop(tx);
+        assert_eq!(handle.join().ok(), Some(1));
+        assert!(matches!(
+            device.status().major_minor_status(),
+            MajorMinorStatus::AbnormalFailure(DEMO_JAM_FAILURE)
+        ));
+
+        Ok(())
+    }
+}
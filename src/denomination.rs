@@ -1,4 +1,4 @@
-use std::{cmp, fmt, mem};
+use core::{cmp, fmt, mem};
 
 use crate::{Error, Result};
 
@@ -22,6 +22,7 @@ pub const DENOM_LEN: usize = 2;
 /// Any denomination above [`u8::MAX`] will have a non-zero exponent, e.g. `500 = 50 * 10^1`.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Denomination(u16);
 
 impl Denomination {
@@ -78,7 +79,7 @@ impl Denomination {
         match val {
             v if v <= u8::MAX as u64 => Self((val << 8) as u16),
             v if v % 10 == 0 => {
-                let exp = (val as f64).log10().floor() as u32;
+                let exp = val.ilog10();
                 let (int, exp) = match val.saturating_div(10u64.pow(exp)) {
                     i if i == 1 || i == 2 => (i * 100, exp - 2),
                     i if i == 5 || i == 25 => (i * 10, exp - 1),
@@ -129,7 +130,7 @@ impl Denomination {
     pub fn valid_value(val: u64) -> bool {
         [1, 2, 5, 10, 20, 50, 100, 200, 250]
             .into_iter()
-            .any(|v| val % v == 0 && (val <= 10 || val % 10 == 0))
+            .any(|v| val.is_multiple_of(v) && (val <= 10 || val.is_multiple_of(10)))
     }
 
     /// Writes the [Denomination] to a byte buffer.
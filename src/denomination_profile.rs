@@ -0,0 +1,350 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::{
+    poll_typed, Currency, CurrencyAssignRequest, CurrencyCode, CurrencyTable, Denomination,
+    DenominationDisable, DenominationDisableMode, DenominationDisableRequest,
+    DenominationDisableResponse, Error, Message, ResponseCode, Result,
+};
+
+/// Represents the denomination acceptance settings of a device, edited by currency and value.
+///
+/// The profile starts from the device settings, read with [query](Self::query), or a
+/// `Denomination Disable` response. Denominations are toggled by value, e.g.
+/// `profile.disable_value("USD", 100)`, or by bit number, and [request](Self::request) emits the
+/// `Set` request only when the settings changed.
+///
+/// The `Set` request replaces the whole table on the device, so the emitted request always
+/// carries every [DenominationDisable] item: diffing skips unchanged profiles, and
+/// [changes](Self::changes) reports the toggled denominations.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{
+///     Currency, CurrencyAssign, CurrencyCode, CurrencyTable, Denomination, DenominationProfile,
+///     DenominationDisableResponse,
+/// };
+///
+/// # fn main() -> jcm::Result<()> {
+/// let usd = |bit, value| {
+///     CurrencyAssign::new().with_bit_number(bit).with_currency(
+///         Currency::new()
+///             .with_code(CurrencyCode::USD)
+///             .with_denomination(Denomination::from_value(value)),
+///     )
+/// };
+/// let table = CurrencyTable::from([usd(0, 20), usd(1, 100)].as_ref());
+///
+/// let mut profile = DenominationProfile::create(table, &DenominationDisableResponse::new());
+/// assert!(profile.request()?.is_none());
+///
+/// profile.disable_value("USD", 100)?;
+/// assert_eq!(profile.changes(), [(1, true)]);
+///
+/// let req = profile.request()?.unwrap();
+/// assert!(req.denominations()[0].is_disabled(1));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DenominationProfile {
+    table: CurrencyTable,
+    current: BTreeSet<u8>,
+    disabled: BTreeSet<u8>,
+}
+
+impl DenominationProfile {
+    /// Creates a new [DenominationProfile] from the `Currency Assign` table and the current
+    /// `Denomination Disable` settings.
+    pub fn create(table: CurrencyTable, current: &DenominationDisableResponse) -> Self {
+        let current: BTreeSet<u8> = current
+            .denominations()
+            .iter()
+            .enumerate()
+            .flat_map(|(item, denoms)| {
+                (0..DenominationDisable::denom_len())
+                    .filter(|&idx| denoms.is_disabled(idx))
+                    .map(move |idx| (item * DenominationDisable::denom_len() + idx) as u8)
+            })
+            .collect();
+
+        Self {
+            table,
+            disabled: current.clone(),
+            current,
+        }
+    }
+
+    /// Queries the `Currency Assign` table and the current `Denomination Disable` settings
+    /// through the polling function.
+    pub fn query<F>(mut poll: F) -> Result<Self>
+    where
+        F: FnMut(&Message) -> Result<Message>,
+    {
+        let assign = poll_typed(CurrencyAssignRequest::new(), &mut poll)?;
+        Self::check_code(assign.code())?;
+
+        let current = poll_typed(DenominationDisableRequest::new(), &mut poll)?;
+        Self::check_code(current.code())?;
+
+        Ok(Self::create(CurrencyTable::from(&assign), &current))
+    }
+
+    /// Gets a reference to the [CurrencyTable] of the device.
+    pub const fn table(&self) -> &CurrencyTable {
+        &self.table
+    }
+
+    /// Gets whether the denomination at the bit number is disabled.
+    pub fn is_disabled(&self, bit_number: u8) -> bool {
+        self.disabled.contains(&bit_number)
+    }
+
+    /// Gets an iterator over the disabled bit numbers, in ascending order.
+    pub fn disabled(&self) -> impl Iterator<Item = u8> + '_ {
+        self.disabled.iter().copied()
+    }
+
+    /// Disables the denomination at the bit number.
+    pub fn disable_index(&mut self, bit_number: u8) -> Result<()> {
+        self.set_index(bit_number, true)
+    }
+
+    /// Enables the denomination at the bit number.
+    pub fn enable_index(&mut self, bit_number: u8) -> Result<()> {
+        self.set_index(bit_number, false)
+    }
+
+    /// Disables the assigned denomination, e.g. `disable_value("USD", 100)`.
+    pub fn disable_value(&mut self, code: &str, value: u64) -> Result<()> {
+        let bit = self.find_value(code, value)?;
+        self.set_index(bit, true)
+    }
+
+    /// Enables the assigned denomination, e.g. `enable_value("USD", 100)`.
+    pub fn enable_value(&mut self, code: &str, value: u64) -> Result<()> {
+        let bit = self.find_value(code, value)?;
+        self.set_index(bit, false)
+    }
+
+    /// Enables every assigned denomination.
+    pub fn enable_all(&mut self) {
+        self.disabled.clear();
+    }
+
+    /// Disables every assigned denomination.
+    pub fn disable_all(&mut self) {
+        self.disabled = self.table.iter().map(|a| a.bit_number()).collect();
+    }
+
+    /// Applies a preset, enabling only the listed denominations, and disabling all others.
+    pub fn only(&mut self, enabled: &[Currency]) -> Result<()> {
+        if let Some(missing) = enabled.iter().find(|c| self.table.bit_number(c).is_none()) {
+            return Err(Error::DenominationNotAssigned(missing.to_string()));
+        }
+
+        self.disabled = self
+            .table
+            .iter()
+            .filter(|assign| !enabled.contains(&assign.currency()))
+            .map(|assign| assign.bit_number())
+            .collect();
+
+        Ok(())
+    }
+
+    /// Gets the denominations toggled since the device settings were read, as
+    /// `(bit number, disabled)` pairs in ascending bit order.
+    pub fn changes(&self) -> Vec<(u8, bool)> {
+        self.current
+            .symmetric_difference(&self.disabled)
+            .map(|&bit| (bit, self.disabled.contains(&bit)))
+            .collect()
+    }
+
+    /// Gets whether any denomination was toggled since the device settings were read.
+    pub fn is_changed(&self) -> bool {
+        self.current != self.disabled
+    }
+
+    /// Creates the `Set` [DenominationDisableRequest] for the profile.
+    ///
+    /// Returns `None` when no denomination was toggled.
+    pub fn request(&self) -> Result<Option<DenominationDisableRequest>> {
+        if !self.is_changed() {
+            return Ok(None);
+        }
+
+        let denom_len = DenominationDisable::denom_len();
+        let max_bit = self
+            .table
+            .iter()
+            .map(|assign| assign.bit_number())
+            .chain(self.current.iter().copied())
+            .chain(self.disabled.iter().copied())
+            .max()
+            .unwrap_or(0) as usize;
+
+        let mut denoms = vec![DenominationDisable::new(); max_bit / denom_len + 1];
+        for &bit in self.disabled.iter() {
+            let bit = bit as usize;
+            denoms[bit / denom_len].disable(bit % denom_len);
+        }
+
+        DenominationDisableRequest::new()
+            .with_mode(DenominationDisableMode::Set)
+            .with_denominations(denoms.as_ref())
+            .map(Some)
+    }
+
+    /// Sends the `Set` request through the polling function, if any denomination was toggled.
+    ///
+    /// Returns whether a request was sent. Once acknowledged, the profile becomes the current
+    /// device settings.
+    pub fn apply<F>(&mut self, mut poll: F) -> Result<bool>
+    where
+        F: FnMut(&Message) -> Result<Message>,
+    {
+        match self.request()? {
+            Some(req) => {
+                let res = poll_typed(req, &mut poll)?;
+                Self::check_code(res.code())?;
+
+                self.current = self.disabled.clone();
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    fn set_index(&mut self, bit_number: u8, disable: bool) -> Result<()> {
+        let max_denom = DenominationDisableRequest::max_denom();
+
+        if bit_number as usize > max_denom {
+            Err(Error::InvalidDenominationLen((
+                bit_number as usize,
+                max_denom,
+            )))
+        } else {
+            if disable {
+                self.disabled.insert(bit_number);
+            } else {
+                self.disabled.remove(&bit_number);
+            }
+            Ok(())
+        }
+    }
+
+    fn find_value(&self, code: &str, value: u64) -> Result<u8> {
+        let currency = Currency::new()
+            .with_code(CurrencyCode::from(code.to_ascii_uppercase().as_str()))
+            .with_denomination(Denomination::from_value(value));
+
+        self.table
+            .bit_number(&currency)
+            .ok_or(Error::DenominationNotAssigned(format!("{value} {code}")))
+    }
+
+    fn check_code(code: ResponseCode) -> Result<()> {
+        match code {
+            ResponseCode::Ack => Ok(()),
+            code => Err(Error::InvalidResponseCode(code.into())),
+        }
+    }
+}
+
+impl fmt::Display for DenominationProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""table": {}, "#, self.table)?;
+        write!(f, r#""disabled": ["#)?;
+        for (i, bit) in self.disabled.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{bit}")?;
+        }
+        write!(f, r#"], "changed": {}"#, self.is_changed())?;
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CurrencyAssign, CurrencyAssignResponse, RequestCode, RequestType};
+
+    fn assign(bit: u8, value: u64) -> CurrencyAssign {
+        CurrencyAssign::new().with_bit_number(bit).with_currency(
+            Currency::new()
+                .with_code(CurrencyCode::USD)
+                .with_denomination(Denomination::from_value(value)),
+        )
+    }
+
+    #[test]
+    fn test_denomination_profile() -> Result<()> {
+        let assigns = [assign(0, 1), assign(1, 5), assign(2, 20), assign(17, 100)];
+        let mut sent = Vec::new();
+
+        let mut poll = |req: &Message| -> Result<Message> {
+            let data = req.data();
+            let additional = match (
+                data.message_code().request_code()?,
+                data.message_type().request_type()?,
+            ) {
+                (RequestCode::CurrencyAssign, _) => {
+                    let res = CurrencyAssignResponse::new()
+                        .with_code(ResponseCode::Ack)
+                        .with_currency_assign(assigns.as_ref());
+                    let mut buf = vec![0u8; res.len()];
+                    res.to_bytes(&mut buf)?;
+                    buf
+                }
+                (RequestCode::DenominationDisable, RequestType::Status) => {
+                    DenominationDisableResponse::new()
+                        .with_code(ResponseCode::Ack)
+                        .with_denominations(&[DenominationDisable::create(0b10)])
+                        .into_bytes()
+                }
+                _ => {
+                    sent.push(DenominationDisableRequest::try_from(req)?);
+                    vec![ResponseCode::Ack.into()]
+                }
+            };
+
+            Ok(Message::new().with_data(data.clone().with_additional(&additional)))
+        };
+
+        let mut profile = DenominationProfile::query(&mut poll)?;
+        assert!(profile.is_disabled(1));
+        assert!(!profile.apply(&mut poll)?);
+
+        profile.disable_value("usd", 100)?;
+        profile.enable_index(1)?;
+        assert_eq!(profile.changes(), [(1, false), (17, true)]);
+        assert_eq!(
+            profile.disable_value("USD", 50),
+            Err(Error::DenominationNotAssigned("50 USD".into()))
+        );
+
+        assert!(profile.apply(&mut poll)?);
+        assert!(!profile.is_changed());
+        assert_eq!(sent.len(), 1);
+        assert_eq!(
+            sent[0].denominations(),
+            [
+                DenominationDisable::new(),
+                DenominationDisable::create(0b10)
+            ]
+        );
+
+        profile.only(&["USD 20".parse()?, "USD 100".parse()?])?;
+        assert_eq!(profile.disabled().collect::<Vec<u8>>(), [0, 1]);
+        profile.disable_all();
+        assert_eq!(profile.disabled().count(), 4);
+
+        Ok(())
+    }
+}
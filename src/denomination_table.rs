@@ -0,0 +1,391 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use crate::{
+    Currency, CurrencyAssign, CurrencyAssignResponse, CurrencyCode, Denomination,
+    DenominationDisable, DenominationDisableMode, DenominationDisableRequest, Error, Result,
+};
+
+/// Represents the header row used for [DenominationTable] CSV files.
+pub const DENOMINATION_TABLE_CSV_HEADER: &str = "bit_number,currency,denomination,disabled";
+
+const CSV_SEPARATOR: char = ',';
+const CSV_COMMENT: char = '#';
+
+/// Represents a single row of a [DenominationTable].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DenominationTableEntry {
+    bit: u8,
+    currency: Currency,
+    disabled: bool,
+}
+
+impl DenominationTableEntry {
+    /// Creates a new [DenominationTableEntry].
+    pub const fn new() -> Self {
+        Self {
+            bit: 0,
+            currency: Currency::new(),
+            disabled: false,
+        }
+    }
+
+    /// Creates a new [DenominationTableEntry] from the provided parameters.
+    pub const fn create(bit: u8, currency: Currency, disabled: bool) -> Self {
+        Self {
+            bit,
+            currency,
+            disabled,
+        }
+    }
+
+    /// Gets the bit number of the [DenominationTableEntry].
+    pub const fn bit_number(&self) -> u8 {
+        self.bit
+    }
+
+    /// Gets the [Currency] of the [DenominationTableEntry].
+    pub const fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Gets whether the denomination is disabled.
+    pub const fn disabled(&self) -> bool {
+        self.disabled
+    }
+
+    /// Sets whether the denomination is disabled.
+    pub fn set_disabled(&mut self, val: bool) {
+        self.disabled = val;
+    }
+
+    /// Builder function that sets whether the denomination is disabled.
+    pub fn with_disabled(mut self, val: bool) -> Self {
+        self.set_disabled(val);
+        self
+    }
+
+    /// Converts the [DenominationTableEntry] into a CSV record (without a line terminator).
+    pub fn to_csv(&self) -> String {
+        format!(
+            "{},{},{},{}",
+            self.bit,
+            <&str>::from(self.currency.code()),
+            self.currency.denomination().value(),
+            self.disabled
+        )
+    }
+
+    /// Parses a CSV record into a [DenominationTableEntry].
+    ///
+    /// The `disabled` column is optional, and defaults to `false`.
+    pub fn from_csv(line: usize, record: &str) -> Result<Self> {
+        let fields: Vec<&str> = record.split(CSV_SEPARATOR).map(str::trim).collect();
+        let csv_err = |err: String| Error::InvalidCsvRecord((line, err));
+
+        if !(3..=4).contains(&fields.len()) {
+            return Err(csv_err(format!(
+                "expected 3 or 4 fields, have: {}",
+                fields.len()
+            )));
+        }
+
+        let bit = fields[0]
+            .parse::<u8>()
+            .map_err(|err| csv_err(format!("bit number: {err}")))?;
+
+        let code = match CurrencyCode::from(fields[1].to_ascii_uppercase().as_str()) {
+            CurrencyCode::XXX => return Err(csv_err(format!("currency: {}", fields[1]))),
+            code => code,
+        };
+
+        let value = fields[2]
+            .parse::<u64>()
+            .map_err(|err| csv_err(format!("denomination: {err}")))?;
+        let denomination = Denomination::from_value(value);
+        if !denomination.is_valid() || denomination.value() != value {
+            return Err(csv_err(format!("denomination: {value}")));
+        }
+
+        let disabled = match fields.get(3).map(|f| f.to_ascii_lowercase()) {
+            None => false,
+            Some(f) if f.is_empty() || f == "false" || f == "0" || f == "no" => false,
+            Some(f) if f == "true" || f == "1" || f == "yes" => true,
+            Some(f) => return Err(csv_err(format!("disabled: {f}"))),
+        };
+
+        Ok(Self {
+            bit,
+            currency: Currency::new()
+                .with_code(code)
+                .with_denomination(denomination),
+            disabled,
+        })
+    }
+}
+
+impl Default for DenominationTableEntry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&CurrencyAssign> for DenominationTableEntry {
+    fn from(val: &CurrencyAssign) -> Self {
+        Self::create(val.bit_number(), val.currency(), false)
+    }
+}
+
+impl From<CurrencyAssign> for DenominationTableEntry {
+    fn from(val: CurrencyAssign) -> Self {
+        (&val).into()
+    }
+}
+
+impl From<&DenominationTableEntry> for CurrencyAssign {
+    fn from(val: &DenominationTableEntry) -> Self {
+        Self::new()
+            .with_bit_number(val.bit)
+            .with_currency(val.currency)
+    }
+}
+
+impl From<DenominationTableEntry> for CurrencyAssign {
+    fn from(val: DenominationTableEntry) -> Self {
+        (&val).into()
+    }
+}
+
+impl fmt::Display for DenominationTableEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""bit_number": {}, "#, self.bit)?;
+        write!(f, r#""currency": {}, "#, self.currency)?;
+        write!(f, r#""disabled": {}"#, self.disabled)?;
+        write!(f, "}}")
+    }
+}
+
+/// Represents a reviewable table of denomination assignments and their acceptance settings.
+///
+/// The table can be exported from a [CurrencyAssignResponse], edited as a CSV file, and converted
+/// back into a [DenominationDisableRequest] for provisioning.
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DenominationTable(Vec<DenominationTableEntry>);
+
+impl DenominationTable {
+    /// Creates a new [DenominationTable].
+    pub const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Gets a reference to the list of [DenominationTableEntry] items.
+    pub fn items(&self) -> &[DenominationTableEntry] {
+        self.0.as_ref()
+    }
+
+    /// Gets the number of [DenominationTableEntry] items.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Gets whether the [DenominationTable] is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Pushes a [DenominationTableEntry] onto the [DenominationTable].
+    pub fn push(&mut self, val: DenominationTableEntry) {
+        self.0.push(val);
+    }
+
+    /// Gets an iterator over the [DenominationTableEntry] items.
+    pub fn iter(&self) -> impl Iterator<Item = &DenominationTableEntry> {
+        self.0.iter()
+    }
+
+    /// Converts the [DenominationTable] into CSV text, including a header row.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(DENOMINATION_TABLE_CSV_HEADER);
+        csv.push('\n');
+
+        for entry in self.0.iter() {
+            csv.push_str(entry.to_csv().as_str());
+            csv.push('\n');
+        }
+
+        csv
+    }
+
+    /// Parses CSV text into a [DenominationTable].
+    ///
+    /// The header row is optional. Blank lines, and lines starting with `#`, are ignored.
+    pub fn from_csv(csv: &str) -> Result<Self> {
+        let mut table = Self::new();
+
+        for (i, record) in csv.lines().enumerate() {
+            let record = record.trim();
+            let line = i + 1;
+
+            if record.is_empty()
+                || record.starts_with(CSV_COMMENT)
+                || record.eq_ignore_ascii_case(DENOMINATION_TABLE_CSV_HEADER)
+            {
+                continue;
+            }
+
+            let entry = DenominationTableEntry::from_csv(line, record)?;
+
+            if table.iter().any(|e| e.bit == entry.bit) {
+                return Err(Error::InvalidCsvRecord((
+                    line,
+                    format!("duplicate bit number: {}", entry.bit),
+                )));
+            }
+
+            table.push(entry);
+        }
+
+        Ok(table)
+    }
+
+    /// Gets the list of [CurrencyAssign] items for the [DenominationTable].
+    pub fn currency_assign(&self) -> Vec<CurrencyAssign> {
+        self.0.iter().map(CurrencyAssign::from).collect()
+    }
+
+    /// Converts the [DenominationTable] into a `Set` [DenominationDisableRequest].
+    pub fn to_denomination_disable_request(&self) -> Result<DenominationDisableRequest> {
+        let denom_len = DenominationDisable::denom_len();
+        let items = self
+            .0
+            .iter()
+            .map(|e| e.bit as usize / denom_len + 1)
+            .max()
+            .unwrap_or(0);
+
+        let mut denoms = vec![DenominationDisable::new(); items];
+        for entry in self.0.iter().filter(|e| e.disabled) {
+            let bit = entry.bit as usize;
+            denoms[bit / denom_len].disable(bit % denom_len);
+        }
+
+        DenominationDisableRequest::new()
+            .with_mode(DenominationDisableMode::Set)
+            .with_denominations(denoms.as_ref())
+    }
+}
+
+impl From<&CurrencyAssignResponse> for DenominationTable {
+    fn from(val: &CurrencyAssignResponse) -> Self {
+        Self(
+            val.currency_assign()
+                .iter()
+                .map(DenominationTableEntry::from)
+                .collect(),
+        )
+    }
+}
+
+impl From<CurrencyAssignResponse> for DenominationTable {
+    fn from(val: CurrencyAssignResponse) -> Self {
+        (&val).into()
+    }
+}
+
+impl From<&[DenominationTableEntry]> for DenominationTable {
+    fn from(val: &[DenominationTableEntry]) -> Self {
+        Self(val.into())
+    }
+}
+
+impl fmt::Display for DenominationTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[")?;
+        for (i, entry) in self.0.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{entry}")?;
+        }
+        write!(f, "]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ResponseCode;
+
+    #[test]
+    fn test_denomination_table_csv() -> Result<()> {
+        let res = CurrencyAssignResponse::new()
+            .with_code(ResponseCode::Ack)
+            .with_currency_assign(&[
+                CurrencyAssign::new().with_bit_number(0).with_currency(
+                    Currency::new()
+                        .with_code(CurrencyCode::USD)
+                        .with_denomination(Denomination::from_value(1)),
+                ),
+                CurrencyAssign::new().with_bit_number(1).with_currency(
+                    Currency::new()
+                        .with_code(CurrencyCode::USD)
+                        .with_denomination(Denomination::from_value(100)),
+                ),
+                CurrencyAssign::new().with_bit_number(17).with_currency(
+                    Currency::new()
+                        .with_code(CurrencyCode::JPY)
+                        .with_denomination(Denomination::from_value(10000)),
+                ),
+            ]);
+
+        let exp_csv = "bit_number,currency,denomination,disabled\n\
+                       0,USD,1,false\n\
+                       1,USD,100,false\n\
+                       17,JPY,10000,false\n";
+
+        assert_eq!(res.to_csv(), exp_csv);
+
+        let table = DenominationTable::from_csv(exp_csv)?;
+        assert_eq!(table.currency_assign(), res.currency_assign());
+        assert_eq!(table.to_csv(), exp_csv);
+
+        let edited = "# reviewed by operations\n\n0, usd, 1\n1,USD,100,true\n17,JPY,10000,yes\n";
+        let table = DenominationTable::from_csv(edited)?;
+
+        assert!(!table.items()[0].disabled());
+        assert!(table.items()[1].disabled());
+        assert!(table.items()[2].disabled());
+
+        let req = table.to_denomination_disable_request()?;
+        assert_eq!(req.mode(), DenominationDisableMode::Set);
+        assert_eq!(
+            req.denominations(),
+            [
+                DenominationDisable::create(0b10),
+                DenominationDisable::create(0b10)
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_denomination_table_csv_invalid() {
+        for (csv, line) in [
+            ("0,USD", 1),
+            ("bit_number,currency,denomination,disabled\n\nx,USD,1", 3),
+            ("0,ZZZ,1", 1),
+            ("0,USD,3", 1),
+            ("0,USD,1,maybe", 1),
+            ("0,USD,1\n0,USD,5", 2),
+        ] {
+            match DenominationTable::from_csv(csv) {
+                Err(Error::InvalidCsvRecord((have, _))) => assert_eq!(have, line),
+                res => panic!("unexpected result: {res:?}"),
+            }
+        }
+    }
+}
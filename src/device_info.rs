@@ -0,0 +1,270 @@
+use std::collections::{hash_map::Entry, HashMap};
+use std::fmt;
+
+use crate::{
+    CurrencyAssign, CurrencyAssignRequest, CurrencyAssignResponse, CurrencyTableChanged, Error,
+    FirmwareVersion, Message, ModelName, ModelNameRequest, ModelNameResponse, Response,
+    ResponseCode, Result, VersionRequest, VersionResponse,
+};
+
+/// Represents the immutable identity of a device, queried once per serial number.
+///
+/// The serial number is the host-side identity of the device (e.g. the USB descriptor serial),
+/// not the note serial number image returned by the `SerialNumber` request.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceInfo {
+    serial: String,
+    model_name: ModelName,
+    firmware_version: FirmwareVersion,
+    currency_assign: Vec<CurrencyAssign>,
+}
+
+impl DeviceInfo {
+    /// Creates a new [DeviceInfo] from the provided parameters.
+    pub fn create(
+        serial: &str,
+        model_name: ModelName,
+        firmware_version: FirmwareVersion,
+        currency_assign: &[CurrencyAssign],
+    ) -> Self {
+        Self {
+            serial: serial.into(),
+            model_name,
+            firmware_version,
+            currency_assign: currency_assign.into(),
+        }
+    }
+
+    /// Queries the `ModelName`, `Version`, and `CurrencyAssign` of the device through the
+    /// polling function.
+    ///
+    /// Fails if any request is not acknowledged by the device.
+    pub fn query<F>(serial: &str, mut poll: F) -> Result<Self>
+    where
+        F: FnMut(&Message) -> Result<Message>,
+    {
+        let mut request = |req: Message| -> Result<Response> {
+            let res = Response::try_from(&poll(&req)?)?;
+
+            match res.code() {
+                ResponseCode::Ack => Ok(res),
+                code => Err(Error::InvalidResponseCode(code.into())),
+            }
+        };
+
+        let model_name = ModelNameResponse::try_from(&request(ModelNameRequest::new().into())?)?;
+        let version = VersionResponse::try_from(&request(VersionRequest::new().into())?)?;
+        let currency_assign =
+            CurrencyAssignResponse::try_from(&request(CurrencyAssignRequest::new().into())?)?;
+
+        Ok(Self::create(
+            serial,
+            model_name.model_name().clone(),
+            version.firmware_version().clone(),
+            currency_assign.currency_assign(),
+        ))
+    }
+
+    /// Gets the serial number of the device.
+    pub fn serial(&self) -> &str {
+        self.serial.as_str()
+    }
+
+    /// Gets a reference to the [ModelName] of the device.
+    pub const fn model_name(&self) -> &ModelName {
+        &self.model_name
+    }
+
+    /// Gets a reference to the [FirmwareVersion] of the device.
+    pub const fn firmware_version(&self) -> &FirmwareVersion {
+        &self.firmware_version
+    }
+
+    /// Gets a reference to the list of [CurrencyAssign] entries of the device.
+    pub fn currency_assign(&self) -> &[CurrencyAssign] {
+        self.currency_assign.as_ref()
+    }
+}
+
+impl fmt::Display for DeviceInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""serial": "{}", "#, self.serial)?;
+        write!(f, r#""model_name": {}, "#, self.model_name)?;
+        write!(f, r#""firmware_version": {}, "#, self.firmware_version)?;
+        write!(f, r#""currency_assign": ["#)?;
+        for (i, assign) in self.currency_assign.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{assign}")?;
+        }
+        write!(f, "]}}")
+    }
+}
+
+/// Caches [DeviceInfo] by serial number, so reconnecting to a known device skips the
+/// immutable queries.
+///
+/// After a firmware update, call [invalidate_cache](Self::invalidate_cache) so the next
+/// reconnect queries the device again.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceInfoCache {
+    entries: HashMap<String, DeviceInfo>,
+}
+
+impl DeviceInfoCache {
+    /// Creates a new, empty [DeviceInfoCache].
+    pub fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Gets the number of cached devices.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Gets whether the [DeviceInfoCache] is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Gets the cached [DeviceInfo] for the serial number, if present.
+    pub fn get(&self, serial: &str) -> Option<&DeviceInfo> {
+        self.entries.get(serial)
+    }
+
+    /// Inserts a [DeviceInfo] into the cache, returning any previous entry for the serial number.
+    pub fn insert(&mut self, info: DeviceInfo) -> Option<DeviceInfo> {
+        self.entries.insert(info.serial().into(), info)
+    }
+
+    /// Gets the cached [DeviceInfo] for the serial number, or queries the device through the
+    /// polling function on a cache miss.
+    pub fn get_or_query<F>(&mut self, serial: &str, poll: F) -> Result<&DeviceInfo>
+    where
+        F: FnMut(&Message) -> Result<Message>,
+    {
+        match self.entries.entry(serial.into()) {
+            Entry::Occupied(entry) => {
+                log::debug!("using cached device info for serial: {serial}");
+                Ok(entry.into_mut())
+            }
+            Entry::Vacant(entry) => {
+                let info = DeviceInfo::query(serial, poll)?;
+                log::debug!("caching device info: {info}");
+                Ok(entry.insert(info))
+            }
+        }
+    }
+
+    /// Queries the device again, e.g. after a firmware or banknote set update, and replaces the
+    /// cached [DeviceInfo].
+    ///
+    /// Returns the [CurrencyTableChanged] between the cached and the new `Currency Assign`
+    /// tables, or `None` if the device was not cached. Pass a non-empty change to a
+    /// [CurrencyTableGuard](crate::CurrencyTableGuard), so the denomination policies are applied
+    /// again before accepting notes.
+    pub fn refresh<F>(&mut self, serial: &str, poll: F) -> Result<Option<CurrencyTableChanged>>
+    where
+        F: FnMut(&Message) -> Result<Message>,
+    {
+        let info = DeviceInfo::query(serial, poll)?;
+        log::debug!("refreshed device info: {info}");
+
+        Ok(self.insert(info.clone()).map(|cached| {
+            CurrencyTableChanged::diff(cached.currency_assign(), info.currency_assign())
+        }))
+    }
+
+    /// Removes the cached [DeviceInfo] for the serial number, e.g. after a firmware update.
+    pub fn invalidate_cache(&mut self, serial: &str) -> Option<DeviceInfo> {
+        self.entries.remove(serial)
+    }
+
+    /// Removes every cached [DeviceInfo].
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CurrencyCode, RequestCode};
+
+    #[test]
+    fn test_device_info_cache() -> Result<()> {
+        let model_name = ModelName::from_string("iVIZION");
+        let firmware_version = FirmwareVersion::new().with_version("1.00");
+
+        let mut queries = 0usize;
+        let mut poll = |req: &Message| -> Result<Message> {
+            queries += 1;
+
+            match req.data().message_code().request_code()? {
+                RequestCode::ModelName => Ok(ModelNameResponse::new()
+                    .with_code(ResponseCode::Ack)
+                    .with_model_name(ModelName::from_string("iVIZION"))
+                    .into()),
+                RequestCode::Version => Ok(VersionResponse::new()
+                    .with_code(ResponseCode::Ack)
+                    .with_firmware_version(FirmwareVersion::new().with_version("1.00"))
+                    .into()),
+                _ => Ok(
+                    Message::new().with_data(req.data().clone().with_additional(&[
+                        ResponseCode::Ack.into(),
+                        0,
+                        b'J',
+                        b'P',
+                        b'Y',
+                        1,
+                        0,
+                    ])),
+                ),
+            }
+        };
+
+        let mut cache = DeviceInfoCache::new();
+        let info = cache.get_or_query("A000001", &mut poll)?.clone();
+
+        assert_eq!(info.model_name(), &model_name);
+        assert_eq!(info.firmware_version(), &firmware_version);
+        assert_eq!(info.currency_assign().len(), 1);
+        assert_eq!(
+            info.currency_assign()[0].currency().code(),
+            CurrencyCode::JPY
+        );
+
+        // reconnecting with the same serial skips the queries
+        cache.get_or_query("A000001", &mut poll)?;
+        cache.get_or_query("A000002", &mut poll)?;
+        assert_eq!(cache.len(), 2);
+
+        // firmware updates invalidate the cached identity
+        assert_eq!(cache.invalidate_cache("A000001"), Some(info));
+        assert!(cache.get("A000001").is_none());
+        cache.get_or_query("A000001", &mut poll)?;
+
+        let nak = |_: &Message| -> Result<Message> {
+            Ok(ModelNameResponse::new().with_code(ResponseCode::Nak).into())
+        };
+        assert!(cache.get_or_query("A000003", nak).is_err());
+        assert!(cache.get("A000003").is_none());
+
+        // refreshing a cached device reports the currency table changes
+        assert_eq!(
+            cache.refresh("A000001", &mut poll)?,
+            Some(CurrencyTableChanged::new())
+        );
+        assert_eq!(cache.refresh("A000004", &mut poll)?, None);
+
+        assert_eq!(queries, 15);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,167 @@
+use std::fmt;
+
+use crate::{
+    DeviceStatus, Error, IdleRequest, InhibitRequest, InhibitResponse, MajorMinorStatus, Message,
+    MessageData, Response, ResponseCode, Result, StatusRequest, StatusResponse,
+};
+
+/// Represents whether the whole device accepts notes.
+///
+/// The device is inhibited with an `Inhibit` request, and enabled again with an `Idle` request.
+///
+/// The protocol has no power-saving mode: inhibit the device to idle it, e.g. overnight, instead
+/// of power cycling it.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DeviceInhibit {
+    /// The device refuses notes.
+    #[default]
+    Inhibited,
+    /// The device accepts notes.
+    Enabled,
+}
+
+impl DeviceInhibit {
+    /// Creates a new [DeviceInhibit].
+    pub const fn new() -> Self {
+        Self::Inhibited
+    }
+
+    /// Gets the [DeviceInhibit] from the [DeviceStatus].
+    ///
+    /// The device is enabled while the acceptor function is idle, or processing a note. Every
+    /// other status, e.g. power up, or failures, counts as inhibited.
+    pub const fn from_status(status: DeviceStatus) -> Self {
+        match status.major_minor_status() {
+            MajorMinorStatus::NormalIdle
+            | MajorMinorStatus::NormalActive
+            | MajorMinorStatus::NormalEscrow
+            | MajorMinorStatus::NormalVendValid
+            | MajorMinorStatus::NormalRejected
+            | MajorMinorStatus::NormalReturned
+            | MajorMinorStatus::NormalCollected
+            | MajorMinorStatus::NormalInsert
+            | MajorMinorStatus::NormalConditionalVend
+            | MajorMinorStatus::NormalPause
+            | MajorMinorStatus::NormalResume => Self::Enabled,
+            _ => Self::Inhibited,
+        }
+    }
+
+    /// Creates the request [Message] that sets the [DeviceInhibit].
+    pub fn request(&self, uid: u8) -> Message {
+        let data = match self {
+            Self::Inhibited => MessageData::from(InhibitRequest::new()),
+            Self::Enabled => MessageData::from(IdleRequest::new()),
+        };
+
+        data.with_uid(uid).into()
+    }
+}
+
+impl From<DeviceInhibit> for &'static str {
+    fn from(val: DeviceInhibit) -> Self {
+        match val {
+            DeviceInhibit::Inhibited => "inhibited",
+            DeviceInhibit::Enabled => "enabled",
+        }
+    }
+}
+
+impl From<&DeviceInhibit> for &'static str {
+    fn from(val: &DeviceInhibit) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for DeviceInhibit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
+
+/// Gets the [DeviceInhibit] of the device with a `Status` request.
+pub fn get_device_inhibit<P>(uid: u8, mut poll: P) -> Result<DeviceInhibit>
+where
+    P: FnMut(&Message) -> Result<Message>,
+{
+    let req = MessageData::from(StatusRequest::new()).with_uid(uid).into();
+    let res = StatusResponse::try_from(poll(&req)?)?;
+
+    match res.code() {
+        ResponseCode::Ack => Ok(DeviceInhibit::from_status(res.status())),
+        code => Err(Error::InvalidResponseCode(code.into())),
+    }
+}
+
+/// Sets the [DeviceInhibit] of the device, with an `Inhibit` or `Idle` request.
+///
+/// Returns [Error::InvalidResponseCode] if the device refuses the request, e.g. enabling a device
+/// with a fatal error.
+pub fn set_device_inhibit<P>(uid: u8, inhibit: DeviceInhibit, mut poll: P) -> Result<()>
+where
+    P: FnMut(&Message) -> Result<Message>,
+{
+    let res = poll(&inhibit.request(uid))?;
+    let code = match inhibit {
+        DeviceInhibit::Inhibited => InhibitResponse::try_from(res)?.code(),
+        DeviceInhibit::Enabled => Response::try_from(res)?.code(),
+    };
+
+    match code {
+        ResponseCode::Ack => {
+            log::info!("device {inhibit}");
+            Ok(())
+        }
+        code => Err(Error::InvalidResponseCode(code.into())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockDevice;
+    use crate::{FailureCode, RequestCode};
+
+    #[test]
+    fn test_device_inhibit() -> Result<()> {
+        let device = MockDevice::new();
+        let poll = |req: &Message| device.handle_request(req);
+
+        // the device starts inhibited, until the host enables it
+        assert_eq!(
+            DeviceInhibit::from_status(device.status()),
+            DeviceInhibit::Inhibited
+        );
+
+        set_device_inhibit(0, DeviceInhibit::Enabled, poll)?;
+        assert_eq!(get_device_inhibit(0, poll)?, DeviceInhibit::Enabled);
+
+        set_device_inhibit(0, DeviceInhibit::Inhibited, poll)?;
+        assert_eq!(get_device_inhibit(0, poll)?, DeviceInhibit::Inhibited);
+
+        device.jam(FailureCode::Sensor);
+        assert_eq!(
+            set_device_inhibit(0, DeviceInhibit::Enabled, poll),
+            Err(Error::InvalidResponseCode(ResponseCode::Nak.into()))
+        );
+
+        let req = DeviceInhibit::Inhibited.request(2);
+        assert_eq!(req.data().uid(), 2);
+        assert_eq!(
+            req.data().message_code().request_code(),
+            Ok(RequestCode::Inhibit)
+        );
+        assert_eq!(
+            DeviceInhibit::Enabled
+                .request(2)
+                .data()
+                .message_code()
+                .request_code(),
+            Ok(RequestCode::Idle)
+        );
+
+        Ok(())
+    }
+}
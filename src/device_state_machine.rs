@@ -0,0 +1,475 @@
+use core::fmt;
+
+use crate::{
+    is_status_message, BillAcceptorState, Error, EventCode, MajorMinorStatus, Message, MessageCode,
+    Result, StatusResponse,
+};
+
+/// Represents the allowed [DeviceState] transitions into, or out of, states without a
+/// [BillAcceptorState], as `(from, to)` pairs.
+///
+/// Transitions between states with a [BillAcceptorState] are listed in
+/// [BILL_ACCEPTOR_TRANSITIONS](crate::BILL_ACCEPTOR_TRANSITIONS).
+pub const DEVICE_STATE_TRANSITIONS: [(DeviceState, DeviceState); 25] = [
+    (DeviceState::PowerUp, DeviceState::Collected),
+    (DeviceState::PowerUp, DeviceState::Rejected),
+    (DeviceState::Idle, DeviceState::Inserted),
+    (DeviceState::Idle, DeviceState::Paused),
+    (DeviceState::Inserted, DeviceState::Inhibited),
+    (DeviceState::Inserted, DeviceState::Idle),
+    (DeviceState::Inserted, DeviceState::Escrow),
+    (DeviceState::Inserted, DeviceState::Rejected),
+    (DeviceState::Inserted, DeviceState::Paused),
+    (DeviceState::Escrow, DeviceState::Rejected),
+    (DeviceState::Escrow, DeviceState::Returned),
+    (DeviceState::VendValid, DeviceState::Collected),
+    (DeviceState::Collected, DeviceState::Inhibited),
+    (DeviceState::Collected, DeviceState::Idle),
+    (DeviceState::Rejected, DeviceState::Inhibited),
+    (DeviceState::Rejected, DeviceState::Idle),
+    (DeviceState::Returned, DeviceState::Inhibited),
+    (DeviceState::Returned, DeviceState::Idle),
+    (DeviceState::Paused, DeviceState::Inhibited),
+    (DeviceState::Paused, DeviceState::Idle),
+    (DeviceState::Paused, DeviceState::Inserted),
+    (DeviceState::Paused, DeviceState::Rejected),
+    (DeviceState::Error, DeviceState::Inhibited),
+    (DeviceState::Error, DeviceState::Idle),
+    (DeviceState::Failure, DeviceState::Inhibited),
+];
+
+/// Represents the lifecycle state of a device tracked by a [DeviceStateMachine].
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum DeviceState {
+    /// The device is powering up.
+    #[default]
+    PowerUp,
+    /// The device is disabled, and does not accept notes.
+    Inhibited,
+    /// The device is enabled, and waiting for a note.
+    Idle,
+    /// A note was inserted, and is being validated.
+    Inserted,
+    /// A validated note is held in escrow.
+    Escrow,
+    /// The escrowed note is being stacked.
+    VendValid,
+    /// The note was collected into the cash box.
+    Collected,
+    /// The note was rejected before reaching escrow.
+    Rejected,
+    /// The escrowed note was returned to the user.
+    Returned,
+    /// The device is paused, e.g. by a second note at the entrance.
+    Paused,
+    /// The device can not process operations until the error is resolved.
+    Error,
+    /// A fatal error occurred.
+    Failure,
+}
+
+impl DeviceState {
+    /// Creates a new [DeviceState].
+    pub const fn new() -> Self {
+        Self::PowerUp
+    }
+
+    /// Converts an [EventCode] into the announced [DeviceState].
+    ///
+    /// Returns `None` for events that do not announce a state, and for `Resume`, which returns to
+    /// the state before the pause.
+    pub const fn from_event_code(code: EventCode) -> Option<Self> {
+        match code {
+            EventCode::PowerUp
+            | EventCode::PowerUpAcceptor
+            | EventCode::PowerUpStacker
+            | EventCode::PowerUpAcceptorAccepting
+            | EventCode::PowerUpStackerAccepting => Some(Self::PowerUp),
+            EventCode::Inhibit => Some(Self::Inhibited),
+            EventCode::Idle => Some(Self::Idle),
+            EventCode::Insert => Some(Self::Inserted),
+            EventCode::Escrow => Some(Self::Escrow),
+            EventCode::VendValid => Some(Self::VendValid),
+            EventCode::Collected | EventCode::AcceptorCollected => Some(Self::Collected),
+            EventCode::Rejected | EventCode::AcceptorRejected => Some(Self::Rejected),
+            EventCode::Returned => Some(Self::Returned),
+            EventCode::Pause => Some(Self::Paused),
+            EventCode::OperationError | EventCode::AcceptorOperationError => Some(Self::Error),
+            EventCode::Failure | EventCode::AcceptorFailure => Some(Self::Failure),
+            _ => None,
+        }
+    }
+
+    /// Converts a [MajorMinorStatus] into the reported [DeviceState].
+    ///
+    /// Returns `None` for statuses that do not report a state, and for `Resume`, which returns to
+    /// the state before the pause.
+    pub const fn from_major_minor_status(status: MajorMinorStatus) -> Option<Self> {
+        match status {
+            MajorMinorStatus::PowerUp
+            | MajorMinorStatus::PowerUpAcceptor
+            | MajorMinorStatus::PowerUpStacker
+            | MajorMinorStatus::PowerUpAcceptorAccepting
+            | MajorMinorStatus::PowerUpStackerAccepting => Some(Self::PowerUp),
+            MajorMinorStatus::Normal => Some(Self::Inhibited),
+            MajorMinorStatus::NormalIdle => Some(Self::Idle),
+            MajorMinorStatus::NormalActive | MajorMinorStatus::NormalInsert => Some(Self::Inserted),
+            MajorMinorStatus::NormalEscrow => Some(Self::Escrow),
+            MajorMinorStatus::NormalVendValid => Some(Self::VendValid),
+            MajorMinorStatus::NormalCollected => Some(Self::Collected),
+            MajorMinorStatus::NormalRejected => Some(Self::Rejected),
+            MajorMinorStatus::NormalReturned => Some(Self::Returned),
+            MajorMinorStatus::NormalPause => Some(Self::Paused),
+            MajorMinorStatus::Abnormal | MajorMinorStatus::AbnormalOperationError => {
+                Some(Self::Error)
+            }
+            MajorMinorStatus::AbnormalFailure(_) => Some(Self::Failure),
+            _ => None,
+        }
+    }
+
+    /// Gets the [BillAcceptorState] tracked by the [StateTracker](crate::StateTracker) for the
+    /// state, if any.
+    ///
+    /// Returns `None` for the finer-grained states a [BillAcceptorState] does not model.
+    pub const fn bill_acceptor_state(self) -> Option<BillAcceptorState> {
+        match self {
+            Self::PowerUp => Some(BillAcceptorState::Initializing),
+            Self::Inhibited => Some(BillAcceptorState::Inhibited),
+            Self::Idle => Some(BillAcceptorState::Idle),
+            Self::Escrow => Some(BillAcceptorState::Escrowed),
+            Self::VendValid => Some(BillAcceptorState::VendValid),
+            _ => None,
+        }
+    }
+
+    /// Gets whether the device may move from the `from` state to the `to` state.
+    ///
+    /// Staying in the same state is always allowed, as is moving to [PowerUp](Self::PowerUp),
+    /// [Error](Self::Error), or [Failure](Self::Failure). Transitions between states with a
+    /// [BillAcceptorState] follow [BILL_ACCEPTOR_TRANSITIONS](crate::BILL_ACCEPTOR_TRANSITIONS), the remaining transitions follow
+    /// [DEVICE_STATE_TRANSITIONS].
+    pub const fn can_transition(from: Self, to: Self) -> bool {
+        if from as u8 == to as u8 || matches!(to, Self::PowerUp | Self::Error | Self::Failure) {
+            return true;
+        }
+
+        if let (Some(f), Some(t)) = (from.bill_acceptor_state(), to.bill_acceptor_state()) {
+            return BillAcceptorState::can_transition(f, t);
+        }
+
+        let mut i = 0;
+        while i < DEVICE_STATE_TRANSITIONS.len() {
+            let (f, t) = DEVICE_STATE_TRANSITIONS[i];
+            if f as u8 == from as u8 && t as u8 == to as u8 {
+                return true;
+            }
+            i += 1;
+        }
+        false
+    }
+}
+
+impl From<DeviceState> for &'static str {
+    fn from(val: DeviceState) -> Self {
+        match val {
+            DeviceState::PowerUp => "power up",
+            DeviceState::Inhibited => "inhibited",
+            DeviceState::Idle => "idle",
+            DeviceState::Inserted => "inserted",
+            DeviceState::Escrow => "escrow",
+            DeviceState::VendValid => "vend valid",
+            DeviceState::Collected => "collected",
+            DeviceState::Rejected => "rejected",
+            DeviceState::Returned => "returned",
+            DeviceState::Paused => "paused",
+            DeviceState::Error => "error",
+            DeviceState::Failure => "failure",
+        }
+    }
+}
+
+impl From<&DeviceState> for &'static str {
+    fn from(val: &DeviceState) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for DeviceState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
+
+/// Represents a validated [DeviceState] transition.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct StateTransition {
+    from: DeviceState,
+    to: DeviceState,
+    code: MessageCode,
+}
+
+impl StateTransition {
+    /// Creates a new [StateTransition] from the provided parameters.
+    pub const fn create(from: DeviceState, to: DeviceState, code: MessageCode) -> Self {
+        Self { from, to, code }
+    }
+
+    /// Gets the [DeviceState] before the transition.
+    pub const fn from(&self) -> DeviceState {
+        self.from
+    }
+
+    /// Gets the [DeviceState] after the transition.
+    pub const fn to(&self) -> DeviceState {
+        self.to
+    }
+
+    /// Gets the [MessageCode] of the message that caused the transition.
+    pub const fn code(&self) -> MessageCode {
+        self.code
+    }
+
+    /// Gets whether the transition changed the [DeviceState].
+    pub fn is_change(&self) -> bool {
+        self.from != self.to
+    }
+}
+
+impl fmt::Display for StateTransition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""from": {}, "#, self.from)?;
+        write!(f, r#""to": {}, "#, self.to)?;
+        write!(f, r#""code": {}"#, self.code)?;
+        write!(f, "}}")
+    }
+}
+
+/// Tracks the device lifecycle from `Status` responses and events, validating each transition.
+///
+/// Unlike the [StateTracker](crate::StateTracker), which records protocol violations as
+/// diagnostics, the [DeviceStateMachine] rejects illegal transitions with
+/// [Error::InvalidStateTransition], and keeps its current state. Hosts can drive vending logic
+/// off the returned [StateTransition]s. Use [force](Self::force) to resynchronize after an
+/// error.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{DeviceState, DeviceStateMachine, EventCode, MessageCode};
+///
+/// # fn main() -> jcm::Result<()> {
+/// let mut machine = DeviceStateMachine::new();
+///
+/// machine.on_event_code(EventCode::Idle)?;
+/// let transition = machine.on_event_code(EventCode::Escrow)?;
+///
+/// assert_eq!(transition.from(), DeviceState::Idle);
+/// assert_eq!(transition.to(), DeviceState::Escrow);
+/// assert_eq!(transition.code(), MessageCode::Event(EventCode::Escrow));
+///
+/// // a note can not be collected before it reaches `Vend Valid`
+/// assert!(machine.on_event_code(EventCode::AcceptorCollected).is_err());
+/// assert_eq!(machine.state(), DeviceState::Escrow);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct DeviceStateMachine {
+    state: DeviceState,
+    paused_from: Option<DeviceState>,
+}
+
+impl DeviceStateMachine {
+    /// Creates a new [DeviceStateMachine].
+    pub const fn new() -> Self {
+        Self {
+            state: DeviceState::new(),
+            paused_from: None,
+        }
+    }
+
+    /// Gets the current [DeviceState].
+    pub const fn state(&self) -> DeviceState {
+        self.state
+    }
+
+    /// Sets the current [DeviceState], without validating the transition.
+    pub fn force(&mut self, state: DeviceState) {
+        self.state = state;
+        self.paused_from = None;
+    }
+
+    /// Updates the state from an event, or `Status` response [Message].
+    ///
+    /// Messages that announce no state return a [StateTransition] without a change. Returns
+    /// [Error::InvalidStateTransition] for illegal transitions.
+    pub fn on_message(&mut self, message: &Message) -> Result<StateTransition> {
+        let code = message.data().message_code();
+
+        if is_status_message(message) {
+            self.on_status(&StatusResponse::try_from(message)?)
+        } else {
+            match code.event_code() {
+                Ok(event) => self.on_event_code(event),
+                Err(_) => Ok(StateTransition::create(self.state, self.state, code)),
+            }
+        }
+    }
+
+    /// Updates the state from a `Status` response.
+    pub fn on_status(&mut self, res: &StatusResponse) -> Result<StateTransition> {
+        let status = res.status().major_minor_status();
+
+        self.apply(
+            DeviceState::from_major_minor_status(status),
+            status == MajorMinorStatus::NormalResume,
+            MessageCode::Request(crate::RequestCode::Status),
+        )
+    }
+
+    /// Updates the state from an [EventCode].
+    pub fn on_event_code(&mut self, code: EventCode) -> Result<StateTransition> {
+        self.apply(
+            DeviceState::from_event_code(code),
+            code == EventCode::Resume,
+            MessageCode::Event(code),
+        )
+    }
+
+    fn apply(
+        &mut self,
+        to: Option<DeviceState>,
+        resume: bool,
+        code: MessageCode,
+    ) -> Result<StateTransition> {
+        let from = self.state;
+        let to = match (to, resume) {
+            (Some(to), _) => to,
+            (None, true) => self.paused_from.unwrap_or(from),
+            (None, false) => return Ok(StateTransition::create(from, from, code)),
+        };
+
+        if !resume && !DeviceState::can_transition(from, to) {
+            log::warn!("illegal device state transition: {from} -> {to}, code: {code}");
+            return Err(Error::InvalidStateTransition((from, to)));
+        }
+
+        if to == DeviceState::Paused && from != DeviceState::Paused {
+            self.paused_from = Some(from);
+        } else if to != DeviceState::Paused {
+            self.paused_from = None;
+        }
+
+        self.state = to;
+
+        Ok(StateTransition::create(from, to, code))
+    }
+}
+
+impl fmt::Display for DeviceStateMachine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""state": {}"#, self.state)?;
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DeviceStatus, FuncId, MessageData, ResponseCode, StatusRequest};
+
+    #[test]
+    fn test_device_state_machine() -> Result<()> {
+        let mut machine = DeviceStateMachine::new();
+
+        for (code, exp) in [
+            (EventCode::PowerUp, DeviceState::PowerUp),
+            (EventCode::Inhibit, DeviceState::Inhibited),
+            (EventCode::Idle, DeviceState::Idle),
+            (EventCode::Insert, DeviceState::Inserted),
+            (EventCode::Pause, DeviceState::Paused),
+            (EventCode::Resume, DeviceState::Inserted),
+            (EventCode::Escrow, DeviceState::Escrow),
+            (EventCode::VendValid, DeviceState::VendValid),
+            (EventCode::AcceptorCollected, DeviceState::Collected),
+            (EventCode::Idle, DeviceState::Idle),
+        ] {
+            assert_eq!(machine.on_event_code(code)?.to(), exp);
+        }
+
+        assert!(!machine.on_event_code(EventCode::Clear)?.is_change());
+        assert_eq!(
+            machine.on_event_code(EventCode::VendValid),
+            Err(Error::InvalidStateTransition((
+                DeviceState::Idle,
+                DeviceState::VendValid
+            )))
+        );
+
+        let status = |major_minor| -> Message {
+            let mut additional = vec![ResponseCode::Ack.into(), DeviceStatus::len() as u8];
+            additional.extend(DeviceStatus::create(FuncId::Acceptor, major_minor).to_bytes());
+            MessageData::from(StatusRequest::new())
+                .with_additional(&additional)
+                .into()
+        };
+
+        let transition = machine.on_message(&status(MajorMinorStatus::NormalEscrow))?;
+        assert_eq!(
+            transition,
+            StateTransition::create(
+                DeviceState::Idle,
+                DeviceState::Escrow,
+                MessageCode::Request(crate::RequestCode::Status)
+            )
+        );
+        assert!(machine
+            .on_message(&status(MajorMinorStatus::NormalCollected))
+            .is_err());
+        // like the `StateTracker`, a note in escrow may go straight back to `Idle`
+        assert!(machine
+            .on_message(&status(MajorMinorStatus::NormalIdle))?
+            .is_change());
+
+        machine.force(DeviceState::Inhibited);
+        assert_eq!(machine.state(), DeviceState::Inhibited);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_device_state_transitions() {
+        use DeviceState::*;
+
+        let states = [
+            PowerUp, Inhibited, Idle, Inserted, Escrow, VendValid, Collected, Rejected, Returned,
+            Paused, Error, Failure,
+        ];
+
+        // states shared with the `StateTracker` follow its transition table
+        for from in states {
+            for to in states {
+                if let (Some(f), Some(t)) = (from.bill_acceptor_state(), to.bill_acceptor_state()) {
+                    assert_eq!(
+                        DeviceState::can_transition(from, to),
+                        BillAcceptorState::can_transition(f, t),
+                        "{from} -> {to}"
+                    );
+                }
+            }
+        }
+
+        for (from, to) in DEVICE_STATE_TRANSITIONS {
+            assert!(from.bill_acceptor_state().is_none() || to.bill_acceptor_state().is_none());
+            assert!(DeviceState::can_transition(from, to));
+        }
+
+        assert!(!DeviceState::can_transition(Escrow, Collected));
+        assert!(!DeviceState::can_transition(Collected, Escrow));
+    }
+}
@@ -1,4 +1,4 @@
-use std::{fmt, mem};
+use core::{fmt, mem};
 
 use crate::{Error, FuncId, Result};
 
@@ -13,6 +13,7 @@ pub use major_minor_status::*;
 /// Represents the JCM device status.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DeviceStatus {
     function_mode: FunctionMode,
     major_minor_status: MajorMinorStatus,
@@ -1,4 +1,4 @@
-use std::{fmt, mem};
+use core::{fmt, mem};
 
 use crate::{Error, FailureCode, Result};
 
@@ -38,6 +38,7 @@ const RESERVED: u16 = 0xffff;
 /// Represents the major-minor status of the JCM device status.
 #[repr(u16)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MajorMinorStatus {
     /// Power up status: device is in normal power up status.
     PowerUp = POWER_UP,
@@ -335,7 +336,7 @@ mod tests {
             MajorMinorStatus::WarningFunctionAbeyance,
         ];
 
-        for (raw, exp) in raw_denom.into_iter().zip(expected.into_iter()) {
+        for (raw, exp) in raw_denom.into_iter().zip(expected) {
             assert_eq!(MajorMinorStatus::try_from(raw), Ok(exp));
             assert!(MajorMinorStatus::try_from((raw >> MAJOR_SHIFT) as u8).is_ok());
 
@@ -346,9 +347,7 @@ mod tests {
             );
         }
 
-        for stat in
-            (0..=0x1fffu16).filter(|s| !raw_denom.iter().any(|&d| d == (*s & MAJOR_MINOR_MASK)))
-        {
+        for stat in (0..=0x1fffu16).filter(|s| !raw_denom.contains(&(*s & MAJOR_MINOR_MASK))) {
             assert!(MajorMinorStatus::try_from(stat).is_err());
             assert_eq!(MajorMinorStatus::from_u16(stat), MajorMinorStatus::Reserved);
         }
@@ -125,7 +125,7 @@ fn test_device_status() {
         DS::create(FM::Acceptor, MS::AbnormalFailure(FC::Abnormal)),
     ];
 
-    for (raw, exp) in raw_vals.into_iter().zip(expected.into_iter()) {
+    for (raw, exp) in raw_vals.into_iter().zip(expected) {
         assert_eq!(DS::try_from(raw), Ok(exp));
         assert_eq!(DS::from_u16(raw), exp);
     }
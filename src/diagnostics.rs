@@ -0,0 +1,377 @@
+//! Structured diagnostics for service UIs.
+//!
+//! A [DiagnosticsReport] collects the [MajorMinorStatus], the [FailureCode]s, and the recent event
+//! history of a device into [Finding]s, each with a recommended operator action.
+//!
+//! The report is serializable with the `serde` feature, and its [Display](fmt::Display) output
+//! is JSON.
+//!
+//! ```
+//! use jcm::diagnostics::{DiagnosticsReport, Severity};
+//! use jcm::{FailureCode, MajorMinorStatus};
+//!
+//! let report = DiagnosticsReport::new()
+//!     .with_status(MajorMinorStatus::AbnormalFailure(FailureCode::TransportMotor));
+//!
+//! assert_eq!(report.severity(), Severity::Error);
+//! assert_eq!(
+//!     report.findings()[0].action(),
+//!     "open the transport path and remove any jammed note, then reset the device"
+//! );
+//! ```
+
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use crate::{EventCode, FailureCode, FailureEvent, MajorMinorStatus, Message, Result};
+
+/// Represents the maximum number of events kept in the [DiagnosticsReport] history.
+pub const MAX_EVENT_HISTORY: usize = 32;
+
+/// Represents the severity of a [Finding].
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Severity {
+    /// Informational, the device keeps operating.
+    #[default]
+    Info,
+    /// The device needs attention soon.
+    Warning,
+    /// The device is out of service until the operator acts.
+    Error,
+}
+
+impl From<Severity> for &'static str {
+    fn from(val: Severity) -> Self {
+        match val {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+impl From<&Severity> for &'static str {
+    fn from(val: &Severity) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
+
+/// Represents a diagnosed condition, with the recommended operator action.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Finding {
+    severity: Severity,
+    summary: String,
+    action: String,
+}
+
+impl Finding {
+    /// Creates a new [Finding] from the provided parameters.
+    pub fn create(severity: Severity, summary: &str, action: &str) -> Self {
+        Self {
+            severity,
+            summary: summary.into(),
+            action: action.into(),
+        }
+    }
+
+    /// Creates a new [Finding] for a device [FailureCode].
+    pub fn from_failure(code: FailureCode) -> Self {
+        Self::create(Severity::Error, (&code).into(), failure_action(code))
+    }
+
+    /// Creates a new [Finding] for a [MajorMinorStatus].
+    ///
+    /// Returns `None` for statuses that need no operator action.
+    pub fn from_status(status: MajorMinorStatus) -> Option<Self> {
+        let (severity, action) = match status {
+            MajorMinorStatus::AbnormalFailure(code) => return Some(Self::from_failure(code)),
+            MajorMinorStatus::AbnormalOperationError => (
+                Severity::Error,
+                "reset the device, and check it is configured before operation",
+            ),
+            MajorMinorStatus::PowerUpAcceptor | MajorMinorStatus::PowerUpAcceptorAccepting => (
+                Severity::Warning,
+                "remove the note returned to the bezel after initialization",
+            ),
+            MajorMinorStatus::PowerUpStacker | MajorMinorStatus::PowerUpStackerAccepting => (
+                Severity::Warning,
+                "reconcile the credit of the note stacked after initialization",
+            ),
+            MajorMinorStatus::WarningNoteStay => (
+                Severity::Warning,
+                "remove the note left in the insertion slot",
+            ),
+            MajorMinorStatus::WarningFunctionAbeyance => {
+                (Severity::Warning, "check the `Function Mode` settings")
+            }
+            MajorMinorStatus::Warning => (Severity::Warning, "inspect the device"),
+            MajorMinorStatus::Normal => (Severity::Info, "enable the device to accept notes"),
+            _ => return None,
+        };
+
+        Some(Self::create(severity, (&status).into(), action))
+    }
+
+    /// Creates a new [Finding] for an event [Message].
+    ///
+    /// Returns `None` for events that need no operator action.
+    pub fn from_event(event: &Message) -> Result<Option<Self>> {
+        let status = match event.data().message_code().event_code()? {
+            EventCode::Failure | EventCode::AcceptorFailure => {
+                return Ok(Some(Self::from_failure(
+                    FailureEvent::try_from(event)?.failure_code(),
+                )));
+            }
+            EventCode::OperationError | EventCode::AcceptorOperationError => {
+                MajorMinorStatus::AbnormalOperationError
+            }
+            EventCode::NoteStay | EventCode::AcceptorNoteStay => MajorMinorStatus::WarningNoteStay,
+            EventCode::FunctionAbeyance => MajorMinorStatus::WarningFunctionAbeyance,
+            _ => return Ok(None),
+        };
+
+        Ok(Self::from_status(status))
+    }
+
+    /// Gets the [Severity] of the [Finding].
+    pub const fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Gets the summary of the diagnosed condition.
+    pub fn summary(&self) -> &str {
+        self.summary.as_str()
+    }
+
+    /// Gets the recommended operator action.
+    pub fn action(&self) -> &str {
+        self.action.as_str()
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""severity": {}, "#, self.severity)?;
+        write!(f, r#""summary": {:?}, "#, self.summary)?;
+        write!(f, r#""action": {:?}"#, self.action)?;
+        write!(f, "}}")
+    }
+}
+
+/// Gets the recommended operator action for a device [FailureCode].
+pub const fn failure_action(code: FailureCode) -> &'static str {
+    match code {
+        FailureCode::TransportMotor => {
+            "open the transport path and remove any jammed note, then reset the device"
+        }
+        FailureCode::StackMotor => {
+            "remove the cashbox, clear any note jammed at the stacker, and reinsert the cashbox"
+        }
+        FailureCode::AntiStringingMechanism => {
+            "open the transport path, and remove any string or foreign object"
+        }
+        FailureCode::Sensor => "clean the transport path sensors, then reset the device",
+        FailureCode::RecyclerMotor => {
+            "open the recycler and remove any jammed note, then reset the device"
+        }
+        FailureCode::RecyclerSensor => "clean the recycler sensors, then reset the device",
+        FailureCode::AcceptorHardware | FailureCode::RecyclyHardware | FailureCode::Ram => {
+            "power cycle the device, and contact service if the failure persists"
+        }
+        FailureCode::Rom => "reinstall the device firmware, or contact service",
+        FailureCode::Communication => "check the USB cable and connectors, then reset the device",
+        FailureCode::Abnormal => "check the device settings, then reset the device",
+        FailureCode::Reserved => "contact service",
+    }
+}
+
+/// Represents a structured diagnostics report of a device.
+///
+/// Findings are deduplicated, and the event history keeps the last [MAX_EVENT_HISTORY] event
+/// codes.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiagnosticsReport {
+    status: Option<MajorMinorStatus>,
+    findings: Vec<Finding>,
+    events: Vec<EventCode>,
+}
+
+impl DiagnosticsReport {
+    /// Creates a new, empty [DiagnosticsReport].
+    pub const fn new() -> Self {
+        Self {
+            status: None,
+            findings: Vec::new(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Gets the last [MajorMinorStatus] of the device, if recorded.
+    pub const fn status(&self) -> Option<MajorMinorStatus> {
+        self.status
+    }
+
+    /// Sets the [MajorMinorStatus] of the device, adding its [Finding], if any.
+    pub fn set_status(&mut self, status: MajorMinorStatus) {
+        self.status = Some(status);
+        if let Some(finding) = Finding::from_status(status) {
+            self.add_finding(finding);
+        }
+    }
+
+    /// Builder function that sets the [MajorMinorStatus] of the device.
+    pub fn with_status(mut self, status: MajorMinorStatus) -> Self {
+        self.set_status(status);
+        self
+    }
+
+    /// Records a device [FailureCode].
+    pub fn record_failure(&mut self, code: FailureCode) {
+        self.add_finding(Finding::from_failure(code));
+    }
+
+    /// Records a device event [Message] in the history, adding its [Finding], if any.
+    pub fn record_event(&mut self, event: &Message) -> Result<()> {
+        let code = event.data().message_code().event_code()?;
+
+        if let Some(finding) = Finding::from_event(event)? {
+            self.add_finding(finding);
+        }
+
+        if self.events.len() == MAX_EVENT_HISTORY {
+            self.events.remove(0);
+        }
+        self.events.push(code);
+
+        Ok(())
+    }
+
+    /// Adds a [Finding] to the report, unless already present.
+    pub fn add_finding(&mut self, finding: Finding) {
+        if !self.findings.contains(&finding) {
+            self.findings.push(finding);
+        }
+    }
+
+    /// Gets the [Finding]s of the report, in the order they were diagnosed.
+    pub fn findings(&self) -> &[Finding] {
+        self.findings.as_ref()
+    }
+
+    /// Gets the recent event history, oldest first.
+    pub fn events(&self) -> &[EventCode] {
+        self.events.as_ref()
+    }
+
+    /// Gets the highest [Severity] of the [Finding]s.
+    pub fn severity(&self) -> Severity {
+        self.findings
+            .iter()
+            .map(Finding::severity)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Gets whether the report has no [Finding]s.
+    pub fn is_healthy(&self) -> bool {
+        self.findings.is_empty()
+    }
+}
+
+impl fmt::Display for DiagnosticsReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        match self.status.as_ref() {
+            Some(status) => write!(f, r#""status": {:?}, "#, <&str>::from(status))?,
+            None => write!(f, r#""status": null, "#)?,
+        }
+        write!(f, r#""severity": {}, "#, self.severity())?;
+        write!(f, r#""findings": ["#)?;
+        for (i, finding) in self.findings.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{finding}")?;
+        }
+        write!(f, r#"], "events": ["#)?;
+        for (i, event) in self.events.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{event}")?;
+        }
+        write!(f, "]}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventType, MessageCode, MessageData, MessageType};
+
+    fn event(code: EventCode) -> Message {
+        Message::new().with_data(
+            MessageData::new()
+                .with_message_type(MessageType::Event(EventType::Sequence1))
+                .with_message_code(MessageCode::Event(code)),
+        )
+    }
+
+    #[test]
+    fn test_diagnostics_report() -> Result<()> {
+        let mut report = DiagnosticsReport::new();
+        assert!(report.is_healthy());
+        assert_eq!(report.severity(), Severity::Info);
+
+        report.record_event(&event(EventCode::Idle))?;
+        report.record_event(&event(EventCode::AcceptorNoteStay))?;
+        report.record_event(&event(EventCode::NoteStay))?;
+        assert_eq!(report.findings().len(), 1);
+        assert_eq!(report.severity(), Severity::Warning);
+
+        report.record_event(
+            &FailureEvent::create(
+                EventType::Sequence2,
+                EventCode::AcceptorFailure,
+                FailureCode::StackMotor,
+            )
+            .into_message(1),
+        )?;
+        report.set_status(MajorMinorStatus::AbnormalFailure(FailureCode::StackMotor));
+
+        assert_eq!(report.findings().len(), 2);
+        assert_eq!(report.severity(), Severity::Error);
+        assert_eq!(
+            report.findings()[1],
+            Finding::create(
+                Severity::Error,
+                "stack motor related error",
+                failure_action(FailureCode::StackMotor)
+            )
+        );
+        assert_eq!(report.events().len(), 4);
+
+        for _ in 0..MAX_EVENT_HISTORY {
+            report.record_event(&event(EventCode::Idle))?;
+        }
+        assert_eq!(report.events(), [EventCode::Idle; MAX_EVENT_HISTORY]);
+
+        assert!(Finding::from_status(MajorMinorStatus::NormalIdle).is_none());
+        assert!(report
+            .to_string()
+            .starts_with(r#"{"status": "fatal error occured", "#));
+
+        Ok(())
+    }
+}
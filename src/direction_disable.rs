@@ -0,0 +1,165 @@
+use core::fmt;
+
+use crate::{
+    DirectionDisableMode, DirectionDisableRequest, DirectionDisableResponse, Error,
+    InhibitDirection, Message, MessageData, ResponseCode, Result,
+};
+
+/// Represents the change applied by [modify_direction_disable].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct DirectionDisableDelta {
+    before: InhibitDirection,
+    after: InhibitDirection,
+}
+
+impl DirectionDisableDelta {
+    /// Creates a new [DirectionDisableDelta] from the provided parameters.
+    pub const fn create(before: InhibitDirection, after: InhibitDirection) -> Self {
+        Self { before, after }
+    }
+
+    /// Gets the [InhibitDirection] read before the modification.
+    pub const fn before(&self) -> InhibitDirection {
+        self.before
+    }
+
+    /// Gets the [InhibitDirection] verified after the modification.
+    pub const fn after(&self) -> InhibitDirection {
+        self.after
+    }
+
+    /// Gets the directions that were newly inhibited.
+    pub const fn inhibited(&self) -> InhibitDirection {
+        InhibitDirection::create(self.after.bits() & !self.before.bits())
+    }
+
+    /// Gets the directions that were newly accepted.
+    pub const fn accepted(&self) -> InhibitDirection {
+        InhibitDirection::create(self.before.bits() & !self.after.bits())
+    }
+
+    /// Gets whether the modification changed any direction.
+    pub const fn is_empty(&self) -> bool {
+        self.before.bits() == self.after.bits()
+    }
+}
+
+impl fmt::Display for DirectionDisableDelta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""before": {}, "#, self.before)?;
+        write!(f, r#""after": {}"#, self.after)?;
+        write!(f, "}}")
+    }
+}
+
+/// Modifies the `Direction Disable` settings of the device.
+///
+/// Performs the full read-modify-write transaction through the polling function:
+///
+/// - gets the current settings
+/// - applies the `modify` function
+/// - sets the modified settings
+/// - gets the settings again, verifying the device applied them
+///
+/// Every request is sent with the provided `uid`.
+pub fn modify_direction_disable<P, F>(
+    uid: u8,
+    mut poll: P,
+    modify: F,
+) -> Result<DirectionDisableDelta>
+where
+    P: FnMut(&Message) -> Result<Message>,
+    F: FnOnce(&mut InhibitDirection),
+{
+    let mut request = |req: DirectionDisableRequest| -> Result<DirectionDisableResponse> {
+        let res = DirectionDisableResponse::try_from(poll(
+            &MessageData::from(req).with_uid(uid).into(),
+        )?)?;
+
+        match res.code() {
+            ResponseCode::Ack => Ok(res),
+            code => Err(Error::InvalidResponseCode(code.into())),
+        }
+    };
+
+    let before = request(DirectionDisableRequest::new())?.directions();
+
+    let mut expected = before;
+    modify(&mut expected);
+
+    request(
+        DirectionDisableRequest::new()
+            .with_mode(DirectionDisableMode::Set)
+            .with_direction(expected),
+    )?;
+
+    let after = request(DirectionDisableRequest::new())?.directions();
+
+    if after.bits() != expected.bits() {
+        Err(Error::InvalidDirectionDisable((
+            expected.bits(),
+            after.bits(),
+        )))
+    } else {
+        Ok(DirectionDisableDelta::create(before, after))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RequestType;
+
+    fn device(state: &mut u8, ignore_set: bool) -> impl FnMut(&Message) -> Result<Message> + '_ {
+        move |req: &Message| {
+            assert_eq!(req.data().uid(), 1);
+
+            if req.data().message_type().request_type() == Ok(RequestType::SetFeature) {
+                if !ignore_set {
+                    *state = req.data().additional()[0];
+                }
+                Ok(Message::new().with_data(
+                    req.data()
+                        .clone()
+                        .with_additional(&[ResponseCode::Ack.into()]),
+                ))
+            } else {
+                Ok(Message::new().with_data(
+                    req.data()
+                        .clone()
+                        .with_additional(&[ResponseCode::Ack.into(), *state]),
+                ))
+            }
+        }
+    }
+
+    #[test]
+    fn test_modify_direction_disable() -> Result<()> {
+        let mut state = 0b0011;
+
+        let delta = modify_direction_disable(1, device(&mut state, false), |dirs| {
+            dirs.set(0b0110);
+        })?;
+
+        assert_eq!(state, 0b0110);
+        assert_eq!(delta.before(), InhibitDirection::create(0b0011));
+        assert_eq!(delta.after(), InhibitDirection::create(0b0110));
+        assert_eq!(delta.inhibited(), InhibitDirection::create(0b0100));
+        assert_eq!(delta.accepted(), InhibitDirection::create(0b0001));
+        assert!(!delta.is_empty());
+
+        let delta = modify_direction_disable(1, device(&mut state, false), |dirs| {
+            dirs.set_face_down_right_side(dirs.face_down_right_side());
+        })?;
+        assert!(delta.is_empty());
+
+        let mut state = 0b0011;
+        assert_eq!(
+            modify_direction_disable(1, device(&mut state, true), |dirs| dirs.set(0xf)),
+            Err(Error::InvalidDirectionDisable((0xf, 0b0011)))
+        );
+
+        Ok(())
+    }
+}
@@ -1,7 +1,16 @@
-use std::fmt;
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use crate::{DeviceState, FailureCode, Feature, RequestCode, ResponseCode, ResponseLen};
+
+mod error_category;
+mod transport_error;
+
+pub use error_category::*;
+pub use transport_error::*;
 
 /// Convenience alias for the library [`Result`](std::result::Result).
-pub type Result<T> = std::result::Result<T, Error>;
+pub type Result<T> = core::result::Result<T, Error>;
 
 /// Represents error variants for the library.
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -52,12 +61,150 @@ pub enum Error {
     InvalidNearFullNumberLen((usize, usize)),
     InvalidNearFullMode(u8),
     InvalidImageSizeLen((usize, usize)),
+    InvalidCsvRecord((usize, String)),
+    Cancelled,
+    CountersStore(String),
+    Journal(String),
+    Accounting(String),
+    InvalidDirectionDisable((u8, u8)),
+    TamperSuspected((Vec<u8>, Vec<u8>)),
+    FrameTooLarge((usize, usize)),
+    InvalidDuration(String),
+    Timeout(String),
+    UnexpectedResponseLen((RequestCode, ResponseLen, usize)),
+    UidExhausted,
+    UnassignedUid(String),
+    InvalidUid((u8, u8)),
+    InvalidStateTransition((DeviceState, DeviceState)),
+    FeatureDisabled(Feature),
+    CrashLog(String),
+    InvalidBarCodeType(u8),
+    InvalidBarCodeDigits((u8, u8)),
+    InvalidBarCodeSettingsLen((usize, usize)),
+    InvalidBarCodeMode(u8),
+    CurrencyTableChanged(String),
+    InvalidInsertNotificationMode(u8),
+    InvalidInsertNotification(u8),
+    InvalidInsertNotificationLen((usize, usize)),
+    InvalidConditionalVendFlag(u8),
+    InvalidConditionalVendThresholds((u64, u64)),
+    InvalidConditionalVendSettingsLen((usize, usize)),
+    InvalidConditionalVendMode(u8),
+    InvalidPauseFlags(u8),
+    InvalidPauseSettingsLen((usize, usize)),
+    InvalidPauseMode(u8),
+    InterlockEngaged(String),
+    InvalidEventResendInterval(u8),
+    InvalidEventResendIntervalLen((usize, usize)),
+    InvalidEventResendIntervalMode(u8),
+    Failover(String),
+    SupportBundle(String),
+    InvalidOrientation(u8),
+    InvalidNoteDataInfoLen((usize, usize)),
+    InvalidImageLen((usize, usize)),
+    InvalidImageBlockLen((usize, usize)),
+    InvalidImageBitDepth(u8),
+    InvalidImageDimensions((usize, usize)),
+    InvalidSerialNumberLen((usize, usize)),
+    InvalidImageKind(&'static str),
+    Trace(String),
+    Replay(String),
+    TruncatedFrame((usize, usize)),
+    InvalidCurrencyString(String),
+    DenominationNotAssigned(String),
+    InvalidMonetaryAmount(String),
+    Transport(TransportError),
+    Device(FailureCode),
+    RequestPending(String),
     InvalidCString,
     InvalidAsciiString,
     InvalidUtf8String,
     InvalidFirmwareVersion,
-    #[cfg(feature = "usb")]
-    Usb(String),
+    #[cfg(feature = "serial")]
+    Serial(String),
+    #[cfg(feature = "demo")]
+    InvalidDemoCommand(String),
+    #[cfg(feature = "demo")]
+    Demo(String),
+    #[cfg(feature = "cli")]
+    InvalidCliCommand(String),
+    #[cfg(feature = "cli")]
+    Cli(String),
+}
+
+impl Error {
+    /// Gets the [ErrorCategory] of the [Error].
+    pub const fn category(&self) -> ErrorCategory {
+        match self {
+            Self::Transport(_) | Self::Timeout(_) => ErrorCategory::Transport,
+            #[cfg(feature = "serial")]
+            Self::Serial(_) => ErrorCategory::Transport,
+            Self::Device(_) | Self::TamperSuspected(_) => ErrorCategory::Device,
+            Self::InvalidCsvRecord(_)
+            | Self::Cancelled
+            | Self::CountersStore(_)
+            | Self::Journal(_)
+            | Self::Accounting(_)
+            | Self::InvalidDuration(_)
+            | Self::UidExhausted
+            | Self::UnassignedUid(_)
+            | Self::InvalidStateTransition(_)
+            | Self::FeatureDisabled(_)
+            | Self::CrashLog(_)
+            | Self::CurrencyTableChanged(_)
+            | Self::InterlockEngaged(_)
+            | Self::Failover(_)
+            | Self::SupportBundle(_)
+            | Self::Trace(_)
+            | Self::Replay(_)
+            | Self::InvalidCurrencyString(_)
+            | Self::DenominationNotAssigned(_)
+            | Self::InvalidMonetaryAmount(_) => ErrorCategory::Host,
+            #[cfg(feature = "demo")]
+            Self::InvalidDemoCommand(_) | Self::Demo(_) => ErrorCategory::Host,
+            #[cfg(feature = "cli")]
+            Self::InvalidCliCommand(_) | Self::Cli(_) => ErrorCategory::Host,
+            _ => ErrorCategory::Protocol,
+        }
+    }
+
+    /// Gets whether retrying the failed operation can succeed.
+    ///
+    /// Timeouts, endpoint stalls, truncated frames, and `Busy` responses are retryable.
+    pub const fn is_retryable(&self) -> bool {
+        match self {
+            Self::Transport(err) => err.is_retryable(),
+            Self::Timeout(_) | Self::TruncatedFrame(_) => true,
+            Self::InvalidResponseCode(code) => {
+                matches!(ResponseCode::from_u8(*code), ResponseCode::Busy)
+            }
+            _ => false,
+        }
+    }
+
+    /// Gets the recommended recovery action for the [Error].
+    pub const fn recovery_hint(&self) -> &'static str {
+        match self {
+            Self::Transport(err) => err.kind().recovery_hint(),
+            Self::Timeout(_) => TransportErrorKind::Timeout.recovery_hint(),
+            #[cfg(feature = "serial")]
+            Self::Serial(_) => TransportErrorKind::Other.recovery_hint(),
+            Self::Device(code) => crate::diagnostics::failure_action(*code),
+            Self::TamperSuspected(_) => "verify the device firmware, and contact service",
+            Self::InvalidResponseCode(code) => match ResponseCode::from_u8(*code) {
+                ResponseCode::Busy => {
+                    "wait for the device to finish the current operation, then retry"
+                }
+                _ => "check the request is valid in the current device state",
+            },
+            Self::FeatureDisabled(_) => "check the device firmware supports the feature",
+            Self::Cancelled => "no action required, the operation was cancelled",
+            err => match err.category() {
+                ErrorCategory::Host => "check the host configuration and the request parameters",
+                _ => "resynchronize with the device, e.g. with a `Reset` request",
+            },
+        }
+    }
 }
 
 impl fmt::Display for Error {
@@ -219,32 +366,167 @@ impl fmt::Display for Error {
                     "invalid serial number size total length, have: {have}, expected: {exp}"
                 )
             }
+            Self::InvalidCsvRecord((line, err)) => {
+                write!(f, "invalid CSV record, line: {line}, error: {err}")
+            }
+            Self::Cancelled => write!(f, "operation cancelled"),
+            Self::CountersStore(err) => write!(f, "counters store error: {err}"),
+            Self::Journal(err) => write!(f, "journal error: {err}"),
+            Self::Accounting(err) => write!(f, "accounting error: {err}"),
+            Self::InvalidDirectionDisable((exp, res)) => write!(
+                f,
+                "invalid direction disable, expected: {exp:#x}, read: {res:#x}"
+            ),
+            Self::TamperSuspected((exp, res)) => write!(
+                f,
+                "tamper suspected, program signature mismatch, expected: {exp:02x?}, read: {res:02x?}"
+            ),
+            Self::FrameTooLarge((len, max)) => {
+                write!(f, "frame too large, length: {len}, max: {max}")
+            }
+            Self::InvalidDuration(err) => write!(f, "invalid duration: {err}"),
+            Self::Timeout(err) => write!(f, "timeout: {err}"),
+            Self::UnexpectedResponseLen((code, exp, have)) => {
+                write!(f, "unexpected {code} response length, expected: {exp}, have: {have}")
+            }
+            Self::UidExhausted => write!(f, "no free UID available"),
+            Self::UnassignedUid(err) => write!(f, "no UID assigned to device: {err}"),
+            Self::InvalidUid((have, exp)) => write!(f, "invalid UID, have: {have}, expected: {exp}"),
+            Self::InvalidStateTransition((from, to)) => {
+                write!(f, "invalid device state transition, from: {from}, to: {to}")
+            }
+            Self::FeatureDisabled(feature) => write!(f, "feature disabled: {feature}"),
+            Self::CrashLog(err) => write!(f, "crash log error: {err}"),
+            Self::InvalidBarCodeType(err) => write!(f, "invalid barcode type: {err:#x}"),
+            Self::InvalidBarCodeDigits((bar_code_type, digits)) => write!(
+                f,
+                "invalid barcode digits, type: {bar_code_type:#x}, digits: {digits}"
+            ),
+            Self::InvalidBarCodeSettingsLen((have, exp)) => write!(
+                f,
+                "invalid barcode settings length, have: {have}, expected: {exp}"
+            ),
+            Self::InvalidBarCodeMode(err) => write!(f, "invalid barcode mode: {err:#x}"),
+            Self::CurrencyTableChanged(err) => {
+                write!(f, "currency table changed, re-apply denominations: {err}")
+            }
+            Self::InvalidInsertNotificationMode(err) => {
+                write!(f, "invalid insert notification mode: {err:#x}")
+            }
+            Self::InvalidInsertNotification(err) => {
+                write!(f, "invalid insert notification: {err:#x}")
+            }
+            Self::InvalidInsertNotificationLen((have, exp)) => write!(
+                f,
+                "invalid insert notification length, have: {have}, expected: {exp}"
+            ),
+            Self::InvalidConditionalVendFlag(err) => {
+                write!(f, "invalid conditional vend flag: {err:#x}")
+            }
+            Self::InvalidConditionalVendThresholds((lower, upper)) => write!(
+                f,
+                "invalid conditional vend thresholds, lower: {lower}, upper: {upper}"
+            ),
+            Self::InvalidConditionalVendSettingsLen((have, exp)) => write!(
+                f,
+                "invalid conditional vend settings length, have: {have}, expected: {exp}"
+            ),
+            Self::InvalidConditionalVendMode(err) => {
+                write!(f, "invalid conditional vend mode: {err:#x}")
+            }
+            Self::InvalidPauseFlags(err) => write!(f, "invalid pause flags: {err:#b}"),
+            Self::InvalidPauseSettingsLen((have, exp)) => {
+                write!(f, "invalid pause settings length, have: {have}, expected: {exp}")
+            }
+            Self::InvalidPauseMode(err) => write!(f, "invalid pause mode: {err:#x}"),
+            Self::InterlockEngaged(err) => write!(f, "interlock engaged: {err}"),
+            Self::InvalidEventResendInterval(err) => {
+                write!(f, "invalid event resend interval, 100 ms units: {err}")
+            }
+            Self::InvalidEventResendIntervalLen((have, exp)) => {
+                write!(f, "invalid event resend interval length, have: {have}, expected: {exp}")
+            }
+            Self::InvalidEventResendIntervalMode(err) => {
+                write!(f, "invalid event resend interval mode: {err:#x}")
+            }
+            Self::Failover(err) => write!(f, "failover error: {err}"),
+            Self::SupportBundle(err) => write!(f, "support bundle error: {err}"),
+            Self::InvalidOrientation(err) => write!(f, "invalid note orientation: {err:#x}"),
+            Self::InvalidNoteDataInfoLen((have, exp)) => {
+                write!(f, "invalid note data info length, have: {have}, expected: {exp}")
+            }
+            Self::InvalidImageLen((have, exp)) => {
+                write!(f, "invalid image length, have: {have}, expected: {exp}")
+            }
+            Self::InvalidImageBlockLen((have, exp)) => {
+                write!(f, "invalid image block length, have: {have}, expected: {exp}")
+            }
+            Self::InvalidImageBitDepth(err) => write!(f, "invalid image bit depth: {err}"),
+            Self::InvalidImageDimensions((width, height)) => {
+                write!(f, "invalid image dimensions, width: {width}, height: {height}")
+            }
+            Self::InvalidSerialNumberLen((have, exp)) => {
+                write!(f, "invalid serial number length, have: {have}, expected: {exp}")
+            }
+            Self::InvalidImageKind(err) => write!(f, "invalid image kind: {err}"),
+            Self::Trace(err) => write!(f, "trace error: {err}"),
+            Self::Replay(err) => write!(f, "replay error: {err}"),
+            Self::TruncatedFrame((have, exp)) => {
+                write!(f, "truncated frame, have: {have}, expected: {exp}")
+            }
+            Self::InvalidCurrencyString(err) => write!(f, "invalid currency string: {err}"),
+            Self::DenominationNotAssigned(err) => write!(f, "denomination not assigned: {err}"),
+            Self::InvalidMonetaryAmount(err) => write!(f, "invalid monetary amount: {err}"),
+            Self::Transport(err) => write!(f, "transport error: {err}"),
+            Self::Device(err) => write!(f, "device failure: {}", <&str>::from(err)),
+            Self::RequestPending(err) => write!(f, "request already pending: {err}"),
             Self::InvalidAsciiString => write!(f, "invalid ASCII encoded string"),
             Self::InvalidCString => write!(f, "invalid null-terminated C string"),
             Self::InvalidUtf8String => write!(f, "invalid UTF-8 encoded string"),
             Self::InvalidFirmwareVersion => write!(f, "invalid firmware version"),
-            #[cfg(feature = "usb")]
-            Self::Usb(err) => write!(f, "USB error: {err}"),
+            #[cfg(feature = "serial")]
+            Self::Serial(err) => write!(f, "serial error: {err}"),
+            #[cfg(feature = "demo")]
+            Self::InvalidDemoCommand(err) => write!(f, "invalid demo command: {err}"),
+            #[cfg(feature = "demo")]
+            Self::Demo(err) => write!(f, "demo error: {err}"),
+            #[cfg(feature = "cli")]
+            Self::InvalidCliCommand(err) => write!(f, "invalid CLI command: {err}"),
+            #[cfg(feature = "cli")]
+            Self::Cli(err) => write!(f, "CLI error: {err}"),
         }
     }
 }
 
-impl From<std::ffi::FromBytesUntilNulError> for Error {
-    fn from(_err: std::ffi::FromBytesUntilNulError) -> Self {
+impl From<core::ffi::FromBytesUntilNulError> for Error {
+    fn from(_err: core::ffi::FromBytesUntilNulError) -> Self {
         Self::InvalidCString
     }
 }
 
-impl From<std::str::Utf8Error> for Error {
-    fn from(_err: std::str::Utf8Error) -> Self {
+impl From<core::str::Utf8Error> for Error {
+    fn from(_err: core::str::Utf8Error) -> Self {
         Self::InvalidUtf8String
     }
 }
 
-impl From<std::string::FromUtf8Error> for Error {
-    fn from(_err: std::string::FromUtf8Error) -> Self {
+impl From<alloc::string::FromUtf8Error> for Error {
+    fn from(_err: alloc::string::FromUtf8Error) -> Self {
         Self::InvalidUtf8String
     }
 }
 
-impl std::error::Error for Error {}
+impl From<TransportError> for Error {
+    fn from(err: TransportError) -> Self {
+        Self::Transport(err)
+    }
+}
+
+impl core::error::Error for Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Transport(err) => Some(err),
+            _ => None,
+        }
+    }
+}
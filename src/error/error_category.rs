@@ -0,0 +1,39 @@
+use core::fmt;
+
+/// Represents the broad category of an [Error](crate::Error).
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum ErrorCategory {
+    /// The transport failed, e.g. timeouts, disconnects, or endpoint stalls.
+    Transport,
+    /// The device sent an invalid, unexpected, or refused message.
+    #[default]
+    Protocol,
+    /// The device reported a failure.
+    Device,
+    /// The host failed, e.g. invalid configuration, storage, or API misuse.
+    Host,
+}
+
+impl From<ErrorCategory> for &'static str {
+    fn from(val: ErrorCategory) -> Self {
+        match val {
+            ErrorCategory::Transport => "transport",
+            ErrorCategory::Protocol => "protocol",
+            ErrorCategory::Device => "device",
+            ErrorCategory::Host => "host",
+        }
+    }
+}
+
+impl From<&ErrorCategory> for &'static str {
+    fn from(val: &ErrorCategory) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for ErrorCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
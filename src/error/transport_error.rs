@@ -0,0 +1,195 @@
+use alloc::{string::String, sync::Arc};
+use core::fmt;
+
+/// Represents the source error wrapped by a [TransportError].
+pub type ErrorSource = Arc<dyn core::error::Error + Send + Sync + 'static>;
+
+/// Represents the kind of a [TransportError].
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum TransportErrorKind {
+    /// The transfer did not complete before the timeout expired.
+    Timeout,
+    /// The device is no longer connected.
+    Disconnected,
+    /// The device endpoint signalled a stall condition.
+    Stall,
+    /// Any other transport failure.
+    #[default]
+    Other,
+}
+
+impl TransportErrorKind {
+    /// Gets whether retrying the transfer can succeed.
+    pub const fn is_retryable(&self) -> bool {
+        matches!(self, Self::Timeout | Self::Stall)
+    }
+
+    /// Gets the recommended recovery action.
+    pub const fn recovery_hint(&self) -> &'static str {
+        match self {
+            Self::Timeout => "retry the request, and check the device is powered",
+            Self::Disconnected => "reconnect the device, and reopen the transport",
+            Self::Stall => "retry the request, and reset the device if the stall persists",
+            Self::Other => "check the cable and connectors, and reopen the transport",
+        }
+    }
+}
+
+impl From<TransportErrorKind> for &'static str {
+    fn from(val: TransportErrorKind) -> Self {
+        match val {
+            TransportErrorKind::Timeout => "timeout",
+            TransportErrorKind::Disconnected => "disconnected",
+            TransportErrorKind::Stall => "stall",
+            TransportErrorKind::Other => "other",
+        }
+    }
+}
+
+impl From<&TransportErrorKind> for &'static str {
+    fn from(val: &TransportErrorKind) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for TransportErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
+
+/// Represents a failure of the underlying transport, e.g. USB or serial.
+///
+/// The original error, e.g. the USB transfer error, is preserved as the
+/// [source](std::error::Error::source). Errors compare equal by kind and message.
+#[derive(Clone, Debug)]
+pub struct TransportError {
+    kind: TransportErrorKind,
+    message: String,
+    source: Option<ErrorSource>,
+}
+
+impl TransportError {
+    /// Creates a new [TransportError] from the provided parameters.
+    pub fn new(kind: TransportErrorKind, message: &str) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    /// Builder function that sets the source error of the [TransportError].
+    pub fn with_source<E>(mut self, source: E) -> Self
+    where
+        E: core::error::Error + Send + Sync + 'static,
+    {
+        self.source = Some(Arc::new(source));
+        self
+    }
+
+    /// Gets the [TransportErrorKind].
+    pub const fn kind(&self) -> TransportErrorKind {
+        self.kind
+    }
+
+    /// Gets the error message.
+    pub fn message(&self) -> &str {
+        self.message.as_str()
+    }
+
+    /// Gets whether retrying the transfer can succeed.
+    pub const fn is_retryable(&self) -> bool {
+        self.kind.is_retryable()
+    }
+}
+
+impl PartialEq for TransportError {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.kind == rhs.kind && self.message == rhs.message
+    }
+}
+
+impl Eq for TransportError {}
+
+impl fmt::Display for TransportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", <&str>::from(self.kind), self.message)
+    }
+}
+
+impl core::error::Error for TransportError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|err| err as &(dyn core::error::Error + 'static))
+    }
+}
+
+#[cfg(feature = "usb")]
+impl From<nusb::transfer::TransferError> for TransportErrorKind {
+    fn from(val: nusb::transfer::TransferError) -> Self {
+        use nusb::transfer::TransferError;
+
+        match val {
+            TransferError::Disconnected => Self::Disconnected,
+            TransferError::Stall => Self::Stall,
+            _ => Self::Other,
+        }
+    }
+}
+
+#[cfg(feature = "usb")]
+impl From<nusb::transfer::TransferError> for TransportError {
+    fn from(val: nusb::transfer::TransferError) -> Self {
+        Self::new(val.into(), &val.to_string()).with_source(val)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error as _;
+
+    use super::*;
+    use crate::{Error, ErrorCategory, FailureCode, ResponseCode};
+
+    #[test]
+    fn test_transport_error() {
+        let io = std::io::Error::new(std::io::ErrorKind::BrokenPipe, "pipe closed");
+        let err = Error::from(
+            TransportError::new(TransportErrorKind::Disconnected, "write failed").with_source(io),
+        );
+
+        assert_eq!(err.category(), ErrorCategory::Transport);
+        assert!(!err.is_retryable());
+        assert_eq!(
+            err.recovery_hint(),
+            TransportErrorKind::Disconnected.recovery_hint()
+        );
+        assert_eq!(
+            err.to_string(),
+            "transport error: disconnected: write failed"
+        );
+
+        let source = err.source().and_then(|err| err.source()).unwrap();
+        assert_eq!(source.to_string(), "pipe closed");
+
+        assert!(Error::from(TransportError::new(TransportErrorKind::Stall, "")).is_retryable());
+        assert!(Error::InvalidResponseCode(ResponseCode::Busy.into()).is_retryable());
+        assert!(!Error::InvalidResponseCode(ResponseCode::Nak.into()).is_retryable());
+
+        let err = Error::Device(FailureCode::TransportMotor);
+        assert_eq!(err.category(), ErrorCategory::Device);
+        assert_eq!(
+            err.recovery_hint(),
+            crate::diagnostics::failure_action(FailureCode::TransportMotor)
+        );
+
+        assert_eq!(
+            Error::InvalidMessageLen((1, 2)).category(),
+            ErrorCategory::Protocol
+        );
+        assert_eq!(Error::UidExhausted.category(), ErrorCategory::Host);
+    }
+}
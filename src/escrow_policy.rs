@@ -0,0 +1,642 @@
+use std::fmt;
+
+use crate::{
+    CurrencyCode, EscrowData, EscrowEvent, Media, Message, RejectRequest, StackRequest, UnitNumber,
+};
+
+/// Represents a single rule evaluated against an escrowed note or ticket.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EscrowRule {
+    /// Accept notes of the currency only up to the provided value (inclusive).
+    MaxValue((CurrencyCode, u64)),
+    /// Accept notes of the currency only from the provided value (inclusive).
+    MinValue((CurrencyCode, u64)),
+    /// Whitelist a currency: if any `AcceptCurrency` rules exist, only whitelisted currencies pass.
+    AcceptCurrency(CurrencyCode),
+    /// Blacklist a currency.
+    RejectCurrency(CurrencyCode),
+    /// Reject all tickets.
+    RejectTickets,
+    /// Accept tickets only, rejecting all notes.
+    TicketsOnly,
+    /// Reject all media of the provided kind, e.g. coupons.
+    RejectMedia(Media),
+}
+
+impl EscrowRule {
+    /// Gets whether the [EscrowData] passes the [EscrowRule].
+    ///
+    /// `AcceptCurrency` rules always pass individually, and are combined by the [EscrowPolicy].
+    pub fn passes(&self, data: &EscrowData) -> bool {
+        match (self, data) {
+            (Self::MaxValue((code, max)), EscrowData::Currency(cur)) => {
+                cur.code() != *code || cur.denomination().value() <= *max
+            }
+            (Self::MinValue((code, min)), EscrowData::Currency(cur)) => {
+                cur.code() != *code || cur.denomination().value() >= *min
+            }
+            (Self::RejectCurrency(code), EscrowData::Currency(cur)) => cur.code() != *code,
+            (Self::RejectTickets, EscrowData::Ticket(_)) => false,
+            (Self::TicketsOnly, EscrowData::Currency(_)) => false,
+            (Self::RejectMedia(media), data) => data.media() != *media,
+            _ => true,
+        }
+    }
+}
+
+impl fmt::Display for EscrowRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MaxValue((code, val)) => {
+                write!(
+                    f,
+                    r#"{{"max_value": {{"{}": {val}}}}}"#,
+                    <&str>::from(*code)
+                )
+            }
+            Self::MinValue((code, val)) => {
+                write!(
+                    f,
+                    r#"{{"min_value": {{"{}": {val}}}}}"#,
+                    <&str>::from(*code)
+                )
+            }
+            Self::AcceptCurrency(code) => {
+                write!(f, r#"{{"accept_currency": "{}"}}"#, <&str>::from(*code))
+            }
+            Self::RejectCurrency(code) => {
+                write!(f, r#"{{"reject_currency": "{}"}}"#, <&str>::from(*code))
+            }
+            Self::RejectTickets => write!(f, r#""reject_tickets""#),
+            Self::TicketsOnly => write!(f, r#""tickets_only""#),
+            Self::RejectMedia(media) => write!(f, r#"{{"reject_media": {media}}}"#),
+        }
+    }
+}
+
+/// Represents the decision of an [EscrowPolicy] evaluation.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PolicyDecision {
+    /// Stack the escrowed note/ticket.
+    #[default]
+    Accept,
+    /// Return the escrowed note/ticket to the customer.
+    Reject,
+}
+
+impl From<PolicyDecision> for &'static str {
+    fn from(val: PolicyDecision) -> Self {
+        match val {
+            PolicyDecision::Accept => "accept",
+            PolicyDecision::Reject => "reject",
+        }
+    }
+}
+
+impl From<&PolicyDecision> for &'static str {
+    fn from(val: &PolicyDecision) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for PolicyDecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
+
+/// Represents the result of evaluating a single [EscrowRule].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RuleResult {
+    rule: EscrowRule,
+    passed: bool,
+}
+
+impl RuleResult {
+    /// Gets the evaluated [EscrowRule].
+    pub const fn rule(&self) -> EscrowRule {
+        self.rule
+    }
+
+    /// Gets whether the [EscrowRule] passed.
+    pub const fn passed(&self) -> bool {
+        self.passed
+    }
+}
+
+impl fmt::Display for RuleResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""rule": {}, "#, self.rule)?;
+        write!(f, r#""passed": {}"#, self.passed)?;
+        write!(f, "}}")
+    }
+}
+
+/// Represents the full result of an [EscrowPolicy] evaluation.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PolicyEvaluation {
+    decision: PolicyDecision,
+    results: Vec<RuleResult>,
+}
+
+impl PolicyEvaluation {
+    /// Gets the [PolicyDecision].
+    pub const fn decision(&self) -> PolicyDecision {
+        self.decision
+    }
+
+    /// Gets the per-rule [RuleResult]s, in rule order.
+    pub fn results(&self) -> &[RuleResult] {
+        self.results.as_ref()
+    }
+
+    /// Gets an iterator over the rules that failed.
+    pub fn failed(&self) -> impl Iterator<Item = &RuleResult> {
+        self.results.iter().filter(|r| !r.passed)
+    }
+
+    /// Converts the [PolicyDecision] into the request to send to the device: a `Stack` request
+    /// to the provided box on acceptance, otherwise a `Reject` request.
+    pub fn to_request(&self, stack_box: UnitNumber) -> Message {
+        match self.decision {
+            PolicyDecision::Accept => StackRequest::new().with_stack_box(stack_box).into(),
+            PolicyDecision::Reject => RejectRequest::new().into(),
+        }
+    }
+}
+
+impl fmt::Display for PolicyEvaluation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""decision": {}, "#, self.decision)?;
+        write!(f, r#""results": ["#)?;
+        for (i, res) in self.results.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{res}")?;
+        }
+        write!(f, "]}}")
+    }
+}
+
+/// Represents how ticket values not representable in the credit unit are credited.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TicketRounding {
+    /// Credit the exact ticket value, including amounts not representable in the credit unit.
+    #[default]
+    Exact,
+    /// Credit the ticket value rounded down to the credit unit.
+    RoundDown,
+    /// Return tickets with values not representable in the credit unit.
+    Reject,
+}
+
+impl From<TicketRounding> for &'static str {
+    fn from(val: TicketRounding) -> Self {
+        match val {
+            TicketRounding::Exact => "exact",
+            TicketRounding::RoundDown => "round_down",
+            TicketRounding::Reject => "reject",
+        }
+    }
+}
+
+impl From<&TicketRounding> for &'static str {
+    fn from(val: &TicketRounding) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for TicketRounding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
+
+/// Represents the credit decided for a ticket by a [TicketCreditPolicy].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TicketCredit {
+    value: u64,
+    credit: u64,
+    rounding: TicketRounding,
+    decision: PolicyDecision,
+}
+
+impl TicketCredit {
+    /// Gets the ticket value, as reported by the host ticket system.
+    pub const fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// Gets the credited amount.
+    pub const fn credit(&self) -> u64 {
+        self.credit
+    }
+
+    /// Gets the part of the ticket value that was not credited.
+    pub const fn remainder(&self) -> u64 {
+        self.value.saturating_sub(self.credit)
+    }
+
+    /// Gets the applied [TicketRounding].
+    pub const fn rounding(&self) -> TicketRounding {
+        self.rounding
+    }
+
+    /// Gets the [PolicyDecision] for the ticket.
+    pub const fn decision(&self) -> PolicyDecision {
+        self.decision
+    }
+}
+
+impl fmt::Display for TicketCredit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""value": {}, "#, self.value)?;
+        write!(f, r#""credit": {}, "#, self.credit)?;
+        write!(f, r#""rounding": {}, "#, self.rounding)?;
+        write!(f, r#""decision": {}"#, self.decision)?;
+        write!(f, "}}")
+    }
+}
+
+/// Decides how ticket values map to credits.
+///
+/// Ticket values are representable if they are a multiple of the credit unit, e.g. the smallest
+/// configured denomination. Values are in the same units as the denominations.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TicketCreditPolicy {
+    rounding: TicketRounding,
+    unit: u64,
+}
+
+impl TicketCreditPolicy {
+    /// Creates a new [TicketCreditPolicy], crediting exact ticket values.
+    pub const fn new() -> Self {
+        Self {
+            rounding: TicketRounding::Exact,
+            unit: 1,
+        }
+    }
+
+    /// Creates a new [TicketCreditPolicy] from the provided parameters.
+    ///
+    /// A zero credit unit is treated as one.
+    pub const fn create(rounding: TicketRounding, unit: u64) -> Self {
+        Self {
+            rounding,
+            unit: if unit == 0 { 1 } else { unit },
+        }
+    }
+
+    /// Gets the [TicketRounding].
+    pub const fn rounding(&self) -> TicketRounding {
+        self.rounding
+    }
+
+    /// Builder function that sets the [TicketRounding].
+    pub fn with_rounding(mut self, rounding: TicketRounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Gets the credit unit.
+    pub const fn unit(&self) -> u64 {
+        self.unit
+    }
+
+    /// Builder function that sets the credit unit.
+    ///
+    /// A zero credit unit is treated as one.
+    pub fn with_unit(mut self, unit: u64) -> Self {
+        self.unit = unit.max(1);
+        self
+    }
+
+    /// Decides the [TicketCredit] for the ticket value.
+    pub const fn credit(&self, value: u64) -> TicketCredit {
+        let rounded = value - value % self.unit;
+        let (credit, decision) = match self.rounding {
+            TicketRounding::Exact => (value, PolicyDecision::Accept),
+            TicketRounding::RoundDown if rounded > 0 => (rounded, PolicyDecision::Accept),
+            TicketRounding::Reject if rounded == value && value > 0 => {
+                (value, PolicyDecision::Accept)
+            }
+            _ => (0, PolicyDecision::Reject),
+        };
+
+        TicketCredit {
+            value,
+            credit,
+            rounding: self.rounding,
+            decision,
+        }
+    }
+}
+
+impl Default for TicketCreditPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for TicketCreditPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""rounding": {}, "#, self.rounding)?;
+        write!(f, r#""unit": {}"#, self.unit)?;
+        write!(f, "}}")
+    }
+}
+
+/// Represents the record of an escrowed deposit, and the policy evaluation applied to it.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DepositRecord {
+    data: EscrowData,
+    evaluation: PolicyEvaluation,
+    ticket_credit: Option<TicketCredit>,
+}
+
+impl DepositRecord {
+    /// Gets a reference to the escrowed [EscrowData].
+    pub const fn data(&self) -> &EscrowData {
+        &self.data
+    }
+
+    /// Gets a reference to the [PolicyEvaluation].
+    pub const fn evaluation(&self) -> &PolicyEvaluation {
+        &self.evaluation
+    }
+
+    /// Gets the [TicketCredit] decided for a ticket deposit, if any.
+    pub const fn ticket_credit(&self) -> Option<&TicketCredit> {
+        self.ticket_credit.as_ref()
+    }
+
+    /// Gets whether the deposit was accepted by the policy.
+    pub fn accepted(&self) -> bool {
+        self.evaluation.decision == PolicyDecision::Accept
+    }
+}
+
+impl fmt::Display for DepositRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""data": {}, "#, self.data)?;
+        write!(f, r#""evaluation": {}"#, self.evaluation)?;
+        if let Some(credit) = self.ticket_credit.as_ref() {
+            write!(f, r#", "ticket_credit": {credit}"#)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+/// Represents a set of [EscrowRule]s enforced when a note or ticket reaches escrow.
+///
+/// A deposit is accepted only if every rule passes. An empty policy accepts everything.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{CurrencyCode, EscrowRule, EscrowPolicy};
+///
+/// // accept only USD notes up to $20, and no tickets
+/// let policy = EscrowPolicy::new()
+///     .with_rule(EscrowRule::AcceptCurrency(CurrencyCode::USD))
+///     .with_rule(EscrowRule::MaxValue((CurrencyCode::USD, 20)))
+///     .with_rule(EscrowRule::RejectTickets);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EscrowPolicy {
+    rules: Vec<EscrowRule>,
+    ticket_credit: TicketCreditPolicy,
+}
+
+impl EscrowPolicy {
+    /// Creates a new, empty [EscrowPolicy].
+    pub const fn new() -> Self {
+        Self {
+            rules: Vec::new(),
+            ticket_credit: TicketCreditPolicy::new(),
+        }
+    }
+
+    /// Gets a reference to the list of [EscrowRule]s.
+    pub fn rules(&self) -> &[EscrowRule] {
+        self.rules.as_ref()
+    }
+
+    /// Adds an [EscrowRule] to the [EscrowPolicy].
+    pub fn push_rule(&mut self, rule: EscrowRule) {
+        self.rules.push(rule);
+    }
+
+    /// Builder function that adds an [EscrowRule] to the [EscrowPolicy].
+    pub fn with_rule(mut self, rule: EscrowRule) -> Self {
+        self.push_rule(rule);
+        self
+    }
+
+    /// Gets the [TicketCreditPolicy].
+    pub const fn ticket_credit(&self) -> TicketCreditPolicy {
+        self.ticket_credit
+    }
+
+    /// Sets the [TicketCreditPolicy].
+    pub fn set_ticket_credit(&mut self, policy: TicketCreditPolicy) {
+        self.ticket_credit = policy;
+    }
+
+    /// Builder function that sets the [TicketCreditPolicy].
+    pub fn with_ticket_credit(mut self, policy: TicketCreditPolicy) -> Self {
+        self.set_ticket_credit(policy);
+        self
+    }
+
+    /// Evaluates the [EscrowPolicy] against the [EscrowData].
+    pub fn evaluate(&self, data: &EscrowData) -> PolicyEvaluation {
+        let mut whitelist = self.rules.iter().filter_map(|r| match r {
+            EscrowRule::AcceptCurrency(code) => Some(*code),
+            _ => None,
+        });
+        let whitelisted = match data {
+            EscrowData::Currency(cur) => whitelist.any(|code| code == cur.code()),
+            _ => true,
+        };
+
+        let results: Vec<RuleResult> = self
+            .rules
+            .iter()
+            .map(|&rule| RuleResult {
+                rule,
+                passed: match rule {
+                    EscrowRule::AcceptCurrency(_) => whitelisted,
+                    rule => rule.passes(data),
+                },
+            })
+            .collect();
+
+        let decision = if results.iter().all(|r| r.passed) {
+            PolicyDecision::Accept
+        } else {
+            PolicyDecision::Reject
+        };
+
+        PolicyEvaluation { decision, results }
+    }
+
+    /// Evaluates the [EscrowPolicy] for an [EscrowEvent], producing the [DepositRecord].
+    pub fn on_escrow(&self, event: &EscrowEvent) -> DepositRecord {
+        let data = event.data().clone();
+        let evaluation = self.evaluate(&data);
+
+        if evaluation.decision == PolicyDecision::Reject {
+            log::info!("escrow rejected by policy: {evaluation}");
+        }
+
+        DepositRecord {
+            data,
+            evaluation,
+            ticket_credit: None,
+        }
+    }
+
+    /// Evaluates the [EscrowPolicy] for a ticket [EscrowEvent], producing the [DepositRecord].
+    ///
+    /// The ticket value is provided by the host ticket system. The ticket is rejected if either
+    /// the rules, or the [TicketCreditPolicy], reject it.
+    pub fn on_ticket_escrow(&self, event: &EscrowEvent, value: u64) -> DepositRecord {
+        let mut record = self.on_escrow(event);
+        if record.data.media() != Media::Ticket {
+            return record;
+        }
+
+        let credit = self.ticket_credit.credit(value);
+        if record.accepted() && credit.decision == PolicyDecision::Reject {
+            log::info!("ticket rejected by credit policy: {credit}");
+            record.evaluation.decision = PolicyDecision::Reject;
+        }
+        record.ticket_credit = Some(credit);
+
+        record
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Currency, Denomination, EventType, Ticket};
+
+    fn note(code: CurrencyCode, value: u64) -> EscrowData {
+        EscrowData::new_currency(
+            Currency::new()
+                .with_code(code)
+                .with_denomination(Denomination::from_value(value)),
+        )
+    }
+
+    #[test]
+    fn test_escrow_policy() {
+        let policy = EscrowPolicy::new()
+            .with_rule(EscrowRule::AcceptCurrency(CurrencyCode::USD))
+            .with_rule(EscrowRule::AcceptCurrency(CurrencyCode::EUR))
+            .with_rule(EscrowRule::MaxValue((CurrencyCode::USD, 20)))
+            .with_rule(EscrowRule::RejectTickets);
+
+        let eval = policy.evaluate(&note(CurrencyCode::USD, 20));
+        assert_eq!(eval.decision(), PolicyDecision::Accept);
+        assert_eq!(eval.failed().count(), 0);
+
+        let eval = policy.evaluate(&note(CurrencyCode::USD, 50));
+        assert_eq!(eval.decision(), PolicyDecision::Reject);
+        assert_eq!(
+            eval.failed().map(|r| r.rule()).collect::<Vec<_>>(),
+            [EscrowRule::MaxValue((CurrencyCode::USD, 20))]
+        );
+
+        // EUR is whitelisted, and not limited by the USD maximum
+        let eval = policy.evaluate(&note(CurrencyCode::EUR, 100));
+        assert_eq!(eval.decision(), PolicyDecision::Accept);
+
+        let eval = policy.evaluate(&note(CurrencyCode::JPY, 1000));
+        assert_eq!(eval.decision(), PolicyDecision::Reject);
+        assert_eq!(eval.failed().count(), 2);
+
+        let eval = policy.evaluate(&EscrowData::new_ticket(Ticket::new()));
+        assert_eq!(eval.decision(), PolicyDecision::Reject);
+        assert_eq!(
+            eval.to_request(UnitNumber::new()),
+            RejectRequest::new().into()
+        );
+    }
+
+    #[test]
+    fn test_escrow_policy_tickets_only() {
+        let policy = EscrowPolicy::new()
+            .with_rule(EscrowRule::RejectCurrency(CurrencyCode::JPY))
+            .with_rule(EscrowRule::TicketsOnly);
+
+        let record = policy.on_escrow(&EscrowEvent::create(
+            EventType::Sequence0,
+            note(CurrencyCode::USD, 1),
+        ));
+        assert!(!record.accepted());
+        assert!(record.evaluation().results()[0].passed());
+        assert!(!record.evaluation().results()[1].passed());
+
+        let ticket = EscrowData::new_ticket(Ticket::new());
+        assert_eq!(policy.evaluate(&ticket).decision(), PolicyDecision::Accept);
+
+        assert_eq!(
+            EscrowPolicy::new().evaluate(&ticket).decision(),
+            PolicyDecision::Accept
+        );
+
+        let ticket_event = EscrowEvent::create(EventType::Sequence0, ticket.clone());
+        let rounding = |rounding| {
+            EscrowPolicy::new()
+                .with_ticket_credit(TicketCreditPolicy::create(rounding, 5))
+                .on_ticket_escrow(&ticket_event, 12)
+        };
+
+        let record = rounding(TicketRounding::Exact);
+        assert!(record.accepted());
+        assert_eq!(record.ticket_credit().map(|c| c.credit()), Some(12));
+
+        let record = rounding(TicketRounding::RoundDown);
+        assert!(record.accepted());
+        assert_eq!(record.ticket_credit().map(|c| c.remainder()), Some(2));
+
+        let record = rounding(TicketRounding::Reject);
+        assert!(!record.accepted());
+        assert_eq!(
+            record.ticket_credit().map(|c| c.decision()),
+            Some(PolicyDecision::Reject)
+        );
+
+        let coupon = EscrowData::Coupon(Ticket::new());
+        assert_eq!(policy.evaluate(&coupon).decision(), PolicyDecision::Accept);
+        assert_eq!(
+            policy
+                .with_rule(EscrowRule::RejectMedia(Media::Coupon))
+                .evaluate(&coupon)
+                .decision(),
+            PolicyDecision::Reject
+        );
+    }
+}
@@ -0,0 +1,261 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use crate::{EventCode, Message};
+
+/// Represents the progress of the note cycle processed by an [EscrowQueue].
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum NoteCycle {
+    /// No note is being processed.
+    #[default]
+    Idle,
+    /// A note was inserted, and has not reached escrow yet.
+    Inserted,
+    /// A note reached escrow, and its vend cycle is not finished yet.
+    Escrowed,
+}
+
+impl From<NoteCycle> for &'static str {
+    fn from(val: NoteCycle) -> Self {
+        match val {
+            NoteCycle::Idle => "idle",
+            NoteCycle::Inserted => "inserted",
+            NoteCycle::Escrowed => "escrowed",
+        }
+    }
+}
+
+impl From<&NoteCycle> for &'static str {
+    fn from(val: &NoteCycle) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for NoteCycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
+
+/// Serializes note processing for fast insert sequences.
+///
+/// A new `Insert` or `Escrow` event can arrive while the vend cycle of the previous note is
+/// finishing. The [EscrowQueue] holds it back until the cycle ends with an `Idle`, `Inhibit`, or
+/// `Power Up` event, so the session layer processes one note at a time, and never loses events
+/// between cycles. Events are never dropped: the queue grows as needed, and records its
+/// [max_depth](Self::max_depth).
+///
+/// Pass every device event to [push](Self::push), and process the events returned by
+/// [pop](Self::pop) in order.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EscrowQueue {
+    cycle: NoteCycle,
+    pending: VecDeque<Message>,
+    ready: VecDeque<Message>,
+    max_depth: usize,
+}
+
+impl EscrowQueue {
+    /// Creates a new [EscrowQueue].
+    pub const fn new() -> Self {
+        Self {
+            cycle: NoteCycle::Idle,
+            pending: VecDeque::new(),
+            ready: VecDeque::new(),
+            max_depth: 0,
+        }
+    }
+
+    /// Gets the [NoteCycle] of the note being processed.
+    pub const fn cycle(&self) -> NoteCycle {
+        self.cycle
+    }
+
+    /// Gets the number of events held back until the current cycle ends.
+    pub fn depth(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Gets the highest [depth](Self::depth) seen.
+    pub const fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Gets whether events are ready to [pop](Self::pop).
+    pub fn is_ready(&self) -> bool {
+        !self.ready.is_empty()
+    }
+
+    /// Adds a device event [Message] to the [EscrowQueue].
+    ///
+    /// Non-event messages are ignored.
+    pub fn push(&mut self, event: &Message) {
+        let code = match event.data().message_code().event_code() {
+            Ok(code) => code,
+            Err(_) => return,
+        };
+
+        if !self.pending.is_empty() && Self::starts_cycle(code) {
+            // keep the arrival order of held notes
+            self.hold(event);
+            return;
+        }
+
+        match (self.cycle, code) {
+            (NoteCycle::Idle, EventCode::Insert) => self.start(event, NoteCycle::Inserted),
+            (NoteCycle::Idle | NoteCycle::Inserted, EventCode::Escrow) => {
+                self.start(event, NoteCycle::Escrowed)
+            }
+            (NoteCycle::Inserted | NoteCycle::Escrowed, EventCode::Insert)
+            | (NoteCycle::Escrowed, EventCode::Escrow) => self.hold(event),
+            (_, code) if Self::ends_cycle(code) => {
+                self.ready.push_back(event.clone());
+                self.cycle = NoteCycle::Idle;
+                self.release();
+            }
+            _ => self.ready.push_back(event.clone()),
+        }
+    }
+
+    /// Takes the next event ready for processing, if any.
+    pub fn pop(&mut self) -> Option<Message> {
+        self.ready.pop_front()
+    }
+
+    /// Discards held and ready events, e.g. after a device reset.
+    pub fn clear(&mut self) {
+        self.cycle = NoteCycle::Idle;
+        self.pending.clear();
+        self.ready.clear();
+    }
+
+    const fn starts_cycle(code: EventCode) -> bool {
+        matches!(code, EventCode::Insert | EventCode::Escrow)
+    }
+
+    const fn ends_cycle(code: EventCode) -> bool {
+        matches!(
+            code,
+            EventCode::Idle
+                | EventCode::Inhibit
+                | EventCode::PowerUp
+                | EventCode::PowerUpAcceptor
+                | EventCode::PowerUpStacker
+                | EventCode::PowerUpAcceptorAccepting
+                | EventCode::PowerUpStackerAccepting
+        )
+    }
+
+    fn start(&mut self, event: &Message, cycle: NoteCycle) {
+        self.cycle = cycle;
+        self.ready.push_back(event.clone());
+    }
+
+    fn hold(&mut self, event: &Message) {
+        self.pending.push_back(event.clone());
+        self.max_depth = self.max_depth.max(self.pending.len());
+        log::debug!(
+            "holding event until the note cycle ends, depth: {}",
+            self.pending.len()
+        );
+    }
+
+    /// Releases held events up to the start of the next note cycle.
+    fn release(&mut self) {
+        while let Some(event) = self.pending.pop_front() {
+            let code = event.data().message_code().event_code().ok();
+            self.ready.push_back(event);
+
+            match code {
+                Some(EventCode::Insert) => {
+                    self.cycle = NoteCycle::Inserted;
+                    // a following escrow belongs to the released note
+                    if self
+                        .pending
+                        .front()
+                        .and_then(|evt| evt.data().message_code().event_code().ok())
+                        == Some(EventCode::Escrow)
+                    {
+                        self.cycle = NoteCycle::Escrowed;
+                        self.ready.extend(self.pending.pop_front());
+                    }
+                    break;
+                }
+                Some(EventCode::Escrow) => {
+                    self.cycle = NoteCycle::Escrowed;
+                    break;
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+impl fmt::Display for EscrowQueue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""cycle": {}, "#, self.cycle)?;
+        write!(f, r#""depth": {}, "#, self.pending.len())?;
+        write!(f, r#""ready": {}, "#, self.ready.len())?;
+        write!(f, r#""max_depth": {}"#, self.max_depth)?;
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventType, MessageCode, MessageData, MessageType};
+
+    fn event(code: EventCode) -> Message {
+        Message::new().with_data(
+            MessageData::new()
+                .with_message_type(MessageType::Event(EventType::Sequence0))
+                .with_message_code(MessageCode::Event(code)),
+        )
+    }
+
+    #[test]
+    fn test_escrow_queue() {
+        let mut queue = EscrowQueue::new();
+        let mut popped = Vec::new();
+        let mut drain = |queue: &mut EscrowQueue| {
+            while let Some(evt) = queue.pop() {
+                popped.push(evt.data().message_code().event_code().unwrap());
+            }
+        };
+
+        for code in [EventCode::Insert, EventCode::Escrow, EventCode::VendValid] {
+            queue.push(&event(code));
+        }
+        drain(&mut queue);
+        assert_eq!(queue.cycle(), NoteCycle::Escrowed);
+
+        // the next note arrives before the first cycle finishes
+        queue.push(&event(EventCode::Insert));
+        queue.push(&event(EventCode::AcceptorCollected));
+        queue.push(&event(EventCode::Escrow));
+        assert_eq!(queue.depth(), 2);
+        drain(&mut queue);
+
+        queue.push(&event(EventCode::Idle));
+        assert_eq!(queue.depth(), 0);
+        assert_eq!(queue.max_depth(), 2);
+        assert_eq!(queue.cycle(), NoteCycle::Escrowed);
+        drain(&mut queue);
+
+        assert_eq!(
+            popped,
+            [
+                EventCode::Insert,
+                EventCode::Escrow,
+                EventCode::VendValid,
+                EventCode::AcceptorCollected,
+                EventCode::Idle,
+                EventCode::Insert,
+                EventCode::Escrow,
+            ]
+        );
+    }
+}
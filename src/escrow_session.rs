@@ -0,0 +1,297 @@
+use std::{fmt, time};
+
+use crate::{
+    poll_typed, Clock, Error, EscrowEvent, EventCode, HoldRequest, Message, RejectRequest,
+    Response, ResponseCode, Result, StackRequest,
+};
+
+/// Represents the default time to wait for the device to finish stacking or returning a note.
+pub const DEFAULT_ESCROW_EVENT_TIMEOUT: time::Duration = time::Duration::from_secs(10);
+
+/// Represents how an [EscrowSession] ended.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum EscrowOutcome {
+    /// The note was stacked, and reached `Vend Valid`.
+    Stacked,
+    /// The note was returned to the user.
+    Returned,
+    /// The device rejected the note.
+    Rejected,
+}
+
+impl From<EscrowOutcome> for &'static str {
+    fn from(val: EscrowOutcome) -> Self {
+        match val {
+            EscrowOutcome::Stacked => "stacked",
+            EscrowOutcome::Returned => "returned",
+            EscrowOutcome::Rejected => "rejected",
+        }
+    }
+}
+
+impl From<&EscrowOutcome> for &'static str {
+    fn from(val: &EscrowOutcome) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for EscrowOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
+
+/// Represents a note held in escrow, waiting for the host decision.
+///
+/// Created from an `Escrow` event, the session sends the operation requests through the polling
+/// function, and waits for the resulting events through the event function. The event function
+/// receives the next device event within the provided timeout, and is responsible for
+/// acknowledging it.
+///
+/// After a [hold](Self::hold), the device returns the note on its own when the hold timeout
+/// expires. The session tracks the deadline, and fails [accept](Self::accept) and
+/// [hold](Self::hold) with [Error::Timeout] once it has passed.
+pub struct EscrowSession<'c, C: Clock + ?Sized> {
+    clock: &'c C,
+    escrow: EscrowEvent,
+    hold_deadline: Option<time::Duration>,
+    event_timeout: time::Duration,
+    outcome: Option<EscrowOutcome>,
+}
+
+impl<'c, C: Clock + ?Sized> EscrowSession<'c, C> {
+    /// Creates a new [EscrowSession] from an `Escrow` event [Message].
+    pub fn create(clock: &'c C, event: &Message) -> Result<Self> {
+        Ok(Self {
+            clock,
+            escrow: EscrowEvent::try_from(event)?,
+            hold_deadline: None,
+            event_timeout: DEFAULT_ESCROW_EVENT_TIMEOUT,
+            outcome: None,
+        })
+    }
+
+    /// Gets a reference to the [EscrowEvent] that opened the session.
+    pub const fn escrow(&self) -> &EscrowEvent {
+        &self.escrow
+    }
+
+    /// Gets the [EscrowOutcome], if the session ended.
+    pub const fn outcome(&self) -> Option<EscrowOutcome> {
+        self.outcome
+    }
+
+    /// Gets the time to wait for the device to finish stacking or returning the note.
+    pub const fn event_timeout(&self) -> time::Duration {
+        self.event_timeout
+    }
+
+    /// Builder function that sets the time to wait for the device to finish stacking or returning
+    /// the note.
+    pub fn with_event_timeout(mut self, val: time::Duration) -> Self {
+        self.event_timeout = val;
+        self
+    }
+
+    /// Gets the time left before the device returns a held note, if held.
+    pub fn hold_remaining(&self) -> Option<time::Duration> {
+        self.hold_deadline
+            .map(|deadline| deadline.saturating_sub(self.clock.now()))
+    }
+
+    /// Gets whether the hold timeout expired.
+    pub fn is_expired(&self) -> bool {
+        self.hold_remaining() == Some(time::Duration::ZERO)
+    }
+
+    /// Stacks the note, waiting for the `Vend Valid` event.
+    pub fn accept<P, E>(&mut self, mut poll: P, events: E) -> Result<EscrowOutcome>
+    where
+        P: FnMut(&Message) -> Result<Message>,
+        E: FnMut(time::Duration) -> Result<Message>,
+    {
+        self.check_open("accept")?;
+        ack(poll(&StackRequest::new().into())?)?;
+        self.wait(events)
+    }
+
+    /// Returns the note to the user, waiting for the `Returned` event.
+    ///
+    /// Returning is allowed after the hold timeout expired, to collect the outcome of the
+    /// device-initiated return.
+    pub fn return_note<P, E>(&mut self, mut poll: P, events: E) -> Result<EscrowOutcome>
+    where
+        P: FnMut(&Message) -> Result<Message>,
+        E: FnMut(time::Duration) -> Result<Message>,
+    {
+        if let Some(outcome) = self.outcome {
+            return Err(Error::Timeout(format!("escrow session ended: {outcome}")));
+        }
+
+        if !self.is_expired() {
+            ack(poll(&RejectRequest::new().into())?)?;
+        }
+
+        self.wait(events)
+    }
+
+    /// Holds the note in escrow for the provided duration, rounded up to whole seconds.
+    ///
+    /// Each call extends the hold, restarting the hold timeout. Holding is only valid while the
+    /// note is in escrow, so this fails once the session ended, or the hold timeout expired,
+    /// without sending the request.
+    pub fn hold<P>(&mut self, duration: time::Duration, mut poll: P) -> Result<()>
+    where
+        P: FnMut(&Message) -> Result<Message>,
+    {
+        self.check_open("hold")?;
+
+        let secs = duration
+            .as_secs()
+            .saturating_add(u64::from(duration.subsec_nanos() > 0));
+        let secs = u16::try_from(secs)
+            .map_err(|_| Error::InvalidDuration(format!("hold duration too long: {secs}s")))?;
+
+        let res = poll_typed(HoldRequest::create(secs), &mut poll)?;
+        if !res.is_held() {
+            return Err(Error::InvalidResponseCode(res.code().into()));
+        }
+
+        self.hold_deadline = Some(self.clock.now() + time::Duration::from_secs(u64::from(secs)));
+        log::debug!("holding escrow for {secs}s");
+
+        Ok(())
+    }
+
+    fn check_open(&self, op: &str) -> Result<()> {
+        match self.outcome {
+            Some(outcome) => Err(Error::Timeout(format!(
+                "escrow {op}: session ended: {outcome}"
+            ))),
+            None if self.is_expired() => {
+                Err(Error::Timeout(format!("escrow {op}: hold timeout expired")))
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn wait<E>(&mut self, mut events: E) -> Result<EscrowOutcome>
+    where
+        E: FnMut(time::Duration) -> Result<Message>,
+    {
+        let start = self.clock.now();
+
+        loop {
+            let remaining = self.event_timeout.saturating_sub(self.clock.elapsed(start));
+            if remaining.is_zero() {
+                return Err(Error::Timeout(
+                    "escrow session: waiting for the outcome".into(),
+                ));
+            }
+
+            let event = events(remaining)?;
+            let outcome = match event.data().message_code().event_code() {
+                Ok(EventCode::VendValid) => EscrowOutcome::Stacked,
+                Ok(EventCode::Returned) => EscrowOutcome::Returned,
+                Ok(EventCode::Rejected | EventCode::AcceptorRejected) => EscrowOutcome::Rejected,
+                _ => continue,
+            };
+
+            log::debug!("escrow session ended: {outcome}");
+            self.outcome = Some(outcome);
+
+            return Ok(outcome);
+        }
+    }
+}
+
+impl<C: Clock + ?Sized> fmt::Display for EscrowSession<'_, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""escrow": {}, "#, self.escrow.data())?;
+        match self.hold_remaining() {
+            Some(rem) => write!(f, r#""hold_remaining_ms": {}, "#, rem.as_millis())?,
+            None => write!(f, r#""hold_remaining_ms": null, "#)?,
+        }
+        match self.outcome {
+            Some(outcome) => write!(f, r#""outcome": {outcome}"#)?,
+            None => write!(f, r#""outcome": null"#)?,
+        }
+        write!(f, "}}")
+    }
+}
+
+fn ack(res: Message) -> Result<()> {
+    match Response::try_from(res)?.code() {
+        ResponseCode::Ack => Ok(()),
+        code => Err(Error::InvalidResponseCode(code.into())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockDevice;
+    use crate::{event_ack, Currency, IdleRequest, SimulatedClock};
+
+    fn events(device: &MockDevice) -> impl FnMut(time::Duration) -> Result<Message> + '_ {
+        |_| {
+            let event = device
+                .pending_event()
+                .ok_or(Error::Timeout("no event".into()))?;
+            device.acknowledge_event(&event_ack(&event))?;
+            Ok(event)
+        }
+    }
+
+    #[test]
+    fn test_escrow_session() -> Result<()> {
+        let clock = SimulatedClock::new();
+        let device = MockDevice::new();
+        let mut next = events(&device);
+
+        next(time::Duration::ZERO)?;
+        device.handle_request(&IdleRequest::new().into())?;
+        next(time::Duration::ZERO)?;
+
+        assert!(device.insert_note(Currency::new()));
+        let mut session = EscrowSession::create(&clock, &next(time::Duration::ZERO)?)?;
+
+        session.hold(time::Duration::from_millis(1500), |req| {
+            device.handle_request(req)
+        })?;
+        assert_eq!(session.hold_remaining(), Some(time::Duration::from_secs(2)));
+
+        clock.advance(time::Duration::from_secs(1));
+        assert_eq!(
+            session.accept(|req| device.handle_request(req), &mut next)?,
+            EscrowOutcome::Stacked
+        );
+        assert!(session
+            .accept(|req| device.handle_request(req), &mut next)
+            .is_err());
+        assert!(session
+            .hold(time::Duration::from_secs(1), |_| -> Result<Message> {
+                panic!("hold sent after the session ended")
+            })
+            .is_err());
+
+        // the device returns a held note after the timeout
+        next(time::Duration::ZERO)?;
+        next(time::Duration::ZERO)?;
+        assert!(device.insert_note(Currency::new()));
+        let mut session = EscrowSession::create(&clock, &next(time::Duration::ZERO)?)?;
+        session.hold(time::Duration::from_secs(1), |req| {
+            device.handle_request(req)
+        })?;
+
+        clock.advance(time::Duration::from_secs(1));
+        assert!(session.is_expired());
+        assert!(session
+            .accept(|req| device.handle_request(req), &mut next)
+            .is_err());
+
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use std::fmt;
+use core::fmt;
 
 use crate::{Error, Result};
 
@@ -19,6 +19,7 @@ const RESERVED: u8 = 0xff;
 /// Represents JCM device failure codes.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FailureCode {
     TransportMotor = TRANSPORT_MOTOR,
     StackMotor = STACK_MOTOR,
@@ -59,6 +60,11 @@ impl FailureCode {
             _ => Self::Reserved,
         }
     }
+
+    /// Converts a [FailureCode] into a [`u8`].
+    pub const fn to_u8(&self) -> u8 {
+        *self as u8
+    }
 }
 
 impl Default for FailureCode {
@@ -67,6 +73,18 @@ impl Default for FailureCode {
     }
 }
 
+impl From<FailureCode> for u8 {
+    fn from(val: FailureCode) -> Self {
+        val.to_u8()
+    }
+}
+
+impl From<&FailureCode> for u8 {
+    fn from(val: &FailureCode) -> Self {
+        val.to_u8()
+    }
+}
+
 impl From<&FailureCode> for &'static str {
     fn from(val: &FailureCode) -> Self {
         match val {
@@ -145,7 +163,7 @@ mod tests {
             FailureCode::Abnormal,
         ];
 
-        for (raw, exp) in raw_denom.into_iter().zip(expected.into_iter()) {
+        for (raw, exp) in raw_denom.into_iter().zip(expected) {
             assert_eq!(FailureCode::try_from(raw), Ok(exp));
             assert_eq!(FailureCode::from_u8(raw), exp);
         }
@@ -0,0 +1,262 @@
+use core::fmt;
+
+use crate::{Error, FirmwareRevision, FirmwareVersion, Message, RequestCode, Result};
+
+/// Represents an advanced device subsystem, only available on newer firmware.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Feature {
+    /// Note image and note data retrieval.
+    NoteImage = 0,
+    /// Recycler collection and dispense.
+    Recycler = 1,
+    /// Security mode configuration.
+    SecurityMode = 2,
+}
+
+impl Feature {
+    /// Represents every [Feature].
+    pub const ALL: [Self; 3] = [Self::NoteImage, Self::Recycler, Self::SecurityMode];
+
+    /// Gets the [RequestCode]s only available when the [Feature] is enabled.
+    pub const fn request_codes(&self) -> &'static [RequestCode] {
+        match self {
+            Self::NoteImage => &[RequestCode::NoteDataInfo],
+            Self::Recycler => &[RequestCode::RecyclerCollect, RequestCode::RecyclerDispense],
+            Self::SecurityMode => &[],
+        }
+    }
+
+    /// Gets the first [FirmwareRevision] supporting the [Feature].
+    pub const fn min_revision(&self) -> FirmwareRevision {
+        match self {
+            Self::NoteImage => FirmwareRevision::create(2, 0, 0),
+            Self::Recycler => FirmwareRevision::create(2, 0, 0),
+            Self::SecurityMode => FirmwareRevision::create(2, 10, 0),
+        }
+    }
+
+    /// Gets the [Feature] gating the [RequestCode], if any.
+    pub fn from_request_code(code: RequestCode) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|feature| feature.request_codes().contains(&code))
+    }
+
+    const fn mask(&self) -> u8 {
+        1 << (*self as u8)
+    }
+}
+
+impl From<Feature> for &'static str {
+    fn from(val: Feature) -> Self {
+        match val {
+            Feature::NoteImage => "note image",
+            Feature::Recycler => "recycler",
+            Feature::SecurityMode => "security mode",
+        }
+    }
+}
+
+impl From<&Feature> for &'static str {
+    fn from(val: &Feature) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
+
+/// Represents the set of enabled advanced [Feature]s of a device.
+///
+/// Build the set from the device firmware with [for_firmware](Self::for_firmware), so subsystems
+/// the firmware predates are disabled up front, with a log message, instead of failing
+/// mid-transaction. Wrap a polling function with [gate](Self::gate) to refuse requests for
+/// disabled features before they reach the device.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{Feature, FeatureSet, FirmwareVersion};
+///
+/// let set = FeatureSet::for_firmware(&FirmwareVersion::new().with_version("V1.05"));
+/// assert!(!set.contains(Feature::Recycler));
+///
+/// let set = FeatureSet::for_firmware(&FirmwareVersion::new().with_version("V2.03"));
+/// assert!(set.contains(Feature::Recycler));
+/// assert!(!set.contains(Feature::SecurityMode));
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct FeatureSet(u8);
+
+impl FeatureSet {
+    /// Creates a new [FeatureSet] with every [Feature] enabled.
+    pub const fn all() -> Self {
+        Self(Feature::NoteImage.mask() | Feature::Recycler.mask() | Feature::SecurityMode.mask())
+    }
+
+    /// Creates a new, empty [FeatureSet].
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Creates a [FeatureSet] with the [Feature]s supported by the [FirmwareRevision].
+    pub fn for_revision(revision: FirmwareRevision) -> Self {
+        Feature::ALL.into_iter().fold(Self::all(), |set, feature| {
+            if revision < feature.min_revision() {
+                log::warn!(
+                    "disabling {} feature: firmware revision {revision} predates {}",
+                    <&str>::from(feature),
+                    feature.min_revision()
+                );
+                set.without_feature(feature)
+            } else {
+                set
+            }
+        })
+    }
+
+    /// Creates a [FeatureSet] with the [Feature]s supported by the [FirmwareVersion].
+    ///
+    /// If the version does not contain a numeric revision, every [Feature] stays enabled, and
+    /// unsupported requests are left for the device to refuse.
+    pub fn for_firmware(firmware: &FirmwareVersion) -> Self {
+        match firmware.revision() {
+            Some(revision) => Self::for_revision(revision),
+            None => {
+                log::warn!(
+                    "unknown firmware revision: {}, leaving all features enabled",
+                    firmware.version()
+                );
+                Self::all()
+            }
+        }
+    }
+
+    /// Gets whether the [Feature] is enabled.
+    pub const fn contains(&self, feature: Feature) -> bool {
+        self.0 & feature.mask() != 0
+    }
+
+    /// Gets whether the [FeatureSet] is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Enables the [Feature].
+    pub fn insert(&mut self, feature: Feature) {
+        self.0 |= feature.mask();
+    }
+
+    /// Disables the [Feature].
+    pub fn remove(&mut self, feature: Feature) {
+        self.0 &= !feature.mask();
+    }
+
+    /// Builder function that enables the [Feature].
+    pub fn with_feature(mut self, feature: Feature) -> Self {
+        self.insert(feature);
+        self
+    }
+
+    /// Builder function that disables the [Feature].
+    pub fn without_feature(mut self, feature: Feature) -> Self {
+        self.remove(feature);
+        self
+    }
+
+    /// Gets an iterator over the enabled [Feature]s.
+    pub fn iter(&self) -> impl Iterator<Item = Feature> + '_ {
+        Feature::ALL
+            .into_iter()
+            .filter(|feature| self.contains(*feature))
+    }
+
+    /// Checks whether the [RequestCode] is allowed by the [FeatureSet].
+    ///
+    /// Returns [Error::FeatureDisabled] if the request belongs to a disabled [Feature].
+    pub fn check(&self, code: RequestCode) -> Result<()> {
+        match Feature::from_request_code(code) {
+            Some(feature) if !self.contains(feature) => Err(Error::FeatureDisabled(feature)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Wraps a polling function, refusing requests for disabled [Feature]s before sending them.
+    pub fn gate<'a, P>(&'a self, mut poll: P) -> impl FnMut(&Message) -> Result<Message> + 'a
+    where
+        P: FnMut(&Message) -> Result<Message> + 'a,
+    {
+        move |req: &Message| {
+            if let Ok(code) = req.data().message_code().request_code() {
+                self.check(code).inspect_err(|err| {
+                    log::warn!("refusing {code} request: {err}");
+                })?;
+            }
+
+            poll(req)
+        }
+    }
+}
+
+impl Default for FeatureSet {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl fmt::Display for FeatureSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[")?;
+        for (i, feature) in self.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{feature}")?;
+        }
+        write!(f, "]")
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::mock::MockDevice;
+    use crate::{DispenseRequest, StatusRequest};
+
+    #[test]
+    fn test_feature_set() -> Result<()> {
+        let old = FeatureSet::for_firmware(&FirmwareVersion::new().with_version("V1.20"));
+        assert!(old.is_empty());
+
+        let set = FeatureSet::for_firmware(&FirmwareVersion::new().with_version("V2.01"));
+        assert!(set.contains(Feature::NoteImage));
+        assert!(set.contains(Feature::Recycler));
+        assert!(!set.contains(Feature::SecurityMode));
+        assert_eq!(set.to_string(), r#"["note image", "recycler"]"#);
+
+        let unknown = FeatureSet::for_firmware(&FirmwareVersion::new().with_version("custom"));
+        assert_eq!(unknown, FeatureSet::all());
+
+        assert_eq!(
+            old.check(RequestCode::RecyclerDispense),
+            Err(Error::FeatureDisabled(Feature::Recycler))
+        );
+        assert_eq!(old.check(RequestCode::Status), Ok(()));
+
+        let device = MockDevice::new();
+        let mut poll = old.gate(|req: &Message| device.handle_request(req));
+
+        assert!(poll(&StatusRequest::new().into()).is_ok());
+        assert_eq!(
+            poll(&DispenseRequest::new().into()),
+            Err(Error::FeatureDisabled(Feature::Recycler))
+        );
+
+        Ok(())
+    }
+}
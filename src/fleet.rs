@@ -0,0 +1,410 @@
+//! Coordination of multiple acceptors in one installation.
+//!
+//! A [FailoverCoordinator] pairs a primary acceptor with a warm-standby backup. The standby is
+//! kept inhibited, so only one acceptor takes notes, while polled requests are routed to the
+//! active acceptor. The coordinator monitors the health of the active acceptor with `Status`
+//! requests, and after [max_failures](FailoverCoordinator::max_failures) consecutive failed
+//! checks, inhibits it and enables the standby.
+//!
+//! There is no automatic fail-back, a recovered acceptor stays the inhibited standby until the
+//! host calls [failover](FailoverCoordinator::failover).
+//!
+//! ```
+//! use jcm::fleet::{AcceptorSlot, FailoverCoordinator};
+//! use jcm::mock::MockDevice;
+//! use jcm::FailureCode;
+//!
+//! # fn main() -> jcm::Result<()> {
+//! let (primary, backup) = (MockDevice::new(), MockDevice::new());
+//! let mut fleet = FailoverCoordinator::new(
+//!     |req: &jcm::Message| primary.handle_request(req),
+//!     |req: &jcm::Message| backup.handle_request(req),
+//! )
+//! .with_max_failures(1);
+//!
+//! fleet.start()?;
+//! assert_eq!(fleet.check(), None);
+//!
+//! primary.jam(FailureCode::TransportMotor);
+//! let event = fleet.check().unwrap();
+//!
+//! assert_eq!(event.to(), AcceptorSlot::Backup);
+//! assert_eq!(fleet.active(), AcceptorSlot::Backup);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::audit::{AuditEntry, AuditRecord, EventLog};
+use crate::{
+    Error, IdleRequest, InhibitRequest, MajorMinorStatus, Message, Response, ResponseCode, Result,
+    StatusRequest, StatusResponse,
+};
+
+/// Represents the default number of consecutive failed health checks before failing over.
+pub const DEFAULT_MAX_FAILURES: usize = 3;
+
+/// Represents an acceptor position in a [FailoverCoordinator].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum AcceptorSlot {
+    /// The preferred acceptor, active on start.
+    Primary,
+    /// The warm-standby acceptor.
+    Backup,
+}
+
+impl AcceptorSlot {
+    /// Gets the other [AcceptorSlot] of the pair.
+    pub const fn other(&self) -> Self {
+        match self {
+            Self::Primary => Self::Backup,
+            Self::Backup => Self::Primary,
+        }
+    }
+}
+
+impl From<AcceptorSlot> for &'static str {
+    fn from(val: AcceptorSlot) -> Self {
+        match val {
+            AcceptorSlot::Primary => "primary",
+            AcceptorSlot::Backup => "backup",
+        }
+    }
+}
+
+impl From<&AcceptorSlot> for &'static str {
+    fn from(val: &AcceptorSlot) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for AcceptorSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
+
+/// Represents a switch of the active acceptor.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FailoverEvent {
+    from: AcceptorSlot,
+    to: AcceptorSlot,
+    reason: String,
+}
+
+impl FailoverEvent {
+    /// Creates a new [FailoverEvent] from the provided parameters.
+    pub fn create(from: AcceptorSlot, reason: &str) -> Self {
+        Self {
+            from,
+            to: from.other(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Gets the previously active [AcceptorSlot].
+    pub const fn from(&self) -> AcceptorSlot {
+        self.from
+    }
+
+    /// Gets the newly active [AcceptorSlot].
+    pub const fn to(&self) -> AcceptorSlot {
+        self.to
+    }
+
+    /// Gets the reason for the switch.
+    pub fn reason(&self) -> &str {
+        self.reason.as_str()
+    }
+}
+
+impl fmt::Display for FailoverEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""from": {}, "#, self.from)?;
+        write!(f, r#""to": {}, "#, self.to)?;
+        write!(f, r#""reason": "{}""#, self.reason)?;
+        write!(f, "}}")
+    }
+}
+
+/// Keeps one of two acceptors active, failing over to the warm standby.
+///
+/// Each acceptor is driven through its own polling function, e.g. a closure over a
+/// [UsbDeviceHandle](crate::usb::UsbDeviceHandle), or a [MockDevice](crate::mock::MockDevice) in
+/// tests. Failovers are recorded to the optional [EventLog].
+pub struct FailoverCoordinator<P, B> {
+    primary: P,
+    backup: B,
+    active: AcceptorSlot,
+    failures: usize,
+    max_failures: usize,
+    failovers: Vec<FailoverEvent>,
+    log: Option<Arc<dyn EventLog>>,
+}
+
+impl<P, B> FailoverCoordinator<P, B>
+where
+    P: FnMut(&Message) -> Result<Message>,
+    B: FnMut(&Message) -> Result<Message>,
+{
+    /// Creates a new [FailoverCoordinator], with the primary acceptor active.
+    pub fn new(primary: P, backup: B) -> Self {
+        Self {
+            primary,
+            backup,
+            active: AcceptorSlot::Primary,
+            failures: 0,
+            max_failures: DEFAULT_MAX_FAILURES,
+            failovers: Vec::new(),
+            log: None,
+        }
+    }
+
+    /// Gets the number of consecutive failed health checks before failing over.
+    pub const fn max_failures(&self) -> usize {
+        self.max_failures
+    }
+
+    /// Sets the number of consecutive failed health checks before failing over.
+    ///
+    /// A value of zero is treated as one.
+    pub fn set_max_failures(&mut self, max_failures: usize) {
+        self.max_failures = max_failures.max(1);
+    }
+
+    /// Builder function that sets the number of consecutive failed health checks before failing
+    /// over.
+    pub fn with_max_failures(mut self, max_failures: usize) -> Self {
+        self.set_max_failures(max_failures);
+        self
+    }
+
+    /// Builder function that records failovers to the [EventLog].
+    pub fn with_event_log(mut self, log: Arc<dyn EventLog>) -> Self {
+        self.log = Some(log);
+        self
+    }
+
+    /// Gets the active [AcceptorSlot].
+    pub const fn active(&self) -> AcceptorSlot {
+        self.active
+    }
+
+    /// Gets the standby [AcceptorSlot].
+    pub const fn standby(&self) -> AcceptorSlot {
+        self.active.other()
+    }
+
+    /// Gets the number of consecutive failed health checks of the active acceptor.
+    pub const fn failures(&self) -> usize {
+        self.failures
+    }
+
+    /// Gets the history of [FailoverEvent]s.
+    pub fn failovers(&self) -> &[FailoverEvent] {
+        self.failovers.as_ref()
+    }
+
+    /// Inhibits the standby acceptor, and enables the active acceptor.
+    pub fn start(&mut self) -> Result<()> {
+        self.send(self.standby(), &InhibitRequest::new().into())?;
+        self.send(self.active, &IdleRequest::new().into())?;
+        self.failures = 0;
+
+        Ok(())
+    }
+
+    /// Sends a request to the active acceptor.
+    ///
+    /// Errors count as failed health checks, and may trigger a failover. The error is returned
+    /// either way, the host retries the request on the newly active acceptor.
+    pub fn poll(&mut self, req: &Message) -> Result<Message> {
+        let res = self.poll_active(req);
+
+        if let Err(err) = res.as_ref() {
+            self.on_failure(&format!("{err}"));
+        }
+
+        res
+    }
+
+    /// Sends a `Status` request to check the health of the active acceptor.
+    ///
+    /// Returns the [FailoverEvent] if the check triggered a failover.
+    pub fn check(&mut self) -> Option<FailoverEvent> {
+        match self.poll_active(&StatusRequest::new().into()) {
+            Ok(res) => match StatusResponse::try_from(&res) {
+                Ok(status) if status.code() != ResponseCode::Ack => {
+                    self.on_failure(&format!("status response: {}", status.code()))
+                }
+                Ok(status) => match status.status().major_minor_status() {
+                    failure @ MajorMinorStatus::AbnormalFailure(_) => {
+                        self.on_failure(&format!("device status: {failure}"))
+                    }
+                    _ => {
+                        self.failures = 0;
+                        None
+                    }
+                },
+                Err(err) => self.on_failure(&format!("invalid status response: {err}")),
+            },
+            Err(err) => self.on_failure(&format!("{err}")),
+        }
+    }
+
+    /// Switches the active acceptor.
+    ///
+    /// The active acceptor is inhibited on a best-effort basis, since it may be unreachable. The
+    /// switch only happens if the standby acknowledges the `Idle` request.
+    pub fn failover(&mut self, reason: &str) -> Result<FailoverEvent> {
+        let from = self.active;
+        let to = from.other();
+
+        if let Err(err) = self.send(from, &InhibitRequest::new().into()) {
+            log::warn!("unable to inhibit the {from} acceptor: {err}");
+        }
+
+        self.send(to, &IdleRequest::new().into())
+            .map_err(|err| Error::Failover(format!("unable to enable the {to} acceptor: {err}")))?;
+
+        let event = FailoverEvent::create(from, reason);
+        log::warn!("acceptor failover: {event}");
+
+        self.active = to;
+        self.failures = 0;
+        self.failovers.push(event.clone());
+
+        if let Some(log) = self.log.as_ref() {
+            log.record(&AuditEntry::new(AuditRecord::Failover(event.clone())));
+        }
+
+        Ok(event)
+    }
+
+    fn on_failure(&mut self, reason: &str) -> Option<FailoverEvent> {
+        self.failures = self.failures.saturating_add(1);
+        log::warn!(
+            "{} acceptor health check failed ({}/{}): {reason}",
+            self.active,
+            self.failures,
+            self.max_failures
+        );
+
+        if self.failures >= self.max_failures {
+            match self.failover(reason) {
+                Ok(event) => Some(event),
+                Err(err) => {
+                    log::error!("{err}");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+
+    fn poll_active(&mut self, req: &Message) -> Result<Message> {
+        match self.active {
+            AcceptorSlot::Primary => (self.primary)(req),
+            AcceptorSlot::Backup => (self.backup)(req),
+        }
+    }
+
+    fn send(&mut self, slot: AcceptorSlot, req: &Message) -> Result<()> {
+        let res = match slot {
+            AcceptorSlot::Primary => (self.primary)(req)?,
+            AcceptorSlot::Backup => (self.backup)(req)?,
+        };
+
+        match Response::try_from(&res)?.code() {
+            ResponseCode::Ack => Ok(()),
+            code => Err(Error::InvalidResponseCode(code.into())),
+        }
+    }
+}
+
+impl<P, B> fmt::Debug for FailoverCoordinator<P, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FailoverCoordinator")
+            .field("active", &self.active)
+            .field("failures", &self.failures)
+            .field("max_failures", &self.max_failures)
+            .field("failovers", &self.failovers)
+            .field("event_log", &self.log.is_some())
+            .finish()
+    }
+}
+
+impl<P, B> fmt::Display for FailoverCoordinator<P, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""active": {}, "#, self.active)?;
+        write!(f, r#""failures": {}, "#, self.failures)?;
+        write!(f, r#""max_failures": {}, "#, self.max_failures)?;
+        write!(f, r#""failovers": {}"#, self.failovers.len())?;
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::MemoryEventLog;
+    use crate::mock::MockDevice;
+    use crate::{FailureCode, MajorMinorStatus};
+
+    #[test]
+    fn test_failover_coordinator() -> Result<()> {
+        let (primary, backup) = (MockDevice::new(), MockDevice::new());
+        let log = Arc::new(MemoryEventLog::new());
+
+        let mut fleet = FailoverCoordinator::new(
+            |req: &Message| primary.handle_request(req),
+            |req: &Message| backup.handle_request(req),
+        )
+        .with_max_failures(2)
+        .with_event_log(log.clone());
+
+        fleet.start()?;
+        assert_eq!(
+            primary.status().major_minor_status(),
+            MajorMinorStatus::NormalIdle
+        );
+        assert_eq!(
+            backup.status().major_minor_status(),
+            MajorMinorStatus::Normal
+        );
+        assert_eq!(fleet.check(), None);
+        assert_eq!(fleet.active(), AcceptorSlot::Primary);
+
+        primary.jam(FailureCode::StackMotor);
+        assert_eq!(fleet.check(), None);
+        assert_eq!(fleet.failures(), 1);
+
+        let event = fleet.check().unwrap();
+        assert_eq!(event.from(), AcceptorSlot::Primary);
+        assert_eq!(event.to(), AcceptorSlot::Backup);
+        assert_eq!(fleet.active(), AcceptorSlot::Backup);
+        assert_eq!(fleet.failures(), 0);
+        assert_eq!(
+            primary.status().major_minor_status(),
+            MajorMinorStatus::Normal
+        );
+        assert_eq!(
+            backup.status().major_minor_status(),
+            MajorMinorStatus::NormalIdle
+        );
+
+        // no fail-back, the recovered primary stays inhibited
+        assert_eq!(fleet.check(), None);
+        assert_eq!(fleet.active(), AcceptorSlot::Backup);
+
+        assert_eq!(fleet.failovers(), std::slice::from_ref(&event));
+        assert_eq!(log.records(), [AuditRecord::Failover(event)]);
+
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use std::{fmt, mem};
+use core::{fmt, mem};
 
 use crate::{Error, Result};
 
@@ -12,6 +12,7 @@ const STATUS_SHIFT: u8 = 12;
 /// Represents the function ID of the JCM device.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FuncId {
     /// Common (entire device).
     Common = COMMON,
@@ -127,7 +128,7 @@ mod tests {
             FuncId::Escrow,
         ];
 
-        for (raw, exp) in raw_vals.into_iter().zip(expected.into_iter()) {
+        for (raw, exp) in raw_vals.into_iter().zip(expected) {
             assert_eq!(FuncId::try_from(raw), Ok(exp));
             assert_eq!(FuncId::from_u8(raw), exp);
         }
@@ -1,4 +1,4 @@
-use std::{fmt, mem};
+use core::{fmt, mem};
 
 use crate::{Error, Result};
 
@@ -30,6 +30,7 @@ const RESERVED: u8 = 0xc0;
 /// Represents the function status of device unit.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FunctionStatus {
     /// Unit is functional.
     Normal = NORMAL,
@@ -221,7 +222,7 @@ mod tests {
             FunctionStatus::Failure,
         ];
 
-        for (raw, exp) in raw_vals.into_iter().zip(exp_vals.into_iter()) {
+        for (raw, exp) in raw_vals.into_iter().zip(exp_vals) {
             assert_eq!(FunctionStatus::from_u8(raw), exp);
             assert_eq!(FunctionStatus::try_from(raw), Ok(exp));
 
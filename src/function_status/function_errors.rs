@@ -1,8 +1,9 @@
-use std::fmt;
+use core::fmt;
 
 /// Represents whether the device unit is functional.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FunctionErrors {
     None = 0,
     ErrorOccurred = 1,
@@ -1,6 +1,7 @@
 //! Represents `Program Signature` hash algorithm information.
 
-use std::fmt;
+use alloc::vec::Vec;
+use core::fmt;
 
 use crate::{Error, Result};
 
@@ -26,6 +27,7 @@ const SHA1_REQ_LEN: usize = 33;
 ///
 /// This value is supplied to the
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HashAlgorithm {
     Crc16([u8; CRC16_LEN]),
     Crc32([u8; CRC32_LEN]),
@@ -185,6 +187,7 @@ impl fmt::Display for HashAlgorithm {
 /// Represents a hash algorithm number for the program firmware signature.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AlgorithmNumber {
     Crc16 = CRC16,
     Crc32 = CRC32,
@@ -202,7 +205,7 @@ impl AlgorithmNumber {
     pub const fn len(&self) -> usize {
         match self {
             Self::Reserved => 0,
-            _ => std::mem::size_of::<u8>(),
+            _ => core::mem::size_of::<u8>(),
         }
     }
 
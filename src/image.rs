@@ -1,7 +1,23 @@
+mod bit_depth;
 mod block;
 mod block_number;
+#[cfg(feature = "image")]
+mod encode;
+mod fetcher;
+mod kind;
+mod note_image;
+mod note_serial_number;
+mod progress;
+mod serial_number_char;
 mod size;
 
+pub use bit_depth::*;
 pub use block::*;
 pub use block_number::*;
+pub use fetcher::*;
+pub use kind::*;
+pub use note_image::*;
+pub use note_serial_number::*;
+pub use progress::*;
+pub use serial_number_char::*;
 pub use size::*;
@@ -0,0 +1,108 @@
+use core::fmt;
+
+use crate::{Error, Result};
+
+const ONE: u8 = 1;
+const TWO: u8 = 2;
+const FOUR: u8 = 4;
+const EIGHT: u8 = 8;
+
+/// Represents the number of bits per grayscale pixel in raw image data.
+///
+/// Pixels narrower than a byte are packed most-significant bits first, and each image row starts
+/// on a byte boundary.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ImageBitDepth {
+    /// Black and white pixels.
+    One = ONE,
+    /// Four shades of gray.
+    Two = TWO,
+    /// Sixteen shades of gray.
+    Four = FOUR,
+    /// 256 shades of gray.
+    #[default]
+    Eight = EIGHT,
+}
+
+impl ImageBitDepth {
+    /// Creates a new [ImageBitDepth].
+    pub const fn new() -> Self {
+        Self::Eight
+    }
+
+    /// Gets the number of bits per pixel.
+    pub const fn bits(&self) -> usize {
+        *self as usize
+    }
+
+    /// Gets the maximum (white) pixel value.
+    pub const fn max_value(&self) -> u8 {
+        ((1u16 << self.bits()) - 1) as u8
+    }
+
+    /// Gets the byte length of an image row of `width` pixels.
+    pub const fn row_len(&self, width: usize) -> usize {
+        (width * self.bits()).div_ceil(8)
+    }
+}
+
+impl From<ImageBitDepth> for u8 {
+    fn from(val: ImageBitDepth) -> Self {
+        val as u8
+    }
+}
+
+impl From<&ImageBitDepth> for u8 {
+    fn from(val: &ImageBitDepth) -> Self {
+        (*val).into()
+    }
+}
+
+impl TryFrom<u8> for ImageBitDepth {
+    type Error = Error;
+
+    fn try_from(val: u8) -> Result<Self> {
+        match val {
+            ONE => Ok(Self::One),
+            TWO => Ok(Self::Two),
+            FOUR => Ok(Self::Four),
+            EIGHT => Ok(Self::Eight),
+            _ => Err(Error::InvalidImageBitDepth(val)),
+        }
+    }
+}
+
+impl fmt::Display for ImageBitDepth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", u8::from(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_bit_depth() {
+        for (raw, exp, max, row) in [
+            (ONE, ImageBitDepth::One, 0x01, 2),
+            (TWO, ImageBitDepth::Two, 0x03, 3),
+            (FOUR, ImageBitDepth::Four, 0x0f, 5),
+            (EIGHT, ImageBitDepth::Eight, 0xff, 10),
+        ] {
+            assert_eq!(ImageBitDepth::try_from(raw), Ok(exp));
+            assert_eq!(u8::from(exp), raw);
+            assert_eq!(exp.max_value(), max);
+            assert_eq!(exp.row_len(10), row);
+        }
+
+        for raw in [0, 3, 5, 16] {
+            assert_eq!(
+                ImageBitDepth::try_from(raw),
+                Err(Error::InvalidImageBitDepth(raw))
+            );
+        }
+    }
+}
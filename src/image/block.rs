@@ -1,4 +1,5 @@
-use std::fmt;
+use alloc::vec::Vec;
+use core::fmt;
 
 /// Represents an image data block.
 ///
@@ -9,6 +10,7 @@ use std::fmt;
 /// ```
 #[repr(C)]
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ImageBlock(Vec<u8>);
 
 impl ImageBlock {
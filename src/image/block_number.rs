@@ -1,10 +1,11 @@
-use std::fmt;
+use core::fmt;
 
 const SIZE_AND_TOTAL: u8 = 0;
 
 /// Represents the block number in an image request.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ImageBlockNumber(u8);
 
 impl ImageBlockNumber {
@@ -0,0 +1,207 @@
+use alloc::vec::Vec;
+
+use crate::{NoteImage, Result};
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+const PNG_COLOR_GRAYSCALE: u8 = 0;
+// zlib header: deflate, 32K window, no preset dictionary, fastest compression
+const ZLIB_HEADER: [u8; 2] = [0x78, 0x01];
+const DEFLATE_STORED_MAX: usize = u16::MAX as usize;
+
+const BMP_FILE_HEADER_LEN: usize = 14;
+const BMP_INFO_HEADER_LEN: usize = 40;
+const BMP_PALETTE_LEN: usize = 256 * 4;
+const BMP_DATA_OFFSET: usize = BMP_FILE_HEADER_LEN + BMP_INFO_HEADER_LEN + BMP_PALETTE_LEN;
+const BMP_BITS_PER_PIXEL: u16 = 8;
+
+impl NoteImage {
+    /// Encodes the [NoteImage] into a grayscale PNG buffer.
+    ///
+    /// Pixels keep the raw [ImageBitDepth](crate::ImageBitDepth), and image data is stored
+    /// uncompressed.
+    pub fn to_png(&self) -> Result<Vec<u8>> {
+        self.validate()?;
+
+        let row_len = self.row_len();
+        let mut raw = Vec::with_capacity((row_len + 1) * self.height());
+        for row in self.rows() {
+            // filter type: none
+            raw.push(0);
+            raw.extend_from_slice(row);
+        }
+
+        let mut ihdr = Vec::with_capacity(13);
+        ihdr.extend_from_slice(&(self.width() as u32).to_be_bytes());
+        ihdr.extend_from_slice(&(self.height() as u32).to_be_bytes());
+        // bit depth, color type, compression, filter, interlace
+        ihdr.extend_from_slice(&[self.bit_depth().into(), PNG_COLOR_GRAYSCALE, 0, 0, 0]);
+
+        let mut buf = PNG_SIGNATURE.to_vec();
+        png_chunk(&mut buf, b"IHDR", ihdr.as_ref());
+        png_chunk(&mut buf, b"IDAT", zlib_stored(raw.as_ref()).as_ref());
+        png_chunk(&mut buf, b"IEND", &[]);
+
+        Ok(buf)
+    }
+
+    /// Encodes the [NoteImage] into an 8-bit grayscale BMP buffer.
+    ///
+    /// Pixels narrower than a byte are scaled to the full 8-bit range.
+    pub fn to_bmp(&self) -> Result<Vec<u8>> {
+        self.validate()?;
+
+        let (width, height) = (self.width(), self.height());
+        let max = self.bit_depth().max_value() as u16;
+        let stride = width.next_multiple_of(4);
+        let data_len = stride * height;
+        let file_len = BMP_DATA_OFFSET + data_len;
+
+        let mut buf = Vec::with_capacity(file_len);
+
+        buf.extend_from_slice(b"BM");
+        buf.extend_from_slice(&(file_len as u32).to_le_bytes());
+        buf.extend_from_slice(&[0u8; 4]);
+        buf.extend_from_slice(&(BMP_DATA_OFFSET as u32).to_le_bytes());
+
+        buf.extend_from_slice(&(BMP_INFO_HEADER_LEN as u32).to_le_bytes());
+        buf.extend_from_slice(&(width as i32).to_le_bytes());
+        // positive height stores rows bottom-up
+        buf.extend_from_slice(&(height as i32).to_le_bytes());
+        buf.extend_from_slice(&1u16.to_le_bytes());
+        buf.extend_from_slice(&BMP_BITS_PER_PIXEL.to_le_bytes());
+        // no compression
+        buf.extend_from_slice(&0u32.to_le_bytes());
+        buf.extend_from_slice(&(data_len as u32).to_le_bytes());
+        // horizontal and vertical resolution unspecified
+        buf.extend_from_slice(&[0u8; 8]);
+        buf.extend_from_slice(&256u32.to_le_bytes());
+        buf.extend_from_slice(&0u32.to_le_bytes());
+
+        for shade in 0..=u8::MAX {
+            buf.extend_from_slice(&[shade, shade, shade, 0]);
+        }
+
+        for y in (0..height).rev() {
+            for x in 0..width {
+                let pixel = self.pixel(x, y).unwrap_or_default() as u16;
+                buf.push((pixel * 0xff / max) as u8);
+            }
+            buf.resize(buf.len() + stride - width, 0);
+        }
+
+        Ok(buf)
+    }
+}
+
+fn png_chunk(buf: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    buf.extend_from_slice(&(data.len() as u32).to_be_bytes());
+
+    let start = buf.len();
+    buf.extend_from_slice(kind);
+    buf.extend_from_slice(data);
+
+    let crc = crc32(&buf[start..]);
+    buf.extend_from_slice(&crc.to_be_bytes());
+}
+
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let blocks = data.len().div_ceil(DEFLATE_STORED_MAX).max(1);
+    let mut buf = Vec::with_capacity(ZLIB_HEADER.len() + data.len() + blocks * 5 + 4);
+
+    buf.extend_from_slice(ZLIB_HEADER.as_ref());
+
+    let mut chunks = data.chunks(DEFLATE_STORED_MAX).peekable();
+    if chunks.peek().is_none() {
+        buf.extend_from_slice(&[0x01, 0x00, 0x00, 0xff, 0xff]);
+    }
+
+    while let Some(chunk) = chunks.next() {
+        let last = chunks.peek().is_none();
+        let len = chunk.len() as u16;
+
+        buf.push(last as u8);
+        buf.extend_from_slice(&len.to_le_bytes());
+        buf.extend_from_slice(&(!len).to_le_bytes());
+        buf.extend_from_slice(chunk);
+    }
+
+    buf.extend_from_slice(&adler32(data).to_be_bytes());
+
+    buf
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(u32::MAX, |crc, &byte| {
+        (0..8).fold(crc ^ byte as u32, |crc, _| {
+            if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            }
+        })
+    })
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+
+    let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + byte as u32) % MOD;
+        (a, (b + a) % MOD)
+    });
+
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Error, ImageBitDepth, ImageKind};
+
+    #[test]
+    fn test_note_image_encode() -> Result<()> {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+
+        // 3x2 image, 2-bit pixels
+        let image = NoteImage::create(ImageKind::Note, vec![0b0001_1000, 0b1110_0100])
+            .with_dimensions(3, 2)
+            .with_bit_depth(ImageBitDepth::Two);
+
+        let png = image.to_png()?;
+        assert_eq!(&png[..8], PNG_SIGNATURE.as_ref());
+        assert_eq!(
+            &png[8..33],
+            [
+                0x00, 0x00, 0x00, 0x0d, b'I', b'H', b'D', b'R', 0x00, 0x00, 0x00, 0x03, 0x00, 0x00,
+                0x00, 0x02, 0x02, 0x00, 0x00, 0x00, 0x00, 0xf2, 0xaf, 0x21, 0x67,
+            ]
+        );
+        // IDAT: zlib header, single stored block of two filtered rows
+        assert_eq!(
+            &png[33..50],
+            [
+                0x00, 0x00, 0x00, 0x0f, b'I', b'D', b'A', b'T', 0x78, 0x01, 0x01, 0x04, 0x00, 0xfb,
+                0xff, 0x00, 0x18,
+            ]
+        );
+        assert_eq!(&png[png.len() - 12..png.len() - 4], b"\0\0\0\0IEND");
+
+        let bmp = image.to_bmp()?;
+        assert_eq!(bmp.len(), BMP_DATA_OFFSET + 8);
+        assert_eq!(&bmp[..2], b"BM");
+        assert_eq!(&bmp[2..6], (bmp.len() as u32).to_le_bytes());
+        // bottom row first, scaled to 8 bits, padded to 4 bytes
+        assert_eq!(
+            &bmp[BMP_DATA_OFFSET..],
+            [0xff, 0xaa, 0x55, 0x00, 0x00, 0x55, 0xaa, 0x00]
+        );
+
+        assert_eq!(
+            NoteImage::new().to_png(),
+            Err(Error::InvalidImageDimensions((0, 0)))
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,312 @@
+use alloc::vec::Vec;
+
+use crate::{
+    CancelToken, Error, ImageBlock, ImageKind, ImageProgress, ImageSize, Message, NoteImage,
+    NoteImageBlockResponse, NoteImageSizeResponse, ResponseCode, Result, SerialNumberBlockResponse,
+    SerialNumberSizeResponse,
+};
+
+/// Represents a cooperatively cancellable retrieval of note image data blocks.
+///
+/// The fetcher is transport-agnostic: each request [Message] is handed to a caller-provided
+/// polling function that returns the device response.
+///
+/// The block responses carry no block number, or checksum, so the fetcher validates the
+/// sequence from the block lengths: every block must contain data, all blocks but the last must
+/// have the length of the first block, and the blocks must add up to the image size.
+///
+/// If the [CancelToken] is triggered mid-transfer, the fetcher re-requests block `00h` to reset
+/// the device block sequence before returning [Error::Cancelled], so the next retrieval starts
+/// from a clean state.
+#[derive(Clone, Debug, Default)]
+pub struct ImageFetcher {
+    kind: ImageKind,
+    cancel: CancelToken,
+}
+
+impl ImageFetcher {
+    /// Creates a new [ImageFetcher].
+    pub fn new() -> Self {
+        Self {
+            kind: ImageKind::new(),
+            cancel: CancelToken::new(),
+        }
+    }
+
+    /// Gets the [ImageKind] retrieved by the [ImageFetcher].
+    pub const fn kind(&self) -> ImageKind {
+        self.kind
+    }
+
+    /// Sets the [ImageKind] retrieved by the [ImageFetcher].
+    pub fn set_kind(&mut self, kind: ImageKind) {
+        self.kind = kind;
+    }
+
+    /// Builder function that sets the [ImageKind] retrieved by the [ImageFetcher].
+    pub fn with_kind(mut self, kind: ImageKind) -> Self {
+        self.set_kind(kind);
+        self
+    }
+
+    /// Gets a reference to the [CancelToken] for the [ImageFetcher].
+    pub const fn cancel_token(&self) -> &CancelToken {
+        &self.cancel
+    }
+
+    /// Sets the [CancelToken] for the [ImageFetcher].
+    pub fn set_cancel_token(&mut self, cancel: CancelToken) {
+        self.cancel = cancel;
+    }
+
+    /// Builder function that sets the [CancelToken] for the [ImageFetcher].
+    pub fn with_cancel_token(mut self, cancel: CancelToken) -> Self {
+        self.set_cancel_token(cancel);
+        self
+    }
+
+    /// Requests the image size and total number of blocks.
+    pub fn fetch_size<F>(&self, poll: &mut F) -> Result<ImageSize>
+    where
+        F: FnMut(&Message) -> Result<Message>,
+    {
+        let res = poll(&self.kind.block_request(0))?;
+        let (code, size) = match self.kind {
+            ImageKind::Note => {
+                let res = NoteImageSizeResponse::try_from(res)?;
+                (res.code(), *res.size_total())
+            }
+            ImageKind::SerialNumber => {
+                let res = SerialNumberSizeResponse::try_from(res)?;
+                (res.code(), *res.size_total())
+            }
+        };
+
+        match code {
+            ResponseCode::Ack => Ok(size),
+            code => Err(Error::InvalidResponseCode(code.into())),
+        }
+    }
+
+    /// Retrieves the full image data.
+    ///
+    /// Returns an empty buffer if the device does not support sending image data.
+    pub fn fetch<F>(&self, poll: F) -> Result<Vec<u8>>
+    where
+        F: FnMut(&Message) -> Result<Message>,
+    {
+        self.fetch_image(poll, |_| ()).map(NoteImage::into_data)
+    }
+
+    /// Retrieves the full image, reporting the [ImageProgress] after each block.
+    ///
+    /// Returns an empty [NoteImage] if the device does not support sending image data.
+    pub fn fetch_image<F, G>(&self, mut poll: F, mut progress: G) -> Result<NoteImage>
+    where
+        F: FnMut(&Message) -> Result<Message>,
+        G: FnMut(ImageProgress),
+    {
+        if self.cancel.is_cancelled() {
+            return Err(Error::Cancelled);
+        }
+
+        let size = self.fetch_size(&mut poll)?;
+
+        if !size.is_supported() {
+            log::debug!(
+                "{} image data not supported by the device: {size}",
+                self.kind
+            );
+            return Ok(NoteImage::create(self.kind, Vec::new()));
+        }
+
+        let total_blocks = size.total_blocks();
+        let mut data = Vec::with_capacity(size.size());
+        let mut block_len = 0;
+
+        for block in 1..=total_blocks {
+            if self.cancel.is_cancelled() {
+                log::info!("image retrieval cancelled at block {block}, resetting block sequence");
+                Self::reset_sequence(self.kind, &mut poll);
+                return Err(Error::Cancelled);
+            }
+
+            let res = self.fetch_block(block as u8, &mut poll).and_then(|res| {
+                match (res.len(), block_len) {
+                    (0, exp) => Err(Error::InvalidImageBlockLen((0, exp))),
+                    (len, 0) => {
+                        block_len = len;
+                        Ok(res)
+                    }
+                    (len, exp) if len != exp && block != total_blocks => {
+                        Err(Error::InvalidImageBlockLen((len, exp)))
+                    }
+                    _ => Ok(res),
+                }
+            });
+
+            match res {
+                Ok(res) => data.extend(res),
+                Err(err) => {
+                    log::warn!("error retrieving image block {block}: {err}");
+                    Self::reset_sequence(self.kind, &mut poll);
+                    return Err(err);
+                }
+            }
+
+            progress(ImageProgress::create(
+                block,
+                total_blocks,
+                data.len().min(size.size()),
+                size.size(),
+            ));
+        }
+
+        if data.len() < size.size() {
+            return Err(Error::InvalidImageLen((data.len(), size.size())));
+        }
+
+        data.truncate(size.size());
+
+        Ok(NoteImage::create(self.kind, data))
+    }
+
+    fn fetch_block<F>(&self, block: u8, poll: &mut F) -> Result<ImageBlock>
+    where
+        F: FnMut(&Message) -> Result<Message>,
+    {
+        let res = poll(&self.kind.block_request(block))?;
+        let (code, block) = match self.kind {
+            ImageKind::Note => {
+                let res = NoteImageBlockResponse::try_from(res)?;
+                (res.code(), res.block().clone())
+            }
+            ImageKind::SerialNumber => {
+                let res = SerialNumberBlockResponse::try_from(res)?;
+                (res.code(), res.block().clone())
+            }
+        };
+
+        match code {
+            ResponseCode::Ack => Ok(block),
+            code => Err(Error::InvalidResponseCode(code.into())),
+        }
+    }
+
+    fn reset_sequence<F>(kind: ImageKind, poll: &mut F)
+    where
+        F: FnMut(&Message) -> Result<Message>,
+    {
+        if let Err(err) = poll(&kind.block_request(0)) {
+            log::warn!("error resetting image block sequence: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NoteImageRequest, Response, SerialNumberRequest};
+
+    fn device(image: &[u8], blocks: usize) -> impl FnMut(&Message) -> Result<Message> + '_ {
+        let block_len = image.len().div_ceil(blocks);
+
+        move |req: &Message| {
+            let block = match req.data().message_code().request_code()? {
+                crate::RequestCode::SerialNumber => {
+                    SerialNumberRequest::try_from(req)?.block_number()
+                }
+                _ => NoteImageRequest::try_from(req)?.block_number(),
+            }
+            .into_u8() as usize;
+            let res: Response = if block == 0 {
+                NoteImageSizeResponse::new()
+                    .with_size_total(
+                        ImageSize::new()
+                            .with_size(image.len())
+                            .with_total_blocks(blocks),
+                    )
+                    .into()
+            } else {
+                let start = (block - 1) * block_len;
+                let end = (start + block_len).min(image.len());
+                NoteImageBlockResponse::new()
+                    .with_block(ImageBlock::from(&image[start..end]))
+                    .into()
+            };
+
+            let mut additional = vec![0u8; res.len()];
+            res.to_bytes(additional.as_mut())?;
+
+            Ok(Message::new().with_data(req.data().clone().with_additional(additional.as_ref())))
+        }
+    }
+
+    #[test]
+    fn test_image_fetcher() -> Result<()> {
+        let image: Vec<u8> = (0..=250u8).collect();
+
+        let fetcher = ImageFetcher::new();
+        assert_eq!(fetcher.fetch(device(image.as_ref(), 4))?, image);
+
+        let mut blocks = Vec::new();
+        let serial = ImageFetcher::new()
+            .with_kind(ImageKind::SerialNumber)
+            .fetch_image(device(image.as_ref(), 3), |p| {
+                blocks.push((p.block(), p.received()))
+            })?;
+        assert_eq!(serial.kind(), ImageKind::SerialNumber);
+        assert_eq!(serial.data(), image.as_slice());
+        assert_eq!(blocks, [(1, 84), (2, 168), (3, 251)]);
+
+        // a block shorter than the first one, before the last block, breaks the sequence
+        let mut inner = device(image.as_ref(), 4);
+        let mut requested = Vec::new();
+        let res = fetcher.fetch(|req: &Message| {
+            let block = NoteImageRequest::try_from(req)?.block_number().into_u8();
+            requested.push(block);
+            let res = inner(req)?;
+            if block == 2 {
+                let additional = &res.data().additional()[..10];
+                Ok(Message::new().with_data(res.data().clone().with_additional(additional)))
+            } else {
+                Ok(res)
+            }
+        });
+        assert_eq!(res, Err(Error::InvalidImageBlockLen((9, 63))));
+        assert_eq!(requested, [0, 1, 2, 0]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_image_fetcher_cancel() {
+        let image: Vec<u8> = (0..=250u8).collect();
+        let mut inner = device(image.as_ref(), 4);
+
+        let fetcher = ImageFetcher::new();
+        let cancel = fetcher.cancel_token().clone();
+
+        let mut requested = Vec::new();
+        let res = fetcher.fetch(|req: &Message| {
+            let block = NoteImageRequest::try_from(req)?.block_number().into_u8();
+            requested.push(block);
+            if block == 2 {
+                cancel.cancel();
+            }
+            inner(req)
+        });
+
+        assert_eq!(res, Err(Error::Cancelled));
+        // the final size request resets the device block sequence
+        assert_eq!(requested, [0, 1, 2, 0]);
+
+        assert_eq!(
+            fetcher.fetch(device(image.as_ref(), 4)),
+            Err(Error::Cancelled)
+        );
+
+        cancel.reset();
+        assert_eq!(fetcher.fetch(device(image.as_ref(), 4)), Ok(image.clone()));
+    }
+}
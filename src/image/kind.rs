@@ -0,0 +1,59 @@
+use core::fmt;
+
+use crate::{ImageBlockNumber, Message, NoteImageRequest, SerialNumberRequest};
+
+/// Represents the kind of image data retrieved from the device.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ImageKind {
+    /// Scan of the whole note, requested with a [NoteImageRequest].
+    #[default]
+    Note,
+    /// Image of the note serial number, requested with a [SerialNumberRequest].
+    SerialNumber,
+}
+
+impl ImageKind {
+    /// Creates a new [ImageKind].
+    pub const fn new() -> Self {
+        Self::Note
+    }
+
+    /// Creates the request [Message] for the image block.
+    ///
+    /// Block `00h` requests the image size and total number of blocks.
+    pub fn block_request(&self, block: u8) -> Message {
+        let block_number = ImageBlockNumber::from_u8(block);
+
+        match self {
+            Self::Note => NoteImageRequest::new()
+                .with_block_number(block_number)
+                .into(),
+            Self::SerialNumber => SerialNumberRequest::new()
+                .with_block_number(block_number)
+                .into(),
+        }
+    }
+}
+
+impl From<ImageKind> for &'static str {
+    fn from(val: ImageKind) -> Self {
+        match val {
+            ImageKind::Note => "note",
+            ImageKind::SerialNumber => "serial number",
+        }
+    }
+}
+
+impl From<&ImageKind> for &'static str {
+    fn from(val: &ImageKind) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for ImageKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
@@ -0,0 +1,210 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Error, ImageBitDepth, ImageKind, Result};
+
+/// Represents image data reassembled from all the image blocks sent by the device.
+///
+/// The device only reports the total image size, so the `width`, `height`, and
+/// [ImageBitDepth] of the scan are set by the caller from the device documentation. Raw image
+/// data is stored row by row, top to bottom, with each row starting on a byte boundary.
+///
+/// With the `image` feature enabled, the [NoteImage] can be encoded into grayscale PNG and BMP
+/// buffers.
+#[repr(C)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NoteImage {
+    kind: ImageKind,
+    width: usize,
+    height: usize,
+    bit_depth: ImageBitDepth,
+    data: Vec<u8>,
+}
+
+impl NoteImage {
+    /// Creates a new, empty [NoteImage].
+    pub const fn new() -> Self {
+        Self {
+            kind: ImageKind::new(),
+            width: 0,
+            height: 0,
+            bit_depth: ImageBitDepth::new(),
+            data: Vec::new(),
+        }
+    }
+
+    /// Creates a new [NoteImage] from the provided parameters.
+    pub const fn create(kind: ImageKind, data: Vec<u8>) -> Self {
+        Self {
+            kind,
+            width: 0,
+            height: 0,
+            bit_depth: ImageBitDepth::new(),
+            data,
+        }
+    }
+
+    /// Gets the [ImageKind] of the [NoteImage].
+    pub const fn kind(&self) -> ImageKind {
+        self.kind
+    }
+
+    /// Gets the image width in pixels.
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Gets the image height in pixels.
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Sets the image width and height in pixels.
+    pub fn set_dimensions(&mut self, width: usize, height: usize) {
+        self.width = width;
+        self.height = height;
+    }
+
+    /// Builder function that sets the image width and height in pixels.
+    pub fn with_dimensions(mut self, width: usize, height: usize) -> Self {
+        self.set_dimensions(width, height);
+        self
+    }
+
+    /// Gets the [ImageBitDepth] of the raw image data.
+    pub const fn bit_depth(&self) -> ImageBitDepth {
+        self.bit_depth
+    }
+
+    /// Sets the [ImageBitDepth] of the raw image data.
+    pub fn set_bit_depth(&mut self, bit_depth: ImageBitDepth) {
+        self.bit_depth = bit_depth;
+    }
+
+    /// Builder function that sets the [ImageBitDepth] of the raw image data.
+    pub fn with_bit_depth(mut self, bit_depth: ImageBitDepth) -> Self {
+        self.set_bit_depth(bit_depth);
+        self
+    }
+
+    /// Gets the byte length of a raw image row.
+    pub const fn row_len(&self) -> usize {
+        self.bit_depth.row_len(self.width)
+    }
+
+    /// Gets a reference to the raw image data.
+    pub fn data(&self) -> &[u8] {
+        self.data.as_ref()
+    }
+
+    /// Converts the [NoteImage] into the raw image data.
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Gets the length of the raw image data.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Gets whether the [NoteImage] is empty, e.g. when the device does not support sending image
+    /// data.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Gets an iterator over the raw image rows.
+    pub fn rows(&self) -> impl Iterator<Item = &[u8]> + '_ {
+        self.data.chunks(self.row_len().max(1)).take(self.height)
+    }
+
+    /// Gets the value of the pixel at the `x` and `y` coordinates.
+    ///
+    /// Returns `None` if the coordinates are outside the image.
+    pub fn pixel(&self, x: usize, y: usize) -> Option<u8> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        let bits = self.bit_depth.bits();
+        let bit = x * bits;
+        let byte = self.data.get(y * self.row_len() + bit / 8)?;
+        let shift = 8 - bits - (bit % 8);
+
+        Some((byte >> shift) & self.bit_depth.max_value())
+    }
+
+    /// Validates the image dimensions against the length of the raw image data.
+    pub fn validate(&self) -> Result<()> {
+        let exp = self.row_len() * self.height;
+
+        if self.width == 0 || self.height == 0 {
+            Err(Error::InvalidImageDimensions((self.width, self.height)))
+        } else if self.data.len() < exp {
+            Err(Error::InvalidImageLen((self.data.len(), exp)))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl From<NoteImage> for Vec<u8> {
+    fn from(val: NoteImage) -> Self {
+        val.into_data()
+    }
+}
+
+impl fmt::Display for NoteImage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""kind": {}, "#, self.kind)?;
+        write!(f, r#""width": {}, "#, self.width)?;
+        write!(f, r#""height": {}, "#, self.height)?;
+        write!(f, r#""bit_depth": {}, "#, self.bit_depth)?;
+        write!(f, r#""len": {}"#, self.data.len())?;
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_note_image() {
+        // 3x2 image, 2-bit pixels: 0b00_01_10 (padded), 0b11_10_01 (padded)
+        let image = NoteImage::create(ImageKind::Note, vec![0b0001_1000, 0b1110_0100])
+            .with_dimensions(3, 2)
+            .with_bit_depth(ImageBitDepth::Two);
+
+        assert_eq!(image.row_len(), 1);
+        assert_eq!(image.validate(), Ok(()));
+        assert_eq!(
+            image.rows().collect::<Vec<&[u8]>>(),
+            [[0b0001_1000], [0b1110_0100]]
+        );
+
+        let pixels: Vec<u8> = (0..2)
+            .flat_map(|y| (0..3).map(move |x| (x, y)))
+            .filter_map(|(x, y)| image.pixel(x, y))
+            .collect();
+        assert_eq!(pixels, [0, 1, 2, 3, 2, 1]);
+        assert_eq!(image.pixel(3, 0), None);
+        assert_eq!(image.pixel(0, 2), None);
+
+        assert_eq!(
+            image.clone().with_dimensions(3, 3).validate(),
+            Err(Error::InvalidImageLen((2, 3)))
+        );
+        assert_eq!(
+            NoteImage::new().validate(),
+            Err(Error::InvalidImageDimensions((0, 0)))
+        );
+
+        assert_eq!(
+            image.to_string(),
+            r#"{"kind": "note", "width": 3, "height": 2, "bit_depth": 2, "len": 2}"#
+        );
+    }
+}
@@ -0,0 +1,246 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use crate::{
+    CancelToken, Error, ImageFetcher, ImageKind, Message, NoteImage, Result, SerialNumberChar,
+};
+
+/// Represents the serial number read from a note.
+///
+/// The reassembled serial number image data starts with a header of recognized characters,
+/// followed by the serial number image.
+///
+/// ## Format
+///
+/// Field  | Count  | Characters                                   | Image
+/// -------|--------|----------------------------------------------|---------
+/// Length | 1 byte | count * [SerialNumberChar::LEN] bytes        | variable
+///
+/// Firmware that only sends the image sets the character count to zero.
+#[repr(C)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NoteSerialNumber {
+    chars: Vec<SerialNumberChar>,
+    image: NoteImage,
+}
+
+impl NoteSerialNumber {
+    /// Creates a new, empty [NoteSerialNumber].
+    pub const fn new() -> Self {
+        Self {
+            chars: Vec::new(),
+            image: NoteImage::create(ImageKind::SerialNumber, Vec::new()),
+        }
+    }
+
+    /// Gets a reference to the list of recognized [SerialNumberChar]s.
+    pub fn chars(&self) -> &[SerialNumberChar] {
+        self.chars.as_ref()
+    }
+
+    /// Builder function that sets the list of recognized [SerialNumberChar]s.
+    pub fn with_chars<C: Into<Vec<SerialNumberChar>>>(mut self, chars: C) -> Self {
+        self.chars = chars.into();
+        self
+    }
+
+    /// Gets a reference to the serial number [NoteImage].
+    pub const fn image(&self) -> &NoteImage {
+        &self.image
+    }
+
+    /// Builder function that sets the serial number [NoteImage].
+    pub fn with_image(mut self, image: NoteImage) -> Self {
+        self.image = image;
+        self
+    }
+
+    /// Gets the recognized serial number string.
+    pub fn serial(&self) -> String {
+        self.chars.iter().map(SerialNumberChar::character).collect()
+    }
+
+    /// Gets the lowest character confidence, if provided by the firmware for every character.
+    pub fn confidence(&self) -> Option<u8> {
+        self.chars
+            .iter()
+            .map(SerialNumberChar::confidence)
+            .collect::<Option<Vec<u8>>>()?
+            .into_iter()
+            .min()
+    }
+
+    /// Gets whether no serial number was read, e.g. when the device does not support sending
+    /// serial number data.
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty() && self.image.is_empty()
+    }
+
+    /// Converts a byte buffer into a [NoteSerialNumber].
+    ///
+    /// An empty buffer converts into an empty [NoteSerialNumber].
+    pub fn from_bytes(buf: &[u8]) -> Result<Self> {
+        let Some((&count, rem)) = buf.split_first() else {
+            return Ok(Self::new());
+        };
+
+        let chars_len = count as usize * SerialNumberChar::LEN;
+        let Some(chars) = rem.get(..chars_len) else {
+            return Err(Error::InvalidSerialNumberLen((buf.len(), chars_len + 1)));
+        };
+
+        Ok(Self {
+            chars: chars
+                .chunks_exact(SerialNumberChar::LEN)
+                .map(SerialNumberChar::from_bytes)
+                .collect::<Result<Vec<SerialNumberChar>>>()?,
+            image: NoteImage::create(ImageKind::SerialNumber, rem[chars_len..].into()),
+        })
+    }
+
+    /// Converts the [NoteSerialNumber] into a byte buffer.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf =
+            Vec::with_capacity(1 + self.chars.len() * SerialNumberChar::LEN + self.image.len());
+
+        buf.push(self.chars.len() as u8);
+        buf.extend(self.chars.iter().flat_map(|c| c.into_bytes()));
+        buf.extend_from_slice(self.image.data());
+
+        buf
+    }
+}
+
+impl TryFrom<&[u8]> for NoteSerialNumber {
+    type Error = Error;
+
+    fn try_from(val: &[u8]) -> Result<Self> {
+        Self::from_bytes(val)
+    }
+}
+
+impl TryFrom<&NoteImage> for NoteSerialNumber {
+    type Error = Error;
+
+    fn try_from(val: &NoteImage) -> Result<Self> {
+        match val.kind() {
+            ImageKind::SerialNumber => Self::from_bytes(val.data()),
+            kind => Err(Error::InvalidImageKind(kind.into())),
+        }
+    }
+}
+
+impl fmt::Display for NoteSerialNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""serial": "{}", "#, self.serial())?;
+        write!(f, r#""chars": ["#)?;
+        for (i, c) in self.chars.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{c}")?;
+        }
+        write!(f, "], ")?;
+        write!(f, r#""image": {}"#, self.image)?;
+        write!(f, "}}")
+    }
+}
+
+/// Reads the serial number of the note in escrow.
+///
+/// Retrieves every serial number image block, and parses the reassembled data into a
+/// [NoteSerialNumber]. Returns an empty [NoteSerialNumber] if the device does not support sending
+/// serial number data.
+///
+/// Retrieval stops between blocks when the [CancelToken] is triggered, resetting the device block
+/// sequence and returning [Error::Cancelled].
+pub fn read_note_serial<F>(cancel: &CancelToken, poll: F) -> Result<NoteSerialNumber>
+where
+    F: FnMut(&Message) -> Result<Message>,
+{
+    let image = ImageFetcher::new()
+        .with_kind(ImageKind::SerialNumber)
+        .with_cancel_token(cancel.clone())
+        .fetch_image(poll, |_| ())?;
+
+    NoteSerialNumber::try_from(&image)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ImageBlock, ImageSize, Response, ResponseCode, SerialNumberBlockResponse,
+        SerialNumberRequest, SerialNumberSizeResponse,
+    };
+
+    #[test]
+    fn test_note_serial_number() -> Result<()> {
+        let chars = [
+            SerialNumberChar::new()
+                .with_character('A')
+                .with_position(10, 4)
+                .with_size(8, 12)
+                .with_confidence(Some(97)),
+            SerialNumberChar::new()
+                .with_character('7')
+                .with_position(0x0112, 4)
+                .with_size(8, 12)
+                .with_confidence(Some(88)),
+        ];
+        let exp = NoteSerialNumber::new()
+            .with_chars(chars)
+            .with_image(NoteImage::create(ImageKind::SerialNumber, vec![0xaa; 5]));
+
+        let raw = exp.to_bytes();
+        assert_eq!(
+            &raw[..SerialNumberChar::LEN + 1],
+            [0x02, b'A', 0x0a, 0x00, 0x04, 0x00, 0x08, 0x0c, 97]
+        );
+        assert_eq!(NoteSerialNumber::from_bytes(raw.as_ref())?, exp);
+        assert_eq!(exp.serial(), "A7");
+        assert_eq!(exp.confidence(), Some(88));
+
+        let no_confidence = NoteSerialNumber::new().with_chars([chars[0].with_confidence(None)]);
+        assert_eq!(no_confidence.confidence(), None);
+        assert_eq!(
+            no_confidence.to_string(),
+            r#"{"serial": "A", "chars": [{"character": "A", "x": 10, "y": 4, "width": 8, "height": 12, "confidence": null}], "image": {"kind": "serial number", "width": 0, "height": 0, "bit_depth": 8, "len": 0}}"#
+        );
+
+        assert_eq!(
+            NoteSerialNumber::from_bytes(&raw[..10]),
+            Err(Error::InvalidSerialNumberLen((10, 17)))
+        );
+        assert_eq!(
+            NoteSerialNumber::try_from(&NoteImage::new()),
+            Err(Error::InvalidImageKind("note"))
+        );
+
+        // full block fetch sequence, split into two blocks
+        let mut res_blocks = raw.chunks(raw.len().div_ceil(2)).map(ImageBlock::from);
+        let cancel = CancelToken::new();
+        let serial = read_note_serial(&cancel, |req: &Message| {
+            let res: Response = match SerialNumberRequest::try_from(req)?.block_number().into_u8() {
+                0 => SerialNumberSizeResponse::new()
+                    .with_code(ResponseCode::Ack)
+                    .with_size_total(ImageSize::new().with_size(raw.len()).with_total_blocks(2))
+                    .into(),
+                _ => SerialNumberBlockResponse::new()
+                    .with_code(ResponseCode::Ack)
+                    .with_block(res_blocks.next().unwrap_or_default())
+                    .into(),
+            };
+
+            let mut additional = vec![0u8; res.len()];
+            res.to_bytes(additional.as_mut())?;
+
+            Ok(Message::new().with_data(req.data().clone().with_additional(additional.as_ref())))
+        })?;
+        assert_eq!(serial, exp);
+
+        Ok(())
+    }
+}
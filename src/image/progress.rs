@@ -0,0 +1,60 @@
+use core::fmt;
+
+/// Represents the progress of an image retrieval, reported after each received block.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImageProgress {
+    block: usize,
+    total_blocks: usize,
+    received: usize,
+    size: usize,
+}
+
+impl ImageProgress {
+    /// Creates a new [ImageProgress] from the provided parameters.
+    pub const fn create(block: usize, total_blocks: usize, received: usize, size: usize) -> Self {
+        Self {
+            block,
+            total_blocks,
+            received,
+            size,
+        }
+    }
+
+    /// Gets the number of the last received block, starting from `1`.
+    pub const fn block(&self) -> usize {
+        self.block
+    }
+
+    /// Gets the total number of image blocks.
+    pub const fn total_blocks(&self) -> usize {
+        self.total_blocks
+    }
+
+    /// Gets the number of received image bytes.
+    pub const fn received(&self) -> usize {
+        self.received
+    }
+
+    /// Gets the total image size in bytes.
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Gets whether all image blocks were received.
+    pub const fn is_complete(&self) -> bool {
+        self.block >= self.total_blocks
+    }
+}
+
+impl fmt::Display for ImageProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""block": {}, "#, self.block)?;
+        write!(f, r#""total_blocks": {}, "#, self.total_blocks)?;
+        write!(f, r#""received": {}, "#, self.received)?;
+        write!(f, r#""size": {}"#, self.size)?;
+        write!(f, "}}")
+    }
+}
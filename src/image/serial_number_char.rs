@@ -0,0 +1,169 @@
+use core::fmt;
+
+use crate::{Error, Result};
+
+const NO_CONFIDENCE: u8 = 0xff;
+
+/// Represents a character recognized in a serial number image.
+///
+/// ## Format
+///
+/// Field  | Character | X       | Y       | Width  | Height | Confidence
+/// -------|-----------|---------|---------|--------|--------|-----------
+/// Length | 1 byte    | 2 bytes | 2 bytes | 1 byte | 1 byte | 1 byte
+///
+/// The character is ASCII encoded, and the position fields are little-endian pixel coordinates
+/// of the character's top-left corner in the serial number image. Confidence is a percentage,
+/// `FFh` when the firmware does not provide one.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SerialNumberChar {
+    character: u8,
+    x: u16,
+    y: u16,
+    width: u8,
+    height: u8,
+    confidence: Option<u8>,
+}
+
+impl SerialNumberChar {
+    /// Represents the byte length of the [SerialNumberChar].
+    pub const LEN: usize = 8;
+
+    /// Creates a new [SerialNumberChar].
+    pub const fn new() -> Self {
+        Self {
+            character: 0,
+            x: 0,
+            y: 0,
+            width: 0,
+            height: 0,
+            confidence: None,
+        }
+    }
+
+    /// Gets the recognized character.
+    pub const fn character(&self) -> char {
+        self.character as char
+    }
+
+    /// Builder function that sets the recognized character.
+    ///
+    /// Non-ASCII characters are replaced with `?`.
+    pub const fn with_character(mut self, character: char) -> Self {
+        self.character = if character.is_ascii() {
+            character as u8
+        } else {
+            b'?'
+        };
+        self
+    }
+
+    /// Gets the horizontal pixel position of the character.
+    pub const fn x(&self) -> u16 {
+        self.x
+    }
+
+    /// Gets the vertical pixel position of the character.
+    pub const fn y(&self) -> u16 {
+        self.y
+    }
+
+    /// Builder function that sets the pixel position of the character.
+    pub const fn with_position(mut self, x: u16, y: u16) -> Self {
+        self.x = x;
+        self.y = y;
+        self
+    }
+
+    /// Gets the pixel width of the character.
+    pub const fn width(&self) -> u8 {
+        self.width
+    }
+
+    /// Gets the pixel height of the character.
+    pub const fn height(&self) -> u8 {
+        self.height
+    }
+
+    /// Builder function that sets the pixel width and height of the character.
+    pub const fn with_size(mut self, width: u8, height: u8) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Gets the recognition confidence percentage, if provided by the firmware.
+    pub const fn confidence(&self) -> Option<u8> {
+        self.confidence
+    }
+
+    /// Builder function that sets the recognition confidence percentage.
+    pub const fn with_confidence(mut self, confidence: Option<u8>) -> Self {
+        self.confidence = confidence;
+        self
+    }
+
+    /// Converts a byte buffer into a [SerialNumberChar].
+    pub fn from_bytes(buf: &[u8]) -> Result<Self> {
+        match buf.get(..Self::LEN) {
+            Some(&[character, x0, x1, y0, y1, width, height, confidence]) => {
+                if !character.is_ascii() {
+                    return Err(Error::InvalidAsciiString);
+                }
+
+                Ok(Self {
+                    character,
+                    x: u16::from_le_bytes([x0, x1]),
+                    y: u16::from_le_bytes([y0, y1]),
+                    width,
+                    height,
+                    confidence: (confidence != NO_CONFIDENCE).then_some(confidence),
+                })
+            }
+            _ => Err(Error::InvalidSerialNumberLen((buf.len(), Self::LEN))),
+        }
+    }
+
+    /// Converts the [SerialNumberChar] into a byte array.
+    pub fn into_bytes(self) -> [u8; Self::LEN] {
+        let [x0, x1] = self.x.to_le_bytes();
+        let [y0, y1] = self.y.to_le_bytes();
+
+        [
+            self.character,
+            x0,
+            x1,
+            y0,
+            y1,
+            self.width,
+            self.height,
+            self.confidence.unwrap_or(NO_CONFIDENCE),
+        ]
+    }
+}
+
+impl TryFrom<&[u8]> for SerialNumberChar {
+    type Error = Error;
+
+    fn try_from(val: &[u8]) -> Result<Self> {
+        Self::from_bytes(val)
+    }
+}
+
+impl fmt::Display for SerialNumberChar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""character": "{}", "#, self.character())?;
+        write!(f, r#""x": {}, "#, self.x)?;
+        write!(f, r#""y": {}, "#, self.y)?;
+        write!(f, r#""width": {}, "#, self.width)?;
+        write!(f, r#""height": {}, "#, self.height)?;
+        match self.confidence {
+            Some(confidence) => write!(f, r#""confidence": {confidence}"#)?,
+            None => write!(f, r#""confidence": null"#)?,
+        }
+        write!(f, "}}")
+    }
+}
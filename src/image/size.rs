@@ -9,6 +9,7 @@ use crate::{Error, Result};
 /// If the size and total are both zero, the device does not support sending the image data.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ImageSize {
     size: u32,
     total: u8,
@@ -146,7 +147,7 @@ impl From<ImageSize> for [u8; ImageSize::LEN] {
 
 impl IntoIterator for ImageSize {
     type Item = u8;
-    type IntoIter = std::array::IntoIter<u8, { Self::LEN }>;
+    type IntoIter = core::array::IntoIter<u8, { Self::LEN }>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.into_bytes().into_iter()
@@ -0,0 +1,291 @@
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::audit::{AuditEntry, AuditRecord, EventLog};
+use crate::{
+    Error, InhibitRequest, Message, RejectRequest, RequestCode, Response, ResponseCode, Result,
+};
+
+/// Represents an external condition that makes accepting notes unsafe.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum InterlockCondition {
+    /// The cabinet door is open.
+    DoorOpen,
+    /// The cabinet is tilted, or was moved.
+    Tilt,
+    /// A tamper switch tripped.
+    Tamper,
+    /// A site-specific input, by input number.
+    Custom(u8),
+}
+
+impl From<InterlockCondition> for &'static str {
+    fn from(val: InterlockCondition) -> Self {
+        match val {
+            InterlockCondition::DoorOpen => "door open",
+            InterlockCondition::Tilt => "tilt",
+            InterlockCondition::Tamper => "tamper",
+            InterlockCondition::Custom(_) => "custom",
+        }
+    }
+}
+
+impl From<&InterlockCondition> for &'static str {
+    fn from(val: &InterlockCondition) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for InterlockCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Custom(input) => write!(f, r#""custom input {input}""#),
+            cond => write!(f, r#""{}""#, <&str>::from(cond)),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct InterlockInner {
+    conditions: Vec<InterlockCondition>,
+    inhibit_pending: bool,
+}
+
+/// Forces the device inhibited while external [InterlockCondition]s are engaged.
+///
+/// The host declares conditions from its own inputs with [engage](Self::engage) and
+/// [release](Self::release). While any condition is engaged, the wrapped polling function:
+///
+/// - sends an `Inhibit` request before the next request
+/// - refuses `Idle` requests with [Error::InterlockEngaged]
+/// - replaces `Stack` and `Hold` requests with a `Reject` request, and returns
+///   [Error::InterlockEngaged]
+///
+/// Clones share the same conditions, so input handlers on other threads can engage the
+/// interlock of a running polling loop. Changes are recorded to the optional [EventLog].
+#[derive(Clone, Default)]
+pub struct Interlock {
+    inner: Arc<Mutex<InterlockInner>>,
+    log: Option<Arc<dyn EventLog>>,
+}
+
+impl Interlock {
+    /// Creates a new, released [Interlock].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder function that records interlock changes to the [EventLog].
+    pub fn with_event_log(mut self, log: Arc<dyn EventLog>) -> Self {
+        self.log = Some(log);
+        self
+    }
+
+    /// Engages the [InterlockCondition].
+    ///
+    /// Returns `false` if the condition was already engaged.
+    pub fn engage(&self, condition: InterlockCondition) -> bool {
+        let engaged = {
+            let mut inner = self.lock();
+            if inner.conditions.contains(&condition) {
+                false
+            } else {
+                inner.conditions.push(condition);
+                inner.conditions.sort();
+                inner.inhibit_pending = true;
+                true
+            }
+        };
+
+        if engaged {
+            log::warn!("interlock engaged: {condition}, inhibiting the device");
+            self.record(AuditRecord::InterlockEngaged(condition));
+        }
+
+        engaged
+    }
+
+    /// Releases the [InterlockCondition].
+    ///
+    /// The device stays inhibited until the host sends an `Idle` request. Returns `false` if the
+    /// condition was not engaged.
+    pub fn release(&self, condition: InterlockCondition) -> bool {
+        let released = {
+            let mut inner = self.lock();
+            let len = inner.conditions.len();
+            inner.conditions.retain(|c| c != &condition);
+            if inner.conditions.is_empty() {
+                inner.inhibit_pending = false;
+            }
+            inner.conditions.len() != len
+        };
+
+        if released {
+            log::info!("interlock released: {condition}");
+            self.record(AuditRecord::InterlockReleased(condition));
+        }
+
+        released
+    }
+
+    /// Gets whether any [InterlockCondition] is engaged.
+    pub fn is_engaged(&self) -> bool {
+        !self.lock().conditions.is_empty()
+    }
+
+    /// Gets the engaged [InterlockCondition]s, in a stable order.
+    pub fn conditions(&self) -> Vec<InterlockCondition> {
+        self.lock().conditions.clone()
+    }
+
+    /// Wraps a polling function, keeping the device inhibited while engaged.
+    pub fn gate<'a, P>(&'a self, mut poll: P) -> impl FnMut(&Message) -> Result<Message> + 'a
+    where
+        P: FnMut(&Message) -> Result<Message> + 'a,
+    {
+        move |req: &Message| {
+            let code = req.data().message_code().request_code();
+            let (conditions, inhibit_pending) = {
+                let inner = self.lock();
+                (inner.conditions.clone(), inner.inhibit_pending)
+            };
+
+            if conditions.is_empty() {
+                return poll(req);
+            }
+
+            let engaged_err = || Error::InterlockEngaged(conditions_string(&conditions));
+
+            if inhibit_pending && code != Ok(RequestCode::Inhibit) {
+                let res = poll(&InhibitRequest::new().into())?;
+                self.on_inhibit_response(&res);
+            }
+
+            match code {
+                Ok(RequestCode::Idle) => {
+                    log::warn!("refusing Idle request, interlock engaged");
+                    Err(engaged_err())
+                }
+                Ok(RequestCode::Stack | RequestCode::Hold) => {
+                    log::warn!("rejecting escrowed note, interlock engaged");
+                    poll(&RejectRequest::new().into())?;
+                    Err(engaged_err())
+                }
+                Ok(RequestCode::Inhibit) => {
+                    let res = poll(req)?;
+                    self.on_inhibit_response(&res);
+                    Ok(res)
+                }
+                _ => poll(req),
+            }
+        }
+    }
+
+    fn on_inhibit_response(&self, res: &Message) {
+        if Response::try_from(res).map(|r| r.code()) == Ok(ResponseCode::Ack) {
+            self.lock().inhibit_pending = false;
+        }
+    }
+
+    fn record(&self, record: AuditRecord) {
+        if let Some(log) = self.log.as_ref() {
+            log.record(&AuditEntry::new(record));
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, InterlockInner> {
+        // a poisoned lock still holds the engaged conditions
+        self.inner.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl fmt::Debug for Interlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Interlock")
+            .field("conditions", &self.conditions())
+            .field("event_log", &self.log.is_some())
+            .finish()
+    }
+}
+
+impl fmt::Display for Interlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", conditions_string(&self.conditions()))
+    }
+}
+
+fn conditions_string(conditions: &[InterlockCondition]) -> String {
+    let list = conditions
+        .iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    format!("[{list}]")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::MemoryEventLog;
+    use crate::{IdleRequest, StackRequest, StatusRequest};
+
+    #[test]
+    fn test_interlock() -> Result<()> {
+        let log = Arc::new(MemoryEventLog::new());
+        let interlock = Interlock::new().with_event_log(log.clone());
+
+        let mut sent = Vec::new();
+        let ack = |req: &Message| -> Result<Message> {
+            sent.push(req.data().message_code().request_code()?);
+            Ok(Message::new().with_data(
+                req.data()
+                    .clone()
+                    .with_additional(&[ResponseCode::Ack.into()]),
+            ))
+        };
+
+        {
+            let mut poll = interlock.gate(ack);
+            poll(&IdleRequest::new().into())?;
+
+            assert!(interlock.engage(InterlockCondition::Tilt));
+            assert!(interlock.engage(InterlockCondition::DoorOpen));
+            assert!(!interlock.engage(InterlockCondition::DoorOpen));
+
+            poll(&StatusRequest::new().into())?;
+            assert_eq!(
+                poll(&IdleRequest::new().into()),
+                Err(Error::InterlockEngaged(r#"["door open", "tilt"]"#.into()))
+            );
+            assert!(poll(&StackRequest::new().into()).is_err());
+
+            assert!(interlock.release(InterlockCondition::DoorOpen));
+            assert!(interlock.release(InterlockCondition::Tilt));
+            assert!(!interlock.release(InterlockCondition::Tilt));
+            poll(&IdleRequest::new().into())?;
+        }
+
+        assert_eq!(
+            sent,
+            [
+                RequestCode::Idle,
+                RequestCode::Inhibit,
+                RequestCode::Status,
+                RequestCode::Reject,
+                RequestCode::Idle,
+            ]
+        );
+        assert_eq!(
+            log.records(),
+            [
+                AuditRecord::InterlockEngaged(InterlockCondition::Tilt),
+                AuditRecord::InterlockEngaged(InterlockCondition::DoorOpen),
+                AuditRecord::InterlockReleased(InterlockCondition::DoorOpen),
+                AuditRecord::InterlockReleased(InterlockCondition::Tilt),
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,188 @@
+use std::{fmt, time};
+
+use crate::{BillAcceptorState, Clock, Message, Result, StatusRequest};
+
+/// Represents the default keep-alive interval.
+pub const KEEP_ALIVE_INTERVAL: time::Duration = time::Duration::from_secs(5);
+
+/// Sends periodic `Status` requests while the link is otherwise idle.
+///
+/// Some USB hubs and hosts power-manage devices that see no traffic. The [KeepAlive] ticker
+/// reports a keep-alive as due once no traffic has been seen for the configured interval.
+///
+/// Keep-alives are suspended while a transaction is active (from `Escrow` until the device returns
+/// to `Idle` or `Inhibit`), so benign traffic never interleaves with note handling.
+#[derive(Clone, Debug)]
+pub struct KeepAlive<C: Clock> {
+    clock: C,
+    interval: time::Duration,
+    last_activity: time::Duration,
+    enabled: bool,
+    in_transaction: bool,
+    sent: u64,
+}
+
+impl<C: Clock> KeepAlive<C> {
+    /// Creates a new, enabled [KeepAlive] using the provided [Clock].
+    pub fn new(clock: C) -> Self {
+        let last_activity = clock.now();
+
+        Self {
+            clock,
+            interval: KEEP_ALIVE_INTERVAL,
+            last_activity,
+            enabled: true,
+            in_transaction: false,
+            sent: 0,
+        }
+    }
+
+    /// Gets the keep-alive interval.
+    pub const fn interval(&self) -> time::Duration {
+        self.interval
+    }
+
+    /// Sets the keep-alive interval.
+    pub fn set_interval(&mut self, interval: time::Duration) {
+        self.interval = interval;
+    }
+
+    /// Builder function that sets the keep-alive interval.
+    pub fn with_interval(mut self, interval: time::Duration) -> Self {
+        self.set_interval(interval);
+        self
+    }
+
+    /// Gets whether keep-alives are enabled.
+    pub const fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Sets whether keep-alives are enabled.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Builder function that sets whether keep-alives are enabled.
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.set_enabled(enabled);
+        self
+    }
+
+    /// Gets whether a transaction is active, suspending keep-alives.
+    pub const fn in_transaction(&self) -> bool {
+        self.in_transaction
+    }
+
+    /// Gets the number of keep-alive requests sent.
+    pub const fn sent(&self) -> u64 {
+        self.sent
+    }
+
+    /// Records traffic on the link, restarting the keep-alive interval.
+    pub fn on_activity(&mut self) {
+        self.last_activity = self.clock.now();
+    }
+
+    /// Records a device event, tracking transaction state from the event code.
+    pub fn on_event(&mut self, event: &Message) {
+        self.on_activity();
+
+        if let Ok(code) = event.data().message_code().event_code() {
+            match BillAcceptorState::from_event_code(code) {
+                BillAcceptorState::Escrowed | BillAcceptorState::VendValid => {
+                    self.in_transaction = true
+                }
+                BillAcceptorState::Idle
+                | BillAcceptorState::Inhibited
+                | BillAcceptorState::Initializing => self.in_transaction = false,
+                _ => (),
+            }
+        }
+    }
+
+    /// Gets whether a keep-alive request is due.
+    pub fn is_due(&self) -> bool {
+        self.enabled
+            && !self.in_transaction
+            && self.clock.elapsed(self.last_activity) >= self.interval
+    }
+
+    /// Sends a keep-alive `Status` request through the polling function if one is due.
+    ///
+    /// Returns `None` if no keep-alive was due.
+    pub fn tick<F>(&mut self, poll: F) -> Option<Result<Message>>
+    where
+        F: FnOnce(&Message) -> Result<Message>,
+    {
+        if self.is_due() {
+            let res = poll(&StatusRequest::new().into());
+
+            self.on_activity();
+            self.sent = self.sent.saturating_add(1);
+
+            if let Err(err) = res.as_ref() {
+                log::warn!("keep-alive request failed: {err}");
+            }
+
+            Some(res)
+        } else {
+            None
+        }
+    }
+}
+
+impl<C: Clock> fmt::Display for KeepAlive<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""interval_ms": {}, "#, self.interval.as_millis())?;
+        write!(f, r#""enabled": {}, "#, self.enabled)?;
+        write!(f, r#""in_transaction": {}, "#, self.in_transaction)?;
+        write!(f, r#""sent": {}"#, self.sent)?;
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventCode, EventType, MessageCode, MessageData, MessageType, SimulatedClock};
+
+    fn event(code: EventCode) -> Message {
+        Message::new().with_data(
+            MessageData::new()
+                .with_message_type(MessageType::Event(EventType::Sequence0))
+                .with_message_code(MessageCode::Event(code)),
+        )
+    }
+
+    #[test]
+    fn test_keep_alive() {
+        let clock = SimulatedClock::new();
+        let interval = time::Duration::from_secs(1);
+        let mut keep_alive = KeepAlive::new(&clock).with_interval(interval);
+
+        let echo = |req: &Message| Ok(req.clone());
+
+        assert!(keep_alive.tick(echo).is_none());
+
+        clock.advance(interval);
+        assert!(keep_alive.tick(echo).is_some());
+        assert!(keep_alive.tick(echo).is_none());
+
+        // suspended while a note is in escrow
+        keep_alive.on_event(&event(EventCode::Escrow));
+        clock.advance(interval * 3);
+        assert!(keep_alive.tick(echo).is_none());
+
+        keep_alive.on_event(&event(EventCode::Idle));
+        clock.advance(interval);
+        assert!(keep_alive.tick(echo).is_some());
+
+        keep_alive.set_enabled(false);
+        clock.advance(interval);
+        assert!(!keep_alive.is_due());
+
+        assert_eq!(keep_alive.sent(), 2);
+    }
+}
@@ -1,35 +1,223 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(not(any(feature = "std", test)))]
+#[macro_use]
+extern crate alloc;
+#[cfg(any(feature = "std", test))]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod accounting;
+#[cfg(feature = "std")]
+pub mod audit;
+#[cfg(feature = "std")]
+mod autoconfig;
+mod bar_code;
 mod bill_acceptor_state;
+mod cancel;
+#[cfg(feature = "std")]
+mod capabilities;
+#[cfg(feature = "std")]
+mod cashbox_exchange;
+#[cfg(feature = "std")]
+mod cashbox_monitor;
+#[cfg(feature = "std")]
+mod catalog;
+#[cfg(feature = "cli")]
+pub mod cli;
+mod clock;
+mod collection_outcome;
+mod conditional_vend;
+#[cfg(feature = "std")]
+mod counters;
+#[cfg(feature = "std")]
+mod crash_log;
+#[cfg(feature = "std")]
+mod credit;
 mod currency;
+mod currency_table;
+#[cfg(feature = "std")]
+mod debug_state;
+#[cfg(feature = "demo")]
+pub mod demo;
 mod denomination;
+#[cfg(feature = "std")]
+mod denomination_profile;
+mod denomination_table;
+#[cfg(feature = "std")]
+mod device_info;
+#[cfg(feature = "std")]
+mod device_inhibit;
+mod device_state_machine;
 mod device_status;
+pub mod diagnostics;
+mod direction_disable;
 mod error;
+#[cfg(feature = "std")]
+mod escrow_policy;
+#[cfg(feature = "std")]
+mod escrow_queue;
+#[cfg(feature = "std")]
+mod escrow_session;
 mod failure_code;
+mod feature_set;
+#[cfg(feature = "std")]
+pub mod fleet;
 mod func_id;
 mod function_status;
 mod hash_algorithm;
 mod image;
+#[cfg(feature = "std")]
+mod interlock;
+#[cfg(feature = "std")]
+mod keep_alive;
 mod message;
+#[cfg(feature = "std")]
+pub mod mock;
+mod monetary_amount;
 mod near_full;
+#[cfg(feature = "std")]
+mod observer;
+mod orientation;
+mod pause_settings;
+#[cfg(feature = "std")]
+mod poll_config;
+#[cfg(feature = "std")]
+mod power_up;
+mod product_family;
+#[cfg(feature = "std")]
+mod quirks;
+mod redaction;
+mod reject_outcome;
+#[cfg(feature = "std")]
+pub mod replay;
+#[cfg(all(feature = "serial", unix))]
+pub mod serial;
+#[cfg(feature = "std")]
+mod signature_audit;
+mod spec_version;
+#[cfg(feature = "std")]
+mod state_tracker;
 mod status_code;
+mod status_mode;
+#[cfg(feature = "std")]
+mod support_bundle;
+#[cfg(feature = "std")]
+pub mod testing;
 mod ticket;
+#[cfg(feature = "std")]
+mod timing;
+#[cfg(feature = "std")]
+mod timing_config;
+#[cfg(feature = "std")]
+pub mod trace;
+#[cfg(feature = "std")]
+mod transport;
+#[cfg(feature = "serial")]
+pub mod uart;
+#[cfg(feature = "std")]
+mod uid_manager;
 mod unit_number;
 mod unit_status;
 #[cfg(feature = "usb")]
 pub mod usb;
+#[cfg(feature = "std")]
+mod vend_valid_ack;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(all(target_arch = "wasm32", feature = "usb"))]
+compile_error!("the `usb` feature is not supported on wasm32, build with `--no-default-features --features wasm`");
 
+#[cfg(feature = "std")]
+pub use autoconfig::*;
+pub use bar_code::*;
 pub use bill_acceptor_state::*;
+pub use cancel::*;
+#[cfg(feature = "std")]
+pub use capabilities::*;
+#[cfg(feature = "std")]
+pub use cashbox_exchange::*;
+#[cfg(feature = "std")]
+pub use cashbox_monitor::*;
+#[cfg(feature = "std")]
+pub use catalog::*;
+pub use clock::*;
+pub use collection_outcome::*;
+pub use conditional_vend::*;
+#[cfg(feature = "std")]
+pub use counters::*;
+#[cfg(feature = "std")]
+pub use crash_log::*;
+#[cfg(feature = "std")]
+pub use credit::*;
 pub use currency::*;
+pub use currency_table::*;
+#[cfg(feature = "std")]
+pub use debug_state::*;
 pub use denomination::*;
+#[cfg(feature = "std")]
+pub use denomination_profile::*;
+pub use denomination_table::*;
+#[cfg(feature = "std")]
+pub use device_info::*;
+#[cfg(feature = "std")]
+pub use device_inhibit::*;
+pub use device_state_machine::*;
 pub use device_status::*;
+pub use direction_disable::*;
 pub use error::*;
+#[cfg(feature = "std")]
+pub use escrow_policy::*;
+#[cfg(feature = "std")]
+pub use escrow_queue::*;
+#[cfg(feature = "std")]
+pub use escrow_session::*;
 pub use failure_code::*;
+pub use feature_set::*;
 pub use func_id::*;
 pub use function_status::*;
 pub use hash_algorithm::*;
 pub use image::*;
+#[cfg(feature = "std")]
+pub use interlock::*;
+#[cfg(feature = "std")]
+pub use keep_alive::*;
 pub use message::*;
+pub use monetary_amount::*;
 pub use near_full::*;
+#[cfg(feature = "std")]
+pub use observer::*;
+pub use orientation::*;
+pub use pause_settings::*;
+#[cfg(feature = "std")]
+pub use poll_config::*;
+#[cfg(feature = "std")]
+pub use power_up::*;
+pub use product_family::*;
+#[cfg(feature = "std")]
+pub use quirks::*;
+pub use redaction::*;
+pub use reject_outcome::*;
+#[cfg(feature = "std")]
+pub use signature_audit::*;
+pub use spec_version::*;
+#[cfg(feature = "std")]
+pub use state_tracker::*;
 pub use status_code::*;
+pub use status_mode::*;
+#[cfg(feature = "std")]
+pub use support_bundle::*;
 pub use ticket::*;
+#[cfg(feature = "std")]
+pub use timing::*;
+#[cfg(feature = "std")]
+pub use timing_config::*;
+#[cfg(feature = "std")]
+pub use transport::*;
+#[cfg(feature = "std")]
+pub use uid_manager::*;
 pub use unit_number::*;
 pub use unit_status::*;
+#[cfg(feature = "std")]
+pub use vend_valid_ack::*;
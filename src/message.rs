@@ -1,14 +1,17 @@
-use std::{fmt, mem};
+use alloc::vec::Vec;
+use core::{fmt, mem};
 
 use crate::{Error, Result};
 
 mod event;
+mod frame_decoder;
 mod message_data;
 mod message_id;
 mod request;
 mod response;
 
 pub use event::*;
+pub use frame_decoder::*;
 pub use message_data::*;
 pub use message_id::*;
 pub use request::*;
@@ -28,6 +31,7 @@ pub const MIN_LEN: usize = Message::meta_len() + MessageData::meta_len();
 /// Size (byte) | 1  | 2      | Variable
 #[repr(C)]
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Message {
     id: MessageId,
     data: MessageData,
@@ -68,6 +72,44 @@ impl Message {
         Self::meta_len() + self.data.len()
     }
 
+    /// Converts a raw frame into a [Message], tolerating length field mismatches.
+    ///
+    /// For devices with firmware quirks: frames shorter than the declared length are parsed from
+    /// the received bytes, and bytes past the declared length are ignored. The strict
+    /// [TryFrom](Self::try_from) conversion rejects both.
+    ///
+    /// The framing defines no checksum, so the length field is the only frame-level check.
+    pub fn from_bytes_lenient(buf: &[u8]) -> Result<Self> {
+        Self::from_frame(buf, true)
+    }
+
+    fn from_frame(val: &[u8], lenient: bool) -> Result<Self> {
+        let len = val.len();
+        if len < MIN_LEN {
+            return Err(Error::InvalidMessageLen((len, MIN_LEN)));
+        }
+
+        let id = MessageId::try_from(val[0])?;
+
+        let frame_len = match u16::from_le_bytes([val[1], val[2]]) as usize {
+            declared if declared == len => len,
+            declared if lenient => {
+                log::warn!("frame length mismatch, received: {len}, declared: {declared}");
+                if (MIN_LEN..len).contains(&declared) {
+                    declared
+                } else {
+                    len
+                }
+            }
+            declared if declared > len => return Err(Error::TruncatedFrame((len, declared))),
+            declared => return Err(Error::InvalidMessageLen((len, declared.max(MIN_LEN)))),
+        };
+
+        let data = MessageData::try_from(&val[Self::meta_len()..frame_len])?;
+
+        Ok(Self { id, data })
+    }
+
     pub(crate) const fn meta_len() -> usize {
         MessageId::len() + mem::size_of::<u16>()
     }
@@ -77,8 +119,8 @@ impl Message {
         self.data.is_empty()
     }
 
-    /// Writes the [Message] to the provided byte buffer.
-    pub fn to_bytes(&self, buf: &mut [u8]) -> Result<()> {
+    /// Writes the [Message] to the provided byte buffer, returning the number of bytes written.
+    pub fn to_bytes(&self, buf: &mut [u8]) -> Result<usize> {
         let len = self.len();
         let buf_len = buf.len();
 
@@ -95,9 +137,18 @@ impl Message {
                 .zip(msg_iter)
                 .for_each(|(dst, src)| *dst = src);
 
-            self.data.to_bytes(&mut buf[meta_len..])
+            self.data
+                .to_bytes(&mut buf[meta_len..])
+                .map(|data_len| meta_len + data_len)
         }
     }
+
+    /// Parses a [Message] from the provided byte buffer.
+    ///
+    /// Equivalent to the [TryFrom] conversion from a byte slice.
+    pub fn from_bytes(buf: &[u8]) -> Result<Self> {
+        Self::try_from(buf)
+    }
 }
 
 impl From<&Message> for Vec<u8> {
@@ -124,24 +175,7 @@ impl TryFrom<&[u8]> for Message {
     type Error = Error;
 
     fn try_from(val: &[u8]) -> Result<Self> {
-        let len = val.len();
-        if len < MIN_LEN {
-            Err(Error::InvalidMessageLen((len, MIN_LEN)))
-        } else {
-            let id = MessageId::try_from(val[0])?;
-
-            let data_len = u16::from_le_bytes([val[1], val[2]]) as usize;
-            if data_len > len {
-                Err(Error::InvalidMessageDataLen((
-                    data_len,
-                    len - Self::meta_len(),
-                )))
-            } else {
-                let data = MessageData::try_from(&val[3..data_len])?;
-
-                Ok(Self { id, data })
-            }
-        }
+        Self::from_frame(val, false)
     }
 }
 
@@ -242,7 +276,49 @@ mod tests {
             0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
         ];
 
-        assert!(Message::try_from(raw.as_ref()).is_err());
+        assert_eq!(
+            Message::try_from(raw.as_ref()),
+            Err(Error::TruncatedFrame((16, 0xff)))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_message_frame_validation() -> Result<()> {
+        let raw = [0x12, 0x09, 0x00, 0x10, 0x01, 0x00, 0x11, 0x00, 0x06];
+        let exp = Message::try_from(raw.as_ref())?;
+
+        // trailing bytes past the declared length
+        let trailing = [raw.as_ref(), &[0xff, 0xff]].concat();
+        assert_eq!(
+            Message::try_from(trailing.as_slice()),
+            Err(Error::InvalidMessageLen((11, 9)))
+        );
+        assert_eq!(Message::from_bytes_lenient(trailing.as_slice())?, exp);
+
+        // declared length past the received bytes
+        let mut truncated = raw;
+        truncated[1] = 0x0a;
+        assert_eq!(
+            Message::try_from(truncated.as_ref()),
+            Err(Error::TruncatedFrame((9, 10)))
+        );
+        assert_eq!(Message::from_bytes_lenient(truncated.as_ref())?, exp);
+
+        // declared length shorter than the frame header
+        let mut short = raw;
+        short[1] = 0x02;
+        assert_eq!(
+            Message::try_from(short.as_ref()),
+            Err(Error::InvalidMessageLen((9, MIN_LEN)))
+        );
+        assert_eq!(Message::from_bytes_lenient(short.as_ref())?, exp);
+
+        assert_eq!(
+            Message::from_bytes_lenient(&raw[..MIN_LEN - 1]),
+            Err(Error::InvalidMessageLen((MIN_LEN - 1, MIN_LEN)))
+        );
 
         Ok(())
     }
@@ -287,4 +363,34 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_message_serde() -> Result<()> {
+        use crate::{MajorMinorStatus, RejectCode};
+
+        let msg =
+            Message::try_from([0x12, 0x09, 0x00, 0x10, 0x01, 0x00, 0x11, 0x00, 0x06].as_ref())?;
+
+        let json = serde_json::to_string(&msg).expect("serialize message");
+        let exp: Message = serde_json::from_str(json.as_str()).expect("deserialize message");
+        assert_eq!(exp, msg);
+
+        for code in [RejectCode::Inhibited, RejectCode::Return] {
+            let json = serde_json::to_string(&code).expect("serialize reject code");
+            assert_eq!(
+                serde_json::from_str::<RejectCode>(json.as_str()).expect("deserialize reject code"),
+                code
+            );
+        }
+
+        let status = MajorMinorStatus::from_u16(0x1000);
+        let json = serde_json::to_string(&status).expect("serialize status");
+        assert_eq!(
+            serde_json::from_str::<MajorMinorStatus>(json.as_str()).expect("deserialize status"),
+            status
+        );
+
+        Ok(())
+    }
 }
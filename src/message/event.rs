@@ -1,18 +1,32 @@
-use std::fmt;
+use alloc::vec::Vec;
+use core::fmt;
 
 use crate::{Error, EventCode, EventType, Message, MessageCode, MessageData, MessageType, Result};
 
+mod conditional_vend_event;
+mod dispense_event;
 mod escrow_event;
+mod failure_event;
 mod inhibit_event;
 mod rejected_event;
+mod typed_event;
+mod vend_valid_event;
+mod vendor_event;
 
+pub use conditional_vend_event::*;
+pub use dispense_event::*;
 pub use escrow_event::*;
+pub use failure_event::*;
 pub use inhibit_event::*;
 pub use rejected_event::*;
+pub use typed_event::*;
+pub use vend_valid_event::*;
+pub use vendor_event::*;
 
 /// Represents an event [Message] sent by the device.
 #[repr(C)]
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Event {
     event_type: EventType,
     event_code: EventCode,
@@ -29,6 +43,15 @@ impl Event {
         }
     }
 
+    /// Creates a new [Event] from the provided parameters.
+    pub fn create(event_type: EventType, event_code: EventCode, additional: &[u8]) -> Self {
+        Self {
+            event_type,
+            event_code,
+            additional: additional.into(),
+        }
+    }
+
     /// Gets the [MessageType] of the [Event].
     pub const fn message_type(&self) -> MessageType {
         MessageType::Event(self.event_type)
@@ -101,8 +124,8 @@ impl Event {
         self.event_type.is_empty() || self.event_code.is_empty()
     }
 
-    /// Writes the [Message] to the provided byte buffer.
-    pub fn to_bytes(&self, buf: &mut [u8]) -> Result<()> {
+    /// Writes the [Event] to the provided byte buffer, returning the number of bytes written.
+    pub fn to_bytes(&self, buf: &mut [u8]) -> Result<usize> {
         let len = self.len();
         let buf_len = buf.len();
 
@@ -119,9 +142,27 @@ impl Event {
                 .zip(msg_iter)
                 .for_each(|(dst, src)| *dst = src);
 
-            Ok(())
+            Ok(len)
         }
     }
+
+    /// Parses a [Event] from the provided byte buffer.
+    ///
+    /// Equivalent to the [TryFrom] conversion from a byte slice.
+    pub fn from_bytes(buf: &[u8]) -> Result<Self> {
+        Self::try_from(buf)
+    }
+
+    /// Converts the [Event] into an event [Message] from the device with the provided UID.
+    pub fn into_message(self, uid: u8) -> Message {
+        Message::new().with_data(
+            MessageData::new()
+                .with_uid(uid)
+                .with_message_type(self.message_type())
+                .with_message_code(self.message_code())
+                .with_additional(self.additional()),
+        )
+    }
 }
 
 impl TryFrom<&[u8]> for Event {
@@ -234,8 +275,12 @@ mod tests {
         assert_eq!(Event::try_from(msg), Ok(exp.clone()));
 
         let mut out = [0u8; Event::meta_len()];
-        assert_eq!(exp.to_bytes(out.as_mut()), Ok(()));
+        assert_eq!(exp.to_bytes(out.as_mut()), Ok(raw.len()));
         assert_eq!(out, raw);
+
+        let msg = Event::create(EventType::Sequence0, EventCode::PowerUp, &[]).into_message(1);
+        assert_eq!(msg.data().uid(), 1);
+        assert_eq!(Event::try_from(msg), Ok(exp));
     }
 
     #[test]
@@ -271,7 +316,7 @@ mod tests {
         assert_eq!(Event::try_from(msg), Ok(exp.clone()));
 
         let mut out = [0u8; 8];
-        assert_eq!(exp.to_bytes(out.as_mut()), Ok(()));
+        assert_eq!(exp.to_bytes(out.as_mut()), Ok(raw.len()));
         assert_eq!(out, raw);
     }
 }
@@ -0,0 +1,212 @@
+use core::fmt;
+
+use crate::{
+    Currency, Error, EventCode, EventType, Message, MessageCode, MessageData, MessageType, Result,
+    CURRENCY_LEN,
+};
+
+/// Represents a `Conditional Vend` event.
+///
+/// Sent instead of stacking, when the [ConditionalVendSettings](crate::ConditionalVendSettings)
+/// thresholds apply to the held note. The host then stacks or returns the note.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{ConditionalVendEvent, Currency, CurrencyCode, Denomination, EventType, Message};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, type, code, data
+/// let frame = [0x12, 0x0d, 0x00, 0x10, 0x00, 0x81, 0x0b, 0x11, 0x55, 0x53, 0x44, 0x32, 0x00];
+///
+/// let event = ConditionalVendEvent::create(
+///     EventType::Sequence1,
+///     Currency::new()
+///         .with_code(CurrencyCode::USD)
+///         .with_denomination(Denomination::from_value(50)),
+/// );
+///
+/// assert_eq!(ConditionalVendEvent::try_from(Message::try_from(frame.as_ref())?)?, event);
+/// assert_eq!(Vec::<u8>::from(Message::from(&event)), frame);
+/// # Ok(())
+/// # }
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConditionalVendEvent {
+    event_type: EventType,
+    currency: Currency,
+}
+
+impl ConditionalVendEvent {
+    /// Creates a new [ConditionalVendEvent].
+    pub const fn new() -> Self {
+        Self {
+            event_type: EventType::new(),
+            currency: Currency::new(),
+        }
+    }
+
+    /// Creates a new [ConditionalVendEvent] from the provided parameters.
+    pub const fn create(event_type: EventType, currency: Currency) -> Self {
+        Self {
+            event_type,
+            currency,
+        }
+    }
+
+    /// Gets the [MessageType] of the [ConditionalVendEvent].
+    pub const fn message_type(&self) -> MessageType {
+        MessageType::Event(self.event_type)
+    }
+
+    /// Gets the [EventType] of the [ConditionalVendEvent].
+    pub const fn event_type(&self) -> EventType {
+        self.event_type
+    }
+
+    /// Gets the [MessageCode] of the [ConditionalVendEvent].
+    pub const fn message_code(&self) -> MessageCode {
+        MessageCode::Event(self.event_code())
+    }
+
+    /// Gets the [EventCode] of the [ConditionalVendEvent].
+    pub const fn event_code(&self) -> EventCode {
+        EventCode::ConditionalVend
+    }
+
+    /// Gets the [Currency] of the held note.
+    pub const fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Converts the [ConditionalVendEvent] into an event [Message] from the device with the
+    /// provided UID.
+    pub fn into_message(self, uid: u8) -> Message {
+        MessageData::from(self).with_uid(uid).into()
+    }
+}
+
+impl Default for ConditionalVendEvent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&ConditionalVendEvent> for MessageData {
+    fn from(val: &ConditionalVendEvent) -> Self {
+        MessageData::new()
+            .with_message_type(val.message_type())
+            .with_message_code(val.message_code())
+            .with_additional(val.currency.into_bytes().as_ref())
+    }
+}
+
+impl From<ConditionalVendEvent> for MessageData {
+    fn from(val: ConditionalVendEvent) -> Self {
+        (&val).into()
+    }
+}
+
+impl From<&ConditionalVendEvent> for Message {
+    fn from(val: &ConditionalVendEvent) -> Self {
+        MessageData::from(val).into()
+    }
+}
+
+impl From<ConditionalVendEvent> for Message {
+    fn from(val: ConditionalVendEvent) -> Self {
+        MessageData::from(val).into()
+    }
+}
+
+impl TryFrom<&MessageData> for ConditionalVendEvent {
+    type Error = Error;
+
+    fn try_from(val: &MessageData) -> Result<Self> {
+        match val.message_code().event_code()? {
+            EventCode::ConditionalVend => {
+                let additional = val.additional();
+
+                if additional.len() < CURRENCY_LEN {
+                    Err(Error::InvalidEventLen((additional.len(), CURRENCY_LEN)))
+                } else {
+                    Ok(Self::create(
+                        val.message_type().event_type()?,
+                        Currency::from_bytes(additional)?,
+                    ))
+                }
+            }
+            code => Err(Error::InvalidEventCode(code.into())),
+        }
+    }
+}
+
+impl TryFrom<MessageData> for ConditionalVendEvent {
+    type Error = Error;
+
+    fn try_from(val: MessageData) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
+impl TryFrom<&Message> for ConditionalVendEvent {
+    type Error = Error;
+
+    fn try_from(val: &Message) -> Result<Self> {
+        val.data().try_into()
+    }
+}
+
+impl TryFrom<Message> for ConditionalVendEvent {
+    type Error = Error;
+
+    fn try_from(val: Message) -> Result<Self> {
+        val.data().try_into()
+    }
+}
+
+impl fmt::Display for ConditionalVendEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""event_type": {}, "#, self.event_type)?;
+        write!(f, r#""event_code": {}, "#, self.event_code())?;
+        write!(f, r#""currency": {}"#, self.currency)?;
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CurrencyCode, Denomination};
+
+    #[test]
+    fn test_conditional_vend_event() -> Result<()> {
+        let event = ConditionalVendEvent::create(
+            EventType::Sequence2,
+            Currency::new()
+                .with_code(CurrencyCode::JPY)
+                .with_denomination(Denomination::from_value(1000)),
+        );
+
+        let msg = Message::from(event);
+        assert_eq!(ConditionalVendEvent::try_from(&msg)?, event);
+
+        let vend_valid =
+            MessageData::from(event).with_message_code(MessageCode::Event(EventCode::VendValid));
+        assert_eq!(
+            ConditionalVendEvent::try_from(&vend_valid),
+            Err(Error::InvalidEventCode(EventCode::VendValid.into()))
+        );
+
+        let short = MessageData::from(event).with_additional(b"JPY");
+        assert_eq!(
+            ConditionalVendEvent::try_from(&short),
+            Err(Error::InvalidEventLen((3, CURRENCY_LEN)))
+        );
+
+        Ok(())
+    }
+}
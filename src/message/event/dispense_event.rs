@@ -0,0 +1,247 @@
+use core::fmt;
+
+use crate::{
+    Denomination, Error, EventCode, EventType, Message, MessageCode, MessageData, MessageType,
+    Result, DENOM_LEN,
+};
+
+/// Represents the byte length of the additional data of a [DispenseEvent].
+pub const DISPENSE_EVENT_LEN: usize = 3;
+
+/// Represents a recycler payout event.
+///
+/// Sent as `Recycler Dispensing` while notes are paid out, and `Recycler Dispensed` once the
+/// payout finished, with the [Denomination] and number of notes paid out so far.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{Denomination, DispenseEvent, EventCode, EventType, Message};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, type, code, data
+/// let frame = [0x12, 0x0b, 0x00, 0x11, 0x00, 0x81, 0x07, 0x21, 0x14, 0x00, 0x02];
+///
+/// let event = DispenseEvent::create(
+///     EventType::Sequence1,
+///     EventCode::RecyclerDispensed,
+///     Denomination::from_value(20),
+///     2,
+/// )?;
+///
+/// assert_eq!(DispenseEvent::try_from(Message::try_from(frame.as_ref())?)?, event);
+/// assert_eq!(Vec::<u8>::from(Message::from(&event)), frame);
+/// # Ok(())
+/// # }
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DispenseEvent {
+    event_type: EventType,
+    event_code: EventCode,
+    denomination: Denomination,
+    count: u8,
+}
+
+impl DispenseEvent {
+    /// Creates a new [DispenseEvent].
+    pub const fn new() -> Self {
+        Self {
+            event_type: EventType::new(),
+            event_code: EventCode::RecyclerDispensing,
+            denomination: Denomination::new(),
+            count: 0,
+        }
+    }
+
+    /// Creates a new [DispenseEvent] from the provided parameters.
+    ///
+    /// Returns an error if the [EventCode] is not a recycler payout event.
+    pub fn create(
+        event_type: EventType,
+        event_code: EventCode,
+        denomination: Denomination,
+        count: u8,
+    ) -> Result<Self> {
+        match event_code {
+            EventCode::RecyclerDispensing | EventCode::RecyclerDispensed => Ok(Self {
+                event_type,
+                event_code,
+                denomination,
+                count,
+            }),
+            code => Err(Error::InvalidEventCode(code.into())),
+        }
+    }
+
+    /// Gets the [MessageType] of the [DispenseEvent].
+    pub const fn message_type(&self) -> MessageType {
+        MessageType::Event(self.event_type)
+    }
+
+    /// Gets the [EventType] of the [DispenseEvent].
+    pub const fn event_type(&self) -> EventType {
+        self.event_type
+    }
+
+    /// Gets the [MessageCode] of the [DispenseEvent].
+    pub const fn message_code(&self) -> MessageCode {
+        MessageCode::Event(self.event_code)
+    }
+
+    /// Gets the [EventCode] of the [DispenseEvent].
+    pub const fn event_code(&self) -> EventCode {
+        self.event_code
+    }
+
+    /// Gets whether the payout finished.
+    pub const fn is_complete(&self) -> bool {
+        matches!(self.event_code, EventCode::RecyclerDispensed)
+    }
+
+    /// Gets the [Denomination] of the paid out notes.
+    pub const fn denomination(&self) -> Denomination {
+        self.denomination
+    }
+
+    /// Gets the number of notes paid out.
+    pub const fn count(&self) -> u8 {
+        self.count
+    }
+
+    /// Converts the [DispenseEvent] into an event [Message] from the device with the provided UID.
+    pub fn into_message(self, uid: u8) -> Message {
+        MessageData::from(self).with_uid(uid).into()
+    }
+}
+
+impl Default for DispenseEvent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&DispenseEvent> for MessageData {
+    fn from(val: &DispenseEvent) -> Self {
+        let [int, exp] = val.denomination.into_bytes();
+
+        MessageData::new()
+            .with_conf_id(crate::ConfId::AcceptorRecycler)
+            .with_message_type(val.message_type())
+            .with_message_code(val.message_code())
+            .with_additional(&[int, exp, val.count])
+    }
+}
+
+impl From<DispenseEvent> for MessageData {
+    fn from(val: DispenseEvent) -> Self {
+        (&val).into()
+    }
+}
+
+impl From<&DispenseEvent> for Message {
+    fn from(val: &DispenseEvent) -> Self {
+        MessageData::from(val).into()
+    }
+}
+
+impl From<DispenseEvent> for Message {
+    fn from(val: DispenseEvent) -> Self {
+        MessageData::from(val).into()
+    }
+}
+
+impl TryFrom<&MessageData> for DispenseEvent {
+    type Error = Error;
+
+    fn try_from(val: &MessageData) -> Result<Self> {
+        let additional = val.additional();
+
+        if additional.len() < DISPENSE_EVENT_LEN {
+            return Err(Error::InvalidEventLen((
+                additional.len(),
+                DISPENSE_EVENT_LEN,
+            )));
+        }
+
+        Self::create(
+            val.message_type().event_type()?,
+            val.message_code().event_code()?,
+            Denomination::from_bytes(&additional[..DENOM_LEN]),
+            additional[DENOM_LEN],
+        )
+    }
+}
+
+impl TryFrom<MessageData> for DispenseEvent {
+    type Error = Error;
+
+    fn try_from(val: MessageData) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
+impl TryFrom<&Message> for DispenseEvent {
+    type Error = Error;
+
+    fn try_from(val: &Message) -> Result<Self> {
+        val.data().try_into()
+    }
+}
+
+impl TryFrom<Message> for DispenseEvent {
+    type Error = Error;
+
+    fn try_from(val: Message) -> Result<Self> {
+        val.data().try_into()
+    }
+}
+
+impl fmt::Display for DispenseEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""event_type": {}, "#, self.event_type)?;
+        write!(f, r#""event_code": {}, "#, self.event_code)?;
+        write!(f, r#""denomination": {}, "#, self.denomination)?;
+        write!(f, r#""count": {}"#, self.count)?;
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dispense_event() -> Result<()> {
+        let event = DispenseEvent::create(
+            EventType::Sequence2,
+            EventCode::RecyclerDispensing,
+            Denomination::from_value(100),
+            1,
+        )?;
+        assert!(!event.is_complete());
+
+        let msg = Message::from(event);
+        assert_eq!(DispenseEvent::try_from(&msg)?, event);
+
+        assert_eq!(
+            DispenseEvent::create(
+                EventType::Sequence2,
+                EventCode::VendValid,
+                Denomination::new(),
+                1
+            ),
+            Err(Error::InvalidEventCode(EventCode::VendValid.into()))
+        );
+
+        let short = MessageData::from(event).with_additional(&[100, 0]);
+        assert_eq!(
+            DispenseEvent::try_from(&short),
+            Err(Error::InvalidEventLen((2, DISPENSE_EVENT_LEN)))
+        );
+
+        Ok(())
+    }
+}
@@ -4,12 +4,39 @@ use crate::{
 };
 
 mod escrow_data;
+mod media;
 
 pub use escrow_data::*;
-
-/// Represents an inhibit event.
+pub use media::*;
+
+/// Represents an escrow event.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{Currency, CurrencyCode, Denomination, EscrowData, EscrowEvent, EventType, Message};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, type, code, data
+/// let frame = [0x12, 0x0d, 0x00, 0x10, 0x00, 0x81, 0x02, 0x11, 0x55, 0x53, 0x44, 0x64, 0x00];
+///
+/// let event = EscrowEvent::create(
+///     EventType::Sequence1,
+///     EscrowData::Currency(
+///         Currency::new()
+///             .with_code(CurrencyCode::USD)
+///             .with_denomination(Denomination::from_value(100)),
+///     ),
+/// );
+///
+/// assert_eq!(EscrowEvent::try_from(Message::try_from(frame.as_ref())?)?, event);
+/// assert_eq!(Vec::<u8>::from(Message::from(&event)), frame);
+/// # Ok(())
+/// # }
+/// ```
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EscrowEvent {
     event_type: EventType,
     data: EscrowData,
@@ -65,6 +92,11 @@ impl EscrowEvent {
         &self.data
     }
 
+    /// Gets the [Media] kind of the [EscrowEvent].
+    pub const fn media(&self) -> Media {
+        self.data.media()
+    }
+
     /// Gets a reference to the [Currency] of the [EscrowEvent].
     pub const fn currency(&self) -> Result<&Currency> {
         match self.data() {
@@ -73,6 +105,11 @@ impl EscrowEvent {
         }
     }
 
+    /// Converts the [EscrowEvent] into an event [Message] from the device with the provided UID.
+    pub fn into_message(self, uid: u8) -> Message {
+        MessageData::from(self).with_uid(uid).into()
+    }
+
     /// Gets a reference to the [Ticket] of the [EscrowEvent].
     pub const fn ticket(&self) -> Result<&Ticket> {
         match self.data() {
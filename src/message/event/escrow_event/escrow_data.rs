@@ -1,14 +1,35 @@
-use crate::{Currency, Error, Result, Ticket};
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Currency, Error, Media, Result, Ticket, MAX_TICKET_LEN};
 
 /// Represents the minimum byte length of [EscrowData].
 pub const MIN_ESCROW_DATA_LEN: usize = 3;
 
 /// Represents the additional data of an [EscrowEvent].
+///
+/// Media other than banknotes are encoded with a zero first byte, followed by the raw media
+/// kind, the payload length, and the payload:
+///
+/// Field name  | Zero | Media kind | Length | Payload
+/// ------------|------|------------|--------|---------
+/// Size (byte) | 1    | 1          | 1      | Variable
+///
+/// New media kinds may be added in future releases: use [media](Self::media) to handle them
+/// gracefully.
 #[repr(C)]
+#[non_exhaustive]
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EscrowData {
     Currency(Currency),
     Ticket(Ticket),
+    /// A barcode coupon, with an ASCII code encoded like a [Ticket].
+    Coupon(Ticket),
+    /// An IC card, with the card identifier.
+    Card(Vec<u8>),
+    /// A media kind unknown to this library, with the raw media kind and payload.
+    Other((u8, Vec<u8>)),
 }
 
 impl EscrowData {
@@ -27,11 +48,25 @@ impl EscrowData {
         Self::Ticket(ticket)
     }
 
+    /// Gets the [Media] kind of the [EscrowData].
+    pub const fn media(&self) -> Media {
+        match self {
+            Self::Currency(_) => Media::Note,
+            Self::Ticket(_) => Media::Ticket,
+            Self::Coupon(_) => Media::Coupon,
+            Self::Card(_) => Media::Card,
+            Self::Other((kind, _)) => Media::from_kind(*kind),
+        }
+    }
+
     /// Gets the length of the [EscrowData].
     pub fn len(&self) -> usize {
         match self {
             Self::Currency(_data) => Currency::len(),
-            Self::Ticket(data) => data.len(),
+            Self::Ticket(data) | Self::Coupon(data) => data.len(),
+            Self::Card(data) | Self::Other((_, data)) => {
+                MIN_ESCROW_DATA_LEN + data.len().min(MAX_TICKET_LEN)
+            }
         }
     }
 
@@ -39,7 +74,8 @@ impl EscrowData {
     pub fn is_empty(&self) -> bool {
         match self {
             Self::Currency(data) => data.is_empty(),
-            Self::Ticket(data) => data.is_empty(),
+            Self::Ticket(data) | Self::Coupon(data) => data.is_empty(),
+            Self::Card(data) | Self::Other((_, data)) => data.is_empty(),
         }
     }
 
@@ -54,6 +90,12 @@ impl EscrowData {
             match self {
                 Self::Currency(data) => data.to_bytes(buf),
                 Self::Ticket(data) => data.to_bytes(buf),
+                _ => {
+                    buf.iter_mut()
+                        .zip(self.to_vec())
+                        .for_each(|(dst, src)| *dst = src);
+                    Ok(())
+                }
             }
         }
     }
@@ -63,6 +105,27 @@ impl EscrowData {
         match self {
             Self::Currency(data) => data.to_vec(),
             Self::Ticket(data) => data.to_vec(),
+            Self::Coupon(data) => Self::media_vec(self.media(), data.code().as_bytes()),
+            Self::Card(data) | Self::Other((_, data)) => Self::media_vec(self.media(), data),
+        }
+    }
+
+    fn media_vec(media: Media, payload: &[u8]) -> Vec<u8> {
+        // the length field is one byte, longer payloads are truncated
+        let payload = &payload[..payload.len().min(MAX_TICKET_LEN)];
+
+        [0, media.kind().unwrap_or_default(), payload.len() as u8]
+            .into_iter()
+            .chain(payload.iter().copied())
+            .collect()
+    }
+
+    fn from_media(kind: u8, payload: &[u8]) -> Result<Self> {
+        match Media::from_kind(kind) {
+            Media::Ticket => Ok(Self::Ticket(Ticket::try_from(payload)?)),
+            Media::Coupon => Ok(Self::Coupon(Ticket::try_from(payload)?)),
+            Media::Card => Ok(Self::Card(payload.into())),
+            _ => Ok(Self::Other((kind, payload.into()))),
         }
     }
 }
@@ -77,17 +140,15 @@ impl TryFrom<&[u8]> for EscrowData {
         if val_len < min_len {
             Err(Error::InvalidEscrowDataLen((val_len, min_len)))
         } else {
-            match u16::from_le_bytes([val[0], val[1]]) {
+            match val[0] {
                 0 => {
-                    let ticket_len = val[2] as usize;
-                    let exp_len = min_len + ticket_len;
-
-                    match ticket_len {
-                        t if t > 0 && val_len >= exp_len => {
-                            Ok(Self::Ticket(Ticket::try_from(val[3..3 + t].as_ref())?))
-                        }
-                        0 => Ok(Self::Ticket(Ticket::new())),
-                        _ => Err(Error::InvalidEscrowDataLen((val_len, exp_len))),
+                    let payload_len = val[2] as usize;
+                    let exp_len = min_len + payload_len;
+
+                    if val_len >= exp_len {
+                        Self::from_media(val[1], &val[min_len..exp_len])
+                    } else {
+                        Err(Error::InvalidEscrowDataLen((val_len, exp_len)))
                     }
                 }
                 _ => Ok(Self::Currency(val.try_into()?)),
@@ -117,3 +178,57 @@ impl Default for EscrowData {
         Self::new()
     }
 }
+
+impl fmt::Display for EscrowData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Currency(data) => write!(f, r#"{{"currency": {data}}}"#),
+            Self::Ticket(data) => write!(f, r#"{{"ticket": {data}}}"#),
+            Self::Coupon(data) => write!(f, r#"{{"coupon": {data}}}"#),
+            Self::Card(data) => write!(f, r#"{{"card": {data:?}}}"#),
+            Self::Other((kind, data)) => {
+                write!(f, r#"{{"media": {{"kind": {kind}, "data": {data:?}}}}}"#)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MEDIA_CARD;
+
+    #[test]
+    fn test_escrow_data_media() -> Result<()> {
+        let coupon = EscrowData::Coupon(Ticket::new().with_code("C0123")?);
+        let card = EscrowData::Card(vec![0xde, 0xad, 0xbe, 0xef]);
+        let other = EscrowData::Other((0x7f, vec![1, 2]));
+
+        for (data, media) in [
+            (&coupon, Media::Coupon),
+            (&card, Media::Card),
+            (&other, Media::Other(0x7f)),
+        ] {
+            assert_eq!(data.media(), media);
+            assert!(!data.media().is_cash());
+            assert_eq!(EscrowData::try_from(data.to_vec().as_slice())?, *data);
+
+            let mut buf = vec![0u8; data.len()];
+            data.to_bytes(&mut buf)?;
+            assert_eq!(buf, data.to_vec());
+        }
+
+        assert_eq!(coupon.to_vec(), b"\x00\x01\x05C0123");
+
+        let ticket = EscrowData::Ticket(Ticket::new().with_code("T0123")?);
+        assert_eq!(EscrowData::try_from(ticket.to_vec().as_slice())?, ticket);
+        assert_eq!(ticket.media(), Media::Ticket);
+
+        assert_eq!(
+            EscrowData::try_from([0x00, MEDIA_CARD, 0x04, 0x01]),
+            Err(Error::InvalidEscrowDataLen((4, 7)))
+        );
+
+        Ok(())
+    }
+}
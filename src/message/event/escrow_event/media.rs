@@ -0,0 +1,94 @@
+use core::fmt;
+
+/// The raw media kind of a [Ticket](crate::Ticket) in [EscrowData](super::EscrowData).
+pub const MEDIA_TICKET: u8 = 0;
+/// The raw media kind of a coupon in [EscrowData](super::EscrowData).
+pub const MEDIA_COUPON: u8 = 1;
+/// The raw media kind of an IC card in [EscrowData](super::EscrowData).
+pub const MEDIA_CARD: u8 = 2;
+
+/// Represents the kind of media held in escrow.
+///
+/// Session logic that only needs to distinguish media (e.g. to count, route, or reject them)
+/// should match on [Media] instead of [EscrowData](super::EscrowData), so new media kinds are
+/// handled gracefully.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Media {
+    /// A banknote.
+    #[default]
+    Note,
+    /// A barcode ticket.
+    Ticket,
+    /// A barcode coupon.
+    Coupon,
+    /// An IC card.
+    Card,
+    /// A media kind unknown to this library, with the raw media kind.
+    Other(u8),
+}
+
+impl Media {
+    /// Creates a new [Media].
+    pub const fn new() -> Self {
+        Self::Note
+    }
+
+    /// Infallible conversion from a raw media kind into a [Media].
+    ///
+    /// Banknotes have no raw media kind.
+    pub const fn from_kind(kind: u8) -> Self {
+        match kind {
+            MEDIA_TICKET => Self::Ticket,
+            MEDIA_COUPON => Self::Coupon,
+            MEDIA_CARD => Self::Card,
+            kind => Self::Other(kind),
+        }
+    }
+
+    /// Gets the raw media kind of the [Media].
+    ///
+    /// Returns `None` for banknotes.
+    pub const fn kind(&self) -> Option<u8> {
+        match self {
+            Self::Note => None,
+            Self::Ticket => Some(MEDIA_TICKET),
+            Self::Coupon => Some(MEDIA_COUPON),
+            Self::Card => Some(MEDIA_CARD),
+            Self::Other(kind) => Some(*kind),
+        }
+    }
+
+    /// Gets whether the [Media] is a banknote with a cash value.
+    pub const fn is_cash(&self) -> bool {
+        matches!(self, Self::Note)
+    }
+}
+
+impl From<Media> for &'static str {
+    fn from(val: Media) -> Self {
+        match val {
+            Media::Note => "note",
+            Media::Ticket => "ticket",
+            Media::Coupon => "coupon",
+            Media::Card => "card",
+            Media::Other(_) => "other",
+        }
+    }
+}
+
+impl From<&Media> for &'static str {
+    fn from(val: &Media) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for Media {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Other(kind) => write!(f, r#""other({kind:#04x})""#),
+            media => write!(f, r#""{}""#, <&str>::from(media)),
+        }
+    }
+}
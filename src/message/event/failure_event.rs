@@ -0,0 +1,241 @@
+use crate::{
+    Error, EventCode, EventType, FailureCode, Message, MessageCode, MessageData, MessageType,
+    Result,
+};
+
+/// Represents a failure event.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{EventCode, EventType, FailureCode, FailureEvent, Message};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, type, code, data
+/// let frame = [0x12, 0x09, 0x00, 0x10, 0x00, 0x84, 0x02, 0x02, 0x12];
+///
+/// let event = FailureEvent::create(EventType::Sequence4, EventCode::Failure, FailureCode::StackMotor);
+///
+/// assert_eq!(FailureEvent::try_from(Message::try_from(frame.as_ref())?)?, event);
+/// assert_eq!(Vec::<u8>::from(Message::from(&event)), frame);
+/// # Ok(())
+/// # }
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FailureEvent {
+    event_type: EventType,
+    event_code: EventCode,
+    failure_code: FailureCode,
+}
+
+impl FailureEvent {
+    /// Creates a new [FailureEvent].
+    pub const fn new() -> Self {
+        Self {
+            event_type: EventType::new(),
+            event_code: EventCode::Failure,
+            failure_code: FailureCode::new(),
+        }
+    }
+
+    /// Creates a new [FailureEvent] from the provided parameters.
+    pub const fn create(
+        event_type: EventType,
+        event_code: EventCode,
+        failure_code: FailureCode,
+    ) -> Self {
+        Self {
+            event_type,
+            event_code,
+            failure_code,
+        }
+    }
+
+    /// Gets the [MessageType] of the [FailureEvent].
+    pub const fn message_type(&self) -> MessageType {
+        MessageType::Event(self.event_type())
+    }
+
+    /// Gets the [EventType] of the [FailureEvent].
+    pub const fn event_type(&self) -> EventType {
+        self.event_type
+    }
+
+    /// Sets the [EventType] of the [FailureEvent].
+    pub fn set_event_type(&mut self, event_type: EventType) {
+        self.event_type = event_type;
+    }
+
+    /// Builder function that sets the [EventType] of the [FailureEvent].
+    pub fn with_event_type(mut self, event_type: EventType) -> Self {
+        self.set_event_type(event_type);
+        self
+    }
+
+    /// Gets the [MessageCode] of the [FailureEvent].
+    pub const fn message_code(&self) -> MessageCode {
+        MessageCode::Event(self.event_code())
+    }
+
+    /// Gets the [EventCode] of the [FailureEvent].
+    pub const fn event_code(&self) -> EventCode {
+        self.event_code
+    }
+
+    /// Sets the [EventCode] of the [FailureEvent].
+    ///
+    /// `event_code` must be one of [EventCode::Failure] or [EventCode::AcceptorFailure].
+    pub fn set_event_code(&mut self, event_code: EventCode) -> Result<()> {
+        match event_code {
+            EventCode::Failure | EventCode::AcceptorFailure => {
+                self.event_code = event_code;
+                Ok(())
+            }
+            _ => Err(Error::InvalidEventCode(event_code.into())),
+        }
+    }
+
+    /// Builder function that sets the [EventCode] of the [FailureEvent].
+    ///
+    /// `event_code` must be one of [EventCode::Failure] or [EventCode::AcceptorFailure].
+    pub fn with_event_code(mut self, event_code: EventCode) -> Result<Self> {
+        self.set_event_code(event_code)?;
+        Ok(self)
+    }
+
+    /// Gets the [FailureCode] of the [FailureEvent].
+    pub const fn failure_code(&self) -> FailureCode {
+        self.failure_code
+    }
+
+    /// Sets the [FailureCode] of the [FailureEvent].
+    pub fn set_failure_code(&mut self, failure_code: FailureCode) {
+        self.failure_code = failure_code;
+    }
+
+    /// Builder function that sets the [FailureCode] of the [FailureEvent].
+    pub fn with_failure_code(mut self, failure_code: FailureCode) -> Self {
+        self.set_failure_code(failure_code);
+        self
+    }
+
+    /// Converts the [FailureEvent] into an event [Message] from the device with the provided UID.
+    pub fn into_message(self, uid: u8) -> Message {
+        MessageData::from(self).with_uid(uid).into()
+    }
+}
+
+impl From<&FailureEvent> for MessageData {
+    fn from(val: &FailureEvent) -> Self {
+        MessageData::new()
+            .with_message_type(val.message_type())
+            .with_message_code(val.message_code())
+            .with_additional(&[val.failure_code().into()])
+    }
+}
+
+impl From<FailureEvent> for MessageData {
+    fn from(val: FailureEvent) -> Self {
+        (&val).into()
+    }
+}
+
+impl From<&FailureEvent> for Message {
+    fn from(val: &FailureEvent) -> Self {
+        MessageData::from(val).into()
+    }
+}
+
+impl From<FailureEvent> for Message {
+    fn from(val: FailureEvent) -> Self {
+        MessageData::from(val).into()
+    }
+}
+
+impl TryFrom<&MessageData> for FailureEvent {
+    type Error = Error;
+
+    fn try_from(val: &MessageData) -> Result<Self> {
+        let event_code = val.message_code().event_code()?;
+        let failure_code =
+            FailureCode::try_from(val.additional().first().cloned().unwrap_or(0xffu8))?;
+
+        match event_code {
+            EventCode::Failure | EventCode::AcceptorFailure => Ok(Self {
+                event_type: val.message_type().event_type()?,
+                event_code,
+                failure_code,
+            }),
+            code => Err(Error::InvalidEventCode(code.into())),
+        }
+    }
+}
+
+impl TryFrom<MessageData> for FailureEvent {
+    type Error = Error;
+
+    fn try_from(val: MessageData) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
+impl TryFrom<&Message> for FailureEvent {
+    type Error = Error;
+
+    fn try_from(val: &Message) -> Result<Self> {
+        val.data().try_into()
+    }
+}
+
+impl TryFrom<Message> for FailureEvent {
+    type Error = Error;
+
+    fn try_from(val: Message) -> Result<Self> {
+        val.data().try_into()
+    }
+}
+
+impl Default for FailureEvent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failure_event() -> Result<()> {
+        let event = FailureEvent::new()
+            .with_event_type(EventType::Sequence2)
+            .with_event_code(EventCode::AcceptorFailure)?
+            .with_failure_code(FailureCode::StackMotor);
+
+        let msg = event.into_message(3);
+
+        assert_eq!(msg.data().uid(), 3);
+        assert_eq!(
+            msg.data().message_type(),
+            MessageType::Event(EventType::Sequence2)
+        );
+        assert_eq!(
+            msg.data().message_code(),
+            MessageCode::Event(EventCode::AcceptorFailure)
+        );
+        assert_eq!(msg.data().additional(), [FailureCode::StackMotor.to_u8()]);
+        assert_eq!(FailureEvent::try_from(&msg), Ok(event));
+
+        assert!(FailureEvent::new()
+            .with_event_code(EventCode::Idle)
+            .is_err());
+        assert!(FailureEvent::try_from(
+            MessageData::from(event).with_additional(&[FailureCode::Reserved.to_u8()])
+        )
+        .is_err());
+
+        Ok(())
+    }
+}
@@ -1,8 +1,26 @@
 use crate::{Error, EventCode, EventType, Message, MessageCode, MessageData, MessageType, Result};
 
 /// Represents an inhibit event.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{EventType, InhibitEvent, Message};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, type, code, data
+/// let frame = [0x12, 0x08, 0x00, 0x10, 0x00, 0x82, 0x00, 0x01];
+///
+/// let event = InhibitEvent::create(EventType::Sequence2);
+///
+/// assert_eq!(InhibitEvent::try_from(Message::try_from(frame.as_ref())?)?, event);
+/// assert_eq!(Vec::<u8>::from(Message::from(&event)), frame);
+/// # Ok(())
+/// # }
+/// ```
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InhibitEvent {
     event_type: EventType,
 }
@@ -50,6 +68,11 @@ impl InhibitEvent {
     pub const fn event_code(&self) -> EventCode {
         EventCode::Inhibit
     }
+
+    /// Converts the [InhibitEvent] into an event [Message] from the device with the provided UID.
+    pub fn into_message(self, uid: u8) -> Message {
+        MessageData::from(self).with_uid(uid).into()
+    }
 }
 
 impl From<&InhibitEvent> for MessageData {
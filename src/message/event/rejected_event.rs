@@ -5,8 +5,30 @@ mod reject_code;
 pub use reject_code::*;
 
 /// Represents a reject event.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{EventCode, EventType, Message, RejectCode, RejectedEvent};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, type, code, data
+/// let frame = [0x12, 0x09, 0x00, 0x10, 0x00, 0x83, 0x04, 0x11, 0x76];
+///
+/// let event = RejectedEvent::create(
+///     EventType::Sequence3,
+///     EventCode::AcceptorRejected,
+///     RejectCode::Inhibited,
+/// );
+///
+/// assert_eq!(RejectedEvent::try_from(Message::try_from(frame.as_ref())?)?, event);
+/// assert_eq!(Vec::<u8>::from(Message::from(&event)), frame);
+/// # Ok(())
+/// # }
+/// ```
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RejectedEvent {
     event_type: EventType,
     event_code: EventCode,
@@ -103,6 +125,11 @@ impl RejectedEvent {
         self.set_reject_code(reject_code);
         self
     }
+
+    /// Converts the [RejectedEvent] into an event [Message] from the device with the provided UID.
+    pub fn into_message(self, uid: u8) -> Message {
+        MessageData::from(self).with_uid(uid).into()
+    }
 }
 
 impl From<&RejectedEvent> for MessageData {
@@ -1,4 +1,4 @@
-use std::{fmt, mem};
+use core::{fmt, mem};
 
 use crate::{Error, Result};
 
@@ -29,6 +29,7 @@ const RESERVED: u8 = 0xff;
 /// Represents note rejection codes.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RejectCode {
     /// Abnormal note insertion.
     AbnormalInsertion = ABNORMAL_INSERTION,
@@ -213,6 +214,7 @@ impl fmt::Display for RejectCode {
 /// Convenience struct to display detailed [RejectCode] message.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RejectCodeDetails(pub RejectCode);
 
 impl From<RejectCodeDetails> for &'static str {
@@ -313,7 +315,7 @@ mod tests {
             RejectCode::TicketLength,
         ];
 
-        for (raw, exp) in raw_vals.into_iter().zip(expected.into_iter()) {
+        for (raw, exp) in raw_vals.into_iter().zip(expected) {
             assert_eq!(RejectCode::try_from(raw), Ok(exp));
             assert_eq!(RejectCode::from_u8(raw), exp);
         }
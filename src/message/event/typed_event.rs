@@ -0,0 +1,113 @@
+use crate::{
+    ConditionalVendEvent, DispenseEvent, EscrowEvent, Event, EventCode, InhibitEvent, Message,
+    RejectedEvent, Result, VendValidEvent, VendorEvent,
+};
+
+/// Represents a device event decoded into its typed representation.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TypedEvent {
+    /// An `Escrow` event.
+    Escrow(EscrowEvent),
+    /// An `Inhibit` event.
+    Inhibit(InhibitEvent),
+    /// A `Rejected` or `AcceptorRejected` event.
+    Rejected(RejectedEvent),
+    /// A `RecyclerDispensing` or `RecyclerDispensed` event.
+    Dispense(DispenseEvent),
+    /// A `ConditionalVend` event.
+    ConditionalVend(ConditionalVendEvent),
+    /// A `VendValid` event.
+    VendValid(VendValidEvent),
+    /// Any other standard event, without a dedicated type.
+    Generic(Event),
+    /// A vendor-specific event decoded by a [VendorRegistry](crate::VendorRegistry).
+    Vendor(VendorEvent),
+}
+
+impl TypedEvent {
+    /// Gets whether the [TypedEvent] is a vendor-specific event.
+    pub const fn is_vendor(&self) -> bool {
+        matches!(self, Self::Vendor(_))
+    }
+
+    /// Gets the raw event code of the [TypedEvent].
+    pub fn code(&self) -> u16 {
+        match self {
+            Self::Escrow(_) => EventCode::Escrow.into(),
+            Self::Inhibit(_) => EventCode::Inhibit.into(),
+            Self::Rejected(evt) => evt.event_code().into(),
+            Self::Dispense(evt) => evt.event_code().into(),
+            Self::ConditionalVend(_) => EventCode::ConditionalVend.into(),
+            Self::VendValid(_) => EventCode::VendValid.into(),
+            Self::Generic(evt) => evt.event_code().into(),
+            Self::Vendor(evt) => evt.code(),
+        }
+    }
+}
+
+impl TryFrom<&Message> for TypedEvent {
+    type Error = crate::Error;
+
+    fn try_from(val: &Message) -> Result<Self> {
+        match val.data().message_code().event_code()? {
+            EventCode::Escrow => Ok(Self::Escrow(val.try_into()?)),
+            EventCode::Inhibit => Ok(Self::Inhibit(val.try_into()?)),
+            EventCode::Rejected | EventCode::AcceptorRejected => {
+                Ok(Self::Rejected(val.try_into()?))
+            }
+            EventCode::RecyclerDispensing | EventCode::RecyclerDispensed => {
+                Ok(Self::Dispense(val.try_into()?))
+            }
+            EventCode::ConditionalVend => Ok(Self::ConditionalVend(val.try_into()?)),
+            EventCode::VendValid => Ok(Self::VendValid(val.try_into()?)),
+            _ => Ok(Self::Generic(val.try_into()?)),
+        }
+    }
+}
+
+impl TryFrom<Message> for TypedEvent {
+    type Error = crate::Error;
+
+    fn try_from(val: Message) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventType, MessageCode, MessageData, MessageType, RejectCode};
+
+    #[test]
+    fn test_typed_event() -> Result<()> {
+        let event_type = MessageType::Event(EventType::Sequence1);
+
+        let msg = Message::new().with_data(
+            MessageData::new()
+                .with_message_type(event_type)
+                .with_message_code(MessageCode::Event(EventCode::AcceptorRejected))
+                .with_additional(&[RejectCode::Inhibited.into()]),
+        );
+
+        assert_eq!(
+            TypedEvent::try_from(&msg)?,
+            TypedEvent::Rejected(RejectedEvent::create(
+                EventType::Sequence1,
+                EventCode::AcceptorRejected,
+                RejectCode::Inhibited
+            ))
+        );
+
+        let msg = Message::new().with_data(
+            MessageData::new()
+                .with_message_type(event_type)
+                .with_message_code(MessageCode::Event(EventCode::Idle)),
+        );
+        let typed = TypedEvent::try_from(&msg)?;
+
+        assert!(matches!(typed, TypedEvent::Generic(_)));
+        assert_eq!(typed.code(), u16::from(EventCode::Idle));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,199 @@
+use crate::{
+    Error, EscrowData, EscrowEvent, EventCode, EventType, Message, MessageCode, MessageData,
+    MessageType, MonetaryAmount, Result,
+};
+
+/// Represents a `Vend Valid` event.
+///
+/// The device sends `Vend Valid` once the escrowed note is stacked, and resends it until the host
+/// acknowledges it. The event frame carries no additional data: the credited media is attached
+/// from the preceding `Escrow` event, with [with_escrow](Self::with_escrow).
+///
+/// # Example
+///
+/// ```
+/// use jcm::{Currency, CurrencyCode, Denomination, EscrowData, EventType, Message, VendValidEvent};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, type, code
+/// let frame = [0x12, 0x08, 0x00, 0x10, 0x00, 0x81, 0x03, 0x11];
+///
+/// let event = VendValidEvent::try_from(Message::try_from(frame.as_ref())?)?;
+/// assert_eq!(event, VendValidEvent::create(EventType::Sequence1));
+/// assert_eq!(event.amount(), None);
+///
+/// let note = Currency::new()
+///     .with_code(CurrencyCode::USD)
+///     .with_denomination(Denomination::from_value(20));
+/// let event = event.with_escrow(EscrowData::new_currency(note));
+/// assert_eq!(event.amount().map(|a| a.format()), Some("$20.00".into()));
+///
+/// assert_eq!(Vec::<u8>::from(Message::from(&event)), frame);
+/// # Ok(())
+/// # }
+/// ```
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VendValidEvent {
+    event_type: EventType,
+    escrow: Option<EscrowData>,
+}
+
+impl VendValidEvent {
+    /// Creates a new [VendValidEvent].
+    pub const fn new() -> Self {
+        Self {
+            event_type: EventType::new(),
+            escrow: None,
+        }
+    }
+
+    /// Creates a new [VendValidEvent] from the provided parameter.
+    pub const fn create(event_type: EventType) -> Self {
+        Self {
+            event_type,
+            escrow: None,
+        }
+    }
+
+    /// Gets the [MessageType] of the [VendValidEvent].
+    pub const fn message_type(&self) -> MessageType {
+        MessageType::Event(self.event_type())
+    }
+
+    /// Gets the [EventType] of the [VendValidEvent].
+    pub const fn event_type(&self) -> EventType {
+        self.event_type
+    }
+
+    /// Sets the [EventType] of the [VendValidEvent].
+    pub fn set_event_type(&mut self, event_type: EventType) {
+        self.event_type = event_type;
+    }
+
+    /// Builder function that sets the [EventType] of the [VendValidEvent].
+    pub fn with_event_type(mut self, event_type: EventType) -> Self {
+        self.set_event_type(event_type);
+        self
+    }
+
+    /// Gets the [MessageCode] of the [VendValidEvent].
+    pub const fn message_code(&self) -> MessageCode {
+        MessageCode::Event(self.event_code())
+    }
+
+    /// Gets the [EventCode] of the [VendValidEvent].
+    pub const fn event_code(&self) -> EventCode {
+        EventCode::VendValid
+    }
+
+    /// Gets the [EscrowData] of the credited media, if attached.
+    pub const fn escrow(&self) -> Option<&EscrowData> {
+        self.escrow.as_ref()
+    }
+
+    /// Sets the [EscrowData] of the credited media.
+    pub fn set_escrow(&mut self, escrow: EscrowData) {
+        self.escrow = Some(escrow);
+    }
+
+    /// Unsets the [EscrowData] of the credited media.
+    pub fn unset_escrow(&mut self) {
+        self.escrow = None;
+    }
+
+    /// Builder function that sets the [EscrowData] of the credited media.
+    pub fn with_escrow(mut self, escrow: EscrowData) -> Self {
+        self.set_escrow(escrow);
+        self
+    }
+
+    /// Builder function that sets the [EscrowData] from the preceding [EscrowEvent].
+    pub fn with_escrow_event(self, escrow: &EscrowEvent) -> Self {
+        self.with_escrow(escrow.data().clone())
+    }
+
+    /// Gets the credited [MonetaryAmount].
+    ///
+    /// Returns `None` if no [EscrowData] is attached, or if the media is not a currency note.
+    pub fn amount(&self) -> Option<MonetaryAmount> {
+        match self.escrow.as_ref() {
+            Some(EscrowData::Currency(currency)) => Some(currency.into()),
+            _ => None,
+        }
+    }
+
+    /// Converts the [VendValidEvent] into an event [Message] from the device with the provided
+    /// UID.
+    pub fn into_message(self, uid: u8) -> Message {
+        MessageData::from(self).with_uid(uid).into()
+    }
+}
+
+impl From<&VendValidEvent> for MessageData {
+    fn from(val: &VendValidEvent) -> Self {
+        MessageData::new()
+            .with_message_type(val.message_type())
+            .with_message_code(val.message_code())
+    }
+}
+
+impl From<VendValidEvent> for MessageData {
+    fn from(val: VendValidEvent) -> Self {
+        (&val).into()
+    }
+}
+
+impl From<&VendValidEvent> for Message {
+    fn from(val: &VendValidEvent) -> Self {
+        MessageData::from(val).into()
+    }
+}
+
+impl From<VendValidEvent> for Message {
+    fn from(val: VendValidEvent) -> Self {
+        MessageData::from(val).into()
+    }
+}
+
+impl TryFrom<&MessageData> for VendValidEvent {
+    type Error = Error;
+
+    fn try_from(val: &MessageData) -> Result<Self> {
+        match val.message_code().event_code()? {
+            EventCode::VendValid => Ok(Self::create(val.message_type().event_type()?)),
+            code => Err(Error::InvalidEventCode(code.into())),
+        }
+    }
+}
+
+impl TryFrom<MessageData> for VendValidEvent {
+    type Error = Error;
+
+    fn try_from(val: MessageData) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
+impl TryFrom<&Message> for VendValidEvent {
+    type Error = Error;
+
+    fn try_from(val: &Message) -> Result<Self> {
+        val.data().try_into()
+    }
+}
+
+impl TryFrom<Message> for VendValidEvent {
+    type Error = Error;
+
+    fn try_from(val: Message) -> Result<Self> {
+        val.data().try_into()
+    }
+}
+
+impl Default for VendValidEvent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,354 @@
+use alloc::{collections::BTreeMap, string::String, sync::Arc, vec::Vec};
+use core::any::Any;
+use core::{fmt, mem};
+
+use crate::{
+    Error, EventCode, EventType, Message, MessageCode, MessageType, Result, SpecVersion, TypedEvent,
+};
+
+type VendorDecoder = Arc<dyn Fn(&[u8]) -> Result<Arc<dyn Any + Send + Sync>> + Send + Sync>;
+
+// Offsets into a raw event frame: message ID, length, conf ID, UID, message type, event code
+const TYPE_OFFSET: usize = 5;
+const CODE_OFFSET: usize = 6;
+const DATA_OFFSET: usize = CODE_OFFSET + mem::size_of::<u16>();
+
+/// Represents an event with a vendor-specific code, decoded by a [VendorRegistry] handler.
+#[derive(Clone)]
+pub struct VendorEvent {
+    event_type: EventType,
+    code: u16,
+    name: String,
+    additional: Vec<u8>,
+    decoded: Arc<dyn Any + Send + Sync>,
+}
+
+impl VendorEvent {
+    /// Gets the [EventType] of the [VendorEvent].
+    pub const fn event_type(&self) -> EventType {
+        self.event_type
+    }
+
+    /// Gets the raw vendor event code of the [VendorEvent].
+    pub const fn code(&self) -> u16 {
+        self.code
+    }
+
+    /// Gets the name registered for the vendor event code.
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Gets a reference to the raw additional data of the [VendorEvent].
+    pub fn additional(&self) -> &[u8] {
+        self.additional.as_ref()
+    }
+
+    /// Gets a reference to the decoded payload, if it has the requested type.
+    pub fn decoded<T: Any>(&self) -> Option<&T> {
+        self.decoded.downcast_ref::<T>()
+    }
+}
+
+impl fmt::Debug for VendorEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VendorEvent")
+            .field("event_type", &self.event_type)
+            .field("code", &self.code)
+            .field("name", &self.name)
+            .field("additional", &self.additional)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for VendorEvent {
+    fn eq(&self, oth: &Self) -> bool {
+        self.event_type == oth.event_type
+            && self.code == oth.code
+            && self.name == oth.name
+            && self.additional == oth.additional
+    }
+}
+
+impl fmt::Display for VendorEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""event_type": {}, "#, self.event_type)?;
+        write!(f, r#""code": {:#06x}, "#, self.code)?;
+        write!(f, r#""name": "{}", "#, self.name)?;
+        write!(f, r#""additional_data": {:?}"#, self.additional)?;
+        write!(f, "}}")
+    }
+}
+
+/// Represents a registry of decoders for vendor-specific event codes.
+///
+/// Event codes outside the [EventCode] set normally fail to parse. Registered codes are instead
+/// decoded into a [TypedEvent::Vendor].
+///
+/// On a live device, pass the registry to
+/// `usb::poll_device_message_with_vendor` to receive vendor events from the device poller.
+///
+/// Standard codes are resolved against the registry [SpecVersion]: codes introduced in a later
+/// revision are free for vendor use on older device generations.
+#[derive(Clone, Default)]
+pub struct VendorRegistry {
+    decoders: BTreeMap<u16, (String, VendorDecoder)>,
+    spec_version: SpecVersion,
+}
+
+impl VendorRegistry {
+    /// Creates a new, empty [VendorRegistry] for the latest [SpecVersion].
+    pub fn new() -> Self {
+        Self {
+            decoders: BTreeMap::new(),
+            spec_version: SpecVersion::LATEST,
+        }
+    }
+
+    /// Gets the [SpecVersion] used to resolve standard event codes.
+    pub const fn spec_version(&self) -> SpecVersion {
+        self.spec_version
+    }
+
+    /// Builder function that sets the [SpecVersion] used to resolve standard event codes.
+    ///
+    /// Set the [SpecVersion] before registering decoders for codes from later revisions.
+    pub fn with_spec_version(mut self, spec_version: SpecVersion) -> Self {
+        self.spec_version = spec_version;
+        self
+    }
+
+    /// Registers a decoder for a vendor-specific event code.
+    ///
+    /// Returns an error if the code is a standard [EventCode] in the registry [SpecVersion].
+    pub fn register<T, F>(&mut self, code: u16, name: &str, decoder: F) -> Result<()>
+    where
+        T: Any + Send + Sync,
+        F: Fn(&[u8]) -> Result<T> + Send + Sync + 'static,
+    {
+        if self.spec_version.event_code(code) != EventCode::Reserved {
+            Err(Error::InvalidEventCode(code))
+        } else {
+            let decoder: VendorDecoder = Arc::new(move |data: &[u8]| {
+                decoder(data).map(|d| Arc::new(d) as Arc<dyn Any + Send + Sync>)
+            });
+            self.decoders.insert(code, (name.into(), decoder));
+            Ok(())
+        }
+    }
+
+    /// Builder function that registers a decoder for a vendor-specific event code.
+    pub fn with_decoder<T, F>(mut self, code: u16, name: &str, decoder: F) -> Result<Self>
+    where
+        T: Any + Send + Sync,
+        F: Fn(&[u8]) -> Result<T> + Send + Sync + 'static,
+    {
+        self.register(code, name, decoder)?;
+        Ok(self)
+    }
+
+    /// Gets whether a decoder is registered for the vendor event code.
+    pub fn contains(&self, code: u16) -> bool {
+        self.decoders.contains_key(&code)
+    }
+
+    /// Decodes the additional data of a vendor-specific event.
+    pub fn decode_vendor(
+        &self,
+        event_type: EventType,
+        code: u16,
+        additional: &[u8],
+    ) -> Result<VendorEvent> {
+        let (name, decoder) = self
+            .decoders
+            .get(&code)
+            .ok_or(Error::InvalidEventCode(code))?;
+
+        Ok(VendorEvent {
+            event_type,
+            code,
+            name: name.clone(),
+            additional: additional.into(),
+            decoded: decoder(additional)?,
+        })
+    }
+
+    /// Decodes a raw event frame into a [TypedEvent].
+    ///
+    /// Standard events are parsed as usual, and registered vendor codes become
+    /// [TypedEvent::Vendor]. Unregistered unknown codes return the original parsing error.
+    ///
+    /// Standard codes from a later [SpecVersion] are decoded as vendor events when registered,
+    /// and rejected otherwise.
+    pub fn decode(&self, buf: &[u8]) -> Result<TypedEvent> {
+        match Message::try_from(buf) {
+            Ok(msg) => match (msg.data().message_type(), msg.data().message_code()) {
+                (MessageType::Event(event_type), MessageCode::Event(code))
+                    if !self.spec_version.supports_event(code) =>
+                {
+                    let code = u16::from(code);
+
+                    if self.contains(code) {
+                        self.decode_vendor(event_type, code, msg.data().additional())
+                            .map(TypedEvent::Vendor)
+                    } else {
+                        Err(Error::InvalidEventCode(code))
+                    }
+                }
+                _ => TypedEvent::try_from(&msg),
+            },
+            Err(err) => {
+                let vendor = match (
+                    buf.get(TYPE_OFFSET).copied().map(MessageType::from_u8),
+                    buf.get(CODE_OFFSET..DATA_OFFSET),
+                ) {
+                    (Some(MessageType::Event(event_type)), Some(code)) => {
+                        let code = u16::from_le_bytes([code[0], code[1]]);
+                        let len = buf
+                            .get(1..3)
+                            .map(|l| u16::from_le_bytes([l[0], l[1]]) as usize)
+                            .unwrap_or(buf.len())
+                            .clamp(DATA_OFFSET, buf.len());
+
+                        self.contains(code)
+                            .then(|| self.decode_vendor(event_type, code, &buf[DATA_OFFSET..len]))
+                    }
+                    _ => None,
+                };
+
+                vendor.unwrap_or(Err(err)).map(TypedEvent::Vendor)
+            }
+        }
+    }
+}
+
+/// Creates the raw `ACK` response frame to a raw vendor-specific event frame.
+///
+/// Vendor codes are outside the [EventCode] set, so the response can not be built as a
+/// [Message]. Returns `None` if the frame is too short to hold an event code.
+#[cfg(feature = "usb")]
+pub(crate) fn vendor_event_ack(frame: &[u8]) -> Option<Vec<u8>> {
+    let header = frame.get(..DATA_OFFSET)?;
+    let len = (DATA_OFFSET + 1) as u16;
+
+    let mut ack = Vec::with_capacity(DATA_OFFSET + 1);
+    ack.extend_from_slice(header);
+    ack[1..3].copy_from_slice(len.to_le_bytes().as_ref());
+    ack.push(crate::ResponseCode::Ack.into());
+
+    Some(ack)
+}
+
+impl fmt::Debug for VendorRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut codes: Vec<&u16> = self.decoders.keys().collect();
+        codes.sort();
+
+        f.debug_struct("VendorRegistry")
+            .field("codes", &codes)
+            .field("spec_version", &self.spec_version)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MessageCode, MessageData};
+
+    #[derive(Debug, PartialEq)]
+    struct CoinLevel(u8);
+
+    #[test]
+    fn test_vendor_registry() -> Result<()> {
+        let registry = VendorRegistry::new().with_decoder(0x7f01, "coin_level", |data| {
+            data.first()
+                .copied()
+                .map(CoinLevel)
+                .ok_or(Error::InvalidEventLen((0, 1)))
+        })?;
+
+        let raw = [0x12, 0x09, 0x00, 0x10, 0x00, 0x80, 0x01, 0x7f, 0x2a];
+        let event = registry.decode(raw.as_ref())?;
+
+        match event {
+            TypedEvent::Vendor(vendor) => {
+                assert_eq!(vendor.event_type(), EventType::Sequence0);
+                assert_eq!(vendor.code(), 0x7f01);
+                assert_eq!(vendor.name(), "coin_level");
+                assert_eq!(vendor.additional(), [0x2a]);
+                assert_eq!(vendor.decoded::<CoinLevel>(), Some(&CoinLevel(0x2a)));
+                assert_eq!(vendor.decoded::<u8>(), None);
+            }
+            evt => panic!("unexpected event: {evt:?}"),
+        }
+
+        // unregistered unknown codes still fail to parse
+        let raw = [0x12, 0x09, 0x00, 0x10, 0x00, 0x80, 0x02, 0x7f, 0x2a];
+        assert!(registry.decode(raw.as_ref()).is_err());
+
+        // standard events are parsed as usual
+        let msg = Message::new().with_data(
+            MessageData::new()
+                .with_message_type(MessageType::Event(EventType::Sequence0))
+                .with_message_code(MessageCode::Event(EventCode::Inhibit)),
+        );
+        let mut raw = vec![0u8; msg.len()];
+        msg.to_bytes(raw.as_mut())?;
+
+        assert!(matches!(
+            registry.decode(raw.as_ref())?,
+            TypedEvent::Inhibit(_)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_vendor_registry_spec_version() -> Result<()> {
+        let pause = u16::from(EventCode::Pause);
+        let msg = Message::new().with_data(
+            MessageData::new()
+                .with_message_type(MessageType::Event(EventType::Sequence0))
+                .with_message_code(MessageCode::Event(EventCode::Pause))
+                .with_additional(&[0x2a]),
+        );
+        let mut raw = vec![0u8; msg.len()];
+        msg.to_bytes(raw.as_mut())?;
+
+        // codes from later revisions are rejected on older device generations
+        let v1 = VendorRegistry::new().with_spec_version(SpecVersion::V1);
+        assert_eq!(v1.decode(raw.as_ref()), Err(Error::InvalidEventCode(pause)));
+
+        // ...unless claimed by a vendor decoder
+        let v1 = v1.with_decoder(pause, "coin_level", |data| {
+            data.first()
+                .copied()
+                .map(CoinLevel)
+                .ok_or(Error::InvalidEventLen((0, 1)))
+        })?;
+        match v1.decode(raw.as_ref())? {
+            TypedEvent::Vendor(vendor) => {
+                assert_eq!(vendor.decoded::<CoinLevel>(), Some(&CoinLevel(0x2a)))
+            }
+            evt => panic!("unexpected event: {evt:?}"),
+        }
+
+        let mut v2 = VendorRegistry::new();
+        assert!(v2.register(pause, "coin_level", |_| Ok(())).is_err());
+        assert!(!v2.decode(raw.as_ref())?.is_vendor());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_vendor_registry_standard_code() {
+        let mut registry = VendorRegistry::new();
+
+        assert_eq!(
+            registry.register(EventCode::Idle.into(), "idle", |_| Ok(())),
+            Err(Error::InvalidEventCode(EventCode::Idle.into()))
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use alloc::vec::Vec;
+use core::mem;
+
+use crate::{Error, Message, Result, MAX_LEN};
+
+/// Accumulates raw bytes received in packets into a bounded [Message] frame.
+///
+/// Bytes are rejected with [Error::FrameTooLarge] as soon as the accumulated length, or the length
+/// declared in the frame header, exceeds the configured maximum. A misbehaving device that keeps
+/// sending full packets can not grow the buffer past the limit.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FrameDecoder {
+    buf: Vec<u8>,
+    max_len: usize,
+    lenient: bool,
+}
+
+impl FrameDecoder {
+    /// Creates a new [FrameDecoder], limited to the [MAX_LEN] of a [Message].
+    pub const fn new() -> Self {
+        Self {
+            buf: Vec::new(),
+            max_len: MAX_LEN,
+            lenient: false,
+        }
+    }
+
+    /// Gets the maximum frame length.
+    pub const fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    /// Sets the maximum frame length.
+    ///
+    /// The length is capped at the [MAX_LEN] of a [Message].
+    pub fn set_max_len(&mut self, max_len: usize) {
+        self.max_len = max_len.min(MAX_LEN);
+    }
+
+    /// Builder function that sets the maximum frame length.
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.set_max_len(max_len);
+        self
+    }
+
+    /// Gets whether frames are decoded with [Message::from_bytes_lenient].
+    pub const fn lenient(&self) -> bool {
+        self.lenient
+    }
+
+    /// Sets whether frames are decoded with [Message::from_bytes_lenient].
+    ///
+    /// Use for devices with firmware quirks in the frame length field.
+    pub fn set_lenient(&mut self, lenient: bool) {
+        self.lenient = lenient;
+    }
+
+    /// Builder function that sets whether frames are decoded with [Message::from_bytes_lenient].
+    pub fn with_lenient(mut self, lenient: bool) -> Self {
+        self.set_lenient(lenient);
+        self
+    }
+
+    /// Gets the number of accumulated bytes.
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Gets whether no bytes are accumulated.
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Gets a reference to the accumulated bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.buf.as_ref()
+    }
+
+    /// Gets the frame length declared in the accumulated header, if received.
+    pub fn declared_len(&self) -> Option<usize> {
+        match self.buf.as_slice() {
+            [_id, lo, hi, ..] => Some(u16::from_le_bytes([*lo, *hi]) as usize),
+            _ => None,
+        }
+    }
+
+    /// Gets whether the accumulated bytes contain the full declared frame.
+    pub fn is_complete(&self) -> bool {
+        self.declared_len()
+            .map(|len| self.buf.len() >= len)
+            .unwrap_or(false)
+    }
+
+    /// Appends received bytes to the frame.
+    ///
+    /// On [Error::FrameTooLarge], the accumulated bytes are discarded, and the caller should flush
+    /// the remainder of the frame from the transport before decoding the next one.
+    pub fn push(&mut self, bytes: &[u8]) -> Result<()> {
+        let len = self.buf.len().saturating_add(bytes.len());
+
+        if len > self.max_len {
+            self.clear();
+            return Err(Error::FrameTooLarge((len, self.max_len)));
+        }
+
+        self.buf.extend_from_slice(bytes);
+
+        match self.declared_len() {
+            Some(declared) if declared > self.max_len => {
+                self.clear();
+                Err(Error::FrameTooLarge((declared, self.max_len)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Decodes the accumulated bytes into a [Message], clearing the [FrameDecoder] for the next
+    /// frame.
+    pub fn decode(&mut self) -> Result<Message> {
+        self.decode_or_raw().map_err(|(_, err)| err)
+    }
+
+    /// Decodes the accumulated bytes into a [Message], clearing the [FrameDecoder] for the next
+    /// frame.
+    ///
+    /// Unlike [decode](Self::decode), a frame that fails to decode is returned with the error,
+    /// e.g. to decode vendor-specific events.
+    pub fn decode_or_raw(&mut self) -> core::result::Result<Message, (Vec<u8>, Error)> {
+        let buf = mem::take(&mut self.buf);
+
+        let res = if self.lenient {
+            Message::from_bytes_lenient(buf.as_slice())
+        } else {
+            Message::try_from(buf.as_slice())
+        };
+
+        res.map_err(|err| (buf, err))
+    }
+
+    /// Discards the accumulated bytes.
+    pub fn clear(&mut self) {
+        self.buf.clear();
+    }
+}
+
+impl Default for FrameDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_decoder() -> Result<()> {
+        let raw: Vec<u8> = Message::new().into();
+        let mut decoder = FrameDecoder::new().with_max_len(16);
+
+        decoder.push(&raw[..2])?;
+        assert!(!decoder.is_complete());
+        decoder.push(&raw[2..])?;
+        assert!(decoder.is_complete());
+
+        assert_eq!(decoder.decode()?, Message::new());
+        assert!(decoder.is_empty());
+
+        // accumulated bytes past the limit
+        decoder.push(&[0u8; 12])?;
+        assert_eq!(
+            decoder.push(&[0u8; 12]),
+            Err(Error::FrameTooLarge((24, 16)))
+        );
+        assert!(decoder.is_empty());
+
+        // declared length past the limit
+        assert_eq!(
+            decoder.push(&[0x12, 0x00, 0x01]),
+            Err(Error::FrameTooLarge((0x100, 16)))
+        );
+        assert!(decoder.is_empty());
+
+        decoder.push(&raw)?;
+        assert_eq!(decoder.decode()?, Message::new());
+
+        // trailing bytes past the declared length
+        let padded = [raw.as_slice(), &[0u8; 2]].concat();
+        decoder.push(&padded)?;
+        assert!(decoder.decode().is_err());
+
+        decoder.set_lenient(true);
+        decoder.push(&padded)?;
+        assert_eq!(decoder.decode()?, Message::new());
+
+        assert_eq!(
+            FrameDecoder::new().with_max_len(usize::MAX).max_len(),
+            MAX_LEN
+        );
+
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
-use std::{cmp, fmt, mem};
+use alloc::vec::Vec;
+use core::{cmp, fmt, mem};
 
 use super::{Message, MAX_LEN};
 use crate::{Error, Result};
@@ -17,6 +18,7 @@ pub const MAX_DATA_LEN: usize = MAX_LEN - MessageData::meta_len();
 /// Represents message data for JCM host-device communication.
 #[repr(C)]
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MessageData {
     conf_id: ConfId,
     uid: u8,
@@ -139,8 +141,8 @@ impl MessageData {
             || self.additional.is_empty()
     }
 
-    /// Writes the [MessageData] to the provided byte buffer.
-    pub fn to_bytes(&self, buf: &mut [u8]) -> Result<()> {
+    /// Writes the [MessageData] to the provided byte buffer, returning the number of bytes written.
+    pub fn to_bytes(&self, buf: &mut [u8]) -> Result<usize> {
         let len = self.len();
         let buf_len = buf.len();
 
@@ -157,9 +159,16 @@ impl MessageData {
                 )
                 .for_each(|(dst, src)| *dst = src);
 
-            Ok(())
+            Ok(len)
         }
     }
+
+    /// Parses a [MessageData] from the provided byte buffer.
+    ///
+    /// Equivalent to the [TryFrom] conversion from a byte slice.
+    pub fn from_bytes(buf: &[u8]) -> Result<Self> {
+        Self::try_from(buf)
+    }
 }
 
 impl From<&MessageData> for Vec<u8> {
@@ -1,4 +1,4 @@
-use std::{fmt, mem};
+use core::{fmt, mem};
 
 use crate::{Error, Result};
 
@@ -11,6 +11,7 @@ const RESERVED: u8 = 0xff;
 /// Represents the JCM device configuration ID.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ConfId {
     /// Only the primary `acceptor` feature.
     Acceptor = ACCEPTOR,
@@ -124,7 +125,7 @@ mod tests {
             ConfId::AcceptorRecyclerEscrow,
         ];
 
-        for (raw, exp) in raw_denom.into_iter().zip(expected.into_iter()) {
+        for (raw, exp) in raw_denom.into_iter().zip(expected) {
             assert_eq!(ConfId::try_from(raw), Ok(exp));
             assert_eq!(ConfId::from_u8(raw), exp);
         }
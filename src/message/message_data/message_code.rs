@@ -1,4 +1,4 @@
-use std::{fmt, mem};
+use core::{fmt, mem};
 
 use crate::{Error, FuncId, MessageType, Result};
 
@@ -12,6 +12,7 @@ const RESERVED: u16 = 0xffff;
 
 #[repr(u16)]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MessageCode {
     Request(RequestCode),
     Event(EventCode),
@@ -330,7 +331,7 @@ mod tests {
             MessageCode::Event(EventCode::FunctionAbeyance),
         ];
 
-        for (raw, exp) in raw_vals.into_iter().zip(expected.into_iter()) {
+        for (raw, exp) in raw_vals.into_iter().zip(expected) {
             assert_eq!(MessageCode::try_from(raw), Ok(exp));
             assert_eq!(u16::from(exp), raw.code);
 
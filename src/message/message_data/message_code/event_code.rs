@@ -1,4 +1,4 @@
-use std::{fmt, mem};
+use core::{fmt, mem};
 
 use crate::{Error, FuncId, Result};
 
@@ -34,11 +34,16 @@ const ACCEPTOR_FAILURE: u16 = 0x1202;
 const ACCEPTOR_NOTE_STAY: u16 = 0x1301;
 const FUNCTION_ABEYANCE: u16 = 0x1302;
 
+// Recycler event codes
+const RECYCLER_DISPENSING: u16 = 0x2106;
+const RECYCLER_DISPENSED: u16 = 0x2107;
+
 const RESERVED: u16 = 0xffff;
 
 /// Represents code variants for specific request messages.
 #[repr(u16)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EventCode {
     /// Normal `Power Up` status.
     PowerUp = POWER_UP,
@@ -96,6 +101,10 @@ pub enum EventCode {
     AcceptorNoteStay = ACCEPTOR_NOTE_STAY,
     /// Enabled functions cannot operate.
     FunctionAbeyance = FUNCTION_ABEYANCE,
+    /// Recycler is paying out notes.
+    RecyclerDispensing = RECYCLER_DISPENSING,
+    /// Recycler finished paying out notes.
+    RecyclerDispensed = RECYCLER_DISPENSED,
     /// Reserved request.
     Reserved = RESERVED,
 }
@@ -137,6 +146,8 @@ impl EventCode {
             ACCEPTOR_FAILURE => Self::AcceptorFailure,
             ACCEPTOR_NOTE_STAY => Self::AcceptorNoteStay,
             FUNCTION_ABEYANCE => Self::FunctionAbeyance,
+            RECYCLER_DISPENSING => Self::RecyclerDispensing,
+            RECYCLER_DISPENSED => Self::RecyclerDispensed,
             _ => Self::Reserved,
         }
     }
@@ -165,6 +176,18 @@ impl EventCode {
     pub const fn is_valid(&self) -> bool {
         !self.is_empty()
     }
+
+    /// Gets whether the [EventCode] is one of the `Power Up` variants.
+    pub const fn is_power_up(&self) -> bool {
+        matches!(
+            self,
+            Self::PowerUp
+                | Self::PowerUpAcceptor
+                | Self::PowerUpStacker
+                | Self::PowerUpAcceptorAccepting
+                | Self::PowerUpStackerAccepting
+        )
+    }
 }
 
 impl Default for EventCode {
@@ -255,6 +278,8 @@ impl From<EventCode> for &'static str {
             EventCode::AcceptorFailure => "AcceptorFailure",
             EventCode::AcceptorNoteStay => "AcceptorNoteStay",
             EventCode::FunctionAbeyance => "FunctionAbeyance",
+            EventCode::RecyclerDispensing => "RecyclerDispensing",
+            EventCode::RecyclerDispensed => "RecyclerDispensed",
             EventCode::Reserved => "Reserved",
         }
     }
@@ -275,6 +300,7 @@ impl fmt::Display for EventCode {
 /// Convenience struct to display details for [EventCode].
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EventCodeDetails(pub EventCode);
 
 impl From<EventCodeDetails> for &'static str {
@@ -316,6 +342,8 @@ impl From<EventCodeDetails> for &'static str {
                 "returned note remains in `Insertion Slot` for a certain time"
             }
             EventCode::FunctionAbeyance => "enabled functions cannot operate",
+            EventCode::RecyclerDispensing => "recycler is paying out notes",
+            EventCode::RecyclerDispensed => "recycler finished paying out notes",
             EventCode::Reserved => "reserved",
         }
     }
@@ -368,6 +396,8 @@ mod tests {
             ACCEPTOR_FAILURE,
             ACCEPTOR_NOTE_STAY,
             FUNCTION_ABEYANCE,
+            RECYCLER_DISPENSING,
+            RECYCLER_DISPENSED,
         ];
 
         let expected = [
@@ -399,9 +429,11 @@ mod tests {
             EventCode::AcceptorFailure,
             EventCode::AcceptorNoteStay,
             EventCode::FunctionAbeyance,
+            EventCode::RecyclerDispensing,
+            EventCode::RecyclerDispensed,
         ];
 
-        for (raw, exp) in raw_vals.into_iter().zip(expected.into_iter()) {
+        for (raw, exp) in raw_vals.into_iter().zip(expected) {
             assert_eq!(EventCode::try_from(raw), Ok(exp));
             assert_eq!(EventCode::from_u16(raw), exp);
             assert_eq!(FuncId::from_u16(raw), exp.func_id());
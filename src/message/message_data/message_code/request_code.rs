@@ -1,4 +1,4 @@
-use std::{fmt, mem};
+use core::{fmt, mem};
 
 use crate::{Error, FuncId, Result};
 
@@ -34,12 +34,14 @@ const NOTE_DATA_INFO: u16 = 0x102f;
 
 // Recycler request codes
 const RECYCLER_COLLECT: u16 = 0x2017;
+const RECYCLER_DISPENSE: u16 = 0x2018;
 
 const RESERVED: u16 = 0xffff;
 
 /// Represents code variants for specific request messages.
 #[repr(u16)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RequestCode {
     /// Request to get/set UID information.
     Uid = UID,
@@ -95,6 +97,8 @@ pub enum RequestCode {
     NoteDataInfo = NOTE_DATA_INFO,
     /// Request for retrieving.
     RecyclerCollect = RECYCLER_COLLECT,
+    /// Request to pay out notes from a recycler box.
+    RecyclerDispense = RECYCLER_DISPENSE,
     /// Reserved request.
     Reserved = RESERVED,
 }
@@ -135,6 +139,7 @@ impl RequestCode {
             PAUSE => Self::Pause,
             NOTE_DATA_INFO => Self::NoteDataInfo,
             RECYCLER_COLLECT => Self::RecyclerCollect,
+            RECYCLER_DISPENSE => Self::RecyclerDispense,
             _ => Self::Reserved,
         }
     }
@@ -252,6 +257,7 @@ impl From<RequestCode> for &'static str {
             RequestCode::Pause => "Pause",
             RequestCode::NoteDataInfo => "NoteDataInfo",
             RequestCode::RecyclerCollect => "RecyclerCollect",
+            RequestCode::RecyclerDispense => "RecyclerDispense",
             RequestCode::Reserved => "Reserved",
         }
     }
@@ -272,6 +278,7 @@ impl fmt::Display for RequestCode {
 /// Convenience struct to display details for [RequestCode].
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RequestCodeDetails(pub RequestCode);
 
 impl From<RequestCodeDetails> for &'static str {
@@ -304,6 +311,7 @@ impl From<RequestCodeDetails> for &'static str {
             RequestCode::Pause => "request to send or set the `Pause` duration, and `Status and Event Message` enabled/disabled settings information",
             RequestCode::NoteDataInfo => "request to send information of an inserted note",
             RequestCode::RecyclerCollect => "request for retrieving",
+            RequestCode::RecyclerDispense => "request to pay out notes from a recycler box",
             RequestCode::Reserved => "reserved code",
         }
     }
@@ -355,6 +363,7 @@ mod tests {
             PAUSE,
             NOTE_DATA_INFO,
             RECYCLER_COLLECT,
+            RECYCLER_DISPENSE,
         ];
         let expected = [
             RequestCode::Uid,
@@ -384,9 +393,10 @@ mod tests {
             RequestCode::Pause,
             RequestCode::NoteDataInfo,
             RequestCode::RecyclerCollect,
+            RequestCode::RecyclerDispense,
         ];
 
-        for (raw, exp) in raw_vals.into_iter().zip(expected.into_iter()) {
+        for (raw, exp) in raw_vals.into_iter().zip(expected) {
             assert_eq!(RequestCode::try_from(raw), Ok(exp));
             assert_eq!(RequestCode::from_u16(raw), exp);
             assert_eq!(FuncId::from_u16(raw), exp.func_id());
@@ -1,4 +1,4 @@
-use std::{fmt, mem};
+use core::{fmt, mem};
 
 use crate::{Error, Result};
 
@@ -13,6 +13,7 @@ const RESERVED: u8 = 0xff;
 /// Represents the message type.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MessageType {
     /// Host-to-device request message.
     Request(RequestType),
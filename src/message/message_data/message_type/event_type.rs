@@ -1,4 +1,4 @@
-use std::fmt;
+use core::fmt;
 
 use super::*;
 use crate::{Error, Result};
@@ -23,6 +23,7 @@ const EVENT_SEQUENCE15: u8 = 0x8f;
 /// Represents the sequence number of the device-to-host event status message.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EventType {
     Sequence0 = EVENT_SEQUENCE0,
     Sequence1 = EVENT_SEQUENCE1,
@@ -189,7 +190,7 @@ mod tests {
             EventType::Sequence15,
         ];
 
-        for (raw, exp) in raw_denom.into_iter().zip(expected.into_iter()) {
+        for (raw, exp) in raw_denom.into_iter().zip(expected) {
             assert_eq!(EventType::try_from(raw), Ok(exp));
             assert_eq!(EventType::from_u8(raw), exp);
         }
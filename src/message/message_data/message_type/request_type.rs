@@ -1,4 +1,4 @@
-use std::{fmt, mem};
+use core::{fmt, mem};
 
 use super::*;
 use crate::{Error, Result};
@@ -10,6 +10,7 @@ const SET_FEATURE_REQ: u8 = 0b0010_0000;
 /// Represents the type of host-to-device request message.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RequestType {
     Operation = OPERATION_REQ,
     Status = STATUS_REQ,
@@ -124,7 +125,7 @@ mod tests {
             RequestType::SetFeature,
         ];
 
-        for (raw, exp) in raw_denom.into_iter().zip(expected.into_iter()) {
+        for (raw, exp) in raw_denom.into_iter().zip(expected) {
             assert_eq!(RequestType::try_from(raw), Ok(exp));
             assert_eq!(RequestType::from_u8(raw), exp);
         }
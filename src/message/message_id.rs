@@ -1,4 +1,4 @@
-use std::{fmt, mem};
+use core::{fmt, mem};
 
 use crate::{Error, Result};
 
@@ -8,6 +8,7 @@ const RESERVED: u8 = 0xff;
 /// Represents the `ID` field of a message.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MessageId {
     Message = MESSAGE,
     Reserved = RESERVED,
@@ -97,7 +98,7 @@ mod tests {
         let raw_denom = [MESSAGE];
         let expected = [MessageId::Message];
 
-        for (raw, exp) in raw_denom.into_iter().zip(expected.into_iter()) {
+        for (raw, exp) in raw_denom.into_iter().zip(expected) {
             assert_eq!(MessageId::try_from(raw), Ok(exp));
             assert_eq!(MessageId::from_u8(raw), exp);
         }
@@ -1,21 +1,31 @@
 //! Contains types for additional data in request messages.
 
-use std::fmt;
+use alloc::vec::Vec;
+use core::fmt;
 
 use crate::{
     Error, Message, MessageCode, MessageData, MessageType, RequestCode, RequestType, Result,
 };
 
+mod acceptor_collect_request;
+mod bar_code_request;
+mod cash_box_size_request;
 mod collect_request;
+mod conditional_vend_request;
 mod currency_assign_request;
 mod denomination_disable_request;
 mod direction_disable_request;
+mod dispense_request;
+mod event_resend_interval_request;
 mod hold_request;
 mod idle_request;
 mod inhibit_request;
+mod insert_notification_request;
 mod model_name_request;
 mod near_full_request;
+mod note_data_info_request;
 mod note_image_request;
+mod pause_request;
 mod program_signature_request;
 mod reject_request;
 mod request_mode;
@@ -23,19 +33,29 @@ mod reset_request;
 mod serial_number_request;
 mod stack_request;
 mod status_request;
+mod typed_request;
 mod uid_request;
 mod version_request;
 
+pub use acceptor_collect_request::*;
+pub use bar_code_request::*;
+pub use cash_box_size_request::*;
 pub use collect_request::*;
+pub use conditional_vend_request::*;
 pub use currency_assign_request::*;
 pub use denomination_disable_request::*;
 pub use direction_disable_request::*;
+pub use dispense_request::*;
+pub use event_resend_interval_request::*;
 pub use hold_request::*;
 pub use idle_request::*;
 pub use inhibit_request::*;
+pub use insert_notification_request::*;
 pub use model_name_request::*;
 pub use near_full_request::*;
+pub use note_data_info_request::*;
 pub use note_image_request::*;
+pub use pause_request::*;
 pub use program_signature_request::*;
 pub use reject_request::*;
 pub use request_mode::*;
@@ -43,12 +63,14 @@ pub use reset_request::*;
 pub use serial_number_request::*;
 pub use stack_request::*;
 pub use status_request::*;
+pub use typed_request::*;
 pub use uid_request::*;
 pub use version_request::*;
 
 /// Represents an event [Message] sent by the device.
 #[repr(C)]
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Request {
     request_type: RequestType,
     request_code: RequestCode,
@@ -137,8 +159,8 @@ impl Request {
         self.request_code.is_empty()
     }
 
-    /// Writes the [Message] to the provided byte buffer.
-    pub fn to_bytes(&self, buf: &mut [u8]) -> Result<()> {
+    /// Writes the [Request] to the provided byte buffer, returning the number of bytes written.
+    pub fn to_bytes(&self, buf: &mut [u8]) -> Result<usize> {
         let len = self.len();
         let buf_len = buf.len();
 
@@ -155,9 +177,16 @@ impl Request {
                 .zip(msg_iter)
                 .for_each(|(dst, src)| *dst = src);
 
-            Ok(())
+            Ok(len)
         }
     }
+
+    /// Parses a [Request] from the provided byte buffer.
+    ///
+    /// Equivalent to the [TryFrom] conversion from a byte slice.
+    pub fn from_bytes(buf: &[u8]) -> Result<Self> {
+        Self::try_from(buf)
+    }
 }
 
 impl TryFrom<&[u8]> for Request {
@@ -247,6 +276,10 @@ impl fmt::Display for Request {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        BarCodeSettings, BarCodeType, ConditionalVendSettings, Denomination, DirectionInhibit,
+        InsertNotification, PauseSettings,
+    };
 
     #[test]
     fn test_request() {
@@ -270,7 +303,7 @@ mod tests {
         assert_eq!(Request::try_from(msg), Ok(exp.clone()));
 
         let mut out = [0u8; Request::meta_len()];
-        assert_eq!(exp.to_bytes(out.as_mut()), Ok(()));
+        assert_eq!(exp.to_bytes(out.as_mut()), Ok(raw.len()));
         assert_eq!(out, raw);
     }
 
@@ -297,7 +330,116 @@ mod tests {
         assert_eq!(Request::try_from(msg), Ok(exp.clone()));
 
         let mut out = [0u8; 4];
-        assert_eq!(exp.to_bytes(out.as_mut()), Ok(()));
+        assert_eq!(exp.to_bytes(out.as_mut()), Ok(raw.len()));
         assert_eq!(out, raw);
     }
+
+    #[test]
+    fn test_set_request_canonical() -> Result<()> {
+        let face_down = InhibitDirection::new()
+            .with_face_down_right_side(DirectionInhibit::Inhibit)
+            .with_face_down_left_side(DirectionInhibit::Inhibit);
+        let face_down_rev = InhibitDirection::new()
+            .with_face_down_left_side(DirectionInhibit::Inhibit)
+            .with_face_up_left_side(DirectionInhibit::Inhibit)
+            .with_face_down_right_side(DirectionInhibit::Inhibit)
+            .with_face_up_left_side(DirectionInhibit::Accept);
+
+        let set_requests: [(Message, Message, &[u8]); 6] = [
+            (
+                DenominationDisableRequest::new()
+                    .with_mode(DenominationDisableMode::Set)
+                    .with_disabled_denominations(&[1, 17, 2])?
+                    .into(),
+                DenominationDisableRequest::new()
+                    .with_mode(DenominationDisableMode::Set)
+                    .with_disabled_denominations(&[17, 2, 1, 2])?
+                    .into(),
+                &[0b0000_0110, 0x00, 0b0000_0010, 0x00],
+            ),
+            (
+                DirectionDisableRequest::new()
+                    .with_mode(DirectionDisableMode::Set)
+                    .with_direction(face_down)
+                    .into(),
+                DirectionDisableRequest::new()
+                    .with_mode(DirectionDisableMode::Set)
+                    .with_direction(face_down_rev)
+                    .into(),
+                &[0b1100],
+            ),
+            (
+                BarCodeRequest::new()
+                    .with_mode(BarCodeMode::Set)
+                    .with_settings(BarCodeSettings::create(BarCodeType::Code128, 16)?)
+                    .into(),
+                BarCodeRequest::new()
+                    .with_settings(BarCodeSettings::create(BarCodeType::Code128, 16)?)
+                    .with_mode(BarCodeMode::Set)
+                    .into(),
+                &BarCodeSettings::create(BarCodeType::Code128, 16)?.into_bytes(),
+            ),
+            (
+                InsertNotificationRequest::new()
+                    .with_mode(InsertNotificationMode::Set)
+                    .with_notification(InsertNotification::Enabled)
+                    .into(),
+                InsertNotificationRequest::new()
+                    .with_notification(InsertNotification::Enabled)
+                    .with_mode(InsertNotificationMode::Set)
+                    .into(),
+                &[0x01],
+            ),
+            (
+                ConditionalVendRequest::new()
+                    .with_mode(ConditionalVendMode::Set)
+                    .with_settings(ConditionalVendSettings::create(
+                        true,
+                        Denomination::from_value(5),
+                        Denomination::from_value(20),
+                    )?)
+                    .into(),
+                ConditionalVendRequest::new()
+                    .with_settings(ConditionalVendSettings::create(
+                        true,
+                        Denomination::from_value(5),
+                        Denomination::from_value(20),
+                    )?)
+                    .with_mode(ConditionalVendMode::Set)
+                    .into(),
+                &[0x01, 0x05, 0x00, 0x14, 0x00],
+            ),
+            (
+                PauseRequest::new()
+                    .with_mode(PauseMode::Set)
+                    .with_settings(
+                        PauseSettings::new()
+                            .with_duration_ms(500)
+                            .with_status_messages(true),
+                    )
+                    .into(),
+                PauseRequest::new()
+                    .with_settings(
+                        PauseSettings::new()
+                            .with_status_messages(true)
+                            .with_duration_ms(500),
+                    )
+                    .with_mode(PauseMode::Set)
+                    .into(),
+                &[0xf4, 0x01, crate::STATUS_MESSAGE_FLAG],
+            ),
+        ];
+
+        for (msg, oth, exp_additional) in set_requests {
+            assert_eq!(
+                msg.data().message_type().request_type(),
+                Ok(RequestType::SetFeature)
+            );
+            assert_eq!(msg.data().additional(), exp_additional);
+            assert_eq!(Vec::<u8>::from(&msg), Vec::<u8>::from(&oth));
+            assert_eq!(Vec::<u8>::from(msg.clone()), Vec::<u8>::from(&msg));
+        }
+
+        Ok(())
+    }
 }
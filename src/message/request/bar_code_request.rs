@@ -0,0 +1,351 @@
+use crate::{
+    BarCodeSettings, Error, Message, MessageCode, MessageData, MessageType, RequestCode,
+    RequestType, Result,
+};
+
+mod bar_code_mode;
+
+pub use bar_code_mode::*;
+
+/// Represents a `Bar Code` request message.
+///
+/// This request is used to get/set the barcode ticket settings of the device: the
+/// [BarCodeType](crate::BarCodeType) symbology, and the number of characters.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{Message, BarCodeRequest};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, type, code, data
+/// let frame = [0x12, 0x08, 0x00, 0x10, 0x00, 0x10, 0x26, 0x10];
+///
+/// let req = BarCodeRequest::new();
+///
+/// assert_eq!(BarCodeRequest::try_from(Message::try_from(frame.as_ref())?)?, req);
+/// assert_eq!(Vec::<u8>::from(Message::from(&req)), frame);
+/// # Ok(())
+/// # }
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BarCodeRequest {
+    mode: BarCodeMode,
+    settings: Option<BarCodeSettings>,
+}
+
+impl BarCodeRequest {
+    /// Creates a new [BarCodeRequest].
+    pub const fn new() -> Self {
+        Self {
+            mode: BarCodeMode::new(),
+            settings: None,
+        }
+    }
+
+    /// Gets the [MessageType] for the [BarCodeRequest].
+    pub const fn message_type(&self) -> MessageType {
+        MessageType::Request(self.request_type())
+    }
+
+    /// Gets the [RequestType] for the [BarCodeRequest].
+    pub const fn request_type(&self) -> RequestType {
+        self.mode.into_request_type()
+    }
+
+    /// Gets the [MessageCode] for the [BarCodeRequest].
+    pub const fn message_code(&self) -> MessageCode {
+        MessageCode::Request(self.request_code())
+    }
+
+    /// Gets the [RequestCode] for the [BarCodeRequest].
+    pub const fn request_code(&self) -> RequestCode {
+        RequestCode::BarCode
+    }
+
+    /// Gets the [BarCodeMode] for the [BarCodeRequest].
+    pub const fn mode(&self) -> BarCodeMode {
+        self.mode
+    }
+
+    /// Sets the [BarCodeMode] for the [BarCodeRequest].
+    pub fn set_mode(&mut self, mode: BarCodeMode) {
+        self.mode = mode;
+    }
+
+    /// Builder function that sets the [BarCodeMode] for the [BarCodeRequest].
+    pub const fn with_mode(self, mode: BarCodeMode) -> Self {
+        Self {
+            mode,
+            settings: self.settings,
+        }
+    }
+
+    /// Gets the [BarCodeSettings] for the [BarCodeRequest].
+    ///
+    /// [BarCodeSettings] are only set for [Set](BarCodeMode::Set) requests.
+    pub const fn settings(&self) -> Option<BarCodeSettings> {
+        self.settings
+    }
+
+    /// Sets the [BarCodeSettings] for the [BarCodeRequest].
+    ///
+    /// [BarCodeSettings] are only set for [Set](BarCodeMode::Set) requests.
+    pub fn set_settings(&mut self, settings: BarCodeSettings) {
+        self.settings = Some(settings);
+    }
+
+    /// Unsets the [BarCodeSettings] for the [BarCodeRequest].
+    pub fn unset_settings(&mut self) -> Option<BarCodeSettings> {
+        self.settings.take()
+    }
+
+    /// Builder function that sets the [BarCodeSettings] for the [BarCodeRequest].
+    ///
+    /// [BarCodeSettings] are only set for [Set](BarCodeMode::Set) requests.
+    pub const fn with_settings(self, settings: BarCodeSettings) -> Self {
+        Self {
+            mode: self.mode,
+            settings: Some(settings),
+        }
+    }
+}
+
+impl Default for BarCodeRequest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<BarCodeRequest> for Message {
+    fn from(val: BarCodeRequest) -> Self {
+        MessageData::from(val).into()
+    }
+}
+
+impl From<&BarCodeRequest> for Message {
+    fn from(val: &BarCodeRequest) -> Self {
+        (*val).into()
+    }
+}
+
+impl From<BarCodeRequest> for MessageData {
+    fn from(val: BarCodeRequest) -> Self {
+        match val.mode() {
+            BarCodeMode::Get => Self::new()
+                .with_message_type(val.message_type())
+                .with_message_code(val.message_code()),
+            BarCodeMode::Set => Self::new()
+                .with_message_type(val.message_type())
+                .with_message_code(val.message_code())
+                .with_additional(val.settings().unwrap_or_default().into_bytes().as_ref()),
+        }
+    }
+}
+
+impl From<&BarCodeRequest> for MessageData {
+    fn from(val: &BarCodeRequest) -> Self {
+        (*val).into()
+    }
+}
+
+impl TryFrom<&Message> for BarCodeRequest {
+    type Error = Error;
+
+    fn try_from(val: &Message) -> Result<Self> {
+        val.data().try_into()
+    }
+}
+
+impl TryFrom<Message> for BarCodeRequest {
+    type Error = Error;
+
+    fn try_from(val: Message) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
+impl TryFrom<&MessageData> for BarCodeRequest {
+    type Error = Error;
+
+    fn try_from(val: &MessageData) -> Result<Self> {
+        // could also be BarCodeMode::Set
+        let exp_type = MessageType::from(BarCodeMode::Get);
+        let exp_code = MessageCode::Request(RequestCode::BarCode);
+
+        match (val.message_type(), val.message_code()) {
+            (msg_type, msg_code) if msg_code == exp_code => {
+                let mode = BarCodeMode::try_from(msg_type.request_type()?)?;
+                match mode {
+                    BarCodeMode::Get => Ok(Self::new().with_mode(mode)),
+                    BarCodeMode::Set => Ok(Self::new()
+                        .with_mode(mode)
+                        .with_settings(BarCodeSettings::try_from(val.additional())?)),
+                }
+            }
+            (msg_type, msg_code) => Err(Error::InvalidMessage((
+                (msg_type.into(), msg_code.into()),
+                (exp_type.into(), exp_code.into()),
+            ))),
+        }
+    }
+}
+
+impl TryFrom<MessageData> for BarCodeRequest {
+    type Error = Error;
+
+    fn try_from(val: MessageData) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventCode, EventType};
+
+    #[test]
+    fn test_bar_code_request() -> Result<()> {
+        let exp_code = RequestCode::BarCode;
+        let exp_settings = BarCodeSettings::new();
+
+        for exp_type in [RequestType::Status, RequestType::SetFeature] {
+            let exp_mode = exp_type.try_into()?;
+
+            let msg_data = MessageData::new()
+                .with_message_type(MessageType::Request(exp_type))
+                .with_message_code(MessageCode::Request(exp_code));
+
+            let msg = match exp_mode {
+                BarCodeMode::Get => Message::new().with_data(msg_data),
+                BarCodeMode::Set => Message::new()
+                    .with_data(msg_data.with_additional(exp_settings.into_bytes().as_ref())),
+            };
+
+            let exp_req = match exp_mode {
+                BarCodeMode::Get => BarCodeRequest::new().with_mode(exp_type.try_into()?),
+                BarCodeMode::Set => BarCodeRequest::new()
+                    .with_mode(exp_type.try_into()?)
+                    .with_settings(exp_settings),
+            };
+
+            assert_eq!(exp_req.message_type(), MessageType::Request(exp_type));
+            assert_eq!(exp_req.request_type(), exp_type);
+
+            assert_eq!(exp_req.message_code(), MessageCode::Request(exp_code));
+            assert_eq!(exp_req.request_code(), exp_code);
+
+            assert_eq!(exp_req.mode(), exp_mode);
+            match exp_mode {
+                BarCodeMode::Get => assert_eq!(exp_req.settings(), None),
+                BarCodeMode::Set => assert_eq!(exp_req.settings(), Some(exp_settings)),
+            };
+
+            assert_eq!(Message::from(exp_req), msg);
+            assert_eq!(BarCodeRequest::try_from(&msg), Ok(exp_req));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bar_code_request_invalid() -> Result<()> {
+        let invalid_types = [MessageType::Reserved]
+            .into_iter()
+            .chain((0x80..=0x8f).map(|m| MessageType::Event(EventType::from_u8(m))))
+            .chain([RequestType::Operation, RequestType::Reserved].map(MessageType::Request))
+            .collect::<Vec<MessageType>>();
+
+        let invalid_codes = [
+            RequestCode::Uid,
+            RequestCode::ProgramSignature,
+            RequestCode::Version,
+            RequestCode::SerialNumber,
+            RequestCode::ModelName,
+            RequestCode::Reset,
+            RequestCode::Stack,
+            RequestCode::Inhibit,
+            RequestCode::Status,
+            RequestCode::Key,
+            RequestCode::EventResendInterval,
+            RequestCode::Idle,
+            RequestCode::Reject,
+            RequestCode::Hold,
+            RequestCode::DenominationDisable,
+            RequestCode::DirectionDisable,
+            RequestCode::CurrencyAssign,
+            RequestCode::CashBoxSize,
+            RequestCode::Collect,
+            RequestCode::NearFull,
+            RequestCode::Insert,
+            RequestCode::ConditionalVend,
+            RequestCode::Pause,
+            RequestCode::NoteDataInfo,
+            RequestCode::Reserved,
+        ]
+        .map(MessageCode::Request)
+        .into_iter()
+        .chain(
+            [
+                EventCode::PowerUp,
+                EventCode::PowerUpAcceptor,
+                EventCode::PowerUpStacker,
+                EventCode::Inhibit,
+                EventCode::ProgramSignature,
+                EventCode::Rejected,
+                EventCode::Collected,
+                EventCode::Clear,
+                EventCode::OperationError,
+                EventCode::Failure,
+                EventCode::NoteStay,
+                EventCode::PowerUpAcceptorAccepting,
+                EventCode::PowerUpStackerAccepting,
+                EventCode::Idle,
+                EventCode::Escrow,
+                EventCode::VendValid,
+                EventCode::AcceptorRejected,
+                EventCode::Returned,
+                EventCode::AcceptorCollected,
+                EventCode::Insert,
+                EventCode::ConditionalVend,
+                EventCode::Pause,
+                EventCode::Resume,
+                EventCode::AcceptorClear,
+                EventCode::AcceptorOperationError,
+                EventCode::AcceptorFailure,
+                EventCode::AcceptorNoteStay,
+                EventCode::FunctionAbeyance,
+                EventCode::Reserved,
+            ]
+            .map(MessageCode::Event),
+        )
+        .collect::<Vec<MessageCode>>();
+
+        for &msg_type in invalid_types.iter() {
+            for &msg_code in invalid_codes.iter() {
+                let inval_data = MessageData::new()
+                    .with_message_type(msg_type)
+                    .with_message_code(msg_code);
+
+                let inval_type = MessageData::new()
+                    .with_message_type(msg_type)
+                    .with_message_code(BarCodeRequest::new().message_code());
+
+                let inval_code = MessageData::new()
+                    .with_message_type(BarCodeRequest::new().message_type())
+                    .with_message_code(msg_code);
+
+                for stack_data in [inval_data, inval_type, inval_code] {
+                    assert!(BarCodeRequest::try_from(&stack_data).is_err());
+                    assert!(
+                        BarCodeRequest::try_from(Message::new().with_data(stack_data)).is_err()
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use core::fmt;
+
+use crate::{Error, MessageType, RequestType, Result};
+
+/// Represents the [RequestType] modes for the [BarCodeRequest].
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BarCodeMode {
+    Get,
+    Set,
+}
+
+impl BarCodeMode {
+    /// Creates a new [BarCodeMode].
+    pub const fn new() -> Self {
+        Self::Get
+    }
+
+    /// Converts a [RequestType] into a [BarCodeMode].
+    pub const fn from_request_type(val: RequestType) -> Option<Self> {
+        match val {
+            RequestType::Status => Some(Self::Get),
+            RequestType::SetFeature => Some(Self::Set),
+            _ => None,
+        }
+    }
+
+    /// Converts a [BarCodeMode] into a [RequestType].
+    pub const fn into_request_type(self) -> RequestType {
+        match self {
+            Self::Get => RequestType::Status,
+            Self::Set => RequestType::SetFeature,
+        }
+    }
+}
+
+impl Default for BarCodeMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TryFrom<RequestType> for BarCodeMode {
+    type Error = Error;
+
+    fn try_from(val: RequestType) -> Result<Self> {
+        Self::from_request_type(val).ok_or(Error::InvalidBarCodeMode(val.into()))
+    }
+}
+
+impl From<BarCodeMode> for RequestType {
+    fn from(val: BarCodeMode) -> Self {
+        val.into_request_type()
+    }
+}
+
+impl From<BarCodeMode> for MessageType {
+    fn from(val: BarCodeMode) -> Self {
+        MessageType::Request(val.into_request_type())
+    }
+}
+
+impl From<BarCodeMode> for &'static str {
+    fn from(val: BarCodeMode) -> Self {
+        match val {
+            BarCodeMode::Get => "get",
+            BarCodeMode::Set => "set",
+        }
+    }
+}
+
+impl From<&BarCodeMode> for &'static str {
+    fn from(val: &BarCodeMode) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for BarCodeMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
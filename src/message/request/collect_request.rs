@@ -9,8 +9,26 @@ pub use collect_mode::*;
 /// Represents a `Collect` request message.
 ///
 /// This request is used to collect notes from the device.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{CollectRequest, Message};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, type, code, data
+/// let frame = [0x12, 0x08, 0x00, 0x10, 0x00, 0x00, 0x17, 0x00];
+///
+/// let req = CollectRequest::new();
+///
+/// assert_eq!(CollectRequest::try_from(Message::try_from(frame.as_ref())?)?, req);
+/// assert_eq!(Vec::<u8>::from(Message::from(&req)), frame);
+/// # Ok(())
+/// # }
+/// ```
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CollectRequest {
     mode: CollectMode,
 }
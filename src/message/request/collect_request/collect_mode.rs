@@ -1,8 +1,11 @@
+use core::fmt;
+
 use crate::{Error, MessageCode, RequestCode, Result};
 
 /// Represents the device mode for collecting notes.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CollectMode {
     /// Collect notes left in device at `PowerUp`.
     PowerUp = 0,
@@ -47,6 +50,29 @@ impl Default for CollectMode {
     }
 }
 
+impl From<CollectMode> for &'static str {
+    fn from(val: CollectMode) -> Self {
+        match val {
+            CollectMode::PowerUp => "power up",
+            CollectMode::Acceptor => "acceptor",
+            CollectMode::Recycler => "recycler",
+            CollectMode::Reserved => "reserved",
+        }
+    }
+}
+
+impl From<&CollectMode> for &'static str {
+    fn from(val: &CollectMode) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for CollectMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
+
 impl TryFrom<RequestCode> for CollectMode {
     type Error = Error;
 
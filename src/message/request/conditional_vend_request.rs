@@ -0,0 +1,358 @@
+use crate::{
+    ConditionalVendSettings, Error, Message, MessageCode, MessageData, MessageType, RequestCode,
+    RequestType, Result,
+};
+
+mod conditional_vend_mode;
+
+pub use conditional_vend_mode::*;
+
+/// Represents a `Conditional Vend` request message.
+///
+/// This request is used to get/set the `Conditional Vend Function` settings of the device: whether
+/// conditional stacking is enabled, and the note value thresholds it applies to.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{Message, ConditionalVendRequest};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, type, code, data
+/// let frame = [0x12, 0x08, 0x00, 0x10, 0x00, 0x10, 0x29, 0x10];
+///
+/// let req = ConditionalVendRequest::new();
+///
+/// assert_eq!(ConditionalVendRequest::try_from(Message::try_from(frame.as_ref())?)?, req);
+/// assert_eq!(Vec::<u8>::from(Message::from(&req)), frame);
+/// # Ok(())
+/// # }
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConditionalVendRequest {
+    mode: ConditionalVendMode,
+    settings: Option<ConditionalVendSettings>,
+}
+
+impl ConditionalVendRequest {
+    /// Creates a new [ConditionalVendRequest].
+    pub const fn new() -> Self {
+        Self {
+            mode: ConditionalVendMode::new(),
+            settings: None,
+        }
+    }
+
+    /// Gets the [MessageType] for the [ConditionalVendRequest].
+    pub const fn message_type(&self) -> MessageType {
+        MessageType::Request(self.request_type())
+    }
+
+    /// Gets the [RequestType] for the [ConditionalVendRequest].
+    pub const fn request_type(&self) -> RequestType {
+        self.mode.into_request_type()
+    }
+
+    /// Gets the [MessageCode] for the [ConditionalVendRequest].
+    pub const fn message_code(&self) -> MessageCode {
+        MessageCode::Request(self.request_code())
+    }
+
+    /// Gets the [RequestCode] for the [ConditionalVendRequest].
+    pub const fn request_code(&self) -> RequestCode {
+        RequestCode::ConditionalVend
+    }
+
+    /// Gets the [ConditionalVendMode] for the [ConditionalVendRequest].
+    pub const fn mode(&self) -> ConditionalVendMode {
+        self.mode
+    }
+
+    /// Sets the [ConditionalVendMode] for the [ConditionalVendRequest].
+    pub fn set_mode(&mut self, mode: ConditionalVendMode) {
+        self.mode = mode;
+    }
+
+    /// Builder function that sets the [ConditionalVendMode] for the [ConditionalVendRequest].
+    pub const fn with_mode(self, mode: ConditionalVendMode) -> Self {
+        Self {
+            mode,
+            settings: self.settings,
+        }
+    }
+
+    /// Gets the [ConditionalVendSettings] for the [ConditionalVendRequest].
+    ///
+    /// [ConditionalVendSettings] are only set for [Set](ConditionalVendMode::Set) requests.
+    pub const fn settings(&self) -> Option<ConditionalVendSettings> {
+        self.settings
+    }
+
+    /// Sets the [ConditionalVendSettings] for the [ConditionalVendRequest].
+    ///
+    /// [ConditionalVendSettings] are only set for [Set](ConditionalVendMode::Set) requests.
+    pub fn set_settings(&mut self, settings: ConditionalVendSettings) {
+        self.settings = Some(settings);
+    }
+
+    /// Unsets the [ConditionalVendSettings] for the [ConditionalVendRequest].
+    pub fn unset_settings(&mut self) -> Option<ConditionalVendSettings> {
+        self.settings.take()
+    }
+
+    /// Builder function that sets the [ConditionalVendSettings] for the [ConditionalVendRequest].
+    ///
+    /// [ConditionalVendSettings] are only set for [Set](ConditionalVendMode::Set) requests.
+    pub const fn with_settings(self, settings: ConditionalVendSettings) -> Self {
+        Self {
+            mode: self.mode,
+            settings: Some(settings),
+        }
+    }
+}
+
+impl Default for ConditionalVendRequest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<ConditionalVendRequest> for Message {
+    fn from(val: ConditionalVendRequest) -> Self {
+        MessageData::from(val).into()
+    }
+}
+
+impl From<&ConditionalVendRequest> for Message {
+    fn from(val: &ConditionalVendRequest) -> Self {
+        (*val).into()
+    }
+}
+
+impl From<ConditionalVendRequest> for MessageData {
+    fn from(val: ConditionalVendRequest) -> Self {
+        match val.mode() {
+            ConditionalVendMode::Get => Self::new()
+                .with_message_type(val.message_type())
+                .with_message_code(val.message_code()),
+            ConditionalVendMode::Set => Self::new()
+                .with_message_type(val.message_type())
+                .with_message_code(val.message_code())
+                .with_additional(val.settings().unwrap_or_default().into_bytes().as_ref()),
+        }
+    }
+}
+
+impl From<&ConditionalVendRequest> for MessageData {
+    fn from(val: &ConditionalVendRequest) -> Self {
+        (*val).into()
+    }
+}
+
+impl TryFrom<&Message> for ConditionalVendRequest {
+    type Error = Error;
+
+    fn try_from(val: &Message) -> Result<Self> {
+        val.data().try_into()
+    }
+}
+
+impl TryFrom<Message> for ConditionalVendRequest {
+    type Error = Error;
+
+    fn try_from(val: Message) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
+impl TryFrom<&MessageData> for ConditionalVendRequest {
+    type Error = Error;
+
+    fn try_from(val: &MessageData) -> Result<Self> {
+        // could also be ConditionalVendMode::Set
+        let exp_type = MessageType::from(ConditionalVendMode::Get);
+        let exp_code = MessageCode::Request(RequestCode::ConditionalVend);
+
+        match (val.message_type(), val.message_code()) {
+            (msg_type, msg_code) if msg_code == exp_code => {
+                let mode = ConditionalVendMode::try_from(msg_type.request_type()?)?;
+                match mode {
+                    ConditionalVendMode::Get => Ok(Self::new().with_mode(mode)),
+                    ConditionalVendMode::Set => Ok(Self::new()
+                        .with_mode(mode)
+                        .with_settings(ConditionalVendSettings::try_from(val.additional())?)),
+                }
+            }
+            (msg_type, msg_code) => Err(Error::InvalidMessage((
+                (msg_type.into(), msg_code.into()),
+                (exp_type.into(), exp_code.into()),
+            ))),
+        }
+    }
+}
+
+impl TryFrom<MessageData> for ConditionalVendRequest {
+    type Error = Error;
+
+    fn try_from(val: MessageData) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Denomination, EventCode, EventType};
+
+    #[test]
+    fn test_conditional_vend_request() -> Result<()> {
+        let exp_code = RequestCode::ConditionalVend;
+        let exp_settings = ConditionalVendSettings::create(
+            true,
+            Denomination::from_value(5),
+            Denomination::from_value(50),
+        )?;
+
+        for exp_type in [RequestType::Status, RequestType::SetFeature] {
+            let exp_mode = exp_type.try_into()?;
+
+            let msg_data = MessageData::new()
+                .with_message_type(MessageType::Request(exp_type))
+                .with_message_code(MessageCode::Request(exp_code));
+
+            let msg = match exp_mode {
+                ConditionalVendMode::Get => Message::new().with_data(msg_data),
+                ConditionalVendMode::Set => Message::new()
+                    .with_data(msg_data.with_additional(exp_settings.into_bytes().as_ref())),
+            };
+
+            let exp_req = match exp_mode {
+                ConditionalVendMode::Get => {
+                    ConditionalVendRequest::new().with_mode(exp_type.try_into()?)
+                }
+                ConditionalVendMode::Set => ConditionalVendRequest::new()
+                    .with_mode(exp_type.try_into()?)
+                    .with_settings(exp_settings),
+            };
+
+            assert_eq!(exp_req.message_type(), MessageType::Request(exp_type));
+            assert_eq!(exp_req.request_type(), exp_type);
+
+            assert_eq!(exp_req.message_code(), MessageCode::Request(exp_code));
+            assert_eq!(exp_req.request_code(), exp_code);
+
+            assert_eq!(exp_req.mode(), exp_mode);
+            match exp_mode {
+                ConditionalVendMode::Get => assert_eq!(exp_req.settings(), None),
+                ConditionalVendMode::Set => assert_eq!(exp_req.settings(), Some(exp_settings)),
+            };
+
+            assert_eq!(Message::from(exp_req), msg);
+            assert_eq!(ConditionalVendRequest::try_from(&msg), Ok(exp_req));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_conditional_vend_request_invalid() -> Result<()> {
+        let invalid_types = [MessageType::Reserved]
+            .into_iter()
+            .chain((0x80..=0x8f).map(|m| MessageType::Event(EventType::from_u8(m))))
+            .chain([RequestType::Operation, RequestType::Reserved].map(MessageType::Request))
+            .collect::<Vec<MessageType>>();
+
+        let invalid_codes = [
+            RequestCode::Uid,
+            RequestCode::ProgramSignature,
+            RequestCode::Version,
+            RequestCode::SerialNumber,
+            RequestCode::ModelName,
+            RequestCode::Reset,
+            RequestCode::Stack,
+            RequestCode::Inhibit,
+            RequestCode::Status,
+            RequestCode::Key,
+            RequestCode::EventResendInterval,
+            RequestCode::Idle,
+            RequestCode::Reject,
+            RequestCode::Hold,
+            RequestCode::DenominationDisable,
+            RequestCode::DirectionDisable,
+            RequestCode::CurrencyAssign,
+            RequestCode::CashBoxSize,
+            RequestCode::Collect,
+            RequestCode::NearFull,
+            RequestCode::Insert,
+            RequestCode::BarCode,
+            RequestCode::Pause,
+            RequestCode::NoteDataInfo,
+            RequestCode::Reserved,
+        ]
+        .map(MessageCode::Request)
+        .into_iter()
+        .chain(
+            [
+                EventCode::PowerUp,
+                EventCode::PowerUpAcceptor,
+                EventCode::PowerUpStacker,
+                EventCode::Inhibit,
+                EventCode::ProgramSignature,
+                EventCode::Rejected,
+                EventCode::Collected,
+                EventCode::Clear,
+                EventCode::OperationError,
+                EventCode::Failure,
+                EventCode::NoteStay,
+                EventCode::PowerUpAcceptorAccepting,
+                EventCode::PowerUpStackerAccepting,
+                EventCode::Idle,
+                EventCode::Escrow,
+                EventCode::VendValid,
+                EventCode::AcceptorRejected,
+                EventCode::Returned,
+                EventCode::AcceptorCollected,
+                EventCode::Insert,
+                EventCode::ConditionalVend,
+                EventCode::Pause,
+                EventCode::Resume,
+                EventCode::AcceptorClear,
+                EventCode::AcceptorOperationError,
+                EventCode::AcceptorFailure,
+                EventCode::AcceptorNoteStay,
+                EventCode::FunctionAbeyance,
+                EventCode::Reserved,
+            ]
+            .map(MessageCode::Event),
+        )
+        .collect::<Vec<MessageCode>>();
+
+        for &msg_type in invalid_types.iter() {
+            for &msg_code in invalid_codes.iter() {
+                let inval_data = MessageData::new()
+                    .with_message_type(msg_type)
+                    .with_message_code(msg_code);
+
+                let inval_type = MessageData::new()
+                    .with_message_type(msg_type)
+                    .with_message_code(ConditionalVendRequest::new().message_code());
+
+                let inval_code = MessageData::new()
+                    .with_message_type(ConditionalVendRequest::new().message_type())
+                    .with_message_code(msg_code);
+
+                for stack_data in [inval_data, inval_type, inval_code] {
+                    assert!(ConditionalVendRequest::try_from(&stack_data).is_err());
+                    assert!(
+                        ConditionalVendRequest::try_from(Message::new().with_data(stack_data))
+                            .is_err()
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use core::fmt;
+
+use crate::{Error, MessageType, RequestType, Result};
+
+/// Represents the [RequestType] modes for the [ConditionalVendRequest].
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConditionalVendMode {
+    Get,
+    Set,
+}
+
+impl ConditionalVendMode {
+    /// Creates a new [ConditionalVendMode].
+    pub const fn new() -> Self {
+        Self::Get
+    }
+
+    /// Converts a [RequestType] into a [ConditionalVendMode].
+    pub const fn from_request_type(val: RequestType) -> Option<Self> {
+        match val {
+            RequestType::Status => Some(Self::Get),
+            RequestType::SetFeature => Some(Self::Set),
+            _ => None,
+        }
+    }
+
+    /// Converts a [ConditionalVendMode] into a [RequestType].
+    pub const fn into_request_type(self) -> RequestType {
+        match self {
+            Self::Get => RequestType::Status,
+            Self::Set => RequestType::SetFeature,
+        }
+    }
+}
+
+impl Default for ConditionalVendMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TryFrom<RequestType> for ConditionalVendMode {
+    type Error = Error;
+
+    fn try_from(val: RequestType) -> Result<Self> {
+        Self::from_request_type(val).ok_or(Error::InvalidConditionalVendMode(val.into()))
+    }
+}
+
+impl From<ConditionalVendMode> for RequestType {
+    fn from(val: ConditionalVendMode) -> Self {
+        val.into_request_type()
+    }
+}
+
+impl From<ConditionalVendMode> for MessageType {
+    fn from(val: ConditionalVendMode) -> Self {
+        MessageType::Request(val.into_request_type())
+    }
+}
+
+impl From<ConditionalVendMode> for &'static str {
+    fn from(val: ConditionalVendMode) -> Self {
+        match val {
+            ConditionalVendMode::Get => "get",
+            ConditionalVendMode::Set => "set",
+        }
+    }
+}
+
+impl From<&ConditionalVendMode> for &'static str {
+    fn from(val: &ConditionalVendMode) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for ConditionalVendMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
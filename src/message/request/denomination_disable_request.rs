@@ -10,8 +10,26 @@ pub use denomination_disable::*;
 pub use denomination_disable_mode::*;
 
 /// Represents a `Denomination Disable` request message.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{DenominationDisableRequest, Message};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, type, code, data
+/// let frame = [0x12, 0x08, 0x00, 0x10, 0x00, 0x10, 0x21, 0x10];
+///
+/// let req = DenominationDisableRequest::new();
+///
+/// assert_eq!(DenominationDisableRequest::try_from(Message::try_from(frame.as_ref())?)?, req);
+/// assert_eq!(Vec::<u8>::from(Message::from(&req)), frame);
+/// # Ok(())
+/// # }
+/// ```
 #[repr(C)]
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DenominationDisableRequest {
     mode: DenominationDisableMode,
     denoms: DenominationDisableList,
@@ -86,6 +104,7 @@ impl DenominationDisableRequest {
     /// Gets the current maximum denomination index
     pub fn cur_max_denom_len(&self) -> usize {
         self.denoms
+            .items()
             .len()
             .saturating_mul(DenominationDisable::denom_len())
     }
@@ -113,6 +132,27 @@ impl DenominationDisableRequest {
         Ok(self)
     }
 
+    /// Sets the disabled denominations from a list of indices, enabling all others.
+    ///
+    /// Indices may be in any order, and repeat. The encoded request only depends on the set of
+    /// indices, see [DenominationDisableList] for the encoding order.
+    pub fn set_disabled_denominations(&mut self, indices: &[usize]) -> Result<()> {
+        let max_denom = Self::max_denom();
+        match indices.iter().find(|&&idx| idx > max_denom) {
+            Some(&idx) => Err(Error::InvalidDenominationLen((idx, max_denom))),
+            None => {
+                self.denoms = DenominationDisableList::from_disabled(indices.iter().copied());
+                Ok(())
+            }
+        }
+    }
+
+    /// Builder function that sets the disabled denominations from a list of indices.
+    pub fn with_disabled_denominations(mut self, indices: &[usize]) -> Result<Self> {
+        self.set_disabled_denominations(indices)?;
+        Ok(self)
+    }
+
     /// Sets a denomination disabled status.
     ///
     /// ## Parameters
@@ -124,9 +164,9 @@ impl DenominationDisableRequest {
 
         if idx > Self::max_denom() {
             Err(Error::InvalidDenominationLen((idx, Self::max_denom())))
-        } else if idx > self.cur_max_denom() {
+        } else if idx >= self.cur_max_denom_len() {
             // get the number of blank denomination sets to add
-            let add = (idx / DENOM_LEN).saturating_sub(self.denoms.len());
+            let add = (idx / DENOM_LEN).saturating_sub(self.denoms.items().len());
             let denom_idx = idx % DENOM_LEN;
 
             self.denoms.append(
@@ -187,13 +227,7 @@ impl From<&DenominationDisableRequest> for MessageData {
             .with_message_type(val.message_type())
             .with_message_code(val.message_code());
         match val.mode() {
-            DenominationDisableMode::Set => data.with_additional(
-                val.denoms
-                    .iter()
-                    .flat_map(|d| d.to_bytes())
-                    .collect::<Vec<u8>>()
-                    .as_ref(),
-            ),
+            DenominationDisableMode::Set => data.with_additional(val.denoms.to_bytes().as_ref()),
             _ => data,
         }
     }
@@ -309,6 +343,40 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_denomination_disable_request_canonical() -> Result<()> {
+        let exp_additional = [0b0010_0001, 0x00, 0x00, 0b1000_0000];
+        let exp = MessageData::new()
+            .with_message_type(MessageType::Request(RequestType::SetFeature))
+            .with_message_code(MessageCode::Request(RequestCode::DenominationDisable))
+            .with_additional(exp_additional.as_ref());
+
+        for indices in [[0, 5, 31], [31, 5, 0], [5, 31, 0], [31, 0, 5]] {
+            let mut req = DenominationDisableRequest::new()
+                .with_mode(DenominationDisableMode::Set)
+                .with_disabled_denominations(&indices)?;
+            assert_eq!(MessageData::from(&req), exp);
+
+            req.set_disabled_denominations(&[indices[2], indices[0], indices[1], indices[0]])?;
+            assert_eq!(MessageData::from(&req), exp);
+
+            let mut incremental =
+                DenominationDisableRequest::new().with_mode(DenominationDisableMode::Set);
+            for idx in indices {
+                incremental.disable_denomination(idx)?;
+            }
+            assert_eq!(MessageData::from(&incremental), exp);
+        }
+
+        let max_denom = DenominationDisableRequest::max_denom();
+        assert_eq!(
+            DenominationDisableRequest::new().with_disabled_denominations(&[0, max_denom + 1]),
+            Err(Error::InvalidDenominationLen((max_denom + 1, max_denom)))
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_status_request_invalid() -> Result<()> {
         let invalid_types = [MessageType::Reserved]
@@ -1,10 +1,12 @@
-use std::fmt;
+use alloc::vec::Vec;
+use core::fmt;
 
 use crate::{Error, Result};
 
 /// Represents a set of denominations to disable on the device.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DenominationDisable(u16);
 
 impl DenominationDisable {
@@ -30,7 +32,7 @@ impl DenominationDisable {
 
     /// Gets the length of the [DenominationDisable].
     pub const fn len() -> usize {
-        std::mem::size_of::<u16>()
+        core::mem::size_of::<u16>()
     }
 
     /// Gets whether the [DenominationDisable] is empty.
@@ -163,8 +165,15 @@ impl fmt::Display for DenominationDisable {
 }
 
 /// Represents a list of [DenominationDisable] items.
+///
+/// ## Encoding order
+///
+/// Items are encoded in ascending denomination order: item `n` holds denominations `16n` to
+/// `16n + 15`, where bit `i` of the little-endian item is denomination `16n + i`. The encoding
+/// only depends on which denominations are disabled, not the order they were disabled in.
 #[repr(C)]
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DenominationDisableList(Vec<DenominationDisable>);
 
 impl DenominationDisableList {
@@ -173,6 +182,25 @@ impl DenominationDisableList {
         Self(Vec::new())
     }
 
+    /// Creates a new [DenominationDisableList] from disabled denomination indices.
+    ///
+    /// Indices may be in any order, and repeat. The list is long enough to hold the highest
+    /// index, so the same set of indices always produces the same list.
+    pub fn from_disabled<I: IntoIterator<Item = usize>>(indices: I) -> Self {
+        const DENOM_LEN: usize = DenominationDisable::denom_len();
+
+        let mut items = Vec::new();
+        for idx in indices {
+            let item_idx = idx / DENOM_LEN;
+            if item_idx >= items.len() {
+                items.resize(item_idx + 1, DenominationDisable::new());
+            }
+            items[item_idx].set(idx % DENOM_LEN, true);
+        }
+
+        Self(items)
+    }
+
     /// Gets a reference to the list of [DenominationDisable] items.
     pub fn items(&self) -> &[DenominationDisable] {
         self.0.as_ref()
@@ -242,7 +270,7 @@ impl DenominationDisableList {
 
 impl IntoIterator for DenominationDisableList {
     type Item = DenominationDisable;
-    type IntoIter = std::vec::IntoIter<DenominationDisable>;
+    type IntoIter = alloc::vec::IntoIter<DenominationDisable>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.0.into_iter()
@@ -3,6 +3,7 @@ use crate::{Error, RequestType, Result};
 /// Represents the request mode for the [DenominationDisableRequest].
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DenominationDisableMode {
     Get = 0,
     Set = 1,
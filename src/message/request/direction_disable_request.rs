@@ -1,5 +1,6 @@
 use crate::{
-    Error, Message, MessageCode, MessageData, MessageType, RequestCode, RequestType, Result,
+    Error, Message, MessageCode, MessageData, MessageType, Orientation, RequestCode, RequestType,
+    Result,
 };
 
 mod direction_disable_mode;
@@ -10,9 +11,27 @@ pub use direction_disable_mode::*;
 pub use direction_inhibit::*;
 pub use inhibit_direction::*;
 
-/// Represents a `Denomination Disable` request message.
+/// Represents a `Direction Disable` request message.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{DirectionDisableRequest, Message};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, type, code, data
+/// let frame = [0x12, 0x08, 0x00, 0x10, 0x00, 0x10, 0x22, 0x10];
+///
+/// let req = DirectionDisableRequest::new();
+///
+/// assert_eq!(DirectionDisableRequest::try_from(Message::try_from(frame.as_ref())?)?, req);
+/// assert_eq!(Vec::<u8>::from(Message::from(&req)), frame);
+/// # Ok(())
+/// # }
+/// ```
 #[repr(C)]
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DirectionDisableRequest {
     mode: DirectionDisableMode,
     direction: InhibitDirection,
@@ -27,6 +46,20 @@ impl DirectionDisableRequest {
         }
     }
 
+    /// Creates a new `Set` [DirectionDisableRequest] accepting only the listed [Orientation]s.
+    pub fn only(allowed: &[Orientation]) -> Self {
+        Self::new()
+            .with_mode(DirectionDisableMode::Set)
+            .with_direction(InhibitDirection::only(allowed))
+    }
+
+    /// Creates a new `Set` [DirectionDisableRequest] accepting every [Orientation].
+    pub fn allow_all() -> Self {
+        Self::new()
+            .with_mode(DirectionDisableMode::Set)
+            .with_direction(InhibitDirection::allow_all())
+    }
+
     /// Gets the [MessageType] for the [DirectionDisableRequest].
     pub const fn message_type(&self) -> MessageType {
         MessageType::Request(self.request_type())
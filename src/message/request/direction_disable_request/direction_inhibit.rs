@@ -1,8 +1,9 @@
-use std::fmt;
+use core::fmt;
 
 /// Represents variants for inhibiting a denomination direction.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DirectionInhibit {
     Accept = 0,
     Inhibit = 1,
@@ -1,6 +1,6 @@
-use std::fmt;
+use core::fmt;
 
-use crate::DirectionInhibit;
+use crate::{DirectionInhibit, Orientation};
 
 const FACE_DOWN_RIGHT_SIDE_SHIFT: u8 = 3;
 const FACE_DOWN_RIGHT_SIDE: u8 = 1 << FACE_DOWN_RIGHT_SIDE_SHIFT;
@@ -15,11 +15,43 @@ const FACE_UP_LEFT_SIDE: u8 = 1 << FACE_UP_LEFT_SIDE_SHIFT;
 pub const INHIBIT_DIRECTION_MASK: u8 = 0xf;
 
 /// Represents denomination direction to inhibit.
+///
+/// Each bit inhibits one [Orientation]: build the set from the named constants, or with
+/// [only](Self::only) and [with_inhibited](Self::with_inhibited), instead of raw bitfields.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{InhibitDirection, Orientation};
+///
+/// let direction =
+///     InhibitDirection::only(&[Orientation::FaceUpForward, Orientation::FaceUpBackward]);
+///
+/// assert_eq!(
+///     direction,
+///     InhibitDirection::FACE_DOWN_FORWARD.with_inhibited(Orientation::FaceDownBackward)
+/// );
+/// assert_eq!(
+///     direction.inhibited().collect::<Vec<Orientation>>(),
+///     [Orientation::FaceDownForward, Orientation::FaceDownBackward]
+/// );
+/// assert!(InhibitDirection::allow_all().is_allowed(Orientation::FaceDownForward));
+/// ```
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InhibitDirection(u8);
 
 impl InhibitDirection {
+    /// Inhibits the [Orientation::FaceUpForward] direction.
+    pub const FACE_UP_FORWARD: Self = Self(FACE_UP_LEFT_SIDE);
+    /// Inhibits the [Orientation::FaceUpBackward] direction.
+    pub const FACE_UP_BACKWARD: Self = Self(FACE_UP_RIGHT_SIDE);
+    /// Inhibits the [Orientation::FaceDownForward] direction.
+    pub const FACE_DOWN_FORWARD: Self = Self(FACE_DOWN_LEFT_SIDE);
+    /// Inhibits the [Orientation::FaceDownBackward] direction.
+    pub const FACE_DOWN_BACKWARD: Self = Self(FACE_DOWN_RIGHT_SIDE);
+
     /// Creates a new [InhibitDirection].
     pub const fn new() -> Self {
         Self(0)
@@ -32,6 +64,73 @@ impl InhibitDirection {
         Self(val & INHIBIT_DIRECTION_MASK)
     }
 
+    /// Creates a new [InhibitDirection] accepting every [Orientation].
+    pub const fn allow_all() -> Self {
+        Self::new()
+    }
+
+    /// Creates a new [InhibitDirection] inhibiting every [Orientation].
+    pub const fn inhibit_all() -> Self {
+        Self(INHIBIT_DIRECTION_MASK)
+    }
+
+    /// Creates a new [InhibitDirection] accepting only the listed [Orientation]s.
+    pub fn only(allowed: &[Orientation]) -> Self {
+        allowed
+            .iter()
+            .fold(Self::inhibit_all(), |dir, &orientation| {
+                dir.with_allowed(orientation)
+            })
+    }
+
+    /// Gets whether the [Orientation] is inhibited.
+    ///
+    /// The [Reserved](Orientation::Reserved) orientation is never inhibited.
+    pub const fn is_inhibited(&self, orientation: Orientation) -> bool {
+        self.0 & orientation.mask() != 0
+    }
+
+    /// Gets whether the [Orientation] is accepted.
+    pub const fn is_allowed(&self, orientation: Orientation) -> bool {
+        orientation.is_valid() && !self.is_inhibited(orientation)
+    }
+
+    /// Inhibits the [Orientation].
+    pub fn inhibit(&mut self, orientation: Orientation) {
+        self.0 |= orientation.mask();
+    }
+
+    /// Builder function that inhibits the [Orientation].
+    pub fn with_inhibited(mut self, orientation: Orientation) -> Self {
+        self.inhibit(orientation);
+        self
+    }
+
+    /// Accepts the [Orientation].
+    pub fn allow(&mut self, orientation: Orientation) {
+        self.0 &= !orientation.mask();
+    }
+
+    /// Builder function that accepts the [Orientation].
+    pub fn with_allowed(mut self, orientation: Orientation) -> Self {
+        self.allow(orientation);
+        self
+    }
+
+    /// Gets an iterator over the inhibited [Orientation]s.
+    pub fn inhibited(&self) -> impl Iterator<Item = Orientation> + '_ {
+        Orientation::ALL
+            .into_iter()
+            .filter(|&orientation| self.is_inhibited(orientation))
+    }
+
+    /// Gets an iterator over the accepted [Orientation]s.
+    pub fn allowed(&self) -> impl Iterator<Item = Orientation> + '_ {
+        Orientation::ALL
+            .into_iter()
+            .filter(|&orientation| self.is_allowed(orientation))
+    }
+
     /// Gets the bitfield value of the [InhibitDirection].
     pub const fn bits(&self) -> u8 {
         self.0 & INHIBIT_DIRECTION_MASK
@@ -114,7 +213,7 @@ impl InhibitDirection {
 
     /// Gets the length of the [InhibitDirection].
     pub const fn len() -> usize {
-        std::mem::size_of::<u8>()
+        core::mem::size_of::<u8>()
     }
 
     /// Gets whether the [InhibitDirection] is empty.
@@ -225,5 +324,30 @@ mod tests {
             InhibitDirection::create(raw_face_up_left).bits(),
             raw_face_up_left
         );
+
+        assert_eq!(InhibitDirection::inhibit_all(), all_inhibit);
+        assert_eq!(InhibitDirection::only(&Orientation::ALL), no_inhibit);
+        assert_eq!(InhibitDirection::only(&[]), all_inhibit);
+        assert_eq!(
+            InhibitDirection::only(&[Orientation::FaceUpForward]).bits(),
+            raw_face_down_right | raw_face_down_left | raw_face_up_right
+        );
+        assert_eq!(
+            InhibitDirection::FACE_DOWN_BACKWARD.bits(),
+            raw_face_down_right
+        );
+        assert!(!all_inhibit.is_inhibited(Orientation::Reserved));
+        assert!(!no_inhibit.is_allowed(Orientation::Reserved));
+        assert_eq!(all_inhibit.inhibited().count(), 4);
+        assert_eq!(
+            InhibitDirection::FACE_UP_BACKWARD
+                .allowed()
+                .collect::<Vec<Orientation>>(),
+            [
+                Orientation::FaceUpForward,
+                Orientation::FaceDownForward,
+                Orientation::FaceDownBackward
+            ]
+        );
     }
 }
@@ -0,0 +1,285 @@
+use core::fmt;
+
+use crate::{
+    ConfId, Denomination, Error, Message, MessageCode, MessageData, MessageType, RequestCode,
+    RequestType, Result, UnitNumber,
+};
+
+/// Represents the byte length of a [DispenseRequest].
+pub const DISPENSE_REQUEST_LEN: usize = 4;
+
+/// Represents a `Recycler Dispense` request message.
+///
+/// This request is used to pay out notes from the recycler boxes of recycler-equipped devices.
+///
+/// ## Format
+///
+/// Field  | Denomination | Count  | Destination box
+/// -------|--------------|--------|----------------
+/// Length | 2 bytes      | 1 byte | 1 byte
+///
+/// The device reports the payout with `Recycler Dispensing` and `Recycler Dispensed` events, see
+/// [DispenseEvent](crate::DispenseEvent).
+///
+/// # Example
+///
+/// ```
+/// use jcm::{Denomination, DispenseRequest, Message, UnitNumber};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, type, code, data
+/// let frame = [0x12, 0x0c, 0x00, 0x11, 0x00, 0x00, 0x18, 0x20, 0x14, 0x00, 0x03, 0x21];
+///
+/// let req = DispenseRequest::create(Denomination::from_value(20), 3, UnitNumber::from_u8(0x21));
+///
+/// assert_eq!(DispenseRequest::try_from(Message::try_from(frame.as_ref())?)?, req);
+/// assert_eq!(Vec::<u8>::from(Message::from(&req)), frame);
+/// # Ok(())
+/// # }
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DispenseRequest {
+    denomination: Denomination,
+    count: u8,
+    destination: UnitNumber,
+}
+
+impl DispenseRequest {
+    /// Creates a new [DispenseRequest].
+    pub const fn new() -> Self {
+        Self {
+            denomination: Denomination::new(),
+            count: 0,
+            destination: UnitNumber::new(),
+        }
+    }
+
+    /// Creates a new [DispenseRequest] from the provided parameters.
+    pub const fn create(denomination: Denomination, count: u8, destination: UnitNumber) -> Self {
+        Self {
+            denomination,
+            count,
+            destination,
+        }
+    }
+
+    /// Gets the [Denomination] of the notes to pay out.
+    pub const fn denomination(&self) -> Denomination {
+        self.denomination
+    }
+
+    /// Sets the [Denomination] of the notes to pay out.
+    pub fn set_denomination(&mut self, denomination: Denomination) {
+        self.denomination = denomination;
+    }
+
+    /// Builder function that sets the [Denomination] of the notes to pay out.
+    pub fn with_denomination(mut self, denomination: Denomination) -> Self {
+        self.set_denomination(denomination);
+        self
+    }
+
+    /// Gets the number of notes to pay out.
+    pub const fn count(&self) -> u8 {
+        self.count
+    }
+
+    /// Sets the number of notes to pay out.
+    pub fn set_count(&mut self, count: u8) {
+        self.count = count;
+    }
+
+    /// Builder function that sets the number of notes to pay out.
+    pub fn with_count(mut self, count: u8) -> Self {
+        self.set_count(count);
+        self
+    }
+
+    /// Gets the destination box [UnitNumber] of the paid out notes.
+    pub const fn destination(&self) -> UnitNumber {
+        self.destination
+    }
+
+    /// Sets the destination box [UnitNumber] of the paid out notes.
+    pub fn set_destination(&mut self, destination: UnitNumber) {
+        self.destination = destination;
+    }
+
+    /// Builder function that sets the destination box [UnitNumber] of the paid out notes.
+    pub fn with_destination(mut self, destination: UnitNumber) -> Self {
+        self.set_destination(destination);
+        self
+    }
+
+    /// Gets the [MessageType] for the [DispenseRequest].
+    pub const fn message_type(&self) -> MessageType {
+        MessageType::Request(self.request_type())
+    }
+
+    /// Gets the [RequestType] for the [DispenseRequest].
+    pub const fn request_type(&self) -> RequestType {
+        RequestType::Operation
+    }
+
+    /// Gets the [MessageCode] for the [DispenseRequest].
+    pub const fn message_code(&self) -> MessageCode {
+        MessageCode::Request(self.request_code())
+    }
+
+    /// Gets the [RequestCode] for the [DispenseRequest].
+    pub const fn request_code(&self) -> RequestCode {
+        RequestCode::RecyclerDispense
+    }
+
+    /// Gets the length of the [DispenseRequest].
+    pub const fn len() -> usize {
+        DISPENSE_REQUEST_LEN
+    }
+
+    /// Gets whether the [DispenseRequest] is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Converts a byte buffer into a [DispenseRequest].
+    pub fn from_bytes(buf: &[u8]) -> Result<Self> {
+        match buf.len() {
+            len if len < Self::len() => Err(Error::InvalidRequestLen((len, Self::len()))),
+            _ => Ok(Self {
+                denomination: Denomination::from_bytes(&buf[..2]),
+                count: buf[2],
+                destination: UnitNumber::from_u8(buf[3]),
+            }),
+        }
+    }
+
+    /// Converts the [DispenseRequest] into a byte array.
+    pub const fn into_bytes(self) -> [u8; DISPENSE_REQUEST_LEN] {
+        let [int, exp] = self.denomination.into_bytes();
+        [int, exp, self.count, self.destination.into_u8()]
+    }
+}
+
+impl Default for DispenseRequest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<DispenseRequest> for MessageData {
+    fn from(val: DispenseRequest) -> Self {
+        Self::new()
+            .with_conf_id(ConfId::AcceptorRecycler)
+            .with_message_type(val.message_type())
+            .with_message_code(val.message_code())
+            .with_additional(val.into_bytes().as_ref())
+    }
+}
+
+impl From<&DispenseRequest> for MessageData {
+    fn from(val: &DispenseRequest) -> Self {
+        (*val).into()
+    }
+}
+
+impl From<DispenseRequest> for Message {
+    fn from(val: DispenseRequest) -> Self {
+        MessageData::from(val).into()
+    }
+}
+
+impl From<&DispenseRequest> for Message {
+    fn from(val: &DispenseRequest) -> Self {
+        (*val).into()
+    }
+}
+
+impl TryFrom<&MessageData> for DispenseRequest {
+    type Error = Error;
+
+    fn try_from(val: &MessageData) -> Result<Self> {
+        let (exp_type, exp_code) = (
+            MessageType::Request(RequestType::Operation),
+            MessageCode::Request(RequestCode::RecyclerDispense),
+        );
+
+        match (val.message_type(), val.message_code()) {
+            (msg_type, msg_code) if msg_type == exp_type && msg_code == exp_code => {
+                Self::from_bytes(val.additional())
+            }
+            (msg_type, msg_code) => Err(Error::InvalidMessage((
+                (msg_type.into(), msg_code.into()),
+                (exp_type.into(), exp_code.into()),
+            ))),
+        }
+    }
+}
+
+impl TryFrom<MessageData> for DispenseRequest {
+    type Error = Error;
+
+    fn try_from(val: MessageData) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
+impl TryFrom<&Message> for DispenseRequest {
+    type Error = Error;
+
+    fn try_from(val: &Message) -> Result<Self> {
+        val.data().try_into()
+    }
+}
+
+impl TryFrom<Message> for DispenseRequest {
+    type Error = Error;
+
+    fn try_from(val: Message) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
+impl fmt::Display for DispenseRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""denomination": {}, "#, self.denomination)?;
+        write!(f, r#""count": {}, "#, self.count)?;
+        write!(f, r#""destination": {}"#, self.destination)?;
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dispense_request() -> Result<()> {
+        let req = DispenseRequest::new()
+            .with_denomination(Denomination::from_value(1000))
+            .with_count(5)
+            .with_destination(UnitNumber::from_u8(0x22));
+
+        let msg = Message::from(req);
+        assert_eq!(msg.data().conf_id(), ConfId::AcceptorRecycler);
+        assert_eq!(msg.data().additional(), [100, 1, 5, 0x22]);
+        assert_eq!(DispenseRequest::try_from(&msg)?, req);
+        assert_eq!(
+            DispenseRequest::try_from(&msg)?.denomination().value(),
+            1000
+        );
+
+        let short = MessageData::from(req).with_additional(&[100, 1, 5]);
+        assert_eq!(
+            DispenseRequest::try_from(&short),
+            Err(Error::InvalidRequestLen((3, DISPENSE_REQUEST_LEN)))
+        );
+
+        let stack = MessageData::from(crate::StackRequest::new());
+        assert!(DispenseRequest::try_from(&stack).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,362 @@
+use crate::{
+    Error, Message, MessageCode, MessageData, MessageType, RequestCode, RequestType, Result,
+};
+
+mod event_resend_interval;
+mod event_resend_interval_mode;
+
+pub use event_resend_interval::*;
+pub use event_resend_interval_mode::*;
+
+/// Represents an `Event Re-sending Interval` request message.
+///
+/// This request is used to get/set how long the device waits for an event acknowledgement,
+/// before re-sending the event.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{Message, EventResendIntervalRequest};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, type, code, data
+/// let frame = [0x12, 0x08, 0x00, 0x10, 0x00, 0x10, 0x2c, 0x00];
+///
+/// let req = EventResendIntervalRequest::new();
+///
+/// assert_eq!(EventResendIntervalRequest::try_from(Message::try_from(frame.as_ref())?)?, req);
+/// assert_eq!(Vec::<u8>::from(Message::from(&req)), frame);
+/// # Ok(())
+/// # }
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EventResendIntervalRequest {
+    mode: EventResendIntervalMode,
+    interval: Option<EventResendInterval>,
+}
+
+impl EventResendIntervalRequest {
+    /// Creates a new [EventResendIntervalRequest].
+    pub const fn new() -> Self {
+        Self {
+            mode: EventResendIntervalMode::new(),
+            interval: None,
+        }
+    }
+
+    /// Gets the [MessageType] for the [EventResendIntervalRequest].
+    pub const fn message_type(&self) -> MessageType {
+        MessageType::Request(self.request_type())
+    }
+
+    /// Gets the [RequestType] for the [EventResendIntervalRequest].
+    pub const fn request_type(&self) -> RequestType {
+        self.mode.into_request_type()
+    }
+
+    /// Gets the [MessageCode] for the [EventResendIntervalRequest].
+    pub const fn message_code(&self) -> MessageCode {
+        MessageCode::Request(self.request_code())
+    }
+
+    /// Gets the [RequestCode] for the [EventResendIntervalRequest].
+    pub const fn request_code(&self) -> RequestCode {
+        RequestCode::EventResendInterval
+    }
+
+    /// Gets the [EventResendIntervalMode] for the [EventResendIntervalRequest].
+    pub const fn mode(&self) -> EventResendIntervalMode {
+        self.mode
+    }
+
+    /// Sets the [EventResendIntervalMode] for the [EventResendIntervalRequest].
+    pub fn set_mode(&mut self, mode: EventResendIntervalMode) {
+        self.mode = mode;
+    }
+
+    /// Builder function that sets the [EventResendIntervalMode] for the [EventResendIntervalRequest].
+    pub const fn with_mode(self, mode: EventResendIntervalMode) -> Self {
+        Self {
+            mode,
+            interval: self.interval,
+        }
+    }
+
+    /// Gets the [EventResendInterval] for the [EventResendIntervalRequest].
+    ///
+    /// The [EventResendInterval] is only set for [Set](EventResendIntervalMode::Set) requests.
+    pub const fn interval(&self) -> Option<EventResendInterval> {
+        self.interval
+    }
+
+    /// Sets the [EventResendInterval] for the [EventResendIntervalRequest].
+    ///
+    /// The [EventResendInterval] is only set for [Set](EventResendIntervalMode::Set) requests.
+    pub fn set_interval(&mut self, interval: EventResendInterval) {
+        self.interval = Some(interval);
+    }
+
+    /// Unsets the [EventResendInterval] for the [EventResendIntervalRequest].
+    pub fn unset_interval(&mut self) -> Option<EventResendInterval> {
+        self.interval.take()
+    }
+
+    /// Builder function that sets the [EventResendInterval] for the [EventResendIntervalRequest].
+    ///
+    /// The [EventResendInterval] is only set for [Set](EventResendIntervalMode::Set) requests.
+    pub const fn with_interval(self, interval: EventResendInterval) -> Self {
+        Self {
+            mode: self.mode,
+            interval: Some(interval),
+        }
+    }
+}
+
+impl Default for EventResendIntervalRequest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<EventResendIntervalRequest> for Message {
+    fn from(val: EventResendIntervalRequest) -> Self {
+        MessageData::from(val).into()
+    }
+}
+
+impl From<&EventResendIntervalRequest> for Message {
+    fn from(val: &EventResendIntervalRequest) -> Self {
+        (*val).into()
+    }
+}
+
+impl From<EventResendIntervalRequest> for MessageData {
+    fn from(val: EventResendIntervalRequest) -> Self {
+        match val.mode() {
+            EventResendIntervalMode::Get => Self::new()
+                .with_message_type(val.message_type())
+                .with_message_code(val.message_code()),
+            EventResendIntervalMode::Set => Self::new()
+                .with_message_type(val.message_type())
+                .with_message_code(val.message_code())
+                .with_additional(val.interval().unwrap_or_default().into_bytes().as_ref()),
+        }
+    }
+}
+
+impl From<&EventResendIntervalRequest> for MessageData {
+    fn from(val: &EventResendIntervalRequest) -> Self {
+        (*val).into()
+    }
+}
+
+impl TryFrom<&Message> for EventResendIntervalRequest {
+    type Error = Error;
+
+    fn try_from(val: &Message) -> Result<Self> {
+        val.data().try_into()
+    }
+}
+
+impl TryFrom<Message> for EventResendIntervalRequest {
+    type Error = Error;
+
+    fn try_from(val: Message) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
+impl TryFrom<&MessageData> for EventResendIntervalRequest {
+    type Error = Error;
+
+    fn try_from(val: &MessageData) -> Result<Self> {
+        // could also be EventResendIntervalMode::Set
+        let exp_type = MessageType::from(EventResendIntervalMode::Get);
+        let exp_code = MessageCode::Request(RequestCode::EventResendInterval);
+
+        match (val.message_type(), val.message_code()) {
+            (msg_type, msg_code) if msg_code == exp_code => {
+                let mode = EventResendIntervalMode::try_from(msg_type.request_type()?)?;
+                match mode {
+                    EventResendIntervalMode::Get => Ok(Self::new().with_mode(mode)),
+                    EventResendIntervalMode::Set => Ok(Self::new()
+                        .with_mode(mode)
+                        .with_interval(EventResendInterval::try_from(val.additional())?)),
+                }
+            }
+            (msg_type, msg_code) => Err(Error::InvalidMessage((
+                (msg_type.into(), msg_code.into()),
+                (exp_type.into(), exp_code.into()),
+            ))),
+        }
+    }
+}
+
+impl TryFrom<MessageData> for EventResendIntervalRequest {
+    type Error = Error;
+
+    fn try_from(val: MessageData) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventCode, EventType, MAX_EVENT_RESEND_INTERVAL, MIN_EVENT_RESEND_INTERVAL};
+
+    #[test]
+    fn test_event_resend_interval_request() -> Result<()> {
+        let exp_code = RequestCode::EventResendInterval;
+        let all_intervals = [
+            EventResendInterval::create(MIN_EVENT_RESEND_INTERVAL)?,
+            EventResendInterval::new(),
+            EventResendInterval::create(MAX_EVENT_RESEND_INTERVAL)?,
+        ];
+
+        for (exp_type, exp_interval) in [RequestType::Status, RequestType::SetFeature]
+            .into_iter()
+            .flat_map(|t| all_intervals.map(|i| (t, i)))
+        {
+            let exp_mode = exp_type.try_into()?;
+
+            let msg_data = MessageData::new()
+                .with_message_type(MessageType::Request(exp_type))
+                .with_message_code(MessageCode::Request(exp_code));
+
+            let msg = match exp_mode {
+                EventResendIntervalMode::Get => Message::new().with_data(msg_data),
+                EventResendIntervalMode::Set => Message::new()
+                    .with_data(msg_data.with_additional(exp_interval.into_bytes().as_ref())),
+            };
+
+            let exp_req = match exp_mode {
+                EventResendIntervalMode::Get => {
+                    EventResendIntervalRequest::new().with_mode(exp_type.try_into()?)
+                }
+                EventResendIntervalMode::Set => EventResendIntervalRequest::new()
+                    .with_mode(exp_type.try_into()?)
+                    .with_interval(exp_interval),
+            };
+
+            assert_eq!(exp_req.message_type(), MessageType::Request(exp_type));
+            assert_eq!(exp_req.request_type(), exp_type);
+
+            assert_eq!(exp_req.message_code(), MessageCode::Request(exp_code));
+            assert_eq!(exp_req.request_code(), exp_code);
+
+            assert_eq!(exp_req.mode(), exp_mode);
+            match exp_mode {
+                EventResendIntervalMode::Get => assert_eq!(exp_req.interval(), None),
+                EventResendIntervalMode::Set => assert_eq!(exp_req.interval(), Some(exp_interval)),
+            };
+
+            assert_eq!(Message::from(exp_req), msg);
+            assert_eq!(EventResendIntervalRequest::try_from(&msg), Ok(exp_req));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_event_resend_interval_request_invalid() -> Result<()> {
+        let invalid_types = [MessageType::Reserved]
+            .into_iter()
+            .chain((0x80..=0x8f).map(|m| MessageType::Event(EventType::from_u8(m))))
+            .chain([RequestType::Operation, RequestType::Reserved].map(MessageType::Request))
+            .collect::<Vec<MessageType>>();
+
+        let invalid_codes = [
+            RequestCode::Uid,
+            RequestCode::ProgramSignature,
+            RequestCode::Version,
+            RequestCode::SerialNumber,
+            RequestCode::ModelName,
+            RequestCode::Reset,
+            RequestCode::Stack,
+            RequestCode::Inhibit,
+            RequestCode::Status,
+            RequestCode::Key,
+            RequestCode::Pause,
+            RequestCode::Idle,
+            RequestCode::Reject,
+            RequestCode::Hold,
+            RequestCode::DenominationDisable,
+            RequestCode::DirectionDisable,
+            RequestCode::CurrencyAssign,
+            RequestCode::CashBoxSize,
+            RequestCode::Collect,
+            RequestCode::NearFull,
+            RequestCode::Insert,
+            RequestCode::ConditionalVend,
+            RequestCode::BarCode,
+            RequestCode::NoteDataInfo,
+            RequestCode::Reserved,
+        ]
+        .map(MessageCode::Request)
+        .into_iter()
+        .chain(
+            [
+                EventCode::PowerUp,
+                EventCode::PowerUpAcceptor,
+                EventCode::PowerUpStacker,
+                EventCode::Inhibit,
+                EventCode::ProgramSignature,
+                EventCode::Rejected,
+                EventCode::Collected,
+                EventCode::Clear,
+                EventCode::OperationError,
+                EventCode::Failure,
+                EventCode::NoteStay,
+                EventCode::PowerUpAcceptorAccepting,
+                EventCode::PowerUpStackerAccepting,
+                EventCode::Idle,
+                EventCode::Escrow,
+                EventCode::VendValid,
+                EventCode::AcceptorRejected,
+                EventCode::Returned,
+                EventCode::AcceptorCollected,
+                EventCode::Insert,
+                EventCode::ConditionalVend,
+                EventCode::Pause,
+                EventCode::Resume,
+                EventCode::AcceptorClear,
+                EventCode::AcceptorOperationError,
+                EventCode::AcceptorFailure,
+                EventCode::AcceptorNoteStay,
+                EventCode::FunctionAbeyance,
+                EventCode::Reserved,
+            ]
+            .map(MessageCode::Event),
+        )
+        .collect::<Vec<MessageCode>>();
+
+        for &msg_type in invalid_types.iter() {
+            for &msg_code in invalid_codes.iter() {
+                let inval_data = MessageData::new()
+                    .with_message_type(msg_type)
+                    .with_message_code(msg_code);
+
+                let inval_type = MessageData::new()
+                    .with_message_type(msg_type)
+                    .with_message_code(EventResendIntervalRequest::new().message_code());
+
+                let inval_code = MessageData::new()
+                    .with_message_type(EventResendIntervalRequest::new().message_type())
+                    .with_message_code(msg_code);
+
+                for stack_data in [inval_data, inval_type, inval_code] {
+                    assert!(EventResendIntervalRequest::try_from(&stack_data).is_err());
+                    assert!(EventResendIntervalRequest::try_from(
+                        Message::new().with_data(stack_data)
+                    )
+                    .is_err());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,200 @@
+use core::{fmt, time};
+
+use crate::{Error, Result};
+
+/// Represents the length of an [EventResendInterval].
+pub const EVENT_RESEND_INTERVAL_LEN: usize = 1;
+/// Represents the minimum [EventResendInterval], in 100 ms units.
+pub const MIN_EVENT_RESEND_INTERVAL: u8 = 1;
+/// Represents the maximum [EventResendInterval], in 100 ms units.
+pub const MAX_EVENT_RESEND_INTERVAL: u8 = 100;
+/// Represents the default [EventResendInterval], in 100 ms units.
+pub const DEFAULT_EVENT_RESEND_INTERVAL: u8 = 10;
+
+const INTERVAL_UNIT_MS: u64 = 100;
+
+/// Represents the `Event Re-sending Interval` setting of the device.
+///
+/// The device re-sends an event when the host does not acknowledge it within the interval. The
+/// interval is encoded as a single byte, in 100 ms units, from [MIN_EVENT_RESEND_INTERVAL]
+/// (100 ms) to [MAX_EVENT_RESEND_INTERVAL] (10 s).
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use jcm::EventResendInterval;
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// let interval = EventResendInterval::from_duration(Duration::from_millis(2500))?;
+///
+/// assert_eq!(interval.units(), 25);
+/// assert_eq!(interval.into_bytes(), [25]);
+/// assert!(EventResendInterval::from_duration(Duration::from_secs(11)).is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EventResendInterval(u8);
+
+impl EventResendInterval {
+    /// Creates a new [EventResendInterval], with the default one second interval.
+    pub const fn new() -> Self {
+        Self(DEFAULT_EVENT_RESEND_INTERVAL)
+    }
+
+    /// Creates a new [EventResendInterval] from a number of 100 ms units.
+    ///
+    /// Returns an error if the interval is outside the protocol limits.
+    pub const fn create(units: u8) -> Result<Self> {
+        if units >= MIN_EVENT_RESEND_INTERVAL && units <= MAX_EVENT_RESEND_INTERVAL {
+            Ok(Self(units))
+        } else {
+            Err(Error::InvalidEventResendInterval(units))
+        }
+    }
+
+    /// Creates a new [EventResendInterval] from a [Duration](time::Duration).
+    ///
+    /// Returns an error if the duration is not a whole number of 100 ms units, or is outside the
+    /// protocol limits.
+    pub fn from_duration(duration: time::Duration) -> Result<Self> {
+        let millis = duration.as_millis();
+        let units = millis / u128::from(INTERVAL_UNIT_MS);
+        let units = u8::try_from(units).unwrap_or(u8::MAX);
+
+        if !millis.is_multiple_of(u128::from(INTERVAL_UNIT_MS)) {
+            Err(Error::InvalidEventResendInterval(units))
+        } else {
+            Self::create(units)
+        }
+    }
+
+    /// Gets the interval in 100 ms units.
+    pub const fn units(&self) -> u8 {
+        self.0
+    }
+
+    /// Gets the interval as a [Duration](time::Duration).
+    pub const fn duration(&self) -> time::Duration {
+        time::Duration::from_millis(self.0 as u64 * INTERVAL_UNIT_MS)
+    }
+
+    /// Gets the byte length of the [EventResendInterval].
+    pub const fn len() -> usize {
+        EVENT_RESEND_INTERVAL_LEN
+    }
+
+    /// Attempts to convert a byte buffer into an [EventResendInterval].
+    pub fn from_bytes(buf: &[u8]) -> Result<Self> {
+        match buf.first() {
+            Some(&units) => Self::create(units),
+            None => Err(Error::InvalidEventResendIntervalLen((
+                buf.len(),
+                EVENT_RESEND_INTERVAL_LEN,
+            ))),
+        }
+    }
+
+    /// Converts the [EventResendInterval] into a byte array.
+    pub const fn into_bytes(self) -> [u8; EVENT_RESEND_INTERVAL_LEN] {
+        [self.0]
+    }
+}
+
+impl Default for EventResendInterval {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TryFrom<u8> for EventResendInterval {
+    type Error = Error;
+
+    fn try_from(val: u8) -> Result<Self> {
+        Self::create(val)
+    }
+}
+
+impl TryFrom<time::Duration> for EventResendInterval {
+    type Error = Error;
+
+    fn try_from(val: time::Duration) -> Result<Self> {
+        Self::from_duration(val)
+    }
+}
+
+impl TryFrom<&[u8]> for EventResendInterval {
+    type Error = Error;
+
+    fn try_from(val: &[u8]) -> Result<Self> {
+        Self::from_bytes(val)
+    }
+}
+
+impl From<EventResendInterval> for time::Duration {
+    fn from(val: EventResendInterval) -> Self {
+        val.duration()
+    }
+}
+
+impl fmt::Display for EventResendInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#"{{"interval_ms": {}}}"#, self.duration().as_millis())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_resend_interval() -> Result<()> {
+        for units in MIN_EVENT_RESEND_INTERVAL..=MAX_EVENT_RESEND_INTERVAL {
+            let interval = EventResendInterval::from_bytes(&[units])?;
+
+            assert_eq!(interval.units(), units);
+            assert_eq!(
+                interval.duration(),
+                time::Duration::from_millis(u64::from(units) * 100)
+            );
+            assert_eq!(
+                EventResendInterval::from_duration(interval.duration()),
+                Ok(interval)
+            );
+            assert_eq!(interval.into_bytes(), [units]);
+        }
+
+        assert_eq!(
+            EventResendInterval::new().to_string(),
+            r#"{"interval_ms": 1000}"#
+        );
+
+        for units in [0, MAX_EVENT_RESEND_INTERVAL + 1, u8::MAX] {
+            assert_eq!(
+                EventResendInterval::create(units),
+                Err(Error::InvalidEventResendInterval(units))
+            );
+        }
+        assert_eq!(
+            EventResendInterval::from_duration(time::Duration::from_millis(150)),
+            Err(Error::InvalidEventResendInterval(1))
+        );
+        assert_eq!(
+            EventResendInterval::from_duration(time::Duration::from_secs(60)),
+            Err(Error::InvalidEventResendInterval(u8::MAX))
+        );
+        assert_eq!(
+            EventResendInterval::from_bytes(&[]),
+            Err(Error::InvalidEventResendIntervalLen((
+                0,
+                EVENT_RESEND_INTERVAL_LEN
+            )))
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use core::fmt;
+
+use crate::{Error, MessageType, RequestType, Result};
+
+/// Represents the [RequestType] modes for the [EventResendIntervalRequest].
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EventResendIntervalMode {
+    Get,
+    Set,
+}
+
+impl EventResendIntervalMode {
+    /// Creates a new [EventResendIntervalMode].
+    pub const fn new() -> Self {
+        Self::Get
+    }
+
+    /// Converts a [RequestType] into a [EventResendIntervalMode].
+    pub const fn from_request_type(val: RequestType) -> Option<Self> {
+        match val {
+            RequestType::Status => Some(Self::Get),
+            RequestType::SetFeature => Some(Self::Set),
+            _ => None,
+        }
+    }
+
+    /// Converts a [EventResendIntervalMode] into a [RequestType].
+    pub const fn into_request_type(self) -> RequestType {
+        match self {
+            Self::Get => RequestType::Status,
+            Self::Set => RequestType::SetFeature,
+        }
+    }
+}
+
+impl Default for EventResendIntervalMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TryFrom<RequestType> for EventResendIntervalMode {
+    type Error = Error;
+
+    fn try_from(val: RequestType) -> Result<Self> {
+        Self::from_request_type(val).ok_or(Error::InvalidEventResendIntervalMode(val.into()))
+    }
+}
+
+impl From<EventResendIntervalMode> for RequestType {
+    fn from(val: EventResendIntervalMode) -> Self {
+        val.into_request_type()
+    }
+}
+
+impl From<EventResendIntervalMode> for MessageType {
+    fn from(val: EventResendIntervalMode) -> Self {
+        MessageType::Request(val.into_request_type())
+    }
+}
+
+impl From<EventResendIntervalMode> for &'static str {
+    fn from(val: EventResendIntervalMode) -> Self {
+        match val {
+            EventResendIntervalMode::Get => "get",
+            EventResendIntervalMode::Set => "set",
+        }
+    }
+}
+
+impl From<&EventResendIntervalMode> for &'static str {
+    fn from(val: &EventResendIntervalMode) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for EventResendIntervalMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
@@ -7,17 +7,42 @@ mod hold_timeout;
 pub use hold_timeout::*;
 
 /// Represents a `Hold` request message.
+///
+/// Holds the note in escrow, until the host stacks or returns it, or the [HoldTimeout] expires.
+/// Sending another `Hold` request while the note is held extends the hold, restarting the
+/// timeout. Without a [HoldTimeout], the device uses its own default timeout.
+///
+/// `Hold` is only valid while a note is in escrow, see [EscrowSession](crate::EscrowSession) for
+/// a session API enforcing it.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{HoldRequest, Message};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, type, code, data
+/// let frame = [0x12, 0x0a, 0x00, 0x10, 0x00, 0x00, 0x16, 0x10, 0x0a, 0x00];
+///
+/// let req = HoldRequest::create(10);
+///
+/// assert_eq!(HoldRequest::try_from(Message::try_from(frame.as_ref())?)?, req);
+/// assert_eq!(Vec::<u8>::from(Message::from(&req)), frame);
+/// # Ok(())
+/// # }
+/// ```
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HoldRequest {
-    timeout: HoldTimeout,
+    timeout: Option<HoldTimeout>,
 }
 
 impl HoldRequest {
-    /// Creates a new [HoldRequest].
+    /// Creates a new [HoldRequest], with the [DEFAULT_HOLD_TIMEOUT].
     pub const fn new() -> Self {
         Self {
-            timeout: HoldTimeout::new(),
+            timeout: Some(HoldTimeout::new()),
         }
     }
 
@@ -26,7 +51,7 @@ impl HoldRequest {
     /// - `timeout`: number of seconds to hold a note in escrow.
     pub const fn create(timeout: u16) -> Self {
         Self {
-            timeout: HoldTimeout::from_u16(timeout),
+            timeout: Some(HoldTimeout::from_u16(timeout)),
         }
     }
 
@@ -50,14 +75,19 @@ impl HoldRequest {
         RequestCode::Hold
     }
 
-    /// Gets the timeout in seconds to hold a note in escrow.
-    pub const fn timeout(&self) -> HoldTimeout {
+    /// Gets the timeout in seconds to hold a note in escrow, if set.
+    pub const fn timeout(&self) -> Option<HoldTimeout> {
         self.timeout
     }
 
     /// Sets the timeout in seconds to hold a note in escrow.
     pub fn set_timeout(&mut self, val: HoldTimeout) {
-        self.timeout = val;
+        self.timeout = Some(val);
+    }
+
+    /// Unsets the timeout, so the device holds the note for its default timeout.
+    pub fn unset_timeout(&mut self) -> Option<HoldTimeout> {
+        self.timeout.take()
     }
 
     /// Builder function that sets the timeout in seconds to hold a note in escrow.
@@ -90,7 +120,12 @@ impl From<HoldRequest> for MessageData {
         Self::new()
             .with_message_type(val.message_type())
             .with_message_code(val.message_code())
-            .with_additional(val.timeout().to_bytes().as_ref())
+            .with_additional(
+                val.timeout()
+                    .map(|t| t.to_bytes().to_vec())
+                    .unwrap_or_default()
+                    .as_ref(),
+            )
     }
 }
 
@@ -125,7 +160,10 @@ impl TryFrom<&MessageData> for HoldRequest {
             MessageCode::Request(RequestCode::Hold),
         );
 
-        let timeout = HoldTimeout::try_from(val.additional())?;
+        let timeout = match val.additional() {
+            [] => None,
+            additional => Some(HoldTimeout::try_from(additional)?),
+        };
 
         match (val.message_type(), val.message_code()) {
             (msg_type, msg_code) if msg_type == exp_type && msg_code == exp_code => {
@@ -172,11 +210,27 @@ mod tests {
         assert_eq!(exp_req.message_code(), exp_code);
         assert_eq!(exp_code.request_code(), Ok(exp_req.request_code()));
 
-        assert_eq!(exp_req.timeout(), exp_timeout);
+        assert_eq!(exp_req.timeout(), Some(exp_timeout));
 
         assert_eq!(Message::from(exp_req), msg);
         assert_eq!(HoldRequest::try_from(&msg), Ok(exp_req));
 
+        let mut default_req = exp_req;
+        assert_eq!(default_req.unset_timeout(), Some(exp_timeout));
+
+        let default_msg = Message::new().with_data(
+            MessageData::new()
+                .with_message_type(exp_type)
+                .with_message_code(exp_code),
+        );
+        assert_eq!(Message::from(default_req), default_msg);
+        assert_eq!(HoldRequest::try_from(&default_msg), Ok(default_req));
+        assert_eq!(default_req.timeout(), None);
+
+        assert!(
+            HoldRequest::try_from(MessageData::from(exp_req).with_additional(&[0x0a])).is_err()
+        );
+
         Ok(())
     }
 
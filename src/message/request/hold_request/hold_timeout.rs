@@ -6,6 +6,7 @@ pub const DEFAULT_HOLD_TIMEOUT: u16 = 10;
 /// Represents the timeout in seconds to hold a note in escrow.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HoldTimeout(u16);
 
 impl HoldTimeout {
@@ -21,7 +22,7 @@ impl HoldTimeout {
 
     /// Gets the length of the [HoldTimeout].
     pub const fn len() -> usize {
-        std::mem::size_of::<u16>()
+        core::mem::size_of::<u16>()
     }
 
     /// Gets whether the [HoldTimeout] is empty.
@@ -2,9 +2,27 @@ use crate::{
     Error, Message, MessageCode, MessageData, MessageType, RequestCode, RequestType, Result,
 };
 
-/// Represents a `Idle` request message.
+/// Represents an `Idle` request message.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{IdleRequest, Message};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, type, code, data
+/// let frame = [0x12, 0x08, 0x00, 0x10, 0x00, 0x00, 0x13, 0x10];
+///
+/// let req = IdleRequest::new();
+///
+/// assert_eq!(IdleRequest::try_from(Message::try_from(frame.as_ref())?)?, req);
+/// assert_eq!(Vec::<u8>::from(Message::from(&req)), frame);
+/// # Ok(())
+/// # }
+/// ```
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IdleRequest;
 
 impl IdleRequest {
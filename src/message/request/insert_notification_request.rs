@@ -0,0 +1,358 @@
+use crate::{
+    Error, Message, MessageCode, MessageData, MessageType, RequestCode, RequestType, Result,
+};
+
+mod insert_notification;
+mod insert_notification_mode;
+
+pub use insert_notification::*;
+pub use insert_notification_mode::*;
+
+/// Represents an `Insert` request message.
+///
+/// This request is used to get/set the `Insert Notification Function` of the device. When
+/// [Enabled](InsertNotification::Enabled), the device sends an `Insert` event as soon as a note
+/// is inserted, before validation completes.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{Message, InsertNotificationRequest};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, type, code, data
+/// let frame = [0x12, 0x08, 0x00, 0x10, 0x00, 0x10, 0x28, 0x10];
+///
+/// let req = InsertNotificationRequest::new();
+///
+/// assert_eq!(InsertNotificationRequest::try_from(Message::try_from(frame.as_ref())?)?, req);
+/// assert_eq!(Vec::<u8>::from(Message::from(&req)), frame);
+/// # Ok(())
+/// # }
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InsertNotificationRequest {
+    mode: InsertNotificationMode,
+    notification: Option<InsertNotification>,
+}
+
+impl InsertNotificationRequest {
+    /// Creates a new [InsertNotificationRequest].
+    pub const fn new() -> Self {
+        Self {
+            mode: InsertNotificationMode::new(),
+            notification: None,
+        }
+    }
+
+    /// Gets the [MessageType] for the [InsertNotificationRequest].
+    pub const fn message_type(&self) -> MessageType {
+        MessageType::Request(self.request_type())
+    }
+
+    /// Gets the [RequestType] for the [InsertNotificationRequest].
+    pub const fn request_type(&self) -> RequestType {
+        self.mode.into_request_type()
+    }
+
+    /// Gets the [MessageCode] for the [InsertNotificationRequest].
+    pub const fn message_code(&self) -> MessageCode {
+        MessageCode::Request(self.request_code())
+    }
+
+    /// Gets the [RequestCode] for the [InsertNotificationRequest].
+    pub const fn request_code(&self) -> RequestCode {
+        RequestCode::Insert
+    }
+
+    /// Gets the [InsertNotificationMode] for the [InsertNotificationRequest].
+    pub const fn mode(&self) -> InsertNotificationMode {
+        self.mode
+    }
+
+    /// Sets the [InsertNotificationMode] for the [InsertNotificationRequest].
+    pub fn set_mode(&mut self, mode: InsertNotificationMode) {
+        self.mode = mode;
+    }
+
+    /// Builder function that sets the [InsertNotificationMode] for the [InsertNotificationRequest].
+    pub const fn with_mode(self, mode: InsertNotificationMode) -> Self {
+        Self {
+            mode,
+            notification: self.notification,
+        }
+    }
+
+    /// Gets the [InsertNotification] for the [InsertNotificationRequest].
+    ///
+    /// [InsertNotification] is only set for [Set](InsertNotificationMode::Set) requests.
+    pub const fn notification(&self) -> Option<InsertNotification> {
+        self.notification
+    }
+
+    /// Sets the [InsertNotification] for the [InsertNotificationRequest].
+    ///
+    /// [InsertNotification] is only set for [Set](InsertNotificationMode::Set) requests.
+    pub fn set_notification(&mut self, notification: InsertNotification) {
+        self.notification = Some(notification);
+    }
+
+    /// Unsets the [InsertNotification] for the [InsertNotificationRequest].
+    pub fn unset_notification(&mut self) -> Option<InsertNotification> {
+        self.notification.take()
+    }
+
+    /// Builder function that sets the [InsertNotification] for the [InsertNotificationRequest].
+    ///
+    /// [InsertNotification] is only set for [Set](InsertNotificationMode::Set) requests.
+    pub const fn with_notification(self, notification: InsertNotification) -> Self {
+        Self {
+            mode: self.mode,
+            notification: Some(notification),
+        }
+    }
+}
+
+impl Default for InsertNotificationRequest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<InsertNotificationRequest> for Message {
+    fn from(val: InsertNotificationRequest) -> Self {
+        MessageData::from(val).into()
+    }
+}
+
+impl From<&InsertNotificationRequest> for Message {
+    fn from(val: &InsertNotificationRequest) -> Self {
+        (*val).into()
+    }
+}
+
+impl From<InsertNotificationRequest> for MessageData {
+    fn from(val: InsertNotificationRequest) -> Self {
+        match val.mode() {
+            InsertNotificationMode::Get => Self::new()
+                .with_message_type(val.message_type())
+                .with_message_code(val.message_code()),
+            InsertNotificationMode::Set => Self::new()
+                .with_message_type(val.message_type())
+                .with_message_code(val.message_code())
+                .with_additional(val.notification().unwrap_or_default().into_bytes().as_ref()),
+        }
+    }
+}
+
+impl From<&InsertNotificationRequest> for MessageData {
+    fn from(val: &InsertNotificationRequest) -> Self {
+        (*val).into()
+    }
+}
+
+impl TryFrom<&Message> for InsertNotificationRequest {
+    type Error = Error;
+
+    fn try_from(val: &Message) -> Result<Self> {
+        val.data().try_into()
+    }
+}
+
+impl TryFrom<Message> for InsertNotificationRequest {
+    type Error = Error;
+
+    fn try_from(val: Message) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
+impl TryFrom<&MessageData> for InsertNotificationRequest {
+    type Error = Error;
+
+    fn try_from(val: &MessageData) -> Result<Self> {
+        // could also be InsertNotificationMode::Set
+        let exp_type = MessageType::from(InsertNotificationMode::Get);
+        let exp_code = MessageCode::Request(RequestCode::Insert);
+
+        match (val.message_type(), val.message_code()) {
+            (msg_type, msg_code) if msg_code == exp_code => {
+                let mode = InsertNotificationMode::try_from(msg_type.request_type()?)?;
+                match mode {
+                    InsertNotificationMode::Get => Ok(Self::new().with_mode(mode)),
+                    InsertNotificationMode::Set => Ok(Self::new()
+                        .with_mode(mode)
+                        .with_notification(InsertNotification::try_from(val.additional())?)),
+                }
+            }
+            (msg_type, msg_code) => Err(Error::InvalidMessage((
+                (msg_type.into(), msg_code.into()),
+                (exp_type.into(), exp_code.into()),
+            ))),
+        }
+    }
+}
+
+impl TryFrom<MessageData> for InsertNotificationRequest {
+    type Error = Error;
+
+    fn try_from(val: MessageData) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventCode, EventType};
+
+    #[test]
+    fn test_insert_notification_request() -> Result<()> {
+        let exp_code = RequestCode::Insert;
+        let exp_notification = InsertNotification::Enabled;
+
+        for exp_type in [RequestType::Status, RequestType::SetFeature] {
+            let exp_mode = exp_type.try_into()?;
+
+            let msg_data = MessageData::new()
+                .with_message_type(MessageType::Request(exp_type))
+                .with_message_code(MessageCode::Request(exp_code));
+
+            let msg = match exp_mode {
+                InsertNotificationMode::Get => Message::new().with_data(msg_data),
+                InsertNotificationMode::Set => Message::new()
+                    .with_data(msg_data.with_additional(exp_notification.into_bytes().as_ref())),
+            };
+
+            let exp_req = match exp_mode {
+                InsertNotificationMode::Get => {
+                    InsertNotificationRequest::new().with_mode(exp_type.try_into()?)
+                }
+                InsertNotificationMode::Set => InsertNotificationRequest::new()
+                    .with_mode(exp_type.try_into()?)
+                    .with_notification(exp_notification),
+            };
+
+            assert_eq!(exp_req.message_type(), MessageType::Request(exp_type));
+            assert_eq!(exp_req.request_type(), exp_type);
+
+            assert_eq!(exp_req.message_code(), MessageCode::Request(exp_code));
+            assert_eq!(exp_req.request_code(), exp_code);
+
+            assert_eq!(exp_req.mode(), exp_mode);
+            match exp_mode {
+                InsertNotificationMode::Get => assert_eq!(exp_req.notification(), None),
+                InsertNotificationMode::Set => {
+                    assert_eq!(exp_req.notification(), Some(exp_notification))
+                }
+            };
+
+            assert_eq!(Message::from(exp_req), msg);
+            assert_eq!(InsertNotificationRequest::try_from(&msg), Ok(exp_req));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_notification_request_invalid() -> Result<()> {
+        let invalid_types = [MessageType::Reserved]
+            .into_iter()
+            .chain((0x80..=0x8f).map(|m| MessageType::Event(EventType::from_u8(m))))
+            .chain([RequestType::Operation, RequestType::Reserved].map(MessageType::Request))
+            .collect::<Vec<MessageType>>();
+
+        let invalid_codes = [
+            RequestCode::Uid,
+            RequestCode::ProgramSignature,
+            RequestCode::Version,
+            RequestCode::SerialNumber,
+            RequestCode::ModelName,
+            RequestCode::Reset,
+            RequestCode::Stack,
+            RequestCode::Inhibit,
+            RequestCode::Status,
+            RequestCode::Key,
+            RequestCode::EventResendInterval,
+            RequestCode::Idle,
+            RequestCode::Reject,
+            RequestCode::Hold,
+            RequestCode::DenominationDisable,
+            RequestCode::DirectionDisable,
+            RequestCode::CurrencyAssign,
+            RequestCode::CashBoxSize,
+            RequestCode::Collect,
+            RequestCode::NearFull,
+            RequestCode::BarCode,
+            RequestCode::ConditionalVend,
+            RequestCode::Pause,
+            RequestCode::NoteDataInfo,
+            RequestCode::Reserved,
+        ]
+        .map(MessageCode::Request)
+        .into_iter()
+        .chain(
+            [
+                EventCode::PowerUp,
+                EventCode::PowerUpAcceptor,
+                EventCode::PowerUpStacker,
+                EventCode::Inhibit,
+                EventCode::ProgramSignature,
+                EventCode::Rejected,
+                EventCode::Collected,
+                EventCode::Clear,
+                EventCode::OperationError,
+                EventCode::Failure,
+                EventCode::NoteStay,
+                EventCode::PowerUpAcceptorAccepting,
+                EventCode::PowerUpStackerAccepting,
+                EventCode::Idle,
+                EventCode::Escrow,
+                EventCode::VendValid,
+                EventCode::AcceptorRejected,
+                EventCode::Returned,
+                EventCode::AcceptorCollected,
+                EventCode::Insert,
+                EventCode::ConditionalVend,
+                EventCode::Pause,
+                EventCode::Resume,
+                EventCode::AcceptorClear,
+                EventCode::AcceptorOperationError,
+                EventCode::AcceptorFailure,
+                EventCode::AcceptorNoteStay,
+                EventCode::FunctionAbeyance,
+                EventCode::Reserved,
+            ]
+            .map(MessageCode::Event),
+        )
+        .collect::<Vec<MessageCode>>();
+
+        for &msg_type in invalid_types.iter() {
+            for &msg_code in invalid_codes.iter() {
+                let inval_data = MessageData::new()
+                    .with_message_type(msg_type)
+                    .with_message_code(msg_code);
+
+                let inval_type = MessageData::new()
+                    .with_message_type(msg_type)
+                    .with_message_code(InsertNotificationRequest::new().message_code());
+
+                let inval_code = MessageData::new()
+                    .with_message_type(InsertNotificationRequest::new().message_type())
+                    .with_message_code(msg_code);
+
+                for stack_data in [inval_data, inval_type, inval_code] {
+                    assert!(InsertNotificationRequest::try_from(&stack_data).is_err());
+                    assert!(InsertNotificationRequest::try_from(
+                        Message::new().with_data(stack_data)
+                    )
+                    .is_err());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+use core::fmt;
+
+use crate::{Error, Result};
+
+/// Represents the length of an [InsertNotification].
+pub const INSERT_NOTIFICATION_LEN: usize = 1;
+
+const DISABLED: u8 = 0x00;
+const ENABLED: u8 = 0x01;
+
+/// Represents the `Insert Notification Function` setting of the device.
+///
+/// When enabled, the device sends an `Insert` event as soon as a note is inserted.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InsertNotification {
+    /// No `Insert` event is sent.
+    #[default]
+    Disabled = DISABLED,
+    /// An `Insert` event is sent for every inserted note.
+    Enabled = ENABLED,
+}
+
+impl InsertNotification {
+    /// Creates a new [InsertNotification].
+    pub const fn new() -> Self {
+        Self::Disabled
+    }
+
+    /// Gets whether the `Insert Notification Function` is enabled.
+    pub const fn is_enabled(&self) -> bool {
+        matches!(self, Self::Enabled)
+    }
+
+    /// Gets the byte length of the [InsertNotification].
+    pub const fn len() -> usize {
+        INSERT_NOTIFICATION_LEN
+    }
+
+    /// Attempts to convert a byte buffer into an [InsertNotification].
+    pub fn from_bytes(buf: &[u8]) -> Result<Self> {
+        match buf.first() {
+            Some(&DISABLED) => Ok(Self::Disabled),
+            Some(&ENABLED) => Ok(Self::Enabled),
+            Some(&val) => Err(Error::InvalidInsertNotification(val)),
+            None => Err(Error::InvalidInsertNotificationLen((
+                buf.len(),
+                INSERT_NOTIFICATION_LEN,
+            ))),
+        }
+    }
+
+    /// Converts the [InsertNotification] into a byte array.
+    pub const fn into_bytes(self) -> [u8; INSERT_NOTIFICATION_LEN] {
+        [self as u8]
+    }
+}
+
+impl From<bool> for InsertNotification {
+    fn from(val: bool) -> Self {
+        if val {
+            Self::Enabled
+        } else {
+            Self::Disabled
+        }
+    }
+}
+
+impl From<InsertNotification> for bool {
+    fn from(val: InsertNotification) -> Self {
+        val.is_enabled()
+    }
+}
+
+impl TryFrom<&[u8]> for InsertNotification {
+    type Error = Error;
+
+    fn try_from(val: &[u8]) -> Result<Self> {
+        Self::from_bytes(val)
+    }
+}
+
+impl From<InsertNotification> for &'static str {
+    fn from(val: InsertNotification) -> Self {
+        match val {
+            InsertNotification::Disabled => "disabled",
+            InsertNotification::Enabled => "enabled",
+        }
+    }
+}
+
+impl From<&InsertNotification> for &'static str {
+    fn from(val: &InsertNotification) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for InsertNotification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_notification() -> Result<()> {
+        assert_eq!(
+            InsertNotification::from_bytes(&[ENABLED])?,
+            InsertNotification::Enabled
+        );
+        assert_eq!(InsertNotification::from(false).into_bytes(), [DISABLED]);
+        assert!(bool::from(InsertNotification::Enabled));
+        assert_eq!(InsertNotification::Enabled.to_string(), r#""enabled""#);
+
+        assert_eq!(
+            InsertNotification::from_bytes(&[0x02]),
+            Err(Error::InvalidInsertNotification(0x02))
+        );
+        assert_eq!(
+            InsertNotification::from_bytes(&[]),
+            Err(Error::InvalidInsertNotificationLen((
+                0,
+                INSERT_NOTIFICATION_LEN
+            )))
+        );
+
+        Ok(())
+    }
+}
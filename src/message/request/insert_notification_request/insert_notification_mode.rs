@@ -0,0 +1,83 @@
+use core::fmt;
+
+use crate::{Error, MessageType, RequestType, Result};
+
+/// Represents the [RequestType] modes for the [InsertNotificationRequest].
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InsertNotificationMode {
+    Get,
+    Set,
+}
+
+impl InsertNotificationMode {
+    /// Creates a new [InsertNotificationMode].
+    pub const fn new() -> Self {
+        Self::Get
+    }
+
+    /// Converts a [RequestType] into an [InsertNotificationMode].
+    pub const fn from_request_type(val: RequestType) -> Option<Self> {
+        match val {
+            RequestType::Status => Some(Self::Get),
+            RequestType::SetFeature => Some(Self::Set),
+            _ => None,
+        }
+    }
+
+    /// Converts an [InsertNotificationMode] into a [RequestType].
+    pub const fn into_request_type(self) -> RequestType {
+        match self {
+            Self::Get => RequestType::Status,
+            Self::Set => RequestType::SetFeature,
+        }
+    }
+}
+
+impl Default for InsertNotificationMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TryFrom<RequestType> for InsertNotificationMode {
+    type Error = Error;
+
+    fn try_from(val: RequestType) -> Result<Self> {
+        Self::from_request_type(val).ok_or(Error::InvalidInsertNotificationMode(val.into()))
+    }
+}
+
+impl From<InsertNotificationMode> for RequestType {
+    fn from(val: InsertNotificationMode) -> Self {
+        val.into_request_type()
+    }
+}
+
+impl From<InsertNotificationMode> for MessageType {
+    fn from(val: InsertNotificationMode) -> Self {
+        MessageType::Request(val.into_request_type())
+    }
+}
+
+impl From<InsertNotificationMode> for &'static str {
+    fn from(val: InsertNotificationMode) -> Self {
+        match val {
+            InsertNotificationMode::Get => "get",
+            InsertNotificationMode::Set => "set",
+        }
+    }
+}
+
+impl From<&InsertNotificationMode> for &'static str {
+    fn from(val: &InsertNotificationMode) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for InsertNotificationMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
@@ -10,8 +10,26 @@ pub use near_full_mode::*;
 /// Represents a `Near Full` request message.
 ///
 /// This request is used to get/set the `Near Full` threshold of the device.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{Message, NearFullRequest};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, type, code, data
+/// let frame = [0x12, 0x08, 0x00, 0x10, 0x00, 0x10, 0x25, 0x10];
+///
+/// let req = NearFullRequest::new();
+///
+/// assert_eq!(NearFullRequest::try_from(Message::try_from(frame.as_ref())?)?, req);
+/// assert_eq!(Vec::<u8>::from(Message::from(&req)), frame);
+/// # Ok(())
+/// # }
+/// ```
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NearFullRequest {
     mode: NearFullMode,
     data: Option<NearFullData>,
@@ -1,10 +1,11 @@
-use std::fmt;
+use core::fmt;
 
 use crate::{Error, MessageType, RequestType, Result};
 
 /// Represents the [RequestType] modes for the [NearFullRequest].
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NearFullMode {
     Get,
     Set,
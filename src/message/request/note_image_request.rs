@@ -3,9 +3,27 @@ use crate::{
     RequestType, Result,
 };
 
-/// Represents a `Serial Number Image` request message.
+/// Represents a `Note Image` request message.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{Message, NoteImageRequest};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, type, code, data
+/// let frame = [0x12, 0x09, 0x00, 0x10, 0x00, 0x10, 0x2f, 0x10, 0x00];
+///
+/// let req = NoteImageRequest::new();
+///
+/// assert_eq!(NoteImageRequest::try_from(Message::try_from(frame.as_ref())?)?, req);
+/// assert_eq!(Vec::<u8>::from(Message::from(&req)), frame);
+/// # Ok(())
+/// # }
+/// ```
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NoteImageRequest {
     block_number: ImageBlockNumber,
 }
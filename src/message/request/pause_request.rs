@@ -0,0 +1,358 @@
+use crate::{
+    Error, Message, MessageCode, MessageData, MessageType, PauseSettings, RequestCode, RequestType,
+    Result,
+};
+
+mod pause_mode;
+
+pub use pause_mode::*;
+
+/// Represents a `Pause` request message.
+///
+/// This request is used to get/set the `Pause` settings of the device: the pause duration, and
+/// whether the device sends `Status` and event messages on its own.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{Message, PauseRequest};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, type, code, data
+/// let frame = [0x12, 0x08, 0x00, 0x10, 0x00, 0x10, 0x2a, 0x10];
+///
+/// let req = PauseRequest::new();
+///
+/// assert_eq!(PauseRequest::try_from(Message::try_from(frame.as_ref())?)?, req);
+/// assert_eq!(Vec::<u8>::from(Message::from(&req)), frame);
+/// # Ok(())
+/// # }
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PauseRequest {
+    mode: PauseMode,
+    settings: Option<PauseSettings>,
+}
+
+impl PauseRequest {
+    /// Creates a new [PauseRequest].
+    pub const fn new() -> Self {
+        Self {
+            mode: PauseMode::new(),
+            settings: None,
+        }
+    }
+
+    /// Gets the [MessageType] for the [PauseRequest].
+    pub const fn message_type(&self) -> MessageType {
+        MessageType::Request(self.request_type())
+    }
+
+    /// Gets the [RequestType] for the [PauseRequest].
+    pub const fn request_type(&self) -> RequestType {
+        self.mode.into_request_type()
+    }
+
+    /// Gets the [MessageCode] for the [PauseRequest].
+    pub const fn message_code(&self) -> MessageCode {
+        MessageCode::Request(self.request_code())
+    }
+
+    /// Gets the [RequestCode] for the [PauseRequest].
+    pub const fn request_code(&self) -> RequestCode {
+        RequestCode::Pause
+    }
+
+    /// Gets the [PauseMode] for the [PauseRequest].
+    pub const fn mode(&self) -> PauseMode {
+        self.mode
+    }
+
+    /// Sets the [PauseMode] for the [PauseRequest].
+    pub fn set_mode(&mut self, mode: PauseMode) {
+        self.mode = mode;
+    }
+
+    /// Builder function that sets the [PauseMode] for the [PauseRequest].
+    pub const fn with_mode(self, mode: PauseMode) -> Self {
+        Self {
+            mode,
+            settings: self.settings,
+        }
+    }
+
+    /// Gets the [PauseSettings] for the [PauseRequest].
+    ///
+    /// [PauseSettings] are only set for [Set](PauseMode::Set) requests.
+    pub const fn settings(&self) -> Option<PauseSettings> {
+        self.settings
+    }
+
+    /// Sets the [PauseSettings] for the [PauseRequest].
+    ///
+    /// [PauseSettings] are only set for [Set](PauseMode::Set) requests.
+    pub fn set_settings(&mut self, settings: PauseSettings) {
+        self.settings = Some(settings);
+    }
+
+    /// Unsets the [PauseSettings] for the [PauseRequest].
+    pub fn unset_settings(&mut self) -> Option<PauseSettings> {
+        self.settings.take()
+    }
+
+    /// Builder function that sets the [PauseSettings] for the [PauseRequest].
+    ///
+    /// [PauseSettings] are only set for [Set](PauseMode::Set) requests.
+    pub const fn with_settings(self, settings: PauseSettings) -> Self {
+        Self {
+            mode: self.mode,
+            settings: Some(settings),
+        }
+    }
+}
+
+impl Default for PauseRequest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<PauseRequest> for Message {
+    fn from(val: PauseRequest) -> Self {
+        MessageData::from(val).into()
+    }
+}
+
+impl From<&PauseRequest> for Message {
+    fn from(val: &PauseRequest) -> Self {
+        (*val).into()
+    }
+}
+
+impl From<PauseRequest> for MessageData {
+    fn from(val: PauseRequest) -> Self {
+        match val.mode() {
+            PauseMode::Get => Self::new()
+                .with_message_type(val.message_type())
+                .with_message_code(val.message_code()),
+            PauseMode::Set => Self::new()
+                .with_message_type(val.message_type())
+                .with_message_code(val.message_code())
+                .with_additional(val.settings().unwrap_or_default().into_bytes().as_ref()),
+        }
+    }
+}
+
+impl From<&PauseRequest> for MessageData {
+    fn from(val: &PauseRequest) -> Self {
+        (*val).into()
+    }
+}
+
+impl TryFrom<&Message> for PauseRequest {
+    type Error = Error;
+
+    fn try_from(val: &Message) -> Result<Self> {
+        val.data().try_into()
+    }
+}
+
+impl TryFrom<Message> for PauseRequest {
+    type Error = Error;
+
+    fn try_from(val: Message) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
+impl TryFrom<&MessageData> for PauseRequest {
+    type Error = Error;
+
+    fn try_from(val: &MessageData) -> Result<Self> {
+        // could also be PauseMode::Set
+        let exp_type = MessageType::from(PauseMode::Get);
+        let exp_code = MessageCode::Request(RequestCode::Pause);
+
+        match (val.message_type(), val.message_code()) {
+            (msg_type, msg_code) if msg_code == exp_code => {
+                let mode = PauseMode::try_from(msg_type.request_type()?)?;
+                match mode {
+                    PauseMode::Get => Ok(Self::new().with_mode(mode)),
+                    PauseMode::Set => Ok(Self::new()
+                        .with_mode(mode)
+                        .with_settings(PauseSettings::try_from(val.additional())?)),
+                }
+            }
+            (msg_type, msg_code) => Err(Error::InvalidMessage((
+                (msg_type.into(), msg_code.into()),
+                (exp_type.into(), exp_code.into()),
+            ))),
+        }
+    }
+}
+
+impl TryFrom<MessageData> for PauseRequest {
+    type Error = Error;
+
+    fn try_from(val: MessageData) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventCode, EventType};
+
+    #[test]
+    fn test_pause_request() -> Result<()> {
+        let exp_code = RequestCode::Pause;
+        let all_settings =
+            [(false, false), (true, false), (false, true), (true, true)].map(|(status, event)| {
+                PauseSettings::new()
+                    .with_duration_ms(500)
+                    .with_status_messages(status)
+                    .with_event_messages(event)
+            });
+
+        for (exp_type, exp_settings) in [RequestType::Status, RequestType::SetFeature]
+            .into_iter()
+            .flat_map(|t| all_settings.map(|s| (t, s)))
+        {
+            let exp_mode = exp_type.try_into()?;
+
+            let msg_data = MessageData::new()
+                .with_message_type(MessageType::Request(exp_type))
+                .with_message_code(MessageCode::Request(exp_code));
+
+            let msg = match exp_mode {
+                PauseMode::Get => Message::new().with_data(msg_data),
+                PauseMode::Set => Message::new()
+                    .with_data(msg_data.with_additional(exp_settings.into_bytes().as_ref())),
+            };
+
+            let exp_req = match exp_mode {
+                PauseMode::Get => PauseRequest::new().with_mode(exp_type.try_into()?),
+                PauseMode::Set => PauseRequest::new()
+                    .with_mode(exp_type.try_into()?)
+                    .with_settings(exp_settings),
+            };
+
+            assert_eq!(exp_req.message_type(), MessageType::Request(exp_type));
+            assert_eq!(exp_req.request_type(), exp_type);
+
+            assert_eq!(exp_req.message_code(), MessageCode::Request(exp_code));
+            assert_eq!(exp_req.request_code(), exp_code);
+
+            assert_eq!(exp_req.mode(), exp_mode);
+            match exp_mode {
+                PauseMode::Get => assert_eq!(exp_req.settings(), None),
+                PauseMode::Set => assert_eq!(exp_req.settings(), Some(exp_settings)),
+            };
+
+            assert_eq!(Message::from(exp_req), msg);
+            assert_eq!(PauseRequest::try_from(&msg), Ok(exp_req));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pause_request_invalid() -> Result<()> {
+        let invalid_types = [MessageType::Reserved]
+            .into_iter()
+            .chain((0x80..=0x8f).map(|m| MessageType::Event(EventType::from_u8(m))))
+            .chain([RequestType::Operation, RequestType::Reserved].map(MessageType::Request))
+            .collect::<Vec<MessageType>>();
+
+        let invalid_codes = [
+            RequestCode::Uid,
+            RequestCode::ProgramSignature,
+            RequestCode::Version,
+            RequestCode::SerialNumber,
+            RequestCode::ModelName,
+            RequestCode::Reset,
+            RequestCode::Stack,
+            RequestCode::Inhibit,
+            RequestCode::Status,
+            RequestCode::Key,
+            RequestCode::EventResendInterval,
+            RequestCode::Idle,
+            RequestCode::Reject,
+            RequestCode::Hold,
+            RequestCode::DenominationDisable,
+            RequestCode::DirectionDisable,
+            RequestCode::CurrencyAssign,
+            RequestCode::CashBoxSize,
+            RequestCode::Collect,
+            RequestCode::NearFull,
+            RequestCode::Insert,
+            RequestCode::ConditionalVend,
+            RequestCode::BarCode,
+            RequestCode::NoteDataInfo,
+            RequestCode::Reserved,
+        ]
+        .map(MessageCode::Request)
+        .into_iter()
+        .chain(
+            [
+                EventCode::PowerUp,
+                EventCode::PowerUpAcceptor,
+                EventCode::PowerUpStacker,
+                EventCode::Inhibit,
+                EventCode::ProgramSignature,
+                EventCode::Rejected,
+                EventCode::Collected,
+                EventCode::Clear,
+                EventCode::OperationError,
+                EventCode::Failure,
+                EventCode::NoteStay,
+                EventCode::PowerUpAcceptorAccepting,
+                EventCode::PowerUpStackerAccepting,
+                EventCode::Idle,
+                EventCode::Escrow,
+                EventCode::VendValid,
+                EventCode::AcceptorRejected,
+                EventCode::Returned,
+                EventCode::AcceptorCollected,
+                EventCode::Insert,
+                EventCode::ConditionalVend,
+                EventCode::Pause,
+                EventCode::Resume,
+                EventCode::AcceptorClear,
+                EventCode::AcceptorOperationError,
+                EventCode::AcceptorFailure,
+                EventCode::AcceptorNoteStay,
+                EventCode::FunctionAbeyance,
+                EventCode::Reserved,
+            ]
+            .map(MessageCode::Event),
+        )
+        .collect::<Vec<MessageCode>>();
+
+        for &msg_type in invalid_types.iter() {
+            for &msg_code in invalid_codes.iter() {
+                let inval_data = MessageData::new()
+                    .with_message_type(msg_type)
+                    .with_message_code(msg_code);
+
+                let inval_type = MessageData::new()
+                    .with_message_type(msg_type)
+                    .with_message_code(PauseRequest::new().message_code());
+
+                let inval_code = MessageData::new()
+                    .with_message_type(PauseRequest::new().message_type())
+                    .with_message_code(msg_code);
+
+                for stack_data in [inval_data, inval_type, inval_code] {
+                    assert!(PauseRequest::try_from(&stack_data).is_err());
+                    assert!(PauseRequest::try_from(Message::new().with_data(stack_data)).is_err());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
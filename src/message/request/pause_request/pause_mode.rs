@@ -0,0 +1,83 @@
+use core::fmt;
+
+use crate::{Error, MessageType, RequestType, Result};
+
+/// Represents the [RequestType] modes for the [PauseRequest].
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PauseMode {
+    Get,
+    Set,
+}
+
+impl PauseMode {
+    /// Creates a new [PauseMode].
+    pub const fn new() -> Self {
+        Self::Get
+    }
+
+    /// Converts a [RequestType] into a [PauseMode].
+    pub const fn from_request_type(val: RequestType) -> Option<Self> {
+        match val {
+            RequestType::Status => Some(Self::Get),
+            RequestType::SetFeature => Some(Self::Set),
+            _ => None,
+        }
+    }
+
+    /// Converts a [PauseMode] into a [RequestType].
+    pub const fn into_request_type(self) -> RequestType {
+        match self {
+            Self::Get => RequestType::Status,
+            Self::Set => RequestType::SetFeature,
+        }
+    }
+}
+
+impl Default for PauseMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TryFrom<RequestType> for PauseMode {
+    type Error = Error;
+
+    fn try_from(val: RequestType) -> Result<Self> {
+        Self::from_request_type(val).ok_or(Error::InvalidPauseMode(val.into()))
+    }
+}
+
+impl From<PauseMode> for RequestType {
+    fn from(val: PauseMode) -> Self {
+        val.into_request_type()
+    }
+}
+
+impl From<PauseMode> for MessageType {
+    fn from(val: PauseMode) -> Self {
+        MessageType::Request(val.into_request_type())
+    }
+}
+
+impl From<PauseMode> for &'static str {
+    fn from(val: PauseMode) -> Self {
+        match val {
+            PauseMode::Get => "get",
+            PauseMode::Set => "set",
+        }
+    }
+}
+
+impl From<&PauseMode> for &'static str {
+    fn from(val: &PauseMode) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for PauseMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
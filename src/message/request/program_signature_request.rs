@@ -8,8 +8,26 @@ mod mode;
 pub use mode::*;
 
 /// Represents a `ProgramSignature` request message.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{Message, ProgramSignatureRequest};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, type, code, data
+/// let frame = [0x12, 0x09, 0x00, 0x10, 0x00, 0x10, 0x02, 0x00, 0x01];
+///
+/// let req = ProgramSignatureRequest::new();
+///
+/// assert_eq!(ProgramSignatureRequest::try_from(Message::try_from(frame.as_ref())?)?, req);
+/// assert_eq!(Vec::<u8>::from(Message::from(&req)), frame);
+/// # Ok(())
+/// # }
+/// ```
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ProgramSignatureRequest {
     mode: ProgramSignatureMode,
     hash_algorithm: HashAlgorithm,
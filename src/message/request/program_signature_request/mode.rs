@@ -3,6 +3,7 @@ use crate::{Error, MessageType, RequestType, Result};
 /// Represents the [ProgramSignatureRequest](super::ProgramSignatureRequest) request mode.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ProgramSignatureMode {
     /// Get the `Program Signature` hash algorithm.
     Get = 0,
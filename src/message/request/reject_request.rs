@@ -2,9 +2,27 @@ use crate::{
     Error, Message, MessageCode, MessageData, MessageType, RequestCode, RequestType, Result,
 };
 
-/// Represents a `Idle` request message.
+/// Represents a `Reject` request message.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{Message, RejectRequest};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, type, code, data
+/// let frame = [0x12, 0x08, 0x00, 0x10, 0x00, 0x00, 0x15, 0x10];
+///
+/// let req = RejectRequest::new();
+///
+/// assert_eq!(RejectRequest::try_from(Message::try_from(frame.as_ref())?)?, req);
+/// assert_eq!(Vec::<u8>::from(Message::from(&req)), frame);
+/// # Ok(())
+/// # }
+/// ```
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RejectRequest;
 
 impl RejectRequest {
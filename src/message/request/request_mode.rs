@@ -1,6 +1,7 @@
 /// Represents the mode for a [Request](crate::Request).
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RequestMode {
     Get = 0,
     Set = 1,
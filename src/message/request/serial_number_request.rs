@@ -4,8 +4,26 @@ use crate::{
 };
 
 /// Represents a `Serial Number Image` request message.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{Message, SerialNumberRequest};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, type, code, data
+/// let frame = [0x12, 0x09, 0x00, 0x10, 0x00, 0x10, 0x04, 0x00, 0x00];
+///
+/// let req = SerialNumberRequest::new();
+///
+/// assert_eq!(SerialNumberRequest::try_from(Message::try_from(frame.as_ref())?)?, req);
+/// assert_eq!(Vec::<u8>::from(Message::from(&req)), frame);
+/// # Ok(())
+/// # }
+/// ```
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SerialNumberRequest {
     block_number: ImageBlockNumber,
 }
@@ -1,4 +1,5 @@
-use std::fmt;
+use alloc::vec::Vec;
+use core::fmt;
 
 use crate::{
     ConfId, Error, Message, MessageCode, MessageData, MessageType, RequestCode, RequestType,
@@ -10,8 +11,26 @@ mod stack_status_change;
 pub use stack_status_change::*;
 
 /// Represents the additional data in a stack request.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{Message, StackRequest};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, type, code, data
+/// let frame = [0x12, 0x08, 0x00, 0x10, 0x00, 0x00, 0x14, 0x10];
+///
+/// let req = StackRequest::new();
+///
+/// assert_eq!(StackRequest::try_from(Message::try_from(frame.as_ref())?)?, req);
+/// assert_eq!(Vec::<u8>::from(Message::from(&req)), frame);
+/// # Ok(())
+/// # }
+/// ```
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StackRequest {
     stack_box: Option<UnitNumber>,
     status_change: Option<StackStatusChange>,
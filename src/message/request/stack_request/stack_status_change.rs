@@ -1,4 +1,4 @@
-use std::{fmt, mem};
+use core::{fmt, mem};
 
 use crate::{Error, Result};
 
@@ -9,6 +9,7 @@ const RESERVED: u8 = 0xff;
 /// Represents the device status change after completing the collection operation.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StackStatusChange {
     /// Device status changes to `Idle` after collection operation.
     Idle = IDLE,
@@ -119,7 +120,7 @@ mod tests {
         let raw_vals = [IDLE, INHIBIT];
         let expected = [StackStatusChange::Idle, StackStatusChange::Inhibit];
 
-        for (raw, exp) in raw_vals.into_iter().zip(expected.into_iter()) {
+        for (raw, exp) in raw_vals.into_iter().zip(expected) {
             assert_eq!(StackStatusChange::try_from(raw), Ok(exp));
             assert_eq!(StackStatusChange::from_u8(raw), exp);
         }
@@ -0,0 +1,106 @@
+use crate::{
+    AcceptorCollectRequest, BarCodeRequest, BarCodeResponse, CashBoxSizeRequest,
+    CashBoxSizeResponse, CollectRequest, ConditionalVendRequest, ConditionalVendResponse,
+    CurrencyAssignRequest, CurrencyAssignResponse, DenominationDisableRequest,
+    DenominationDisableResponse, DirectionDisableRequest, DirectionDisableResponse,
+    DispenseRequest, DispenseResponse, Error, EventResendIntervalRequest,
+    EventResendIntervalResponse, HoldRequest, HoldResponse, IdleRequest, InhibitRequest,
+    InhibitResponse, InsertNotificationRequest, InsertNotificationResponse, Message,
+    ModelNameRequest, ModelNameResponse, NearFullRequest, NearFullResponse, NoteDataInfoRequest,
+    NoteDataInfoResponse, NoteImageRequest, PauseRequest, PauseResponse, ProgramSignatureRequest,
+    ProgramSignatureResponse, RejectRequest, ResetRequest, Response, Result, SerialNumberRequest,
+    StackRequest, StatusRequest, StatusResponse, TypedResponse, UidRequest, UidResponse,
+    VersionRequest, VersionResponse,
+};
+
+/// Pairs a typed request with the typed response returned by the device.
+///
+/// Polling functions generic over [TypedRequest] return the paired response, so a response can
+/// not be parsed into the wrong type.
+///
+/// Requests without a dedicated response type, and image requests whose response layout depends
+/// on the block number, pair with the generic [Response].
+pub trait TypedRequest: Into<Message> {
+    /// The typed response returned by the device.
+    type Response: TypedResponse + TryFrom<Message, Error = Error>;
+
+    /// Parses the device response [Message] into the paired [Response](Self::Response).
+    fn parse_response(response: Message) -> Result<Self::Response> {
+        Self::Response::try_from(response)
+    }
+}
+
+macro_rules! impl_typed_request {
+    ($($req:ty => $res:ty),+ $(,)?) => {
+        $(
+            impl TypedRequest for $req {
+                type Response = $res;
+            }
+        )+
+    };
+}
+
+impl_typed_request!(
+    AcceptorCollectRequest => Response,
+    BarCodeRequest => BarCodeResponse,
+    CashBoxSizeRequest => CashBoxSizeResponse,
+    CollectRequest => Response,
+    ConditionalVendRequest => ConditionalVendResponse,
+    CurrencyAssignRequest => CurrencyAssignResponse,
+    DenominationDisableRequest => DenominationDisableResponse,
+    DirectionDisableRequest => DirectionDisableResponse,
+    DispenseRequest => DispenseResponse,
+    EventResendIntervalRequest => EventResendIntervalResponse,
+    HoldRequest => HoldResponse,
+    IdleRequest => Response,
+    InhibitRequest => InhibitResponse,
+    InsertNotificationRequest => InsertNotificationResponse,
+    ModelNameRequest => ModelNameResponse,
+    NearFullRequest => NearFullResponse,
+    NoteDataInfoRequest => NoteDataInfoResponse,
+    NoteImageRequest => Response,
+    PauseRequest => PauseResponse,
+    ProgramSignatureRequest => ProgramSignatureResponse,
+    RejectRequest => Response,
+    ResetRequest => Response,
+    SerialNumberRequest => Response,
+    StackRequest => Response,
+    StatusRequest => StatusResponse,
+    UidRequest => UidResponse,
+    VersionRequest => VersionResponse,
+);
+
+/// Polls a [TypedRequest], parsing the device response into the paired response type.
+///
+/// The polling function sends the request [Message], and returns the device response, e.g. a
+/// closure over [poll_request](crate::usb::poll_request).
+pub fn poll_typed<R, F>(request: R, mut poll: F) -> Result<R::Response>
+where
+    R: TypedRequest,
+    F: FnMut(&Message) -> Result<Message>,
+{
+    R::parse_response(poll(&request.into())?)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::mock::MockDevice;
+
+    #[test]
+    fn test_poll_typed() -> Result<()> {
+        let device = MockDevice::new();
+        let poll = |req: &Message| device.handle_request(req);
+
+        let res: InhibitResponse = poll_typed(InhibitRequest::new(), poll)?;
+        assert!(res.is_ack());
+
+        let res = poll_typed(StatusRequest::new(), poll)?;
+        assert_eq!(res.status(), device.status());
+
+        let res = poll_typed(UidRequest::new(), poll)?;
+        assert_eq!(res.uid(), device.uid());
+
+        Ok(())
+    }
+}
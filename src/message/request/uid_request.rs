@@ -3,9 +3,27 @@ use crate::{
     Result,
 };
 
-/// Represents a `Status` request message.
+/// Represents a `UID` request message.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{Message, UidRequest};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, type, code, data
+/// let frame = [0x12, 0x09, 0x00, 0x10, 0x00, 0x20, 0x01, 0x00, 0x01];
+///
+/// let req = UidRequest::new_set(1);
+///
+/// assert_eq!(UidRequest::try_from(Message::try_from(frame.as_ref())?)?, req);
+/// assert_eq!(Vec::<u8>::from(Message::from(&req)), frame);
+/// # Ok(())
+/// # }
+/// ```
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UidRequest {
     uid: u8,
     mode: RequestMode,
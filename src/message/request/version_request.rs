@@ -3,8 +3,26 @@ use crate::{
 };
 
 /// Represents a `Version` request message.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{Message, VersionRequest};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, type, code, data
+/// let frame = [0x12, 0x08, 0x00, 0x10, 0x00, 0x10, 0x03, 0x00];
+///
+/// let req = VersionRequest::new();
+///
+/// assert_eq!(VersionRequest::try_from(Message::try_from(frame.as_ref())?)?, req);
+/// assert_eq!(Vec::<u8>::from(Message::from(&req)), frame);
+/// # Ok(())
+/// # }
+/// ```
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VersionRequest;
 
 impl VersionRequest {
@@ -1,33 +1,109 @@
-use std::fmt;
+use alloc::vec::Vec;
+use core::fmt;
 
 use crate::{Error, Message, Result};
 
+mod bar_code_response;
+mod cash_box_size_response;
+mod conditional_vend_response;
 mod currency_assign_response;
 mod denomination_disable_response;
 mod direction_disable_response;
+mod dispense_response;
+mod event_resend_interval_response;
+mod hold_response;
+mod inhibit_response;
+mod insert_notification_response;
 mod model_name_response;
 mod near_full_response;
+mod note_data_info_response;
 mod note_image_response;
+mod pause_response;
 mod program_signature_response;
 mod response_code;
+mod response_len;
 mod serial_number_response;
 mod status_response;
 mod uid_response;
 mod version_response;
 
+pub use bar_code_response::*;
+pub use cash_box_size_response::*;
+pub use conditional_vend_response::*;
 pub use currency_assign_response::*;
 pub use denomination_disable_response::*;
 pub use direction_disable_response::*;
+pub use dispense_response::*;
+pub use event_resend_interval_response::*;
+pub use hold_response::*;
+pub use inhibit_response::*;
+pub use insert_notification_response::*;
 pub use model_name_response::*;
 pub use near_full_response::*;
+pub use note_data_info_response::*;
 pub use note_image_response::*;
+pub use pause_response::*;
 pub use program_signature_response::*;
 pub use response_code::*;
+pub use response_len::*;
 pub use serial_number_response::*;
 pub use status_response::*;
 pub use uid_response::*;
 pub use version_response::*;
 
+/// Common accessors for typed responses.
+///
+/// Every typed response keeps the [ResponseCode] returned by the device alongside its payload,
+/// so a `NAK` (or other failure code) is never silently discarded during conversion.
+pub trait TypedResponse {
+    /// Gets the [ResponseCode] returned by the device.
+    fn response_code(&self) -> ResponseCode;
+
+    /// Gets whether the device acknowledged the request.
+    fn is_ack(&self) -> bool {
+        self.response_code() == ResponseCode::Ack
+    }
+}
+
+macro_rules! impl_typed_response {
+    ($($res:ty),+ $(,)?) => {
+        $(
+            impl TypedResponse for $res {
+                fn response_code(&self) -> ResponseCode {
+                    self.code()
+                }
+            }
+        )+
+    };
+}
+
+impl_typed_response!(
+    Response,
+    BarCodeResponse,
+    CashBoxSizeResponse,
+    ConditionalVendResponse,
+    CurrencyAssignResponse,
+    DenominationDisableResponse,
+    DirectionDisableResponse,
+    DispenseResponse,
+    EventResendIntervalResponse,
+    HoldResponse,
+    InhibitResponse,
+    InsertNotificationResponse,
+    ModelNameResponse,
+    NearFullResponse,
+    NoteDataInfoResponse,
+    NoteImageBlockResponse,
+    NoteImageSizeResponse,
+    PauseResponse,
+    ProgramSignatureResponse,
+    SerialNumberBlockResponse,
+    SerialNumberSizeResponse,
+    StatusResponse,
+    UidResponse,
+    VersionResponse,
+);
+
 /// Represents the generic response format for JCM host-device communication.
 ///
 /// Response data is encoded in the [Message](crate::Message) format as additional data in
@@ -40,6 +116,7 @@ pub use version_response::*;
 /// Size (byte) | 1             | Variable
 #[repr(C)]
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Response {
     code: ResponseCode,
     additional: Vec<u8>,
@@ -100,8 +177,8 @@ impl Response {
         self.code.is_empty()
     }
 
-    /// Writes the [Message] to the provided byte buffer.
-    pub fn to_bytes(&self, buf: &mut [u8]) -> Result<()> {
+    /// Writes the [Response] to the provided byte buffer, returning the number of bytes written.
+    pub fn to_bytes(&self, buf: &mut [u8]) -> Result<usize> {
         let len = self.len();
         let buf_len = buf.len();
 
@@ -117,9 +194,16 @@ impl Response {
                 .zip(msg_iter)
                 .for_each(|(dst, src)| *dst = src);
 
-            Ok(())
+            Ok(len)
         }
     }
+
+    /// Parses a [Response] from the provided byte buffer.
+    ///
+    /// Equivalent to the [TryFrom] conversion from a byte slice.
+    pub fn from_bytes(buf: &[u8]) -> Result<Self> {
+        Self::try_from(buf)
+    }
 }
 
 impl TryFrom<&[u8]> for Response {
@@ -196,7 +280,7 @@ mod tests {
         assert_eq!(Response::try_from(msg), Ok(exp.clone()));
 
         let mut out = [0u8];
-        assert_eq!(exp.to_bytes(out.as_mut()), Ok(()));
+        assert_eq!(exp.to_bytes(out.as_mut()), Ok(raw.len()));
         assert_eq!(out, raw);
     }
 
@@ -213,7 +297,40 @@ mod tests {
         assert_eq!(Response::try_from(msg), Ok(exp.clone()));
 
         let mut out = [0u8; 3];
-        assert_eq!(exp.to_bytes(out.as_mut()), Ok(()));
+        assert_eq!(exp.to_bytes(out.as_mut()), Ok(raw.len()));
         assert_eq!(out, raw);
     }
+
+    #[test]
+    fn test_typed_response_code() -> Result<()> {
+        use crate::{MessageCode, RequestCode};
+
+        let nak = |code: RequestCode| {
+            Message::new().with_data(
+                MessageData::new()
+                    .with_message_code(MessageCode::Request(code))
+                    .with_additional(&[ResponseCode::Nak.into()]),
+            )
+        };
+
+        let res = NearFullResponse::try_from(&nak(RequestCode::NearFull))?;
+        assert_eq!(res.response_code(), ResponseCode::Nak);
+        assert!(!res.is_ack());
+
+        let res = DenominationDisableResponse::try_from(&nak(RequestCode::DenominationDisable))?;
+        assert_eq!(res.response_code(), ResponseCode::Nak);
+
+        let res = DirectionDisableResponse::try_from(&nak(RequestCode::DirectionDisable))?;
+        assert_eq!(res.response_code(), ResponseCode::Nak);
+
+        let res = UidResponse::try_from(&nak(RequestCode::Uid))?;
+        assert_eq!(res.response_code(), ResponseCode::Nak);
+
+        let res = ModelNameResponse::try_from(&nak(RequestCode::ModelName))?;
+        assert_eq!(res.response_code(), ResponseCode::Nak);
+
+        assert!(Response::new().with_code(ResponseCode::Ack).is_ack());
+
+        Ok(())
+    }
 }
@@ -0,0 +1,239 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{BarCodeSettings, Error, Message, RequestCode, Response, ResponseCode, Result};
+
+/// Represents the [Response] to a [BarCodeRequest](crate::BarCodeRequest).
+///
+/// [BarCodeSettings] are only reported in response to [Get](crate::BarCodeMode::Get) requests.
+#[repr(C)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BarCodeResponse {
+    code: ResponseCode,
+    settings: Option<BarCodeSettings>,
+}
+
+impl BarCodeResponse {
+    /// Creates a new [BarCodeResponse].
+    pub const fn new() -> Self {
+        Self {
+            code: ResponseCode::new(),
+            settings: None,
+        }
+    }
+
+    /// Gets the [ResponseCode] for the [BarCodeResponse].
+    pub const fn code(&self) -> ResponseCode {
+        self.code
+    }
+
+    /// Sets the [ResponseCode] for the [BarCodeResponse].
+    pub fn set_code(&mut self, code: ResponseCode) {
+        self.code = code;
+    }
+
+    /// Builder function that sets the [ResponseCode] for the [BarCodeResponse].
+    pub const fn with_code(self, code: ResponseCode) -> Self {
+        Self {
+            code,
+            settings: self.settings,
+        }
+    }
+
+    /// Gets the [BarCodeSettings] for the [BarCodeResponse].
+    pub const fn settings(&self) -> Option<BarCodeSettings> {
+        self.settings
+    }
+
+    /// Sets the [BarCodeSettings] for the [BarCodeResponse].
+    pub fn set_settings(&mut self, settings: BarCodeSettings) {
+        self.settings = Some(settings);
+    }
+
+    /// Unsets the [BarCodeSettings] for the [BarCodeResponse].
+    pub fn unset_settings(&mut self) -> Option<BarCodeSettings> {
+        self.settings.take()
+    }
+
+    /// Builder function that sets the [BarCodeSettings] for the [BarCodeResponse].
+    pub const fn with_settings(self, settings: BarCodeSettings) -> Self {
+        Self {
+            code: self.code,
+            settings: Some(settings),
+        }
+    }
+
+    /// Gets the length of the [BarCodeResponse].
+    pub const fn len() -> usize {
+        ResponseCode::len() + BarCodeSettings::len()
+    }
+
+    /// Gets whether the [BarCodeResponse] is empty.
+    pub fn is_empty(&self) -> bool {
+        self.code.is_empty() && self.settings.is_none()
+    }
+
+    /// Gets an iterator over [BarCodeResponse] bytes.
+    pub fn into_iter_bytes(self) -> impl Iterator<Item = u8> {
+        let mut code_iter = [self.code.into()].into_iter();
+        let mut data_iter = self.settings.map(|s| s.into_bytes().into_iter());
+
+        core::iter::from_fn(move || match (code_iter.next(), data_iter.as_mut()) {
+            (Some(c), _) => Some(c),
+            (None, Some(d)) => d.next(),
+            (None, None) => None,
+        })
+    }
+
+    /// Converts a [BarCodeResponse] into a byte vector.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.into_iter_bytes().collect()
+    }
+
+    /// Converts a byte buffer into a [BarCodeResponse].
+    pub fn from_bytes(buf: &[u8]) -> Result<Self> {
+        Ok(Self {
+            code: buf
+                .first()
+                .copied()
+                .ok_or(Error::InvalidResponseLen((0, 1)))?
+                .try_into()?,
+            settings: match buf
+                .get(1..1 + BarCodeSettings::len())
+                .map(BarCodeSettings::from_bytes)
+            {
+                Some(d) => Some(d?),
+                None => None,
+            },
+        })
+    }
+}
+
+impl Default for BarCodeResponse {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&Response> for BarCodeResponse {
+    fn from(val: &Response) -> Self {
+        Self {
+            code: val.code(),
+            settings: val.additional().try_into().ok(),
+        }
+    }
+}
+
+impl From<Response> for BarCodeResponse {
+    fn from(val: Response) -> Self {
+        (&val).into()
+    }
+}
+
+impl From<BarCodeResponse> for Response {
+    fn from(val: BarCodeResponse) -> Self {
+        Self {
+            code: val.code,
+            additional: val
+                .settings
+                .map(|d| d.into_bytes().to_vec())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl From<&BarCodeResponse> for Response {
+    fn from(val: &BarCodeResponse) -> Self {
+        Self {
+            code: val.code,
+            additional: val
+                .settings
+                .map(|d| d.into_bytes().to_vec())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl TryFrom<Message> for BarCodeResponse {
+    type Error = Error;
+
+    fn try_from(val: Message) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
+impl TryFrom<&Message> for BarCodeResponse {
+    type Error = Error;
+
+    fn try_from(val: &Message) -> Result<Self> {
+        match val.data.message_code().request_code()? {
+            RequestCode::BarCode => Ok(Response::try_from(val)?.into()),
+            code => Err(Error::InvalidRequestCode(code.into())),
+        }
+    }
+}
+
+impl fmt::Display for BarCodeResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""code": {}"#, self.code)?;
+        if let Some(settings) = self.settings.as_ref() {
+            write!(f, r#", "settings": {settings}"#)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BarCodeMode;
+
+    #[test]
+    fn test_bar_code_response() {
+        for mode in [BarCodeMode::Get, BarCodeMode::Set] {
+            let raw = match mode {
+                BarCodeMode::Get => vec![ResponseCode::Ack as u8, 0x01, 18],
+                BarCodeMode::Set => vec![ResponseCode::Ack as u8],
+            };
+
+            let exp = match mode {
+                BarCodeMode::Get => BarCodeResponse::new()
+                    .with_code(ResponseCode::Ack)
+                    .with_settings(BarCodeSettings::new()),
+                BarCodeMode::Set => BarCodeResponse::new().with_code(ResponseCode::Ack),
+            };
+
+            let res = match mode {
+                BarCodeMode::Get => Response::new()
+                    .with_code(ResponseCode::Ack)
+                    .with_additional(raw[1..].as_ref()),
+                BarCodeMode::Set => Response::new().with_code(ResponseCode::Ack),
+            };
+
+            assert_eq!(BarCodeResponse::from_bytes(raw.as_ref()).as_ref(), Ok(&exp),);
+            assert_eq!(&BarCodeResponse::from(&res), &exp);
+            assert_eq!(Response::from(&exp), res);
+
+            let out = exp.into_bytes();
+
+            assert_eq!(out, raw);
+        }
+
+        assert_eq!(
+            BarCodeResponse::new()
+                .with_code(ResponseCode::Ack)
+                .with_settings(BarCodeSettings::new())
+                .to_string(),
+            r#"{"code": "affirmative response", "settings": {"bar_code_type": "interleaved 2 of 5", "digits": 18}}"#
+        );
+    }
+
+    #[test]
+    fn test_bar_code_response_invalid() {
+        assert!(BarCodeResponse::from_bytes(&[]).is_err());
+        assert!(BarCodeResponse::from_bytes([ResponseCode::Reserved as u8, 0].as_ref()).is_err());
+        assert!(BarCodeResponse::from_bytes([ResponseCode::Ack as u8, 0x01, 7].as_ref()).is_err());
+    }
+}
@@ -0,0 +1,282 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Error, Message, RequestCode, Response, ResponseCode, Result};
+
+/// Represents the byte length of the cash box capacity, and the stored note count.
+pub const CASH_BOX_SIZE_LEN: usize = 2;
+
+/// Represents the [Response] to a [CashBoxSizeRequest](crate::CashBoxSizeRequest).
+///
+/// ## Format
+///
+/// Field  | Response Code | Capacity | Count (optional)
+/// -------|---------------|----------|-----------------
+/// Length | 1 byte        | 2 bytes  | 2 bytes
+///
+/// Both the capacity and count are little-endian numbers of notes. Devices that do not track the
+/// cash box contents only report the capacity.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{CashBoxSizeResponse, Message, ResponseCode};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, request type, request code, response code, data
+/// let frame = [0x12, 0x0d, 0x00, 0x10, 0x00, 0x10, 0x24, 0x10, 0x06, 0xf4, 0x01, 0x2a, 0x00];
+///
+/// let res = CashBoxSizeResponse::try_from(Message::try_from(frame.as_ref())?)?;
+///
+/// assert_eq!(res.code(), ResponseCode::Ack);
+/// assert_eq!(res.capacity(), Some(500));
+/// assert_eq!(res.count(), Some(42));
+/// assert_eq!(res.remaining(), Some(458));
+/// # Ok(())
+/// # }
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CashBoxSizeResponse {
+    code: ResponseCode,
+    capacity: Option<u16>,
+    count: Option<u16>,
+}
+
+impl CashBoxSizeResponse {
+    /// Creates a new [CashBoxSizeResponse].
+    pub const fn new() -> Self {
+        Self {
+            code: ResponseCode::new(),
+            capacity: None,
+            count: None,
+        }
+    }
+
+    /// Gets the [ResponseCode] for the [CashBoxSizeResponse].
+    pub const fn code(&self) -> ResponseCode {
+        self.code
+    }
+
+    /// Sets the [ResponseCode] for the [CashBoxSizeResponse].
+    pub fn set_code(&mut self, code: ResponseCode) {
+        self.code = code;
+    }
+
+    /// Builder function that sets the [ResponseCode] for the [CashBoxSizeResponse].
+    pub const fn with_code(self, code: ResponseCode) -> Self {
+        Self {
+            code,
+            capacity: self.capacity,
+            count: self.count,
+        }
+    }
+
+    /// Gets the number of notes the cash box holds, if reported.
+    pub const fn capacity(&self) -> Option<u16> {
+        self.capacity
+    }
+
+    /// Sets the number of notes the cash box holds.
+    pub fn set_capacity(&mut self, capacity: u16) {
+        self.capacity = Some(capacity);
+    }
+
+    /// Builder function that sets the number of notes the cash box holds.
+    pub const fn with_capacity(self, capacity: u16) -> Self {
+        Self {
+            code: self.code,
+            capacity: Some(capacity),
+            count: self.count,
+        }
+    }
+
+    /// Gets the number of notes currently in the cash box, if reported.
+    pub const fn count(&self) -> Option<u16> {
+        self.count
+    }
+
+    /// Sets the number of notes currently in the cash box.
+    ///
+    /// **NOTE**: the count is only encoded after the capacity.
+    pub fn set_count(&mut self, count: u16) {
+        self.count = Some(count);
+    }
+
+    /// Builder function that sets the number of notes currently in the cash box.
+    ///
+    /// **NOTE**: the count is only encoded after the capacity.
+    pub const fn with_count(self, count: u16) -> Self {
+        Self {
+            code: self.code,
+            capacity: self.capacity,
+            count: Some(count),
+        }
+    }
+
+    /// Gets the number of notes the cash box can still hold, if both capacity and count are
+    /// reported.
+    pub fn remaining(&self) -> Option<u16> {
+        Some(self.capacity?.saturating_sub(self.count?))
+    }
+
+    /// Gets the minimum length of an `ACK` [CashBoxSizeResponse].
+    pub const fn meta_len() -> usize {
+        ResponseCode::len() + CASH_BOX_SIZE_LEN
+    }
+
+    /// Gets whether the [CashBoxSizeResponse] is empty.
+    pub fn is_empty(&self) -> bool {
+        self.code.is_empty() && self.capacity.is_none() && self.count.is_none()
+    }
+
+    /// Converts a byte buffer into a [CashBoxSizeResponse].
+    pub fn from_bytes(buf: &[u8]) -> Result<Self> {
+        let code = buf
+            .first()
+            .copied()
+            .ok_or(Error::InvalidResponseLen((0, 1)))?
+            .try_into()?;
+
+        Ok(Self::from_parts(code, &buf[1..]))
+    }
+
+    /// Converts the [CashBoxSizeResponse] into a byte vector.
+    pub fn into_bytes(self) -> Vec<u8> {
+        [self.code.into()]
+            .into_iter()
+            .chain(self.additional())
+            .collect()
+    }
+
+    fn from_parts(code: ResponseCode, additional: &[u8]) -> Self {
+        let read = |offset: usize| {
+            additional
+                .get(offset..offset + CASH_BOX_SIZE_LEN)
+                .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        };
+
+        Self {
+            code,
+            capacity: read(0),
+            count: read(CASH_BOX_SIZE_LEN),
+        }
+    }
+
+    fn additional(&self) -> Vec<u8> {
+        self.capacity
+            .into_iter()
+            .chain(self.capacity.and(self.count))
+            .flat_map(u16::to_le_bytes)
+            .collect()
+    }
+}
+
+impl Default for CashBoxSizeResponse {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&Response> for CashBoxSizeResponse {
+    fn from(val: &Response) -> Self {
+        Self::from_parts(val.code(), val.additional())
+    }
+}
+
+impl From<Response> for CashBoxSizeResponse {
+    fn from(val: Response) -> Self {
+        (&val).into()
+    }
+}
+
+impl From<CashBoxSizeResponse> for Response {
+    fn from(val: CashBoxSizeResponse) -> Self {
+        (&val).into()
+    }
+}
+
+impl From<&CashBoxSizeResponse> for Response {
+    fn from(val: &CashBoxSizeResponse) -> Self {
+        Self {
+            code: val.code,
+            additional: val.additional(),
+        }
+    }
+}
+
+impl TryFrom<Message> for CashBoxSizeResponse {
+    type Error = Error;
+
+    fn try_from(val: Message) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
+impl TryFrom<&Message> for CashBoxSizeResponse {
+    type Error = Error;
+
+    fn try_from(val: &Message) -> Result<Self> {
+        match val.data.message_code().request_code()? {
+            RequestCode::CashBoxSize => Ok(Response::try_from(val)?.into()),
+            code => Err(Error::InvalidRequestCode(code.into())),
+        }
+    }
+}
+
+impl fmt::Display for CashBoxSizeResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""code": {}"#, self.code)?;
+        if let Some(capacity) = self.capacity {
+            write!(f, r#", "capacity": {capacity}"#)?;
+        }
+        if let Some(count) = self.count {
+            write!(f, r#", "count": {count}"#)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cash_box_size_response() -> Result<()> {
+        let exp = CashBoxSizeResponse::new()
+            .with_code(ResponseCode::Ack)
+            .with_capacity(1000)
+            .with_count(250);
+        let raw = [ResponseCode::Ack.into(), 0xe8, 0x03, 0xfa, 0x00];
+
+        assert_eq!(CashBoxSizeResponse::from_bytes(raw.as_ref())?, exp);
+        assert_eq!(exp.into_bytes(), raw);
+        assert_eq!(CashBoxSizeResponse::from(Response::from(exp)), exp);
+        assert_eq!(exp.remaining(), Some(750));
+        assert_eq!(
+            exp.to_string(),
+            r#"{"code": "affirmative response", "capacity": 1000, "count": 250}"#
+        );
+
+        let capacity_only = CashBoxSizeResponse::from_bytes(&raw[..3])?;
+        assert_eq!(capacity_only.capacity(), Some(1000));
+        assert_eq!(capacity_only.count(), None);
+        assert_eq!(capacity_only.remaining(), None);
+
+        let nak = CashBoxSizeResponse::from_bytes(&[u8::from(ResponseCode::Nak)])?;
+        assert_eq!(nak.capacity(), None);
+        assert_eq!(nak.into_bytes(), [u8::from(ResponseCode::Nak)]);
+
+        assert!(CashBoxSizeResponse::from_bytes(&[]).is_err());
+
+        let status = Message::new().with_data(
+            crate::MessageData::from(crate::StatusRequest::new())
+                .with_additional(&[u8::from(ResponseCode::Ack)]),
+        );
+        assert!(CashBoxSizeResponse::try_from(&status).is_err());
+
+        Ok(())
+    }
+}
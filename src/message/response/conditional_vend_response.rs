@@ -0,0 +1,265 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{ConditionalVendSettings, Error, Message, RequestCode, Response, ResponseCode, Result};
+
+/// Represents the [Response] to a [ConditionalVendRequest](crate::ConditionalVendRequest).
+///
+/// [ConditionalVendSettings] are only reported in response to [Get](crate::ConditionalVendMode::Get) requests.
+#[repr(C)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConditionalVendResponse {
+    code: ResponseCode,
+    settings: Option<ConditionalVendSettings>,
+}
+
+impl ConditionalVendResponse {
+    /// Creates a new [ConditionalVendResponse].
+    pub const fn new() -> Self {
+        Self {
+            code: ResponseCode::new(),
+            settings: None,
+        }
+    }
+
+    /// Gets the [ResponseCode] for the [ConditionalVendResponse].
+    pub const fn code(&self) -> ResponseCode {
+        self.code
+    }
+
+    /// Sets the [ResponseCode] for the [ConditionalVendResponse].
+    pub fn set_code(&mut self, code: ResponseCode) {
+        self.code = code;
+    }
+
+    /// Builder function that sets the [ResponseCode] for the [ConditionalVendResponse].
+    pub const fn with_code(self, code: ResponseCode) -> Self {
+        Self {
+            code,
+            settings: self.settings,
+        }
+    }
+
+    /// Gets the [ConditionalVendSettings] for the [ConditionalVendResponse].
+    pub const fn settings(&self) -> Option<ConditionalVendSettings> {
+        self.settings
+    }
+
+    /// Sets the [ConditionalVendSettings] for the [ConditionalVendResponse].
+    pub fn set_settings(&mut self, settings: ConditionalVendSettings) {
+        self.settings = Some(settings);
+    }
+
+    /// Unsets the [ConditionalVendSettings] for the [ConditionalVendResponse].
+    pub fn unset_settings(&mut self) -> Option<ConditionalVendSettings> {
+        self.settings.take()
+    }
+
+    /// Builder function that sets the [ConditionalVendSettings] for the [ConditionalVendResponse].
+    pub const fn with_settings(self, settings: ConditionalVendSettings) -> Self {
+        Self {
+            code: self.code,
+            settings: Some(settings),
+        }
+    }
+
+    /// Gets the length of the [ConditionalVendResponse].
+    pub const fn len() -> usize {
+        ResponseCode::len() + ConditionalVendSettings::len()
+    }
+
+    /// Gets whether the [ConditionalVendResponse] is empty.
+    pub fn is_empty(&self) -> bool {
+        self.code.is_empty() && self.settings.is_none()
+    }
+
+    /// Gets an iterator over [ConditionalVendResponse] bytes.
+    pub fn into_iter_bytes(self) -> impl Iterator<Item = u8> {
+        let mut code_iter = [self.code.into()].into_iter();
+        let mut data_iter = self.settings.map(|s| s.into_bytes().into_iter());
+
+        core::iter::from_fn(move || match (code_iter.next(), data_iter.as_mut()) {
+            (Some(c), _) => Some(c),
+            (None, Some(d)) => d.next(),
+            (None, None) => None,
+        })
+    }
+
+    /// Converts a [ConditionalVendResponse] into a byte vector.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.into_iter_bytes().collect()
+    }
+
+    /// Converts a byte buffer into a [ConditionalVendResponse].
+    pub fn from_bytes(buf: &[u8]) -> Result<Self> {
+        Ok(Self {
+            code: buf
+                .first()
+                .copied()
+                .ok_or(Error::InvalidResponseLen((0, 1)))?
+                .try_into()?,
+            settings: match buf
+                .get(1..1 + ConditionalVendSettings::len())
+                .map(ConditionalVendSettings::from_bytes)
+            {
+                Some(d) => Some(d?),
+                None => None,
+            },
+        })
+    }
+}
+
+impl Default for ConditionalVendResponse {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&Response> for ConditionalVendResponse {
+    fn from(val: &Response) -> Self {
+        Self {
+            code: val.code(),
+            settings: val.additional().try_into().ok(),
+        }
+    }
+}
+
+impl From<Response> for ConditionalVendResponse {
+    fn from(val: Response) -> Self {
+        (&val).into()
+    }
+}
+
+impl From<ConditionalVendResponse> for Response {
+    fn from(val: ConditionalVendResponse) -> Self {
+        Self {
+            code: val.code,
+            additional: val
+                .settings
+                .map(|d| d.into_bytes().to_vec())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl From<&ConditionalVendResponse> for Response {
+    fn from(val: &ConditionalVendResponse) -> Self {
+        Self {
+            code: val.code,
+            additional: val
+                .settings
+                .map(|d| d.into_bytes().to_vec())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl TryFrom<Message> for ConditionalVendResponse {
+    type Error = Error;
+
+    fn try_from(val: Message) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
+impl TryFrom<&Message> for ConditionalVendResponse {
+    type Error = Error;
+
+    fn try_from(val: &Message) -> Result<Self> {
+        match val.data.message_code().request_code()? {
+            RequestCode::ConditionalVend => Ok(Response::try_from(val)?.into()),
+            code => Err(Error::InvalidRequestCode(code.into())),
+        }
+    }
+}
+
+impl fmt::Display for ConditionalVendResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""code": {}"#, self.code)?;
+        if let Some(settings) = self.settings.as_ref() {
+            write!(f, r#", "settings": {settings}"#)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConditionalVendMode, Denomination};
+
+    fn settings() -> ConditionalVendSettings {
+        ConditionalVendSettings::create(
+            true,
+            Denomination::from_value(5),
+            Denomination::from_value(50),
+        )
+        .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_conditional_vend_response() {
+        for mode in [ConditionalVendMode::Get, ConditionalVendMode::Set] {
+            let raw = match mode {
+                ConditionalVendMode::Get => {
+                    vec![ResponseCode::Ack as u8, 0x01, 0x05, 0x00, 0x32, 0x00]
+                }
+                ConditionalVendMode::Set => vec![ResponseCode::Ack as u8],
+            };
+
+            let exp = match mode {
+                ConditionalVendMode::Get => ConditionalVendResponse::new()
+                    .with_code(ResponseCode::Ack)
+                    .with_settings(settings()),
+                ConditionalVendMode::Set => {
+                    ConditionalVendResponse::new().with_code(ResponseCode::Ack)
+                }
+            };
+
+            let res = match mode {
+                ConditionalVendMode::Get => Response::new()
+                    .with_code(ResponseCode::Ack)
+                    .with_additional(raw[1..].as_ref()),
+                ConditionalVendMode::Set => Response::new().with_code(ResponseCode::Ack),
+            };
+
+            assert_eq!(
+                ConditionalVendResponse::from_bytes(raw.as_ref()).as_ref(),
+                Ok(&exp),
+            );
+            assert_eq!(&ConditionalVendResponse::from(&res), &exp);
+            assert_eq!(Response::from(&exp), res);
+
+            let out = exp.into_bytes();
+
+            assert_eq!(out, raw);
+        }
+
+        assert_eq!(
+            ConditionalVendResponse::new()
+                .with_code(ResponseCode::Ack)
+                .with_settings(settings())
+                .to_string(),
+            format!(
+                r#"{{"code": "affirmative response", "settings": {{"enabled": true, "lower": {}, "upper": {}}}}}"#,
+                Denomination::from_value(5),
+                Denomination::from_value(50),
+            )
+        );
+    }
+
+    #[test]
+    fn test_conditional_vend_response_invalid() {
+        assert!(ConditionalVendResponse::from_bytes(&[]).is_err());
+        assert!(
+            ConditionalVendResponse::from_bytes([ResponseCode::Reserved as u8, 0].as_ref())
+                .is_err()
+        );
+        assert!(ConditionalVendResponse::from_bytes(
+            [ResponseCode::Ack as u8, 0x01, 0x32, 0x00, 0x05, 0x00].as_ref()
+        )
+        .is_err());
+    }
+}
@@ -1,6 +1,7 @@
-use std::fmt;
+use alloc::string::String;
+use core::fmt;
 
-use crate::{Error, Message, Response, ResponseCode, Result};
+use crate::{DenominationTable, Error, Message, Response, ResponseCode, Result};
 
 mod currency_assign;
 
@@ -9,6 +10,7 @@ pub use currency_assign::*;
 /// Represents the [Response] to a UID request [Message](crate::Message).
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CurrencyAssignResponse {
     code: ResponseCode,
     currency_assign: CurrencyAssignList,
@@ -109,6 +111,13 @@ impl CurrencyAssignResponse {
             }),
         }
     }
+
+    /// Exports the [CurrencyAssign] items as CSV text, including a header row.
+    ///
+    /// The output can be edited, and loaded with [DenominationTable::from_csv].
+    pub fn to_csv(&self) -> String {
+        DenominationTable::from(self).to_csv()
+    }
 }
 
 impl Default for CurrencyAssignResponse {
@@ -1,4 +1,5 @@
-use std::fmt;
+use alloc::vec::Vec;
+use core::fmt;
 
 use crate::{Currency, Error, Result};
 
@@ -8,6 +9,7 @@ pub const CURRENCY_ASSIGN_LEN: usize = 6;
 /// [CurrencyAssignResponse](crate::CurrencyAssignResponse) messages.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CurrencyAssign {
     bit: u8,
     currency: Currency,
@@ -138,6 +140,7 @@ impl fmt::Display for CurrencyAssign {
 /// Represents a list of [CurrencyAsssign].
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CurrencyAssignList(Vec<CurrencyAssign>);
 
 impl CurrencyAssignList {
@@ -1,4 +1,5 @@
-use std::fmt;
+use alloc::vec::Vec;
+use core::fmt;
 
 use crate::{
     DenominationDisable, DenominationDisableList, Error, Message, RequestCode, Response,
@@ -8,6 +9,7 @@ use crate::{
 /// Represents the [Response] to a [DenominationDisableRequest](crate::DenominationDisableRequest).
 #[repr(C)]
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DenominationDisableResponse {
     code: ResponseCode,
     denoms: DenominationDisableList,
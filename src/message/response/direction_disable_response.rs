@@ -1,10 +1,29 @@
-use std::fmt;
+use alloc::vec::Vec;
+use core::fmt;
 
 use crate::{Error, InhibitDirection, Message, RequestCode, Response, ResponseCode, Result};
 
 /// Represents the [Response] to a [DirectionDisableRequest](crate::DirectionDisableRequest).
+///
+/// # Example
+///
+/// ```
+/// use jcm::{DirectionDisableResponse, InhibitDirection, Message, ResponseCode};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, request type, request code, response code, data
+/// let frame = [0x12, 0x0a, 0x00, 0x10, 0x00, 0x10, 0x22, 0x10, 0x06, 0x05];
+///
+/// let res = DirectionDisableResponse::try_from(Message::try_from(frame.as_ref())?)?;
+///
+/// assert_eq!(res.code(), ResponseCode::Ack);
+/// assert_eq!(res.directions(), InhibitDirection::create(0b0101));
+/// # Ok(())
+/// # }
+/// ```
 #[repr(C)]
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DirectionDisableResponse {
     code: ResponseCode,
     dirs: InhibitDirection,
@@ -0,0 +1,169 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Error, Message, Response, ResponseCode, Result};
+
+/// Represents the [Response] to a [DispenseRequest](crate::DispenseRequest).
+///
+/// Devices may report the number of notes they will pay out, e.g. fewer than requested when the
+/// recycler box runs low.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{DispenseResponse, Message, ResponseCode};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, request type, request code, response code, data
+/// let frame = [0x12, 0x0a, 0x00, 0x11, 0x00, 0x00, 0x18, 0x20, 0x06, 0x02];
+///
+/// let res = DispenseResponse::try_from(Message::try_from(frame.as_ref())?)?;
+///
+/// assert_eq!(res.code(), ResponseCode::Ack);
+/// assert_eq!(res.count(), Some(2));
+/// # Ok(())
+/// # }
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DispenseResponse {
+    code: ResponseCode,
+    count: Option<u8>,
+}
+
+impl DispenseResponse {
+    /// Creates a new [DispenseResponse].
+    pub const fn new() -> Self {
+        Self {
+            code: ResponseCode::new(),
+            count: None,
+        }
+    }
+
+    /// Gets the [ResponseCode] for the [DispenseResponse].
+    pub const fn code(&self) -> ResponseCode {
+        self.code
+    }
+
+    /// Sets the [ResponseCode] for the [DispenseResponse].
+    pub fn set_code(&mut self, code: ResponseCode) {
+        self.code = code;
+    }
+
+    /// Builder function that sets the [ResponseCode] for the [DispenseResponse].
+    pub fn with_code(mut self, code: ResponseCode) -> Self {
+        self.set_code(code);
+        self
+    }
+
+    /// Gets the number of notes the device will pay out, if reported.
+    pub const fn count(&self) -> Option<u8> {
+        self.count
+    }
+
+    /// Sets the number of notes the device will pay out.
+    pub fn set_count(&mut self, count: u8) {
+        self.count = Some(count);
+    }
+
+    /// Builder function that sets the number of notes the device will pay out.
+    pub fn with_count(mut self, count: u8) -> Self {
+        self.set_count(count);
+        self
+    }
+
+    /// Converts the [DispenseResponse] into a byte vector.
+    pub fn into_bytes(self) -> Vec<u8> {
+        [self.code.into()].into_iter().chain(self.count).collect()
+    }
+}
+
+impl Default for DispenseResponse {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&Response> for DispenseResponse {
+    fn from(val: &Response) -> Self {
+        Self {
+            code: val.code(),
+            count: val.additional().first().copied(),
+        }
+    }
+}
+
+impl From<Response> for DispenseResponse {
+    fn from(val: Response) -> Self {
+        (&val).into()
+    }
+}
+
+impl From<DispenseResponse> for Response {
+    fn from(val: DispenseResponse) -> Self {
+        Self {
+            code: val.code,
+            additional: val.count.into_iter().collect(),
+        }
+    }
+}
+
+impl From<&DispenseResponse> for Response {
+    fn from(val: &DispenseResponse) -> Self {
+        (*val).into()
+    }
+}
+
+impl TryFrom<&Message> for DispenseResponse {
+    type Error = Error;
+
+    fn try_from(val: &Message) -> Result<Self> {
+        Ok(Response::try_from(val)?.into())
+    }
+}
+
+impl TryFrom<Message> for DispenseResponse {
+    type Error = Error;
+
+    fn try_from(val: Message) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
+impl fmt::Display for DispenseResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""code": {}, "#, self.code)?;
+        match self.count {
+            Some(count) => write!(f, r#""count": {count}"#)?,
+            None => write!(f, r#""count": null"#)?,
+        }
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dispense_response() {
+        let res = DispenseResponse::new()
+            .with_code(ResponseCode::Ack)
+            .with_count(3);
+        let raw = Response::from(res);
+
+        assert_eq!(raw.additional(), [3]);
+        assert_eq!(DispenseResponse::from(&raw), res);
+        assert_eq!(res.into_bytes(), [ResponseCode::Ack.into(), 3]);
+        assert_eq!(
+            res.to_string(),
+            r#"{"code": "affirmative response", "count": 3}"#
+        );
+
+        let nak = DispenseResponse::from(Response::new().with_code(ResponseCode::Nak));
+        assert_eq!(nak.code(), ResponseCode::Nak);
+        assert_eq!(nak.count(), None);
+    }
+}
@@ -0,0 +1,256 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Error, EventResendInterval, Message, RequestCode, Response, ResponseCode, Result};
+
+/// Represents the [Response] to a [EventResendIntervalRequest](crate::EventResendIntervalRequest).
+///
+/// The [EventResendInterval] is only reported in response to [Get](crate::EventResendIntervalMode::Get) requests.
+#[repr(C)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EventResendIntervalResponse {
+    code: ResponseCode,
+    interval: Option<EventResendInterval>,
+}
+
+impl EventResendIntervalResponse {
+    /// Creates a new [EventResendIntervalResponse].
+    pub const fn new() -> Self {
+        Self {
+            code: ResponseCode::new(),
+            interval: None,
+        }
+    }
+
+    /// Gets the [ResponseCode] for the [EventResendIntervalResponse].
+    pub const fn code(&self) -> ResponseCode {
+        self.code
+    }
+
+    /// Sets the [ResponseCode] for the [EventResendIntervalResponse].
+    pub fn set_code(&mut self, code: ResponseCode) {
+        self.code = code;
+    }
+
+    /// Builder function that sets the [ResponseCode] for the [EventResendIntervalResponse].
+    pub const fn with_code(self, code: ResponseCode) -> Self {
+        Self {
+            code,
+            interval: self.interval,
+        }
+    }
+
+    /// Gets the [EventResendInterval] for the [EventResendIntervalResponse].
+    pub const fn interval(&self) -> Option<EventResendInterval> {
+        self.interval
+    }
+
+    /// Sets the [EventResendInterval] for the [EventResendIntervalResponse].
+    pub fn set_interval(&mut self, interval: EventResendInterval) {
+        self.interval = Some(interval);
+    }
+
+    /// Unsets the [EventResendInterval] for the [EventResendIntervalResponse].
+    pub fn unset_interval(&mut self) -> Option<EventResendInterval> {
+        self.interval.take()
+    }
+
+    /// Builder function that sets the [EventResendInterval] for the [EventResendIntervalResponse].
+    pub const fn with_interval(self, interval: EventResendInterval) -> Self {
+        Self {
+            code: self.code,
+            interval: Some(interval),
+        }
+    }
+
+    /// Gets the length of the [EventResendIntervalResponse].
+    pub const fn len() -> usize {
+        ResponseCode::len() + EventResendInterval::len()
+    }
+
+    /// Gets whether the [EventResendIntervalResponse] is empty.
+    pub fn is_empty(&self) -> bool {
+        self.code.is_empty() && self.interval.is_none()
+    }
+
+    /// Gets an iterator over [EventResendIntervalResponse] bytes.
+    pub fn into_iter_bytes(self) -> impl Iterator<Item = u8> {
+        let mut code_iter = [self.code.into()].into_iter();
+        let mut data_iter = self.interval.map(|s| s.into_bytes().into_iter());
+
+        core::iter::from_fn(move || match (code_iter.next(), data_iter.as_mut()) {
+            (Some(c), _) => Some(c),
+            (None, Some(d)) => d.next(),
+            (None, None) => None,
+        })
+    }
+
+    /// Converts a [EventResendIntervalResponse] into a byte vector.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.into_iter_bytes().collect()
+    }
+
+    /// Converts a byte buffer into a [EventResendIntervalResponse].
+    pub fn from_bytes(buf: &[u8]) -> Result<Self> {
+        Ok(Self {
+            code: buf
+                .first()
+                .copied()
+                .ok_or(Error::InvalidResponseLen((0, 1)))?
+                .try_into()?,
+            interval: match buf
+                .get(1..1 + EventResendInterval::len())
+                .map(EventResendInterval::from_bytes)
+            {
+                Some(d) => Some(d?),
+                None => None,
+            },
+        })
+    }
+}
+
+impl Default for EventResendIntervalResponse {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&Response> for EventResendIntervalResponse {
+    fn from(val: &Response) -> Self {
+        Self {
+            code: val.code(),
+            interval: val.additional().try_into().ok(),
+        }
+    }
+}
+
+impl From<Response> for EventResendIntervalResponse {
+    fn from(val: Response) -> Self {
+        (&val).into()
+    }
+}
+
+impl From<EventResendIntervalResponse> for Response {
+    fn from(val: EventResendIntervalResponse) -> Self {
+        Self {
+            code: val.code,
+            additional: val
+                .interval
+                .map(|d| d.into_bytes().to_vec())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl From<&EventResendIntervalResponse> for Response {
+    fn from(val: &EventResendIntervalResponse) -> Self {
+        Self {
+            code: val.code,
+            additional: val
+                .interval
+                .map(|d| d.into_bytes().to_vec())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl TryFrom<Message> for EventResendIntervalResponse {
+    type Error = Error;
+
+    fn try_from(val: Message) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
+impl TryFrom<&Message> for EventResendIntervalResponse {
+    type Error = Error;
+
+    fn try_from(val: &Message) -> Result<Self> {
+        match val.data.message_code().request_code()? {
+            RequestCode::EventResendInterval => Ok(Response::try_from(val)?.into()),
+            code => Err(Error::InvalidRequestCode(code.into())),
+        }
+    }
+}
+
+impl fmt::Display for EventResendIntervalResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""code": {}"#, self.code)?;
+        if let Some(interval) = self.interval.as_ref() {
+            write!(f, r#", "interval": {interval}"#)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventResendIntervalMode, MAX_EVENT_RESEND_INTERVAL};
+
+    #[test]
+    fn test_event_resend_interval_response() -> Result<()> {
+        let interval = EventResendInterval::create(50)?;
+
+        for mode in [EventResendIntervalMode::Get, EventResendIntervalMode::Set] {
+            let raw = match mode {
+                EventResendIntervalMode::Get => vec![ResponseCode::Ack as u8, 50],
+                EventResendIntervalMode::Set => vec![ResponseCode::Ack as u8],
+            };
+
+            let exp = match mode {
+                EventResendIntervalMode::Get => EventResendIntervalResponse::new()
+                    .with_code(ResponseCode::Ack)
+                    .with_interval(interval),
+                EventResendIntervalMode::Set => {
+                    EventResendIntervalResponse::new().with_code(ResponseCode::Ack)
+                }
+            };
+
+            let res = match mode {
+                EventResendIntervalMode::Get => Response::new()
+                    .with_code(ResponseCode::Ack)
+                    .with_additional(raw[1..].as_ref()),
+                EventResendIntervalMode::Set => Response::new().with_code(ResponseCode::Ack),
+            };
+
+            assert_eq!(
+                EventResendIntervalResponse::from_bytes(raw.as_ref()).as_ref(),
+                Ok(&exp),
+            );
+            assert_eq!(&EventResendIntervalResponse::from(&res), &exp);
+            assert_eq!(Response::from(&exp), res);
+
+            let out = exp.into_bytes();
+
+            assert_eq!(out, raw);
+        }
+
+        assert_eq!(
+            EventResendIntervalResponse::new()
+                .with_code(ResponseCode::Ack)
+                .with_interval(interval)
+                .to_string(),
+            r#"{"code": "affirmative response", "interval": {"interval_ms": 5000}}"#
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_event_resend_interval_response_invalid() {
+        assert!(EventResendIntervalResponse::from_bytes(&[]).is_err());
+        assert!(EventResendIntervalResponse::from_bytes(
+            [ResponseCode::Reserved as u8, 0].as_ref()
+        )
+        .is_err());
+        for units in [0, MAX_EVENT_RESEND_INTERVAL + 1] {
+            assert!(EventResendIntervalResponse::from_bytes(
+                [ResponseCode::Ack as u8, units].as_ref()
+            )
+            .is_err());
+        }
+    }
+}
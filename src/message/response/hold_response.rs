@@ -0,0 +1,182 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Error, Message, RequestCode, Response, ResponseCode, Result};
+
+/// Represents the [Response] to a [HoldRequest](crate::HoldRequest).
+///
+/// Devices acknowledge a `Hold` request while a note is in escrow, and respond with a `NAK`
+/// otherwise.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{HoldResponse, Message, ResponseCode};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, request type, request code, response code
+/// let frame = [0x12, 0x09, 0x00, 0x11, 0x00, 0x00, 0x16, 0x10, 0x06];
+///
+/// let res = HoldResponse::try_from(Message::try_from(frame.as_ref())?)?;
+///
+/// assert_eq!(res.code(), ResponseCode::Ack);
+/// assert!(res.is_held());
+/// # Ok(())
+/// # }
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HoldResponse {
+    code: ResponseCode,
+}
+
+impl HoldResponse {
+    /// Creates a new [HoldResponse].
+    pub const fn new() -> Self {
+        Self {
+            code: ResponseCode::new(),
+        }
+    }
+
+    /// Gets the [ResponseCode] for the [HoldResponse].
+    pub const fn code(&self) -> ResponseCode {
+        self.code
+    }
+
+    /// Sets the [ResponseCode] for the [HoldResponse].
+    pub fn set_code(&mut self, code: ResponseCode) {
+        self.code = code;
+    }
+
+    /// Builder function that sets the [ResponseCode] for the [HoldResponse].
+    pub const fn with_code(self, code: ResponseCode) -> Self {
+        Self { code }
+    }
+
+    /// Gets whether the device holds the note, starting or extending the hold timeout.
+    pub const fn is_held(&self) -> bool {
+        matches!(self.code, ResponseCode::Ack)
+    }
+
+    /// Gets the length of the [HoldResponse].
+    pub const fn len() -> usize {
+        ResponseCode::len()
+    }
+
+    /// Gets whether the [HoldResponse] is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.code.is_empty()
+    }
+
+    /// Converts a [HoldResponse] into a byte vector.
+    pub fn into_bytes(self) -> Vec<u8> {
+        vec![self.code.into()]
+    }
+
+    /// Converts a byte buffer into a [HoldResponse].
+    pub fn from_bytes(buf: &[u8]) -> Result<Self> {
+        Ok(Self {
+            code: buf
+                .first()
+                .copied()
+                .ok_or(Error::InvalidResponseLen((0, Self::len())))?
+                .try_into()?,
+        })
+    }
+}
+
+impl Default for HoldResponse {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&Response> for HoldResponse {
+    fn from(val: &Response) -> Self {
+        Self { code: val.code() }
+    }
+}
+
+impl From<Response> for HoldResponse {
+    fn from(val: Response) -> Self {
+        (&val).into()
+    }
+}
+
+impl From<HoldResponse> for Response {
+    fn from(val: HoldResponse) -> Self {
+        Self {
+            code: val.code,
+            additional: Vec::new(),
+        }
+    }
+}
+
+impl From<&HoldResponse> for Response {
+    fn from(val: &HoldResponse) -> Self {
+        (*val).into()
+    }
+}
+
+impl TryFrom<Message> for HoldResponse {
+    type Error = Error;
+
+    fn try_from(val: Message) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
+impl TryFrom<&Message> for HoldResponse {
+    type Error = Error;
+
+    fn try_from(val: &Message) -> Result<Self> {
+        match val.data.message_code().request_code()? {
+            RequestCode::Hold => Ok(Response::try_from(val)?.into()),
+            code => Err(Error::InvalidRequestCode(code.into())),
+        }
+    }
+}
+
+impl fmt::Display for HoldResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""code": {}, "#, self.code)?;
+        write!(f, r#""held": {}"#, self.is_held())?;
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HoldRequest, MessageData, StackRequest};
+
+    #[test]
+    fn test_hold_response() -> Result<()> {
+        for (code, held) in [(ResponseCode::Ack, true), (ResponseCode::Nak, false)] {
+            let raw = [code.into()];
+            let exp = HoldResponse::new().with_code(code);
+
+            assert_eq!(HoldResponse::from_bytes(raw.as_ref()), Ok(exp));
+            assert_eq!(HoldResponse::from(Response::from(exp)), exp);
+            assert_eq!(exp.is_held(), held);
+            assert_eq!(exp.into_bytes(), raw);
+
+            let msg = Message::new()
+                .with_data(MessageData::from(HoldRequest::new()).with_additional(raw.as_ref()));
+            assert_eq!(HoldResponse::try_from(&msg), Ok(exp));
+        }
+
+        let stack = Message::new().with_data(
+            MessageData::from(StackRequest::new()).with_additional(&[ResponseCode::Ack.into()]),
+        );
+        assert_eq!(
+            HoldResponse::try_from(&stack),
+            Err(Error::InvalidRequestCode(RequestCode::Stack.into()))
+        );
+        assert!(HoldResponse::from_bytes(&[]).is_err());
+
+        Ok(())
+    }
+}
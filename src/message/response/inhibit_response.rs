@@ -0,0 +1,182 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Error, Message, RequestCode, Response, ResponseCode, Result};
+
+/// Represents the [Response] to a [InhibitRequest](crate::InhibitRequest).
+///
+/// Devices acknowledge an `Inhibit` request once they stop accepting notes. Sending `Idle`
+/// enables the device again, see [DeviceInhibit](crate::DeviceInhibit).
+///
+/// # Example
+///
+/// ```
+/// use jcm::{InhibitResponse, Message, ResponseCode};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, request type, request code, response code
+/// let frame = [0x12, 0x09, 0x00, 0x11, 0x00, 0x00, 0x12, 0x00, 0x06];
+///
+/// let res = InhibitResponse::try_from(Message::try_from(frame.as_ref())?)?;
+///
+/// assert_eq!(res.code(), ResponseCode::Ack);
+/// assert!(res.is_inhibited());
+/// # Ok(())
+/// # }
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InhibitResponse {
+    code: ResponseCode,
+}
+
+impl InhibitResponse {
+    /// Creates a new [InhibitResponse].
+    pub const fn new() -> Self {
+        Self {
+            code: ResponseCode::new(),
+        }
+    }
+
+    /// Gets the [ResponseCode] for the [InhibitResponse].
+    pub const fn code(&self) -> ResponseCode {
+        self.code
+    }
+
+    /// Sets the [ResponseCode] for the [InhibitResponse].
+    pub fn set_code(&mut self, code: ResponseCode) {
+        self.code = code;
+    }
+
+    /// Builder function that sets the [ResponseCode] for the [InhibitResponse].
+    pub const fn with_code(self, code: ResponseCode) -> Self {
+        Self { code }
+    }
+
+    /// Gets whether the device acknowledged, and is inhibited.
+    pub const fn is_inhibited(&self) -> bool {
+        matches!(self.code, ResponseCode::Ack)
+    }
+
+    /// Gets the length of the [InhibitResponse].
+    pub const fn len() -> usize {
+        ResponseCode::len()
+    }
+
+    /// Gets whether the [InhibitResponse] is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.code.is_empty()
+    }
+
+    /// Converts a [InhibitResponse] into a byte vector.
+    pub fn into_bytes(self) -> Vec<u8> {
+        vec![self.code.into()]
+    }
+
+    /// Converts a byte buffer into a [InhibitResponse].
+    pub fn from_bytes(buf: &[u8]) -> Result<Self> {
+        Ok(Self {
+            code: buf
+                .first()
+                .copied()
+                .ok_or(Error::InvalidResponseLen((0, Self::len())))?
+                .try_into()?,
+        })
+    }
+}
+
+impl Default for InhibitResponse {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&Response> for InhibitResponse {
+    fn from(val: &Response) -> Self {
+        Self { code: val.code() }
+    }
+}
+
+impl From<Response> for InhibitResponse {
+    fn from(val: Response) -> Self {
+        (&val).into()
+    }
+}
+
+impl From<InhibitResponse> for Response {
+    fn from(val: InhibitResponse) -> Self {
+        Self {
+            code: val.code,
+            additional: Vec::new(),
+        }
+    }
+}
+
+impl From<&InhibitResponse> for Response {
+    fn from(val: &InhibitResponse) -> Self {
+        (*val).into()
+    }
+}
+
+impl TryFrom<Message> for InhibitResponse {
+    type Error = Error;
+
+    fn try_from(val: Message) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
+impl TryFrom<&Message> for InhibitResponse {
+    type Error = Error;
+
+    fn try_from(val: &Message) -> Result<Self> {
+        match val.data.message_code().request_code()? {
+            RequestCode::Inhibit => Ok(Response::try_from(val)?.into()),
+            code => Err(Error::InvalidRequestCode(code.into())),
+        }
+    }
+}
+
+impl fmt::Display for InhibitResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""code": {}, "#, self.code)?;
+        write!(f, r#""inhibited": {}"#, self.is_inhibited())?;
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InhibitRequest, MessageData, StackRequest};
+
+    #[test]
+    fn test_inhibit_response() -> Result<()> {
+        for (code, held) in [(ResponseCode::Ack, true), (ResponseCode::Nak, false)] {
+            let raw = [code.into()];
+            let exp = InhibitResponse::new().with_code(code);
+
+            assert_eq!(InhibitResponse::from_bytes(raw.as_ref()), Ok(exp));
+            assert_eq!(InhibitResponse::from(Response::from(exp)), exp);
+            assert_eq!(exp.is_inhibited(), held);
+            assert_eq!(exp.into_bytes(), raw);
+
+            let msg = Message::new()
+                .with_data(MessageData::from(InhibitRequest::new()).with_additional(raw.as_ref()));
+            assert_eq!(InhibitResponse::try_from(&msg), Ok(exp));
+        }
+
+        let stack = Message::new().with_data(
+            MessageData::from(StackRequest::new()).with_additional(&[ResponseCode::Ack.into()]),
+        );
+        assert_eq!(
+            InhibitResponse::try_from(&stack),
+            Err(Error::InvalidRequestCode(RequestCode::Stack.into()))
+        );
+        assert!(InhibitResponse::from_bytes(&[]).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,250 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Error, InsertNotification, Message, RequestCode, Response, ResponseCode, Result};
+
+/// Represents the [Response] to an [InsertNotificationRequest](crate::InsertNotificationRequest).
+///
+/// [InsertNotification] is only reported in response to [Get](crate::InsertNotificationMode::Get) requests.
+#[repr(C)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InsertNotificationResponse {
+    code: ResponseCode,
+    notification: Option<InsertNotification>,
+}
+
+impl InsertNotificationResponse {
+    /// Creates a new [InsertNotificationResponse].
+    pub const fn new() -> Self {
+        Self {
+            code: ResponseCode::new(),
+            notification: None,
+        }
+    }
+
+    /// Gets the [ResponseCode] for the [InsertNotificationResponse].
+    pub const fn code(&self) -> ResponseCode {
+        self.code
+    }
+
+    /// Sets the [ResponseCode] for the [InsertNotificationResponse].
+    pub fn set_code(&mut self, code: ResponseCode) {
+        self.code = code;
+    }
+
+    /// Builder function that sets the [ResponseCode] for the [InsertNotificationResponse].
+    pub const fn with_code(self, code: ResponseCode) -> Self {
+        Self {
+            code,
+            notification: self.notification,
+        }
+    }
+
+    /// Gets the [InsertNotification] for the [InsertNotificationResponse].
+    pub const fn notification(&self) -> Option<InsertNotification> {
+        self.notification
+    }
+
+    /// Sets the [InsertNotification] for the [InsertNotificationResponse].
+    pub fn set_notification(&mut self, notification: InsertNotification) {
+        self.notification = Some(notification);
+    }
+
+    /// Unsets the [InsertNotification] for the [InsertNotificationResponse].
+    pub fn unset_notification(&mut self) -> Option<InsertNotification> {
+        self.notification.take()
+    }
+
+    /// Builder function that sets the [InsertNotification] for the [InsertNotificationResponse].
+    pub const fn with_notification(self, notification: InsertNotification) -> Self {
+        Self {
+            code: self.code,
+            notification: Some(notification),
+        }
+    }
+
+    /// Gets the length of the [InsertNotificationResponse].
+    pub const fn len() -> usize {
+        ResponseCode::len() + InsertNotification::len()
+    }
+
+    /// Gets whether the [InsertNotificationResponse] is empty.
+    pub fn is_empty(&self) -> bool {
+        self.code.is_empty() && self.notification.is_none()
+    }
+
+    /// Gets an iterator over [InsertNotificationResponse] bytes.
+    pub fn into_iter_bytes(self) -> impl Iterator<Item = u8> {
+        let mut code_iter = [self.code.into()].into_iter();
+        let mut data_iter = self.notification.map(|s| s.into_bytes().into_iter());
+
+        core::iter::from_fn(move || match (code_iter.next(), data_iter.as_mut()) {
+            (Some(c), _) => Some(c),
+            (None, Some(d)) => d.next(),
+            (None, None) => None,
+        })
+    }
+
+    /// Converts an [InsertNotificationResponse] into a byte vector.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.into_iter_bytes().collect()
+    }
+
+    /// Converts a byte buffer into an [InsertNotificationResponse].
+    pub fn from_bytes(buf: &[u8]) -> Result<Self> {
+        Ok(Self {
+            code: buf
+                .first()
+                .copied()
+                .ok_or(Error::InvalidResponseLen((0, 1)))?
+                .try_into()?,
+            notification: match buf
+                .get(1..1 + InsertNotification::len())
+                .map(InsertNotification::from_bytes)
+            {
+                Some(d) => Some(d?),
+                None => None,
+            },
+        })
+    }
+}
+
+impl Default for InsertNotificationResponse {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&Response> for InsertNotificationResponse {
+    fn from(val: &Response) -> Self {
+        Self {
+            code: val.code(),
+            notification: val.additional().try_into().ok(),
+        }
+    }
+}
+
+impl From<Response> for InsertNotificationResponse {
+    fn from(val: Response) -> Self {
+        (&val).into()
+    }
+}
+
+impl From<InsertNotificationResponse> for Response {
+    fn from(val: InsertNotificationResponse) -> Self {
+        Self {
+            code: val.code,
+            additional: val
+                .notification
+                .map(|d| d.into_bytes().to_vec())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl From<&InsertNotificationResponse> for Response {
+    fn from(val: &InsertNotificationResponse) -> Self {
+        Self {
+            code: val.code,
+            additional: val
+                .notification
+                .map(|d| d.into_bytes().to_vec())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl TryFrom<Message> for InsertNotificationResponse {
+    type Error = Error;
+
+    fn try_from(val: Message) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
+impl TryFrom<&Message> for InsertNotificationResponse {
+    type Error = Error;
+
+    fn try_from(val: &Message) -> Result<Self> {
+        match val.data.message_code().request_code()? {
+            RequestCode::Insert => Ok(Response::try_from(val)?.into()),
+            code => Err(Error::InvalidRequestCode(code.into())),
+        }
+    }
+}
+
+impl fmt::Display for InsertNotificationResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""code": {}"#, self.code)?;
+        if let Some(notification) = self.notification.as_ref() {
+            write!(f, r#", "notification": {notification}"#)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InsertNotificationMode;
+
+    #[test]
+    fn test_insert_notification_response() {
+        for mode in [InsertNotificationMode::Get, InsertNotificationMode::Set] {
+            let raw = match mode {
+                InsertNotificationMode::Get => vec![ResponseCode::Ack as u8, 0x01],
+                InsertNotificationMode::Set => vec![ResponseCode::Ack as u8],
+            };
+
+            let exp = match mode {
+                InsertNotificationMode::Get => InsertNotificationResponse::new()
+                    .with_code(ResponseCode::Ack)
+                    .with_notification(InsertNotification::Enabled),
+                InsertNotificationMode::Set => {
+                    InsertNotificationResponse::new().with_code(ResponseCode::Ack)
+                }
+            };
+
+            let res = match mode {
+                InsertNotificationMode::Get => Response::new()
+                    .with_code(ResponseCode::Ack)
+                    .with_additional(raw[1..].as_ref()),
+                InsertNotificationMode::Set => Response::new().with_code(ResponseCode::Ack),
+            };
+
+            assert_eq!(
+                InsertNotificationResponse::from_bytes(raw.as_ref()).as_ref(),
+                Ok(&exp),
+            );
+            assert_eq!(&InsertNotificationResponse::from(&res), &exp);
+            assert_eq!(Response::from(&exp), res);
+
+            let out = exp.into_bytes();
+
+            assert_eq!(out, raw);
+        }
+
+        assert_eq!(
+            InsertNotificationResponse::new()
+                .with_code(ResponseCode::Ack)
+                .with_notification(InsertNotification::Enabled)
+                .to_string(),
+            r#"{"code": "affirmative response", "notification": "enabled"}"#
+        );
+    }
+
+    #[test]
+    fn test_insert_notification_response_invalid() {
+        assert!(InsertNotificationResponse::from_bytes(&[]).is_err());
+        assert!(
+            InsertNotificationResponse::from_bytes([ResponseCode::Reserved as u8, 0].as_ref())
+                .is_err()
+        );
+        assert!(
+            InsertNotificationResponse::from_bytes([ResponseCode::Ack as u8, 0x02].as_ref())
+                .is_err()
+        );
+    }
+}
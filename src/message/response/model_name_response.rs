@@ -1,4 +1,5 @@
-use std::fmt;
+use alloc::vec::Vec;
+use core::fmt;
 
 use crate::{Error, Message, Response, ResponseCode, Result};
 
@@ -6,9 +7,27 @@ mod model_name;
 
 pub use model_name::*;
 
-/// Represents the response to a [VersionRequest](crate::VersionRequest).
+/// Represents the response to a [ModelNameRequest](crate::ModelNameRequest).
+///
+/// # Example
+///
+/// ```
+/// use jcm::{Message, ModelNameResponse, ResponseCode};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, request type, request code, response code, data
+/// let frame = [0x12, 0x0d, 0x00, 0x10, 0x00, 0x10, 0x05, 0x00, 0x06, 0x55, 0x42, 0x41, 0x00];
+///
+/// let res = ModelNameResponse::try_from(Message::try_from(frame.as_ref())?)?;
+///
+/// assert_eq!(res.code(), ResponseCode::Ack);
+/// assert_eq!(res.model_name().as_str(), "UBA");
+/// # Ok(())
+/// # }
+/// ```
 #[repr(C)]
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ModelNameResponse {
     code: ResponseCode,
     model_name: ModelName,
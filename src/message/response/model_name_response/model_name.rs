@@ -1,10 +1,12 @@
-use std::fmt;
+use alloc::{string::String, vec::Vec};
+use core::fmt;
 
 use crate::{Error, Result};
 
 /// Represents the device model name.
 #[repr(C)]
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ModelName(String);
 
 impl ModelName {
@@ -32,7 +34,7 @@ impl ModelName {
 
     /// Gets the byte length of the [ModelName].
     pub fn len(&self) -> usize {
-        self.0.as_bytes().len()
+        self.0.len()
     }
 
     /// Gets whether the [ModelName] is empty.
@@ -76,12 +78,12 @@ impl IntoIterator for ModelName {
     type Item = u8;
     type IntoIter = <Vec<u8> as IntoIterator>::IntoIter;
 
-    fn into_iter(self) -> std::vec::IntoIter<u8> {
+    fn into_iter(self) -> alloc::vec::IntoIter<u8> {
         self.0.into_bytes().into_iter()
     }
 }
 
-impl std::str::FromStr for ModelName {
+impl core::str::FromStr for ModelName {
     type Err = Error;
 
     fn from_str(val: &str) -> Result<Self> {
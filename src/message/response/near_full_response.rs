@@ -1,10 +1,12 @@
-use std::fmt;
+use alloc::vec::Vec;
+use core::fmt;
 
 use crate::{Error, Message, NearFullData, RequestCode, Response, ResponseCode, Result};
 
-/// Represents the [Response] to a [DirectionDisableRequest](crate::DirectionDisableRequest).
+/// Represents the [Response] to a [NearFullRequest](crate::NearFullRequest).
 #[repr(C)]
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NearFullResponse {
     code: ResponseCode,
     data: Option<NearFullData>,
@@ -75,7 +77,7 @@ impl NearFullResponse {
         let mut code_iter = [self.code.into()].into_iter();
         let mut data_iter = self.data.map(|d| d.into_iter());
 
-        std::iter::from_fn(move || match (code_iter.next(), data_iter.as_mut()) {
+        core::iter::from_fn(move || match (code_iter.next(), data_iter.as_mut()) {
             (Some(c), _) => Some(c),
             (None, Some(d)) => d.next(),
             (None, None) => None,
@@ -164,7 +166,7 @@ impl TryFrom<&Message> for NearFullResponse {
 
     fn try_from(val: &Message) -> Result<Self> {
         match val.data.message_code().request_code()? {
-            RequestCode::DirectionDisable => Ok(Response::try_from(val)?.into()),
+            RequestCode::NearFull => Ok(Response::try_from(val)?.into()),
             code => Err(Error::InvalidRequestCode(code.into())),
         }
     }
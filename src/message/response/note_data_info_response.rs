@@ -0,0 +1,246 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Error, Message, RequestCode, Response, ResponseCode, Result};
+
+mod note_data_info;
+
+pub use note_data_info::*;
+
+/// Represents the [Response] to a [NoteDataInfoRequest](crate::NoteDataInfoRequest).
+///
+/// The [NoteDataInfo] is only reported in `ACK` responses, while a note is in the device.
+///
+/// The request code is shared with image block transfers, so the response length is not
+/// validated by [expected_response_len](crate::expected_response_len).
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NoteDataInfoResponse {
+    code: ResponseCode,
+    info: Option<NoteDataInfo>,
+}
+
+impl NoteDataInfoResponse {
+    /// Creates a new [NoteDataInfoResponse].
+    pub const fn new() -> Self {
+        Self {
+            code: ResponseCode::new(),
+            info: None,
+        }
+    }
+
+    /// Gets the [ResponseCode] for the [NoteDataInfoResponse].
+    pub const fn code(&self) -> ResponseCode {
+        self.code
+    }
+
+    /// Sets the [ResponseCode] for the [NoteDataInfoResponse].
+    pub fn set_code(&mut self, code: ResponseCode) {
+        self.code = code;
+    }
+
+    /// Builder function that sets the [ResponseCode] for the [NoteDataInfoResponse].
+    pub const fn with_code(self, code: ResponseCode) -> Self {
+        Self {
+            code,
+            info: self.info,
+        }
+    }
+
+    /// Gets the [NoteDataInfo] for the [NoteDataInfoResponse].
+    pub const fn info(&self) -> Option<NoteDataInfo> {
+        self.info
+    }
+
+    /// Sets the [NoteDataInfo] for the [NoteDataInfoResponse].
+    pub fn set_info(&mut self, info: NoteDataInfo) {
+        self.info = Some(info);
+    }
+
+    /// Unsets the [NoteDataInfo] for the [NoteDataInfoResponse].
+    pub fn unset_info(&mut self) -> Option<NoteDataInfo> {
+        self.info.take()
+    }
+
+    /// Builder function that sets the [NoteDataInfo] for the [NoteDataInfoResponse].
+    pub const fn with_info(self, info: NoteDataInfo) -> Self {
+        Self {
+            code: self.code,
+            info: Some(info),
+        }
+    }
+
+    /// Gets the length of the [NoteDataInfoResponse].
+    pub const fn len() -> usize {
+        ResponseCode::len() + NoteDataInfo::len()
+    }
+
+    /// Gets whether the [NoteDataInfoResponse] is empty.
+    pub fn is_empty(&self) -> bool {
+        self.code.is_empty() && self.info.is_none()
+    }
+
+    /// Gets an iterator over [NoteDataInfoResponse] bytes.
+    pub fn into_iter_bytes(self) -> impl Iterator<Item = u8> {
+        let mut code_iter = [self.code.into()].into_iter();
+        let mut data_iter = self.info.map(|s| s.into_bytes().into_iter());
+
+        core::iter::from_fn(move || match (code_iter.next(), data_iter.as_mut()) {
+            (Some(c), _) => Some(c),
+            (None, Some(d)) => d.next(),
+            (None, None) => None,
+        })
+    }
+
+    /// Converts a [NoteDataInfoResponse] into a byte vector.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.into_iter_bytes().collect()
+    }
+
+    /// Converts a byte buffer into a [NoteDataInfoResponse].
+    pub fn from_bytes(buf: &[u8]) -> Result<Self> {
+        Ok(Self {
+            code: buf
+                .first()
+                .copied()
+                .ok_or(Error::InvalidResponseLen((0, 1)))?
+                .try_into()?,
+            info: match buf
+                .get(1..1 + NoteDataInfo::len())
+                .map(NoteDataInfo::from_bytes)
+            {
+                Some(d) => Some(d?),
+                None => None,
+            },
+        })
+    }
+}
+
+impl Default for NoteDataInfoResponse {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&Response> for NoteDataInfoResponse {
+    fn from(val: &Response) -> Self {
+        Self {
+            code: val.code(),
+            info: val.additional().try_into().ok(),
+        }
+    }
+}
+
+impl From<Response> for NoteDataInfoResponse {
+    fn from(val: Response) -> Self {
+        (&val).into()
+    }
+}
+
+impl From<NoteDataInfoResponse> for Response {
+    fn from(val: NoteDataInfoResponse) -> Self {
+        Self {
+            code: val.code,
+            additional: val
+                .info
+                .map(|d| d.into_bytes().to_vec())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl From<&NoteDataInfoResponse> for Response {
+    fn from(val: &NoteDataInfoResponse) -> Self {
+        Self {
+            code: val.code,
+            additional: val
+                .info
+                .map(|d| d.into_bytes().to_vec())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl TryFrom<Message> for NoteDataInfoResponse {
+    type Error = Error;
+
+    fn try_from(val: Message) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
+impl TryFrom<&Message> for NoteDataInfoResponse {
+    type Error = Error;
+
+    fn try_from(val: &Message) -> Result<Self> {
+        match val.data.message_code().request_code()? {
+            RequestCode::NoteDataInfo => Ok(Response::try_from(val)?.into()),
+            code => Err(Error::InvalidRequestCode(code.into())),
+        }
+    }
+}
+
+impl fmt::Display for NoteDataInfoResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""code": {}"#, self.code)?;
+        if let Some(info) = self.info.as_ref() {
+            write!(f, r#", "info": {info}"#)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Currency, CurrencyCode, Denomination, Orientation};
+
+    fn info() -> NoteDataInfo {
+        NoteDataInfo::new()
+            .with_currency(
+                Currency::new()
+                    .with_code(CurrencyCode::USD)
+                    .with_denomination(Denomination::from_value(20)),
+            )
+            .with_orientation(Orientation::FaceUpBackward)
+    }
+
+    #[test]
+    fn test_note_data_info_response() -> Result<()> {
+        let raw = [ResponseCode::Ack as u8]
+            .into_iter()
+            .chain(info().into_bytes())
+            .collect::<Vec<u8>>();
+
+        let exp = NoteDataInfoResponse::new()
+            .with_code(ResponseCode::Ack)
+            .with_info(info());
+        let res = Response::new()
+            .with_code(ResponseCode::Ack)
+            .with_additional(raw[1..].as_ref());
+
+        assert_eq!(NoteDataInfoResponse::from_bytes(raw.as_ref())?, exp);
+        assert_eq!(NoteDataInfoResponse::from(&res), exp);
+        assert_eq!(Response::from(&exp), res);
+        assert_eq!(exp.clone().into_bytes(), raw);
+        assert_eq!(raw.len(), NoteDataInfoResponse::len());
+
+        let nak = NoteDataInfoResponse::from_bytes(&[ResponseCode::Nak as u8])?;
+        assert_eq!(nak.info(), None);
+        assert_eq!(
+            nak.to_string(),
+            format!(r#"{{"code": {}}}"#, ResponseCode::Nak)
+        );
+
+        assert_eq!(
+            exp.to_string(),
+            format!(r#"{{"code": {}, "info": {}}}"#, ResponseCode::Ack, info())
+        );
+
+        assert!(NoteDataInfoResponse::from_bytes(&[]).is_err());
+
+        Ok(())
+    }
+}
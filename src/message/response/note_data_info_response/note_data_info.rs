@@ -0,0 +1,189 @@
+use core::fmt;
+
+use crate::{Currency, Error, Orientation, Result, CURRENCY_LEN};
+
+/// Represents the length of [NoteDataInfo].
+pub const NOTE_DATA_INFO_LEN: usize = CURRENCY_LEN + 2;
+
+const SERIAL_NUMBER_FLAG: u8 = 0b1;
+
+/// Represents the data record of the note inserted into the device.
+///
+/// ## Format
+///
+/// Field  | Currency | Orientation | Flags
+/// -------|----------|-------------|-------
+/// Length | 5 bytes  | 1 byte      | 1 byte
+///
+/// Bit 0 of the flags is set when a serial number image is available for the note, other bits
+/// are reserved.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NoteDataInfo {
+    currency: Currency,
+    orientation: Orientation,
+    serial_number: bool,
+}
+
+impl NoteDataInfo {
+    /// Creates a new [NoteDataInfo].
+    pub const fn new() -> Self {
+        Self {
+            currency: Currency::new(),
+            orientation: Orientation::new(),
+            serial_number: false,
+        }
+    }
+
+    /// Gets the [Currency] of the note.
+    pub const fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Sets the [Currency] of the note.
+    pub fn set_currency(&mut self, currency: Currency) {
+        self.currency = currency;
+    }
+
+    /// Builder function that sets the [Currency] of the note.
+    pub const fn with_currency(self, currency: Currency) -> Self {
+        Self {
+            currency,
+            orientation: self.orientation,
+            serial_number: self.serial_number,
+        }
+    }
+
+    /// Gets the [Orientation] of the note.
+    pub const fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Sets the [Orientation] of the note.
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+    }
+
+    /// Builder function that sets the [Orientation] of the note.
+    pub const fn with_orientation(self, orientation: Orientation) -> Self {
+        Self {
+            currency: self.currency,
+            orientation,
+            serial_number: self.serial_number,
+        }
+    }
+
+    /// Gets whether a serial number image is available for the note.
+    pub const fn serial_number(&self) -> bool {
+        self.serial_number
+    }
+
+    /// Sets whether a serial number image is available for the note.
+    pub fn set_serial_number(&mut self, available: bool) {
+        self.serial_number = available;
+    }
+
+    /// Builder function that sets whether a serial number image is available for the note.
+    pub const fn with_serial_number(self, available: bool) -> Self {
+        Self {
+            currency: self.currency,
+            orientation: self.orientation,
+            serial_number: available,
+        }
+    }
+
+    /// Gets the byte length of the [NoteDataInfo].
+    pub const fn len() -> usize {
+        NOTE_DATA_INFO_LEN
+    }
+
+    /// Attempts to convert a byte buffer into [NoteDataInfo].
+    pub fn from_bytes(buf: &[u8]) -> Result<Self> {
+        match buf.get(..NOTE_DATA_INFO_LEN) {
+            Some(info) => Ok(Self {
+                currency: Currency::from_bytes(&info[..CURRENCY_LEN])?,
+                orientation: Orientation::from_u8(info[CURRENCY_LEN]),
+                serial_number: info[CURRENCY_LEN + 1] & SERIAL_NUMBER_FLAG != 0,
+            }),
+            None => Err(Error::InvalidNoteDataInfoLen((
+                buf.len(),
+                NOTE_DATA_INFO_LEN,
+            ))),
+        }
+    }
+
+    /// Converts the [NoteDataInfo] into a byte array.
+    pub fn into_bytes(self) -> [u8; NOTE_DATA_INFO_LEN] {
+        let mut buf = [0u8; NOTE_DATA_INFO_LEN];
+
+        buf[..CURRENCY_LEN].copy_from_slice(self.currency.into_bytes().as_ref());
+        buf[CURRENCY_LEN] = self.orientation.into();
+        buf[CURRENCY_LEN + 1] = if self.serial_number {
+            SERIAL_NUMBER_FLAG
+        } else {
+            0
+        };
+
+        buf
+    }
+}
+
+impl TryFrom<&[u8]> for NoteDataInfo {
+    type Error = Error;
+
+    fn try_from(val: &[u8]) -> Result<Self> {
+        Self::from_bytes(val)
+    }
+}
+
+impl fmt::Display for NoteDataInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""currency": {}, "#, self.currency)?;
+        write!(f, r#""orientation": {}, "#, self.orientation)?;
+        write!(f, r#""serial_number": {}"#, self.serial_number)?;
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CurrencyCode, Denomination};
+
+    #[test]
+    fn test_note_data_info() -> Result<()> {
+        let currency = Currency::new()
+            .with_code(CurrencyCode::JPY)
+            .with_denomination(Denomination::from_value(1000));
+        let mut raw = [0u8; NOTE_DATA_INFO_LEN];
+        raw[..CURRENCY_LEN].copy_from_slice(currency.into_bytes().as_ref());
+        raw[CURRENCY_LEN..].copy_from_slice(&[0x02, 0x01]);
+
+        let exp = NoteDataInfo::new()
+            .with_currency(currency)
+            .with_orientation(Orientation::FaceDownForward)
+            .with_serial_number(true);
+
+        assert_eq!(NoteDataInfo::from_bytes(raw.as_ref())?, exp);
+        assert_eq!(exp.into_bytes(), raw);
+
+        assert_eq!(
+            exp.to_string(),
+            format!(
+                r#"{{"currency": {currency}, "orientation": "face down forward", "serial_number": true}}"#
+            )
+        );
+
+        assert_eq!(
+            NoteDataInfo::from_bytes(&raw[..CURRENCY_LEN]),
+            Err(Error::InvalidNoteDataInfoLen((
+                CURRENCY_LEN,
+                NOTE_DATA_INFO_LEN
+            )))
+        );
+
+        Ok(())
+    }
+}
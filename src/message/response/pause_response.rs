@@ -0,0 +1,249 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Error, Message, PauseSettings, RequestCode, Response, ResponseCode, Result};
+
+/// Represents the [Response] to a [PauseRequest](crate::PauseRequest).
+///
+/// [PauseSettings] are only reported in response to [Get](crate::PauseMode::Get) requests.
+#[repr(C)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PauseResponse {
+    code: ResponseCode,
+    settings: Option<PauseSettings>,
+}
+
+impl PauseResponse {
+    /// Creates a new [PauseResponse].
+    pub const fn new() -> Self {
+        Self {
+            code: ResponseCode::new(),
+            settings: None,
+        }
+    }
+
+    /// Gets the [ResponseCode] for the [PauseResponse].
+    pub const fn code(&self) -> ResponseCode {
+        self.code
+    }
+
+    /// Sets the [ResponseCode] for the [PauseResponse].
+    pub fn set_code(&mut self, code: ResponseCode) {
+        self.code = code;
+    }
+
+    /// Builder function that sets the [ResponseCode] for the [PauseResponse].
+    pub const fn with_code(self, code: ResponseCode) -> Self {
+        Self {
+            code,
+            settings: self.settings,
+        }
+    }
+
+    /// Gets the [PauseSettings] for the [PauseResponse].
+    pub const fn settings(&self) -> Option<PauseSettings> {
+        self.settings
+    }
+
+    /// Sets the [PauseSettings] for the [PauseResponse].
+    pub fn set_settings(&mut self, settings: PauseSettings) {
+        self.settings = Some(settings);
+    }
+
+    /// Unsets the [PauseSettings] for the [PauseResponse].
+    pub fn unset_settings(&mut self) -> Option<PauseSettings> {
+        self.settings.take()
+    }
+
+    /// Builder function that sets the [PauseSettings] for the [PauseResponse].
+    pub const fn with_settings(self, settings: PauseSettings) -> Self {
+        Self {
+            code: self.code,
+            settings: Some(settings),
+        }
+    }
+
+    /// Gets the length of the [PauseResponse].
+    pub const fn len() -> usize {
+        ResponseCode::len() + PauseSettings::len()
+    }
+
+    /// Gets whether the [PauseResponse] is empty.
+    pub fn is_empty(&self) -> bool {
+        self.code.is_empty() && self.settings.is_none()
+    }
+
+    /// Gets an iterator over [PauseResponse] bytes.
+    pub fn into_iter_bytes(self) -> impl Iterator<Item = u8> {
+        let mut code_iter = [self.code.into()].into_iter();
+        let mut data_iter = self.settings.map(|s| s.into_bytes().into_iter());
+
+        core::iter::from_fn(move || match (code_iter.next(), data_iter.as_mut()) {
+            (Some(c), _) => Some(c),
+            (None, Some(d)) => d.next(),
+            (None, None) => None,
+        })
+    }
+
+    /// Converts a [PauseResponse] into a byte vector.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.into_iter_bytes().collect()
+    }
+
+    /// Converts a byte buffer into a [PauseResponse].
+    pub fn from_bytes(buf: &[u8]) -> Result<Self> {
+        Ok(Self {
+            code: buf
+                .first()
+                .copied()
+                .ok_or(Error::InvalidResponseLen((0, 1)))?
+                .try_into()?,
+            settings: match buf
+                .get(1..1 + PauseSettings::len())
+                .map(PauseSettings::from_bytes)
+            {
+                Some(d) => Some(d?),
+                None => None,
+            },
+        })
+    }
+}
+
+impl Default for PauseResponse {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&Response> for PauseResponse {
+    fn from(val: &Response) -> Self {
+        Self {
+            code: val.code(),
+            settings: val.additional().try_into().ok(),
+        }
+    }
+}
+
+impl From<Response> for PauseResponse {
+    fn from(val: Response) -> Self {
+        (&val).into()
+    }
+}
+
+impl From<PauseResponse> for Response {
+    fn from(val: PauseResponse) -> Self {
+        Self {
+            code: val.code,
+            additional: val
+                .settings
+                .map(|d| d.into_bytes().to_vec())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl From<&PauseResponse> for Response {
+    fn from(val: &PauseResponse) -> Self {
+        Self {
+            code: val.code,
+            additional: val
+                .settings
+                .map(|d| d.into_bytes().to_vec())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl TryFrom<Message> for PauseResponse {
+    type Error = Error;
+
+    fn try_from(val: Message) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
+impl TryFrom<&Message> for PauseResponse {
+    type Error = Error;
+
+    fn try_from(val: &Message) -> Result<Self> {
+        match val.data.message_code().request_code()? {
+            RequestCode::Pause => Ok(Response::try_from(val)?.into()),
+            code => Err(Error::InvalidRequestCode(code.into())),
+        }
+    }
+}
+
+impl fmt::Display for PauseResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""code": {}"#, self.code)?;
+        if let Some(settings) = self.settings.as_ref() {
+            write!(f, r#", "settings": {settings}"#)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PauseMode;
+
+    fn settings() -> PauseSettings {
+        PauseSettings::new()
+            .with_duration_ms(1000)
+            .with_status_messages(true)
+            .with_event_messages(true)
+    }
+
+    #[test]
+    fn test_pause_response() {
+        for mode in [PauseMode::Get, PauseMode::Set] {
+            let raw = match mode {
+                PauseMode::Get => vec![ResponseCode::Ack as u8, 0xe8, 0x03, 0x03],
+                PauseMode::Set => vec![ResponseCode::Ack as u8],
+            };
+
+            let exp = match mode {
+                PauseMode::Get => PauseResponse::new()
+                    .with_code(ResponseCode::Ack)
+                    .with_settings(settings()),
+                PauseMode::Set => PauseResponse::new().with_code(ResponseCode::Ack),
+            };
+
+            let res = match mode {
+                PauseMode::Get => Response::new()
+                    .with_code(ResponseCode::Ack)
+                    .with_additional(raw[1..].as_ref()),
+                PauseMode::Set => Response::new().with_code(ResponseCode::Ack),
+            };
+
+            assert_eq!(PauseResponse::from_bytes(raw.as_ref()).as_ref(), Ok(&exp),);
+            assert_eq!(&PauseResponse::from(&res), &exp);
+            assert_eq!(Response::from(&exp), res);
+
+            let out = exp.into_bytes();
+
+            assert_eq!(out, raw);
+        }
+
+        assert_eq!(
+            PauseResponse::new()
+                .with_code(ResponseCode::Ack)
+                .with_settings(settings())
+                .to_string(),
+            r#"{"code": "affirmative response", "settings": {"duration_ms": 1000, "status_messages": true, "event_messages": true}}"#
+        );
+    }
+
+    #[test]
+    fn test_pause_response_invalid() {
+        assert!(PauseResponse::from_bytes(&[]).is_err());
+        assert!(PauseResponse::from_bytes([ResponseCode::Reserved as u8, 0].as_ref()).is_err());
+        assert!(
+            PauseResponse::from_bytes([ResponseCode::Ack as u8, 0xe8, 0x03, 0x04].as_ref())
+                .is_err()
+        );
+    }
+}
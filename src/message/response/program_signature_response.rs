@@ -1,10 +1,29 @@
-use std::fmt;
+use alloc::vec::Vec;
+use core::fmt;
 
 use crate::{AlgorithmNumber, Error, Message, Response, ResponseCode, Result};
 
-/// Represents the [Response] to a UID request [Message](crate::Message).
+/// Represents the [Response] to a [ProgramSignatureRequest](crate::ProgramSignatureRequest).
+///
+/// # Example
+///
+/// ```
+/// use jcm::{AlgorithmNumber, Message, ProgramSignatureResponse, ResponseCode};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, request type, request code, response code, data
+/// let frame = [0x12, 0x0a, 0x00, 0x10, 0x00, 0x10, 0x02, 0x00, 0x06, 0x01];
+///
+/// let res = ProgramSignatureResponse::try_from(Message::try_from(frame.as_ref())?)?;
+///
+/// assert_eq!(res.code(), ResponseCode::Ack);
+/// assert_eq!(res.algorithm_number(), AlgorithmNumber::Crc16);
+/// # Ok(())
+/// # }
+/// ```
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ProgramSignatureResponse {
     code: ResponseCode,
     algorithm: AlgorithmNumber,
@@ -1,4 +1,4 @@
-use std::{fmt, mem};
+use core::{fmt, mem};
 
 use crate::{Error, Result};
 
@@ -13,6 +13,7 @@ const RESERVED: u8 = 0xff;
 /// Represents response code variants for reponse messages.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ResponseCode {
     /// Affirmative response.
     Ack = ACK,
@@ -145,7 +146,7 @@ mod tests {
             ResponseCode::Collision,
         ];
 
-        for (raw, exp) in raw_vals.into_iter().zip(expected.into_iter()) {
+        for (raw, exp) in raw_vals.into_iter().zip(expected) {
             assert_eq!(ResponseCode::try_from(raw), Ok(exp));
             assert_eq!(ResponseCode::from_u8(raw), exp);
 
@@ -0,0 +1,206 @@
+use core::fmt;
+
+use crate::{
+    BarCodeResponse, CashBoxSizeResponse, ConditionalVendResponse, CurrencyAssignResponse,
+    DenominationDisableResponse, DirectionDisableResponse, Error, EventResendIntervalResponse,
+    InsertNotificationResponse, Message, ModelNameResponse, NearFullResponse, PauseResponse,
+    RequestCode, RequestType, Response, ResponseCode, Result, StatusResponse, UidResponse,
+    VersionResponse,
+};
+
+/// Represents the expected length of a [Response], including the [ResponseCode].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ResponseLen {
+    /// The response has exactly the provided length.
+    Exact(usize),
+    /// The response has at least the provided length.
+    AtLeast(usize),
+}
+
+impl ResponseLen {
+    /// Gets the minimum length of the response.
+    pub const fn min(&self) -> usize {
+        match self {
+            Self::Exact(len) | Self::AtLeast(len) => *len,
+        }
+    }
+
+    /// Gets the maximum length of the response, if bounded.
+    pub const fn max(&self) -> Option<usize> {
+        match self {
+            Self::Exact(len) => Some(*len),
+            Self::AtLeast(_) => None,
+        }
+    }
+
+    /// Gets whether the response length matches the [ResponseLen].
+    pub const fn matches(&self, len: usize) -> bool {
+        match self {
+            Self::Exact(exp) => len == *exp,
+            Self::AtLeast(exp) => len >= *exp,
+        }
+    }
+}
+
+impl fmt::Display for ResponseLen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exact(len) => write!(f, "{len}"),
+            Self::AtLeast(len) => write!(f, ">={len}"),
+        }
+    }
+}
+
+/// Gets the expected length of an `ACK` [Response] to a request, from the protocol tables.
+///
+/// Returns `None` for requests with no fixed response layout, e.g. multi-part image transfers,
+/// and for reserved codes.
+pub const fn expected_response_len(
+    code: RequestCode,
+    request_type: RequestType,
+) -> Option<ResponseLen> {
+    let ack_len = ResponseCode::len();
+
+    match (code, request_type) {
+        (
+            RequestCode::Reset
+            | RequestCode::Inhibit
+            | RequestCode::Idle
+            | RequestCode::Stack
+            | RequestCode::Reject
+            | RequestCode::Hold
+            | RequestCode::Collect
+            | RequestCode::AcceptorCollect,
+            RequestType::Operation,
+        ) => Some(ResponseLen::Exact(ack_len)),
+        (
+            RequestCode::Uid | RequestCode::DirectionDisable | RequestCode::DenominationDisable,
+            RequestType::SetFeature,
+        ) => Some(ResponseLen::Exact(ack_len)),
+        (RequestCode::Status, RequestType::Status) => {
+            Some(ResponseLen::AtLeast(StatusResponse::meta_len()))
+        }
+        (RequestCode::Uid, RequestType::Status) => Some(ResponseLen::Exact(UidResponse::len())),
+        (RequestCode::DirectionDisable, RequestType::Status) => {
+            Some(ResponseLen::Exact(DirectionDisableResponse::len()))
+        }
+        (RequestCode::NearFull, RequestType::Status) => {
+            Some(ResponseLen::Exact(NearFullResponse::len()))
+        }
+        (RequestCode::BarCode, RequestType::Status) => {
+            Some(ResponseLen::Exact(BarCodeResponse::len()))
+        }
+        (RequestCode::Insert, RequestType::Status) => {
+            Some(ResponseLen::Exact(InsertNotificationResponse::len()))
+        }
+        (RequestCode::ConditionalVend, RequestType::Status) => {
+            Some(ResponseLen::Exact(ConditionalVendResponse::len()))
+        }
+        (RequestCode::Pause, RequestType::Status) => Some(ResponseLen::Exact(PauseResponse::len())),
+        (RequestCode::EventResendInterval, RequestType::Status) => {
+            Some(ResponseLen::Exact(EventResendIntervalResponse::len()))
+        }
+        (RequestCode::CashBoxSize, RequestType::Status) => {
+            Some(ResponseLen::AtLeast(CashBoxSizeResponse::meta_len()))
+        }
+        (RequestCode::DenominationDisable, RequestType::Status) => {
+            Some(ResponseLen::AtLeast(DenominationDisableResponse::meta_len()))
+        }
+        (RequestCode::CurrencyAssign, RequestType::Status) => {
+            Some(ResponseLen::AtLeast(CurrencyAssignResponse::meta_len()))
+        }
+        (RequestCode::Version, RequestType::Status) => {
+            Some(ResponseLen::AtLeast(VersionResponse::meta_len()))
+        }
+        (RequestCode::ModelName, RequestType::Status) => {
+            Some(ResponseLen::AtLeast(ModelNameResponse::meta_len()))
+        }
+        _ => None,
+    }
+}
+
+/// Validates the length of a response [Message] against the expected length of an `ACK` to the
+/// request [Message].
+///
+/// Failure responses (e.g. `NAK`) may omit their data, and are not validated. Returns
+/// [Error::UnexpectedResponseLen] for truncated or padded `ACK` responses.
+pub fn validate_response_len(request: &Message, response: &Message) -> Result<()> {
+    let data = request.data();
+    let (code, request_type) = match (
+        data.message_code().request_code(),
+        data.message_type().request_type(),
+    ) {
+        (Ok(code), Ok(request_type)) => (code, request_type),
+        _ => return Ok(()),
+    };
+
+    let res = Response::try_from(response)?;
+
+    match expected_response_len(code, request_type) {
+        Some(exp) if res.code() == ResponseCode::Ack && !exp.matches(res.len()) => {
+            Err(Error::UnexpectedResponseLen((code, exp, res.len())))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Wraps a polling function, rejecting responses that fail [validate_response_len].
+///
+/// The typed response conversions accept truncated and padded responses where they can, to
+/// interoperate with nonstandard firmware. The strict decoder rejects them early instead. Apply
+/// [QuirkRegistry](crate::QuirkRegistry) normalization inside the strict decoder, so devices with
+/// known deviations still pass.
+pub fn strict_responses<P>(mut poll: P) -> impl FnMut(&Message) -> Result<Message>
+where
+    P: FnMut(&Message) -> Result<Message>,
+{
+    move |req: &Message| {
+        let res = poll(req)?;
+        validate_response_len(req, &res)?;
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DirectionDisableRequest, ResetRequest, UidRequest};
+
+    fn reply(req: &Message, additional: &[u8]) -> Message {
+        Message::new().with_data(req.data().clone().with_additional(additional))
+    }
+
+    #[test]
+    fn test_strict_responses() {
+        let ack = ResponseCode::Ack.into();
+        let nak = ResponseCode::Nak.into();
+
+        let uid = Message::from(UidRequest::new_get());
+        let reset = Message::from(ResetRequest::new());
+        let dirs = Message::from(DirectionDisableRequest::new());
+
+        assert_eq!(
+            expected_response_len(RequestCode::Uid, RequestType::Status),
+            Some(ResponseLen::Exact(2))
+        );
+        assert!(expected_response_len(RequestCode::Reserved, RequestType::Status).is_none());
+
+        assert!(validate_response_len(&uid, &reply(&uid, &[ack, 1])).is_ok());
+        assert!(validate_response_len(&uid, &reply(&uid, &[nak])).is_ok());
+        assert_eq!(
+            validate_response_len(&uid, &reply(&uid, &[ack])),
+            Err(Error::UnexpectedResponseLen((
+                RequestCode::Uid,
+                ResponseLen::Exact(2),
+                1
+            )))
+        );
+        assert!(validate_response_len(&reset, &reply(&reset, &[ack, 0])).is_err());
+
+        let mut poll = strict_responses(|req: &Message| Ok(reply(req, &[ack, 0, 0])));
+        assert!(poll(&reset).is_err());
+        assert!(poll(&dirs).is_err());
+    }
+}
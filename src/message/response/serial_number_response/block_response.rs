@@ -1,10 +1,11 @@
-use std::fmt;
+use core::fmt;
 
 use crate::{Error, ImageBlock, Message, Response, ResponseCode, Result};
 
 /// Represents the [Response] to a `Serial Number Image Block` request [Message](crate::Message).
 #[repr(C)]
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SerialNumberBlockResponse {
     code: ResponseCode,
     block: ImageBlock,
@@ -1,10 +1,11 @@
-use std::fmt;
+use core::fmt;
 
 use crate::{Error, ImageSize, Message, Response, ResponseCode, Result};
 
 /// Represents the [Response] to a `Serial Number Image Size` request [Message](crate::Message).
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SerialNumberSizeResponse {
     code: ResponseCode,
     size_total: ImageSize,
@@ -1,4 +1,4 @@
-use std::{cmp, fmt, mem};
+use core::{cmp, fmt, mem};
 
 use crate::{
     DeviceStatus, Error, Message, Response, ResponseCode, Result, UnitStatus, UnitStatusList,
@@ -7,9 +7,28 @@ use crate::{
 /// Maximum number of [UnitStatus] items in a [StatusResponse].
 pub const MAX_UNIT_STATUS_LEN: usize = u8::MAX as usize;
 
-/// Represents the [Response] to a UID request [Message](crate::Message).
+/// Represents the [Response] to a [StatusRequest](crate::StatusRequest).
+///
+/// # Example
+///
+/// ```
+/// use jcm::{MajorMinorStatus, Message, ResponseCode, StatusResponse};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, request type, request code, response code, data
+/// let frame = [0x12, 0x0c, 0x00, 0x10, 0x00, 0x10, 0x10, 0x00, 0x06, 0x02, 0x01, 0x11];
+///
+/// let res = StatusResponse::try_from(Message::try_from(frame.as_ref())?)?;
+///
+/// assert_eq!(res.code(), ResponseCode::Ack);
+/// assert_eq!(res.status().major_minor_status(), MajorMinorStatus::NormalIdle);
+/// assert!(res.unit_status().is_empty());
+/// # Ok(())
+/// # }
+/// ```
 #[repr(C)]
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StatusResponse {
     code: ResponseCode,
     status: DeviceStatus,
@@ -1,10 +1,28 @@
-use std::{fmt, mem};
+use core::{fmt, mem};
 
-use crate::{Error, Response, ResponseCode, Result};
+use crate::{Error, Message, Response, ResponseCode, Result};
 
 /// Represents the [Response] to a UID request [Message](crate::Message).
+///
+/// # Example
+///
+/// ```
+/// use jcm::{Message, ResponseCode, UidResponse};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // ID, length, conf ID, UID, request type, request code, response code, data
+/// let frame = [0x12, 0x0a, 0x00, 0x10, 0x00, 0x10, 0x01, 0x00, 0x06, 0x01];
+///
+/// let res = UidResponse::try_from(Message::try_from(frame.as_ref())?)?;
+///
+/// assert_eq!(res.code(), ResponseCode::Ack);
+/// assert_eq!(res.uid(), 1);
+/// # Ok(())
+/// # }
+/// ```
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UidResponse {
     code: ResponseCode,
     uid: u8,
@@ -107,7 +125,13 @@ impl TryFrom<&Response> for UidResponse {
         let len = Self::len();
         let res_len = val.len();
 
-        if res_len < len {
+        if res_len == Response::meta_len() && val.code != ResponseCode::Ack {
+            // failure responses may omit the UID, keep the code instead of discarding it
+            Ok(Self {
+                code: val.code,
+                uid: 0,
+            })
+        } else if res_len < len {
             Err(Error::InvalidResponseLen((res_len, len)))
         } else {
             Ok(Self {
@@ -126,6 +150,22 @@ impl TryFrom<Response> for UidResponse {
     }
 }
 
+impl TryFrom<&Message> for UidResponse {
+    type Error = Error;
+
+    fn try_from(val: &Message) -> Result<Self> {
+        Response::try_from(val)?.try_into()
+    }
+}
+
+impl TryFrom<Message> for UidResponse {
+    type Error = Error;
+
+    fn try_from(val: Message) -> Result<Self> {
+        (&val).try_into()
+    }
+}
+
 impl From<UidResponse> for Response {
     fn from(val: UidResponse) -> Self {
         Self {
@@ -190,4 +230,12 @@ mod tests {
         )
         .is_err());
     }
+
+    #[test]
+    fn test_uid_response_nak() {
+        let res = Response::new().with_code(ResponseCode::Nak);
+        let exp = UidResponse::new().with_code(ResponseCode::Nak);
+
+        assert_eq!(UidResponse::try_from(&res), Ok(exp));
+    }
 }
@@ -1,14 +1,18 @@
-use std::fmt;
+use alloc::vec::Vec;
+use core::fmt;
 
 use crate::{Error, Message, Response, ResponseCode, Result};
 
+mod firmware_revision;
 mod firmware_version;
 
+pub use firmware_revision::*;
 pub use firmware_version::*;
 
 /// Represents the response to a [VersionRequest](crate::VersionRequest).
 #[repr(C)]
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VersionResponse {
     code: ResponseCode,
     firmware_version: FirmwareVersion,
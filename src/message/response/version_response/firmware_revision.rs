@@ -0,0 +1,121 @@
+use core::fmt;
+
+/// Represents the numeric revision parsed from the version field of a
+/// [FirmwareVersion](crate::FirmwareVersion).
+///
+/// Revisions compare numerically, component by component, so `V1.10` is newer than `V1.9`.
+///
+/// # Example
+///
+/// ```
+/// use jcm::FirmwareRevision;
+///
+/// let rev = FirmwareRevision::parse("V1.10").unwrap();
+///
+/// assert_eq!(rev, FirmwareRevision::create(1, 10, 0));
+/// assert!(rev > FirmwareRevision::create(1, 9, 0));
+/// assert_eq!(FirmwareRevision::parse("unknown"), None);
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FirmwareRevision {
+    major: u16,
+    minor: u16,
+    patch: u16,
+}
+
+impl FirmwareRevision {
+    /// Creates a new [FirmwareRevision].
+    pub const fn new() -> Self {
+        Self {
+            major: 0,
+            minor: 0,
+            patch: 0,
+        }
+    }
+
+    /// Creates a new [FirmwareRevision] from the provided parameters.
+    pub const fn create(major: u16, minor: u16, patch: u16) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Gets the major revision number.
+    pub const fn major(&self) -> u16 {
+        self.major
+    }
+
+    /// Gets the minor revision number.
+    pub const fn minor(&self) -> u16 {
+        self.minor
+    }
+
+    /// Gets the patch revision number.
+    pub const fn patch(&self) -> u16 {
+        self.patch
+    }
+
+    /// Parses a [FirmwareRevision] from a firmware version string, e.g. `V1.10` or `1.02.3`.
+    ///
+    /// A leading `V`/`v` is ignored, and missing components default to zero. Returns `None` if
+    /// the string does not start with a numeric major revision.
+    pub fn parse(version: &str) -> Option<Self> {
+        let version = version.trim();
+        let version = version.strip_prefix(['V', 'v']).unwrap_or(version);
+
+        let mut parts = version.splitn(3, '.').map(|part| {
+            let digits = part
+                .find(|c: char| !c.is_ascii_digit())
+                .map(|end| &part[..end])
+                .unwrap_or(part);
+            digits.parse::<u16>().ok()
+        });
+
+        let major = parts.next().flatten()?;
+        let minor = parts.next().flatten().unwrap_or_default();
+        let patch = parts.next().flatten().unwrap_or_default();
+
+        Some(Self::create(major, minor, patch))
+    }
+}
+
+impl fmt::Display for FirmwareRevision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}.{}.{}""#, self.major, self.minor, self.patch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_firmware_revision() {
+        assert_eq!(
+            FirmwareRevision::parse("V2.01"),
+            Some(FirmwareRevision::create(2, 1, 0))
+        );
+        assert_eq!(
+            FirmwareRevision::parse("1.02.3"),
+            Some(FirmwareRevision::create(1, 2, 3))
+        );
+        assert_eq!(
+            FirmwareRevision::parse("v3"),
+            Some(FirmwareRevision::create(3, 0, 0))
+        );
+        assert_eq!(
+            FirmwareRevision::parse("V1.10b"),
+            Some(FirmwareRevision::create(1, 10, 0))
+        );
+        assert_eq!(FirmwareRevision::parse(""), None);
+        assert_eq!(FirmwareRevision::parse("Vx.1"), None);
+
+        assert!(FirmwareRevision::create(1, 10, 0) > FirmwareRevision::create(1, 9, 9));
+        assert!(FirmwareRevision::create(2, 0, 0) > FirmwareRevision::create(1, 10, 0));
+        assert_eq!(FirmwareRevision::create(1, 2, 3).to_string(), r#""1.2.3""#);
+    }
+}
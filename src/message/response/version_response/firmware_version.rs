@@ -1,11 +1,13 @@
-use std::ffi::CStr;
-use std::{fmt, mem};
+use alloc::{string::String, vec::Vec};
+use core::ffi::CStr;
+use core::{fmt, mem};
 
-use crate::{Error, Result};
+use crate::{Error, FirmwareRevision, Result};
 
 /// Represents the firmware version from a [VersionResponse](crate::VersionResponse).
 #[repr(C)]
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FirmwareVersion {
     firmware_name: String,
     interface_number: String,
@@ -88,6 +90,11 @@ impl FirmwareVersion {
         self
     }
 
+    /// Gets the numeric [FirmwareRevision] parsed from the version, if any.
+    pub fn revision(&self) -> Option<FirmwareRevision> {
+        FirmwareRevision::parse(&self.version)
+    }
+
     /// Gets the length of the [FirmwareVersion].
     pub fn len(&self) -> usize {
         self.firmware_name.len()
@@ -0,0 +1,517 @@
+//! Simulated JCM device for testing host logic without hardware.
+//!
+//! The [MockDevice] answers `Status`, `UID`, `Reset`, `Inhibit`, `Idle`, `Stack`, `Reject`, and
+//! `Hold` requests, and emits events for scripted note insertions and jams. Every message crossing the
+//! mock is round-tripped through its wire encoding, so framing errors surface in tests.
+//!
+//! Timing, e.g. the `Hold` timeout, follows the [Clock](crate::Clock) passed to
+//! [MockDevice::create], so tests can expire a hold instantly by advancing a
+//! [SimulatedClock](crate::SimulatedClock).
+//!
+//! The [MockDevice] implements [DeviceTransport], so it can stand in for a
+//! [UsbDeviceHandle](crate::usb::UsbDeviceHandle) in the polling functions.
+//!
+//! ```
+//! use jcm::{Currency, EventCode, IdleRequest, Message, StackRequest};
+//! use jcm::mock::MockDevice;
+//!
+//! # fn main() -> jcm::Result<()> {
+//! let device = MockDevice::new();
+//!
+//! // acknowledge the `Power Up` event, and enable the device
+//! let power_up = device.pending_event().unwrap();
+//! device.acknowledge_event(&jcm::event_ack(&power_up))?;
+//! device.handle_request(&IdleRequest::new().into())?;
+//! device.acknowledge_event(&jcm::event_ack(&device.pending_event().unwrap()))?;
+//!
+//! assert!(device.insert_note(Currency::new()));
+//!
+//! let escrow = device.pending_event().unwrap();
+//! assert_eq!(escrow.data().message_code().event_code(), Ok(EventCode::Escrow));
+//! device.acknowledge_event(&jcm::event_ack(&escrow))?;
+//!
+//! device.handle_request(&StackRequest::new().into())?;
+//! let vend = device.pending_event().unwrap();
+//! assert_eq!(vend.data().message_code().event_code(), Ok(EventCode::VendValid));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::{fmt, time};
+
+use crate::{
+    Clock, Currency, DeviceStatus, DeviceTransport, EscrowData, EscrowEvent, Event, EventCode,
+    EventType, FailureCode, FuncId, HoldRequest, MajorMinorStatus, Message, MessageData,
+    MessageType, RequestCode, RequestType, ResponseCode, Result, SystemClock, DEFAULT_HOLD_TIMEOUT,
+};
+
+#[derive(Debug)]
+struct MockState {
+    uid: u8,
+    status: MajorMinorStatus,
+    sequence: u8,
+    events: VecDeque<Message>,
+    responses: VecDeque<Message>,
+    hold_deadline: Option<time::Duration>,
+}
+
+impl MockState {
+    fn expire_hold(&mut self, now: time::Duration) {
+        if self.hold_deadline.is_some_and(|deadline| now >= deadline) {
+            // the host let the hold lapse, so the device returns the note
+            self.hold_deadline = None;
+            self.status = MajorMinorStatus::NormalReturned;
+            self.push_event(EventCode::Returned, &[]);
+        }
+    }
+
+    fn push_event(&mut self, code: EventCode, additional: &[u8]) {
+        let event_type = EventType::from_u8(0x80 | (self.sequence & 0xf));
+        self.sequence = self.sequence.wrapping_add(1);
+
+        self.events
+            .push_back(Event::create(event_type, code, additional).into_message(self.uid));
+    }
+
+    fn push_escrow(&mut self, currency: Currency) {
+        let event_type = EventType::from_u8(0x80 | (self.sequence & 0xf));
+        self.sequence = self.sequence.wrapping_add(1);
+
+        self.events.push_back(
+            EscrowEvent::create(event_type, EscrowData::Currency(currency)).into_message(self.uid),
+        );
+    }
+}
+
+/// Represents a simulated JCM device.
+///
+/// Events stay pending, and are resent by [pending_event](Self::pending_event), until the host
+/// acknowledges them, like on a real device.
+///
+/// A note held in escrow by a `Hold` request is returned once the hold timeout elapses on the
+/// device [Clock], unless the host stacks, rejects, or holds it again.
+pub struct MockDevice {
+    state: Mutex<MockState>,
+    clock: Arc<dyn Clock>,
+}
+
+impl MockDevice {
+    /// Creates a new [MockDevice] that just powered up, timed by the [SystemClock].
+    pub fn new() -> Self {
+        Self::create(SystemClock::new())
+    }
+
+    /// Creates a new [MockDevice] that just powered up, timed by the provided [Clock].
+    pub fn create<C: Clock + 'static>(clock: C) -> Self {
+        let mut state = MockState {
+            uid: 0,
+            status: MajorMinorStatus::PowerUp,
+            sequence: 0,
+            events: VecDeque::new(),
+            responses: VecDeque::new(),
+            hold_deadline: None,
+        };
+        state.push_event(EventCode::PowerUp, &[]);
+
+        Self {
+            state: Mutex::new(state),
+            clock: Arc::new(clock),
+        }
+    }
+
+    /// Gets the UID of the [MockDevice].
+    pub fn uid(&self) -> u8 {
+        self.with_state(|state| state.uid)
+    }
+
+    /// Gets the [DeviceStatus] of the [MockDevice].
+    pub fn status(&self) -> DeviceStatus {
+        self.with_state(|state| DeviceStatus::create(FuncId::Acceptor, state.status))
+    }
+
+    /// Simulates a customer inserting a note.
+    ///
+    /// Returns `false` if the device is not accepting notes.
+    pub fn insert_note(&self, currency: Currency) -> bool {
+        self.with_state(|state| {
+            if state.status == MajorMinorStatus::NormalIdle {
+                state.status = MajorMinorStatus::NormalEscrow;
+                state.push_escrow(currency);
+                true
+            } else {
+                false
+            }
+        })
+    }
+
+    /// Simulates a note jam, or any other fatal acceptor error.
+    ///
+    /// The device sends an `Acceptor Failure` event, and refuses to accept notes until the jam is
+    /// [cleared](Self::clear_jam). Returns `false` if the device already failed.
+    pub fn jam(&self, failure_code: FailureCode) -> bool {
+        self.with_state(|state| {
+            if matches!(state.status, MajorMinorStatus::AbnormalFailure(_)) {
+                false
+            } else {
+                state.status = MajorMinorStatus::AbnormalFailure(failure_code);
+                state.push_event(EventCode::AcceptorFailure, &[failure_code.into()]);
+                true
+            }
+        })
+    }
+
+    /// Simulates an operator clearing a jam.
+    ///
+    /// The device sends an `Acceptor Clear` event, and stays inhibited until the host enables it
+    /// again. Returns `false` if the device did not fail.
+    pub fn clear_jam(&self) -> bool {
+        self.with_state(|state| {
+            if matches!(state.status, MajorMinorStatus::AbnormalFailure(_)) {
+                state.status = MajorMinorStatus::Normal;
+                state.push_event(EventCode::AcceptorClear, &[]);
+                true
+            } else {
+                false
+            }
+        })
+    }
+
+    /// Gets the oldest unacknowledged event [Message], if any.
+    pub fn pending_event(&self) -> Option<Message> {
+        self.with_state(|state| state.events.front().cloned())
+    }
+
+    /// Handles the host response to the oldest pending event.
+    ///
+    /// Returns an error if the response is malformed, or does not match the pending event.
+    pub fn acknowledge_event(&self, response: &Message) -> Result<()> {
+        let response = round_trip(response)?;
+
+        self.with_state(|state| {
+            let event = match state.events.front() {
+                Some(event) => event,
+                None => return Ok(()),
+            };
+
+            let (have, exp) = (response.data(), event.data());
+            if have.message_type() != exp.message_type()
+                || have.message_code() != exp.message_code()
+            {
+                return Err(crate::Error::InvalidMessage((
+                    (have.message_type().into(), have.message_code().into()),
+                    (exp.message_type().into(), exp.message_code().into()),
+                )));
+            }
+
+            match have
+                .additional()
+                .first()
+                .copied()
+                .map(ResponseCode::from_u8)
+            {
+                Some(ResponseCode::Ack) => (),
+                Some(code) => return Err(crate::Error::InvalidResponseCode(code.into())),
+                None => return Err(crate::Error::InvalidResponseLen((0, ResponseCode::len()))),
+            }
+
+            if let Some(event) = state.events.pop_front() {
+                match event.data().message_code().event_code() {
+                    Ok(EventCode::VendValid) => {
+                        state.status = MajorMinorStatus::NormalIdle;
+                        state.push_event(EventCode::AcceptorCollected, &[]);
+                        state.push_event(EventCode::Idle, &[]);
+                    }
+                    Ok(EventCode::Returned) => {
+                        state.status = MajorMinorStatus::NormalIdle;
+                        state.push_event(EventCode::Idle, &[]);
+                    }
+                    _ => (),
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Handles a request [Message] from the host, returning the response [Message].
+    ///
+    /// Returns an error if the request is malformed.
+    pub fn handle_request(&self, request: &Message) -> Result<Message> {
+        let request = round_trip(request)?;
+        let data = request.data();
+
+        let code = data.message_code().request_code()?;
+        let request_type = data.message_type().request_type()?;
+
+        let now = self.clock.now();
+        let response = self.with_state(|state| {
+            let (res, additional): (ResponseCode, Vec<u8>) = match (code, request_type) {
+                (RequestCode::Status, RequestType::Status) => {
+                    let status = DeviceStatus::create(FuncId::Acceptor, state.status);
+                    let mut additional = vec![DeviceStatus::len() as u8];
+                    additional.extend(status.to_bytes());
+                    (ResponseCode::Ack, additional)
+                }
+                (RequestCode::Uid, RequestType::Status) => (ResponseCode::Ack, vec![state.uid]),
+                (RequestCode::Uid, RequestType::SetFeature) => match data.additional().first() {
+                    Some(&uid) => {
+                        state.uid = uid;
+                        (ResponseCode::Ack, Vec::new())
+                    }
+                    None => (ResponseCode::Nak, Vec::new()),
+                },
+                (RequestCode::Reset, RequestType::Operation) => {
+                    state.events.clear();
+                    state.status = MajorMinorStatus::Normal;
+                    state.push_event(EventCode::Inhibit, &[]);
+                    (ResponseCode::Ack, Vec::new())
+                }
+                (RequestCode::Inhibit, RequestType::Operation) => {
+                    if state.status != MajorMinorStatus::Normal {
+                        state.status = MajorMinorStatus::Normal;
+                        state.push_event(EventCode::Inhibit, &[]);
+                    }
+                    (ResponseCode::Ack, Vec::new())
+                }
+                (RequestCode::Idle, RequestType::Operation)
+                    if matches!(state.status, MajorMinorStatus::AbnormalFailure(_)) =>
+                {
+                    (ResponseCode::Nak, Vec::new())
+                }
+                (RequestCode::Idle, RequestType::Operation) => {
+                    if state.status != MajorMinorStatus::NormalIdle {
+                        state.status = MajorMinorStatus::NormalIdle;
+                        state.push_event(EventCode::Idle, &[]);
+                    }
+                    (ResponseCode::Ack, Vec::new())
+                }
+                (RequestCode::Stack, RequestType::Operation)
+                    if state.status == MajorMinorStatus::NormalEscrow =>
+                {
+                    state.hold_deadline = None;
+                    state.status = MajorMinorStatus::NormalVendValid;
+                    state.push_event(EventCode::VendValid, &[]);
+                    (ResponseCode::Ack, Vec::new())
+                }
+                (RequestCode::Reject, RequestType::Operation)
+                    if state.status == MajorMinorStatus::NormalEscrow =>
+                {
+                    state.hold_deadline = None;
+                    state.status = MajorMinorStatus::NormalReturned;
+                    state.push_event(EventCode::Returned, &[]);
+                    (ResponseCode::Ack, Vec::new())
+                }
+                (RequestCode::Hold, RequestType::Operation)
+                    if state.status == MajorMinorStatus::NormalEscrow =>
+                {
+                    let secs = HoldRequest::try_from(&request)
+                        .ok()
+                        .and_then(|req| req.timeout())
+                        .map(|timeout| timeout.to_u16())
+                        .unwrap_or(DEFAULT_HOLD_TIMEOUT);
+                    state.hold_deadline = Some(now + time::Duration::from_secs(secs.into()));
+                    (ResponseCode::Ack, Vec::new())
+                }
+                (
+                    RequestCode::Stack | RequestCode::Reject | RequestCode::Hold,
+                    RequestType::Operation,
+                ) => (ResponseCode::Nak, Vec::new()),
+                _ => (ResponseCode::Unsupported, Vec::new()),
+            };
+
+            let additional = [res.into()]
+                .into_iter()
+                .chain(additional)
+                .collect::<Vec<u8>>();
+
+            Message::new().with_data(
+                MessageData::new()
+                    .with_uid(state.uid)
+                    .with_message_type(data.message_type())
+                    .with_message_code(data.message_code())
+                    .with_additional(additional.as_ref()),
+            )
+        });
+
+        round_trip(&response)
+    }
+
+    fn with_state<T>(&self, f: impl FnOnce(&mut MockState) -> T) -> T {
+        let now = self.clock.now();
+        let mut state = match self.state.lock() {
+            Ok(state) => state,
+            Err(err) => err.into_inner(),
+        };
+
+        state.expire_hold(now);
+        f(&mut state)
+    }
+}
+
+impl fmt::Debug for MockDevice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MockDevice")
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for MockDevice {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeviceTransport for MockDevice {
+    fn write_message(&self, message: &Message) -> Result<()> {
+        let response = self.handle_request(message)?;
+        self.with_state(|state| state.responses.push_back(response));
+        Ok(())
+    }
+
+    fn read_message(&self) -> Result<Message> {
+        self.with_state(|state| state.responses.pop_front())
+            .or_else(|| self.pending_event())
+            .ok_or(crate::Error::Timeout("mock device read".into()))
+    }
+
+    fn write_event_response(&self, message: &Message) -> Result<()> {
+        self.acknowledge_event(message)
+    }
+}
+
+fn round_trip(message: &Message) -> Result<Message> {
+    let bytes: Vec<u8> = message.into();
+    let decoded = Message::try_from(bytes.as_slice())?;
+
+    if decoded.data().message_type() == MessageType::Reserved {
+        Err(crate::Error::InvalidMessageType(
+            MessageType::Reserved.into(),
+        ))
+    } else {
+        Ok(decoded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event_ack, IdleRequest, RejectRequest, SimulatedClock, StackRequest, StatusRequest,
+    };
+
+    fn ack_next(device: &MockDevice) -> Result<EventCode> {
+        let event = device.pending_event().unwrap();
+        device.acknowledge_event(&event_ack(&event))?;
+        event.data().message_code().event_code()
+    }
+
+    #[test]
+    fn test_mock_device() -> Result<()> {
+        let device = MockDevice::new();
+
+        // events are resent until acknowledged
+        assert_eq!(device.pending_event(), device.pending_event());
+        assert_eq!(ack_next(&device)?, EventCode::PowerUp);
+        assert_eq!(device.pending_event(), None);
+
+        assert!(!device.insert_note(Currency::new()));
+
+        device.handle_request(&IdleRequest::new().into())?;
+        assert_eq!(ack_next(&device)?, EventCode::Idle);
+
+        let status = crate::poll_typed(StatusRequest::new(), |req| device.handle_request(req))?;
+        assert_eq!(status.code(), ResponseCode::Ack);
+        assert_eq!(
+            status.status().major_minor_status(),
+            MajorMinorStatus::NormalIdle
+        );
+
+        assert!(device.insert_note(Currency::new()));
+        assert_eq!(ack_next(&device)?, EventCode::Escrow);
+
+        let res = device.handle_request(&StackRequest::new().into())?;
+        assert_eq!(res.data().additional(), [u8::from(ResponseCode::Ack)]);
+
+        let vend = device.pending_event().unwrap();
+        assert!(device
+            .acknowledge_event(&event_ack(&IdleRequest::new().into()))
+            .is_err());
+        device.acknowledge_event(&event_ack(&vend))?;
+        assert_eq!(ack_next(&device)?, EventCode::AcceptorCollected);
+        assert_eq!(ack_next(&device)?, EventCode::Idle);
+
+        let res = device.handle_request(&RejectRequest::new().into())?;
+        assert_eq!(res.data().additional(), [u8::from(ResponseCode::Nak)]);
+
+        assert!(device.jam(FailureCode::TransportMotor));
+        assert!(!device.jam(FailureCode::TransportMotor));
+        assert_eq!(ack_next(&device)?, EventCode::AcceptorFailure);
+        assert!(!device.insert_note(Currency::new()));
+
+        let res = device.handle_request(&IdleRequest::new().into())?;
+        assert_eq!(res.data().additional(), [u8::from(ResponseCode::Nak)]);
+
+        assert!(device.clear_jam());
+        assert_eq!(ack_next(&device)?, EventCode::AcceptorClear);
+        assert_eq!(
+            device.status().major_minor_status(),
+            MajorMinorStatus::Normal
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mock_device_hold_timeout() -> Result<()> {
+        let clock = SimulatedClock::new();
+        let device = MockDevice::create(clock.clone());
+
+        assert_eq!(ack_next(&device)?, EventCode::PowerUp);
+        device.handle_request(&IdleRequest::new().into())?;
+        assert_eq!(ack_next(&device)?, EventCode::Idle);
+
+        assert!(device.insert_note(Currency::new()));
+        assert_eq!(ack_next(&device)?, EventCode::Escrow);
+
+        let res = device.handle_request(&HoldRequest::create(5).into())?;
+        assert_eq!(res.data().additional(), [u8::from(ResponseCode::Ack)]);
+
+        // holding again restarts the timeout
+        clock.advance(time::Duration::from_secs(4));
+        device.handle_request(&HoldRequest::create(5).into())?;
+        clock.advance(time::Duration::from_secs(4));
+        assert_eq!(device.pending_event(), None);
+        assert_eq!(
+            device.status().major_minor_status(),
+            MajorMinorStatus::NormalEscrow
+        );
+
+        // the hold lapses, and the device returns the note
+        clock.advance(time::Duration::from_secs(1));
+        assert_eq!(
+            device.status().major_minor_status(),
+            MajorMinorStatus::NormalReturned
+        );
+        let res = device.handle_request(&StackRequest::new().into())?;
+        assert_eq!(res.data().additional(), [u8::from(ResponseCode::Nak)]);
+
+        assert_eq!(ack_next(&device)?, EventCode::Returned);
+        assert_eq!(ack_next(&device)?, EventCode::Idle);
+
+        // stacking a held note cancels the timeout
+        assert!(device.insert_note(Currency::new()));
+        assert_eq!(ack_next(&device)?, EventCode::Escrow);
+        device.handle_request(&HoldRequest::new().into())?;
+        device.handle_request(&StackRequest::new().into())?;
+        clock.advance(time::Duration::from_secs(DEFAULT_HOLD_TIMEOUT.into()));
+        assert_eq!(ack_next(&device)?, EventCode::VendValid);
+        assert_eq!(ack_next(&device)?, EventCode::AcceptorCollected);
+        assert_eq!(ack_next(&device)?, EventCode::Idle);
+        assert_eq!(device.pending_event(), None);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,268 @@
+use alloc::string::String;
+use core::{cmp, fmt};
+
+use crate::{minor_units, Currency, CurrencyCode, Denomination, Error, Result};
+
+/// Represents an exact amount of money, stored in ISO 4217 minor units of the currency.
+///
+/// Arithmetic is checked, and only defined between amounts of the same currency. Amounts of
+/// different currencies are not comparable.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{Currency, CurrencyCode, Denomination, MonetaryAmount};
+///
+/// let note = Currency::new()
+///     .with_code(CurrencyCode::USD)
+///     .with_denomination(Denomination::from_value(20));
+///
+/// let amount = MonetaryAmount::from(note);
+/// assert_eq!(amount.minor_value(), 2000);
+/// assert_eq!(amount.format(), "$20.00");
+///
+/// let total = amount
+///     .checked_add(MonetaryAmount::create(CurrencyCode::USD, 50))
+///     .unwrap();
+/// assert_eq!(total.format(), "$20.50");
+///
+/// let yen = MonetaryAmount::from_major(CurrencyCode::JPY, 1000).unwrap();
+/// assert_eq!(yen.format(), "¥1000");
+/// assert_eq!(total.checked_add(yen), None);
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MonetaryAmount {
+    code: CurrencyCode,
+    minor_value: u64,
+}
+
+impl MonetaryAmount {
+    /// Creates a new, zero [MonetaryAmount].
+    pub const fn new() -> Self {
+        Self {
+            code: CurrencyCode::XXX,
+            minor_value: 0,
+        }
+    }
+
+    /// Creates a new [MonetaryAmount] from a value in minor units, e.g. cents.
+    pub const fn create(code: CurrencyCode, minor_value: u64) -> Self {
+        Self { code, minor_value }
+    }
+
+    /// Creates a new [MonetaryAmount] from a value in major units, e.g. dollars.
+    ///
+    /// Returns `None` if the value overflows in minor units.
+    pub fn from_major(code: CurrencyCode, major_value: u64) -> Option<Self> {
+        major_value
+            .checked_mul(Self::scale(code))
+            .map(|minor_value| Self::create(code, minor_value))
+    }
+
+    /// Creates a new [MonetaryAmount] from a [Denomination] of the currency.
+    pub fn from_denomination(code: CurrencyCode, denomination: Denomination) -> Option<Self> {
+        Self::from_major(code, denomination.value())
+    }
+
+    /// Gets the [CurrencyCode] of the [MonetaryAmount].
+    pub const fn code(&self) -> CurrencyCode {
+        self.code
+    }
+
+    /// Gets the number of ISO 4217 minor units (decimal places) of the currency.
+    pub const fn minor_units(&self) -> u8 {
+        minor_units(self.code)
+    }
+
+    /// Gets the value in minor units.
+    pub const fn minor_value(&self) -> u64 {
+        self.minor_value
+    }
+
+    /// Gets the whole part of the value in major units.
+    pub fn major_value(&self) -> u64 {
+        self.minor_value / Self::scale(self.code)
+    }
+
+    /// Gets the fractional part of the value, in minor units.
+    pub fn fraction(&self) -> u64 {
+        self.minor_value % Self::scale(self.code)
+    }
+
+    /// Gets whether the [MonetaryAmount] is zero.
+    pub const fn is_zero(&self) -> bool {
+        self.minor_value == 0
+    }
+
+    /// Adds two amounts of the same currency.
+    ///
+    /// Returns `None` if the currencies differ, or on overflow.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.same_code(&rhs)?;
+        self.minor_value
+            .checked_add(rhs.minor_value)
+            .map(|minor_value| Self::create(self.code, minor_value))
+    }
+
+    /// Subtracts an amount of the same currency.
+    ///
+    /// Returns `None` if the currencies differ, or if `rhs` is larger.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.same_code(&rhs)?;
+        self.minor_value
+            .checked_sub(rhs.minor_value)
+            .map(|minor_value| Self::create(self.code, minor_value))
+    }
+
+    /// Multiplies the amount, e.g. by a note count.
+    ///
+    /// Returns `None` on overflow.
+    pub fn checked_mul(self, rhs: u64) -> Option<Self> {
+        self.minor_value
+            .checked_mul(rhs)
+            .map(|minor_value| Self::create(self.code, minor_value))
+    }
+
+    /// Formats the amount with the currency symbol, e.g. `$20.00` or `¥1000`.
+    ///
+    /// Currencies without a well-known symbol are suffixed by the currency code, e.g.
+    /// `20.00 CHF`.
+    pub fn format(&self) -> String {
+        let value = self.format_value();
+
+        match Self::symbol(self.code) {
+            Some(symbol) => format!("{symbol}{value}"),
+            None => format!("{value} {}", <&str>::from(self.code)),
+        }
+    }
+
+    fn format_value(&self) -> String {
+        match self.minor_units() as usize {
+            0 => format!("{}", self.major_value()),
+            units => format!("{}.{:0units$}", self.major_value(), self.fraction()),
+        }
+    }
+
+    const fn symbol(code: CurrencyCode) -> Option<&'static str> {
+        match code {
+            CurrencyCode::USD => Some("$"),
+            CurrencyCode::EUR => Some("€"),
+            CurrencyCode::GBP => Some("£"),
+            CurrencyCode::JPY | CurrencyCode::CNY => Some("¥"),
+            CurrencyCode::KRW => Some("₩"),
+            CurrencyCode::INR => Some("₹"),
+            _ => None,
+        }
+    }
+
+    fn scale(code: CurrencyCode) -> u64 {
+        10u64.pow(minor_units(code) as u32)
+    }
+
+    fn same_code(&self, rhs: &Self) -> Option<()> {
+        (self.code == rhs.code).then_some(())
+    }
+}
+
+impl Default for MonetaryAmount {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialOrd for MonetaryAmount {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        self.same_code(other)?;
+        self.minor_value.partial_cmp(&other.minor_value)
+    }
+}
+
+impl From<Currency> for MonetaryAmount {
+    /// Converts the [Currency] denomination into a [MonetaryAmount], saturating on overflow.
+    fn from(val: Currency) -> Self {
+        Self::create(val.code(), val.minor_value())
+    }
+}
+
+impl From<&Currency> for MonetaryAmount {
+    fn from(val: &Currency) -> Self {
+        (*val).into()
+    }
+}
+
+impl TryFrom<MonetaryAmount> for Denomination {
+    type Error = Error;
+
+    /// Converts a whole [MonetaryAmount] into a [Denomination].
+    ///
+    /// Fails if the amount has a fractional part, or is not representable as a [Denomination].
+    fn try_from(val: MonetaryAmount) -> Result<Self> {
+        let major = val.major_value();
+        let denomination = Denomination::from_value(major);
+
+        if val.fraction() != 0 || !denomination.is_valid() || denomination.value() != major {
+            Err(Error::InvalidMonetaryAmount(val.format()))
+        } else {
+            Ok(denomination)
+        }
+    }
+}
+
+impl TryFrom<MonetaryAmount> for Currency {
+    type Error = Error;
+
+    fn try_from(val: MonetaryAmount) -> Result<Self> {
+        Ok(Self::new()
+            .with_code(val.code())
+            .with_denomination(val.try_into()?))
+    }
+}
+
+impl fmt::Display for MonetaryAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, self.format())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_monetary_amount() -> Result<()> {
+        let usd = |minor| MonetaryAmount::create(CurrencyCode::USD, minor);
+
+        assert_eq!(usd(2005).format(), "$20.05");
+        assert_eq!(usd(2005).major_value(), 20);
+        assert_eq!(usd(2005).fraction(), 5);
+        assert_eq!(
+            MonetaryAmount::create(CurrencyCode::KWD, 1500).format(),
+            "1.500 KWD"
+        );
+        assert_eq!(usd(100).to_string(), r#""$1.00""#);
+
+        assert_eq!(usd(100).checked_sub(usd(101)), None);
+        assert_eq!(usd(u64::MAX).checked_add(usd(1)), None);
+        assert_eq!(usd(500).checked_mul(3), Some(usd(1500)));
+        assert!(usd(100) < usd(200));
+        assert_eq!(
+            usd(100).partial_cmp(&MonetaryAmount::create(CurrencyCode::EUR, 100)),
+            None
+        );
+
+        let note = Currency::try_from(usd(10_000))?;
+        assert_eq!(note.code(), CurrencyCode::USD);
+        assert_eq!(note.denomination().value(), 100);
+        assert_eq!(MonetaryAmount::from(note), usd(10_000));
+
+        assert_eq!(
+            Denomination::try_from(usd(150)),
+            Err(Error::InvalidMonetaryAmount("$1.50".into()))
+        );
+        assert!(Denomination::try_from(usd(257 * 100)).is_err());
+
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use std::fmt;
+use core::fmt;
 
 use crate::{Error, Result};
 
@@ -17,6 +17,7 @@ pub const NEAR_FULL_DATA_LEN: usize = 3;
 /// to the host when the threshold number of banknotes are inserted into storage.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NearFullData {
     status: NearFullStatus,
     number: NearFullNumber,
@@ -99,7 +100,7 @@ impl Default for NearFullData {
 
 impl IntoIterator for NearFullData {
     type Item = u8;
-    type IntoIter = std::array::IntoIter<u8, NEAR_FULL_DATA_LEN>;
+    type IntoIter = core::array::IntoIter<u8, NEAR_FULL_DATA_LEN>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.into_bytes().into_iter()
@@ -1,4 +1,4 @@
-use std::fmt;
+use core::fmt;
 
 use crate::{Error, Result};
 
@@ -8,6 +8,7 @@ pub const NEAR_FULL_NUMBER_LEN: usize = 2;
 /// Represents the threshold number of banknotes to send a `Near Full` event message.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NearFullNumber(u16);
 
 impl NearFullNumber {
@@ -49,7 +50,7 @@ impl Default for NearFullNumber {
 
 impl IntoIterator for NearFullNumber {
     type Item = u8;
-    type IntoIter = std::array::IntoIter<u8, NEAR_FULL_NUMBER_LEN>;
+    type IntoIter = core::array::IntoIter<u8, NEAR_FULL_NUMBER_LEN>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.into_bytes().into_iter()
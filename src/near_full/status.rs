@@ -1,4 +1,4 @@
-use std::fmt;
+use core::fmt;
 
 use crate::{Error, Result};
 
@@ -11,6 +11,7 @@ const ENABLED: u8 = 1;
 /// Represents whether the `Near Full` feature is enabled.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NearFullStatus {
     Disabled = DISABLED,
     Enabled = ENABLED,
@@ -0,0 +1,133 @@
+use std::sync::Arc;
+
+use crate::{Error, Message};
+
+/// Receives notifications about the progress of a polled request.
+///
+/// All methods have empty default implementations, so observers only override the hooks they
+/// need, e.g. for custom logging, tracing spans, metrics, or UI progress.
+///
+/// The `attempt` argument is zero-based.
+pub trait PollObserver: Send + Sync {
+    /// Called after a request [Message] is written to the device.
+    fn on_request_sent(&self, _request: &Message, _attempt: usize) {}
+
+    /// Called when the matching response [Message] is received.
+    fn on_response(&self, _request: &Message, _response: &Message, _attempt: usize) {}
+
+    /// Called when an attempt fails, and the request will be retried.
+    fn on_retry(&self, _request: &Message, _attempt: usize, _err: &Error) {}
+
+    /// Called when the request fails after all retries.
+    fn on_failure(&self, _request: &Message, _err: &Error) {}
+
+    /// Called for a response that matches no outstanding request, e.g. a late arrival after a
+    /// previous request timed out.
+    fn on_unsolicited(&self, _response: &Message) {}
+
+    /// Called for a device-initiated `Status` message received while polling.
+    ///
+    /// See [StatusMessageMode::Unsolicited](crate::StatusMessageMode::Unsolicited).
+    fn on_status(&self, _status: &Message) {}
+}
+
+impl<O: PollObserver + ?Sized> PollObserver for &O {
+    fn on_request_sent(&self, request: &Message, attempt: usize) {
+        (**self).on_request_sent(request, attempt)
+    }
+
+    fn on_response(&self, request: &Message, response: &Message, attempt: usize) {
+        (**self).on_response(request, response, attempt)
+    }
+
+    fn on_retry(&self, request: &Message, attempt: usize, err: &Error) {
+        (**self).on_retry(request, attempt, err)
+    }
+
+    fn on_failure(&self, request: &Message, err: &Error) {
+        (**self).on_failure(request, err)
+    }
+
+    fn on_unsolicited(&self, response: &Message) {
+        (**self).on_unsolicited(response)
+    }
+
+    fn on_status(&self, status: &Message) {
+        (**self).on_status(status)
+    }
+}
+
+impl<O: PollObserver + ?Sized> PollObserver for Arc<O> {
+    fn on_request_sent(&self, request: &Message, attempt: usize) {
+        (**self).on_request_sent(request, attempt)
+    }
+
+    fn on_response(&self, request: &Message, response: &Message, attempt: usize) {
+        (**self).on_response(request, response, attempt)
+    }
+
+    fn on_retry(&self, request: &Message, attempt: usize, err: &Error) {
+        (**self).on_retry(request, attempt, err)
+    }
+
+    fn on_failure(&self, request: &Message, err: &Error) {
+        (**self).on_failure(request, err)
+    }
+
+    fn on_unsolicited(&self, response: &Message) {
+        (**self).on_unsolicited(response)
+    }
+
+    fn on_status(&self, status: &Message) {
+        (**self).on_status(status)
+    }
+}
+
+/// Represents a [PollObserver] that ignores all notifications.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct NoopObserver;
+
+impl NoopObserver {
+    /// Creates a new [NoopObserver].
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl PollObserver for NoopObserver {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<String>>);
+
+    impl PollObserver for Recorder {
+        fn on_request_sent(&self, _request: &Message, attempt: usize) {
+            self.0.lock().unwrap().push(format!("sent {attempt}"));
+        }
+
+        fn on_failure(&self, _request: &Message, err: &Error) {
+            self.0.lock().unwrap().push(format!("failed: {err}"));
+        }
+    }
+
+    #[test]
+    fn test_poll_observer() {
+        let recorder = Arc::new(Recorder::default());
+        let observer: Arc<dyn PollObserver> = recorder.clone();
+        let msg = Message::new();
+
+        observer.on_request_sent(&msg, 0);
+        observer.on_retry(&msg, 0, &Error::Cancelled);
+        observer.on_response(&msg, &msg, 1);
+        observer.on_failure(&msg, &Error::Cancelled);
+
+        assert_eq!(
+            recorder.0.lock().unwrap().as_slice(),
+            ["sent 0", "failed: operation cancelled"]
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use core::fmt;
+
+use crate::{Error, Result};
+
+const FACE_UP_FORWARD: u8 = 0;
+const FACE_UP_BACKWARD: u8 = 1;
+const FACE_DOWN_FORWARD: u8 = 2;
+const FACE_DOWN_BACKWARD: u8 = 3;
+const RESERVED: u8 = 0xff;
+
+/// Represents the orientation of a note inserted into the device.
+///
+/// Forward orientations are inserted left side first, backward orientations right side first.
+/// The raw values match the bit positions of [InhibitDirection](crate::InhibitDirection).
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Orientation {
+    /// Face up, left side first.
+    FaceUpForward = FACE_UP_FORWARD,
+    /// Face up, right side first.
+    FaceUpBackward = FACE_UP_BACKWARD,
+    /// Face down, left side first.
+    FaceDownForward = FACE_DOWN_FORWARD,
+    /// Face down, right side first.
+    FaceDownBackward = FACE_DOWN_BACKWARD,
+    /// Unknown, or undetected orientation.
+    Reserved = RESERVED,
+}
+
+impl Orientation {
+    /// Represents every valid [Orientation].
+    pub const ALL: [Self; 4] = [
+        Self::FaceUpForward,
+        Self::FaceUpBackward,
+        Self::FaceDownForward,
+        Self::FaceDownBackward,
+    ];
+
+    /// Creates a new [Orientation].
+    pub const fn new() -> Self {
+        Self::Reserved
+    }
+
+    /// Converts a [`u8`] into an [Orientation].
+    pub const fn from_u8(val: u8) -> Self {
+        match val {
+            FACE_UP_FORWARD => Self::FaceUpForward,
+            FACE_UP_BACKWARD => Self::FaceUpBackward,
+            FACE_DOWN_FORWARD => Self::FaceDownForward,
+            FACE_DOWN_BACKWARD => Self::FaceDownBackward,
+            _ => Self::Reserved,
+        }
+    }
+
+    /// Converts an [Orientation] into a [`u8`].
+    pub const fn to_u8(&self) -> u8 {
+        *self as u8
+    }
+
+    /// Gets whether the [Orientation] contains a valid variant.
+    pub const fn is_valid(&self) -> bool {
+        !matches!(self, Self::Reserved)
+    }
+
+    /// Gets the [InhibitDirection](crate::InhibitDirection) bit mask of the [Orientation].
+    ///
+    /// The [Reserved](Self::Reserved) orientation has an empty mask.
+    pub const fn mask(&self) -> u8 {
+        if self.is_valid() {
+            1 << (*self as u8)
+        } else {
+            0
+        }
+    }
+}
+
+impl Default for Orientation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Orientation> for u8 {
+    fn from(val: Orientation) -> Self {
+        val.to_u8()
+    }
+}
+
+impl From<&Orientation> for u8 {
+    fn from(val: &Orientation) -> Self {
+        val.to_u8()
+    }
+}
+
+impl TryFrom<u8> for Orientation {
+    type Error = Error;
+
+    fn try_from(val: u8) -> Result<Self> {
+        match Self::from_u8(val) {
+            Self::Reserved => Err(Error::InvalidOrientation(val)),
+            orientation => Ok(orientation),
+        }
+    }
+}
+
+impl From<Orientation> for &'static str {
+    fn from(val: Orientation) -> Self {
+        match val {
+            Orientation::FaceUpForward => "face up forward",
+            Orientation::FaceUpBackward => "face up backward",
+            Orientation::FaceDownForward => "face down forward",
+            Orientation::FaceDownBackward => "face down backward",
+            Orientation::Reserved => "reserved",
+        }
+    }
+}
+
+impl From<&Orientation> for &'static str {
+    fn from(val: &Orientation) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for Orientation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_orientation() {
+        for (raw, exp) in [
+            (FACE_UP_FORWARD, Orientation::FaceUpForward),
+            (FACE_UP_BACKWARD, Orientation::FaceUpBackward),
+            (FACE_DOWN_FORWARD, Orientation::FaceDownForward),
+            (FACE_DOWN_BACKWARD, Orientation::FaceDownBackward),
+        ] {
+            assert_eq!(Orientation::try_from(raw), Ok(exp));
+            assert_eq!(u8::from(exp), raw);
+            assert!(exp.is_valid());
+        }
+
+        for raw in 4..=u8::MAX {
+            assert_eq!(Orientation::from_u8(raw), Orientation::Reserved);
+            assert_eq!(
+                Orientation::try_from(raw),
+                Err(Error::InvalidOrientation(raw))
+            );
+        }
+
+        assert_eq!(
+            Orientation::FaceDownBackward.to_string(),
+            r#""face down backward""#
+        );
+    }
+}
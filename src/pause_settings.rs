@@ -0,0 +1,219 @@
+use core::{fmt, time};
+
+use crate::{Error, Result, EVENT_MESSAGE_FLAG, STATUS_MESSAGE_FLAG};
+
+/// Represents the length of [PauseSettings].
+pub const PAUSE_SETTINGS_LEN: usize = 3;
+
+const PAUSE_FLAGS: u8 = STATUS_MESSAGE_FLAG | EVENT_MESSAGE_FLAG;
+
+/// Represents the `Pause` settings of the device.
+///
+/// The device pauses operation for the duration, and sends device-initiated `Status` and event
+/// messages when enabled. A zero duration leaves the device running.
+///
+/// ## Format
+///
+/// Field  | Duration | Message flags
+/// -------|----------|--------------
+/// Length | 2 bytes  | 1 byte
+///
+/// The duration is a little-endian number of milliseconds.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use jcm::PauseSettings;
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// let settings = PauseSettings::new()
+///     .with_duration_ms(1500)
+///     .with_event_messages(true);
+///
+/// assert_eq!(settings.duration(), Duration::from_millis(1500));
+/// assert_eq!(settings.into_bytes(), [0xdc, 0x05, jcm::EVENT_MESSAGE_FLAG]);
+/// # Ok(())
+/// # }
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PauseSettings {
+    duration_ms: u16,
+    status_messages: bool,
+    event_messages: bool,
+}
+
+impl PauseSettings {
+    /// Creates a new [PauseSettings], with no pause, and no device-initiated messages.
+    pub const fn new() -> Self {
+        Self {
+            duration_ms: 0,
+            status_messages: false,
+            event_messages: false,
+        }
+    }
+
+    /// Gets the pause duration in milliseconds.
+    pub const fn duration_ms(&self) -> u16 {
+        self.duration_ms
+    }
+
+    /// Gets the pause duration.
+    pub const fn duration(&self) -> time::Duration {
+        time::Duration::from_millis(self.duration_ms as u64)
+    }
+
+    /// Sets the pause duration in milliseconds.
+    pub fn set_duration_ms(&mut self, duration_ms: u16) {
+        self.duration_ms = duration_ms;
+    }
+
+    /// Builder function that sets the pause duration in milliseconds.
+    pub const fn with_duration_ms(self, duration_ms: u16) -> Self {
+        Self {
+            duration_ms,
+            status_messages: self.status_messages,
+            event_messages: self.event_messages,
+        }
+    }
+
+    /// Gets whether device-initiated `Status` messages are enabled.
+    pub const fn status_messages(&self) -> bool {
+        self.status_messages
+    }
+
+    /// Sets whether device-initiated `Status` messages are enabled.
+    pub fn set_status_messages(&mut self, enabled: bool) {
+        self.status_messages = enabled;
+    }
+
+    /// Builder function that sets whether device-initiated `Status` messages are enabled.
+    pub const fn with_status_messages(self, enabled: bool) -> Self {
+        Self {
+            duration_ms: self.duration_ms,
+            status_messages: enabled,
+            event_messages: self.event_messages,
+        }
+    }
+
+    /// Gets whether device-initiated event messages are enabled.
+    pub const fn event_messages(&self) -> bool {
+        self.event_messages
+    }
+
+    /// Sets whether device-initiated event messages are enabled.
+    pub fn set_event_messages(&mut self, enabled: bool) {
+        self.event_messages = enabled;
+    }
+
+    /// Builder function that sets whether device-initiated event messages are enabled.
+    pub const fn with_event_messages(self, enabled: bool) -> Self {
+        Self {
+            duration_ms: self.duration_ms,
+            status_messages: self.status_messages,
+            event_messages: enabled,
+        }
+    }
+
+    /// Gets the message flags of the [PauseSettings].
+    pub const fn flags(&self) -> u8 {
+        let status = if self.status_messages {
+            STATUS_MESSAGE_FLAG
+        } else {
+            0
+        };
+        let event = if self.event_messages {
+            EVENT_MESSAGE_FLAG
+        } else {
+            0
+        };
+
+        status | event
+    }
+
+    /// Gets the byte length of the [PauseSettings].
+    pub const fn len() -> usize {
+        PAUSE_SETTINGS_LEN
+    }
+
+    /// Attempts to convert a byte buffer into [PauseSettings].
+    pub fn from_bytes(buf: &[u8]) -> Result<Self> {
+        match buf {
+            [_, _, flags, ..] if flags & !PAUSE_FLAGS != 0 => Err(Error::InvalidPauseFlags(*flags)),
+            [dur_lo, dur_hi, flags, ..] => Ok(Self {
+                duration_ms: u16::from_le_bytes([*dur_lo, *dur_hi]),
+                status_messages: flags & STATUS_MESSAGE_FLAG != 0,
+                event_messages: flags & EVENT_MESSAGE_FLAG != 0,
+            }),
+            _ => Err(Error::InvalidPauseSettingsLen((
+                buf.len(),
+                PAUSE_SETTINGS_LEN,
+            ))),
+        }
+    }
+
+    /// Converts the [PauseSettings] into a byte array.
+    pub const fn into_bytes(self) -> [u8; PAUSE_SETTINGS_LEN] {
+        let [dur_lo, dur_hi] = self.duration_ms.to_le_bytes();
+        [dur_lo, dur_hi, self.flags()]
+    }
+}
+
+impl TryFrom<&[u8]> for PauseSettings {
+    type Error = Error;
+
+    fn try_from(val: &[u8]) -> Result<Self> {
+        Self::from_bytes(val)
+    }
+}
+
+impl fmt::Display for PauseSettings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""duration_ms": {}, "#, self.duration_ms)?;
+        write!(f, r#""status_messages": {}, "#, self.status_messages)?;
+        write!(f, r#""event_messages": {}"#, self.event_messages)?;
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pause_settings() -> Result<()> {
+        for (flags, status, event) in [
+            (0, false, false),
+            (STATUS_MESSAGE_FLAG, true, false),
+            (EVENT_MESSAGE_FLAG, false, true),
+            (PAUSE_FLAGS, true, true),
+        ] {
+            let settings = PauseSettings::from_bytes(&[0x10, 0x27, flags])?;
+
+            assert_eq!(settings.duration_ms(), 10_000);
+            assert_eq!(settings.status_messages(), status);
+            assert_eq!(settings.event_messages(), event);
+            assert_eq!(settings.into_bytes(), [0x10, 0x27, flags]);
+        }
+
+        assert_eq!(
+            PauseSettings::new().with_status_messages(true).to_string(),
+            r#"{"duration_ms": 0, "status_messages": true, "event_messages": false}"#
+        );
+
+        assert_eq!(
+            PauseSettings::from_bytes(&[0, 0, 0b100]),
+            Err(Error::InvalidPauseFlags(0b100))
+        );
+        assert_eq!(
+            PauseSettings::from_bytes(&[0, 0]),
+            Err(Error::InvalidPauseSettingsLen((2, PAUSE_SETTINGS_LEN)))
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,193 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::{fmt, time};
+
+use crate::{format_duration, TimingConfig, VendValidAckMode};
+
+/// Represents the default number of attempts for a polled request.
+pub const DEFAULT_RETRIES: usize = 3;
+/// Represents the default timeout for receiving a response to a polled request.
+pub const DEFAULT_RESPONSE_TIMEOUT: time::Duration = time::Duration::from_millis(500);
+/// Represents the default interval between polled request attempts.
+pub const DEFAULT_RETRY_INTERVAL: time::Duration = time::Duration::from_millis(100);
+
+/// Represents the runtime configuration of request polling, event acknowledgement, and timing
+/// thresholds.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PollConfig {
+    retries: usize,
+    response_timeout: time::Duration,
+    retry_interval: time::Duration,
+    vend_valid_ack: VendValidAckMode,
+    timing: TimingConfig,
+}
+
+impl PollConfig {
+    /// Creates a new [PollConfig] with the default settings.
+    pub const fn new() -> Self {
+        Self {
+            retries: DEFAULT_RETRIES,
+            response_timeout: DEFAULT_RESPONSE_TIMEOUT,
+            retry_interval: DEFAULT_RETRY_INTERVAL,
+            vend_valid_ack: VendValidAckMode::Confirmed,
+            timing: TimingConfig::new(),
+        }
+    }
+
+    /// Gets the number of attempts for a polled request.
+    pub const fn retries(&self) -> usize {
+        self.retries
+    }
+
+    /// Builder function that sets the number of attempts for a polled request.
+    pub fn with_retries(mut self, val: usize) -> Self {
+        self.retries = val;
+        self
+    }
+
+    /// Gets the timeout for receiving a response to a polled request.
+    pub const fn response_timeout(&self) -> time::Duration {
+        self.response_timeout
+    }
+
+    /// Builder function that sets the timeout for receiving a response to a polled request.
+    pub fn with_response_timeout(mut self, val: time::Duration) -> Self {
+        self.response_timeout = val;
+        self
+    }
+
+    /// Gets the interval between polled request attempts.
+    pub const fn retry_interval(&self) -> time::Duration {
+        self.retry_interval
+    }
+
+    /// Builder function that sets the interval between polled request attempts.
+    pub fn with_retry_interval(mut self, val: time::Duration) -> Self {
+        self.retry_interval = val;
+        self
+    }
+
+    /// Gets the [VendValidAckMode].
+    pub const fn vend_valid_ack(&self) -> VendValidAckMode {
+        self.vend_valid_ack
+    }
+
+    /// Builder function that sets the [VendValidAckMode].
+    pub fn with_vend_valid_ack(mut self, val: VendValidAckMode) -> Self {
+        self.vend_valid_ack = val;
+        self
+    }
+
+    /// Gets the [TimingConfig] thresholds.
+    pub const fn timing(&self) -> TimingConfig {
+        self.timing
+    }
+
+    /// Builder function that sets the [TimingConfig] thresholds.
+    pub fn with_timing(mut self, val: TimingConfig) -> Self {
+        self.timing = val;
+        self
+    }
+}
+
+impl Default for PollConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for PollConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""retries": {}, "#, self.retries)?;
+        write!(
+            f,
+            r#""response_timeout": "{}", "#,
+            format_duration(self.response_timeout)
+        )?;
+        write!(
+            f,
+            r#""retry_interval": "{}", "#,
+            format_duration(self.retry_interval)
+        )?;
+        write!(f, r#""vend_valid_ack": {}, "#, self.vend_valid_ack)?;
+        write!(f, r#""timing": {}"#, self.timing)?;
+        write!(f, "}}")
+    }
+}
+
+/// Represents a [PollConfig] shared between the host threads, and replaceable at runtime.
+///
+/// Readers take a [current](Self::current) snapshot at the start of each transaction, so a
+/// [reconfigure](Self::reconfigure) never changes the settings of an in-flight request, and never
+/// requires tearing down the device connection. Clones share the same configuration.
+#[derive(Clone, Debug, Default)]
+pub struct LiveConfig {
+    config: Arc<RwLock<PollConfig>>,
+    generation: Arc<AtomicU64>,
+}
+
+impl LiveConfig {
+    /// Creates a new [LiveConfig] from the initial [PollConfig].
+    pub fn new(config: PollConfig) -> Self {
+        Self {
+            config: Arc::new(RwLock::new(config)),
+            generation: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Gets a snapshot of the current [PollConfig].
+    pub fn current(&self) -> PollConfig {
+        match self.config.read() {
+            Ok(config) => *config,
+            Err(err) => *err.into_inner(),
+        }
+    }
+
+    /// Gets the number of times the [PollConfig] was replaced.
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::SeqCst)
+    }
+
+    /// Replaces the [PollConfig], returning the new generation.
+    ///
+    /// Transactions started after the call use the new settings.
+    pub fn reconfigure(&self, config: PollConfig) -> u64 {
+        match self.config.write() {
+            Ok(mut cur) => *cur = config,
+            Err(err) => *err.into_inner() = config,
+        }
+
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        log::info!("reconfigured, generation: {generation}, config: {config}");
+
+        generation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_live_config() {
+        let live = LiveConfig::new(PollConfig::new());
+        let shared = live.clone();
+
+        let in_flight = live.current();
+        let new_cfg = PollConfig::new()
+            .with_retries(5)
+            .with_vend_valid_ack(VendValidAckMode::Immediate)
+            .with_timing(
+                TimingConfig::new().with_keep_alive_interval(time::Duration::from_secs(2)),
+            );
+
+        assert_eq!(shared.reconfigure(new_cfg), 1);
+        assert_eq!(live.generation(), 1);
+        assert_eq!(live.current(), new_cfg);
+
+        // snapshots taken before the reload keep their settings
+        assert_eq!(in_flight.retries(), DEFAULT_RETRIES);
+        assert_eq!(in_flight.vend_valid_ack(), VendValidAckMode::Confirmed);
+    }
+}
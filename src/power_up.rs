@@ -0,0 +1,363 @@
+use std::{fmt, time};
+
+use crate::{
+    CollectMode, CollectRequest, Error, EventCode, Message, ResetRequest, Response, ResponseCode,
+    Result,
+};
+
+/// Represents the default time to wait for the device to finish sending `Power Up` events.
+pub const POWER_UP_GRACE_PERIOD: time::Duration = time::Duration::from_secs(3);
+
+/// Represents the host policy for a note left in the transport at `Power Up`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum OnPowerUpNote {
+    /// Collect the note into the cashbox.
+    #[default]
+    Collect,
+    /// Return the note to the customer, if returnable.
+    ///
+    /// Non-returnable notes are collected.
+    Return,
+    /// Leave the note in the transport, and let the application decide.
+    Ask,
+}
+
+impl From<OnPowerUpNote> for &'static str {
+    fn from(val: OnPowerUpNote) -> Self {
+        match val {
+            OnPowerUpNote::Collect => "collect",
+            OnPowerUpNote::Return => "return",
+            OnPowerUpNote::Ask => "ask",
+        }
+    }
+}
+
+impl From<&OnPowerUpNote> for &'static str {
+    fn from(val: &OnPowerUpNote) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for OnPowerUpNote {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
+
+/// Represents what happened to a note left in the transport at `Power Up`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum PowerUpNoteOutcome {
+    /// No note was detected.
+    #[default]
+    NoNote,
+    /// The note was collected into the cashbox.
+    Collected,
+    /// The note was returned to the customer.
+    Returned,
+    /// The note is still in the transport, awaiting an application decision.
+    Pending,
+}
+
+impl From<PowerUpNoteOutcome> for &'static str {
+    fn from(val: PowerUpNoteOutcome) -> Self {
+        match val {
+            PowerUpNoteOutcome::NoNote => "no note",
+            PowerUpNoteOutcome::Collected => "collected",
+            PowerUpNoteOutcome::Returned => "returned",
+            PowerUpNoteOutcome::Pending => "pending",
+        }
+    }
+}
+
+impl From<&PowerUpNoteOutcome> for &'static str {
+    fn from(val: &PowerUpNoteOutcome) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for PowerUpNoteOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
+
+/// Represents the outcome of the [PowerUpRoutine].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PowerUpReport {
+    events: Vec<EventCode>,
+    note: Option<EventCode>,
+    policy: OnPowerUpNote,
+    outcome: PowerUpNoteOutcome,
+}
+
+impl PowerUpReport {
+    /// Gets the list of `Power Up` [EventCode]s received from the device.
+    pub fn events(&self) -> &[EventCode] {
+        self.events.as_ref()
+    }
+
+    /// Gets the `Power Up` [EventCode] reporting a note in the transport, if any.
+    pub const fn note(&self) -> Option<EventCode> {
+        self.note
+    }
+
+    /// Gets the [OnPowerUpNote] policy applied to the note.
+    pub const fn policy(&self) -> OnPowerUpNote {
+        self.policy
+    }
+
+    /// Gets the [PowerUpNoteOutcome].
+    pub const fn outcome(&self) -> PowerUpNoteOutcome {
+        self.outcome
+    }
+}
+
+impl fmt::Display for PowerUpReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""events": ["#)?;
+        for (i, code) in self.events.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{code}")?;
+        }
+        write!(f, "], ")?;
+        match self.note {
+            Some(note) => write!(f, r#""note": {note}, "#)?,
+            None => write!(f, r#""note": null, "#)?,
+        }
+        write!(f, r#""policy": {}, "#, self.policy)?;
+        write!(f, r#""outcome": {}"#, self.outcome)?;
+        write!(f, "}}")
+    }
+}
+
+/// Handles a note left in the transport at `Power Up`, according to the [OnPowerUpNote] policy.
+///
+/// `PowerUpAcceptor` events report a returnable note, and `PowerUpStacker` events a
+/// non-returnable note. Notes are collected with a `Collect` request, and returned by resetting
+/// the device.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PowerUpRoutine {
+    policy: OnPowerUpNote,
+    grace_period: time::Duration,
+}
+
+impl PowerUpRoutine {
+    /// Creates a new [PowerUpRoutine].
+    pub const fn new() -> Self {
+        Self {
+            policy: OnPowerUpNote::Collect,
+            grace_period: POWER_UP_GRACE_PERIOD,
+        }
+    }
+
+    /// Gets the [OnPowerUpNote] policy.
+    pub const fn policy(&self) -> OnPowerUpNote {
+        self.policy
+    }
+
+    /// Sets the [OnPowerUpNote] policy.
+    pub fn set_policy(&mut self, policy: OnPowerUpNote) {
+        self.policy = policy;
+    }
+
+    /// Builder function that sets the [OnPowerUpNote] policy.
+    pub fn with_policy(mut self, policy: OnPowerUpNote) -> Self {
+        self.set_policy(policy);
+        self
+    }
+
+    /// Gets the time to wait for the device to finish sending `Power Up` events.
+    pub const fn grace_period(&self) -> time::Duration {
+        self.grace_period
+    }
+
+    /// Sets the time to wait for the device to finish sending `Power Up` events.
+    pub fn set_grace_period(&mut self, val: time::Duration) {
+        self.grace_period = val;
+    }
+
+    /// Builder function that sets the time to wait for the device to finish sending `Power Up`
+    /// events.
+    pub fn with_grace_period(mut self, val: time::Duration) -> Self {
+        self.set_grace_period(val);
+        self
+    }
+
+    /// Applies the [OnPowerUpNote] policy to the `Power Up` events received from the device.
+    ///
+    /// With [OnPowerUpNote::Ask], the note is left in the transport, and the application decides
+    /// later with [resolve](Self::resolve).
+    pub fn run<P>(&self, events: &[Message], poll: P) -> Result<PowerUpReport>
+    where
+        P: FnMut(&Message) -> Result<Message>,
+    {
+        let events = events
+            .iter()
+            .filter_map(|evt| evt.data().message_code().event_code().ok())
+            .filter(|code| code.is_power_up())
+            .collect::<Vec<_>>();
+        let note = events.iter().rev().copied().find(|&code| has_note(code));
+
+        let outcome = match (note, self.policy) {
+            (None, _) => PowerUpNoteOutcome::NoNote,
+            (Some(_), OnPowerUpNote::Ask) => {
+                log::info!("note left in the transport at Power Up, awaiting application");
+                PowerUpNoteOutcome::Pending
+            }
+            (Some(code), policy) => Self::resolve_note(code, policy, poll)?,
+        };
+
+        Ok(PowerUpReport {
+            events,
+            note,
+            policy: self.policy,
+            outcome,
+        })
+    }
+
+    /// Resolves a [PowerUpNoteOutcome::Pending] note from the [PowerUpReport] with the provided
+    /// decision.
+    ///
+    /// Returns the updated [PowerUpReport].
+    pub fn resolve<P>(
+        &self,
+        report: &PowerUpReport,
+        decision: OnPowerUpNote,
+        poll: P,
+    ) -> Result<PowerUpReport>
+    where
+        P: FnMut(&Message) -> Result<Message>,
+    {
+        let mut report = report.clone();
+
+        if let (Some(code), PowerUpNoteOutcome::Pending) = (report.note, report.outcome) {
+            report.outcome = Self::resolve_note(code, decision, poll)?;
+            report.policy = decision;
+        }
+
+        Ok(report)
+    }
+
+    fn resolve_note<P>(
+        code: EventCode,
+        policy: OnPowerUpNote,
+        mut poll: P,
+    ) -> Result<PowerUpNoteOutcome>
+    where
+        P: FnMut(&Message) -> Result<Message>,
+    {
+        let returnable = matches!(
+            code,
+            EventCode::PowerUpAcceptor | EventCode::PowerUpAcceptorAccepting
+        );
+
+        let (request, outcome): (Message, _) = match policy {
+            OnPowerUpNote::Ask => return Ok(PowerUpNoteOutcome::Pending),
+            OnPowerUpNote::Return if returnable => {
+                (ResetRequest::new().into(), PowerUpNoteOutcome::Returned)
+            }
+            OnPowerUpNote::Return => {
+                log::warn!("note left at Power Up is non-returnable, collecting");
+                (
+                    CollectRequest::create(CollectMode::PowerUp).into(),
+                    PowerUpNoteOutcome::Collected,
+                )
+            }
+            OnPowerUpNote::Collect => (
+                CollectRequest::create(CollectMode::PowerUp).into(),
+                PowerUpNoteOutcome::Collected,
+            ),
+        };
+
+        let res = Response::try_from(poll(&request)?)?;
+
+        match res.code() {
+            ResponseCode::Ack => {
+                log::info!("note left at Power Up: {outcome}");
+                Ok(outcome)
+            }
+            code => Err(Error::InvalidResponseCode(code.into())),
+        }
+    }
+}
+
+impl Default for PowerUpRoutine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+const fn has_note(code: EventCode) -> bool {
+    matches!(
+        code,
+        EventCode::PowerUpAcceptor
+            | EventCode::PowerUpStacker
+            | EventCode::PowerUpAcceptorAccepting
+            | EventCode::PowerUpStackerAccepting
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Event, EventType, RequestCode};
+
+    #[test]
+    fn test_power_up_routine() -> Result<()> {
+        let mut sent = Vec::new();
+        let mut poll = |req: &Message| {
+            sent.push(req.data().message_code().request_code()?);
+            Ok(Message::new().with_data(
+                req.data()
+                    .clone()
+                    .with_additional(&[ResponseCode::Ack.into()]),
+            ))
+        };
+
+        let power_up = |code| Event::create(EventType::Sequence0, code, &[]).into_message(1);
+        let clean = [power_up(EventCode::PowerUp)];
+        let returnable = [power_up(EventCode::PowerUpAcceptor)];
+        let stacked = [power_up(EventCode::PowerUpStacker)];
+
+        let routine = PowerUpRoutine::new().with_policy(OnPowerUpNote::Return);
+
+        let report = routine.run(&clean, &mut poll)?;
+        assert_eq!(report.note(), None);
+        assert_eq!(report.outcome(), PowerUpNoteOutcome::NoNote);
+
+        assert_eq!(
+            routine.run(&returnable, &mut poll)?.outcome(),
+            PowerUpNoteOutcome::Returned
+        );
+        assert_eq!(
+            routine.run(&stacked, &mut poll)?.outcome(),
+            PowerUpNoteOutcome::Collected
+        );
+
+        let report = routine
+            .with_policy(OnPowerUpNote::Ask)
+            .run(&returnable, &mut poll)?;
+        assert_eq!(report.outcome(), PowerUpNoteOutcome::Pending);
+
+        let report = routine.resolve(&report, OnPowerUpNote::Collect, &mut poll)?;
+        assert_eq!(report.outcome(), PowerUpNoteOutcome::Collected);
+        assert_eq!(report.policy(), OnPowerUpNote::Collect);
+
+        assert_eq!(
+            sent,
+            [
+                RequestCode::Reset,
+                RequestCode::Collect,
+                RequestCode::Collect
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+use core::fmt;
+
+use crate::ModelName;
+
+/// Represents the JCM product family of a device.
+///
+/// Families share the ID-008 protocol, but differ in the supported requests and timings.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ProductFamily {
+    /// iVIZION bill validators.
+    IVizion,
+    /// UBA bill validators.
+    Uba,
+    /// TBV bill validators.
+    Tbv,
+    /// iPRO bill validators.
+    IPro,
+    /// Unrecognized product family.
+    #[default]
+    Unknown,
+}
+
+impl ProductFamily {
+    /// Creates a new [ProductFamily].
+    pub const fn new() -> Self {
+        Self::Unknown
+    }
+
+    /// Gets the [ProductFamily] from a model name, or a USB product string.
+    ///
+    /// Matches the family name at the start of a word, ignoring case, e.g. `UBA-14` and
+    /// `JCM iVIZION` are recognized.
+    pub fn from_model_name(name: &str) -> Self {
+        name.split(|c: char| !c.is_ascii_alphanumeric())
+            .find_map(|word| {
+                let word = word.to_ascii_lowercase();
+
+                [Self::IVizion, Self::Uba, Self::Tbv, Self::IPro]
+                    .into_iter()
+                    .find(|family| word.starts_with(family.prefix()))
+            })
+            .unwrap_or(Self::Unknown)
+    }
+
+    /// Gets whether the [ProductFamily] is recognized.
+    pub const fn is_known(&self) -> bool {
+        !matches!(self, Self::Unknown)
+    }
+
+    const fn prefix(&self) -> &'static str {
+        match self {
+            Self::IVizion => "ivizion",
+            Self::Uba => "uba",
+            Self::Tbv => "tbv",
+            Self::IPro => "ipro",
+            Self::Unknown => "",
+        }
+    }
+}
+
+impl From<&ModelName> for ProductFamily {
+    fn from(val: &ModelName) -> Self {
+        Self::from_model_name(val.as_str())
+    }
+}
+
+impl From<ProductFamily> for &'static str {
+    fn from(val: ProductFamily) -> Self {
+        match val {
+            ProductFamily::IVizion => "iVIZION",
+            ProductFamily::Uba => "UBA",
+            ProductFamily::Tbv => "TBV",
+            ProductFamily::IPro => "iPRO",
+            ProductFamily::Unknown => "unknown",
+        }
+    }
+}
+
+impl From<&ProductFamily> for &'static str {
+    fn from(val: &ProductFamily) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for ProductFamily {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_product_family() {
+        assert_eq!(
+            ProductFamily::from_model_name("iVIZION"),
+            ProductFamily::IVizion
+        );
+        assert_eq!(ProductFamily::from_model_name("UBA-14"), ProductFamily::Uba);
+        assert_eq!(
+            ProductFamily::from_model_name("JCM TBV"),
+            ProductFamily::Tbv
+        );
+        assert_eq!(
+            ProductFamily::from_model_name("iPRO-RC"),
+            ProductFamily::IPro
+        );
+        assert_eq!(
+            ProductFamily::from_model_name("XFS USB device"),
+            ProductFamily::Unknown
+        );
+        assert_eq!(
+            ProductFamily::from(&ModelName::from_string("UBA")),
+            ProductFamily::Uba
+        );
+
+        assert!(!ProductFamily::new().is_known());
+        assert_eq!(ProductFamily::IPro.to_string(), r#""iPRO""#);
+    }
+}
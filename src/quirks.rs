@@ -0,0 +1,282 @@
+use alloc::{string::String, vec::Vec};
+use core::{fmt, time};
+
+use crate::{Clock, DeviceInfo, Message, RequestCode, ResponseCode, Result};
+
+/// Represents a device-specific deviation from the protocol specification.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Quirk {
+    /// The device responds to the request with a nonstandard additional data length.
+    ///
+    /// Responses are normalized to the provided length (including the response code), by
+    /// truncating extra bytes, or padding missing bytes with zeros.
+    ResponseLen((RequestCode, usize)),
+    /// The device needs the provided delay after an acknowledged request, before accepting the
+    /// next request.
+    DelayAfter((RequestCode, time::Duration)),
+}
+
+impl Quirk {
+    /// Gets the [RequestCode] affected by the [Quirk].
+    pub const fn request_code(&self) -> RequestCode {
+        match self {
+            Self::ResponseLen((code, _)) | Self::DelayAfter((code, _)) => *code,
+        }
+    }
+}
+
+impl fmt::Display for Quirk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ResponseLen((code, len)) => {
+                write!(f, r#"{{"response_len": {{"code": {code}, "len": {len}}}}}"#)
+            }
+            Self::DelayAfter((code, delay)) => write!(
+                f,
+                r#"{{"delay_after": {{"code": {code}, "delay_ms": {}}}}}"#,
+                delay.as_millis()
+            ),
+        }
+    }
+}
+
+/// Selects the devices affected by a [Quirk], by model name and firmware version.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QuirkMatch {
+    model: String,
+    firmware: Option<String>,
+}
+
+impl QuirkMatch {
+    /// Creates a new [QuirkMatch] for every firmware version of the model.
+    pub fn new(model: &str) -> Self {
+        Self {
+            model: model.into(),
+            firmware: None,
+        }
+    }
+
+    /// Gets the model name matched by the [QuirkMatch].
+    pub fn model(&self) -> &str {
+        self.model.as_str()
+    }
+
+    /// Gets the firmware version prefix matched by the [QuirkMatch], if any.
+    pub fn firmware(&self) -> Option<&str> {
+        self.firmware.as_deref()
+    }
+
+    /// Builder function that restricts the [QuirkMatch] to firmware versions starting with the
+    /// provided prefix.
+    pub fn with_firmware(mut self, prefix: &str) -> Self {
+        self.firmware = Some(prefix.into());
+        self
+    }
+
+    /// Gets whether the [QuirkMatch] matches the model name and firmware version.
+    pub fn matches(&self, model: &str, firmware: &str) -> bool {
+        self.model == model
+            && self
+                .firmware
+                .as_deref()
+                .map(|prefix| firmware.starts_with(prefix))
+                .unwrap_or(true)
+    }
+
+    /// Gets whether the [QuirkMatch] matches the [DeviceInfo].
+    pub fn matches_device(&self, info: &DeviceInfo) -> bool {
+        self.matches(
+            info.model_name().as_str(),
+            info.firmware_version().version(),
+        )
+    }
+}
+
+/// Represents the registry of device-specific [Quirk]s, keyed by model name and firmware
+/// version.
+///
+/// The registry is consulted when decoding responses, and when sequencing requests: wrap a
+/// polling function with [apply](Self::apply) to handle the quirks of a device transparently.
+/// Additional quirks can be registered at runtime with [register](Self::register).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct QuirkRegistry {
+    quirks: Vec<(QuirkMatch, Quirk)>,
+}
+
+impl QuirkRegistry {
+    /// Creates a new, empty [QuirkRegistry].
+    pub const fn new() -> Self {
+        Self { quirks: Vec::new() }
+    }
+
+    /// Gets the number of registered [Quirk]s.
+    pub fn len(&self) -> usize {
+        self.quirks.len()
+    }
+
+    /// Gets whether the [QuirkRegistry] is empty.
+    pub fn is_empty(&self) -> bool {
+        self.quirks.is_empty()
+    }
+
+    /// Registers a [Quirk] for the devices selected by the [QuirkMatch].
+    pub fn register(&mut self, matcher: QuirkMatch, quirk: Quirk) {
+        log::debug!(
+            r#"registering quirk: {{"model": "{}", "quirk": {quirk}}}"#,
+            matcher.model()
+        );
+        self.quirks.push((matcher, quirk));
+    }
+
+    /// Builder function that registers a [Quirk] for the devices selected by the [QuirkMatch].
+    pub fn with_quirk(mut self, matcher: QuirkMatch, quirk: Quirk) -> Self {
+        self.register(matcher, quirk);
+        self
+    }
+
+    /// Gets an iterator over the [Quirk]s of the device.
+    pub fn quirks_for<'a>(&'a self, info: &'a DeviceInfo) -> impl Iterator<Item = &'a Quirk> + 'a {
+        self.quirks
+            .iter()
+            .filter(|(matcher, _)| matcher.matches_device(info))
+            .map(|(_, quirk)| quirk)
+    }
+
+    /// Gets the normalized response length for the request, if the device has a
+    /// [Quirk::ResponseLen].
+    pub fn response_len(&self, info: &DeviceInfo, code: RequestCode) -> Option<usize> {
+        self.quirks_for(info).find_map(|quirk| match quirk {
+            Quirk::ResponseLen((c, len)) if *c == code => Some(*len),
+            _ => None,
+        })
+    }
+
+    /// Gets the delay after the request, if the device has a [Quirk::DelayAfter].
+    pub fn delay_after(&self, info: &DeviceInfo, code: RequestCode) -> Option<time::Duration> {
+        self.quirks_for(info).find_map(|quirk| match quirk {
+            Quirk::DelayAfter((c, delay)) if *c == code => Some(*delay),
+            _ => None,
+        })
+    }
+
+    /// Normalizes a response [Message] to the request according to the device [Quirk]s.
+    pub fn normalize_response(
+        &self,
+        info: &DeviceInfo,
+        code: RequestCode,
+        response: Message,
+    ) -> Message {
+        match self.response_len(info, code) {
+            Some(len) if response.data().additional().len() != len => {
+                let mut additional = response.data().additional().to_vec();
+                additional.resize(len, 0);
+
+                log::trace!(
+                    "normalizing {code} response length, read: {}, normalized: {len}",
+                    response.data().additional().len()
+                );
+
+                let data = response.data().clone().with_additional(&additional);
+                response.with_data(data)
+            }
+            _ => response,
+        }
+    }
+
+    /// Wraps a polling function, applying the device [Quirk]s to every request.
+    ///
+    /// Responses are [normalized](Self::normalize_response), and [Quirk::DelayAfter] delays are
+    /// waited on the [Clock] after acknowledged requests.
+    pub fn apply<'a, P, C>(
+        &'a self,
+        info: &'a DeviceInfo,
+        clock: &'a C,
+        mut poll: P,
+    ) -> impl FnMut(&Message) -> Result<Message> + 'a
+    where
+        P: FnMut(&Message) -> Result<Message> + 'a,
+        C: Clock + ?Sized,
+    {
+        move |req: &Message| {
+            let code = match req.data().message_code().request_code() {
+                Ok(code) => code,
+                Err(_) => return poll(req),
+            };
+
+            let res = self.normalize_response(info, code, poll(req)?);
+
+            if let Some(delay) = self.delay_after(info, code) {
+                if res.data().additional().first() == Some(&ResponseCode::Ack.into()) {
+                    log::trace!("waiting {}ms after {code} request", delay.as_millis());
+                    clock.sleep(delay);
+                }
+            }
+
+            Ok(res)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        FirmwareVersion, ModelName, NearFullRequest, ResetRequest, SimulatedClock, StatusRequest,
+    };
+
+    #[test]
+    fn test_quirk_registry() -> Result<()> {
+        let info = DeviceInfo::create(
+            "A000001",
+            ModelName::from_string("iVIZION"),
+            FirmwareVersion::new().with_version("1.02"),
+            &[],
+        );
+        let delay = time::Duration::from_millis(200);
+
+        let mut quirks = QuirkRegistry::new()
+            .with_quirk(
+                QuirkMatch::new("iVIZION").with_firmware("1.0"),
+                Quirk::ResponseLen((RequestCode::NearFull, 3)),
+            )
+            .with_quirk(
+                QuirkMatch::new("iVIZION").with_firmware("2."),
+                Quirk::ResponseLen((RequestCode::Status, 1)),
+            );
+        // registered at runtime
+        quirks.register(
+            QuirkMatch::new("iVIZION"),
+            Quirk::DelayAfter((RequestCode::Reset, delay)),
+        );
+
+        assert_eq!(quirks.len(), 3);
+        assert_eq!(quirks.quirks_for(&info).count(), 2);
+        assert!(quirks.response_len(&info, RequestCode::Status).is_none());
+
+        let clock = SimulatedClock::new();
+        let ack = |req: &Message| -> Result<Message> {
+            Ok(
+                Message::new().with_data(req.data().clone().with_additional(&[
+                    ResponseCode::Ack.into(),
+                    1,
+                    2,
+                    3,
+                    4,
+                ])),
+            )
+        };
+        let mut poll = quirks.apply(&info, &clock, ack);
+
+        let res = poll(&NearFullRequest::new().into())?;
+        assert_eq!(res.data().additional(), [ResponseCode::Ack.into(), 1, 2]);
+        assert_eq!(clock.now(), time::Duration::ZERO);
+
+        let res = poll(&StatusRequest::new().into())?;
+        assert_eq!(res.data().additional().len(), 5);
+
+        poll(&ResetRequest::new().into())?;
+        assert_eq!(clock.now(), delay);
+
+        Ok(())
+    }
+}
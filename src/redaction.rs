@@ -0,0 +1,202 @@
+use core::fmt;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg(feature = "std")]
+use crate::{Credit, DepositRecord};
+use crate::{Currency, EscrowData, EventCode, Message, MessageCode, MessageData, Ticket};
+
+/// Represents the placeholder written in place of redacted values.
+pub const REDACTED: &str = "***";
+
+static LOG_REDACTION: AtomicBool = AtomicBool::new(false);
+
+/// Sets whether log and trace output masks monetary amounts and barcodes.
+///
+/// Message codes, event types, and timings are always logged.
+pub fn set_log_redaction(enabled: bool) {
+    LOG_REDACTION.store(enabled, Ordering::Relaxed);
+}
+
+/// Gets whether log and trace output masks monetary amounts and barcodes.
+pub fn log_redaction() -> bool {
+    LOG_REDACTION.load(Ordering::Relaxed)
+}
+
+/// Formats a value with sensitive fields (amounts, barcodes) masked.
+pub trait Redact: fmt::Display {
+    /// Formats the value with sensitive fields replaced by [REDACTED].
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+impl<T: Redact + ?Sized> Redact for &T {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt_redacted(f)
+    }
+}
+
+/// Wraps a [Redact] value for logging.
+///
+/// The wrapper formats the redacted value when redaction is enabled, and the full value
+/// otherwise.
+#[derive(Clone, Copy, Debug)]
+pub struct Redacted<'a, T: Redact + ?Sized> {
+    value: &'a T,
+    enabled: bool,
+}
+
+impl<'a, T: Redact + ?Sized> Redacted<'a, T> {
+    /// Creates a new [Redacted] wrapper using the global [log_redaction] setting.
+    pub fn new(value: &'a T) -> Self {
+        Self::with_enabled(value, log_redaction())
+    }
+
+    /// Creates a new [Redacted] wrapper, overriding the global [log_redaction] setting.
+    pub const fn with_enabled(value: &'a T, enabled: bool) -> Self {
+        Self { value, enabled }
+    }
+}
+
+impl<T: Redact + ?Sized> fmt::Display for Redacted<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.enabled {
+            self.value.fmt_redacted(f)
+        } else {
+            fmt::Display::fmt(self.value, f)
+        }
+    }
+}
+
+/// Convenience function to wrap a value for logging with the global [log_redaction] setting.
+pub fn redact<T: Redact + ?Sized>(value: &T) -> Redacted<'_, T> {
+    Redacted::new(value)
+}
+
+impl Redact for Currency {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{REDACTED} {}""#, <&str>::from(self.code()))
+    }
+}
+
+impl Redact for Ticket {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{REDACTED}""#)
+    }
+}
+
+impl Redact for EscrowData {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Currency(data) => write!(f, r#"{{"currency": {}}}"#, redact_on(data)),
+            Self::Ticket(data) => write!(f, r#"{{"ticket": {}}}"#, redact_on(data)),
+            Self::Coupon(data) => write!(f, r#"{{"coupon": {}}}"#, redact_on(data)),
+            Self::Card(_) => write!(f, r#"{{"card": "{REDACTED}"}}"#),
+            Self::Other((kind, _)) => {
+                write!(
+                    f,
+                    r#"{{"media": {{"kind": {kind}, "data": "{REDACTED}"}}}}"#
+                )
+            }
+        }
+    }
+}
+
+impl Redact for MessageData {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // only escrow events carry note values and barcodes
+        if self.message_code() != MessageCode::Event(EventCode::Escrow) {
+            return fmt::Display::fmt(self, f);
+        }
+
+        write!(f, "{{")?;
+        write!(f, r#""conf_id": {},"#, self.conf_id())?;
+        write!(f, r#""uid": {},"#, self.uid())?;
+        write!(f, r#""message_type": {},"#, self.message_type())?;
+        write!(f, r#""message_code": {},"#, self.message_code())?;
+        write!(f, r#""additional": "{REDACTED}""#)?;
+        write!(f, "}}")
+    }
+}
+
+impl Redact for Message {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""id": {},"#, self.id())?;
+        write!(f, r#""data": {}"#, redact_on(self.data()))?;
+        write!(f, "}}")
+    }
+}
+
+#[cfg(feature = "std")]
+impl Redact for Credit {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""id": {}, "#, self.id())?;
+        match self.data() {
+            Some(data) => write!(f, r#""data": {}"#, redact_on(data))?,
+            None => write!(f, r#""data": null"#)?,
+        }
+        write!(f, "}}")
+    }
+}
+
+#[cfg(feature = "std")]
+impl Redact for DepositRecord {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""data": {}, "#, redact_on(self.data()))?;
+        write!(f, r#""evaluation": {}"#, self.evaluation())?;
+        if let Some(credit) = self.ticket_credit() {
+            write!(
+                f,
+                r#", "ticket_credit": {{"rounding": {}, "decision": {}}}"#,
+                credit.rounding(),
+                credit.decision()
+            )?;
+        }
+        write!(f, "}}")
+    }
+}
+
+fn redact_on<T: Redact + ?Sized>(value: &T) -> Redacted<'_, T> {
+    Redacted::with_enabled(value, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CurrencyCode, Denomination, EscrowEvent, EventType};
+
+    #[test]
+    fn test_redaction() {
+        let currency = Currency::new()
+            .with_code(CurrencyCode::JPY)
+            .with_denomination(Denomination::from_value(1000));
+        let data = EscrowData::Currency(currency);
+        let ticket = EscrowData::Ticket(Ticket::new().with_code("0123456789").unwrap());
+
+        assert_eq!(
+            Redacted::with_enabled(&data, true).to_string(),
+            r#"{"currency": "*** JPY"}"#
+        );
+        assert_eq!(
+            Redacted::with_enabled(&data, false).to_string(),
+            data.to_string()
+        );
+        assert_eq!(
+            Redacted::with_enabled(&ticket, true).to_string(),
+            r#"{"ticket": "***"}"#
+        );
+
+        let escrow = Message::from(EscrowEvent::create(EventType::Sequence0, data));
+        let redacted = Redacted::with_enabled(&escrow, true).to_string();
+        assert!(redacted.contains(r#""additional": "***""#));
+        assert!(redacted.contains(&format!("{}", escrow.data().message_code())));
+
+        let idle = Message::new()
+            .with_data(MessageData::new().with_message_code(MessageCode::Event(EventCode::Idle)));
+        assert_eq!(
+            Redacted::with_enabled(&idle, true).to_string(),
+            idle.to_string()
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use core::{fmt, time};
+
+use crate::{
+    poll_typed, Clock, Error, EventCode, Message, RejectCode, RejectRequest, RejectedEvent,
+    ResponseCode, Result,
+};
+
+/// Represents the default time to wait for the event ending a `Reject` request.
+pub const DEFAULT_REJECT_TIMEOUT: time::Duration = time::Duration::from_secs(10);
+
+/// Represents the outcome of a `Reject` request, from the event that ended it.
+///
+/// A `Returned` event means the device returned the note as requested, and reports
+/// [RejectCode::Return]. A `Rejected` or `AcceptorRejected` event carries the [RejectCode] of
+/// the device, e.g. when the note was already being rejected when the request arrived.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RejectOutcome {
+    event_code: EventCode,
+    reject_code: RejectCode,
+}
+
+impl RejectOutcome {
+    /// Creates a new [RejectOutcome] from the provided parameters.
+    pub const fn create(event_code: EventCode, reject_code: RejectCode) -> Self {
+        Self {
+            event_code,
+            reject_code,
+        }
+    }
+
+    /// Attempts to get the [RejectOutcome] from a device event.
+    ///
+    /// Returns `Ok(None)` for events that do not end a `Reject` request.
+    pub fn from_event(event: &Message) -> Result<Option<Self>> {
+        match event.data().message_code().event_code() {
+            Ok(EventCode::Returned) => {
+                Ok(Some(Self::create(EventCode::Returned, RejectCode::Return)))
+            }
+            Ok(EventCode::Rejected | EventCode::AcceptorRejected) => {
+                let evt = RejectedEvent::try_from(event)?;
+                Ok(Some(Self::create(evt.event_code(), evt.reject_code())))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Gets the [EventCode] that ended the `Reject` request.
+    pub const fn event_code(&self) -> EventCode {
+        self.event_code
+    }
+
+    /// Gets the [RejectCode] reported by the device.
+    pub const fn reject_code(&self) -> RejectCode {
+        self.reject_code
+    }
+
+    /// Gets whether the device returned the note as requested by the host.
+    pub fn is_returned(&self) -> bool {
+        self.event_code == EventCode::Returned
+    }
+}
+
+impl fmt::Display for RejectOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""event_code": {}, "#, self.event_code)?;
+        write!(f, r#""reject_code": {}"#, self.reject_code)?;
+        write!(f, "}}")
+    }
+}
+
+/// Sends a `Reject` request for the escrowed note, and waits for the event ending it.
+///
+/// The event function receives the next device event within the provided timeout, and is
+/// responsible for acknowledging it. Unrelated events are skipped. Fails with [Error::Timeout] if
+/// no `Returned`, `Rejected`, or `AcceptorRejected` event arrives within `timeout`.
+pub fn reject_note<C, P, E>(
+    clock: &C,
+    timeout: time::Duration,
+    mut poll: P,
+    mut events: E,
+) -> Result<RejectOutcome>
+where
+    C: Clock + ?Sized,
+    P: FnMut(&Message) -> Result<Message>,
+    E: FnMut(time::Duration) -> Result<Message>,
+{
+    match poll_typed(RejectRequest::new(), &mut poll)?.code() {
+        ResponseCode::Ack => (),
+        code => return Err(Error::InvalidResponseCode(code.into())),
+    }
+
+    let start = clock.now();
+
+    loop {
+        let remaining = timeout.saturating_sub(clock.elapsed(start));
+        if remaining.is_zero() {
+            return Err(Error::Timeout("reject: waiting for the outcome".into()));
+        }
+
+        if let Some(outcome) = RejectOutcome::from_event(&events(remaining)?)? {
+            log::debug!("reject outcome: {outcome}");
+            return Ok(outcome);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::mock::MockDevice;
+    use crate::{event_ack, Currency, EventType, IdleRequest, SimulatedClock};
+
+    #[test]
+    fn test_reject_note() -> Result<()> {
+        let clock = SimulatedClock::new();
+        let device = MockDevice::new();
+        let poll = |req: &Message| device.handle_request(req);
+        let events = |_| {
+            let event = device
+                .pending_event()
+                .ok_or(Error::Timeout("no event".into()))?;
+            device.acknowledge_event(&event_ack(&event))?;
+            Ok(event)
+        };
+
+        assert_eq!(
+            reject_note(&clock, DEFAULT_REJECT_TIMEOUT, poll, events),
+            Err(Error::InvalidResponseCode(ResponseCode::Nak.into()))
+        );
+
+        device.handle_request(&IdleRequest::new().into())?;
+        assert!(device.insert_note(Currency::new()));
+
+        // skips the pending `Power Up`, `Idle`, and `Escrow` events
+        let outcome = reject_note(&clock, DEFAULT_REJECT_TIMEOUT, poll, events)?;
+        assert!(outcome.is_returned());
+        assert_eq!(outcome.reject_code(), RejectCode::Return);
+
+        let event = RejectedEvent::create(
+            EventType::new(),
+            EventCode::AcceptorRejected,
+            RejectCode::PhotoLevel,
+        );
+        assert_eq!(
+            RejectOutcome::from_event(&Message::from(&event))?,
+            Some(RejectOutcome::create(
+                EventCode::AcceptorRejected,
+                RejectCode::PhotoLevel
+            ))
+        );
+        assert_eq!(RejectOutcome::from_event(&IdleRequest::new().into())?, None);
+
+        Ok(())
+    }
+}
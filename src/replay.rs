@@ -0,0 +1,393 @@
+//! Offline replay of captured protocol traces.
+//!
+//! A [TraceLog] loads the JSON lines written by a [JsonlTracer](crate::trace::JsonlTracer), so
+//! traces captured in the field can be:
+//!
+//! - re-parsed with the current parsing layer, see [TraceLog::parse_all]
+//! - played back to the polling functions through a [ReplayTransport], standing in for the device
+//! - re-sent to another transport, e.g. a [MockDevice](crate::mock::MockDevice), see
+//!   [replay_requests]
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::sync::Mutex;
+use std::{fmt, time};
+
+use crate::trace::TraceRecord;
+use crate::{DeviceTransport, Error, Message, MessageDirection, Result, REDACTED};
+
+/// Represents a protocol trace loaded for replay.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TraceLog {
+    records: Vec<TraceRecord>,
+}
+
+impl TraceLog {
+    /// Creates a new, empty [TraceLog].
+    pub const fn new() -> Self {
+        Self {
+            records: Vec::new(),
+        }
+    }
+
+    /// Creates a new [TraceLog] from a list of [TraceRecord]s.
+    pub fn create<R: Into<Vec<TraceRecord>>>(records: R) -> Self {
+        Self {
+            records: records.into(),
+        }
+    }
+
+    /// Gets a reference to the list of [TraceRecord]s.
+    pub fn records(&self) -> &[TraceRecord] {
+        self.records.as_ref()
+    }
+
+    /// Gets the number of [TraceRecord]s in the [TraceLog].
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Gets whether the [TraceLog] is empty.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Gets an iterator over the [TraceRecord]s with the [MessageDirection].
+    pub fn direction(
+        &self,
+        direction: MessageDirection,
+    ) -> impl Iterator<Item = &TraceRecord> + '_ {
+        self.records
+            .iter()
+            .filter(move |r| r.direction() == direction)
+    }
+
+    /// Parses one JSON trace line into a [TraceRecord].
+    ///
+    /// The raw frame bytes are the source of truth, the recorded message is re-parsed from them.
+    /// Lines recorded with log redaction enabled can not be replayed.
+    pub fn parse_line(line: &str) -> Result<TraceRecord> {
+        let timestamp_ms = field(line, "timestamp_ms")?
+            .parse::<u64>()
+            .map_err(|err| Error::Replay(format!("invalid timestamp: {err}")))?;
+
+        let direction = match field(line, "direction")? {
+            r#""sent""# => MessageDirection::Sent,
+            r#""received""# => MessageDirection::Received,
+            dir => return Err(Error::Replay(format!("invalid direction: {dir}"))),
+        };
+
+        let raw = field(line, "raw")?.trim_matches('"');
+        if raw == REDACTED {
+            return Err(Error::Replay("raw bytes redacted in the capture".into()));
+        }
+
+        let raw = (0..raw.len())
+            .step_by(2)
+            .map(|i| {
+                raw.get(i..i + 2)
+                    .and_then(|b| u8::from_str_radix(b, 16).ok())
+                    .ok_or(Error::Replay(format!("invalid raw bytes: {raw}")))
+            })
+            .collect::<Result<Vec<u8>>>()?;
+
+        let message = Message::try_from(raw.as_slice()).ok();
+
+        Ok(TraceRecord::create(
+            time::UNIX_EPOCH + time::Duration::from_millis(timestamp_ms),
+            direction,
+            raw,
+            message,
+        ))
+    }
+
+    /// Reads a [TraceLog] from JSON trace lines, skipping blank lines.
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self> {
+        let mut records = Vec::new();
+
+        for (i, line) in reader.lines().enumerate() {
+            let line = line.map_err(|err| Error::Replay(format!("error reading trace: {err}")))?;
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            records.push(
+                Self::parse_line(line.as_str())
+                    .map_err(|err| Error::Replay(format!("line {}: {err}", i + 1)))?,
+            );
+        }
+
+        Ok(Self { records })
+    }
+
+    /// Loads a [TraceLog] from the JSON trace file at `path`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path)
+            .map_err(|err| Error::Replay(format!("unable to open {}: {err}", path.display())))?;
+
+        Self::from_reader(BufReader::new(file))
+    }
+
+    /// Re-parses every captured frame with the current parsing layer.
+    pub fn parse_all(&self) -> Vec<ReplayOutcome> {
+        self.records
+            .iter()
+            .enumerate()
+            .map(|(index, record)| ReplayOutcome {
+                index,
+                direction: record.direction(),
+                result: Message::try_from(record.raw()),
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for TraceLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for record in self.records.iter() {
+            writeln!(f, "{record}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Represents the result of replaying a captured frame.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReplayOutcome {
+    index: usize,
+    direction: MessageDirection,
+    result: Result<Message>,
+}
+
+impl ReplayOutcome {
+    /// Gets the index of the frame in the [TraceLog].
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Gets the [MessageDirection] of the captured frame.
+    pub const fn direction(&self) -> MessageDirection {
+        self.direction
+    }
+
+    /// Gets a reference to the replay result.
+    pub const fn result(&self) -> &Result<Message> {
+        &self.result
+    }
+
+    /// Gets whether the replay succeeded.
+    pub const fn is_ok(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+impl fmt::Display for ReplayOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""index": {}, "#, self.index)?;
+        write!(f, r#""direction": {}, "#, self.direction)?;
+        match self.result.as_ref() {
+            Ok(msg) => write!(f, r#""message": {msg}"#)?,
+            Err(err) => write!(f, r#""error": "{err}""#)?,
+        }
+        write!(f, "}}")
+    }
+}
+
+/// Plays back a captured trace as a [DeviceTransport].
+///
+/// Every read returns the next captured frame read from the device, including the parse error of
+/// malformed frames, so the polling functions see the same traffic as in the field. Written
+/// messages are checked against the next captured frame sent by the host: mismatches are logged,
+/// or returned as [Error::Replay] in strict mode.
+#[derive(Debug)]
+pub struct ReplayTransport {
+    sent: Mutex<VecDeque<TraceRecord>>,
+    received: Mutex<VecDeque<TraceRecord>>,
+    strict: bool,
+}
+
+impl ReplayTransport {
+    /// Creates a new [ReplayTransport] from a [TraceLog].
+    pub fn new(log: &TraceLog) -> Self {
+        Self {
+            sent: Mutex::new(log.direction(MessageDirection::Sent).cloned().collect()),
+            received: Mutex::new(log.direction(MessageDirection::Received).cloned().collect()),
+            strict: false,
+        }
+    }
+
+    /// Gets whether written messages must match the captured trace.
+    pub const fn strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Sets whether written messages must match the captured trace.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Builder function that sets whether written messages must match the captured trace.
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.set_strict(strict);
+        self
+    }
+
+    /// Gets the number of captured device frames not yet read.
+    pub fn remaining(&self) -> usize {
+        self.received
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .len()
+    }
+
+    fn check_sent(&self, message: &Message) -> Result<()> {
+        let raw: Vec<u8> = message.into();
+        let exp = self
+            .sent
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .pop_front();
+
+        match exp {
+            Some(exp) if exp.raw() == raw.as_slice() => Ok(()),
+            exp => {
+                let err_msg = match exp {
+                    Some(exp) => format!("sent message differs from the capture: {exp}"),
+                    None => "sent message past the end of the capture".to_string(),
+                };
+
+                if self.strict {
+                    Err(Error::Replay(err_msg))
+                } else {
+                    log::warn!("{err_msg}");
+                    Ok(())
+                }
+            }
+        }
+    }
+}
+
+impl DeviceTransport for ReplayTransport {
+    fn write_message(&self, message: &Message) -> Result<()> {
+        self.check_sent(message)
+    }
+
+    fn read_message(&self) -> Result<Message> {
+        let record = self
+            .received
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .pop_front()
+            .ok_or(Error::Replay("end of capture".into()))?;
+
+        Message::try_from(record.raw())
+    }
+}
+
+/// Re-sends every captured host [Message] to the [DeviceTransport], and reads the reply.
+///
+/// Use with a [MockDevice](crate::mock::MockDevice) to compare the simulated device behaviour
+/// against the captured device frames.
+pub fn replay_requests<T: DeviceTransport>(log: &TraceLog, transport: &T) -> Vec<ReplayOutcome> {
+    log.records
+        .iter()
+        .enumerate()
+        .filter(|(_, record)| record.direction() == MessageDirection::Sent)
+        .map(|(index, record)| ReplayOutcome {
+            index,
+            direction: MessageDirection::Received,
+            result: Message::try_from(record.raw())
+                .and_then(|msg| transport.write_message(&msg))
+                .and_then(|_| transport.read_message()),
+        })
+        .collect()
+}
+
+// Gets the raw value of a top-level field in a JSON trace line.
+fn field<'a>(line: &'a str, name: &str) -> Result<&'a str> {
+    let key = format!(r#""{name}": "#);
+    let start = line
+        .find(key.as_str())
+        .map(|i| i + key.len())
+        .ok_or(Error::Replay(format!("missing field: {name}")))?;
+    let rem = &line[start..];
+
+    let end = match rem.strip_prefix('"') {
+        Some(quoted) => quoted.find('"').map(|i| i + 2),
+        None => rem.find([',', '}']),
+    };
+
+    Ok(rem[..end.unwrap_or(rem.len())].trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockDevice;
+    use crate::trace::{JsonlTracer, MessageTracer};
+    use crate::{RequestCode, ResponseCode, StatusRequest};
+
+    #[test]
+    fn test_replay() -> Result<()> {
+        let req: Message = StatusRequest::new().into();
+        let res = MockDevice::new();
+        res.write_message(&req)?;
+        let res = res.read_message()?;
+
+        let tracer = JsonlTracer::new(Vec::new());
+        tracer.trace(&TraceRecord::from_message(MessageDirection::Sent, &req));
+        tracer.trace(&TraceRecord::from_message(MessageDirection::Received, &res));
+        tracer.trace(&TraceRecord::new(MessageDirection::Received, &[0x12, 0x01]));
+
+        let capture = tracer.into_inner();
+        let log = TraceLog::from_reader(capture.as_slice())?;
+        assert_eq!(log.len(), 3);
+        assert_eq!(log.records()[0].message(), Some(&req));
+
+        let outcomes = log.parse_all();
+        assert_eq!(
+            outcomes
+                .iter()
+                .map(ReplayOutcome::is_ok)
+                .collect::<Vec<bool>>(),
+            [true, true, false]
+        );
+
+        let transport = ReplayTransport::new(&log).with_strict(true);
+        transport.write_message(&req)?;
+        assert_eq!(transport.read_message()?, res);
+        assert!(transport.read_message().is_err());
+        assert_eq!(
+            transport.read_message(),
+            Err(Error::Replay("end of capture".into()))
+        );
+        assert!(matches!(
+            transport.write_message(&req),
+            Err(Error::Replay(_))
+        ));
+
+        let replayed = replay_requests(&log, &MockDevice::new());
+        assert_eq!(replayed.len(), 1);
+        let replayed = replayed[0].result().as_ref().map_err(Clone::clone)?;
+        assert_eq!(
+            replayed.data().message_code().request_code()?,
+            RequestCode::Status
+        );
+        assert_ne!(
+            replayed.data().additional().first(),
+            Some(&u8::from(ResponseCode::Nak))
+        );
+
+        assert!(TraceLog::parse_line(
+            r#"{"timestamp_ms": 1, "direction": "sent", "raw": "***", "message": null}"#
+        )
+        .is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,326 @@
+//! Serial (RS-232/TTL) transport for JCM devices.
+//!
+//! Frames the same [Message] types as the USB transport over a serial port. The
+//! [SerialDeviceHandle] implements [DeviceTransport], so it works with the polling functions in
+//! place of a USB handle. For streams configured elsewhere (USB-CDC bridges, RS-485 adapters),
+//! use a [UartDeviceHandle] directly.
+
+use std::fs::{File, OpenOptions};
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::io::AsRawFd;
+use std::{fmt, time};
+
+use crate::uart::UartDeviceHandle;
+use crate::{DeviceTransport, Error, Message, Result};
+
+pub use crate::uart::{DEFAULT_INTER_BYTE_TIMEOUT, DEFAULT_SERIAL_READ_TIMEOUT};
+
+/// Represents the default serial baud rate.
+pub const DEFAULT_BAUD_RATE: u32 = 9600;
+
+/// Represents the parity setting of a serial port.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Parity {
+    /// No parity bit.
+    None,
+    /// Even parity.
+    #[default]
+    Even,
+    /// Odd parity.
+    Odd,
+}
+
+impl From<Parity> for &'static str {
+    fn from(val: Parity) -> Self {
+        match val {
+            Parity::None => "none",
+            Parity::Even => "even",
+            Parity::Odd => "odd",
+        }
+    }
+}
+
+impl From<&Parity> for &'static str {
+    fn from(val: &Parity) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for Parity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
+
+/// Represents the serial port settings of a [SerialDeviceHandle].
+///
+/// Frames always use eight data bits and one stop bit.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SerialConfig {
+    baud_rate: u32,
+    parity: Parity,
+    inter_byte_timeout: time::Duration,
+    read_timeout: time::Duration,
+}
+
+impl SerialConfig {
+    /// Creates a new [SerialConfig] with the default settings.
+    pub const fn new() -> Self {
+        Self {
+            baud_rate: DEFAULT_BAUD_RATE,
+            parity: Parity::Even,
+            inter_byte_timeout: DEFAULT_INTER_BYTE_TIMEOUT,
+            read_timeout: DEFAULT_SERIAL_READ_TIMEOUT,
+        }
+    }
+
+    /// Gets the baud rate.
+    pub const fn baud_rate(&self) -> u32 {
+        self.baud_rate
+    }
+
+    /// Builder function that sets the baud rate.
+    ///
+    /// Supported rates: 9600, 19200, 38400, 57600, and 115200.
+    pub fn with_baud_rate(mut self, val: u32) -> Self {
+        self.baud_rate = val;
+        self
+    }
+
+    /// Gets the [Parity].
+    pub const fn parity(&self) -> Parity {
+        self.parity
+    }
+
+    /// Builder function that sets the [Parity].
+    pub fn with_parity(mut self, val: Parity) -> Self {
+        self.parity = val;
+        self
+    }
+
+    /// Gets the maximum gap between two bytes of the same frame.
+    ///
+    /// The serial driver measures the gap in tenths of a second, so the timeout is rounded up
+    /// to the next 100ms, and capped at 25.5s.
+    pub const fn inter_byte_timeout(&self) -> time::Duration {
+        self.inter_byte_timeout
+    }
+
+    /// Builder function that sets the maximum gap between two bytes of the same frame.
+    pub fn with_inter_byte_timeout(mut self, val: time::Duration) -> Self {
+        self.inter_byte_timeout = val;
+        self
+    }
+
+    /// Gets the time to wait for the first byte of a frame.
+    pub const fn read_timeout(&self) -> time::Duration {
+        self.read_timeout
+    }
+
+    /// Builder function that sets the time to wait for the first byte of a frame.
+    pub fn with_read_timeout(mut self, val: time::Duration) -> Self {
+        self.read_timeout = val;
+        self
+    }
+
+    fn speed(&self) -> Result<libc::speed_t> {
+        match self.baud_rate {
+            9600 => Ok(libc::B9600),
+            19200 => Ok(libc::B19200),
+            38400 => Ok(libc::B38400),
+            57600 => Ok(libc::B57600),
+            115200 => Ok(libc::B115200),
+            rate => Err(Error::Serial(format!("unsupported baud rate: {rate}"))),
+        }
+    }
+
+    fn vtime(&self) -> libc::cc_t {
+        self.inter_byte_timeout
+            .as_millis()
+            .div_ceil(100)
+            .clamp(1, 255) as libc::cc_t
+    }
+}
+
+impl Default for SerialConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for SerialConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""baud_rate": {}, "#, self.baud_rate)?;
+        write!(f, r#""parity": {}, "#, self.parity)?;
+        write!(
+            f,
+            r#""inter_byte_timeout_ms": {}, "#,
+            self.inter_byte_timeout.as_millis()
+        )?;
+        write!(f, r#""read_timeout_ms": {}"#, self.read_timeout.as_millis())?;
+        write!(f, "}}")
+    }
+}
+
+/// Represents a host-side serial device handle.
+///
+/// Reads and writes go through a [UartDeviceHandle] over the configured port.
+pub struct SerialDeviceHandle {
+    uart: UartDeviceHandle<File>,
+    path: String,
+    config: SerialConfig,
+}
+
+impl SerialDeviceHandle {
+    /// Opens the serial port at the provided path (e.g. `/dev/ttyUSB0`), and applies the
+    /// [SerialConfig].
+    pub fn open(path: &str, config: SerialConfig) -> Result<Self> {
+        let port = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .custom_flags(libc::O_NOCTTY)
+            .open(path)
+            .map_err(|err| Error::Serial(format!("unable to open {path}: {err}")))?;
+
+        configure_port(&port, &config)?;
+
+        log::info!("opened serial port {path}: {config}");
+
+        Ok(Self {
+            uart: UartDeviceHandle::new(port)
+                .with_read_timeout(config.read_timeout)
+                .with_inter_byte_timeout(config.inter_byte_timeout),
+            path: path.into(),
+            config,
+        })
+    }
+
+    /// Gets the path of the serial port.
+    pub fn path(&self) -> &str {
+        self.path.as_str()
+    }
+
+    /// Gets the [SerialConfig].
+    pub const fn config(&self) -> SerialConfig {
+        self.config
+    }
+
+    /// Gets the maximum length of a frame read from the device.
+    pub const fn max_frame_len(&self) -> usize {
+        self.uart.max_frame_len()
+    }
+
+    /// Builder function that sets the maximum length of a frame read from the device.
+    ///
+    /// The length is capped at the [MAX_LEN](crate::MAX_LEN) of a [Message].
+    pub fn with_max_frame_len(mut self, max_frame_len: usize) -> Self {
+        self.uart = self.uart.with_max_frame_len(max_frame_len);
+        self
+    }
+
+    /// Writes a [Message] to the serial port.
+    pub fn write_message(&self, message: &Message) -> Result<()> {
+        self.uart.write_message(message)
+    }
+
+    /// Reads a [Message] from the serial port.
+    ///
+    /// Waits up to the [read timeout](SerialConfig::read_timeout) for the start of a frame. Once
+    /// started, the frame fails with [Error::Timeout] if the gap between two bytes exceeds the
+    /// [inter-byte timeout](SerialConfig::inter_byte_timeout).
+    pub fn read_message(&self) -> Result<Message> {
+        self.uart.read_message()
+    }
+}
+
+impl DeviceTransport for SerialDeviceHandle {
+    fn write_message(&self, message: &Message) -> Result<()> {
+        SerialDeviceHandle::write_message(self, message)
+    }
+
+    fn read_message(&self) -> Result<Message> {
+        SerialDeviceHandle::read_message(self)
+    }
+
+    fn timeout(&self) -> time::Duration {
+        self.config.read_timeout
+    }
+}
+
+fn configure_port(port: &File, config: &SerialConfig) -> Result<()> {
+    let fd = port.as_raw_fd();
+    let speed = config.speed()?;
+
+    let last_err = |call: &str| {
+        Error::Serial(format!(
+            "{call} failed: {}",
+            std::io::Error::last_os_error()
+        ))
+    };
+
+    // SAFETY: `termios` is plain old data, and is fully initialized by `tcgetattr` before use.
+    let mut tty: libc::termios = unsafe { std::mem::zeroed() };
+
+    // SAFETY: `fd` is an open file descriptor owned by `port`, and `tty` is a valid pointer.
+    unsafe {
+        if libc::tcgetattr(fd, &mut tty) != 0 {
+            return Err(last_err("tcgetattr"));
+        }
+
+        libc::cfmakeraw(&mut tty);
+
+        if libc::cfsetispeed(&mut tty, speed) != 0 || libc::cfsetospeed(&mut tty, speed) != 0 {
+            return Err(last_err("cfsetspeed"));
+        }
+    }
+
+    tty.c_cflag &= !(libc::CSIZE | libc::CSTOPB | libc::PARENB | libc::PARODD | libc::CRTSCTS);
+    tty.c_cflag |= libc::CS8 | libc::CLOCAL | libc::CREAD;
+    match config.parity {
+        Parity::None => (),
+        Parity::Even => tty.c_cflag |= libc::PARENB,
+        Parity::Odd => tty.c_cflag |= libc::PARENB | libc::PARODD,
+    }
+
+    // return as soon as any byte arrives, or after the inter-byte timeout with no data
+    tty.c_cc[libc::VMIN] = 0;
+    tty.c_cc[libc::VTIME] = config.vtime();
+
+    // SAFETY: `fd` is an open file descriptor owned by `port`, and `tty` is a valid pointer.
+    unsafe {
+        if libc::tcsetattr(fd, libc::TCSANOW, &tty) != 0 {
+            return Err(last_err("tcsetattr"));
+        }
+
+        libc::tcflush(fd, libc::TCIOFLUSH);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serial_config() {
+        let config = SerialConfig::new()
+            .with_baud_rate(38400)
+            .with_parity(Parity::None)
+            .with_inter_byte_timeout(time::Duration::from_millis(150));
+
+        assert_eq!(config.speed(), Ok(libc::B38400));
+        assert_eq!(config.vtime(), 2);
+        assert_eq!(
+            SerialConfig::new()
+                .with_inter_byte_timeout(time::Duration::ZERO)
+                .vtime(),
+            1
+        );
+        assert!(SerialConfig::new().with_baud_rate(1234).speed().is_err());
+        assert!(SerialDeviceHandle::open("/nonexistent/tty", config).is_err());
+    }
+}
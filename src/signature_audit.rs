@@ -0,0 +1,251 @@
+use std::{fmt, time};
+
+use crate::{
+    Clock, Error, EventCode, HashAlgorithm, Message, MessageCode, MessageData,
+    ProgramSignatureMode, ProgramSignatureRequest, ProgramSignatureResponse, ResponseCode, Result,
+};
+
+/// Represents the default firmware signature audit interval.
+pub const SIGNATURE_AUDIT_INTERVAL: time::Duration = time::Duration::from_secs(3_600);
+
+/// Periodically re-runs `Program Signature` verification against a pinned expected hash.
+///
+/// Each audit sends a `Program Signature` request with the configured seed value. The device
+/// acknowledges the request, and later reports the computed hash in a `Program Signature` event.
+/// Pass device events to [on_event](Self::on_event): a reported hash that differs from the pinned
+/// hash raises [Error::TamperSuspected].
+///
+/// Every reported hash is verified, including ones the device sends without a pending audit.
+#[derive(Clone, Debug)]
+pub struct SignatureAudit<C: Clock> {
+    clock: C,
+    interval: time::Duration,
+    seed: HashAlgorithm,
+    expected: HashAlgorithm,
+    last_audit: Option<time::Duration>,
+    pending: bool,
+    audits: u64,
+    alarms: u64,
+}
+
+impl<C: Clock> SignatureAudit<C> {
+    /// Creates a new [SignatureAudit] using the provided [Clock], and pinned expected hash.
+    ///
+    /// The seed value defaults to zero for the expected hash algorithm. The first audit is due
+    /// immediately.
+    pub fn new(clock: C, expected: HashAlgorithm) -> Self {
+        Self {
+            clock,
+            interval: SIGNATURE_AUDIT_INTERVAL,
+            seed: HashAlgorithm::from_u8(expected.algorithm_number().into_u8()).unwrap_or_default(),
+            expected,
+            last_audit: None,
+            pending: false,
+            audits: 0,
+            alarms: 0,
+        }
+    }
+
+    /// Gets the audit interval.
+    pub const fn interval(&self) -> time::Duration {
+        self.interval
+    }
+
+    /// Sets the audit interval.
+    pub fn set_interval(&mut self, interval: time::Duration) {
+        self.interval = interval;
+    }
+
+    /// Builder function that sets the audit interval.
+    pub fn with_interval(mut self, interval: time::Duration) -> Self {
+        self.set_interval(interval);
+        self
+    }
+
+    /// Gets the seed value sent with each audit request.
+    pub const fn seed(&self) -> &HashAlgorithm {
+        &self.seed
+    }
+
+    /// Sets the seed value sent with each audit request.
+    pub fn set_seed(&mut self, seed: HashAlgorithm) {
+        self.seed = seed;
+    }
+
+    /// Builder function that sets the seed value sent with each audit request.
+    pub fn with_seed(mut self, seed: HashAlgorithm) -> Self {
+        self.set_seed(seed);
+        self
+    }
+
+    /// Gets the pinned expected hash.
+    pub const fn expected(&self) -> &HashAlgorithm {
+        &self.expected
+    }
+
+    /// Gets whether an audit is waiting for the device to report the hash.
+    pub const fn pending(&self) -> bool {
+        self.pending
+    }
+
+    /// Gets the number of audit requests sent.
+    pub const fn audits(&self) -> u64 {
+        self.audits
+    }
+
+    /// Gets the number of [Error::TamperSuspected] alarms raised.
+    pub const fn alarms(&self) -> u64 {
+        self.alarms
+    }
+
+    /// Gets whether an audit request is due.
+    pub fn is_due(&self) -> bool {
+        !self.pending
+            && self
+                .last_audit
+                .map(|last| self.clock.elapsed(last) >= self.interval)
+                .unwrap_or(true)
+    }
+
+    /// Sends an audit `Program Signature` request through the polling function if one is due.
+    ///
+    /// Returns `None` if no audit was due.
+    pub fn tick<F>(&mut self, poll: F) -> Option<Result<ProgramSignatureResponse>>
+    where
+        F: FnOnce(&Message) -> Result<Message>,
+    {
+        if !self.is_due() {
+            return None;
+        }
+
+        let req = ProgramSignatureRequest::new()
+            .with_mode(ProgramSignatureMode::Set)
+            .with_hash_algorithm(self.seed);
+
+        self.last_audit = Some(self.clock.now());
+        self.audits = self.audits.saturating_add(1);
+
+        let res = poll(&req.into())
+            .and_then(ProgramSignatureResponse::try_from)
+            .and_then(|res| match res.code() {
+                ResponseCode::Ack => Ok(res),
+                code => Err(Error::InvalidResponseCode(code.into())),
+            });
+
+        match res.as_ref() {
+            Ok(_) => self.pending = true,
+            Err(err) => log::warn!("program signature audit request failed: {err}"),
+        }
+
+        Some(res)
+    }
+
+    /// Verifies the hash reported in a device `Program Signature` event.
+    ///
+    /// Returns `None` for any other event.
+    pub fn on_event(&mut self, event: &Message) -> Option<Result<()>> {
+        let data = event.data();
+
+        if data.message_code() != MessageCode::Event(EventCode::ProgramSignature) {
+            return None;
+        }
+
+        self.pending = false;
+
+        Some(self.verify(data))
+    }
+
+    fn verify(&mut self, data: &MessageData) -> Result<()> {
+        let hash = HashAlgorithm::from_request(data.additional())?;
+
+        if hash == self.expected {
+            log::debug!("program signature verified: {hash}");
+            Ok(())
+        } else {
+            self.alarms = self.alarms.saturating_add(1);
+            log::error!(
+                "program signature mismatch, expected: {}, read: {hash}",
+                self.expected
+            );
+
+            Err(Error::TamperSuspected((
+                self.expected.as_bytes().into(),
+                hash.as_bytes().into(),
+            )))
+        }
+    }
+}
+
+impl<C: Clock> fmt::Display for SignatureAudit<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""interval_ms": {}, "#, self.interval.as_millis())?;
+        write!(f, r#""expected": {}, "#, self.expected)?;
+        write!(f, r#""pending": {}, "#, self.pending)?;
+        write!(f, r#""audits": {}, "#, self.audits)?;
+        write!(f, r#""alarms": {}"#, self.alarms)?;
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventType, MessageType, SimulatedClock};
+
+    fn signature_event(hash: HashAlgorithm) -> Message {
+        Message::new().with_data(
+            MessageData::new()
+                .with_message_type(MessageType::Event(EventType::Sequence0))
+                .with_message_code(MessageCode::Event(EventCode::ProgramSignature))
+                .with_additional(hash.into_request().as_ref()),
+        )
+    }
+
+    #[test]
+    fn test_signature_audit() -> Result<()> {
+        let clock = SimulatedClock::new();
+        let interval = time::Duration::from_secs(60);
+        let expected = HashAlgorithm::Crc16([0x12, 0x34]);
+        let mut audit = SignatureAudit::new(&clock, expected).with_interval(interval);
+
+        let ack = |req: &Message| -> Result<Message> {
+            assert_eq!(
+                ProgramSignatureRequest::try_from(req)?.mode(),
+                ProgramSignatureMode::Set
+            );
+            Ok(Message::new().with_data(
+                req.data()
+                    .clone()
+                    .with_additional(&[ResponseCode::Ack.into()]),
+            ))
+        };
+
+        assert!(audit.tick(ack).transpose()?.is_some());
+        assert!(audit.pending());
+
+        // no new audit until the device reports the hash
+        clock.advance(interval);
+        assert!(audit.tick(ack).is_none());
+
+        assert_eq!(audit.on_event(&signature_event(expected)), Some(Ok(())));
+        assert!(!audit.pending());
+        assert!(audit.on_event(&Message::new()).is_none());
+
+        assert!(audit.tick(ack).is_some());
+        assert!(audit.tick(ack).is_none());
+
+        assert_eq!(
+            audit.on_event(&signature_event(HashAlgorithm::Crc16([0xde, 0xad]))),
+            Some(Err(Error::TamperSuspected((
+                vec![0x12, 0x34],
+                vec![0xde, 0xad]
+            ))))
+        );
+
+        assert_eq!(audit.audits(), 2);
+        assert_eq!(audit.alarms(), 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,178 @@
+use core::fmt;
+
+use crate::{Error, EventCode, MessageCode, RequestCode, Result};
+
+/// Event codes introduced in [SpecVersion::V2].
+pub const V2_EVENT_CODES: &[EventCode] = &[
+    EventCode::Insert,
+    EventCode::ConditionalVend,
+    EventCode::Pause,
+    EventCode::Resume,
+    EventCode::FunctionAbeyance,
+    EventCode::RecyclerDispensing,
+    EventCode::RecyclerDispensed,
+];
+
+/// Request codes introduced in [SpecVersion::V2].
+pub const V2_REQUEST_CODES: &[RequestCode] = &[
+    RequestCode::Insert,
+    RequestCode::ConditionalVend,
+    RequestCode::Pause,
+    RequestCode::NoteDataInfo,
+    RequestCode::RecyclerCollect,
+    RequestCode::RecyclerDispense,
+];
+
+/// Represents the revision of the ID-008 protocol specification implemented by a device.
+///
+/// Code tables differ across revisions. Decoding against a specific [SpecVersion] treats codes
+/// from later revisions as unknown, so a code reused by an older device generation (e.g. for a
+/// vendor-specific event) is never mistaken for a newer standard code.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpecVersion {
+    /// Initial revision: common and acceptor features.
+    V1 = 1,
+    /// Adds the `Insert`, `Conditional Vend`, and `Pause` operations, note data info, and
+    /// recycler collection and dispense.
+    #[default]
+    V2 = 2,
+}
+
+impl SpecVersion {
+    /// Represents the latest supported [SpecVersion].
+    pub const LATEST: Self = Self::V2;
+
+    /// Creates a new [SpecVersion] for the latest revision.
+    pub const fn new() -> Self {
+        Self::LATEST
+    }
+
+    /// Gets the [SpecVersion] that introduced the [EventCode].
+    pub fn event_since(code: EventCode) -> Self {
+        if V2_EVENT_CODES.contains(&code) {
+            Self::V2
+        } else {
+            Self::V1
+        }
+    }
+
+    /// Gets the [SpecVersion] that introduced the [RequestCode].
+    pub fn request_since(code: RequestCode) -> Self {
+        if V2_REQUEST_CODES.contains(&code) {
+            Self::V2
+        } else {
+            Self::V1
+        }
+    }
+
+    /// Gets whether the [EventCode] is defined in this [SpecVersion].
+    pub fn supports_event(&self, code: EventCode) -> bool {
+        code.is_valid() && Self::event_since(code) <= *self
+    }
+
+    /// Gets whether the [RequestCode] is defined in this [SpecVersion].
+    pub fn supports_request(&self, code: RequestCode) -> bool {
+        code.is_valid() && Self::request_since(code) <= *self
+    }
+
+    /// Gets whether the [MessageCode] is defined in this [SpecVersion].
+    pub fn supports(&self, code: MessageCode) -> bool {
+        match code {
+            MessageCode::Event(code) => self.supports_event(code),
+            MessageCode::Request(code) => self.supports_request(code),
+            MessageCode::Reserved => false,
+        }
+    }
+
+    /// Infallible conversion from a [`u16`] into an [EventCode] defined in this [SpecVersion].
+    ///
+    /// Codes from later revisions convert to [EventCode::Reserved].
+    pub fn event_code(&self, val: u16) -> EventCode {
+        match EventCode::from_u16(val) {
+            code if self.supports_event(code) => code,
+            _ => EventCode::Reserved,
+        }
+    }
+
+    /// Infallible conversion from a [`u16`] into a [RequestCode] defined in this [SpecVersion].
+    ///
+    /// Codes from later revisions convert to [RequestCode::Reserved].
+    pub fn request_code(&self, val: u16) -> RequestCode {
+        match RequestCode::from_u16(val) {
+            code if self.supports_request(code) => code,
+            _ => RequestCode::Reserved,
+        }
+    }
+
+    /// Validates that the [MessageCode] is defined in this [SpecVersion].
+    pub fn validate(&self, code: MessageCode) -> Result<MessageCode> {
+        match code {
+            code if self.supports(code) => Ok(code),
+            MessageCode::Event(code) => Err(Error::InvalidEventCode(code.into())),
+            MessageCode::Request(code) => Err(Error::InvalidRequestCode(code.into())),
+            MessageCode::Reserved => Err(Error::InvalidRequestCode(RequestCode::Reserved.into())),
+        }
+    }
+}
+
+impl From<SpecVersion> for &'static str {
+    fn from(val: SpecVersion) -> Self {
+        match val {
+            SpecVersion::V1 => "v1",
+            SpecVersion::V2 => "v2",
+        }
+    }
+}
+
+impl From<&SpecVersion> for &'static str {
+    fn from(val: &SpecVersion) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for SpecVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec_version() {
+        assert_eq!(SpecVersion::default(), SpecVersion::LATEST);
+
+        for version in [SpecVersion::V1, SpecVersion::V2] {
+            assert!(version.supports_event(EventCode::Escrow));
+            assert!(version.supports_request(RequestCode::Status));
+            assert!(!version.supports_event(EventCode::Reserved));
+            assert_eq!(version.event_code(EventCode::Idle.into()), EventCode::Idle);
+        }
+
+        let pause = u16::from(EventCode::Pause);
+        assert_eq!(SpecVersion::V1.event_code(pause), EventCode::Reserved);
+        assert_eq!(SpecVersion::V2.event_code(pause), EventCode::Pause);
+
+        let recycler = u16::from(RequestCode::RecyclerCollect);
+        assert_eq!(
+            SpecVersion::V1.request_code(recycler),
+            RequestCode::Reserved
+        );
+        assert_eq!(
+            SpecVersion::V2.request_code(recycler),
+            RequestCode::RecyclerCollect
+        );
+
+        assert_eq!(
+            SpecVersion::V1.validate(MessageCode::Event(EventCode::Insert)),
+            Err(Error::InvalidEventCode(EventCode::Insert.into()))
+        );
+        assert!(SpecVersion::V2
+            .validate(MessageCode::Event(EventCode::Insert))
+            .is_ok());
+    }
+}
@@ -0,0 +1,281 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Mutex;
+
+use crate::{
+    is_status_message, status_message_status, BillAcceptorState, DeviceStatus, EventCode,
+    MajorMinorStatus, Message, PollObserver,
+};
+
+/// Maximum number of [StateDiagnostic] items retained by a [StateTracker].
+pub const MAX_STATE_DIAGNOSTICS: usize = 64;
+
+/// Represents a protocol-violating [BillAcceptorState] transition reported by the device.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct StateDiagnostic {
+    from: BillAcceptorState,
+    to: BillAcceptorState,
+    event_code: EventCode,
+}
+
+impl StateDiagnostic {
+    /// Creates a new [StateDiagnostic] from the provided parameters.
+    pub const fn create(
+        from: BillAcceptorState,
+        to: BillAcceptorState,
+        event_code: EventCode,
+    ) -> Self {
+        Self {
+            from,
+            to,
+            event_code,
+        }
+    }
+
+    /// Gets the [BillAcceptorState] before the invalid transition.
+    pub const fn from(&self) -> BillAcceptorState {
+        self.from
+    }
+
+    /// Gets the [BillAcceptorState] announced by the device.
+    pub const fn to(&self) -> BillAcceptorState {
+        self.to
+    }
+
+    /// Gets the [EventCode] that announced the invalid transition.
+    pub const fn event_code(&self) -> EventCode {
+        self.event_code
+    }
+}
+
+impl fmt::Display for StateDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""from": {}, "#, self.from)?;
+        write!(f, r#""to": {}, "#, self.to)?;
+        write!(f, r#""event_code": {}"#, self.event_code)?;
+        write!(f, "}}")
+    }
+}
+
+/// Tracks the [BillAcceptorState] of a device from its event [Message]s.
+///
+/// Transitions not allowed by [BillAcceptorState::can_transition] are still applied, since the
+/// device is the source of truth, but are recorded as [StateDiagnostic]s.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StateTracker {
+    state: BillAcceptorState,
+    diagnostics: VecDeque<StateDiagnostic>,
+}
+
+impl StateTracker {
+    /// Creates a new [StateTracker].
+    pub const fn new() -> Self {
+        Self {
+            state: BillAcceptorState::new(),
+            diagnostics: VecDeque::new(),
+        }
+    }
+
+    /// Gets the current [BillAcceptorState].
+    pub const fn state(&self) -> BillAcceptorState {
+        self.state
+    }
+
+    /// Gets an iterator over the recorded [StateDiagnostic]s, oldest first.
+    pub fn diagnostics(&self) -> impl Iterator<Item = &StateDiagnostic> {
+        self.diagnostics.iter()
+    }
+
+    /// Takes the recorded [StateDiagnostic]s, leaving the [StateTracker] diagnostics empty.
+    pub fn take_diagnostics(&mut self) -> Vec<StateDiagnostic> {
+        self.diagnostics.drain(..).collect()
+    }
+
+    /// Resets the [StateTracker] to its initial state, and clears diagnostics.
+    pub fn reset(&mut self) {
+        self.state = BillAcceptorState::new();
+        self.diagnostics.clear();
+    }
+
+    /// Updates the tracked state from an [EventCode].
+    ///
+    /// Returns a [StateDiagnostic] if the announced transition is not allowed.
+    pub fn on_event_code(&mut self, event_code: EventCode) -> Option<StateDiagnostic> {
+        let to = BillAcceptorState::from_event_code(event_code);
+
+        if to == BillAcceptorState::Reserved {
+            return None;
+        }
+
+        let from = self.state;
+        self.state = to;
+
+        if BillAcceptorState::can_transition(from, to) {
+            None
+        } else {
+            let diag = StateDiagnostic::create(from, to, event_code);
+            log::warn!("invalid bill acceptor state transition: {diag}");
+
+            if self.diagnostics.len() >= MAX_STATE_DIAGNOSTICS {
+                self.diagnostics.pop_front();
+            }
+            self.diagnostics.push_back(diag);
+
+            Some(diag)
+        }
+    }
+
+    /// Updates the tracked state from a [DeviceStatus].
+    ///
+    /// Statuses that repeat the tracked state, or announce no state, are ignored. Diagnostics
+    /// record the event that announces the same state.
+    pub fn on_status(&mut self, status: DeviceStatus) -> Option<StateDiagnostic> {
+        let event_code = match status.major_minor_status() {
+            MajorMinorStatus::PowerUp => EventCode::PowerUp,
+            MajorMinorStatus::PowerUpAcceptor => EventCode::PowerUpAcceptor,
+            MajorMinorStatus::PowerUpStacker => EventCode::PowerUpStacker,
+            MajorMinorStatus::PowerUpAcceptorAccepting => EventCode::PowerUpAcceptorAccepting,
+            MajorMinorStatus::PowerUpStackerAccepting => EventCode::PowerUpStackerAccepting,
+            MajorMinorStatus::Normal => EventCode::Inhibit,
+            MajorMinorStatus::NormalIdle => EventCode::Idle,
+            MajorMinorStatus::NormalEscrow => EventCode::Escrow,
+            MajorMinorStatus::NormalVendValid => EventCode::VendValid,
+            _ => return None,
+        };
+
+        if BillAcceptorState::from_event_code(event_code) == self.state {
+            None
+        } else {
+            self.on_event_code(event_code)
+        }
+    }
+
+    /// Updates the tracked state from an event, or device `Status` [Message].
+    ///
+    /// Other messages are ignored.
+    pub fn on_message(&mut self, message: &Message) -> Option<StateDiagnostic> {
+        if is_status_message(message) {
+            status_message_status(message)
+                .ok()
+                .and_then(|status| self.on_status(status))
+        } else {
+            message
+                .data()
+                .message_code()
+                .event_code()
+                .ok()
+                .and_then(|code| self.on_event_code(code))
+        }
+    }
+}
+
+/// Routes device-initiated `Status` messages seen while polling into the [StateTracker].
+impl PollObserver for Mutex<StateTracker> {
+    fn on_status(&self, status: &Message) {
+        if let Ok(mut tracker) = self.lock() {
+            tracker.on_message(status);
+        }
+    }
+}
+
+impl Default for StateTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for StateTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""state": {}, "#, self.state)?;
+        write!(f, r#""diagnostics": ["#)?;
+        for (i, diag) in self.diagnostics.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{diag}")?;
+        }
+        write!(f, "]}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        EventType, FuncId, MessageCode, MessageData, MessageType, ResponseCode, StatusRequest,
+    };
+
+    #[test]
+    fn test_state_tracker() {
+        let mut tracker = StateTracker::new();
+
+        for code in [
+            EventCode::PowerUp,
+            EventCode::Inhibit,
+            EventCode::Idle,
+            EventCode::Insert,
+            EventCode::Escrow,
+            EventCode::VendValid,
+            EventCode::AcceptorCollected,
+            EventCode::Idle,
+        ] {
+            assert_eq!(tracker.on_event_code(code), None);
+        }
+
+        assert_eq!(tracker.state(), BillAcceptorState::Idle);
+        assert_eq!(tracker.diagnostics().count(), 0);
+
+        let exp_diag = StateDiagnostic::create(
+            BillAcceptorState::Idle,
+            BillAcceptorState::VendValid,
+            EventCode::VendValid,
+        );
+
+        let msg = Message::new().with_data(
+            MessageData::new()
+                .with_message_type(MessageType::Event(EventType::Sequence0))
+                .with_message_code(MessageCode::Event(EventCode::VendValid)),
+        );
+
+        assert_eq!(tracker.on_message(&msg), Some(exp_diag));
+        assert_eq!(tracker.state(), BillAcceptorState::VendValid);
+        assert_eq!(tracker.take_diagnostics(), [exp_diag]);
+        assert_eq!(tracker.diagnostics().count(), 0);
+    }
+
+    #[test]
+    fn test_state_tracker_max_diagnostics() {
+        let mut tracker = StateTracker::new();
+
+        tracker.on_event_code(EventCode::Inhibit);
+
+        for _ in 0..MAX_STATE_DIAGNOSTICS + 2 {
+            assert!(tracker.on_event_code(EventCode::Escrow).is_some());
+            tracker.on_event_code(EventCode::Inhibit);
+        }
+
+        assert_eq!(tracker.diagnostics().count(), MAX_STATE_DIAGNOSTICS);
+    }
+
+    #[test]
+    fn test_state_tracker_status() {
+        let tracker = Mutex::new(StateTracker::new());
+        let status = |major_minor| {
+            let mut additional = vec![ResponseCode::Ack.into(), DeviceStatus::len() as u8];
+            additional.extend(DeviceStatus::create(FuncId::Acceptor, major_minor).to_bytes());
+            Message::from(MessageData::from(StatusRequest::new()).with_additional(&additional))
+        };
+
+        tracker.on_status(&status(MajorMinorStatus::PowerUp));
+        tracker.on_status(&status(MajorMinorStatus::NormalIdle));
+        tracker.on_status(&status(MajorMinorStatus::NormalIdle));
+        tracker.on_status(&status(MajorMinorStatus::NormalActive));
+
+        let tracker = tracker.into_inner().unwrap();
+        assert_eq!(tracker.state(), BillAcceptorState::Idle);
+        assert_eq!(tracker.diagnostics().count(), 0);
+    }
+}
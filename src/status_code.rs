@@ -1,4 +1,4 @@
-use std::fmt;
+use core::fmt;
 
 use crate::{Error, Result};
 
@@ -30,6 +30,7 @@ const RESERVED: u8 = 0xff;
 /// Represents status codes returned by JCM devices.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StatusCode {
     CompletedResetReq = COMPLETED_RESET_REQ,
     ReceivedResetReq = RECEIVED_RESET_REQ,
@@ -213,7 +214,7 @@ mod tests {
             StatusCode::ReceivedResetReqAlt,
         ];
 
-        for (raw, exp) in raw_denom.into_iter().zip(expected.into_iter()) {
+        for (raw, exp) in raw_denom.into_iter().zip(expected) {
             assert_eq!(StatusCode::try_from(raw), Ok(exp));
             assert_eq!(StatusCode::from_u8(raw), exp);
         }
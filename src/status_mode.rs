@@ -0,0 +1,174 @@
+use core::fmt;
+
+use crate::{
+    DeviceStatus, Error, Message, MessageCode, MessageData, MessageType, PauseMode, PauseRequest,
+    PauseSettings, RequestCode, RequestType, Response, ResponseCode, Result, StatusResponse,
+};
+
+/// `Pause` settings flag enabling device-initiated `Status` messages.
+pub const STATUS_MESSAGE_FLAG: u8 = 0b01;
+/// `Pause` settings flag enabling device-initiated event messages.
+pub const EVENT_MESSAGE_FLAG: u8 = 0b10;
+
+/// Represents how the host receives the device status.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum StatusMessageMode {
+    /// The host polls the device with `Status` requests.
+    #[default]
+    Polled,
+    /// The device pushes periodic `Status` messages, without a request.
+    Unsolicited,
+}
+
+impl StatusMessageMode {
+    /// Creates a new [StatusMessageMode].
+    pub const fn new() -> Self {
+        Self::Polled
+    }
+
+    /// Gets the `Pause` settings flags for the [StatusMessageMode].
+    ///
+    /// Event messages stay enabled in both modes.
+    pub const fn flags(&self) -> u8 {
+        match self {
+            Self::Polled => EVENT_MESSAGE_FLAG,
+            Self::Unsolicited => EVENT_MESSAGE_FLAG | STATUS_MESSAGE_FLAG,
+        }
+    }
+
+    /// Creates the `Pause` settings request [Message] that enables the [StatusMessageMode].
+    ///
+    /// The request data is the pause duration in milliseconds (little-endian, zero leaves the
+    /// device running), followed by the message flags.
+    pub fn request(&self, uid: u8) -> Message {
+        MessageData::from(self.pause_request()).with_uid(uid).into()
+    }
+
+    /// Creates the [PauseRequest] that enables the [StatusMessageMode].
+    pub const fn pause_request(&self) -> PauseRequest {
+        PauseRequest::new()
+            .with_mode(PauseMode::Set)
+            .with_settings(self.pause_settings())
+    }
+
+    /// Gets the [PauseSettings] that enable the [StatusMessageMode], without pausing the device.
+    pub const fn pause_settings(&self) -> PauseSettings {
+        PauseSettings::new()
+            .with_status_messages(matches!(self, Self::Unsolicited))
+            .with_event_messages(true)
+    }
+}
+
+impl From<StatusMessageMode> for &'static str {
+    fn from(val: StatusMessageMode) -> Self {
+        match val {
+            StatusMessageMode::Polled => "polled",
+            StatusMessageMode::Unsolicited => "unsolicited",
+        }
+    }
+}
+
+impl From<&StatusMessageMode> for &'static str {
+    fn from(val: &StatusMessageMode) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for StatusMessageMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
+
+/// Gets whether the [Message] is a device `Status` message.
+///
+/// In [StatusMessageMode::Unsolicited], these arrive on the response channel without a matching
+/// request.
+pub fn is_status_message(message: &Message) -> bool {
+    let data = message.data();
+
+    data.message_type() == MessageType::Request(RequestType::Status)
+        && data.message_code() == MessageCode::Request(RequestCode::Status)
+}
+
+/// Parses the [DeviceStatus] from a device `Status` message.
+pub fn status_message_status(message: &Message) -> Result<DeviceStatus> {
+    if is_status_message(message) {
+        StatusResponse::try_from(message).map(|res| res.status())
+    } else {
+        let data = message.data();
+        Err(Error::InvalidMessage((
+            (data.message_type().into(), data.message_code().into()),
+            (
+                MessageType::Request(RequestType::Status).into(),
+                MessageCode::Request(RequestCode::Status).into(),
+            ),
+        )))
+    }
+}
+
+/// Sets the [StatusMessageMode] of the device with a `Pause` settings request.
+///
+/// Returns [Error::InvalidResponseCode] if the device rejects the settings, e.g. firmware
+/// without support for device-initiated `Status` messages.
+pub fn set_status_message_mode<P>(uid: u8, mode: StatusMessageMode, mut poll: P) -> Result<()>
+where
+    P: FnMut(&Message) -> Result<Message>,
+{
+    let res = Response::try_from(poll(&mode.request(uid))?)?;
+
+    match res.code() {
+        ResponseCode::Ack => {
+            log::info!("status message mode: {mode}");
+            Ok(())
+        }
+        code => Err(Error::InvalidResponseCode(code.into())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FuncId, MajorMinorStatus, StatusRequest};
+
+    #[test]
+    fn test_status_message_mode() -> Result<()> {
+        let req = StatusMessageMode::Unsolicited.request(1);
+        assert_eq!(req.data().uid(), 1);
+        assert_eq!(
+            req.data().message_code(),
+            MessageCode::Request(RequestCode::Pause)
+        );
+        assert_eq!(req.data().additional(), [0, 0, 0b11]);
+
+        let status = DeviceStatus::create(FuncId::Acceptor, MajorMinorStatus::NormalIdle);
+        let push: Message = MessageData::from(StatusRequest::new())
+            .with_additional(
+                &[
+                    [ResponseCode::Ack.into(), DeviceStatus::len() as u8].as_ref(),
+                    status.to_bytes().as_ref(),
+                ]
+                .concat(),
+            )
+            .into();
+
+        assert!(is_status_message(&push));
+        assert_eq!(status_message_status(&push)?, status);
+        assert!(status_message_status(&req).is_err());
+
+        let mut sent = Vec::new();
+        set_status_message_mode(1, StatusMessageMode::Polled, |req: &Message| {
+            sent.push(req.clone());
+            Ok(req
+                .data()
+                .clone()
+                .with_additional(&[ResponseCode::Nak.into()])
+                .into())
+        })
+        .unwrap_err();
+        assert_eq!(sent[0].data().additional(), [0, 0, EVENT_MESSAGE_FLAG]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,444 @@
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+use std::{fmt, time};
+
+use crate::{
+    AuditCounters, DeviceInfo, Error, EventCode, FailureCode, FailureEvent, Message, MessageRecord,
+    MessageRing, RejectCode, RejectedEvent, Result,
+};
+
+/// Represents the name of the JSON summary in a [SupportBundle] archive.
+pub const SUPPORT_BUNDLE_SUMMARY: &str = "bundle.json";
+/// Represents the name of the raw message captures in a [SupportBundle] archive.
+pub const SUPPORT_BUNDLE_CAPTURES: &str = "captures.txt";
+
+const TAR_BLOCK_LEN: usize = 512;
+const TAR_NAME_LEN: usize = 100;
+
+/// Counts rejected notes by [RejectCode], and device failures by [FailureCode].
+///
+/// Feed every device event to [record_event](Self::record_event); other events are ignored.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RejectStats {
+    rejects: Vec<(RejectCode, u64)>,
+    failures: Vec<(FailureCode, u64)>,
+}
+
+impl RejectStats {
+    /// Creates a new, empty [RejectStats].
+    pub const fn new() -> Self {
+        Self {
+            rejects: Vec::new(),
+            failures: Vec::new(),
+        }
+    }
+
+    /// Records a `Rejected`, `AcceptorRejected`, `Failure`, or `AcceptorFailure` event.
+    ///
+    /// Returns `false` for any other event.
+    pub fn record_event(&mut self, event: &Message) -> bool {
+        match event.data().message_code().event_code() {
+            Ok(EventCode::Rejected | EventCode::AcceptorRejected) => RejectedEvent::try_from(event)
+                .map(|evt| self.record_reject(evt.reject_code()))
+                .is_ok(),
+            Ok(EventCode::Failure | EventCode::AcceptorFailure) => FailureEvent::try_from(event)
+                .map(|evt| self.record_failure(evt.failure_code()))
+                .is_ok(),
+            _ => false,
+        }
+    }
+
+    /// Records a rejected note.
+    pub fn record_reject(&mut self, code: RejectCode) {
+        match self.rejects.iter_mut().find(|(c, _)| c == &code) {
+            Some((_, count)) => *count = count.saturating_add(1),
+            None => {
+                self.rejects.push((code, 1));
+                self.rejects.sort_by_key(|(c, _)| u8::from(c));
+            }
+        }
+    }
+
+    /// Records a device failure.
+    pub fn record_failure(&mut self, code: FailureCode) {
+        match self.failures.iter_mut().find(|(c, _)| c == &code) {
+            Some((_, count)) => *count = count.saturating_add(1),
+            None => {
+                self.failures.push((code, 1));
+                self.failures.sort_by_key(|(c, _)| u8::from(*c));
+            }
+        }
+    }
+
+    /// Gets the number of notes rejected with the [RejectCode].
+    pub fn reject_count(&self, code: RejectCode) -> u64 {
+        self.rejects
+            .iter()
+            .find(|(c, _)| c == &code)
+            .map(|(_, count)| *count)
+            .unwrap_or(0)
+    }
+
+    /// Gets the number of failures with the [FailureCode].
+    pub fn failure_count(&self, code: FailureCode) -> u64 {
+        self.failures
+            .iter()
+            .find(|(c, _)| c == &code)
+            .map(|(_, count)| *count)
+            .unwrap_or(0)
+    }
+
+    /// Gets the reject counts, ordered by [RejectCode] value.
+    pub fn rejects(&self) -> &[(RejectCode, u64)] {
+        self.rejects.as_ref()
+    }
+
+    /// Gets the failure counts, ordered by [FailureCode] value.
+    pub fn failures(&self) -> &[(FailureCode, u64)] {
+        self.failures.as_ref()
+    }
+
+    /// Gets whether no rejects or failures were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.rejects.is_empty() && self.failures.is_empty()
+    }
+
+    /// Clears all counts.
+    pub fn reset(&mut self) {
+        self.rejects.clear();
+        self.failures.clear();
+    }
+}
+
+impl fmt::Display for RejectStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#"{{"rejects": {{"#)?;
+        for (i, (code, count)) in self.rejects.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{code}: {count}")?;
+        }
+        write!(f, r#"}}, "failures": {{"#)?;
+        for (i, (code, count)) in self.failures.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{code}: {count}")?;
+        }
+        write!(f, "}}}}")
+    }
+}
+
+/// Packages device diagnostics into a single archive, for attaching to vendor support
+/// escalations.
+///
+/// The archive is an uncompressed `tar` file, containing:
+///
+/// File                      | Contents
+/// --------------------------|-------------------------------------------------------------------
+/// [SUPPORT_BUNDLE_SUMMARY]  | JSON summary: device identity, [RejectStats], counters, config
+/// [SUPPORT_BUNDLE_CAPTURES] | raw message captures, one per line: timestamp, direction, hex bytes
+///
+/// Configuration values are written with their [Display](fmt::Display) implementation, which is
+/// JSON for the crate types, e.g. [PauseSettings](crate::PauseSettings).
+#[derive(Clone, Debug, PartialEq)]
+pub struct SupportBundle {
+    created: time::SystemTime,
+    device_info: Option<DeviceInfo>,
+    reject_stats: RejectStats,
+    counters: Option<AuditCounters>,
+    config: Vec<(String, String)>,
+    captures: Vec<MessageRecord>,
+}
+
+impl SupportBundle {
+    /// Creates a new, empty [SupportBundle], timestamped with the current system time.
+    pub fn new() -> Self {
+        Self {
+            created: time::SystemTime::now(),
+            device_info: None,
+            reject_stats: RejectStats::new(),
+            counters: None,
+            config: Vec::new(),
+            captures: Vec::new(),
+        }
+    }
+
+    /// Gets the creation time of the [SupportBundle].
+    pub const fn created(&self) -> time::SystemTime {
+        self.created
+    }
+
+    /// Builder function that sets the creation time of the [SupportBundle].
+    pub fn with_created(mut self, created: time::SystemTime) -> Self {
+        self.created = created;
+        self
+    }
+
+    /// Gets the optional [DeviceInfo].
+    pub const fn device_info(&self) -> Option<&DeviceInfo> {
+        self.device_info.as_ref()
+    }
+
+    /// Sets the [DeviceInfo], i.e. serial number, model name, and firmware version.
+    pub fn set_device_info(&mut self, info: DeviceInfo) {
+        self.device_info = Some(info);
+    }
+
+    /// Builder function that sets the [DeviceInfo].
+    pub fn with_device_info(mut self, info: DeviceInfo) -> Self {
+        self.set_device_info(info);
+        self
+    }
+
+    /// Gets a reference to the [RejectStats].
+    pub const fn reject_stats(&self) -> &RejectStats {
+        &self.reject_stats
+    }
+
+    /// Builder function that sets the [RejectStats].
+    pub fn with_reject_stats(mut self, stats: RejectStats) -> Self {
+        self.reject_stats = stats;
+        self
+    }
+
+    /// Gets the optional [AuditCounters].
+    pub const fn counters(&self) -> Option<&AuditCounters> {
+        self.counters.as_ref()
+    }
+
+    /// Builder function that sets the [AuditCounters].
+    pub fn with_counters(mut self, counters: AuditCounters) -> Self {
+        self.counters = Some(counters);
+        self
+    }
+
+    /// Gets the configuration snapshot, as key-value pairs.
+    pub fn config(&self) -> &[(String, String)] {
+        self.config.as_ref()
+    }
+
+    /// Builder function that adds a configuration value to the snapshot.
+    ///
+    /// Replaces any previous value with the same key.
+    pub fn with_config<V: fmt::Display>(mut self, key: &str, value: V) -> Self {
+        let value = value.to_string();
+        match self.config.iter_mut().find(|(k, _)| k == key) {
+            Some((_, v)) => *v = value,
+            None => self.config.push((key.into(), value)),
+        }
+        self
+    }
+
+    /// Gets the captured [MessageRecord]s.
+    pub fn captures(&self) -> &[MessageRecord] {
+        self.captures.as_ref()
+    }
+
+    /// Builder function that copies the recent messages from the [MessageRing].
+    pub fn with_captures(mut self, ring: &MessageRing) -> Self {
+        self.captures = ring.records();
+        self
+    }
+
+    /// Writes the [SupportBundle] archive.
+    pub fn write_to<W: Write>(&self, out: &mut W) -> Result<()> {
+        let mtime = unix_secs(self.created);
+        let summary = self.to_string();
+
+        write_tar_entry(out, SUPPORT_BUNDLE_SUMMARY, summary.as_bytes(), mtime)
+            .and_then(|_| {
+                write_tar_entry(out, SUPPORT_BUNDLE_CAPTURES, &self.raw_captures(), mtime)
+            })
+            .and_then(|_| out.write_all(&[0u8; TAR_BLOCK_LEN * 2]))
+            .and_then(|_| out.flush())
+            .map_err(|err| Error::SupportBundle(format!("error writing archive: {err}")))
+    }
+
+    /// Writes the [SupportBundle] archive to the file at `path`, replacing any existing file.
+    pub fn export<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let mut file = File::create(path).map_err(|err| {
+            Error::SupportBundle(format!("unable to create {}: {err}", path.display()))
+        })?;
+
+        self.write_to(&mut file)?;
+        log::info!("support bundle exported to {}", path.display());
+
+        Ok(())
+    }
+
+    fn raw_captures(&self) -> Vec<u8> {
+        let mut out = String::new();
+
+        for record in self.captures.iter() {
+            let timestamp_ms = record
+                .timestamp()
+                .duration_since(time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis();
+            let bytes = Vec::<u8>::from(record.message())
+                .iter()
+                .map(|b| format!("{b:02x}"))
+                .collect::<Vec<String>>()
+                .join(" ");
+
+            out.push_str(&format!(
+                "{timestamp_ms} {} {bytes}\n",
+                <&str>::from(record.direction())
+            ));
+        }
+
+        out.into_bytes()
+    }
+}
+
+impl Default for SupportBundle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for SupportBundle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""created": {}, "#, unix_secs(self.created))?;
+        match self.device_info.as_ref() {
+            Some(info) => write!(f, r#""device_info": {info}, "#)?,
+            None => write!(f, r#""device_info": null, "#)?,
+        }
+        write!(f, r#""reject_stats": {}, "#, self.reject_stats)?;
+        match self.counters.as_ref() {
+            Some(counters) => write!(f, r#""counters": {counters}, "#)?,
+            None => write!(f, r#""counters": null, "#)?,
+        }
+        write!(f, r#""config": {{"#)?;
+        for (i, (key, value)) in self.config.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, r#""{key}": {value}"#)?;
+        }
+        write!(f, r#"}}, "captures": {}"#, self.captures.len())?;
+        write!(f, "}}")
+    }
+}
+
+fn unix_secs(time: time::SystemTime) -> u64 {
+    time.duration_since(time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+// writes a regular file entry in the POSIX `ustar` format
+fn write_tar_entry<W: Write>(out: &mut W, name: &str, data: &[u8], mtime: u64) -> io::Result<()> {
+    let mut header = [0u8; TAR_BLOCK_LEN];
+    let name_len = name.len().min(TAR_NAME_LEN);
+
+    header[..name_len].copy_from_slice(&name.as_bytes()[..name_len]);
+    header[100..107].copy_from_slice(b"0000644");
+    header[108..115].copy_from_slice(b"0000000");
+    header[116..123].copy_from_slice(b"0000000");
+    header[124..135].copy_from_slice(format!("{:011o}", data.len()).as_bytes());
+    header[136..147].copy_from_slice(format!("{mtime:011o}").as_bytes());
+    header[148..156].fill(b' ');
+    header[156] = b'0';
+    header[257..263].copy_from_slice(b"ustar\0");
+    header[263..265].copy_from_slice(b"00");
+
+    let checksum: u32 = header.iter().map(|&b| b as u32).sum();
+    header[148..155].copy_from_slice(format!("{checksum:06o}\0").as_bytes());
+
+    let pad = (TAR_BLOCK_LEN - data.len() % TAR_BLOCK_LEN) % TAR_BLOCK_LEN;
+
+    out.write_all(&header)?;
+    out.write_all(data)?;
+    out.write_all(&[0u8; TAR_BLOCK_LEN][..pad])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventType, MessageDirection, PauseSettings, StatusRequest};
+
+    #[test]
+    fn test_support_bundle() -> Result<()> {
+        let mut stats = RejectStats::new();
+        for code in [
+            RejectCode::PhotoLevel,
+            RejectCode::Inhibited,
+            RejectCode::PhotoLevel,
+        ] {
+            let event = RejectedEvent::create(EventType::new(), EventCode::Rejected, code);
+            assert!(stats.record_event(&Message::from(&event)));
+        }
+        let failure = FailureEvent::new().with_failure_code(FailureCode::StackMotor);
+        assert!(stats.record_event(&Message::from(&failure)));
+        assert!(!stats.record_event(&StatusRequest::new().into()));
+
+        assert_eq!(stats.reject_count(RejectCode::PhotoLevel), 2);
+        assert_eq!(stats.reject_count(RejectCode::Return), 0);
+        assert_eq!(stats.failure_count(FailureCode::StackMotor), 1);
+        assert_eq!(
+            stats.to_string(),
+            r#"{"rejects": {"Inhibited": 1, "PhotoLevel": 2}, "failures": {"stack motor related error": 1}}"#
+        );
+
+        let ring = MessageRing::new();
+        ring.record(MessageRecord::create(
+            time::UNIX_EPOCH + time::Duration::from_millis(1500),
+            MessageDirection::Sent,
+            StatusRequest::new().into(),
+        ));
+
+        let bundle = SupportBundle::new()
+            .with_created(time::UNIX_EPOCH + time::Duration::from_secs(8))
+            .with_reject_stats(stats.clone())
+            .with_config("pause", PauseSettings::new())
+            .with_captures(&ring);
+
+        let summary = bundle.to_string();
+        assert_eq!(
+            summary,
+            format!(
+                r#"{{"created": 8, "device_info": null, "reject_stats": {stats}, "counters": null, "config": {{"pause": {}}}, "captures": 1}}"#,
+                PauseSettings::new()
+            )
+        );
+        assert_eq!(
+            bundle.raw_captures(),
+            b"1500 sent 12 08 00 10 00 10 10 00\n"
+        );
+
+        let mut archive = Vec::new();
+        bundle.write_to(&mut archive)?;
+
+        assert!(archive.len().is_multiple_of(TAR_BLOCK_LEN));
+        assert_eq!(
+            &archive[..SUPPORT_BUNDLE_SUMMARY.len()],
+            SUPPORT_BUNDLE_SUMMARY.as_bytes()
+        );
+        assert_eq!(&archive[257..262], b"ustar");
+        assert_eq!(
+            &archive[TAR_BLOCK_LEN..TAR_BLOCK_LEN + summary.len()],
+            summary.as_bytes()
+        );
+
+        let checksum: u32 = archive[..TAR_BLOCK_LEN]
+            .iter()
+            .enumerate()
+            .map(|(i, &b)| {
+                if (148..156).contains(&i) {
+                    b' ' as u32
+                } else {
+                    b as u32
+                }
+            })
+            .sum();
+        assert_eq!(&archive[148..155], format!("{checksum:06o}\0").as_bytes());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,244 @@
+//! Helpers for testing typed messages.
+//!
+//! Typed requests and events only parse from a narrow set of [MessageType] and [MessageCode]
+//! combinations. The helpers here build every other combination, so new typed messages get the
+//! same negative coverage as the built-in types with one line:
+//!
+//! ```
+//! use jcm::{MessageCode, MessageType, RequestCode, RequestType, UidRequest};
+//!
+//! jcm::testing::assert_invalid_combinations::<UidRequest>(
+//!     &[
+//!         MessageType::Request(RequestType::Status),
+//!         MessageType::Request(RequestType::SetFeature),
+//!     ],
+//!     &[MessageCode::Request(RequestCode::Uid)],
+//! );
+//! ```
+
+use std::fmt;
+
+use crate::{
+    EventCode, EventType, Message, MessageCode, MessageData, MessageType, RequestCode, RequestType,
+};
+
+/// Represents every [RequestType] variant, including `Reserved`.
+pub const REQUEST_TYPES: &[RequestType] = &[
+    RequestType::Operation,
+    RequestType::Status,
+    RequestType::SetFeature,
+    RequestType::Reserved,
+];
+
+/// Represents every [RequestCode] variant, including `Reserved`.
+pub const REQUEST_CODES: &[RequestCode] = &[
+    RequestCode::Uid,
+    RequestCode::ProgramSignature,
+    RequestCode::Version,
+    RequestCode::SerialNumber,
+    RequestCode::ModelName,
+    RequestCode::Status,
+    RequestCode::Reset,
+    RequestCode::Inhibit,
+    RequestCode::Collect,
+    RequestCode::Key,
+    RequestCode::EventResendInterval,
+    RequestCode::Idle,
+    RequestCode::Stack,
+    RequestCode::Reject,
+    RequestCode::Hold,
+    RequestCode::AcceptorCollect,
+    RequestCode::DenominationDisable,
+    RequestCode::DirectionDisable,
+    RequestCode::CurrencyAssign,
+    RequestCode::CashBoxSize,
+    RequestCode::NearFull,
+    RequestCode::BarCode,
+    RequestCode::Insert,
+    RequestCode::ConditionalVend,
+    RequestCode::Pause,
+    RequestCode::NoteDataInfo,
+    RequestCode::RecyclerCollect,
+    RequestCode::RecyclerDispense,
+    RequestCode::Reserved,
+];
+
+/// Represents every [EventCode] variant, including `Reserved`.
+pub const EVENT_CODES: &[EventCode] = &[
+    EventCode::PowerUp,
+    EventCode::PowerUpAcceptor,
+    EventCode::PowerUpStacker,
+    EventCode::Inhibit,
+    EventCode::ProgramSignature,
+    EventCode::Rejected,
+    EventCode::Collected,
+    EventCode::Clear,
+    EventCode::OperationError,
+    EventCode::Failure,
+    EventCode::NoteStay,
+    EventCode::PowerUpAcceptorAccepting,
+    EventCode::PowerUpStackerAccepting,
+    EventCode::Idle,
+    EventCode::Escrow,
+    EventCode::VendValid,
+    EventCode::AcceptorRejected,
+    EventCode::Returned,
+    EventCode::AcceptorCollected,
+    EventCode::Insert,
+    EventCode::ConditionalVend,
+    EventCode::Pause,
+    EventCode::Resume,
+    EventCode::AcceptorClear,
+    EventCode::AcceptorOperationError,
+    EventCode::AcceptorFailure,
+    EventCode::AcceptorNoteStay,
+    EventCode::FunctionAbeyance,
+    EventCode::RecyclerDispensing,
+    EventCode::RecyclerDispensed,
+    EventCode::Reserved,
+];
+
+/// Gets every [MessageType]: `Reserved`, all event sequence types, and all request types.
+pub fn all_message_types() -> Vec<MessageType> {
+    [MessageType::Reserved]
+        .into_iter()
+        .chain((0x80..=0x8f).map(|m| MessageType::Event(EventType::from_u8(m))))
+        .chain(REQUEST_TYPES.iter().copied().map(MessageType::Request))
+        .collect()
+}
+
+/// Gets every [MessageCode]: all request codes, and all event codes.
+pub fn all_message_codes() -> Vec<MessageCode> {
+    REQUEST_CODES
+        .iter()
+        .copied()
+        .map(MessageCode::Request)
+        .chain(EVENT_CODES.iter().copied().map(MessageCode::Event))
+        .collect()
+}
+
+/// Builds every [MessageData] combination that is invalid for a typed message.
+///
+/// `valid_types` and `valid_codes` list the combinations the typed message accepts. The matrix
+/// contains:
+///
+/// - every invalid type with every invalid code
+/// - every invalid type with each valid code
+/// - each valid type with every invalid code
+pub fn invalid_combination_matrix(
+    valid_types: &[MessageType],
+    valid_codes: &[MessageCode],
+) -> Vec<MessageData> {
+    let invalid_types: Vec<MessageType> = all_message_types()
+        .into_iter()
+        .filter(|t| !valid_types.contains(t))
+        .collect();
+    let invalid_codes: Vec<MessageCode> = all_message_codes()
+        .into_iter()
+        .filter(|c| !valid_codes.contains(c))
+        .collect();
+
+    let data = |msg_type: MessageType, msg_code: MessageCode| {
+        MessageData::new()
+            .with_message_type(msg_type)
+            .with_message_code(msg_code)
+    };
+
+    let mut matrix = Vec::new();
+
+    for &msg_type in invalid_types.iter() {
+        for &msg_code in invalid_codes.iter() {
+            matrix.push(data(msg_type, msg_code));
+        }
+        for &msg_code in valid_codes.iter() {
+            matrix.push(data(msg_type, msg_code));
+        }
+    }
+
+    for &msg_type in valid_types.iter() {
+        for &msg_code in invalid_codes.iter() {
+            matrix.push(data(msg_type, msg_code));
+        }
+    }
+
+    matrix
+}
+
+/// Asserts that the typed message fails to parse from every entry of the
+/// [invalid_combination_matrix], both as [MessageData] and as a full [Message].
+///
+/// # Panics
+///
+/// Panics on the first combination that parses successfully.
+pub fn assert_invalid_combinations<T>(valid_types: &[MessageType], valid_codes: &[MessageCode])
+where
+    T: for<'a> TryFrom<&'a MessageData> + for<'a> TryFrom<&'a Message>,
+    for<'a> <T as TryFrom<&'a MessageData>>::Error: fmt::Debug,
+    for<'a> <T as TryFrom<&'a Message>>::Error: fmt::Debug,
+{
+    for data in invalid_combination_matrix(valid_types, valid_codes) {
+        assert!(
+            T::try_from(&data).is_err(),
+            "invalid message data parsed successfully: {data}"
+        );
+
+        let msg = Message::new().with_data(data);
+        assert!(
+            T::try_from(&msg).is_err(),
+            "invalid message parsed successfully: {msg}"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InhibitEvent, UidRequest};
+
+    #[test]
+    fn test_invalid_combination_matrix() {
+        let types = all_message_types();
+        let codes = all_message_codes();
+
+        assert_eq!(types.len(), 1 + 16 + REQUEST_TYPES.len());
+        assert_eq!(codes.len(), REQUEST_CODES.len() + EVENT_CODES.len());
+
+        let valid_types = [MessageType::Request(RequestType::Status)];
+        let valid_codes = [MessageCode::Request(RequestCode::Uid)];
+        let matrix = invalid_combination_matrix(&valid_types, &valid_codes);
+
+        let (inval_types, inval_codes) = (types.len() - 1, codes.len() - 1);
+        assert_eq!(matrix.len(), inval_types * (inval_codes + 1) + inval_codes);
+        assert!(!matrix
+            .iter()
+            .any(|d| d.message_type() == valid_types[0] && d.message_code() == valid_codes[0]));
+    }
+
+    #[test]
+    fn test_assert_invalid_combinations() {
+        assert_invalid_combinations::<UidRequest>(
+            &[
+                MessageType::Request(RequestType::Status),
+                MessageType::Request(RequestType::SetFeature),
+            ],
+            &[MessageCode::Request(RequestCode::Uid)],
+        );
+
+        assert_invalid_combinations::<InhibitEvent>(
+            &(0x80..=0x8f)
+                .map(|m| MessageType::Event(EventType::from_u8(m)))
+                .collect::<Vec<_>>(),
+            &[MessageCode::Event(EventCode::Inhibit)],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "parsed successfully")]
+    fn test_assert_invalid_combinations_missing_valid() {
+        // omitting valid event sequence types exposes combinations that parse successfully
+        assert_invalid_combinations::<InhibitEvent>(
+            &[MessageType::Event(EventType::Sequence0)],
+            &[MessageCode::Event(EventCode::Inhibit)],
+        );
+    }
+}
@@ -1,4 +1,5 @@
-use std::{fmt, mem};
+use alloc::{string::String, vec::Vec};
+use core::{fmt, mem};
 
 use crate::{Error, Result};
 
@@ -7,6 +8,7 @@ pub const MAX_TICKET_LEN: usize = u8::MAX as usize;
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TicketMetadata {
     ticket_len: u8,
 }
@@ -54,6 +56,7 @@ impl Default for TicketMetadata {
 /// Represents a `ticket` handled by the JCM device.
 #[repr(C)]
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Ticket {
     code: String,
 }
@@ -98,12 +101,12 @@ impl Ticket {
 
     /// Gets the [TicketMetadata] for the [Ticket].
     pub fn metadata(&self) -> TicketMetadata {
-        TicketMetadata::create(self.code.as_bytes().len())
+        TicketMetadata::create(self.code.len())
     }
 
     /// Gets the length of the metadata + ASCII code of the [Ticket].
     pub fn len(&self) -> usize {
-        TicketMetadata::len() + self.code.as_bytes().len()
+        TicketMetadata::len() + self.code.len()
     }
 
     /// Gets whether the ASCII code of the [Ticket] is empty.
@@ -115,7 +118,7 @@ impl Ticket {
     ///
     /// The buffer should only contain the ASCII-encode portion of the ticket.
     pub fn from_bytes(buf: &[u8]) -> Result<Self> {
-        Self::new().with_code(std::str::from_utf8(buf).map_err(|_| Error::InvalidUtf8String)?)
+        Self::new().with_code(core::str::from_utf8(buf).map_err(|_| Error::InvalidUtf8String)?)
     }
 
     /// Writes the [Ticket] to a byte buffer.
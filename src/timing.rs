@@ -0,0 +1,184 @@
+use std::{fmt, time};
+
+use crate::{Clock, EventCode, Message, RequestCode};
+
+/// Represents the default limit for the host to respond to a device event.
+pub const EVENT_RESPONSE_LIMIT: time::Duration = time::Duration::from_millis(200);
+
+/// Represents host behavior that violates protocol timing or ordering rules.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TimingViolation {
+    /// The host responded to an event after the response limit.
+    LateEventResponse((EventCode, time::Duration, time::Duration)),
+    /// The host sent a request while an event was still waiting for a response.
+    RequestBeforeEventResponse((RequestCode, EventCode)),
+}
+
+impl fmt::Display for TimingViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LateEventResponse((code, elapsed, limit)) => write!(
+                f,
+                r#"{{"late_event_response": {{"event_code": {code}, "elapsed_ms": {}, "limit_ms": {}}}}}"#,
+                elapsed.as_millis(),
+                limit.as_millis()
+            ),
+            Self::RequestBeforeEventResponse((req, evt)) => write!(
+                f,
+                r#"{{"request_before_event_response": {{"request_code": {req}, "event_code": {evt}}}}}"#
+            ),
+        }
+    }
+}
+
+/// Checks host traffic for protocol timing and ordering violations.
+///
+/// Violations are logged as warnings. In `strict` mode, they also trigger a panic in debug
+/// builds, so compliance bugs surface during development instead of at device certification.
+///
+/// The `usb` polling helpers run a [TimingMonitor] automatically in debug builds.
+#[derive(Clone, Debug)]
+pub struct TimingMonitor<C: Clock> {
+    clock: C,
+    event_response_limit: time::Duration,
+    pending: Option<(EventCode, time::Duration)>,
+    strict: bool,
+    violations: usize,
+}
+
+impl<C: Clock> TimingMonitor<C> {
+    /// Creates a new [TimingMonitor] using the provided [Clock].
+    pub fn new(clock: C) -> Self {
+        Self {
+            clock,
+            event_response_limit: EVENT_RESPONSE_LIMIT,
+            pending: None,
+            strict: false,
+            violations: 0,
+        }
+    }
+
+    /// Gets the event response limit.
+    pub const fn event_response_limit(&self) -> time::Duration {
+        self.event_response_limit
+    }
+
+    /// Builder function that sets the event response limit.
+    pub fn with_event_response_limit(mut self, limit: time::Duration) -> Self {
+        self.event_response_limit = limit;
+        self
+    }
+
+    /// Gets whether violations panic in debug builds.
+    pub const fn strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Builder function that sets whether violations panic in debug builds.
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Gets the number of violations detected.
+    pub const fn violations(&self) -> usize {
+        self.violations
+    }
+
+    /// Records a device event that requires a host response.
+    pub fn on_event(&mut self, event: &Message) {
+        if let Ok(code) = event.data().message_code().event_code() {
+            self.pending = Some((code, self.clock.now()));
+        }
+    }
+
+    /// Records the host response to the pending event.
+    pub fn on_event_response(&mut self, _response: &Message) -> Option<TimingViolation> {
+        let (code, start) = self.pending.take()?;
+        let elapsed = self.clock.elapsed(start);
+
+        if elapsed > self.event_response_limit {
+            self.report(TimingViolation::LateEventResponse((
+                code,
+                elapsed,
+                self.event_response_limit,
+            )))
+        } else {
+            None
+        }
+    }
+
+    /// Records a host request.
+    pub fn on_request(&mut self, request: &Message) -> Option<TimingViolation> {
+        match (self.pending, request.data().message_code().request_code()) {
+            (Some((evt, _)), Ok(req)) => {
+                self.report(TimingViolation::RequestBeforeEventResponse((req, evt)))
+            }
+            _ => None,
+        }
+    }
+
+    fn report(&mut self, violation: TimingViolation) -> Option<TimingViolation> {
+        self.violations = self.violations.saturating_add(1);
+
+        log::warn!("protocol timing violation: {violation}");
+        debug_assert!(!self.strict, "protocol timing violation: {violation}");
+
+        Some(violation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventType, MessageCode, MessageData, MessageType, SimulatedClock, StatusRequest};
+
+    fn idle_event() -> Message {
+        Message::new().with_data(
+            MessageData::new()
+                .with_message_type(MessageType::Event(EventType::Sequence0))
+                .with_message_code(MessageCode::Event(EventCode::Idle)),
+        )
+    }
+
+    #[test]
+    fn test_timing_monitor() {
+        let clock = SimulatedClock::new();
+        let mut monitor = TimingMonitor::new(&clock);
+        let event = idle_event();
+
+        monitor.on_event(&event);
+        clock.advance(EVENT_RESPONSE_LIMIT);
+        assert_eq!(monitor.on_event_response(&event), None);
+
+        monitor.on_event(&event);
+        assert_eq!(
+            monitor.on_request(&StatusRequest::new().into()),
+            Some(TimingViolation::RequestBeforeEventResponse((
+                RequestCode::Status,
+                EventCode::Idle
+            )))
+        );
+
+        clock.advance(EVENT_RESPONSE_LIMIT + time::Duration::from_millis(1));
+        assert!(matches!(
+            monitor.on_event_response(&event),
+            Some(TimingViolation::LateEventResponse((EventCode::Idle, _, _)))
+        ));
+
+        assert_eq!(monitor.on_request(&StatusRequest::new().into()), None);
+        assert_eq!(monitor.violations(), 2);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "protocol timing violation")]
+    fn test_timing_monitor_strict() {
+        let clock = SimulatedClock::new();
+        let mut monitor = TimingMonitor::new(&clock).with_strict(true);
+
+        monitor.on_event(&idle_event());
+        clock.advance(time::Duration::from_secs(1));
+        monitor.on_event_response(&idle_event());
+    }
+}
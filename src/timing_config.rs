@@ -0,0 +1,208 @@
+use std::{fmt, time};
+
+use crate::{
+    Clock, Error, KeepAlive, Result, SignatureAudit, TimingMonitor, EVENT_RESPONSE_LIMIT,
+    KEEP_ALIVE_INTERVAL, SIGNATURE_AUDIT_INTERVAL,
+};
+
+const KEY_KEEP_ALIVE_INTERVAL: &str = "keep_alive_interval";
+const KEY_EVENT_RESPONSE_LIMIT: &str = "event_response_limit";
+const KEY_SIGNATURE_AUDIT_INTERVAL: &str = "signature_audit_interval";
+
+/// Parses a human-friendly duration string, e.g. `500ms`, `3s`, or `1h 30m`.
+///
+/// Units are required: bare numbers are rejected, so values are never misread as seconds or
+/// milliseconds.
+pub fn parse_duration(val: &str) -> Result<time::Duration> {
+    humantime::parse_duration(val.trim())
+        .map_err(|err| Error::InvalidDuration(format!("{val}: {err}")))
+}
+
+/// Formats a duration as a human-friendly string, e.g. `500ms` or `1h 30m`.
+///
+/// The output is accepted by [parse_duration].
+pub fn format_duration(val: time::Duration) -> String {
+    humantime::format_duration(val).to_string()
+}
+
+/// Represents the tunable timing configuration of the host.
+///
+/// The configuration is read from `key=value` lines, with human-friendly duration values:
+///
+/// ```
+/// # fn main() -> jcm::Result<()> {
+/// let config = jcm::TimingConfig::from_records("keep_alive_interval=3s\nevent_response_limit=150ms\n")?;
+///
+/// assert_eq!(config.keep_alive_interval(), std::time::Duration::from_secs(3));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TimingConfig {
+    keep_alive_interval: time::Duration,
+    event_response_limit: time::Duration,
+    signature_audit_interval: time::Duration,
+}
+
+impl TimingConfig {
+    /// Creates a new [TimingConfig] with the default timings.
+    pub const fn new() -> Self {
+        Self {
+            keep_alive_interval: KEEP_ALIVE_INTERVAL,
+            event_response_limit: EVENT_RESPONSE_LIMIT,
+            signature_audit_interval: SIGNATURE_AUDIT_INTERVAL,
+        }
+    }
+
+    /// Gets the [KeepAlive] interval.
+    pub const fn keep_alive_interval(&self) -> time::Duration {
+        self.keep_alive_interval
+    }
+
+    /// Builder function that sets the [KeepAlive] interval.
+    pub fn with_keep_alive_interval(mut self, val: time::Duration) -> Self {
+        self.keep_alive_interval = val;
+        self
+    }
+
+    /// Gets the [TimingMonitor] event response limit.
+    pub const fn event_response_limit(&self) -> time::Duration {
+        self.event_response_limit
+    }
+
+    /// Builder function that sets the [TimingMonitor] event response limit.
+    pub fn with_event_response_limit(mut self, val: time::Duration) -> Self {
+        self.event_response_limit = val;
+        self
+    }
+
+    /// Gets the [SignatureAudit] interval.
+    pub const fn signature_audit_interval(&self) -> time::Duration {
+        self.signature_audit_interval
+    }
+
+    /// Builder function that sets the [SignatureAudit] interval.
+    pub fn with_signature_audit_interval(mut self, val: time::Duration) -> Self {
+        self.signature_audit_interval = val;
+        self
+    }
+
+    /// Applies the configured interval to a [KeepAlive].
+    pub fn keep_alive<C: Clock>(&self, keep_alive: KeepAlive<C>) -> KeepAlive<C> {
+        keep_alive.with_interval(self.keep_alive_interval)
+    }
+
+    /// Applies the configured limit to a [TimingMonitor].
+    pub fn timing_monitor<C: Clock>(&self, monitor: TimingMonitor<C>) -> TimingMonitor<C> {
+        monitor.with_event_response_limit(self.event_response_limit)
+    }
+
+    /// Applies the configured interval to a [SignatureAudit].
+    pub fn signature_audit<C: Clock>(&self, audit: SignatureAudit<C>) -> SignatureAudit<C> {
+        audit.with_interval(self.signature_audit_interval)
+    }
+
+    /// Serializes the [TimingConfig] into `key=value` lines.
+    pub fn to_records(&self) -> String {
+        [
+            (KEY_KEEP_ALIVE_INTERVAL, self.keep_alive_interval),
+            (KEY_EVENT_RESPONSE_LIMIT, self.event_response_limit),
+            (KEY_SIGNATURE_AUDIT_INTERVAL, self.signature_audit_interval),
+        ]
+        .into_iter()
+        .map(|(key, val)| format!("{key}={}\n", format_duration(val)))
+        .collect()
+    }
+
+    /// Parses `key=value` lines into a [TimingConfig].
+    ///
+    /// Missing keys keep their default timings. Blank lines, `#` comments, and unknown keys are
+    /// ignored.
+    pub fn from_records(records: &str) -> Result<Self> {
+        let mut config = Self::new();
+
+        for (i, record) in records.lines().enumerate() {
+            let record = record.trim();
+            if record.is_empty() || record.starts_with('#') {
+                continue;
+            }
+
+            let (key, val) = record
+                .split_once('=')
+                .ok_or(Error::InvalidDuration(format!(
+                    "invalid record, line: {}",
+                    i + 1
+                )))?;
+            let val = parse_duration(val).map_err(|err| {
+                Error::InvalidDuration(format!("invalid value, line: {}, error: {err}", i + 1))
+            })?;
+
+            match key.trim() {
+                KEY_KEEP_ALIVE_INTERVAL => config.keep_alive_interval = val,
+                KEY_EVENT_RESPONSE_LIMIT => config.event_response_limit = val,
+                KEY_SIGNATURE_AUDIT_INTERVAL => config.signature_audit_interval = val,
+                key => log::debug!("ignoring unknown timing key: {key}"),
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+impl Default for TimingConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for TimingConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(
+            f,
+            r#""keep_alive_interval": "{}", "#,
+            format_duration(self.keep_alive_interval)
+        )?;
+        write!(
+            f,
+            r#""event_response_limit": "{}", "#,
+            format_duration(self.event_response_limit)
+        )?;
+        write!(
+            f,
+            r#""signature_audit_interval": "{}""#,
+            format_duration(self.signature_audit_interval)
+        )?;
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timing_config() -> Result<()> {
+        assert_eq!(parse_duration("500ms")?, time::Duration::from_millis(500));
+        assert_eq!(parse_duration(" 3s ")?, time::Duration::from_secs(3));
+        assert!(parse_duration("500").is_err());
+
+        let config = TimingConfig::from_records(
+            "# tuned for slow hubs\nkeep_alive_interval = 2s 500ms\n\nevent_response_limit=150ms\n",
+        )?;
+        assert_eq!(
+            config.keep_alive_interval(),
+            time::Duration::from_millis(2_500)
+        );
+        assert_eq!(
+            config.event_response_limit(),
+            time::Duration::from_millis(150)
+        );
+        assert_eq!(config.signature_audit_interval(), SIGNATURE_AUDIT_INTERVAL);
+
+        assert_eq!(TimingConfig::from_records(&config.to_records())?, config);
+        assert!(TimingConfig::from_records("keep_alive_interval=5000").is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,385 @@
+//! Structured protocol traces.
+//!
+//! A [MessageTracer] receives a [TraceRecord] for every frame exchanged with a device, with the
+//! raw bytes and the parsed [Message]. Attach a tracer to a
+//! [UsbDeviceHandle](crate::usb::UsbDeviceHandle), or wrap any other transport in a
+//! [TracedTransport], and pick one of the built-in sinks:
+//!
+//! - [RingTracer]: keeps the last records in memory
+//! - [JsonlTracer]: writes one JSON record per line, e.g. to a file attached to a support ticket
+
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::{fmt, time};
+
+use crate::{
+    log_redaction, redact, DeviceTransport, Error, Message, MessageDirection, Result, REDACTED,
+};
+
+/// Represents the default number of records kept by a [RingTracer].
+pub const DEFAULT_TRACE_LEN: usize = 1024;
+
+/// Represents a timestamped protocol frame exchanged with a device.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TraceRecord {
+    timestamp: time::SystemTime,
+    direction: MessageDirection,
+    raw: Vec<u8>,
+    message: Option<Message>,
+}
+
+impl TraceRecord {
+    /// Creates a new [TraceRecord] from the raw frame bytes, timestamped with the current system
+    /// time.
+    ///
+    /// Frames that fail to parse are recorded with the raw bytes only.
+    pub fn new(direction: MessageDirection, raw: &[u8]) -> Self {
+        Self::create(
+            time::SystemTime::now(),
+            direction,
+            raw.into(),
+            Message::try_from(raw).ok(),
+        )
+    }
+
+    /// Creates a new [TraceRecord] from a [Message], timestamped with the current system time.
+    pub fn from_message(direction: MessageDirection, message: &Message) -> Self {
+        Self::create(
+            time::SystemTime::now(),
+            direction,
+            message.into(),
+            Some(message.clone()),
+        )
+    }
+
+    /// Creates a new [TraceRecord] from the provided parameters.
+    pub const fn create(
+        timestamp: time::SystemTime,
+        direction: MessageDirection,
+        raw: Vec<u8>,
+        message: Option<Message>,
+    ) -> Self {
+        Self {
+            timestamp,
+            direction,
+            raw,
+            message,
+        }
+    }
+
+    /// Gets the time the frame was recorded.
+    pub const fn timestamp(&self) -> time::SystemTime {
+        self.timestamp
+    }
+
+    /// Gets the [MessageDirection].
+    pub const fn direction(&self) -> MessageDirection {
+        self.direction
+    }
+
+    /// Gets a reference to the raw frame bytes.
+    pub fn raw(&self) -> &[u8] {
+        self.raw.as_ref()
+    }
+
+    /// Gets a reference to the parsed [Message], if the frame is valid.
+    pub const fn message(&self) -> Option<&Message> {
+        self.message.as_ref()
+    }
+}
+
+impl fmt::Display for TraceRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let timestamp_ms = self
+            .timestamp
+            .duration_since(time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+
+        write!(f, "{{")?;
+        write!(f, r#""timestamp_ms": {timestamp_ms}, "#)?;
+        write!(f, r#""direction": {}, "#, self.direction)?;
+        if log_redaction() {
+            write!(f, r#""raw": "{REDACTED}", "#)?;
+        } else {
+            write!(f, r#""raw": ""#)?;
+            for b in self.raw.iter() {
+                write!(f, "{b:02x}")?;
+            }
+            write!(f, r#"", "#)?;
+        }
+        match self.message.as_ref() {
+            Some(message) => write!(f, r#""message": {}"#, redact(message))?,
+            None => write!(f, r#""message": null"#)?,
+        }
+        write!(f, "}}")
+    }
+}
+
+/// Receives a [TraceRecord] for every frame exchanged with a device.
+///
+/// Tracers are called from the I/O path, so implementations should return quickly.
+pub trait MessageTracer: Send + Sync {
+    /// Called for every frame written to, or read from the device.
+    fn trace(&self, record: &TraceRecord);
+}
+
+impl<T: MessageTracer + ?Sized> MessageTracer for &T {
+    fn trace(&self, record: &TraceRecord) {
+        (**self).trace(record)
+    }
+}
+
+impl<T: MessageTracer + ?Sized> MessageTracer for Arc<T> {
+    fn trace(&self, record: &TraceRecord) {
+        (**self).trace(record)
+    }
+}
+
+/// Keeps the last [TraceRecord]s in memory.
+///
+/// Clones share the same buffer. Once full, the oldest record is dropped for each new one.
+#[derive(Clone, Debug)]
+pub struct RingTracer {
+    records: Arc<Mutex<VecDeque<TraceRecord>>>,
+    capacity: usize,
+}
+
+impl RingTracer {
+    /// Creates a new [RingTracer] holding up to [DEFAULT_TRACE_LEN] records.
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_TRACE_LEN)
+    }
+
+    /// Creates a new [RingTracer] holding up to `capacity` records.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+
+        Self {
+            records: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    /// Gets the maximum number of records kept by the [RingTracer].
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Gets the number of records currently kept by the [RingTracer].
+    pub fn len(&self) -> usize {
+        self.records
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .len()
+    }
+
+    /// Gets whether the [RingTracer] is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets a copy of the recorded [TraceRecord]s, oldest first.
+    pub fn records(&self) -> Vec<TraceRecord> {
+        self.records
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Removes all recorded [TraceRecord]s.
+    pub fn clear(&self) {
+        self.records
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .clear();
+    }
+
+    /// Writes the recorded [TraceRecord]s, one JSON record per line, oldest first.
+    pub fn write_to<W: Write>(&self, out: &mut W) -> Result<()> {
+        for record in self.records() {
+            writeln!(out, "{record}")
+                .map_err(|err| Error::Trace(format!("error writing record: {err}")))?;
+        }
+
+        out.flush()
+            .map_err(|err| Error::Trace(format!("error flushing records: {err}")))
+    }
+}
+
+impl Default for RingTracer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MessageTracer for RingTracer {
+    fn trace(&self, record: &TraceRecord) {
+        let mut records = self.records.lock().unwrap_or_else(|err| err.into_inner());
+
+        if records.len() >= self.capacity {
+            records.pop_front();
+        }
+        records.push_back(record.clone());
+    }
+}
+
+/// Writes every [TraceRecord] as a JSON line.
+///
+/// Write errors are logged, and do not interrupt the device I/O.
+#[derive(Debug)]
+pub struct JsonlTracer<W: Write + Send> {
+    out: Mutex<W>,
+}
+
+impl<W: Write + Send> JsonlTracer<W> {
+    /// Creates a new [JsonlTracer] writing to `out`.
+    pub const fn new(out: W) -> Self {
+        Self {
+            out: Mutex::new(out),
+        }
+    }
+
+    /// Converts the [JsonlTracer] into the inner writer.
+    pub fn into_inner(self) -> W {
+        self.out.into_inner().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl JsonlTracer<File> {
+    /// Creates a new [JsonlTracer] appending to the file at `path`, creating it if needed.
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map(Self::new)
+            .map_err(|err| Error::Trace(format!("unable to open {}: {err}", path.display())))
+    }
+}
+
+impl<W: Write + Send> MessageTracer for JsonlTracer<W> {
+    fn trace(&self, record: &TraceRecord) {
+        let mut out = self.out.lock().unwrap_or_else(|err| err.into_inner());
+
+        if let Err(err) = writeln!(out, "{record}").and_then(|_| out.flush()) {
+            log::warn!("error writing trace record: {err}");
+        }
+    }
+}
+
+/// Wraps a [DeviceTransport], passing every written and read [Message] to a [MessageTracer].
+///
+/// Frames that fail to parse are not traced, since the wrapped transport only returns the parse
+/// error; trace at the transport layer, like [UsbDeviceHandle](crate::usb::UsbDeviceHandle), to
+/// capture them.
+pub struct TracedTransport<T: DeviceTransport> {
+    transport: T,
+    tracer: Arc<dyn MessageTracer>,
+}
+
+impl<T: DeviceTransport> TracedTransport<T> {
+    /// Creates a new [TracedTransport] from the provided parameters.
+    pub fn new(transport: T, tracer: Arc<dyn MessageTracer>) -> Self {
+        Self { transport, tracer }
+    }
+
+    /// Gets a reference to the wrapped [DeviceTransport].
+    pub const fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Converts the [TracedTransport] into the wrapped [DeviceTransport].
+    pub fn into_inner(self) -> T {
+        self.transport
+    }
+}
+
+impl<T: DeviceTransport> DeviceTransport for TracedTransport<T> {
+    fn write_message(&self, message: &Message) -> Result<()> {
+        self.transport.write_message(message).inspect(|_| {
+            self.tracer
+                .trace(&TraceRecord::from_message(MessageDirection::Sent, message))
+        })
+    }
+
+    fn read_message(&self) -> Result<Message> {
+        self.transport.read_message().inspect(|message| {
+            self.tracer.trace(&TraceRecord::from_message(
+                MessageDirection::Received,
+                message,
+            ))
+        })
+    }
+
+    fn write_event_response(&self, message: &Message) -> Result<()> {
+        self.transport.write_event_response(message).inspect(|_| {
+            self.tracer
+                .trace(&TraceRecord::from_message(MessageDirection::Sent, message))
+        })
+    }
+
+    fn timeout(&self) -> time::Duration {
+        self.transport.timeout()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockDevice;
+    use crate::{RequestCode, StatusRequest};
+
+    #[test]
+    fn test_traced_transport() -> Result<()> {
+        let ring = RingTracer::with_capacity(3);
+        let transport = TracedTransport::new(MockDevice::new(), Arc::new(ring.clone()));
+
+        let req: Message = StatusRequest::new().into();
+        for _ in 0..2 {
+            transport.write_message(&req)?;
+            transport.read_message()?;
+        }
+
+        let records = ring.records();
+        assert_eq!(records.len(), 3);
+        assert_eq!(
+            records
+                .iter()
+                .map(TraceRecord::direction)
+                .collect::<Vec<MessageDirection>>(),
+            [
+                MessageDirection::Received,
+                MessageDirection::Sent,
+                MessageDirection::Received
+            ]
+        );
+        assert_eq!(records[1].raw(), Vec::<u8>::from(&req).as_slice());
+        assert_eq!(
+            records[2]
+                .message()
+                .map(|m| m.data().message_code().request_code()),
+            Some(Ok(RequestCode::Status))
+        );
+
+        let invalid = TraceRecord::new(MessageDirection::Received, &[0x12, 0x01]);
+        assert_eq!(invalid.message(), None);
+
+        let jsonl = JsonlTracer::new(Vec::new());
+        jsonl.trace(&invalid);
+        let out = String::from_utf8(jsonl.into_inner()).unwrap();
+        assert!(out.ends_with(
+            r#""direction": "received", "raw": "1201", "message": null}
+"#
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+use std::time;
+
+use crate::{Error, Message, Result};
+
+/// Represents the default timeout for a single transport read or write.
+pub const DEFAULT_TRANSPORT_TIMEOUT: time::Duration = time::Duration::from_millis(100);
+
+/// Represents a frame read by [DeviceTransport::read_frame].
+#[derive(Clone, Debug, PartialEq)]
+pub enum DeviceFrame {
+    /// A decoded [Message].
+    Message(Message),
+    /// A raw frame that failed to decode, e.g. a vendor-specific event, with the decoding error.
+    Undecoded((Vec<u8>, Error)),
+}
+
+/// Represents the message I/O between the host and a JCM device.
+///
+/// The polling functions are generic over the [DeviceTransport], so alternate backends (serial
+/// bridges, TCP proxies, simulated devices) reuse the protocol handling unchanged.
+pub trait DeviceTransport: Send + 'static {
+    /// Writes a request [Message] to the device.
+    fn write_message(&self, message: &Message) -> Result<()>;
+
+    /// Reads the next [Message] sent by the device, either a response or an event.
+    ///
+    /// Returns an error if no [Message] arrives before the [timeout](Self::timeout).
+    fn read_message(&self) -> Result<Message>;
+
+    /// Writes the host response to a device event [Message].
+    ///
+    /// The default implementation writes the response like any other [Message].
+    fn write_event_response(&self, message: &Message) -> Result<()> {
+        self.write_message(message)
+    }
+
+    /// Reads the next frame sent by the device, passing on frames that fail to decode.
+    ///
+    /// The device poller hands undecoded frames to a [VendorRegistry](crate::VendorRegistry). The
+    /// default implementation passes on no frames: decoding errors are returned as errors.
+    fn read_frame(&self) -> Result<DeviceFrame> {
+        self.read_message().map(DeviceFrame::Message)
+    }
+
+    /// Writes a raw frame to the device, e.g. the response to a vendor-specific event.
+    ///
+    /// The default implementation decodes the frame, and writes it as an event response.
+    fn write_frame(&self, frame: &[u8]) -> Result<()> {
+        self.write_event_response(&Message::try_from(frame)?)
+    }
+
+    /// Gets the timeout for a single read or write.
+    ///
+    /// The polling functions wait at least this long for each response, and read the device at
+    /// least this often.
+    fn timeout(&self) -> time::Duration {
+        DEFAULT_TRANSPORT_TIMEOUT
+    }
+}
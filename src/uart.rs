@@ -0,0 +1,373 @@
+//! Generic UART transport for JCM devices.
+//!
+//! [UartDeviceHandle] frames [Message]s over any byte stream implementing [io::Read] and
+//! [io::Write], e.g. a USB-CDC bridge, an RS-485 adapter, or an in-firmware UART driver, and
+//! implements [DeviceTransport], so it works with the polling functions in place of a USB handle.
+//!
+//! Line settings (baud rate, parity) are left to the stream. On unix hosts, the `serial` module
+//! provides a `SerialDeviceHandle` that configures a serial port, and reads through a
+//! [UartDeviceHandle].
+//!
+//! Streams signal "no data yet" by returning `Ok(0)`, or an [io::ErrorKind::WouldBlock] or
+//! [io::ErrorKind::TimedOut] error, from [read](io::Read::read). The handle keeps track of the
+//! frame timeouts itself. For streams where `Ok(0)` means the end of the stream, e.g. pipes and
+//! sockets, set [with_eof_disconnect](UartDeviceHandle::with_eof_disconnect), so reads fail
+//! with a [Disconnected](TransportErrorKind::Disconnected) error instead.
+
+use std::io::{self, Read, Write};
+use std::sync::{Mutex, MutexGuard};
+use std::{fmt, thread, time};
+
+use crate::{
+    DeviceTransport, Error, FrameDecoder, Message, Result, TransportError, TransportErrorKind,
+    MAX_LEN,
+};
+
+/// Represents the default maximum gap between two bytes of the same frame.
+pub const DEFAULT_INTER_BYTE_TIMEOUT: time::Duration = time::Duration::from_millis(100);
+/// Represents the default time to wait for the first byte of a frame.
+pub const DEFAULT_SERIAL_READ_TIMEOUT: time::Duration = time::Duration::from_millis(500);
+
+// ID + length
+const HEADER_LEN: usize = 3;
+// Delay between reads of a non-blocking stream without pending data.
+const POLL_INTERVAL: time::Duration = time::Duration::from_millis(1);
+
+/// Represents a host-side device handle over a generic UART byte stream.
+///
+/// # Example
+///
+/// ```
+/// use std::io;
+///
+/// use jcm::uart::UartDeviceHandle;
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// // status response: ID, length, conf ID, UID, type, code, response code
+/// let input = [0x12, 0x09, 0x00, 0x10, 0x00, 0x10, 0x10, 0x00, 0x06];
+/// let port = io::Cursor::new(input.to_vec());
+///
+/// let handle = UartDeviceHandle::new(port);
+/// let res = handle.read_message()?;
+///
+/// assert_eq!(res.data().additional(), [0x06]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct UartDeviceHandle<P> {
+    port: Mutex<P>,
+    read_timeout: time::Duration,
+    inter_byte_timeout: time::Duration,
+    max_frame_len: usize,
+    lenient_framing: bool,
+    eof_disconnect: bool,
+}
+
+impl<P: Read + Write> UartDeviceHandle<P> {
+    /// Creates a new [UartDeviceHandle] over the byte stream.
+    pub fn new(port: P) -> Self {
+        Self {
+            port: Mutex::new(port),
+            read_timeout: DEFAULT_SERIAL_READ_TIMEOUT,
+            inter_byte_timeout: DEFAULT_INTER_BYTE_TIMEOUT,
+            max_frame_len: MAX_LEN,
+            lenient_framing: false,
+            eof_disconnect: false,
+        }
+    }
+
+    /// Gets the time to wait for the first byte of a frame.
+    pub const fn read_timeout(&self) -> time::Duration {
+        self.read_timeout
+    }
+
+    /// Builder function that sets the time to wait for the first byte of a frame.
+    pub fn with_read_timeout(mut self, val: time::Duration) -> Self {
+        self.read_timeout = val;
+        self
+    }
+
+    /// Gets the maximum gap between two bytes of the same frame.
+    pub const fn inter_byte_timeout(&self) -> time::Duration {
+        self.inter_byte_timeout
+    }
+
+    /// Builder function that sets the maximum gap between two bytes of the same frame.
+    pub fn with_inter_byte_timeout(mut self, val: time::Duration) -> Self {
+        self.inter_byte_timeout = val;
+        self
+    }
+
+    /// Gets the maximum length of a frame read from the device.
+    pub const fn max_frame_len(&self) -> usize {
+        self.max_frame_len
+    }
+
+    /// Builder function that sets the maximum length of a frame read from the device.
+    ///
+    /// The length is capped at the [MAX_LEN] of a [Message].
+    pub fn with_max_frame_len(mut self, max_frame_len: usize) -> Self {
+        self.max_frame_len = max_frame_len.min(MAX_LEN);
+        self
+    }
+
+    /// Gets whether frames read from the device are parsed with
+    /// [Message::from_bytes_lenient].
+    pub const fn lenient_framing(&self) -> bool {
+        self.lenient_framing
+    }
+
+    /// Builder function that sets whether frames read from the device are parsed with
+    /// [Message::from_bytes_lenient], for firmware with quirks in the frame length field.
+    pub fn with_lenient_framing(mut self, lenient: bool) -> Self {
+        self.lenient_framing = lenient;
+        self
+    }
+
+    /// Gets whether the end of the byte stream is a disconnected device.
+    pub const fn eof_disconnect(&self) -> bool {
+        self.eof_disconnect
+    }
+
+    /// Builder function that sets whether the end of the byte stream is a disconnected device.
+    ///
+    /// By default, reads returning `Ok(0)` mean no data yet, like a serial port read with
+    /// `VMIN = 0`. Enable for streams that return `Ok(0)` only once closed.
+    pub fn with_eof_disconnect(mut self, val: bool) -> Self {
+        self.eof_disconnect = val;
+        self
+    }
+
+    /// Consumes the [UartDeviceHandle], returning the byte stream.
+    pub fn into_inner(self) -> Result<P> {
+        self.port
+            .into_inner()
+            .map_err(|err| Error::Serial(format!("error unlocking serial port: {err}")))
+    }
+
+    /// Writes a [Message] to the byte stream.
+    pub fn write_message(&self, message: &Message) -> Result<()> {
+        let bytes: Vec<u8> = message.into();
+
+        let mut port = self.lock_port()?;
+        port.write_all(bytes.as_ref())
+            .and_then(|_| port.flush())
+            .map_err(|err| {
+                let err_msg =
+                    format!(r#"error writing message: {{"message": {message}, "error": "{err}"}}"#);
+                log::warn!("{err_msg}");
+                Error::Serial(err_msg)
+            })
+    }
+
+    /// Reads a [Message] from the byte stream.
+    ///
+    /// Waits up to the [read timeout](Self::read_timeout) for the start of a frame. Once
+    /// started, the frame fails with [Error::Timeout] if the gap between two bytes exceeds the
+    /// [inter-byte timeout](Self::inter_byte_timeout).
+    pub fn read_message(&self) -> Result<Message> {
+        let mut port = self.lock_port()?;
+        let mut decoder = FrameDecoder::new()
+            .with_max_len(self.max_frame_len)
+            .with_lenient(self.lenient_framing);
+        let mut buf = [0u8; 64];
+
+        let mut last = time::Instant::now();
+        while !decoder.is_complete() {
+            let want = if decoder.is_empty() {
+                1
+            } else {
+                decoder
+                    .declared_len()
+                    .unwrap_or(HEADER_LEN)
+                    .saturating_sub(decoder.len())
+                    .clamp(1, buf.len())
+            };
+
+            match read_some(&mut *port, &mut buf[..want], self.eof_disconnect)? {
+                Some(read) => {
+                    decoder.push(&buf[..read]).inspect_err(|err| {
+                        let discarded = discard_input(&mut *port);
+                        log::error!("{err}, discarded {discarded} bytes of pending input");
+                    })?;
+                    last = time::Instant::now();
+                }
+                None if decoder.is_empty() && last.elapsed() >= self.read_timeout => {
+                    return Err(Error::Timeout("serial read".into()));
+                }
+                None if !decoder.is_empty() && last.elapsed() >= self.inter_byte_timeout => {
+                    return Err(Error::Timeout(format!(
+                        "serial inter-byte, read {} bytes",
+                        decoder.len()
+                    )));
+                }
+                None => (),
+            }
+        }
+
+        decoder.decode()
+    }
+
+    fn lock_port(&self) -> Result<MutexGuard<'_, P>> {
+        self.port
+            .lock()
+            .map_err(|err| Error::Serial(format!("error locking serial port: {err}")))
+    }
+}
+
+impl<P> fmt::Debug for UartDeviceHandle<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UartDeviceHandle")
+            .field("read_timeout", &self.read_timeout)
+            .field("inter_byte_timeout", &self.inter_byte_timeout)
+            .field("max_frame_len", &self.max_frame_len)
+            .field("eof_disconnect", &self.eof_disconnect)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P: Read + Write + Send + 'static> DeviceTransport for UartDeviceHandle<P> {
+    fn write_message(&self, message: &Message) -> Result<()> {
+        UartDeviceHandle::write_message(self, message)
+    }
+
+    fn read_message(&self) -> Result<Message> {
+        UartDeviceHandle::read_message(self)
+    }
+
+    fn timeout(&self) -> time::Duration {
+        self.read_timeout
+    }
+}
+
+/// Reads available bytes, returning `None` if the stream has no data yet.
+///
+/// Fails with a [Disconnected](TransportErrorKind::Disconnected) error at the end of the stream,
+/// if `eof_disconnect` is set.
+fn read_some<P: Read + ?Sized>(
+    port: &mut P,
+    buf: &mut [u8],
+    eof_disconnect: bool,
+) -> Result<Option<usize>> {
+    match port.read(buf) {
+        Ok(0) if eof_disconnect => Err(TransportError::new(
+            TransportErrorKind::Disconnected,
+            "serial stream closed",
+        )
+        .into()),
+        Ok(0) => {
+            thread::sleep(POLL_INTERVAL);
+            Ok(None)
+        }
+        Ok(read) => Ok(Some(read)),
+        Err(err) if err.kind() == io::ErrorKind::Interrupted => Ok(None),
+        Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+            thread::sleep(POLL_INTERVAL);
+            Ok(None)
+        }
+        Err(err) if err.kind() == io::ErrorKind::TimedOut => Ok(None),
+        Err(err) => Err(Error::Serial(format!("error reading message: {err}"))),
+    }
+}
+
+/// Discards pending input, up to the length of a full frame, returning the number of bytes read.
+fn discard_input<P: Read + ?Sized>(port: &mut P) -> usize {
+    let mut buf = [0u8; 64];
+    let mut discarded = 0;
+
+    while discarded < MAX_LEN {
+        match port.read(&mut buf) {
+            Ok(read) if read > 0 => discarded += read,
+            _ => break,
+        }
+    }
+
+    discarded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MessageCode, RequestCode, StatusRequest};
+
+    struct Loopback {
+        input: io::Cursor<Vec<u8>>,
+        output: Vec<u8>,
+    }
+
+    impl Read for Loopback {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.input.read(buf)
+        }
+    }
+
+    impl Write for Loopback {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.output.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn handle(input: &[u8]) -> UartDeviceHandle<Loopback> {
+        UartDeviceHandle::new(Loopback {
+            input: io::Cursor::new(input.to_vec()),
+            output: Vec::new(),
+        })
+        .with_read_timeout(time::Duration::ZERO)
+        .with_inter_byte_timeout(time::Duration::ZERO)
+    }
+
+    #[test]
+    fn test_uart_device_handle() -> Result<()> {
+        let req = Message::from(StatusRequest::new());
+        let frame: Vec<u8> = (&req).into();
+
+        let uart = handle(&frame);
+        uart.write_message(&req)?;
+        assert_eq!(
+            uart.read_message()?.data().message_code(),
+            MessageCode::Request(RequestCode::Status)
+        );
+        assert!(matches!(uart.read_message(), Err(Error::Timeout(_))));
+        assert_eq!(uart.into_inner()?.output, frame);
+
+        let truncated = handle(&frame[..frame.len() - 1]);
+        assert!(matches!(truncated.read_message(), Err(Error::Timeout(_))));
+
+        let oversized = handle(&frame).with_max_frame_len(frame.len() - 1);
+        assert!(oversized.read_message().is_err());
+        assert!(oversized.into_inner()?.input.position() as usize == frame.len());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_uart_closed_input() -> Result<()> {
+        let req = Message::from(StatusRequest::new());
+        let frame: Vec<u8> = (&req).into();
+
+        let uart = handle(&frame).with_eof_disconnect(true);
+        assert!(uart.read_message().is_ok());
+
+        for closed in [uart, handle(&frame[..2]).with_eof_disconnect(true)] {
+            match closed.read_message() {
+                Err(Error::Transport(err)) => {
+                    assert_eq!(err.kind(), TransportErrorKind::Disconnected);
+                    assert!(!Error::Transport(err).is_retryable());
+                }
+                res => panic!("unexpected read result: {res:?}"),
+            }
+        }
+
+        // by default, a closed input has no data yet, and the read times out
+        let uart = handle(&[]).with_read_timeout(time::Duration::from_millis(10));
+        let start = time::Instant::now();
+        assert!(matches!(uart.read_message(), Err(Error::Timeout(_))));
+        assert!(start.elapsed() >= time::Duration::from_millis(10));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Mutex, MutexGuard};
+
+use crate::{Error, Message, MessageData, Response, ResponseCode, Result, UidRequest, UidResponse};
+
+/// Represents the UID of a device without an assigned UID.
+pub const UNASSIGNED_UID: u8 = 0;
+/// Represents the lowest UID assigned by a [UidManager].
+pub const MIN_UID: u8 = 1;
+/// Represents the highest UID assigned by a [UidManager].
+pub const MAX_UID: u8 = 0xff;
+
+/// Assigns and tracks the UIDs of the devices on a host.
+///
+/// Devices are identified by a host-side key, e.g. the USB serial number or the serial port path.
+/// On startup, [negotiate](Self::negotiate) keeps the UID reported by the device if no other live
+/// device uses it, and assigns the lowest free UID otherwise. Requests polled through
+/// [stamped](Self::stamped) carry the assigned UID of the device, so callers never hard-code it.
+#[derive(Debug, Default)]
+pub struct UidManager {
+    devices: Mutex<BTreeMap<String, u8>>,
+}
+
+impl UidManager {
+    /// Creates a new [UidManager].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Discovers the UID of the device, assigning a free UID if needed.
+    ///
+    /// Returns the UID tracked for the device.
+    pub fn negotiate<P>(&self, device: &str, mut poll: P) -> Result<u8>
+    where
+        P: FnMut(&Message) -> Result<Message>,
+    {
+        let mut request = |uid: u8, req: UidRequest| -> Result<Response> {
+            let res = Response::try_from(poll(&MessageData::from(req).with_uid(uid).into())?)?;
+
+            match res.code() {
+                ResponseCode::Ack => Ok(res),
+                code => Err(Error::InvalidResponseCode(code.into())),
+            }
+        };
+        let current = UidResponse::try_from(request(UNASSIGNED_UID, UidRequest::new_get())?)?.uid();
+
+        let mut devices = self.lock();
+        devices.remove(device);
+
+        if current != UNASSIGNED_UID && !devices.values().any(|&uid| uid == current) {
+            log::debug!("device {device}: keeping UID {current}");
+            devices.insert(device.into(), current);
+            return Ok(current);
+        }
+
+        let uid = (MIN_UID..=MAX_UID)
+            .find(|uid| !devices.values().any(|u| u == uid))
+            .ok_or(Error::UidExhausted)?;
+
+        request(current, UidRequest::new_set(uid))?;
+
+        let assigned = UidResponse::try_from(request(uid, UidRequest::new_get())?)?.uid();
+        if assigned != uid {
+            return Err(Error::InvalidUid((assigned, uid)));
+        }
+
+        log::info!("device {device}: assigned UID {uid}, previous: {current}");
+        devices.insert(device.into(), uid);
+
+        Ok(uid)
+    }
+
+    /// Gets the UID assigned to the device, if any.
+    pub fn uid(&self, device: &str) -> Option<u8> {
+        self.lock().get(device).copied()
+    }
+
+    /// Gets whether a live device uses the UID.
+    pub fn is_live(&self, uid: u8) -> bool {
+        self.lock().values().any(|&u| u == uid)
+    }
+
+    /// Gets the list of live devices, and their UIDs.
+    pub fn devices(&self) -> Vec<(String, u8)> {
+        self.lock()
+            .iter()
+            .map(|(device, uid)| (device.clone(), *uid))
+            .collect()
+    }
+
+    /// Releases the UID of a disconnected device, returning the released UID.
+    pub fn release(&self, device: &str) -> Option<u8> {
+        self.lock().remove(device)
+    }
+
+    /// Stamps the request [Message] with the UID assigned to the device.
+    pub fn stamp(&self, device: &str, message: &Message) -> Result<Message> {
+        let uid = self
+            .uid(device)
+            .ok_or(Error::UnassignedUid(device.into()))?;
+
+        Ok(Message::new().with_data(message.data().clone().with_uid(uid)))
+    }
+
+    /// Wraps a polling function, stamping every request with the UID assigned to the device.
+    pub fn stamped<'a, P>(
+        &'a self,
+        device: &'a str,
+        mut poll: P,
+    ) -> impl FnMut(&Message) -> Result<Message> + 'a
+    where
+        P: FnMut(&Message) -> Result<Message> + 'a,
+    {
+        move |req: &Message| poll(&self.stamp(device, req)?)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<String, u8>> {
+        self.devices.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl fmt::Display for UidManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        for (i, (device, uid)) in self.devices().iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, r#""{device}": {uid}"#)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockDevice;
+    use crate::StatusRequest;
+
+    #[test]
+    fn test_uid_manager() -> Result<()> {
+        let manager = UidManager::new();
+        let (left, right) = (MockDevice::new(), MockDevice::new());
+
+        assert_eq!(
+            manager.negotiate("left", |req| left.handle_request(req))?,
+            1
+        );
+        assert_eq!(
+            manager.negotiate("right", |req| right.handle_request(req))?,
+            2
+        );
+        assert_eq!((left.uid(), right.uid()), (1, 2));
+
+        // renegotiating keeps the UID the device already has
+        assert_eq!(
+            manager.negotiate("right", |req| right.handle_request(req))?,
+            2
+        );
+        assert!(manager.is_live(2));
+        assert_eq!(manager.to_string(), r#"{"left": 1, "right": 2}"#);
+
+        let mut sent = Vec::new();
+        let mut poll = manager.stamped("right", |req: &Message| {
+            sent.push(req.data().uid());
+            right.handle_request(req)
+        });
+        poll(&StatusRequest::new().into())?;
+        drop(poll);
+        assert_eq!(sent, [2]);
+
+        assert_eq!(manager.release("left"), Some(1));
+        assert_eq!(
+            manager.stamp("left", &StatusRequest::new().into()),
+            Err(Error::UnassignedUid("left".into()))
+        );
+
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use std::{fmt, mem};
+use core::{fmt, mem};
 
 use crate::{Error, FuncId, Result};
 
@@ -15,6 +15,7 @@ const UNIT_NUM_MASK: u8 = 0x0f;
 /// Bitmask | 0xf0     | 0x0f (0x1 - 0xf, zero is invalid)
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UnitNumber(u8);
 
 impl UnitNumber {
@@ -1,10 +1,12 @@
-use std::fmt;
+use alloc::vec::Vec;
+use core::fmt;
 
 use crate::{Error, FunctionStatus, Result, UnitNumber};
 
 /// Represents the status of a JCM device unit, e.g. `Acceptor`, `Stacker`, `Recycler`, etc.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UnitStatus {
     unit_number: UnitNumber,
     function_status: FunctionStatus,
@@ -144,6 +146,7 @@ impl fmt::Display for UnitStatus {
 /// Convenience container for a list of [UnitStatus] items.
 #[repr(C)]
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UnitStatusList(pub Vec<UnitStatus>);
 
 impl UnitStatusList {
@@ -206,7 +209,7 @@ impl TryFrom<&[u8]> for UnitStatusList {
         let unit_len = UnitStatus::len();
         let val_len = val.len();
 
-        if val_len % unit_len == 0 {
+        if val_len.is_multiple_of(unit_len) {
             Ok(Self(
                 val.chunks_exact(UnitStatus::len())
                     .filter_map(|c| match UnitStatus::try_from(c) {
@@ -1,22 +1,89 @@
+use std::collections::{BTreeMap, VecDeque};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, Weak};
 use std::{thread, time};
 
 use futures_lite::future::block_on;
 use nusb::transfer::{ControlOut, ControlType, Recipient, RequestBuffer};
 use smol_timeout::TimeoutExt;
 
-use crate::{Error, Message, ResponseCode, Result};
+use crate::trace::{MessageTracer, TraceRecord};
+use crate::{
+    event_ack, is_status_message, redact, AuditCounters, CancelToken, Capabilities,
+    CashboxExchange, CashboxExchangeReport, Clock, Credit, CreditAcknowledger, CreditJournal,
+    DebugMonitor, DebugState, DeviceFrame, DeviceInhibit, DeviceTransport, DirectionDisableDelta,
+    Error, FrameDecoder, ImageFetcher, ImageKind, ImageProgress, InhibitDirection, KeepAlive,
+    Message, MessageDirection, NoteImage, NoteSerialNumber, PollConfig, PollObserver,
+    PowerUpReport, PowerUpRoutine, ProductFamily, ProgramSignatureResponse, RequestCode, Result,
+    SignatureAudit, StatusMessageMode, SystemClock, TransportError, TransportErrorKind, TypedEvent,
+    TypedRequest, UidManager, VendorRegistry, MAX_LEN, POWER_UP_GRACE_PERIOD,
+};
 
+mod buffer_pool;
+mod correlation;
+mod device_descriptor;
+mod device_io;
 mod endpoint;
+mod event_acknowledger;
+mod product_id;
+#[cfg(test)]
+mod test_transport;
+mod transfer_stats;
+mod unsolicited;
 
+pub use buffer_pool::*;
+pub use correlation::*;
+pub use device_descriptor::*;
+pub use device_io::*;
 pub use endpoint::*;
+pub use event_acknowledger::*;
+pub use product_id::*;
+pub use transfer_stats::*;
+pub use unsolicited::*;
 
+/// Default JCM USB vendor ID
 pub const JCM_VID: u16 = 0x2475;
+/// Default JCM XFS USB product ID
 pub const JCM_PID: u16 = 0x0105;
 
-/// USB communication timeout (ms)
-pub const USB_TIMEOUT: u64 = 100;
+/// USB communication timeout
+pub const USB_TIMEOUT: time::Duration = time::Duration::from_millis(100);
+
+/// Interval between device message polls
+pub const POLL_INTERVAL: time::Duration = time::Duration::from_millis(100);
+
+/// Timeout for receiving a response to a polled request
+pub const RESPONSE_TIMEOUT: time::Duration = crate::DEFAULT_RESPONSE_TIMEOUT;
+
+/// Granularity used when waiting on a channel against a [Clock]
+pub const CLOCK_RECV_INTERVAL: time::Duration = time::Duration::from_millis(10);
+
+/// Lists the JCM XFS USB devices currently attached to the host.
+///
+/// Only enumerates USB descriptors: devices are not opened, and interfaces are not claimed, so
+/// the check is safe to run while another process drives the device.
+pub fn list_present() -> Vec<nusb::DeviceInfo> {
+    match nusb::list_devices() {
+        Ok(devices) => devices.filter(is_jcm_device).collect(),
+        Err(err) => {
+            log::debug!("unable to enumerate USB devices: {err}");
+            Vec::new()
+        }
+    }
+}
+
+/// Gets whether a JCM XFS USB device is attached to the host.
+///
+/// See [list_present] for details.
+pub fn is_device_present() -> bool {
+    nusb::list_devices()
+        .map(|mut devices| devices.any(|dev| is_jcm_device(&dev)))
+        .unwrap_or(false)
+}
+
+fn is_jcm_device(dev: &nusb::DeviceInfo) -> bool {
+    ProductId::find(dev.vendor_id(), dev.product_id()).is_some()
+}
 
 /// Represents a host-side USB device handle.
 pub struct UsbDeviceHandle {
@@ -24,23 +91,102 @@ pub struct UsbDeviceHandle {
     interface: nusb::Interface,
     req_ep: Endpoint,
     res_ep: Endpoint,
+    serial: Option<String>,
+    family: ProductFamily,
+    capabilities: Option<Capabilities>,
+    max_frame_len: usize,
+    lenient_framing: bool,
+    counters: TransferCounters,
+    buffers: BufferPool,
+    tracer: Option<Arc<dyn MessageTracer>>,
 }
 
 impl UsbDeviceHandle {
-    /// Finds the JCM XFS USB device by PID:VID pair.
+    /// Finds the JCM XFS USB device by the VID:PID pairs in [PRODUCT_IDS].
+    ///
+    /// Opens the first matching device. Use [open_by_serial](Self::open_by_serial) or
+    /// [open_by_address](Self::open_by_address) when more than one unit is attached.
     pub fn find_usb() -> Result<Self> {
         let info = nusb::list_devices()
-            .map_err(|err| {
-                Error::Usb(format!("no devices found: {err}"))
-            })?
-        .find(|dev| {
-            dev.vendor_id() == JCM_VID && dev.product_id() == JCM_PID
-        })
-        .ok_or(Error::Usb(format!("failed to find a USB device with the correct VID({JCM_VID:04x}):PID({JCM_PID:04x}) pair")))?;
+            .map_err(|err| io_error("no devices found", err))?
+            .find(is_jcm_device)
+            .ok_or_else(|| {
+                transport_error(
+                    TransportErrorKind::Disconnected,
+                    &format!(
+                        "failed to find a supported USB device, tried VID:PID pairs: {}",
+                        ProductId::supported_pairs()
+                    ),
+                )
+            })?;
+
+        Self::open(&info)
+    }
+
+    /// Finds the USB device by a custom VID:PID pair, e.g. for products missing from
+    /// [PRODUCT_IDS].
+    pub fn find_usb_with(vendor_id: u16, product_id: u16) -> Result<Self> {
+        let info = nusb::list_devices()
+            .map_err(|err| io_error("no devices found", err))?
+            .find(|info| info.vendor_id() == vendor_id && info.product_id() == product_id)
+            .ok_or_else(|| {
+                transport_error(TransportErrorKind::Disconnected, &format!("failed to find a USB device with the correct VID({vendor_id:04x}):PID({product_id:04x}) pair"))
+            })?;
+
+        Self::open(&info)
+    }
+
+    /// Lists the [UsbDeviceDescriptor]s of all attached JCM XFS USB devices.
+    ///
+    /// Devices are not opened, see [list_present] for details.
+    pub fn list() -> Result<Vec<UsbDeviceDescriptor>> {
+        nusb::list_devices()
+            .map(|devices| {
+                devices
+                    .filter(is_jcm_device)
+                    .map(UsbDeviceDescriptor::from)
+                    .collect()
+            })
+            .map_err(|err| io_error("no devices found", err))
+    }
+
+    /// Opens the JCM XFS USB device with the USB descriptor serial number.
+    pub fn open_by_serial(serial_number: &str) -> Result<Self> {
+        Self::open_matching(
+            |info| info.serial_number() == Some(serial_number),
+            || format!("failed to find a USB device with serial number: {serial_number}"),
+        )
+    }
+
+    /// Opens the JCM XFS USB device at the USB bus number and device address.
+    ///
+    /// Device addresses are assigned on enumeration, and may change after a reconnect.
+    pub fn open_by_address(bus_number: u8, device_address: u8) -> Result<Self> {
+        Self::open_matching(
+            |info| info.bus_number() == bus_number && info.device_address() == device_address,
+            || {
+                format!("failed to find a USB device at bus({bus_number:03}):address({device_address:03})")
+            },
+        )
+    }
+
+    fn open_matching<F, E>(matches: F, not_found: E) -> Result<Self>
+    where
+        F: Fn(&nusb::DeviceInfo) -> bool,
+        E: FnOnce() -> String,
+    {
+        let info = nusb::list_devices()
+            .map_err(|err| io_error("no devices found", err))?
+            .find(|info| is_jcm_device(info) && matches(info))
+            .ok_or_else(|| transport_error(TransportErrorKind::Disconnected, &not_found()))?;
 
+        Self::open(&info)
+    }
+
+    fn open(info: &nusb::DeviceInfo) -> Result<Self> {
         let device = info
             .open()
-            .map_err(|err| Error::Usb(format!("unable to open device: {err}")))?;
+            .map_err(|err| io_error("unable to open device", err))?;
 
         Self::setup_device(&device).map_err(|err| {
             log::error!("Device setup failed: {err}");
@@ -54,6 +200,18 @@ impl UsbDeviceHandle {
             interface,
             req_ep,
             res_ep,
+            serial: info.serial_number().map(String::from),
+            family: ProductId::detect_family(
+                info.vendor_id(),
+                info.product_id(),
+                info.product_string(),
+            ),
+            capabilities: None,
+            max_frame_len: MAX_LEN,
+            lenient_framing: false,
+            counters: TransferCounters::new(),
+            buffers: BufferPool::new(),
+            tracer: None,
         })
     }
 
@@ -67,150 +225,343 @@ impl UsbDeviceHandle {
         &self.interface
     }
 
+    /// Gets the USB descriptor serial number of the device, if reported.
+    ///
+    /// Use the serial number as the key into a [DeviceInfoCache](crate::DeviceInfoCache) to skip
+    /// immutable queries on reconnect.
+    pub fn serial_number(&self) -> Option<&str> {
+        self.serial.as_deref()
+    }
+
+    /// Gets the [ProductFamily] of the device.
+    ///
+    /// Detected from the VID:PID pair, or the USB product string, when the device is opened.
+    pub const fn product_family(&self) -> ProductFamily {
+        self.family
+    }
+
+    /// Gets a reference to the [Capabilities] of the device, if queried.
+    ///
+    /// See [query_capabilities] for querying the device.
+    pub const fn capabilities(&self) -> Option<&Capabilities> {
+        self.capabilities.as_ref()
+    }
+
+    /// Sets the [Capabilities] of the device.
+    pub fn set_capabilities(&mut self, capabilities: Capabilities) {
+        self.capabilities = Some(capabilities);
+    }
+
+    /// Unsets the [Capabilities] of the device, e.g. after a firmware update.
+    pub fn unset_capabilities(&mut self) -> Option<Capabilities> {
+        self.capabilities.take()
+    }
+
+    /// Gets the maximum length of a frame read from the device.
+    pub const fn max_frame_len(&self) -> usize {
+        self.max_frame_len
+    }
+
+    /// Sets the maximum length of a frame read from the device.
+    ///
+    /// Reads accumulating more bytes fail with [Error::FrameTooLarge], after flushing the
+    /// remainder of the frame from the endpoint. The length is capped at the [MAX_LEN] of a
+    /// [Message].
+    pub fn set_max_frame_len(&mut self, max_frame_len: usize) {
+        self.max_frame_len = max_frame_len.min(MAX_LEN);
+    }
+
+    /// Builder function that sets the maximum length of a frame read from the device.
+    pub fn with_max_frame_len(mut self, max_frame_len: usize) -> Self {
+        self.set_max_frame_len(max_frame_len);
+        self
+    }
+
+    /// Gets whether frames read from the device are parsed with
+    /// [Message::from_bytes_lenient].
+    pub const fn lenient_framing(&self) -> bool {
+        self.lenient_framing
+    }
+
+    /// Sets whether frames read from the device are parsed with
+    /// [Message::from_bytes_lenient], for firmware with quirks in the frame length field.
+    pub fn set_lenient_framing(&mut self, lenient: bool) {
+        self.lenient_framing = lenient;
+    }
+
+    /// Builder function that sets whether frames read from the device are parsed with
+    /// [Message::from_bytes_lenient].
+    pub fn with_lenient_framing(mut self, lenient: bool) -> Self {
+        self.set_lenient_framing(lenient);
+        self
+    }
+
+    /// Gets a reference to the [MessageTracer] receiving every frame exchanged with the device.
+    pub fn tracer(&self) -> Option<&Arc<dyn MessageTracer>> {
+        self.tracer.as_ref()
+    }
+
+    /// Sets the [MessageTracer] receiving every frame exchanged with the device.
+    ///
+    /// Received frames are traced before parsing, so malformed frames are captured too.
+    pub fn set_tracer(&mut self, tracer: Arc<dyn MessageTracer>) {
+        self.tracer.replace(tracer);
+    }
+
+    /// Unsets the [MessageTracer].
+    pub fn unset_tracer(&mut self) {
+        self.tracer.take();
+    }
+
+    /// Builder function that sets the [MessageTracer] receiving every frame exchanged with the
+    /// device.
+    pub fn with_tracer(mut self, tracer: Arc<dyn MessageTracer>) -> Self {
+        self.set_tracer(tracer);
+        self
+    }
+
+    /// Gets a reference to the [BufferPool] reused by the bulk transfers.
+    pub const fn buffer_pool(&self) -> &BufferPool {
+        &self.buffers
+    }
+
+    /// Gets a snapshot of the transport-level [TransferStats].
+    pub fn stats(&self) -> TransferStats {
+        self.counters.stats()
+    }
+
+    /// Gets a snapshot of the transport-level [TransferStats], and resets the counters.
+    ///
+    /// Use from periodic scrapers to report the counters accumulated since the last scrape.
+    pub fn take_stats(&self) -> TransferStats {
+        self.counters.take()
+    }
+
     /// Writes a request [Message] to the JCM device.
     pub fn write_request(&self, message: &Message) -> Result<()> {
-        block_on(
-            self.interface
-                .bulk_out(self.req_ep.address(), message.into())
-                .timeout(time::Duration::from_millis(USB_TIMEOUT)),
-        )
-        .ok_or(Error::Usb("write Request timeout expired".into()))?
-        .into_result()
-        .map(|_| ())
-        .map_err(|err| {
-            let err_msg =
-                format!(r#"error writing message: {{"message": {message}, "error": {err}}}"#);
-            log::warn!("{err_msg}");
-            Error::Usb(err_msg)
-        })
+        self.write_message(message, "Request")
     }
 
     /// Reads the response from a JCM device.
     pub fn read_response(&self) -> Result<Message> {
-        let mut res_acc = Vec::with_capacity(self.res_ep.max_packet_size());
-        let mut res_buf = block_on(
-            self.interface
-                .bulk_in(
-                    self.res_ep.address(),
-                    RequestBuffer::new(self.res_ep.max_packet_size()),
+        self.read_message("Response")
+    }
+
+    /// Reads an event [Message] from the JCM device.
+    pub fn read_event(&self) -> Result<Message> {
+        self.read_message("Event")
+    }
+
+    fn read_message(&self, kind: &str) -> Result<Message> {
+        match self.read_frame(kind)? {
+            DeviceFrame::Message(msg) => Ok(msg),
+            DeviceFrame::Undecoded((_, err)) => Err(err),
+        }
+    }
+
+    fn read_frame(&self, kind: &str) -> Result<DeviceFrame> {
+        let max_packet_size = self.res_ep.max_packet_size();
+        let mut decoder = FrameDecoder::new()
+            .with_max_len(self.max_frame_len)
+            .with_lenient(self.lenient_framing);
+
+        let (mut res_buf, status) = self.read_packet(self.buffers.take());
+        match status {
+            Some(Ok(())) => (),
+            Some(Err(err)) => {
+                self.buffers.put(res_buf);
+                self.counters.record_error(&err);
+                let err_msg = format!("Error reading response: {err}");
+                log::error!("{err_msg}");
+                return Err(TransportError::new(err.into(), &err_msg)
+                    .with_source(err)
+                    .into());
+            }
+            None => {
+                self.buffers.put(res_buf);
+                self.counters.record_timeout();
+                return Err(TransportError::new(
+                    TransportErrorKind::Timeout,
+                    &format!("read {kind} timeout expired"),
                 )
-                .timeout(time::Duration::from_millis(USB_TIMEOUT)),
-        )
-        .ok_or(Error::Usb("read Response timeout expired".into()))?
-        .into_result()
-        .map_err(|err| {
-            let err_msg = format!("Error reading response: {err}");
-            log::error!("{err_msg}");
-            Error::Usb(err_msg)
-        })?;
+                .into());
+            }
+        }
 
         let mut read = res_buf.len();
-        res_acc.append(&mut res_buf);
-        while read == self.res_ep.max_packet_size() {
-            // clear the buffer to avoid leaving old data in the trailing bytes
-            res_buf = match block_on(
-                self.interface
-                    .bulk_in(
-                        self.res_ep.address(),
-                        RequestBuffer::reuse(res_buf, self.res_ep.max_packet_size()),
-                    )
-                    .timeout(time::Duration::from_millis(USB_TIMEOUT)),
-            )
-            .ok_or(Error::Usb(
-                "read Response follow-on packet timeout expired".into(),
-            ))?
-            .into_result()
-            {
-                Ok(r) => r,
-                Err(_err) => Vec::new(),
-            };
+        self.counters.record_in(read);
+        let mut pushed = self.push_frame(&mut decoder, &res_buf);
+        while pushed.is_ok() && read == max_packet_size {
+            let (buf, status) = self.read_packet(res_buf);
+            res_buf = buf;
+            match status {
+                Some(Ok(())) => self.counters.record_in(res_buf.len()),
+                Some(Err(err)) => {
+                    self.counters.record_error(&err);
+                    res_buf.clear();
+                }
+                None => {
+                    self.buffers.put(res_buf);
+                    self.counters.record_timeout();
+                    let err_msg = format!("read {kind} follow-on packet timeout expired");
+                    return Err(TransportError::new(TransportErrorKind::Timeout, &err_msg).into());
+                }
+            }
             read = res_buf.len();
             if read > 0 {
-                res_acc.append(&mut res_buf);
+                pushed = self.push_frame(&mut decoder, &res_buf);
             }
         }
+        self.buffers.put(res_buf);
+        pushed?;
 
-        log::trace!("Raw response: {res_acc:?}");
-        match Message::try_from(res_acc.as_slice()) {
-            Ok(msg) => Ok(msg),
-            Err(err) => {
+        if crate::log_redaction() {
+            log::trace!("Raw response: {} bytes", decoder.len());
+        } else {
+            log::trace!("Raw response: {:?}", decoder.as_bytes());
+        }
+        if let Some(tracer) = self.tracer.as_ref() {
+            tracer.trace(&TraceRecord::new(
+                MessageDirection::Received,
+                decoder.as_bytes(),
+            ));
+        }
+        match decoder.decode_or_raw() {
+            Ok(msg) => Ok(DeviceFrame::Message(msg)),
+            Err((frame, err)) => {
                 log::error!("Error parsing response: {err}");
-                Err(err)
+                Ok(DeviceFrame::Undecoded((frame, err)))
             }
         }
     }
 
-    /// Reads an event [Message] from the JCM device.
-    pub fn read_event(&self) -> Result<Message> {
-        let mut res_acc = Vec::with_capacity(self.res_ep.max_packet_size());
-        let mut res_buf = block_on(
-            self.interface
-                .bulk_in(
-                    self.res_ep.address(),
-                    RequestBuffer::new(self.res_ep.max_packet_size()),
-                )
-                .timeout(time::Duration::from_millis(USB_TIMEOUT)),
-        )
-        .ok_or(Error::Usb("read Event timeout expired".into()))?
-        .into_result()
-        .map_err(|err| {
-            let err_msg = format!("Error reading response: {err}");
-            log::error!("{err_msg}");
-            Error::Usb(err_msg)
-        })?;
+    /// Reads one packet into the buffer, returning the buffer with the transfer status.
+    ///
+    /// The status is `None` if the read timed out. The timed out transfer is cancelled, and
+    /// awaited, so its buffer returns to the [BufferPool] instead of being dropped with the
+    /// transfer.
+    fn read_packet(
+        &self,
+        buf: Vec<u8>,
+    ) -> (
+        Vec<u8>,
+        Option<std::result::Result<(), nusb::transfer::TransferError>>,
+    ) {
+        let mut queue = self.interface.bulk_in_queue(self.res_ep.address());
+        queue.submit(RequestBuffer::reuse(buf, self.res_ep.max_packet_size()));
 
-        let mut read = res_buf.len();
-        res_acc.append(&mut res_buf);
-        while read == self.res_ep.max_packet_size() {
-            // clear the buffer to avoid leaving old data in the trailing bytes
-            res_buf = match block_on(
-                self.interface
-                    .bulk_in(
-                        self.res_ep.address(),
-                        RequestBuffer::reuse(res_buf, self.res_ep.max_packet_size()),
-                    )
-                    .timeout(time::Duration::from_millis(USB_TIMEOUT)),
-            )
-            .ok_or(Error::Usb(
-                "read Event follow-on packet timeout expired".into(),
-            ))?
-            .into_result()
-            {
-                Ok(r) => r,
-                Err(_err) => Vec::new(),
-            };
-            read = res_buf.len();
-            if read > 0 {
-                res_acc.append(&mut res_buf);
+        match block_on(queue.next_complete().timeout(USB_TIMEOUT)) {
+            Some(completion) => (completion.data, Some(completion.status)),
+            None => {
+                queue.cancel_all();
+                let completion = block_on(queue.next_complete());
+                // the transfer may complete before it is cancelled
+                let status = completion.status.is_ok().then_some(Ok(()));
+                (completion.data, status)
             }
         }
+    }
 
-        log::trace!("Raw response: {res_acc:?}");
-        match Message::try_from(res_acc.as_slice()) {
-            Ok(msg) => Ok(msg),
-            Err(err) => {
-                log::error!("Error parsing response: {err}");
-                Err(err)
+    /// Appends a packet to the frame, flushing the endpoint if the frame grows too large.
+    fn push_frame(&self, decoder: &mut FrameDecoder, packet: &[u8]) -> Result<()> {
+        decoder.push(packet).inspect_err(|err| {
+            log::error!("{err}, flushed bytes: {}", self.flush_response());
+        })
+    }
+
+    /// Reads and discards packets until the end of the current frame.
+    ///
+    /// Reads at most a [MAX_LEN] frame, so a device that keeps sending full packets can not stall
+    /// the host. Returns the number of discarded bytes.
+    fn flush_response(&self) -> usize {
+        let max_packet_size = self.res_ep.max_packet_size();
+        let mut flushed = 0usize;
+        let mut res_buf = self.buffers.take();
+
+        while flushed < MAX_LEN {
+            let (buf, status) = self.read_packet(res_buf);
+            res_buf = buf;
+            if !matches!(status, Some(Ok(()))) {
+                break;
+            }
+
+            self.counters.record_in(res_buf.len());
+            flushed = flushed.saturating_add(res_buf.len());
+
+            if res_buf.len() < max_packet_size {
+                break;
             }
         }
+        self.buffers.put(res_buf);
+
+        flushed
     }
 
     /// Writes an event response [Message] to the JCM device.
     pub fn write_event_response(&self, message: &Message) -> Result<()> {
-        block_on(
-            self.interface
-                .bulk_out(self.req_ep.address(), message.into())
-                .timeout(time::Duration::from_millis(USB_TIMEOUT)),
-        )
-        .ok_or(Error::Usb("write Event response timeout expired".into()))?
-        .into_result()
-        .map(|_| ())
-        .map_err(|err| {
-            let err_msg =
-                format!(r#"error writing message: {{"message": {message}, "error": {err}}}"#);
-            log::warn!("{err_msg}");
-            Error::Usb(err_msg)
-        })
+        self.write_message(message, "Event response")
+    }
+
+    fn write_message(&self, message: &Message, kind: &str) -> Result<()> {
+        let mut buf = self.buffers.take();
+        buf.resize(message.len(), 0);
+        message.to_bytes(&mut buf)?;
+
+        self.write_bytes(buf, Some(message), kind)
+    }
+
+    fn write_bytes(&self, buf: Vec<u8>, message: Option<&Message>, kind: &str) -> Result<()> {
+        let len = buf.len();
+        let record = self.tracer.as_ref().map(|_| {
+            TraceRecord::create(
+                time::SystemTime::now(),
+                MessageDirection::Sent,
+                buf.clone(),
+                message.cloned(),
+            )
+        });
+
+        // a timed out transfer is cancelled, and awaited, to return its buffer to the pool
+        let mut queue = self.interface.bulk_out_queue(self.req_ep.address());
+        queue.submit(buf);
+
+        let (completion, timed_out) = match block_on(queue.next_complete().timeout(USB_TIMEOUT)) {
+            Some(completion) => (completion, false),
+            None => {
+                queue.cancel_all();
+                (block_on(queue.next_complete()), true)
+            }
+        };
+        self.buffers.put(completion.data.reuse());
+
+        // the transfer may complete before it is cancelled
+        if timed_out && completion.status.is_err() {
+            self.counters.record_timeout();
+            return Err(TransportError::new(
+                TransportErrorKind::Timeout,
+                &format!("write {kind} timeout expired"),
+            )
+            .into());
+        }
+
+        completion
+            .status
+            .map(|_| {
+                self.counters.record_out(len);
+                if let (Some(tracer), Some(record)) = (self.tracer.as_ref(), record.as_ref()) {
+                    tracer.trace(record);
+                }
+            })
+            .map_err(|err| {
+                self.counters.record_error(&err);
+                let message = message
+                    .map(Message::to_string)
+                    .unwrap_or_else(|| format!(r#""{len} raw bytes""#));
+                let err_msg =
+                    format!(r#"error writing message: {{"message": {message}, "error": {err}}}"#);
+                log::warn!("{err_msg}");
+                Error::from(TransportError::new(err.into(), &err_msg).with_source(err))
+            })
     }
 
     fn setup_device(device: &nusb::Device) -> Result<()> {
@@ -224,10 +575,12 @@ impl UsbDeviceHandle {
                     index: 0x0,
                     data: &[],
                 })
-                .timeout(time::Duration::from_millis(USB_TIMEOUT)),
+                .timeout(USB_TIMEOUT),
         )
         .map(|_| ())
-        .ok_or(Error::Usb("device setup timeout expired".into()))
+        .ok_or(
+            TransportError::new(TransportErrorKind::Timeout, "device setup timeout expired").into(),
+        )
     }
 
     fn find_interface(device: &nusb::Device) -> Result<(nusb::Interface, Endpoint, Endpoint)> {
@@ -270,23 +623,66 @@ impl UsbDeviceHandle {
         });
 
         let interface = device
-            .claim_interface(iface.ok_or(Error::Usb("unable to find matching interface".into()))?)
-            .map_err(|err| Error::Usb(format!("unable to open main interface: {err}")))?;
+            .claim_interface(iface.ok_or_else(|| {
+                transport_error(
+                    TransportErrorKind::Other,
+                    "unable to find matching interface",
+                )
+            })?)
+            .map_err(|err| io_error("unable to open main interface", err))?;
 
         match (req_ep, res_ep) {
             (Some(req_ep), Some(res_ep)) => Ok((interface, req_ep, res_ep)),
-            (None, _) => Err(Error::Usb(
-                "unable to find matching request endpoint".into(),
+            (None, _) => Err(transport_error(
+                TransportErrorKind::Other,
+                "unable to find matching request endpoint",
             )),
-            (_, None) => Err(Error::Usb(
-                "unable to find matching response endpoint".into(),
+            (_, None) => Err(transport_error(
+                TransportErrorKind::Other,
+                "unable to find matching response endpoint",
             )),
         }
     }
 }
 
+impl DeviceTransport for UsbDeviceHandle {
+    fn write_message(&self, message: &Message) -> Result<()> {
+        UsbDeviceHandle::write_request(self, message)
+    }
+
+    fn read_message(&self) -> Result<Message> {
+        UsbDeviceHandle::read_response(self)
+    }
+
+    fn write_event_response(&self, message: &Message) -> Result<()> {
+        UsbDeviceHandle::write_event_response(self, message)
+    }
+
+    fn read_frame(&self) -> Result<DeviceFrame> {
+        UsbDeviceHandle::read_frame(self, "Response")
+    }
+
+    fn write_frame(&self, frame: &[u8]) -> Result<()> {
+        let mut buf = self.buffers.take();
+        buf.extend_from_slice(frame);
+
+        self.write_bytes(buf, None, "Event response")
+    }
+
+    fn timeout(&self) -> time::Duration {
+        USB_TIMEOUT
+    }
+}
+
 /// Polls for device-sent [Message]s.
 ///
+/// The device is read every [POLL_INTERVAL], or every [DeviceTransport::timeout] if shorter, so
+/// fast transports are not slowed down to the USB pace.
+///
+/// The poller and the request functions contend on the shared `Mutex` around the device. A
+/// [DeviceIo] owns the device instead, and its [DeviceIoHandle] provides the same request
+/// helpers.
+///
 /// # Example
 ///
 /// ```no_run
@@ -313,130 +709,547 @@ impl UsbDeviceHandle {
 /// # Ok(())
 /// # }
 /// ```
-pub fn poll_device_message(
-    usb_handle: Arc<Mutex<UsbDeviceHandle>>,
+#[deprecated(note = "use `DeviceIo`, which owns the device on a dedicated I/O thread")]
+pub fn poll_device_message<T: DeviceTransport>(
+    usb_handle: Arc<Mutex<T>>,
     stop: Arc<AtomicBool>,
     event_send: crossbeam::channel::Sender<Message>,
     event_res_rcv: crossbeam::channel::Receiver<Message>,
     response_send: crossbeam::channel::Sender<Message>,
 ) -> Result<()> {
-    thread::spawn(move || -> Result<()> {
-        while !stop.load(Ordering::Relaxed) {
-            match usb_handle.lock() {
-                Ok(usb) => match usb.read_response() {
-                    Ok(msg) if msg.data().message_type().is_event() => {
-                        event_send
-                            .send(msg)
-                            .map_err(|err| Error::Usb(format!("error sending event: {err}")))?;
+    spawn_device_poller(
+        usb_handle,
+        stop,
+        event_send,
+        event_res_rcv,
+        ResponseRoute::Channel(response_send),
+        EventDrain::Single,
+        SystemClock::new(),
+        None,
+    );
 
-                        let res = event_res_rcv.recv().map_err(|err| {
-                            Error::Usb(format!("error receiving event response: {err}"))
-                        })?;
+    Ok(())
+}
 
-                        usb.write_event_response(&res)?;
-                    }
-                    Ok(msg) => response_send
-                        .send(msg)
-                        .map_err(|err| Error::Usb(format!("error sending response: {err}")))?,
-                    Err(err) => log::trace!("No device-sent message available: {err}"),
-                },
-                Err(err) => log::warn!("unable to lock USB: {err}"),
-            }
+/// Polls for device-sent [Message]s, using the provided [Clock] for poll intervals.
+///
+/// See [poll_device_message] for usage.
+#[deprecated(note = "use `DeviceIo`, which owns the device on a dedicated I/O thread")]
+pub fn poll_device_message_with_clock<T: DeviceTransport, C: Clock + 'static>(
+    usb_handle: Arc<Mutex<T>>,
+    stop: Arc<AtomicBool>,
+    event_send: crossbeam::channel::Sender<Message>,
+    event_res_rcv: crossbeam::channel::Receiver<Message>,
+    response_send: crossbeam::channel::Sender<Message>,
+    clock: C,
+) -> Result<()> {
+    spawn_device_poller(
+        usb_handle,
+        stop,
+        event_send,
+        event_res_rcv,
+        ResponseRoute::Channel(response_send),
+        EventDrain::Single,
+        clock,
+        None,
+    );
 
-            thread::sleep(time::Duration::from_millis(100));
-        }
+    Ok(())
+}
 
-        Ok(())
-    });
+/// Polls for device-sent [Message]s, acknowledging events according to the [EventDrain] mode.
+///
+/// See [poll_device_message] for usage.
+#[deprecated(note = "use `DeviceIo`, which owns the device on a dedicated I/O thread")]
+pub fn poll_device_message_with_drain<T: DeviceTransport, C: Clock + 'static>(
+    usb_handle: Arc<Mutex<T>>,
+    stop: Arc<AtomicBool>,
+    event_send: crossbeam::channel::Sender<Message>,
+    event_res_rcv: crossbeam::channel::Receiver<Message>,
+    response_send: crossbeam::channel::Sender<Message>,
+    drain: EventDrain,
+    clock: C,
+) -> Result<()> {
+    spawn_device_poller(
+        usb_handle,
+        stop,
+        event_send,
+        event_res_rcv,
+        ResponseRoute::Channel(response_send),
+        drain,
+        clock,
+        None,
+    );
 
     Ok(())
 }
 
-/// Waits for the device to finish sending `Power Up` events at startup.
+/// Polls for device-sent [Message]s, decoding vendor-specific events with the [VendorRegistry].
+///
+/// Frames with an event code registered in the [VendorRegistry] are delivered as a
+/// [TypedEvent::Vendor] on the vendor event channel. The host has no [Message] to respond to a
+/// vendor code with, so the poller acknowledges vendor events itself. Other events and responses
+/// are handled like [poll_device_message].
+///
+/// See [poll_device_message] for usage.
+#[deprecated(note = "use `DeviceIo`, which owns the device on a dedicated I/O thread")]
+#[allow(clippy::too_many_arguments)]
+pub fn poll_device_message_with_vendor<T: DeviceTransport, C: Clock + 'static>(
+    usb_handle: Arc<Mutex<T>>,
+    stop: Arc<AtomicBool>,
+    event_send: crossbeam::channel::Sender<Message>,
+    event_res_rcv: crossbeam::channel::Receiver<Message>,
+    response_send: crossbeam::channel::Sender<Message>,
+    registry: Arc<VendorRegistry>,
+    vendor_send: crossbeam::channel::Sender<TypedEvent>,
+    clock: C,
+) -> Result<()> {
+    spawn_device_poller(
+        usb_handle,
+        stop,
+        event_send,
+        event_res_rcv,
+        ResponseRoute::Channel(response_send),
+        EventDrain::Single,
+        clock,
+        Some(VendorRoute {
+            registry,
+            send: vendor_send,
+        }),
+    );
+
+    Ok(())
+}
+
+/// Polls for device-sent [Message]s, delivering each response to its outstanding request in the
+/// [PendingRequests] table.
+///
+/// Use with [poll_request_correlated], so concurrent requests with different codes are not
+/// serialized, and never receive each other's responses. Responses matching no outstanding
+/// request are logged, and dropped.
 ///
 /// # Example
 ///
 /// ```no_run
 /// use std::sync::{Arc, Mutex};
-/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use std::sync::atomic::AtomicBool;
 ///
 /// # pub fn main() -> jcm::Result<()> {
 /// let usb = Arc::new(Mutex::new(jcm::usb::UsbDeviceHandle::find_usb()?));
 /// let stop = Arc::new(AtomicBool::new(false));
+/// let pending = jcm::usb::PendingRequests::new();
 ///
 /// let (event_send, event_recv) = crossbeam::channel::unbounded();
-/// let (response_send, response_recv) = crossbeam::channel::unbounded();
 /// let (event_res_send, event_res_recv) = crossbeam::channel::unbounded();
 ///
-/// jcm::usb::poll_device_message(
+/// jcm::usb::poll_device_message_correlated(
 ///     Arc::clone(&usb),
 ///     Arc::clone(&stop),
 ///     event_send,
 ///     event_res_recv,
-///     response_send,
+///     pending.clone(),
 /// )?;
 ///
-/// jcm::usb::wait_for_power_up(&event_recv, &event_res_send)?;
-///
+/// let req = jcm::Message::from(jcm::StatusRequest::new());
+/// let res = jcm::usb::poll_request_correlated(Arc::clone(&usb), &req, &pending, 3)?;
 /// # Ok(())
 /// # }
 /// ```
-pub fn wait_for_power_up(
-    event_recv: &crossbeam::channel::Receiver<Message>,
-    event_res_send: &crossbeam::channel::Sender<Message>,
+#[deprecated(note = "use `DeviceIo`, which owns the device on a dedicated I/O thread")]
+pub fn poll_device_message_correlated<T: DeviceTransport>(
+    usb_handle: Arc<Mutex<T>>,
+    stop: Arc<AtomicBool>,
+    event_send: crossbeam::channel::Sender<Message>,
+    event_res_rcv: crossbeam::channel::Receiver<Message>,
+    pending: PendingRequests,
 ) -> Result<()> {
-    let mut powerup = false;
-    let mut powerup_count = 0;
-
-    let now = time::Instant::now();
+    spawn_device_poller(
+        usb_handle,
+        stop,
+        event_send,
+        event_res_rcv,
+        ResponseRoute::Pending(pending),
+        EventDrain::Single,
+        SystemClock::new(),
+        None,
+    );
 
-    while now.elapsed() <= time::Duration::from_secs(3) && !powerup {
-        match event_recv.recv_timeout(time::Duration::from_secs(1)) {
-            Ok(evt) if evt.data().message_code().is_power_up_event() => {
-                powerup_count += 1;
+    Ok(())
+}
 
-                log::info!("receive Power Up event: {evt}");
+/// Represents where the device poller sends responses.
+enum ResponseRoute {
+    Channel(crossbeam::channel::Sender<Message>),
+    Pending(PendingRequests),
+}
 
-                event_res_send
-                    .send(
-                        Message::new().with_data(
-                            evt.data()
-                                .clone()
-                                .with_additional(&[ResponseCode::Ack.into()]),
-                        ),
-                    )
-                    .unwrap();
+impl ResponseRoute {
+    fn send(&self, msg: Message) -> Result<()> {
+        match self {
+            Self::Channel(send) => send
+                .send(msg)
+                .map_err(|err| send_error("error sending response", err)),
+            Self::Pending(pending) => {
+                if let Some(res) = pending.complete(msg) {
+                    log::debug!("unsolicited response: {}", redact(&res));
+                }
+                Ok(())
             }
-            Ok(evt) => {
-                log::debug!("received unexpected event: {evt}");
-
-                event_res_send
-                    .send(
-                        Message::new().with_data(
-                            evt.data()
-                                .clone()
-                                .with_additional(&[ResponseCode::Ack.into()]),
-                        ),
-                    )
-                    .unwrap();
+        }
+    }
+}
 
-                powerup = true;
+/// Represents how the device poller acknowledges queued events.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum EventDrain {
+    /// Read one event, wait for its response, and write it before the next poll.
+    #[default]
+    Single,
+    /// Read every pending event (up to [MAX_EVENT_BATCH]), then write their responses in order,
+    /// back-to-back.
+    ///
+    /// Speeds up recovery when many events queue up, e.g. after a host stall. Events the device
+    /// resent while waiting for an acknowledgement are suppressed, so the application sees each
+    /// event once.
+    Batched,
+}
+
+/// Maximum number of events read in one [EventDrain::Batched] drain.
+pub const MAX_EVENT_BATCH: usize = 16;
+
+#[allow(clippy::too_many_arguments)]
+fn spawn_device_poller<T: DeviceTransport, C: Clock + 'static>(
+    usb_handle: Arc<Mutex<T>>,
+    stop: Arc<AtomicBool>,
+    event_send: crossbeam::channel::Sender<Message>,
+    event_res_rcv: crossbeam::channel::Receiver<Message>,
+    response_send: ResponseRoute,
+    drain: EventDrain,
+    clock: C,
+    vendor: Option<VendorRoute>,
+) -> thread::JoinHandle<Result<()>> {
+    thread::spawn(move || -> Result<()> {
+        #[cfg(debug_assertions)]
+        let mut timing = crate::TimingMonitor::new(&clock);
+
+        let interval = match usb_handle.lock() {
+            Ok(usb) => POLL_INTERVAL.min(usb.timeout()),
+            Err(_) => POLL_INTERVAL,
+        };
+
+        // events passed to the host, awaiting their responses in order
+        let mut pending = VecDeque::new();
+
+        while !stop.load(Ordering::Relaxed) {
+            // responses the host sent since the last read, e.g. a confirmed `Vend Valid` ACK
+            let _written = write_event_responses(
+                &usb_handle,
+                &event_res_rcv,
+                &mut pending,
+                time::Duration::ZERO,
+            )?;
+            #[cfg(debug_assertions)]
+            for res in &_written {
+                timing.on_event_response(res);
             }
-            Err(err) => {
-                log::info!("end of events: {err}");
-                powerup = true;
+
+            let read = match usb_handle.lock() {
+                Ok(usb) => usb.read_frame(),
+                Err(err) => {
+                    log::warn!("unable to lock USB: {err}");
+                    clock.sleep(interval);
+                    continue;
+                }
+            };
+
+            match read {
+                // the device resends events until the host responds, e.g. a withheld `Vend Valid`
+                Ok(DeviceFrame::Message(msg))
+                    if pending.iter().any(|evt: &Message| evt.data() == msg.data()) =>
+                {
+                    log::trace!("suppressing resent event: {}", redact(&msg));
+                }
+                Ok(DeviceFrame::Message(msg)) if msg.data().message_type().is_event() => {
+                    let events = match drain {
+                        EventDrain::Batched => {
+                            let mut undecoded = Vec::new();
+                            let events =
+                                drain_events(&usb_handle, msg, &response_send, &mut undecoded)?;
+
+                            for frame in undecoded {
+                                route_undecoded(frame, vendor.as_ref(), |ack| {
+                                    usb_handle.lock().map_err(lock_error)?.write_frame(ack)
+                                })?;
+                            }
+
+                            events
+                        }
+                        EventDrain::Single => vec![msg],
+                    };
+
+                    for evt in events {
+                        #[cfg(debug_assertions)]
+                        timing.on_event(&evt);
+
+                        event_send
+                            .send(evt.clone())
+                            .map_err(|err| send_error("error sending event", err))?;
+                        pending.push_back(evt);
+                    }
+
+                    // the host usually responds right away, otherwise polling goes on without
+                    // the response, so requests still reach the device while the host decides
+                    let _written =
+                        write_event_responses(&usb_handle, &event_res_rcv, &mut pending, interval)?;
+                    #[cfg(debug_assertions)]
+                    for res in &_written {
+                        timing.on_event_response(res);
+                    }
+                }
+                Ok(DeviceFrame::Message(msg)) => response_send.send(msg)?,
+                Ok(DeviceFrame::Undecoded(frame)) => {
+                    route_undecoded(frame, vendor.as_ref(), |ack| {
+                        usb_handle.lock().map_err(lock_error)?.write_frame(ack)
+                    })?;
+                }
+                Err(err) => log::trace!("No device-sent message available: {err}"),
             }
+
+            clock.sleep(interval);
         }
-    }
 
-    if powerup_count == 0 {
-        Err(Error::Usb("no `Power Up` event before timeout".into()))
-    } else {
         Ok(())
-    }
+    })
 }
 
-/// Polls a request [Message] from the host to the device.
+/// Reads every pending event after the first, so their responses are written back-to-back.
+///
+/// Responses read during the drain are forwarded as usual.
+fn drain_events<T: DeviceTransport>(
+    usb_handle: &Mutex<T>,
+    first: Message,
+    response_send: &ResponseRoute,
+    undecoded: &mut Vec<(Vec<u8>, Error)>,
+) -> Result<Vec<Message>> {
+    let mut events = vec![first];
+    let mut suppressed = 0usize;
+
+    let usb = usb_handle.lock().map_err(lock_error)?;
+    while events.len() < MAX_EVENT_BATCH {
+        match usb.read_frame() {
+            Ok(DeviceFrame::Undecoded(frame)) => undecoded.push(frame),
+            Ok(DeviceFrame::Message(msg)) if msg.data().message_type().is_event() => {
+                if events.iter().any(|evt| evt.data() == msg.data()) {
+                    log::trace!("suppressing resent event: {}", redact(&msg));
+                    suppressed += 1;
+                } else {
+                    events.push(msg);
+                }
+            }
+            Ok(DeviceFrame::Message(msg)) => response_send.send(msg)?,
+            Err(_) => break,
+        }
+    }
+
+    log::debug!(
+        "draining {} events, suppressed resends: {suppressed}",
+        events.len()
+    );
+
+    Ok(events)
+}
+
+/// Represents where the device poller sends vendor-specific events.
+struct VendorRoute {
+    registry: Arc<VendorRegistry>,
+    send: crossbeam::channel::Sender<TypedEvent>,
+}
+
+// delivers a frame the transport could not decode as a vendor-specific event, if registered
+//
+// the host has no [Message] to respond with for vendor codes, so the poller writes the `ACK`
+fn route_undecoded<F: FnOnce(&[u8]) -> Result<()>>(
+    (frame, err): (Vec<u8>, Error),
+    vendor: Option<&VendorRoute>,
+    write_ack: F,
+) -> Result<()> {
+    let event = vendor.map(|vendor| (vendor, vendor.registry.decode(frame.as_slice())));
+
+    match (event, crate::vendor_event_ack(frame.as_slice())) {
+        (Some((vendor, Ok(event @ TypedEvent::Vendor(_)))), Some(ack)) => {
+            log::debug!("vendor event: {event:?}");
+
+            vendor
+                .send
+                .send(event)
+                .map_err(|err| send_error("error sending vendor event", err))?;
+
+            write_ack(&ack)
+        }
+        _ => {
+            log::trace!("No device-sent message available: {err}");
+            Ok(())
+        }
+    }
+}
+
+// collects the host responses to pending events for up to `wait`, then writes them back-to-back
+//
+// the device lock is only held for the writes, never while waiting on the host
+fn write_event_responses<T: DeviceTransport>(
+    usb_handle: &Mutex<T>,
+    event_res_rcv: &crossbeam::channel::Receiver<Message>,
+    pending: &mut VecDeque<Message>,
+    wait: time::Duration,
+) -> Result<Vec<Message>> {
+    use crossbeam::channel::RecvTimeoutError;
+
+    let deadline = time::Instant::now() + wait;
+    let mut responses = Vec::new();
+
+    while !pending.is_empty() {
+        let remaining = deadline.saturating_duration_since(time::Instant::now());
+
+        match event_res_rcv.recv_timeout(remaining) {
+            Ok(res) => {
+                pending.pop_front();
+                responses.push(res);
+            }
+            Err(RecvTimeoutError::Timeout) => break,
+            Err(err) => return Err(recv_error("error receiving event response", err)),
+        }
+    }
+
+    if !responses.is_empty() {
+        let usb = usb_handle.lock().map_err(lock_error)?;
+        responses
+            .iter()
+            .try_for_each(|res| usb.write_event_response(res))?;
+    }
+
+    Ok(responses)
+}
+
+/// Waits for the device to finish sending `Power Up` events at startup.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::sync::{Arc, Mutex};
+/// use std::sync::atomic::{AtomicBool, Ordering};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// let usb = Arc::new(Mutex::new(jcm::usb::UsbDeviceHandle::find_usb()?));
+/// let stop = Arc::new(AtomicBool::new(false));
+///
+/// let (event_send, event_recv) = crossbeam::channel::unbounded();
+/// let (response_send, response_recv) = crossbeam::channel::unbounded();
+/// let (event_res_send, event_res_recv) = crossbeam::channel::unbounded();
+///
+/// jcm::usb::poll_device_message(
+///     Arc::clone(&usb),
+///     Arc::clone(&stop),
+///     event_send,
+///     event_res_recv,
+///     response_send,
+/// )?;
+///
+/// jcm::usb::wait_for_power_up(&event_recv, &event_res_send)?;
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn wait_for_power_up(
+    event_recv: &crossbeam::channel::Receiver<Message>,
+    event_res_send: &crossbeam::channel::Sender<Message>,
+) -> Result<()> {
+    wait_for_power_up_with_clock(event_recv, event_res_send, &SystemClock::new())
+}
+
+/// Waits for the device to finish sending `Power Up` events at startup, using the provided [Clock]
+/// for timeouts.
+///
+/// See [wait_for_power_up] for usage.
+pub fn wait_for_power_up_with_clock<C: Clock + ?Sized>(
+    event_recv: &crossbeam::channel::Receiver<Message>,
+    event_res_send: &crossbeam::channel::Sender<Message>,
+    clock: &C,
+) -> Result<()> {
+    wait_for_power_up_events(event_recv, event_res_send, POWER_UP_GRACE_PERIOD, clock).map(|_| ())
+}
+
+/// Waits for the device to finish sending `Power Up` events at startup, then handles any note
+/// left in the transport according to the [PowerUpRoutine] policy.
+///
+/// See [wait_for_power_up] and [poll_request] for usage.
+pub fn power_up<T: DeviceTransport>(
+    usb: Arc<Mutex<T>>,
+    event_recv: &crossbeam::channel::Receiver<Message>,
+    event_res_send: &crossbeam::channel::Sender<Message>,
+    response_recv: &crossbeam::channel::Receiver<Message>,
+    retries: usize,
+    routine: &PowerUpRoutine,
+) -> Result<PowerUpReport> {
+    let clock = SystemClock::new();
+    let events =
+        wait_for_power_up_events(event_recv, event_res_send, routine.grace_period(), &clock)?;
+
+    let report = routine.run(&events, |req| {
+        poll_request(Arc::clone(&usb), req, response_recv, retries)
+    })?;
+    log::info!("Power Up report: {report}");
+
+    Ok(report)
+}
+
+fn wait_for_power_up_events<C: Clock + ?Sized>(
+    event_recv: &crossbeam::channel::Receiver<Message>,
+    event_res_send: &crossbeam::channel::Sender<Message>,
+    grace_period: time::Duration,
+    clock: &C,
+) -> Result<Vec<Message>> {
+    let mut powerup = false;
+    let mut events = Vec::new();
+
+    let now = clock.now();
+
+    while clock.elapsed(now) <= grace_period && !powerup {
+        match recv_timeout(event_recv, time::Duration::from_secs(1), clock, None) {
+            Ok(evt) if evt.data().message_code().is_power_up_event() => {
+                log::info!("receive Power Up event: {}", redact(&evt));
+
+                event_res_send.send(event_ack(&evt)).unwrap();
+                events.push(evt);
+            }
+            Ok(evt) => {
+                log::debug!("received unexpected event: {}", redact(&evt));
+
+                event_res_send.send(event_ack(&evt)).unwrap();
+
+                powerup = true;
+            }
+            Err(err) => {
+                log::info!("end of events: {err}");
+                powerup = true;
+            }
+        }
+    }
+
+    if events.is_empty() {
+        Err(transport_error(
+            TransportErrorKind::Timeout,
+            "no `Power Up` event before timeout",
+        ))
+    } else {
+        Ok(events)
+    }
+}
+
+/// Polls a request [Message] from the host to the device.
+///
+/// Each attempt waits [RESPONSE_TIMEOUT] for the response, or the [DeviceTransport::timeout] if
+/// longer, since the response can not arrive before the transport read completes.
 ///
 /// # Example
 ///
@@ -474,42 +1287,1943 @@ pub fn wait_for_power_up(
 /// # Ok(())
 /// # }
 /// ```
-pub fn poll_request(
-    usb: Arc<Mutex<UsbDeviceHandle>>,
+pub fn poll_request<T: DeviceTransport>(
+    usb: Arc<Mutex<T>>,
+    request: &Message,
+    response_recv: &crossbeam::channel::Receiver<Message>,
+    retries: usize,
+) -> Result<Message> {
+    poll_request_with_clock(usb, request, response_recv, retries, &SystemClock::new())
+}
+
+/// Polls a request [Message] from the host to the device, until the [CancelToken] is triggered.
+///
+/// Behaves like [poll_request], but stops waiting for the response, and sleeping between
+/// retries, once the [CancelToken] is triggered, so shutdown paths do not block through every
+/// retry. Returns [Error::Cancelled] when cancelled.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::sync::{Arc, Mutex};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// let usb = Arc::new(Mutex::new(jcm::usb::UsbDeviceHandle::find_usb()?));
+/// let (_response_send, response_recv) = crossbeam::channel::unbounded();
+///
+/// let cancel = jcm::CancelToken::new();
+/// let shutdown = cancel.clone();
+/// std::thread::spawn(move || shutdown.cancel());
+///
+/// let req = jcm::Message::from(jcm::StatusRequest::new());
+/// match jcm::usb::poll_request_cancellable(Arc::clone(&usb), &req, &response_recv, 3, &cancel) {
+///     Err(jcm::Error::Cancelled) => log::info!("request cancelled"),
+///     res => log::info!("status response: {}", res?),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn poll_request_cancellable<T: DeviceTransport>(
+    usb: Arc<Mutex<T>>,
+    request: &Message,
+    response_recv: &crossbeam::channel::Receiver<Message>,
+    retries: usize,
+    cancel: &CancelToken,
+) -> Result<Message> {
+    let config = PollConfig::new()
+        .with_retries(retries)
+        .with_response_timeout(RESPONSE_TIMEOUT)
+        .with_retry_interval(POLL_INTERVAL);
+
+    poll_transport(
+        usb,
+        request,
+        ReplySource::Channel(response_recv),
+        &config,
+        &SystemClock::new(),
+        &UnsolicitedSink::default(),
+        cancel,
+    )
+}
+
+/// Polls a request [Message] from the host to the device, waiting for the response on its own
+/// reply channel in the [PendingRequests] table.
+///
+/// Requires a device poller started with [poll_device_message_correlated]. A request with the
+/// same [CorrelationKey] as an outstanding request waits for the outstanding request to finish,
+/// since their responses would be indistinguishable.
+///
+/// See [poll_device_message_correlated] for usage.
+pub fn poll_request_correlated<T: DeviceTransport>(
+    usb: Arc<Mutex<T>>,
+    request: &Message,
+    pending: &PendingRequests,
+    retries: usize,
+) -> Result<Message> {
+    let config = PollConfig::new()
+        .with_retries(retries)
+        .with_response_timeout(RESPONSE_TIMEOUT)
+        .with_retry_interval(POLL_INTERVAL);
+
+    poll_request_correlated_with_config(
+        usb,
+        request,
+        pending,
+        &config,
+        &SystemClock::new(),
+        &UnsolicitedSink::default(),
+        &CancelToken::new(),
+    )
+}
+
+/// Polls a request [Message] from the host to the device, waiting for the response on its own
+/// reply channel in the [PendingRequests] table, using the settings of the [PollConfig].
+///
+/// Behaves like [poll_request_correlated], with the [Clock], [PollObserver], and [CancelToken]
+/// handling of [poll_request_observed] and [poll_request_cancellable].
+///
+/// See [poll_device_message_correlated] for usage.
+pub fn poll_request_correlated_with_config<
+    T: DeviceTransport,
+    C: Clock + ?Sized,
+    O: PollObserver + ?Sized,
+>(
+    usb: Arc<Mutex<T>>,
+    request: &Message,
+    pending: &PendingRequests,
+    config: &PollConfig,
+    clock: &C,
+    observer: &O,
+    cancel: &CancelToken,
+) -> Result<Message> {
+    poll_transport(
+        usb,
+        request,
+        ReplySource::Pending(pending),
+        config,
+        clock,
+        observer,
+        cancel,
+    )
+}
+
+/// Polls a [TypedRequest] from the host to the device, parsing the response into the paired
+/// response type.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::sync::{Arc, Mutex};
+/// use std::sync::atomic::AtomicBool;
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// let usb = Arc::new(Mutex::new(jcm::usb::UsbDeviceHandle::find_usb()?));
+/// let stop = Arc::new(AtomicBool::new(false));
+///
+/// let (event_send, event_recv) = crossbeam::channel::unbounded();
+/// let (response_send, response_recv) = crossbeam::channel::unbounded();
+/// let (event_res_send, event_res_recv) = crossbeam::channel::unbounded();
+///
+/// jcm::usb::poll_device_message(
+///     Arc::clone(&usb),
+///     Arc::clone(&stop),
+///     event_send,
+///     event_res_recv,
+///     response_send,
+/// )?;
+///
+/// let res = jcm::usb::poll_request_typed(
+///     Arc::clone(&usb),
+///     jcm::StatusRequest::new(),
+///     &response_recv,
+///     3,
+/// )?;
+/// log::info!("device status: {}", res.status());
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn poll_request_typed<T: DeviceTransport, R: TypedRequest>(
+    usb: Arc<Mutex<T>>,
+    request: R,
+    response_recv: &crossbeam::channel::Receiver<Message>,
+    retries: usize,
+) -> Result<R::Response> {
+    crate::poll_typed(request, |req| {
+        poll_request(Arc::clone(&usb), req, response_recv, retries)
+    })
+}
+
+/// Polls a request [Message] from the host to the device, using the provided [Clock] for
+/// timeouts and retry intervals.
+///
+/// See [poll_request] for usage.
+pub fn poll_request_with_clock<T: DeviceTransport, C: Clock + ?Sized>(
+    usb: Arc<Mutex<T>>,
+    request: &Message,
+    response_recv: &crossbeam::channel::Receiver<Message>,
+    retries: usize,
+    clock: &C,
+) -> Result<Message> {
+    poll_request_observed(
+        usb,
+        request,
+        response_recv,
+        retries,
+        clock,
+        &UnsolicitedSink::default(),
+    )
+}
+
+/// Polls a request [Message] from the host to the device, notifying the [PollObserver] when the
+/// request is sent, a response is received, an attempt is retried, and when polling fails.
+///
+/// Responses that match no outstanding request are passed to
+/// [on_unsolicited](PollObserver::on_unsolicited), and never returned. Use an [UnsolicitedSink]
+/// to log, drop, or deliver them on a dedicated channel.
+///
+/// See [poll_request] for usage.
+pub fn poll_request_observed<T: DeviceTransport, C: Clock + ?Sized, O: PollObserver + ?Sized>(
+    usb: Arc<Mutex<T>>,
     request: &Message,
     response_recv: &crossbeam::channel::Receiver<Message>,
     retries: usize,
+    clock: &C,
+    observer: &O,
+) -> Result<Message> {
+    let config = PollConfig::new()
+        .with_retries(retries)
+        .with_response_timeout(RESPONSE_TIMEOUT)
+        .with_retry_interval(POLL_INTERVAL);
+
+    poll_transport(
+        usb,
+        request,
+        ReplySource::Channel(response_recv),
+        &config,
+        clock,
+        observer,
+        &CancelToken::new(),
+    )
+}
+
+/// Polls a request [Message] from the host to the device, using the retry and timeout settings
+/// of the [PollConfig].
+///
+/// Take the [PollConfig] from a [LiveConfig](crate::LiveConfig) snapshot to apply runtime
+/// reconfiguration to the next request, without affecting requests already in flight.
+pub fn poll_request_with_config<T: DeviceTransport>(
+    usb: Arc<Mutex<T>>,
+    request: &Message,
+    response_recv: &crossbeam::channel::Receiver<Message>,
+    config: &PollConfig,
+) -> Result<Message> {
+    poll_transport(
+        usb,
+        request,
+        ReplySource::Channel(response_recv),
+        config,
+        &SystemClock::new(),
+        &UnsolicitedSink::default(),
+        &CancelToken::new(),
+    )
+}
+
+// where a polled request waits for its response
+#[derive(Clone, Copy)]
+enum ReplySource<'a> {
+    // the response channel shared by every request to the device
+    Channel(&'a crossbeam::channel::Receiver<Message>),
+    // a reply channel per request, completed by the device poller
+    Pending(&'a PendingRequests),
+}
+
+fn poll_transport<T: DeviceTransport, C: Clock + ?Sized, O: PollObserver + ?Sized>(
+    usb: Arc<Mutex<T>>,
+    request: &Message,
+    replies: ReplySource<'_>,
+    config: &PollConfig,
+    clock: &C,
+    observer: &O,
+    cancel: &CancelToken,
 ) -> Result<Message> {
-    let code = request.data().message_code().request_code()?;
+    let retries = config.retries();
+
+    let code = match request.data().message_code().request_code() {
+        Ok(code) => code,
+        Err(err) => {
+            observer.on_failure(request, &err);
+            return Err(err);
+        }
+    };
+
+    // only one request/response transaction per device at a time, so concurrent callers never
+    // consume each other's responses from the shared channel
+    //
+    // correlated responses only collide with requests of the same key
+    let key = CorrelationKey::from(request);
+    let transaction = match replies {
+        ReplySource::Channel(_) => transaction_lock(&usb),
+        ReplySource::Pending(pending) => pending.key_lock(&key),
+    };
+    let _transaction = transaction.lock().unwrap_or_else(|err| err.into_inner());
+
+    let transport_timeout = match usb.lock() {
+        Ok(usb_lock) => usb_lock.timeout(),
+        Err(err) => {
+            let err = lock_error(err);
+            observer.on_failure(request, &err);
+            return Err(err);
+        }
+    };
+
+    // a response can not arrive before a slow transport completes its read
+    let response_timeout = config.response_timeout().max(transport_timeout);
 
+    let mut last_err = None;
     for retry in 0..retries {
+        if cancel.is_cancelled() {
+            break;
+        }
+
         log::debug!("Sending {code} request, attempt: {retry}...");
 
-        match usb.lock() {
-            Ok(usb_lock) => {
-                if let Err(err) = usb_lock.write_request(request) {
-                    log::warn!("error sending message: {err}");
-                } else {
-                    match response_recv.recv_timeout(time::Duration::from_millis(500)) {
-                        Ok(res) if res.data().message_code().request_code() == Ok(code) => {
-                            return Ok(res)
-                        }
-                        Ok(res) => log::warn!("unexpected response: {res}"),
-                        Err(err) => {
-                            log::warn!("error receiving {code} response: {err}, retry: {retry}")
-                        }
+        let reply;
+        let response_recv = match replies {
+            ReplySource::Channel(response_recv) => {
+                // responses already queued match no outstanding request
+                while let Ok(res) = response_recv.try_recv() {
+                    if is_status_message(&res) {
+                        observer.on_status(&res);
+                    } else {
+                        log::debug!("unsolicited response: {}", redact(&res));
+                        observer.on_unsolicited(&res);
                     }
                 }
+
+                response_recv
             }
+            ReplySource::Pending(pending) => match pending.register(request) {
+                Ok(recv) => {
+                    reply = recv;
+                    &reply
+                }
+                Err(err) => {
+                    observer.on_failure(request, &err);
+                    return Err(err);
+                }
+            },
+        };
+
+        let sent = match usb.lock() {
+            Ok(usb_lock) => usb_lock.write_message(request).inspect_err(|err| {
+                log::warn!("error sending message: {err}");
+            }),
             Err(err) => {
                 log::warn!("error locking USB: {err}");
+                Err(lock_error(err))
+            }
+        };
+
+        // the device lock is released while waiting, so the device poller can read the response
+        let err = match sent {
+            Ok(()) => {
+                observer.on_request_sent(request, retry);
+
+                // device-initiated status messages do not consume the attempt
+                let start = clock.now();
+                let received = loop {
+                    let remaining = response_timeout.saturating_sub(clock.elapsed(start));
+
+                    match recv_timeout(response_recv, remaining, clock, Some(cancel)) {
+                        Ok(res) if code != RequestCode::Status && is_status_message(&res) => {
+                            log::trace!("status message: {}", redact(&res));
+                            observer.on_status(&res);
+                        }
+                        res => break res,
+                    }
+                };
+
+                match received {
+                    Ok(res) if res.data().message_code().request_code() == Ok(code) => {
+                        observer.on_response(request, &res, retry);
+                        return Ok(res);
+                    }
+                    Ok(res) => {
+                        log::debug!("unsolicited response: {}", redact(&res));
+                        observer.on_unsolicited(&res);
+                        let (req, res) = (request.data(), res.data());
+                        Error::InvalidMessage((
+                            (res.message_type().into(), res.message_code().into()),
+                            (req.message_type().into(), req.message_code().into()),
+                        ))
+                    }
+                    Err(err) => {
+                        log::warn!("error receiving {code} response: {err}, retry: {retry}");
+                        recv_error(&format!("error receiving {code} response"), err)
+                    }
+                }
             }
+            Err(err) => err,
+        };
+
+        if let ReplySource::Pending(pending) = replies {
+            pending.cancel(&key);
+        }
+
+        // a retry can not reach a disconnected device
+        let disconnected = matches!(
+            &err,
+            Error::Transport(err) if err.kind() == TransportErrorKind::Disconnected
+        );
+
+        if retry + 1 < retries && !disconnected {
+            observer.on_retry(request, retry, &err);
+        }
+        last_err = Some(err);
+
+        if disconnected || retry + 1 == retries {
+            break;
         }
 
-        thread::sleep(time::Duration::from_millis(100));
+        sleep_cancellable(clock, config.retry_interval(), cancel);
     }
 
-    Err(Error::Usb(format!(
-        "receiving response failed after {retries} retries"
-    )))
+    if cancel.is_cancelled() {
+        log::debug!("{code} request cancelled");
+        observer.on_failure(request, &Error::Cancelled);
+        return Err(Error::Cancelled);
+    }
+
+    let err = retries_exhausted(retries, last_err);
+    observer.on_failure(request, &err);
+
+    Err(err)
+}
+
+/// Sends a keep-alive `Status` request to the device if one is due.
+///
+/// Call periodically from the host event loop, after passing device events to
+/// [KeepAlive::on_event]. Returns `None` if no keep-alive was due.
+pub fn poll_keep_alive<T: DeviceTransport, C: Clock>(
+    keep_alive: &mut KeepAlive<C>,
+    usb: Arc<Mutex<T>>,
+    response_recv: &crossbeam::channel::Receiver<Message>,
+    retries: usize,
+) -> Option<Result<Message>> {
+    keep_alive.tick(|req| poll_request(usb, req, response_recv, retries))
+}
+
+/// Sends a firmware `Program Signature` audit request to the device if one is due.
+///
+/// Call periodically from the host event loop, after passing device events to
+/// [SignatureAudit::on_event], which raises [Error::TamperSuspected] on a hash mismatch. Returns
+/// `None` if no audit was due.
+pub fn poll_signature_audit<T: DeviceTransport, C: Clock>(
+    audit: &mut SignatureAudit<C>,
+    usb: Arc<Mutex<T>>,
+    response_recv: &crossbeam::channel::Receiver<Message>,
+    retries: usize,
+) -> Option<Result<ProgramSignatureResponse>> {
+    audit.tick(|req| poll_request(usb, req, response_recv, retries))
+}
+
+/// Takes a [DebugState] snapshot of the host internals, including the depths of the device message
+/// queues.
+///
+/// Pass the [DebugMonitor] to [poll_request_observed] to track outstanding requests.
+pub fn debug_state(
+    monitor: &DebugMonitor,
+    event_recv: &crossbeam::channel::Receiver<Message>,
+    response_recv: &crossbeam::channel::Receiver<Message>,
+) -> DebugState {
+    monitor.snapshot(&[
+        ("events", event_recv.len()),
+        ("responses", response_recv.len()),
+    ])
+}
+
+/// Responds to a device event [Message] through the [CreditAcknowledger].
+///
+/// The `Vend Valid` acknowledgement is withheld until [confirm_credit] in
+/// [VendValidAckMode::Confirmed](crate::VendValidAckMode::Confirmed). The device poller keeps
+/// serving requests meanwhile, and suppresses the device resends of the event. Returns the new
+/// [Credit] delivered by the event, if any.
+pub fn respond_event<J: CreditJournal>(
+    acker: &mut CreditAcknowledger<J>,
+    event: &Message,
+    event_res_send: &crossbeam::channel::Sender<Message>,
+) -> Result<Option<Credit>> {
+    let ack = acker.on_event(event)?;
+
+    if let Some(res) = ack.response() {
+        send_event_response(event_res_send, res.clone())?;
+    }
+
+    Ok(ack.credit().cloned())
+}
+
+/// Confirms the application durably recorded the [Credit] with the provided ID, releasing the
+/// withheld `Vend Valid` acknowledgement to the device.
+pub fn confirm_credit<J: CreditJournal>(
+    acker: &mut CreditAcknowledger<J>,
+    id: u64,
+    event_res_send: &crossbeam::channel::Sender<Message>,
+) -> Result<()> {
+    match acker.confirm(id)? {
+        Some(res) => send_event_response(event_res_send, res),
+        None => Ok(()),
+    }
+}
+
+fn send_event_response(
+    event_res_send: &crossbeam::channel::Sender<Message>,
+    res: Message,
+) -> Result<()> {
+    event_res_send
+        .send(res)
+        .map_err(|err| send_error("error sending event response", err))
+}
+
+/// Polls the device for the current note image data.
+///
+/// Retrieval stops between blocks when the [CancelToken] is triggered, resetting the device block
+/// sequence and returning [Error::Cancelled].
+///
+/// # Example
+///
+/// ```no_run
+/// use std::sync::{Arc, Mutex};
+/// use std::sync::atomic::AtomicBool;
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// let usb = Arc::new(Mutex::new(jcm::usb::UsbDeviceHandle::find_usb()?));
+/// let stop = Arc::new(AtomicBool::new(false));
+///
+/// let (event_send, event_recv) = crossbeam::channel::unbounded();
+/// let (response_send, response_recv) = crossbeam::channel::unbounded();
+/// let (event_res_send, event_res_recv) = crossbeam::channel::unbounded();
+///
+/// jcm::usb::poll_device_message(
+///     Arc::clone(&usb),
+///     Arc::clone(&stop),
+///     event_send,
+///     event_res_recv,
+///     response_send,
+/// )?;
+///
+/// let cancel = jcm::CancelToken::new();
+/// let _image = jcm::usb::poll_note_image(Arc::clone(&usb), &response_recv, 3, &cancel)?;
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn poll_note_image<T: DeviceTransport>(
+    usb: Arc<Mutex<T>>,
+    response_recv: &crossbeam::channel::Receiver<Message>,
+    retries: usize,
+    cancel: &CancelToken,
+) -> Result<Vec<u8>> {
+    ImageFetcher::new()
+        .with_cancel_token(cancel.clone())
+        .fetch(|req| poll_request(Arc::clone(&usb), req, response_recv, retries))
+}
+
+/// Retrieves the full note image, reporting the [ImageProgress] after each received block.
+///
+/// Queries the image size, requests every image block, and reassembles the blocks into a
+/// [NoteImage]. See [ImageFetcher] for how the block sequence is validated.
+///
+/// Retrieval stops between blocks when the [CancelToken] is triggered, resetting the device block
+/// sequence and returning [Error::Cancelled].
+///
+/// # Example
+///
+/// ```no_run
+/// use std::sync::{Arc, Mutex};
+/// use std::sync::atomic::AtomicBool;
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// let usb = Arc::new(Mutex::new(jcm::usb::UsbDeviceHandle::find_usb()?));
+/// let stop = Arc::new(AtomicBool::new(false));
+///
+/// let (event_send, event_recv) = crossbeam::channel::unbounded();
+/// let (response_send, response_recv) = crossbeam::channel::unbounded();
+/// let (event_res_send, event_res_recv) = crossbeam::channel::unbounded();
+///
+/// jcm::usb::poll_device_message(
+///     Arc::clone(&usb),
+///     Arc::clone(&stop),
+///     event_send,
+///     event_res_recv,
+///     response_send,
+/// )?;
+///
+/// let cancel = jcm::CancelToken::new();
+/// let image = jcm::usb::fetch_note_image(Arc::clone(&usb), &response_recv, 3, &cancel, |p| {
+///     log::info!("received image block: {p}");
+/// })?;
+/// log::info!("note image: {image}");
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn fetch_note_image<T: DeviceTransport, F: FnMut(ImageProgress)>(
+    usb: Arc<Mutex<T>>,
+    response_recv: &crossbeam::channel::Receiver<Message>,
+    retries: usize,
+    cancel: &CancelToken,
+    progress: F,
+) -> Result<NoteImage> {
+    fetch_image(
+        usb,
+        response_recv,
+        retries,
+        ImageKind::Note,
+        cancel,
+        progress,
+    )
+}
+
+/// Retrieves the full serial number image, reporting the [ImageProgress] after each received
+/// block.
+///
+/// See [fetch_note_image] for details.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::sync::{Arc, Mutex};
+/// use std::sync::atomic::AtomicBool;
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// let usb = Arc::new(Mutex::new(jcm::usb::UsbDeviceHandle::find_usb()?));
+/// let stop = Arc::new(AtomicBool::new(false));
+///
+/// let (event_send, event_recv) = crossbeam::channel::unbounded();
+/// let (response_send, response_recv) = crossbeam::channel::unbounded();
+/// let (event_res_send, event_res_recv) = crossbeam::channel::unbounded();
+///
+/// jcm::usb::poll_device_message(
+///     Arc::clone(&usb),
+///     Arc::clone(&stop),
+///     event_send,
+///     event_res_recv,
+///     response_send,
+/// )?;
+///
+/// let cancel = jcm::CancelToken::new();
+/// let image = jcm::usb::fetch_serial_number_image(Arc::clone(&usb), &response_recv, 3, &cancel, |p| {
+///     log::info!("received image block: {p}");
+/// })?;
+/// log::info!("serial number image: {image}");
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn fetch_serial_number_image<T: DeviceTransport, F: FnMut(ImageProgress)>(
+    usb: Arc<Mutex<T>>,
+    response_recv: &crossbeam::channel::Receiver<Message>,
+    retries: usize,
+    cancel: &CancelToken,
+    progress: F,
+) -> Result<NoteImage> {
+    fetch_image(
+        usb,
+        response_recv,
+        retries,
+        ImageKind::SerialNumber,
+        cancel,
+        progress,
+    )
+}
+
+/// Reads the serial number of the note in escrow.
+///
+/// See [read_note_serial](crate::read_note_serial) for details.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::sync::{Arc, Mutex};
+/// use std::sync::atomic::AtomicBool;
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// let usb = Arc::new(Mutex::new(jcm::usb::UsbDeviceHandle::find_usb()?));
+/// let stop = Arc::new(AtomicBool::new(false));
+///
+/// let (event_send, event_recv) = crossbeam::channel::unbounded();
+/// let (response_send, response_recv) = crossbeam::channel::unbounded();
+/// let (event_res_send, event_res_recv) = crossbeam::channel::unbounded();
+///
+/// jcm::usb::poll_device_message(
+///     Arc::clone(&usb),
+///     Arc::clone(&stop),
+///     event_send,
+///     event_res_recv,
+///     response_send,
+/// )?;
+///
+/// let cancel = jcm::CancelToken::new();
+/// let serial = jcm::usb::read_note_serial(Arc::clone(&usb), &response_recv, 3, &cancel)?;
+/// log::info!("note serial number: {}", serial.serial());
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn read_note_serial<T: DeviceTransport>(
+    usb: Arc<Mutex<T>>,
+    response_recv: &crossbeam::channel::Receiver<Message>,
+    retries: usize,
+    cancel: &CancelToken,
+) -> Result<NoteSerialNumber> {
+    crate::read_note_serial(cancel, |req| {
+        poll_request(Arc::clone(&usb), req, response_recv, retries)
+    })
+}
+
+fn fetch_image<T: DeviceTransport, F: FnMut(ImageProgress)>(
+    usb: Arc<Mutex<T>>,
+    response_recv: &crossbeam::channel::Receiver<Message>,
+    retries: usize,
+    kind: ImageKind,
+    cancel: &CancelToken,
+    progress: F,
+) -> Result<NoteImage> {
+    ImageFetcher::new()
+        .with_kind(kind)
+        .with_cancel_token(cancel.clone())
+        .fetch_image(
+            |req| poll_request(Arc::clone(&usb), req, response_recv, retries),
+            progress,
+        )
+}
+
+/// Modifies the `Direction Disable` settings of the device, verifying the device applied them.
+///
+/// See [modify_direction_disable](crate::modify_direction_disable) for details.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::sync::{Arc, Mutex};
+/// use std::sync::atomic::AtomicBool;
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// let usb = Arc::new(Mutex::new(jcm::usb::UsbDeviceHandle::find_usb()?));
+/// let stop = Arc::new(AtomicBool::new(false));
+///
+/// let (event_send, event_recv) = crossbeam::channel::unbounded();
+/// let (response_send, response_recv) = crossbeam::channel::unbounded();
+/// let (event_res_send, event_res_recv) = crossbeam::channel::unbounded();
+///
+/// jcm::usb::poll_device_message(
+///     Arc::clone(&usb),
+///     Arc::clone(&stop),
+///     event_send,
+///     event_res_recv,
+///     response_send,
+/// )?;
+///
+/// let delta = jcm::usb::modify_direction_disable(Arc::clone(&usb), &response_recv, 3, 1, |dirs| {
+///     dirs.set_face_up_left_side(jcm::DirectionInhibit::Inhibit);
+/// })?;
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn modify_direction_disable<T: DeviceTransport, F: FnOnce(&mut InhibitDirection)>(
+    usb: Arc<Mutex<T>>,
+    response_recv: &crossbeam::channel::Receiver<Message>,
+    retries: usize,
+    uid: u8,
+    modify: F,
+) -> Result<DirectionDisableDelta> {
+    crate::modify_direction_disable(
+        uid,
+        |req| poll_request(Arc::clone(&usb), req, response_recv, retries),
+        modify,
+    )
+}
+
+/// Sets the [StatusMessageMode] of the device.
+///
+/// In [StatusMessageMode::Unsolicited], the device pushes periodic `Status` messages. The polling
+/// functions pass them to [on_status](PollObserver::on_status), instead of treating them as
+/// unexpected responses. Pass a [DebugMonitor], or a [StateTracker](crate::StateTracker) behind a
+/// [Mutex], as the observer to track the device state from them.
+pub fn set_status_message_mode<T: DeviceTransport>(
+    usb: Arc<Mutex<T>>,
+    response_recv: &crossbeam::channel::Receiver<Message>,
+    retries: usize,
+    uid: u8,
+    mode: StatusMessageMode,
+) -> Result<()> {
+    crate::set_status_message_mode(uid, mode, |req| {
+        poll_request(Arc::clone(&usb), req, response_recv, retries)
+    })
+}
+
+/// Inhibits the whole device with an `Inhibit` request.
+///
+/// The device refuses notes until enabled again with [enable].
+pub fn inhibit<T: DeviceTransport>(
+    usb: Arc<Mutex<T>>,
+    response_recv: &crossbeam::channel::Receiver<Message>,
+    retries: usize,
+    uid: u8,
+) -> Result<()> {
+    crate::set_device_inhibit(uid, DeviceInhibit::Inhibited, |req| {
+        poll_request(Arc::clone(&usb), req, response_recv, retries)
+    })
+}
+
+/// Enables the whole device with an `Idle` request.
+pub fn enable<T: DeviceTransport>(
+    usb: Arc<Mutex<T>>,
+    response_recv: &crossbeam::channel::Receiver<Message>,
+    retries: usize,
+    uid: u8,
+) -> Result<()> {
+    crate::set_device_inhibit(uid, DeviceInhibit::Enabled, |req| {
+        poll_request(Arc::clone(&usb), req, response_recv, retries)
+    })
+}
+
+/// Gets whether the whole device is inhibited, from a `Status` request.
+pub fn device_inhibit<T: DeviceTransport>(
+    usb: Arc<Mutex<T>>,
+    response_recv: &crossbeam::channel::Receiver<Message>,
+    retries: usize,
+    uid: u8,
+) -> Result<DeviceInhibit> {
+    crate::get_device_inhibit(uid, |req| {
+        poll_request(Arc::clone(&usb), req, response_recv, retries)
+    })
+}
+
+/// Exports a [SupportBundle](crate::SupportBundle) archive to the file at `path`.
+///
+/// Queries the [DeviceInfo](crate::DeviceInfo) of the device, and adds it to the `bundle` with the
+/// provided `serial` number.
+pub fn export_support_bundle<T: DeviceTransport, P: AsRef<std::path::Path>>(
+    usb: Arc<Mutex<T>>,
+    response_recv: &crossbeam::channel::Receiver<Message>,
+    retries: usize,
+    serial: &str,
+    bundle: crate::SupportBundle,
+    path: P,
+) -> Result<()> {
+    let info = crate::DeviceInfo::query(serial, |req| {
+        poll_request(Arc::clone(&usb), req, response_recv, retries)
+    })?;
+
+    bundle.with_device_info(info).export(path)
+}
+
+/// Queries the [Capabilities] of the device with the provided `serial` number.
+///
+/// Store the result with [UsbDeviceHandle::set_capabilities], so the rest of the application can
+/// branch on the supported features.
+pub fn query_capabilities<T: DeviceTransport>(
+    usb: Arc<Mutex<T>>,
+    response_recv: &crossbeam::channel::Receiver<Message>,
+    retries: usize,
+    serial: &str,
+) -> Result<Capabilities> {
+    Capabilities::query(serial, |req| {
+        poll_request(Arc::clone(&usb), req, response_recv, retries)
+    })
+}
+
+/// Discovers the UID of the device, assigning a free UID with the [UidManager] if needed.
+///
+/// Use one [UidManager] for all devices on the host, keyed e.g. by the USB serial number.
+pub fn negotiate_uid<T: DeviceTransport>(
+    usb: Arc<Mutex<T>>,
+    response_recv: &crossbeam::channel::Receiver<Message>,
+    retries: usize,
+    manager: &UidManager,
+    device: &str,
+) -> Result<u8> {
+    manager.negotiate(device, |req| {
+        poll_request(Arc::clone(&usb), req, response_recv, retries)
+    })
+}
+
+/// Guides the host through a cashbox exchange, returning the [CashboxExchangeReport].
+///
+/// See [CashboxExchange] for the exchange steps.
+pub fn exchange_cashbox<T: DeviceTransport>(
+    usb: Arc<Mutex<T>>,
+    response_recv: &crossbeam::channel::Receiver<Message>,
+    retries: usize,
+    counters: &AuditCounters,
+) -> Result<CashboxExchangeReport> {
+    CashboxExchange::new(&SystemClock::new()).run(counters, |req| {
+        poll_request(Arc::clone(&usb), req, response_recv, retries)
+    })
+}
+
+/// Requests a recycler payout, returning the [DispenseResponse](crate::DispenseResponse).
+///
+/// The payout progress is reported as [DispenseEvent](crate::DispenseEvent)s on the event
+/// channel.
+pub fn dispense<T: DeviceTransport>(
+    usb: Arc<Mutex<T>>,
+    response_recv: &crossbeam::channel::Receiver<Message>,
+    retries: usize,
+    request: &crate::DispenseRequest,
+) -> Result<crate::DispenseResponse> {
+    poll_request(Arc::clone(&usb), &request.into(), response_recv, retries)?.try_into()
+}
+
+/// Collects a note stranded in the device into the cashbox, waiting for the collection event.
+///
+/// See [collect_stranded_note](crate::collect_stranded_note) for the collection sequence.
+pub fn collect_stranded_note<T: DeviceTransport>(
+    usb: Arc<Mutex<T>>,
+    response_recv: &crossbeam::channel::Receiver<Message>,
+    retries: usize,
+    event_recv: &crossbeam::channel::Receiver<Message>,
+    event_res_send: &crossbeam::channel::Sender<Message>,
+    mode: crate::CollectMode,
+) -> Result<crate::CollectionOutcome> {
+    crate::collect_stranded_note(
+        &SystemClock::new(),
+        mode,
+        crate::DEFAULT_COLLECT_TIMEOUT,
+        |req| poll_request(Arc::clone(&usb), req, response_recv, retries),
+        escrow_events(event_recv, event_res_send),
+    )
+}
+
+/// Creates an event function for an [EscrowSession](crate::EscrowSession).
+///
+/// The function receives the next device event within the timeout, and acknowledges it.
+pub fn escrow_events<'a>(
+    event_recv: &'a crossbeam::channel::Receiver<Message>,
+    event_res_send: &'a crossbeam::channel::Sender<Message>,
+) -> impl FnMut(time::Duration) -> Result<Message> + 'a {
+    move |timeout| {
+        let evt = event_recv
+            .recv_timeout(timeout)
+            .map_err(|err| Error::Timeout(format!("error receiving escrow event: {err}")))?;
+        send_event_response(event_res_send, event_ack(&evt))?;
+
+        Ok(evt)
+    }
+}
+
+/// Receives a message from the channel, timing out according to the provided [Clock].
+fn recv_timeout<T, C: Clock + ?Sized>(
+    recv: &crossbeam::channel::Receiver<T>,
+    timeout: time::Duration,
+    clock: &C,
+    cancel: Option<&CancelToken>,
+) -> std::result::Result<T, crossbeam::channel::RecvTimeoutError> {
+    use crossbeam::channel::{RecvTimeoutError, TryRecvError};
+
+    let interval = CLOCK_RECV_INTERVAL;
+    let start = clock.now();
+
+    loop {
+        match recv.try_recv() {
+            Ok(msg) => return Ok(msg),
+            Err(TryRecvError::Disconnected) => return Err(RecvTimeoutError::Disconnected),
+            Err(TryRecvError::Empty) => (),
+        }
+
+        let elapsed = clock.elapsed(start);
+        if elapsed >= timeout || cancel.is_some_and(CancelToken::is_cancelled) {
+            return Err(RecvTimeoutError::Timeout);
+        }
+
+        clock.sleep(interval.min(timeout - elapsed));
+    }
+}
+
+// gets the transaction lock of the shared device, keyed by the address of its `Mutex`
+//
+// the lock outlives no transaction, and every transaction holds a clone of the `Arc`, so the
+// address is never reused while its lock is held
+fn transaction_lock<T>(usb: &Arc<Mutex<T>>) -> Arc<Mutex<()>> {
+    static LOCKS: Mutex<BTreeMap<usize, Weak<Mutex<()>>>> = Mutex::new(BTreeMap::new());
+
+    let key = Arc::as_ptr(usb) as usize;
+    let mut locks = LOCKS.lock().unwrap_or_else(|err| err.into_inner());
+    locks.retain(|_, lock| lock.strong_count() > 0);
+
+    match locks.get(&key).and_then(Weak::upgrade) {
+        Some(lock) => lock,
+        None => {
+            let lock = Arc::new(Mutex::new(()));
+            locks.insert(key, Arc::downgrade(&lock));
+            lock
+        }
+    }
+}
+
+// a disconnected device stays disconnected, any other failure means no response arrived in time
+fn retries_exhausted(retries: usize, last_err: Option<Error>) -> Error {
+    let err_msg = format!("receiving response failed after {retries} retries");
+
+    match last_err {
+        Some(last) => {
+            let kind = match &last {
+                Error::Transport(err) if err.kind() == TransportErrorKind::Disconnected => {
+                    TransportErrorKind::Disconnected
+                }
+                _ => TransportErrorKind::Timeout,
+            };
+            TransportError::new(kind, &err_msg).with_source(last)
+        }
+        None => TransportError::new(TransportErrorKind::Timeout, &err_msg),
+    }
+    .into()
+}
+
+// creates a transport error without a source error
+fn transport_error(kind: TransportErrorKind, msg: &str) -> Error {
+    TransportError::new(kind, msg).into()
+}
+
+// a poisoned lock means another thread panicked mid-transfer, so the state is unknown
+fn lock_error<T>(err: std::sync::PoisonError<T>) -> Error {
+    transport_error(
+        TransportErrorKind::Other,
+        &format!("error locking USB: {err}"),
+    )
+}
+
+fn io_error(msg: &str, err: std::io::Error) -> Error {
+    use std::io::ErrorKind;
+
+    let kind = match err.kind() {
+        ErrorKind::TimedOut | ErrorKind::WouldBlock => TransportErrorKind::Timeout,
+        ErrorKind::NotFound | ErrorKind::NotConnected | ErrorKind::BrokenPipe => {
+            TransportErrorKind::Disconnected
+        }
+        _ => TransportErrorKind::Other,
+    };
+
+    TransportError::new(kind, &format!("{msg}: {err}"))
+        .with_source(err)
+        .into()
+}
+
+// a closed channel means the other end of the transport is gone
+fn send_error<T: Send + Sync + 'static>(msg: &str, err: crossbeam::channel::SendError<T>) -> Error {
+    TransportError::new(TransportErrorKind::Disconnected, &format!("{msg}: {err}"))
+        .with_source(err)
+        .into()
+}
+
+fn recv_error(msg: &str, err: crossbeam::channel::RecvTimeoutError) -> Error {
+    use crossbeam::channel::RecvTimeoutError;
+
+    let kind = match err {
+        RecvTimeoutError::Timeout => TransportErrorKind::Timeout,
+        RecvTimeoutError::Disconnected => TransportErrorKind::Disconnected,
+    };
+
+    TransportError::new(kind, &format!("{msg}: {err}"))
+        .with_source(err)
+        .into()
+}
+
+// sleeps in short intervals, so a cancellation interrupts the sleep
+fn sleep_cancellable<C: Clock + ?Sized>(clock: &C, dur: time::Duration, cancel: &CancelToken) {
+    let start = clock.now();
+
+    loop {
+        let elapsed = clock.elapsed(start);
+        if elapsed >= dur || cancel.is_cancelled() {
+            return;
+        }
+
+        clock.sleep(CLOCK_RECV_INTERVAL.min(dur - elapsed));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_transport::{ack, idle_event, TestTransport};
+    use super::*;
+
+    use crate::{
+        EventCode, EventType, MessageCode, MessageData, MessageType, RequestCode, RequestType,
+        ResponseCode, SimulatedClock,
+    };
+
+    /// Scales real time, so protocol timeouts elapse quickly under real thread contention.
+    struct ScaledClock {
+        start: time::Instant,
+        scale: u32,
+    }
+
+    impl Clock for ScaledClock {
+        fn now(&self) -> time::Duration {
+            self.start.elapsed() * self.scale
+        }
+
+        fn sleep(&self, dur: time::Duration) {
+            thread::sleep(dur / self.scale);
+        }
+    }
+
+    #[test]
+    fn test_poll_request_contention() {
+        const RETRIES: usize = 5;
+        const REQUESTS: usize = 10;
+
+        let clock = Arc::new(ScaledClock {
+            start: time::Instant::now(),
+            scale: 20,
+        });
+        let usb = Arc::new(Mutex::new(TestTransport::new().with_event_every(7)));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let (event_send, event_recv) = crossbeam::channel::unbounded();
+        let (event_res_send, event_res_recv) = crossbeam::channel::unbounded();
+        let (response_send, response_recv) = crossbeam::channel::unbounded();
+
+        let poller = spawn_device_poller(
+            Arc::clone(&usb),
+            Arc::clone(&stop),
+            event_send,
+            event_res_recv,
+            ResponseRoute::Channel(response_send),
+            EventDrain::Single,
+            Arc::clone(&clock),
+            None,
+        );
+
+        let event_stop = Arc::new(AtomicBool::new(false));
+        let events_stop = Arc::clone(&event_stop);
+        let events = thread::spawn(move || {
+            while !events_stop.load(Ordering::Relaxed) {
+                if let Ok(evt) = event_recv.recv_timeout(time::Duration::from_millis(10)) {
+                    event_res_send.send(evt).ok();
+                }
+            }
+        });
+
+        let codes = [
+            RequestCode::Status,
+            RequestCode::Version,
+            RequestCode::ModelName,
+            RequestCode::Uid,
+        ];
+        // worst case: waiting behind every other caller's transaction
+        let max_latency = (RESPONSE_TIMEOUT + POLL_INTERVAL) * (codes.len() * RETRIES) as u32;
+        let (done_send, done_recv) = crossbeam::channel::unbounded();
+
+        for code in codes {
+            let (usb, clock, recv, done) = (
+                Arc::clone(&usb),
+                Arc::clone(&clock),
+                response_recv.clone(),
+                done_send.clone(),
+            );
+
+            thread::spawn(move || {
+                let req = Message::new().with_data(
+                    MessageData::new()
+                        .with_message_type(MessageType::Request(RequestType::Status))
+                        .with_message_code(MessageCode::Request(code)),
+                );
+                let sink = UnsolicitedSink::new(UnsolicitedPolicy::Drop);
+                let config = PollConfig::new()
+                    .with_retries(RETRIES)
+                    .with_response_timeout(RESPONSE_TIMEOUT)
+                    .with_retry_interval(POLL_INTERVAL);
+
+                let res = (0..REQUESTS).try_for_each(|_| -> Result<()> {
+                    let start = clock.now();
+                    let res = poll_transport(
+                        Arc::clone(&usb),
+                        &req,
+                        ReplySource::Channel(&recv),
+                        &config,
+                        &*clock,
+                        &sink,
+                        &CancelToken::new(),
+                    )?;
+
+                    assert_eq!(res.data().message_code(), req.data().message_code());
+                    assert!(clock.elapsed(start) <= max_latency);
+
+                    Ok(())
+                });
+
+                done.send(res).ok();
+            });
+        }
+
+        for _ in codes {
+            let res = done_recv
+                .recv_timeout(time::Duration::from_secs(60))
+                .expect("deadlocked polling requests");
+            assert_eq!(res, Ok(()));
+        }
+
+        stop.store(true, Ordering::SeqCst);
+        assert_eq!(poller.join().unwrap(), Ok(()));
+
+        event_stop.store(true, Ordering::SeqCst);
+        events.join().unwrap();
+
+        assert!(usb.lock().unwrap().event_responses() > 0);
+    }
+
+    #[test]
+    fn test_poll_request_cancellable() {
+        let usb = Arc::new(Mutex::new(TestTransport::new().with_event_every(7)));
+        // no device poller, so every attempt waits the full response timeout
+        let (_response_send, response_recv) = crossbeam::channel::unbounded();
+        let req = Message::from(crate::StatusRequest::new());
+
+        let cancel = CancelToken::new();
+        let shutdown = cancel.clone();
+        let canceller = thread::spawn(move || {
+            thread::sleep(time::Duration::from_millis(50));
+            shutdown.cancel();
+        });
+
+        let start = time::Instant::now();
+        let res = poll_request_cancellable(Arc::clone(&usb), &req, &response_recv, 10, &cancel);
+
+        assert_eq!(res, Err(Error::Cancelled));
+        assert!(start.elapsed() < RESPONSE_TIMEOUT);
+        canceller.join().unwrap();
+
+        assert_eq!(
+            poll_request_cancellable(usb, &req, &response_recv, 10, &cancel),
+            Err(Error::Cancelled)
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_poll_request_correlated() -> Result<()> {
+        let usb = Arc::new(Mutex::new(TestTransport::new().with_event_every(7)));
+        let stop = Arc::new(AtomicBool::new(false));
+        let pending = PendingRequests::new();
+
+        let (event_send, event_recv) = crossbeam::channel::unbounded();
+        let (event_res_send, event_res_recv) = crossbeam::channel::unbounded();
+        thread::spawn(move || {
+            while let Ok(evt) = event_recv.recv() {
+                event_res_send.send(evt).ok();
+            }
+        });
+
+        poll_device_message_correlated(
+            Arc::clone(&usb),
+            Arc::clone(&stop),
+            event_send,
+            event_res_recv,
+            pending.clone(),
+        )?;
+
+        let callers = [RequestCode::Status, RequestCode::Version, RequestCode::Uid].map(|code| {
+            let (usb, pending) = (Arc::clone(&usb), pending.clone());
+            thread::spawn(move || -> Result<()> {
+                let req = Message::new().with_data(
+                    MessageData::new()
+                        .with_message_type(MessageType::Request(RequestType::Status))
+                        .with_message_code(MessageCode::Request(code)),
+                );
+
+                for _ in 0..3 {
+                    let res = poll_request_correlated(Arc::clone(&usb), &req, &pending, 5)?;
+                    assert_eq!(res.data().message_code(), MessageCode::Request(code));
+                }
+                Ok(())
+            })
+        });
+
+        for caller in callers {
+            caller.join().unwrap()?;
+        }
+
+        stop.store(true, Ordering::Relaxed);
+        assert!(pending.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_poll_request_correlated_same_key() -> Result<()> {
+        #[derive(Default)]
+        struct Counter {
+            sent: std::sync::atomic::AtomicUsize,
+            responses: std::sync::atomic::AtomicUsize,
+            failures: std::sync::atomic::AtomicUsize,
+        }
+
+        impl PollObserver for Counter {
+            fn on_request_sent(&self, _request: &Message, _attempt: usize) {
+                self.sent.fetch_add(1, Ordering::SeqCst);
+            }
+
+            fn on_response(&self, _request: &Message, _response: &Message, _attempt: usize) {
+                self.responses.fetch_add(1, Ordering::SeqCst);
+            }
+
+            fn on_failure(&self, _request: &Message, _err: &Error) {
+                self.failures.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let req = Message::from(crate::StatusRequest::new());
+        let config = PollConfig::new()
+            .with_retries(2)
+            .with_response_timeout(RESPONSE_TIMEOUT)
+            .with_retry_interval(POLL_INTERVAL);
+
+        // no device poller: both attempts time out, without a trailing retry interval
+        let transport = TestTransport::new().with_timeout(time::Duration::from_secs(2));
+        let clock = SimulatedClock::new();
+        let counter = Counter::default();
+        let res = poll_request_correlated_with_config(
+            Arc::new(Mutex::new(transport)),
+            &req,
+            &PendingRequests::new(),
+            &config,
+            &clock,
+            &counter,
+            &CancelToken::new(),
+        );
+
+        assert!(matches!(res, Err(Error::Transport(_))));
+        assert_eq!(clock.now(), time::Duration::from_secs(4) + POLL_INTERVAL);
+        assert_eq!(counter.sent.load(Ordering::SeqCst), 2);
+        assert_eq!(counter.failures.load(Ordering::SeqCst), 1);
+
+        // concurrent requests with the same key wait for the one in flight
+        let usb = Arc::new(Mutex::new(
+            TestTransport::new().with_delay(time::Duration::from_millis(20)),
+        ));
+        let stop = Arc::new(AtomicBool::new(false));
+        let pending = PendingRequests::new();
+        let (event_send, _event_recv) = crossbeam::channel::unbounded();
+        let (_event_res_send, event_res_recv) = crossbeam::channel::unbounded();
+
+        poll_device_message_correlated(
+            Arc::clone(&usb),
+            Arc::clone(&stop),
+            event_send,
+            event_res_recv,
+            pending.clone(),
+        )?;
+
+        let counter = Arc::new(Counter::default());
+        let callers: Vec<_> = (0..3)
+            .map(|_| {
+                let (usb, pending, req) = (Arc::clone(&usb), pending.clone(), req.clone());
+                let counter = Arc::clone(&counter);
+                thread::spawn(move || {
+                    poll_request_correlated_with_config(
+                        usb,
+                        &req,
+                        &pending,
+                        &config,
+                        &SystemClock::new(),
+                        &*counter,
+                        &CancelToken::new(),
+                    )
+                })
+            })
+            .collect();
+
+        for caller in callers {
+            let res = caller.join().unwrap()?;
+            assert_eq!(res.data().message_code(), req.data().message_code());
+        }
+
+        stop.store(true, Ordering::SeqCst);
+        assert_eq!(counter.responses.load(Ordering::SeqCst), 3);
+        assert_eq!(counter.failures.load(Ordering::SeqCst), 0);
+        assert!(pending.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_batched_event_drain() {
+        let event = |event_type, code| {
+            Message::new().with_data(
+                MessageData::new()
+                    .with_message_type(MessageType::Event(event_type))
+                    .with_message_code(MessageCode::Event(code)),
+            )
+        };
+        let escrow = event(EventType::Sequence0, EventCode::Escrow);
+        let vend = event(EventType::Sequence1, EventCode::VendValid);
+        let idle = event(EventType::Sequence2, EventCode::Idle);
+
+        let transport = TestTransport::new();
+        // the device resent `Vend Valid` while the host was stalled
+        [&escrow, &vend, &vend, &idle]
+            .into_iter()
+            .for_each(|evt| transport.push(evt.clone()));
+        let usb = Arc::new(Mutex::new(transport));
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let (event_send, event_recv) = crossbeam::channel::unbounded();
+        let (event_res_send, event_res_recv) = crossbeam::channel::unbounded();
+        let (response_send, _response_recv) = crossbeam::channel::unbounded();
+
+        let poller = spawn_device_poller(
+            Arc::clone(&usb),
+            Arc::clone(&stop),
+            event_send,
+            event_res_recv,
+            ResponseRoute::Channel(response_send),
+            EventDrain::Batched,
+            SimulatedClock::new(),
+            None,
+        );
+
+        let mut received = Vec::new();
+        for _ in 0..3 {
+            let evt = event_recv
+                .recv_timeout(time::Duration::from_secs(5))
+                .unwrap();
+            event_res_send.send(evt.clone()).unwrap();
+            received.push(evt);
+        }
+        assert_eq!(received, [escrow, vend, idle]);
+
+        let start = time::Instant::now();
+        while usb.lock().unwrap().ops().len() < 7 && start.elapsed() < time::Duration::from_secs(5)
+        {
+            thread::sleep(time::Duration::from_millis(1));
+        }
+
+        stop.store(true, Ordering::SeqCst);
+        assert!(poller.join().unwrap().is_ok());
+        assert!(event_recv.try_recv().is_err());
+
+        // every event is read before the responses are written, in order
+        assert_eq!(
+            usb.lock().unwrap().ops(),
+            [
+                "read",
+                "read",
+                "read",
+                "read",
+                r#"ack "Escrow""#,
+                r#"ack "VendValid""#,
+                r#"ack "Idle""#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wait_for_power_up_simulated() -> Result<()> {
+        let clock = SimulatedClock::new();
+        let (event_send, event_recv) = crossbeam::channel::unbounded();
+        let (event_res_send, event_res_recv) = crossbeam::channel::unbounded();
+
+        let real = time::Instant::now();
+
+        assert!(wait_for_power_up_with_clock(&event_recv, &event_res_send, &clock).is_err());
+        assert!(clock.now() >= time::Duration::from_secs(1));
+
+        let power_up = Message::new().with_data(
+            MessageData::new()
+                .with_message_type(MessageType::Event(EventType::Sequence0))
+                .with_message_code(MessageCode::Event(EventCode::PowerUp)),
+        );
+        event_send.send(power_up.clone()).unwrap();
+
+        wait_for_power_up_with_clock(&event_recv, &event_res_send, &clock)?;
+
+        let ack = event_res_recv.try_recv().unwrap();
+        assert_eq!(
+            ack.data().additional(),
+            [u8::from(ResponseCode::Ack)].as_ref()
+        );
+
+        assert!(real.elapsed() < time::Duration::from_secs(1));
+
+        Ok(())
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_unsolicited_status_routing() -> Result<()> {
+        // the device pushes a `Status` message ahead of every response
+        let usb = Arc::new(Mutex::new(TestTransport::with_responder(|msg| {
+            let mut status = vec![ResponseCode::Ack.into(), crate::DeviceStatus::len() as u8];
+            status.extend(
+                crate::DeviceStatus::create(
+                    crate::FuncId::Acceptor,
+                    crate::MajorMinorStatus::NormalIdle,
+                )
+                .to_bytes(),
+            );
+            let push = MessageData::from(crate::StatusRequest::new()).with_additional(&status);
+
+            Ok(vec![Message::from(push).into(), ack(msg).into()])
+        })));
+        let stop = Arc::new(AtomicBool::new(false));
+        let (event_send, _event_recv) = crossbeam::channel::unbounded();
+        let (_event_res_send, event_res_recv) = crossbeam::channel::unbounded();
+        let (response_send, response_recv) = crossbeam::channel::unbounded();
+
+        poll_device_message(
+            Arc::clone(&usb),
+            Arc::clone(&stop),
+            event_send,
+            event_res_recv,
+            response_send,
+        )?;
+
+        let tracker = Mutex::new(crate::StateTracker::new());
+        let req = Message::from(crate::IdleRequest::new());
+
+        let res = poll_request_observed(
+            Arc::clone(&usb),
+            &req,
+            &response_recv,
+            1,
+            &SystemClock::new(),
+            &tracker,
+        )?;
+
+        assert_eq!(res.data().message_code(), req.data().message_code());
+        assert_eq!(
+            tracker.lock().unwrap().state(),
+            crate::BillAcceptorState::Idle
+        );
+
+        stop.store(true, Ordering::SeqCst);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mock_device_poller() -> Result<()> {
+        let usb = Arc::new(Mutex::new(crate::mock::MockDevice::new()));
+        let stop = Arc::new(AtomicBool::new(false));
+        let (event_send, event_recv) = crossbeam::channel::unbounded();
+        let (event_res_send, event_res_recv) = crossbeam::channel::unbounded();
+        let (response_send, response_recv) = crossbeam::channel::unbounded();
+
+        let poller = spawn_device_poller(
+            Arc::clone(&usb),
+            Arc::clone(&stop),
+            event_send,
+            event_res_recv,
+            ResponseRoute::Channel(response_send),
+            EventDrain::Single,
+            SystemClock::new(),
+            None,
+        );
+
+        let next_event = || -> Result<EventCode> {
+            let evt = event_recv
+                .recv_timeout(time::Duration::from_secs(5))
+                .unwrap();
+            event_res_send.send(event_ack(&evt)).unwrap();
+            evt.data().message_code().event_code()
+        };
+        let poll = |req: Message| poll_request(Arc::clone(&usb), &req, &response_recv, 3);
+
+        assert_eq!(next_event()?, EventCode::PowerUp);
+        poll(crate::IdleRequest::new().into())?;
+        assert_eq!(next_event()?, EventCode::Idle);
+
+        assert!(usb.lock().unwrap().insert_note(crate::Currency::new()));
+        assert_eq!(next_event()?, EventCode::Escrow);
+
+        let res = poll(crate::StackRequest::new().into())?;
+        assert_eq!(res.data().additional(), [u8::from(ResponseCode::Ack)]);
+        assert_eq!(next_event()?, EventCode::VendValid);
+        assert_eq!(next_event()?, EventCode::AcceptorCollected);
+        assert_eq!(next_event()?, EventCode::Idle);
+
+        stop.store(true, Ordering::SeqCst);
+        assert!(poller.join().unwrap().is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_withheld_vend_valid_ack() -> Result<()> {
+        let transport = TestTransport::new();
+        let device = transport.clone();
+        device.push(crate::VendValidEvent::create(EventType::Sequence1).into());
+
+        let usb = Arc::new(Mutex::new(transport));
+        let stop = Arc::new(AtomicBool::new(false));
+        let (event_send, event_recv) = crossbeam::channel::unbounded();
+        let (event_res_send, event_res_recv) = crossbeam::channel::unbounded();
+        let (response_send, response_recv) = crossbeam::channel::unbounded();
+
+        let poller = spawn_device_poller(
+            Arc::clone(&usb),
+            Arc::clone(&stop),
+            event_send,
+            event_res_recv,
+            ResponseRoute::Channel(response_send),
+            EventDrain::Single,
+            SystemClock::new(),
+            None,
+        );
+
+        let mut acker =
+            CreditAcknowledger::new(crate::CreditLedger::open(crate::MemoryJournal::new())?);
+        let vend = event_recv
+            .recv_timeout(time::Duration::from_secs(5))
+            .unwrap();
+        let credit = respond_event(&mut acker, &vend, &event_res_send)?.unwrap();
+        assert_eq!(acker.withheld(), Some(credit.id()));
+
+        // requests reach the device while the `Vend Valid` ACK is withheld
+        let req = Message::from(crate::StatusRequest::new());
+        let res = poll_request(Arc::clone(&usb), &req, &response_recv, 1)?;
+        assert_eq!(res.data().message_code(), req.data().message_code());
+        assert_eq!(device.event_responses(), 0);
+
+        confirm_credit(&mut acker, credit.id(), &event_res_send)?;
+
+        let start = time::Instant::now();
+        while device.event_responses() == 0 && start.elapsed() < time::Duration::from_secs(5) {
+            thread::sleep(time::Duration::from_millis(1));
+        }
+        assert_eq!(
+            device.ops().last().map(String::as_str),
+            Some(r#"ack "VendValid""#)
+        );
+
+        // a poller stopped while an ACK is withheld still exits
+        device.push(crate::VendValidEvent::create(EventType::Sequence2).into());
+        event_recv
+            .recv_timeout(time::Duration::from_secs(5))
+            .unwrap();
+        stop.store(true, Ordering::SeqCst);
+        assert!(poller.join().unwrap().is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_vendor_event_poller() -> Result<()> {
+        let transport = TestTransport::new();
+        let device = transport.clone();
+
+        let registry = VendorRegistry::new().with_decoder(0x7f01, "coin_level", |data| {
+            data.first().copied().ok_or(Error::InvalidEventLen((0, 1)))
+        })?;
+
+        let usb = Arc::new(Mutex::new(transport));
+        let stop = Arc::new(AtomicBool::new(false));
+        let (event_send, event_recv) = crossbeam::channel::unbounded();
+        let (_event_res_send, event_res_recv) = crossbeam::channel::unbounded();
+        let (response_send, _response_recv) = crossbeam::channel::unbounded();
+        let (vendor_send, vendor_recv) = crossbeam::channel::unbounded();
+
+        poll_device_message_with_vendor(
+            Arc::clone(&usb),
+            Arc::clone(&stop),
+            event_send,
+            event_res_recv,
+            response_send,
+            Arc::new(registry),
+            vendor_send,
+            SystemClock::new(),
+        )?;
+
+        device.push_frame(vec![0x12, 0x09, 0x00, 0x10, 0x00, 0x80, 0x01, 0x7f, 0x2a]);
+
+        match vendor_recv.recv_timeout(time::Duration::from_secs(5)) {
+            Ok(TypedEvent::Vendor(vendor)) => {
+                assert_eq!(vendor.code(), 0x7f01);
+                assert_eq!(vendor.decoded::<u8>(), Some(&0x2a));
+            }
+            evt => panic!("unexpected vendor event: {evt:?}"),
+        }
+
+        // standard events still reach the event channel
+        device.push(idle_event());
+        let idle = event_recv
+            .recv_timeout(time::Duration::from_secs(5))
+            .unwrap();
+        assert_eq!(
+            idle.data().message_code(),
+            idle_event().data().message_code()
+        );
+
+        // the poller acknowledges the vendor event
+        assert!(device
+            .ops()
+            .contains(&"frame [12, 09, 00, 10, 00, 80, 01, 7f, 06]".to_string()));
+
+        // unregistered vendor codes are not delivered
+        device.push_frame(vec![0x12, 0x09, 0x00, 0x10, 0x00, 0x80, 0x02, 0x7f, 0x2a]);
+        assert!(vendor_recv
+            .recv_timeout(time::Duration::from_millis(100))
+            .is_err());
+        assert_eq!(
+            device
+                .ops()
+                .iter()
+                .filter(|op| op.starts_with("frame"))
+                .count(),
+            1
+        );
+
+        stop.store(true, Ordering::SeqCst);
+
+        Ok(())
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_transport_timeout() {
+        let delay = RESPONSE_TIMEOUT + time::Duration::from_millis(200);
+        let req = Message::from(crate::IdleRequest::new());
+
+        for (timeout, delivered) in [(delay * 2, true), (USB_TIMEOUT, false)] {
+            let usb = Arc::new(Mutex::new(
+                TestTransport::new().with_timeout(timeout).with_delay(delay),
+            ));
+            let stop = Arc::new(AtomicBool::new(false));
+            let (event_send, _event_recv) = crossbeam::channel::unbounded();
+            let (_event_res_send, event_res_recv) = crossbeam::channel::unbounded();
+            let (response_send, response_recv) = crossbeam::channel::unbounded();
+
+            poll_device_message(
+                Arc::clone(&usb),
+                Arc::clone(&stop),
+                event_send,
+                event_res_recv,
+                response_send,
+            )
+            .unwrap();
+
+            // the response wait covers a transport timeout longer than `RESPONSE_TIMEOUT`
+            let res = poll_request(Arc::clone(&usb), &req, &response_recv, 1);
+            assert_eq!(res.is_ok(), delivered, "transport timeout: {timeout:?}");
+
+            if let Err(err) = res {
+                assert!(err.is_retryable());
+                assert!(matches!(
+                    err,
+                    Error::Transport(ref err) if err.kind() == TransportErrorKind::Timeout
+                ));
+            }
+
+            stop.store(true, Ordering::SeqCst);
+        }
+
+        // transports with a short timeout are polled at their own pace
+        let usb = Arc::new(Mutex::new(
+            TestTransport::new().with_timeout(POLL_INTERVAL / 10),
+        ));
+        let stop = Arc::new(AtomicBool::new(false));
+        let (event_send, _event_recv) = crossbeam::channel::unbounded();
+        let (_event_res_send, event_res_recv) = crossbeam::channel::unbounded();
+        let (response_send, _response_recv) = crossbeam::channel::unbounded();
+
+        poll_device_message(
+            Arc::clone(&usb),
+            Arc::clone(&stop),
+            event_send,
+            event_res_recv,
+            response_send,
+        )
+        .unwrap();
+
+        thread::sleep(POLL_INTERVAL * 3);
+        stop.store(true, Ordering::SeqCst);
+
+        // polling at `POLL_INTERVAL` would only read about three times
+        assert!(usb.lock().unwrap().reads() >= 10);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_transport_disconnect() {
+        use std::error::Error as _;
+
+        let usb = Arc::new(Mutex::new(TestTransport::new()));
+        let req = Message::from(crate::IdleRequest::new());
+
+        // the poller is gone, so no response can ever arrive
+        let (response_send, response_recv) = crossbeam::channel::unbounded::<Message>();
+        drop(response_send);
+
+        let err = poll_request(usb, &req, &response_recv, 3).unwrap_err();
+
+        assert!(!err.is_retryable());
+        assert!(matches!(
+            err,
+            Error::Transport(ref err) if err.kind() == TransportErrorKind::Disconnected
+        ));
+        assert!(err.source().and_then(|err| err.source()).is_some());
+    }
+
+    /// Serves an image in blocks, optionally dropping a byte from, or reordering, block frames.
+    struct ImageDevice {
+        image: Vec<u8>,
+        blocks: usize,
+        corrupt: Option<usize>,
+        swap: Option<(usize, usize)>,
+        requested: Mutex<Vec<usize>>,
+    }
+
+    impl ImageDevice {
+        fn new(image: &[u8], blocks: usize) -> Self {
+            Self {
+                image: image.into(),
+                blocks,
+                corrupt: None,
+                swap: None,
+                requested: Mutex::new(Vec::new()),
+            }
+        }
+
+        fn with_corrupt(mut self, block: usize) -> Self {
+            self.corrupt = Some(block);
+            self
+        }
+
+        fn with_swap(mut self, a: usize, b: usize) -> Self {
+            self.swap = Some((a, b));
+            self
+        }
+
+        fn respond(&self, message: &Message) -> Result<Vec<Vec<u8>>> {
+            let block = match message.data().message_code().request_code()? {
+                RequestCode::SerialNumber => {
+                    crate::SerialNumberRequest::try_from(message)?.block_number()
+                }
+                _ => crate::NoteImageRequest::try_from(message)?.block_number(),
+            }
+            .into_u8() as usize;
+            self.requested.lock().unwrap().push(block);
+
+            let served = match self.swap {
+                Some((a, b)) if block == a => b,
+                Some((a, b)) if block == b => a,
+                _ => block,
+            };
+            let block_len = self.image.len().div_ceil(self.blocks);
+
+            let res: crate::Response = if served == 0 {
+                crate::NoteImageSizeResponse::new()
+                    .with_size_total(
+                        crate::ImageSize::new()
+                            .with_size(self.image.len())
+                            .with_total_blocks(self.blocks),
+                    )
+                    .into()
+            } else {
+                let start = (served - 1) * block_len;
+                let end = (start + block_len).min(self.image.len());
+                crate::NoteImageBlockResponse::new()
+                    .with_block(crate::ImageBlock::from(&self.image[start..end]))
+                    .into()
+            };
+
+            let mut additional = vec![0u8; res.len()];
+            res.to_bytes(additional.as_mut())?;
+
+            let mut frame: Vec<u8> = Message::new()
+                .with_data(message.data().clone().with_additional(additional.as_ref()))
+                .into();
+            if self.corrupt == Some(block) {
+                frame.pop();
+            }
+
+            Ok(vec![frame])
+        }
+    }
+
+    type ImageFetchResult = (Result<crate::NoteImage>, Vec<(usize, usize)>, Vec<usize>);
+
+    #[allow(deprecated)]
+    fn run_image_fetch(device: ImageDevice, kind: ImageKind) -> ImageFetchResult {
+        let device = Arc::new(device);
+        let responder = Arc::clone(&device);
+        let usb = Arc::new(Mutex::new(
+            TestTransport::with_responder(move |msg| responder.respond(msg))
+                .with_timeout(POLL_INTERVAL / 10),
+        ));
+        let stop = Arc::new(AtomicBool::new(false));
+        let (event_send, _event_recv) = crossbeam::channel::unbounded();
+        let (_event_res_send, event_res_recv) = crossbeam::channel::unbounded();
+        let (response_send, response_recv) = crossbeam::channel::unbounded();
+
+        poll_device_message(
+            Arc::clone(&usb),
+            Arc::clone(&stop),
+            event_send,
+            event_res_recv,
+            response_send,
+        )
+        .unwrap();
+
+        let cancel = CancelToken::new();
+        let mut progress = Vec::new();
+        let on_progress = |p: ImageProgress| progress.push((p.block(), p.received()));
+        let res = match kind {
+            ImageKind::Note => {
+                fetch_note_image(Arc::clone(&usb), &response_recv, 1, &cancel, on_progress)
+            }
+            ImageKind::SerialNumber => {
+                fetch_serial_number_image(Arc::clone(&usb), &response_recv, 1, &cancel, on_progress)
+            }
+        };
+
+        stop.store(true, Ordering::SeqCst);
+        let requested = device.requested.lock().unwrap().clone();
+
+        (res, progress, requested)
+    }
+
+    #[test]
+    fn test_fetch_image_blocks() {
+        let image: Vec<u8> = (0..=250u8).collect();
+
+        let (res, progress, requested) =
+            run_image_fetch(ImageDevice::new(&image, 4), ImageKind::Note);
+        let note = res.unwrap();
+        assert_eq!(note.kind(), ImageKind::Note);
+        assert_eq!(note.data(), image.as_slice());
+        assert_eq!(progress, [(1, 63), (2, 126), (3, 189), (4, 251)]);
+        assert_eq!(requested, [0, 1, 2, 3, 4]);
+
+        let (res, progress, _) =
+            run_image_fetch(ImageDevice::new(&image, 3), ImageKind::SerialNumber);
+        let serial = res.unwrap();
+        assert_eq!(serial.kind(), ImageKind::SerialNumber);
+        assert_eq!(serial.data(), image.as_slice());
+        assert_eq!(progress, [(1, 84), (2, 168), (3, 251)]);
+
+        // the framing has no checksum, a damaged frame fails its length check, and never arrives
+        let (res, progress, requested) =
+            run_image_fetch(ImageDevice::new(&image, 4).with_corrupt(2), ImageKind::Note);
+        assert!(res.is_err());
+        assert_eq!(progress, [(1, 63)]);
+        // the block sequence is reset after the failure
+        assert_eq!(requested, [0, 1, 2, 0]);
+
+        // the short last block delivered out of order breaks the block sequence
+        let (res, progress, requested) = run_image_fetch(
+            ImageDevice::new(&image, 3).with_swap(2, 3),
+            ImageKind::SerialNumber,
+        );
+        assert_eq!(res.err(), Some(Error::InvalidImageBlockLen((83, 84))));
+        assert_eq!(progress, [(1, 84)]);
+        assert_eq!(requested, [0, 1, 2, 0]);
+    }
 }
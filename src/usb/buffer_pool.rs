@@ -0,0 +1,135 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Default number of idle buffers kept by a [BufferPool].
+pub const DEFAULT_POOL_SIZE: usize = 4;
+
+/// Represents a pool of reusable transfer buffers.
+///
+/// A [UsbDeviceHandle](super::UsbDeviceHandle) takes a buffer for every bulk transfer, and puts
+/// it back once the transfer completes, so high-throughput polling loops do not allocate a new
+/// buffer per message.
+///
+/// Buffers return to the pool on every path, including failed transfers, and transfers
+/// cancelled by the timeout, so an idle device polled for events does not allocate either.
+///
+/// # Example
+///
+/// ```
+/// use jcm::usb::BufferPool;
+///
+/// let pool = BufferPool::new();
+///
+/// let mut buf = pool.take();
+/// buf.extend_from_slice(&[0x12, 0x08, 0x00]);
+/// pool.put(buf);
+///
+/// let buf = pool.take();
+/// assert!(buf.is_empty());
+/// assert_eq!(pool.allocations(), 1);
+/// ```
+#[derive(Debug)]
+pub struct BufferPool {
+    buffers: Mutex<Vec<Vec<u8>>>,
+    max_buffers: usize,
+    allocations: AtomicU64,
+}
+
+impl BufferPool {
+    /// Creates a new [BufferPool], keeping up to [DEFAULT_POOL_SIZE] idle buffers.
+    pub const fn new() -> Self {
+        Self::create(DEFAULT_POOL_SIZE)
+    }
+
+    /// Creates a new [BufferPool], keeping up to `max_buffers` idle buffers.
+    pub const fn create(max_buffers: usize) -> Self {
+        Self {
+            buffers: Mutex::new(Vec::new()),
+            max_buffers,
+            allocations: AtomicU64::new(0),
+        }
+    }
+
+    /// Gets the maximum number of idle buffers kept by the [BufferPool].
+    pub const fn max_buffers(&self) -> usize {
+        self.max_buffers
+    }
+
+    /// Takes an empty buffer from the pool, allocating a new one if none are idle.
+    pub fn take(&self) -> Vec<u8> {
+        match self.lock().pop() {
+            Some(buf) => buf,
+            None => {
+                self.allocations.fetch_add(1, Ordering::Relaxed);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Puts a buffer back into the pool, for reuse by the next transfer.
+    ///
+    /// The buffer is dropped if the pool already holds [max_buffers](Self::max_buffers) idle
+    /// buffers.
+    pub fn put(&self, mut buf: Vec<u8>) {
+        buf.clear();
+
+        let mut buffers = self.lock();
+        if buffers.len() < self.max_buffers {
+            buffers.push(buf);
+        }
+    }
+
+    /// Gets the number of idle buffers in the pool.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Gets whether the pool holds no idle buffers.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets the number of buffers allocated because none were idle.
+    pub fn allocations(&self) -> u64 {
+        self.allocations.load(Ordering::Relaxed)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Vec<u8>>> {
+        self.buffers.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl Default for BufferPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Message, StatusRequest};
+
+    #[test]
+    fn test_buffer_pool() -> crate::Result<()> {
+        let pool = BufferPool::create(2);
+        let msg = Message::from(StatusRequest::new());
+
+        for _ in 0..10 {
+            let mut buf = pool.take();
+            buf.resize(msg.len(), 0);
+            assert_eq!(msg.to_bytes(&mut buf)?, msg.len());
+            assert_eq!(Message::from_bytes(&buf)?, msg);
+            pool.put(buf);
+        }
+        assert_eq!(pool.allocations(), 1);
+        assert_eq!(pool.len(), 1);
+
+        let bufs = [pool.take(), pool.take(), pool.take()];
+        assert_eq!(pool.allocations(), 3);
+        bufs.into_iter().for_each(|buf| pool.put(buf));
+        assert_eq!(pool.len(), pool.max_buffers());
+
+        Ok(())
+    }
+}
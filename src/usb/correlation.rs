@@ -0,0 +1,240 @@
+use std::fmt;
+use std::sync::{Arc, Mutex, Weak};
+
+use crate::{Error, Message, MessageCode, MessageData, MessageType, Result};
+
+type PendingTable = Vec<(CorrelationKey, crossbeam::channel::Sender<Message>)>;
+type InFlightTable = Vec<(CorrelationKey, Weak<Mutex<()>>)>;
+
+/// Represents the fields correlating a device response with its request.
+///
+/// The protocol has no per-request sequence number: the message `ID` is always `0x12`. The
+/// device echoes the UID, message type, and message code of the request in its response, so
+/// together they identify the outstanding request.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CorrelationKey {
+    uid: u8,
+    message_type: MessageType,
+    message_code: MessageCode,
+}
+
+impl CorrelationKey {
+    /// Creates a new [CorrelationKey] from a request or response [MessageData].
+    pub const fn create(data: &MessageData) -> Self {
+        Self {
+            uid: data.uid(),
+            message_type: data.message_type(),
+            message_code: data.message_code(),
+        }
+    }
+
+    /// Gets the UID of the [CorrelationKey].
+    pub const fn uid(&self) -> u8 {
+        self.uid
+    }
+
+    /// Gets the [MessageType] of the [CorrelationKey].
+    pub const fn message_type(&self) -> MessageType {
+        self.message_type
+    }
+
+    /// Gets the [MessageCode] of the [CorrelationKey].
+    pub const fn message_code(&self) -> MessageCode {
+        self.message_code
+    }
+}
+
+impl From<&MessageData> for CorrelationKey {
+    fn from(val: &MessageData) -> Self {
+        Self::create(val)
+    }
+}
+
+impl From<&Message> for CorrelationKey {
+    fn from(val: &Message) -> Self {
+        Self::create(val.data())
+    }
+}
+
+impl fmt::Display for CorrelationKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""uid": {}, "#, self.uid)?;
+        write!(f, r#""message_type": {}, "#, self.message_type)?;
+        write!(f, r#""message_code": {}"#, self.message_code)?;
+        write!(f, "}}")
+    }
+}
+
+/// Represents the table of outstanding requests, each with its own reply channel.
+///
+/// The device poller completes the matching entry with each response, so concurrent requests
+/// with different [CorrelationKey]s never receive each other's responses. Only one request per
+/// [CorrelationKey] can be outstanding, since the responses would be indistinguishable:
+/// [poll_request_correlated](super::poll_request_correlated) waits for the request in flight.
+///
+/// Clones share the same table.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{Message, ResponseCode, StatusRequest};
+/// use jcm::usb::PendingRequests;
+///
+/// # fn main() -> jcm::Result<()> {
+/// let pending = PendingRequests::new();
+///
+/// let req = Message::from(StatusRequest::new());
+/// let reply = pending.register(&req)?;
+/// assert!(pending.register(&req).is_err());
+///
+/// let res = Message::new().with_data(
+///     req.data()
+///         .clone()
+///         .with_additional(&[ResponseCode::Ack.into()]),
+/// );
+/// assert_eq!(pending.complete(res.clone()), None);
+/// assert_eq!(reply.try_recv().ok(), Some(res));
+/// assert!(pending.is_empty());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PendingRequests {
+    pending: Arc<Mutex<PendingTable>>,
+    in_flight: Arc<Mutex<InFlightTable>>,
+}
+
+impl PendingRequests {
+    /// Creates a new, empty [PendingRequests] table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers an outstanding request, returning the channel receiving its response.
+    ///
+    /// Fails if a request with the same [CorrelationKey] is already outstanding.
+    pub fn register(&self, request: &Message) -> Result<crossbeam::channel::Receiver<Message>> {
+        let key = CorrelationKey::from(request);
+        let mut pending = self.lock();
+
+        if pending.iter().any(|(k, _)| k == &key) {
+            Err(Error::RequestPending(key.to_string()))
+        } else {
+            let (reply_send, reply_recv) = crossbeam::channel::bounded(1);
+            pending.push((key, reply_send));
+            Ok(reply_recv)
+        }
+    }
+
+    /// Removes the outstanding request, e.g. after its response timed out.
+    ///
+    /// Returns whether a request was outstanding.
+    pub fn cancel(&self, key: &CorrelationKey) -> bool {
+        let mut pending = self.lock();
+        let len = pending.len();
+        pending.retain(|(k, _)| k != key);
+
+        pending.len() != len
+    }
+
+    /// Delivers the response to the matching outstanding request.
+    ///
+    /// Returns the response if it matches no outstanding request.
+    pub fn complete(&self, response: Message) -> Option<Message> {
+        let key = CorrelationKey::from(&response);
+        let mut pending = self.lock();
+
+        match pending.iter().position(|(k, _)| k == &key) {
+            Some(idx) => {
+                let (_, reply) = pending.remove(idx);
+                reply.send(response).err().map(|err| err.into_inner())
+            }
+            None => Some(response),
+        }
+    }
+
+    /// Gets the number of outstanding requests.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Gets whether no request is outstanding.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // serializes the requests with the same key, held by each for all of its attempts
+    pub(crate) fn key_lock(&self, key: &CorrelationKey) -> Arc<Mutex<()>> {
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(|err| err.into_inner());
+        in_flight.retain(|(_, lock)| lock.strong_count() > 0);
+
+        match in_flight
+            .iter()
+            .find(|(k, _)| k == key)
+            .and_then(|(_, lock)| lock.upgrade())
+        {
+            Some(lock) => lock,
+            None => {
+                let lock = Arc::new(Mutex::new(()));
+                in_flight.push((*key, Arc::downgrade(&lock)));
+                lock
+            }
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, PendingTable> {
+        self.pending.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RequestCode, RequestType};
+
+    #[test]
+    fn test_pending_requests() -> Result<()> {
+        let request = |code| {
+            Message::new().with_data(
+                MessageData::new()
+                    .with_uid(1)
+                    .with_message_type(MessageType::Request(RequestType::Status))
+                    .with_message_code(MessageCode::Request(code)),
+            )
+        };
+
+        let pending = PendingRequests::new();
+        let status = request(RequestCode::Status);
+        let version = request(RequestCode::Version);
+
+        let status_reply = pending.register(&status)?;
+        let version_reply = pending.register(&version)?;
+        assert_eq!(
+            pending.register(&status).err(),
+            Some(Error::RequestPending(
+                CorrelationKey::from(&status).to_string()
+            ))
+        );
+        assert_eq!(pending.len(), 2);
+
+        // responses arrive out of order, and each reaches its own request
+        assert_eq!(pending.complete(version.clone()), None);
+        assert_eq!(pending.complete(status.clone()), None);
+        assert_eq!(version_reply.try_recv().ok(), Some(version.clone()));
+        assert_eq!(status_reply.try_recv().ok(), Some(status.clone()));
+
+        // late response, after the request was cancelled
+        let _reply = pending.register(&version)?;
+        assert!(pending.cancel(&CorrelationKey::from(&version)));
+        assert_eq!(pending.complete(version.clone()), Some(version));
+
+        // other UID
+        let other = Message::new().with_data(status.data().clone().with_uid(2));
+        let _reply = pending.register(&status)?;
+        assert_eq!(pending.complete(other.clone()), Some(other));
+        assert!(!pending.is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+use std::fmt;
+
+/// Represents the USB descriptor of an attached JCM device.
+///
+/// Returned by [UsbDeviceHandle::list](super::UsbDeviceHandle::list) to pick a device when more
+/// than one unit is attached to the host.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct UsbDeviceDescriptor {
+    bus_number: u8,
+    device_address: u8,
+    vendor_id: u16,
+    product_id: u16,
+    serial_number: Option<String>,
+}
+
+impl UsbDeviceDescriptor {
+    /// Creates a new [UsbDeviceDescriptor].
+    pub const fn new() -> Self {
+        Self {
+            bus_number: 0,
+            device_address: 0,
+            vendor_id: 0,
+            product_id: 0,
+            serial_number: None,
+        }
+    }
+
+    /// Creates a new [UsbDeviceDescriptor] from the provided parameters.
+    pub const fn create(
+        bus_number: u8,
+        device_address: u8,
+        vendor_id: u16,
+        product_id: u16,
+        serial_number: Option<String>,
+    ) -> Self {
+        Self {
+            bus_number,
+            device_address,
+            vendor_id,
+            product_id,
+            serial_number,
+        }
+    }
+
+    /// Gets the number of the USB bus the device is attached to.
+    pub const fn bus_number(&self) -> u8 {
+        self.bus_number
+    }
+
+    /// Gets the address of the device on its USB bus.
+    pub const fn device_address(&self) -> u8 {
+        self.device_address
+    }
+
+    /// Gets the USB vendor ID.
+    pub const fn vendor_id(&self) -> u16 {
+        self.vendor_id
+    }
+
+    /// Gets the USB product ID.
+    pub const fn product_id(&self) -> u16 {
+        self.product_id
+    }
+
+    /// Gets the USB descriptor serial number of the device, if reported.
+    pub fn serial_number(&self) -> Option<&str> {
+        self.serial_number.as_deref()
+    }
+
+    /// Gets whether the descriptor matches the bus number and device address.
+    pub const fn is_at(&self, bus_number: u8, device_address: u8) -> bool {
+        self.bus_number == bus_number && self.device_address == device_address
+    }
+}
+
+impl From<&nusb::DeviceInfo> for UsbDeviceDescriptor {
+    fn from(val: &nusb::DeviceInfo) -> Self {
+        Self::create(
+            val.bus_number(),
+            val.device_address(),
+            val.vendor_id(),
+            val.product_id(),
+            val.serial_number().map(String::from),
+        )
+    }
+}
+
+impl From<nusb::DeviceInfo> for UsbDeviceDescriptor {
+    fn from(val: nusb::DeviceInfo) -> Self {
+        (&val).into()
+    }
+}
+
+impl fmt::Display for UsbDeviceDescriptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""bus_number": {}, "#, self.bus_number)?;
+        write!(f, r#""device_address": {}, "#, self.device_address)?;
+        write!(f, r#""vendor_id": "{:04x}", "#, self.vendor_id)?;
+        write!(f, r#""product_id": "{:04x}", "#, self.product_id)?;
+        match self.serial_number.as_deref() {
+            Some(serial) => write!(f, r#""serial_number": "{serial}""#)?,
+            None => write!(f, r#""serial_number": null"#)?,
+        }
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usb_device_descriptor() {
+        let desc = UsbDeviceDescriptor::create(1, 7, 0x2475, 0x0105, Some("JCM0001".into()));
+
+        assert!(desc.is_at(1, 7));
+        assert!(!desc.is_at(7, 1));
+        assert_eq!(desc.serial_number(), Some("JCM0001"));
+        assert_eq!(
+            desc.to_string(),
+            r#"{"bus_number": 1, "device_address": 7, "vendor_id": "2475", "product_id": "0105", "serial_number": "JCM0001"}"#
+        );
+        assert_eq!(
+            UsbDeviceDescriptor::new().to_string(),
+            r#"{"bus_number": 0, "device_address": 0, "vendor_id": "0000", "product_id": "0000", "serial_number": null}"#
+        );
+    }
+}
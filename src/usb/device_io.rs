@@ -0,0 +1,814 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use crossbeam::channel::{self, RecvTimeoutError, TryRecvError};
+
+use crate::{
+    is_status_message, redact, AuditCounters, CancelToken, Capabilities, CashboxExchange,
+    CashboxExchangeReport, Clock, CollectMode, CollectionOutcome, DeviceFrame, DeviceInfo,
+    DeviceInhibit, DeviceTransport, DirectionDisableDelta, DispenseRequest, DispenseResponse,
+    ImageFetcher, ImageKind, ImageProgress, InhibitDirection, KeepAlive, Message, NoopObserver,
+    NoteImage, NoteSerialNumber, PollConfig, PollObserver, PowerUpReport, PowerUpRoutine,
+    ProgramSignatureResponse, RequestCode, Result, SignatureAudit, StatusMessageMode,
+    SupportBundle, SystemClock, TransportErrorKind, TypedEvent, TypedRequest, UidManager,
+    VendorRegistry,
+};
+
+use super::{
+    escrow_events, recv_error, retries_exhausted, route_undecoded, send_error, transport_error,
+    wait_for_power_up_events, CorrelationKey, VendorRoute, CLOCK_RECV_INTERVAL, POLL_INTERVAL,
+    RESPONSE_TIMEOUT,
+};
+
+/// Represents a request queued for the I/O thread, with its reply channel.
+struct DeviceCommand {
+    request: Message,
+    reply: channel::Sender<Result<Message>>,
+}
+
+/// Represents a dedicated I/O thread that owns the device exclusively.
+///
+/// Unlike [poll_device_message](super::poll_device_message) and
+/// [poll_request](super::poll_request), which contend on a shared `Mutex` around the device, the
+/// I/O thread is the only reader and writer of the [DeviceTransport]. Requests are queued with
+/// their reply channels, and processed one at a time, so a request never interleaves with event
+/// reads or another request.
+///
+/// Between requests, the thread reads device events, passes them on the event channel, and
+/// writes the response read from the event response channel. Queued requests are picked up as
+/// soon as they arrive, instead of on a fixed poll interval.
+///
+/// Device-initiated status messages, see [is_status_message], are also passed on the event
+/// channel, including those read while awaiting a response. They take no event response.
+///
+/// Dropping the [DeviceIo] stops the I/O thread, even while it waits for an event response.
+///
+/// # Example
+///
+/// ```no_run
+/// # pub fn main() -> jcm::Result<()> {
+/// let usb = jcm::usb::UsbDeviceHandle::find_usb()?;
+///
+/// let (event_send, event_recv) = crossbeam::channel::unbounded();
+/// let (event_res_send, event_res_recv) = crossbeam::channel::unbounded();
+///
+/// let io = jcm::usb::DeviceIo::spawn(usb, event_send, event_res_recv);
+///
+/// jcm::usb::wait_for_power_up(&event_recv, &event_res_send)?;
+///
+/// let res = io.handle().request_typed(jcm::StatusRequest::new())?;
+/// log::info!("device status: {}", res.status());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct DeviceIo {
+    handle: DeviceIoHandle,
+    stop: Arc<AtomicBool>,
+    thread: Option<thread::JoinHandle<Result<()>>>,
+}
+
+impl DeviceIo {
+    /// Spawns the I/O thread, taking ownership of the [DeviceTransport].
+    ///
+    /// Requests use the default retries, response timeout, and retry interval.
+    pub fn spawn<T: DeviceTransport>(
+        transport: T,
+        event_send: channel::Sender<Message>,
+        event_res_recv: channel::Receiver<Message>,
+    ) -> Self {
+        let config = PollConfig::new()
+            .with_retries(3)
+            .with_response_timeout(RESPONSE_TIMEOUT)
+            .with_retry_interval(POLL_INTERVAL);
+
+        Self::spawn_with_config(transport, event_send, event_res_recv, config)
+    }
+
+    /// Spawns the I/O thread, using the [PollConfig] for request retries and timeouts.
+    pub fn spawn_with_config<T: DeviceTransport>(
+        transport: T,
+        event_send: channel::Sender<Message>,
+        event_res_recv: channel::Receiver<Message>,
+        config: PollConfig,
+    ) -> Self {
+        Self::spawn_with_clock(
+            transport,
+            event_send,
+            event_res_recv,
+            config,
+            SystemClock::new(),
+        )
+    }
+
+    /// Spawns the I/O thread, using the [PollConfig] for request retries and timeouts, and the
+    /// provided [Clock] to measure them.
+    pub fn spawn_with_clock<T: DeviceTransport, C: Clock + 'static>(
+        transport: T,
+        event_send: channel::Sender<Message>,
+        event_res_recv: channel::Receiver<Message>,
+        config: PollConfig,
+        clock: C,
+    ) -> Self {
+        Self::spawn_observed(
+            transport,
+            event_send,
+            event_res_recv,
+            config,
+            clock,
+            NoopObserver,
+        )
+    }
+
+    /// Spawns the I/O thread, notifying the [PollObserver] about the progress of every request.
+    ///
+    /// The observer receives the same notifications as with
+    /// [poll_request_observed](super::poll_request_observed).
+    pub fn spawn_observed<T: DeviceTransport, C: Clock + 'static, O: PollObserver + 'static>(
+        transport: T,
+        event_send: channel::Sender<Message>,
+        event_res_recv: channel::Receiver<Message>,
+        config: PollConfig,
+        clock: C,
+        observer: O,
+    ) -> Self {
+        Self::spawn_worker(DeviceWorker {
+            transport,
+            event_send,
+            event_res_recv,
+            config,
+            clock,
+            observer: Box::new(observer),
+            vendor: None,
+            stop: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    /// Spawns the I/O thread, decoding vendor-specific events with the [VendorRegistry].
+    ///
+    /// Frames with an event code registered in the [VendorRegistry] are delivered as a
+    /// [TypedEvent::Vendor] on the vendor event channel, and acknowledged by the I/O thread, like
+    /// with [poll_device_message_with_vendor](super::poll_device_message_with_vendor).
+    pub fn spawn_with_vendor<T: DeviceTransport>(
+        transport: T,
+        event_send: channel::Sender<Message>,
+        event_res_recv: channel::Receiver<Message>,
+        config: PollConfig,
+        registry: Arc<VendorRegistry>,
+        vendor_send: channel::Sender<TypedEvent>,
+    ) -> Self {
+        Self::spawn_worker(DeviceWorker {
+            transport,
+            event_send,
+            event_res_recv,
+            config,
+            clock: SystemClock::new(),
+            observer: Box::new(NoopObserver),
+            vendor: Some(VendorRoute {
+                registry,
+                send: vendor_send,
+            }),
+            stop: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    fn spawn_worker<T: DeviceTransport, C: Clock + 'static>(worker: DeviceWorker<T, C>) -> Self {
+        let (command_send, command_recv) = channel::unbounded();
+        let stop = Arc::clone(&worker.stop);
+
+        let thread = thread::spawn(move || worker.run(&command_recv));
+
+        Self {
+            handle: DeviceIoHandle { command_send },
+            stop,
+            thread: Some(thread),
+        }
+    }
+
+    /// Gets a [DeviceIoHandle] for queueing requests, which can be cloned across threads.
+    pub fn handle(&self) -> DeviceIoHandle {
+        self.handle.clone()
+    }
+
+    /// Queues a request [Message], and waits for the device response.
+    ///
+    /// See [DeviceIoHandle::request].
+    pub fn request(&self, request: &Message) -> Result<Message> {
+        self.handle.request(request)
+    }
+
+    /// Gets whether the I/O thread is still running.
+    pub fn is_running(&self) -> bool {
+        self.thread
+            .as_ref()
+            .map(|thread| !thread.is_finished())
+            .unwrap_or(false)
+    }
+
+    /// Stops the I/O thread, and waits for it to exit.
+    ///
+    /// Returns the error that stopped the thread early, if any.
+    pub fn stop(mut self) -> Result<()> {
+        self.join()
+    }
+
+    fn join(&mut self) -> Result<()> {
+        self.stop.store(true, Ordering::Relaxed);
+
+        match self.thread.take() {
+            Some(thread) => thread.join().map_err(|_| {
+                transport_error(TransportErrorKind::Other, "device I/O thread panicked")
+            })?,
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for DeviceIo {
+    fn drop(&mut self) {
+        if let Err(err) = self.join() {
+            log::warn!("device I/O thread stopped with an error: {err}");
+        }
+    }
+}
+
+/// Represents a cloneable handle for queueing requests on a [DeviceIo] thread.
+#[derive(Clone, Debug)]
+pub struct DeviceIoHandle {
+    command_send: channel::Sender<DeviceCommand>,
+}
+
+impl DeviceIoHandle {
+    /// Queues a request [Message], and waits for the device response.
+    ///
+    /// Requests from every handle are processed in the order they are queued. Fails if the I/O
+    /// thread stopped, or the device does not respond after the configured retries.
+    pub fn request(&self, request: &Message) -> Result<Message> {
+        let (reply, reply_recv) = channel::bounded(1);
+
+        self.command_send
+            .send(DeviceCommand {
+                request: request.clone(),
+                reply,
+            })
+            .map_err(|_| {
+                transport_error(
+                    TransportErrorKind::Disconnected,
+                    "device I/O thread stopped",
+                )
+            })?;
+
+        reply_recv.recv().map_err(|_| {
+            transport_error(
+                TransportErrorKind::Disconnected,
+                "device I/O thread stopped",
+            )
+        })?
+    }
+
+    /// Queues a [TypedRequest], parsing the response into the paired response type.
+    pub fn request_typed<R: TypedRequest>(&self, request: R) -> Result<R::Response> {
+        crate::poll_typed(request, |req| self.request(req))
+    }
+
+    /// Gets the number of requests queued for the I/O thread, and not yet picked up.
+    pub fn queued(&self) -> usize {
+        self.command_send.len()
+    }
+
+    /// Waits for the device `Power Up` events, and runs the [PowerUpRoutine] for them.
+    ///
+    /// Pass the event channels of the [DeviceIo]. See [power_up](super::power_up).
+    pub fn power_up(
+        &self,
+        event_recv: &channel::Receiver<Message>,
+        event_res_send: &channel::Sender<Message>,
+        routine: &PowerUpRoutine,
+    ) -> Result<PowerUpReport> {
+        let events = wait_for_power_up_events(
+            event_recv,
+            event_res_send,
+            routine.grace_period(),
+            &SystemClock::new(),
+        )?;
+
+        let report = routine.run(&events, |req| self.request(req))?;
+        log::info!("Power Up report: {report}");
+
+        Ok(report)
+    }
+
+    /// Sends a keep-alive `Status` request to the device if one is due.
+    ///
+    /// See [poll_keep_alive](super::poll_keep_alive).
+    pub fn keep_alive<C: Clock>(&self, keep_alive: &mut KeepAlive<C>) -> Option<Result<Message>> {
+        keep_alive.tick(|req| self.request(req))
+    }
+
+    /// Sends a firmware `Program Signature` audit request to the device if one is due.
+    ///
+    /// See [poll_signature_audit](super::poll_signature_audit).
+    pub fn signature_audit<C: Clock>(
+        &self,
+        audit: &mut SignatureAudit<C>,
+    ) -> Option<Result<ProgramSignatureResponse>> {
+        audit.tick(|req| self.request(req))
+    }
+
+    /// Retrieves the current note image data.
+    ///
+    /// See [poll_note_image](super::poll_note_image).
+    pub fn note_image(&self, cancel: &CancelToken) -> Result<Vec<u8>> {
+        ImageFetcher::new()
+            .with_cancel_token(cancel.clone())
+            .fetch(|req| self.request(req))
+    }
+
+    /// Retrieves the full note image, reporting the [ImageProgress] after each received block.
+    ///
+    /// See [fetch_note_image](super::fetch_note_image).
+    pub fn fetch_note_image<F: FnMut(ImageProgress)>(
+        &self,
+        cancel: &CancelToken,
+        progress: F,
+    ) -> Result<NoteImage> {
+        self.fetch_image(ImageKind::Note, cancel, progress)
+    }
+
+    /// Retrieves the full serial number image, reporting the [ImageProgress] after each received
+    /// block.
+    ///
+    /// See [fetch_serial_number_image](super::fetch_serial_number_image).
+    pub fn fetch_serial_number_image<F: FnMut(ImageProgress)>(
+        &self,
+        cancel: &CancelToken,
+        progress: F,
+    ) -> Result<NoteImage> {
+        self.fetch_image(ImageKind::SerialNumber, cancel, progress)
+    }
+
+    /// Reads the serial number of the note in escrow.
+    ///
+    /// See [read_note_serial](crate::read_note_serial).
+    pub fn read_note_serial(&self, cancel: &CancelToken) -> Result<NoteSerialNumber> {
+        crate::read_note_serial(cancel, |req| self.request(req))
+    }
+
+    /// Modifies the `Direction Disable` settings of the device, verifying the device applied them.
+    ///
+    /// See [modify_direction_disable](crate::modify_direction_disable).
+    pub fn modify_direction_disable<F: FnOnce(&mut InhibitDirection)>(
+        &self,
+        uid: u8,
+        modify: F,
+    ) -> Result<DirectionDisableDelta> {
+        crate::modify_direction_disable(uid, |req| self.request(req), modify)
+    }
+
+    /// Sets the [StatusMessageMode] of the device.
+    ///
+    /// See [set_status_message_mode](super::set_status_message_mode).
+    pub fn set_status_message_mode(&self, uid: u8, mode: StatusMessageMode) -> Result<()> {
+        crate::set_status_message_mode(uid, mode, |req| self.request(req))
+    }
+
+    /// Inhibits the whole device with an `Inhibit` request.
+    pub fn inhibit(&self, uid: u8) -> Result<()> {
+        crate::set_device_inhibit(uid, DeviceInhibit::Inhibited, |req| self.request(req))
+    }
+
+    /// Enables the whole device with an `Idle` request.
+    pub fn enable(&self, uid: u8) -> Result<()> {
+        crate::set_device_inhibit(uid, DeviceInhibit::Enabled, |req| self.request(req))
+    }
+
+    /// Gets whether the whole device is inhibited, from a `Status` request.
+    pub fn device_inhibit(&self, uid: u8) -> Result<DeviceInhibit> {
+        crate::get_device_inhibit(uid, |req| self.request(req))
+    }
+
+    /// Exports a [SupportBundle] archive to the file at `path`.
+    ///
+    /// See [export_support_bundle](super::export_support_bundle).
+    pub fn export_support_bundle<P: AsRef<std::path::Path>>(
+        &self,
+        serial: &str,
+        bundle: SupportBundle,
+        path: P,
+    ) -> Result<()> {
+        let info = DeviceInfo::query(serial, |req| self.request(req))?;
+
+        bundle.with_device_info(info).export(path)
+    }
+
+    /// Queries the [Capabilities] of the device with the provided `serial` number.
+    pub fn query_capabilities(&self, serial: &str) -> Result<Capabilities> {
+        Capabilities::query(serial, |req| self.request(req))
+    }
+
+    /// Discovers the UID of the device, assigning a free UID with the [UidManager] if needed.
+    pub fn negotiate_uid(&self, manager: &UidManager, device: &str) -> Result<u8> {
+        manager.negotiate(device, |req| self.request(req))
+    }
+
+    /// Guides the host through a cashbox exchange, returning the [CashboxExchangeReport].
+    pub fn exchange_cashbox(&self, counters: &AuditCounters) -> Result<CashboxExchangeReport> {
+        CashboxExchange::new(&SystemClock::new()).run(counters, |req| self.request(req))
+    }
+
+    /// Requests a recycler payout, returning the [DispenseResponse].
+    pub fn dispense(&self, request: &DispenseRequest) -> Result<DispenseResponse> {
+        self.request(&request.into())?.try_into()
+    }
+
+    /// Collects a note stranded in the device into the cashbox, waiting for the collection event.
+    ///
+    /// Pass the event channels of the [DeviceIo]. See
+    /// [collect_stranded_note](crate::collect_stranded_note) for the collection sequence.
+    pub fn collect_stranded_note(
+        &self,
+        event_recv: &channel::Receiver<Message>,
+        event_res_send: &channel::Sender<Message>,
+        mode: CollectMode,
+    ) -> Result<CollectionOutcome> {
+        crate::collect_stranded_note(
+            &SystemClock::new(),
+            mode,
+            crate::DEFAULT_COLLECT_TIMEOUT,
+            |req| self.request(req),
+            escrow_events(event_recv, event_res_send),
+        )
+    }
+
+    fn fetch_image<F: FnMut(ImageProgress)>(
+        &self,
+        kind: ImageKind,
+        cancel: &CancelToken,
+        progress: F,
+    ) -> Result<NoteImage> {
+        ImageFetcher::new()
+            .with_kind(kind)
+            .with_cancel_token(cancel.clone())
+            .fetch_image(|req| self.request(req), progress)
+    }
+}
+
+struct DeviceWorker<T: DeviceTransport, C: Clock> {
+    transport: T,
+    event_send: channel::Sender<Message>,
+    event_res_recv: channel::Receiver<Message>,
+    config: PollConfig,
+    clock: C,
+    observer: Box<dyn PollObserver>,
+    vendor: Option<VendorRoute>,
+    stop: Arc<AtomicBool>,
+}
+
+impl<T: DeviceTransport, C: Clock> DeviceWorker<T, C> {
+    fn run(self, command_recv: &channel::Receiver<DeviceCommand>) -> Result<()> {
+        while !self.is_stopped() {
+            match command_recv.try_recv() {
+                Ok(cmd) => {
+                    let res = self.transact(&cmd.request);
+                    if cmd.reply.send(res).is_err() {
+                        log::debug!("request caller left before the response");
+                    }
+                }
+                Err(TryRecvError::Empty) => match self.transport.read_frame() {
+                    Ok(DeviceFrame::Message(msg)) => self.dispatch(msg)?,
+                    Ok(DeviceFrame::Undecoded(frame)) => self.route_undecoded(frame)?,
+                    Err(err) => {
+                        log::trace!("No device-sent message available: {err}");
+
+                        // wakes as soon as a request is queued
+                        match command_recv.recv_timeout(CLOCK_RECV_INTERVAL) {
+                            Ok(cmd) => {
+                                let res = self.transact(&cmd.request);
+                                cmd.reply.send(res).ok();
+                            }
+                            Err(RecvTimeoutError::Timeout) => (),
+                            Err(RecvTimeoutError::Disconnected) => break,
+                        }
+                    }
+                },
+                Err(TryRecvError::Disconnected) => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn transact(&self, request: &Message) -> Result<Message> {
+        let code = match request.data().message_code().request_code() {
+            Ok(code) => code,
+            Err(err) => {
+                self.observer.on_failure(request, &err);
+                return Err(err);
+            }
+        };
+        let retries = self.config.retries();
+
+        let mut last_err = None;
+        for retry in 0..retries {
+            log::debug!("Sending {code} request, attempt: {retry}...");
+
+            let err = match self.transport.write_message(request) {
+                Ok(()) => {
+                    self.observer.on_request_sent(request, retry);
+
+                    match self.read_response(request, code) {
+                        Ok(res) => {
+                            self.observer.on_response(request, &res, retry);
+                            return Ok(res);
+                        }
+                        Err(err) => err,
+                    }
+                }
+                Err(err) => err,
+            };
+
+            log::warn!("error sending {code} request: {err}, retry: {retry}");
+
+            if retry + 1 < retries {
+                self.observer.on_retry(request, retry, &err);
+                self.clock.sleep(self.config.retry_interval());
+            }
+            last_err = Some(err);
+        }
+
+        let err = retries_exhausted(retries, last_err);
+        self.observer.on_failure(request, &err);
+
+        Err(err)
+    }
+
+    // reads until the matching response, dispatching events read in the meantime
+    fn read_response(&self, request: &Message, code: RequestCode) -> Result<Message> {
+        let key = CorrelationKey::from(request);
+        let start = self.clock.now();
+
+        while self.clock.elapsed(start) < self.config.response_timeout() {
+            match self.transport.read_frame() {
+                Ok(DeviceFrame::Undecoded(frame)) => self.route_undecoded(frame)?,
+                Ok(DeviceFrame::Message(msg)) if msg.data().message_type().is_event() => {
+                    self.dispatch(msg)?;
+                }
+                Ok(DeviceFrame::Message(res))
+                    if code != RequestCode::Status && is_status_message(&res) =>
+                {
+                    self.forward_status(res)?;
+                }
+                Ok(DeviceFrame::Message(res)) if Self::is_response(&key, &res) => {
+                    return Ok(res);
+                }
+                Ok(DeviceFrame::Message(res)) => {
+                    log::debug!("unsolicited response: {}", redact(&res));
+                    self.observer.on_unsolicited(&res);
+                }
+                Err(err) => {
+                    log::trace!("No {code} response available: {err}");
+                    self.clock.sleep(CLOCK_RECV_INTERVAL);
+                }
+            }
+        }
+
+        Err(transport_error(
+            TransportErrorKind::Timeout,
+            &format!("error receiving {code} response: timeout"),
+        ))
+    }
+
+    // the device answers a UID assignment with its new UID, so only the type and code must match
+    fn is_response(key: &CorrelationKey, res: &Message) -> bool {
+        let res = CorrelationKey::from(res);
+
+        res.message_type() == key.message_type() && res.message_code() == key.message_code()
+    }
+
+    fn dispatch(&self, msg: Message) -> Result<()> {
+        if is_status_message(&msg) {
+            return self.forward_status(msg);
+        } else if !msg.data().message_type().is_event() {
+            log::debug!("unsolicited response: {}", redact(&msg));
+            self.observer.on_unsolicited(&msg);
+            return Ok(());
+        }
+
+        self.event_send
+            .send(msg)
+            .map_err(|err| send_error("error sending event", err))?;
+
+        // the host may never answer, so keep checking for a stop request
+        let res = loop {
+            match self.event_res_recv.recv_timeout(CLOCK_RECV_INTERVAL) {
+                Ok(res) => break res,
+                Err(RecvTimeoutError::Timeout) if self.is_stopped() => {
+                    log::debug!("stopped while waiting for an event response");
+                    return Ok(());
+                }
+                Err(RecvTimeoutError::Timeout) => (),
+                Err(err) => return Err(recv_error("error receiving event response", err)),
+            }
+        };
+
+        self.transport.write_event_response(&res)
+    }
+
+    fn route_undecoded(&self, frame: (Vec<u8>, crate::Error)) -> Result<()> {
+        route_undecoded(frame, self.vendor.as_ref(), |ack| {
+            self.transport.write_frame(ack)
+        })
+    }
+
+    fn forward_status(&self, msg: Message) -> Result<()> {
+        log::trace!("status message: {}", redact(&msg));
+        self.observer.on_status(&msg);
+
+        self.event_send
+            .send(msg)
+            .map_err(|err| send_error("error sending status message", err))
+    }
+
+    fn is_stopped(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{Error, ResponseCode, SimulatedClock, StatusRequest, VersionRequest};
+
+    use crate::usb::test_transport::{self, idle_event, TestTransport};
+
+    #[test]
+    fn test_device_io_simulated() -> Result<()> {
+        let real = std::time::Instant::now();
+        let clock = Arc::new(SimulatedClock::new());
+        let config = PollConfig::new()
+            .with_retries(3)
+            .with_response_timeout(std::time::Duration::from_millis(500))
+            .with_retry_interval(std::time::Duration::from_millis(100));
+
+        // the device never answers requests, pushing a status message instead
+        let transport =
+            TestTransport::with_responder(|_| Ok(vec![Message::from(StatusRequest::new()).into()]));
+        let device = transport.clone();
+
+        let (event_send, event_recv) = channel::unbounded();
+        let (_event_res_send, event_res_recv) = channel::unbounded();
+        let io = DeviceIo::spawn_with_clock(
+            transport,
+            event_send,
+            event_res_recv,
+            config,
+            Arc::clone(&clock),
+        );
+
+        // every attempt times out, with no retry interval after the last one
+        assert!(io.request(&VersionRequest::new().into()).is_err());
+        assert_eq!(
+            clock.now(),
+            std::time::Duration::from_millis(3 * 500 + 2 * 100)
+        );
+
+        // status messages pushed while awaiting the response reach the event channel
+        let status: Vec<Message> = event_recv.try_iter().collect();
+        assert_eq!(status.len(), 3);
+        assert!(status.iter().all(is_status_message));
+
+        // the host never answers the event, and stopping still joins the I/O thread
+        device.push(idle_event());
+        let event = event_recv.recv_timeout(std::time::Duration::from_secs(1));
+        assert!(event.is_ok_and(|evt| evt.data().message_type().is_event()));
+
+        io.stop()?;
+        assert!(real.elapsed() < std::time::Duration::from_secs(1));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_device_io_observed() -> Result<()> {
+        use std::sync::atomic::AtomicUsize;
+
+        #[derive(Default)]
+        struct Counter {
+            responses: AtomicUsize,
+            status: AtomicUsize,
+        }
+
+        impl PollObserver for Counter {
+            fn on_response(&self, _request: &Message, _response: &Message, _attempt: usize) {
+                self.responses.fetch_add(1, Ordering::SeqCst);
+            }
+
+            fn on_status(&self, _status: &Message) {
+                self.status.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        // the device pushes a status message, and a vendor event, ahead of every response
+        let transport = TestTransport::with_responder(|msg| {
+            Ok(vec![
+                Message::from(StatusRequest::new()).into(),
+                vec![0x12, 0x09, 0x00, 0x10, 0x00, 0x80, 0x01, 0x7f, 0x2a],
+                test_transport::ack(msg).into(),
+            ])
+        });
+        let device = transport.clone();
+
+        let counter = Arc::new(Counter::default());
+        let (event_send, _event_recv) = channel::unbounded();
+        let (_event_res_send, event_res_recv) = channel::unbounded();
+        let io = DeviceIo::spawn_observed(
+            transport,
+            event_send,
+            event_res_recv,
+            PollConfig::new(),
+            SystemClock::new(),
+            Arc::clone(&counter),
+        );
+
+        io.request(&VersionRequest::new().into())?;
+        assert_eq!(counter.responses.load(Ordering::SeqCst), 1);
+        assert_eq!(counter.status.load(Ordering::SeqCst), 1);
+        io.stop()?;
+
+        // without a registry, the vendor event is dropped unacknowledged
+        assert!(!device.ops().iter().any(|op| op.starts_with("frame")));
+
+        let registry = VendorRegistry::new().with_decoder(0x7f01, "coin_level", |data| {
+            data.first().copied().ok_or(Error::InvalidEventLen((0, 1)))
+        })?;
+        let transport = device.clone();
+        let (event_send, _event_recv) = channel::unbounded();
+        let (vendor_send, vendor_recv) = channel::unbounded();
+        let io = DeviceIo::spawn_with_vendor(
+            transport,
+            event_send,
+            channel::unbounded().1,
+            PollConfig::new(),
+            Arc::new(registry),
+            vendor_send,
+        );
+
+        io.request(&VersionRequest::new().into())?;
+        match vendor_recv.try_recv() {
+            Ok(TypedEvent::Vendor(vendor)) => assert_eq!(vendor.decoded::<u8>(), Some(&0x2a)),
+            evt => panic!("unexpected vendor event: {evt:?}"),
+        }
+        assert!(device
+            .ops()
+            .contains(&"frame [12, 09, 00, 10, 00, 80, 01, 7f, 06]".to_string()));
+
+        io.stop()
+    }
+
+    #[test]
+    fn test_device_io() -> Result<()> {
+        // every response follows a device event
+        let transport = TestTransport::with_responder(|msg| {
+            Ok(vec![idle_event().into(), test_transport::ack(msg).into()])
+        });
+        let device = transport.clone();
+
+        let (event_send, event_recv) = channel::unbounded::<Message>();
+        let (event_res_send, event_res_recv) = channel::unbounded();
+        thread::spawn(move || {
+            while let Ok(evt) = event_recv.recv() {
+                event_res_send.send(evt).ok();
+            }
+        });
+
+        let io = DeviceIo::spawn(transport, event_send, event_res_recv);
+
+        let callers = (0..4)
+            .map(|_| {
+                let handle = io.handle();
+                thread::spawn(move || {
+                    (0..5).try_for_each(|_| {
+                        let res = handle.request(&StatusRequest::new().into())?;
+                        assert_eq!(res.data().additional(), [u8::from(ResponseCode::Ack)]);
+                        Ok::<(), Error>(())
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for caller in callers {
+            caller.join().unwrap()?;
+        }
+
+        assert_eq!(device.event_responses(), 20);
+        assert!(io.is_running());
+
+        let handle = io.handle();
+        io.stop()?;
+        assert!(handle.request(&StatusRequest::new().into()).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,236 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::{fmt, thread, time};
+
+use crate::{event_ack, EventCode, Message};
+
+const ACK_RECV_INTERVAL: time::Duration = time::Duration::from_millis(50);
+
+/// Represents a callback deciding the response to a device event.
+///
+/// Returns the event response to send to the device, or `None` to intercept the event.
+pub type EventAckCallback = Box<dyn FnMut(&Message) -> Option<Message> + Send>;
+
+/// Represents how the [spawn_event_acknowledger] thread responds to device events.
+///
+/// Intercepted events are not answered by the thread. They are delivered on the returned channel,
+/// and the host sends its own response on the event response channel.
+#[derive(Default)]
+pub enum EventAckPolicy {
+    /// Acknowledge every event with an `ACK`.
+    #[default]
+    AckAll,
+    /// Acknowledge every event with an `ACK`, except for the listed [EventCode]s.
+    AckExcept(&'static [EventCode]),
+    /// Respond to events with the [EventAckCallback].
+    Custom(EventAckCallback),
+}
+
+impl EventAckPolicy {
+    /// Gets the response to the device event, or `None` if the event is intercepted.
+    pub fn respond(&mut self, event: &Message) -> Option<Message> {
+        match self {
+            Self::AckAll => Some(event_ack(event)),
+            Self::AckExcept(codes) => match event.data().message_code().event_code() {
+                Ok(code) if codes.contains(&code) => None,
+                _ => Some(event_ack(event)),
+            },
+            Self::Custom(callback) => callback(event),
+        }
+    }
+}
+
+impl fmt::Debug for EventAckPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AckAll => write!(f, "AckAll"),
+            Self::AckExcept(codes) => f.debug_tuple("AckExcept").field(codes).finish(),
+            Self::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+impl fmt::Display for EventAckPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AckAll => write!(f, r#""ack_all""#),
+            Self::AckExcept(codes) => {
+                write!(f, r#"{{"ack_except": ["#)?;
+                for (i, code) in codes.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{code}")?;
+                }
+                write!(f, "]}}")
+            }
+            Self::Custom(_) => write!(f, r#""custom""#),
+        }
+    }
+}
+
+/// Spawns a thread responding to device events according to the [EventAckPolicy].
+///
+/// Pass the event channels of [poll_device_message](super::poll_device_message), so the poller
+/// never blocks waiting on an event response. Returns the channel delivering intercepted events;
+/// respond to them on a clone of the `event_res_send` channel.
+///
+/// Messages other than events, e.g. status messages passed on by a
+/// [DeviceIo](super::DeviceIo), take no response, and are always delivered on the returned
+/// channel.
+///
+/// The thread exits once `stop` is set, or the event channel disconnects.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::sync::{Arc, Mutex};
+/// use std::sync::atomic::AtomicBool;
+///
+/// use jcm::EventCode;
+/// use jcm::usb::EventAckPolicy;
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// let usb = Arc::new(Mutex::new(jcm::usb::UsbDeviceHandle::find_usb()?));
+/// let stop = Arc::new(AtomicBool::new(false));
+///
+/// let (event_send, event_recv) = crossbeam::channel::unbounded();
+/// let (response_send, response_recv) = crossbeam::channel::unbounded();
+/// let (event_res_send, event_res_recv) = crossbeam::channel::unbounded();
+///
+/// jcm::usb::poll_device_message(
+///     Arc::clone(&usb),
+///     Arc::clone(&stop),
+///     event_send,
+///     event_res_recv,
+///     response_send,
+/// )?;
+///
+/// let escrow_recv = jcm::usb::spawn_event_acknowledger(
+///     Arc::clone(&stop),
+///     event_recv,
+///     event_res_send.clone(),
+///     EventAckPolicy::AckExcept(&[EventCode::Escrow]),
+/// );
+///
+/// if let Ok(escrow) = escrow_recv.recv() {
+///     log::info!("note in escrow: {escrow}");
+///     event_res_send.send(jcm::event_ack(&escrow)).ok();
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn spawn_event_acknowledger(
+    stop: Arc<AtomicBool>,
+    event_recv: crossbeam::channel::Receiver<Message>,
+    event_res_send: crossbeam::channel::Sender<Message>,
+    mut policy: EventAckPolicy,
+) -> crossbeam::channel::Receiver<Message> {
+    let (intercept_send, intercept_recv) = crossbeam::channel::unbounded();
+
+    thread::spawn(move || {
+        while !stop.load(Ordering::Relaxed) {
+            let event = match event_recv.recv_timeout(ACK_RECV_INTERVAL) {
+                Ok(event) => event,
+                Err(crossbeam::channel::RecvTimeoutError::Timeout) => continue,
+                Err(crossbeam::channel::RecvTimeoutError::Disconnected) => break,
+            };
+
+            let response = if event.data().message_type().is_event() {
+                policy.respond(&event)
+            } else {
+                None
+            };
+
+            match response {
+                Some(res) => {
+                    if let Err(err) = event_res_send.send(res) {
+                        log::warn!("error sending event response: {err}");
+                        break;
+                    }
+                }
+                None => {
+                    if intercept_send.send(event).is_err() {
+                        log::warn!("intercepted event dropped, the receiver disconnected");
+                    }
+                }
+            }
+        }
+    });
+
+    intercept_recv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventType, MessageCode, MessageData, MessageType, ResponseCode};
+
+    #[test]
+    fn test_spawn_event_acknowledger() {
+        let event = |code| {
+            Message::new().with_data(
+                MessageData::new()
+                    .with_message_type(MessageType::Event(EventType::Sequence0))
+                    .with_message_code(MessageCode::Event(code)),
+            )
+        };
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let (event_send, event_recv) = crossbeam::channel::unbounded();
+        let (event_res_send, event_res_recv) = crossbeam::channel::unbounded();
+        let intercepted = spawn_event_acknowledger(
+            Arc::clone(&stop),
+            event_recv,
+            event_res_send,
+            EventAckPolicy::AckExcept(&[EventCode::Escrow]),
+        );
+
+        event_send.send(event(EventCode::Idle)).ok();
+        event_send.send(event(EventCode::Escrow)).ok();
+
+        let timeout = time::Duration::from_secs(1);
+        assert_eq!(
+            event_res_recv.recv_timeout(timeout).ok(),
+            Some(event_ack(&event(EventCode::Idle)))
+        );
+        assert_eq!(
+            intercepted.recv_timeout(timeout).ok(),
+            Some(event(EventCode::Escrow))
+        );
+        assert!(event_res_recv.try_recv().is_err());
+
+        // status messages are never answered
+        let status = Message::from(crate::StatusRequest::new());
+        event_send.send(status.clone()).ok();
+        assert_eq!(intercepted.recv_timeout(timeout).ok(), Some(status));
+        assert!(event_res_recv.try_recv().is_err());
+
+        // custom callback rejecting events with a NAK
+        let (event_send, event_recv) = crossbeam::channel::unbounded();
+        let (event_res_send, event_res_recv) = crossbeam::channel::unbounded();
+        let nak = |evt: &Message| {
+            Some(
+                Message::new().with_data(
+                    evt.data()
+                        .clone()
+                        .with_additional(&[ResponseCode::Nak.into()]),
+                ),
+            )
+        };
+        spawn_event_acknowledger(
+            Arc::clone(&stop),
+            event_recv,
+            event_res_send,
+            EventAckPolicy::Custom(Box::new(nak)),
+        );
+
+        event_send.send(event(EventCode::PowerUp)).ok();
+        assert_eq!(
+            event_res_recv.recv_timeout(timeout).ok(),
+            nak(&event(EventCode::PowerUp))
+        );
+
+        stop.store(true, Ordering::SeqCst);
+    }
+}
@@ -0,0 +1,140 @@
+use std::fmt;
+
+use crate::ProductFamily;
+
+use super::{JCM_PID, JCM_VID};
+
+/// Represents the USB VID:PID pairs recognized as JCM devices.
+///
+/// The JCM XFS pair `2475:0105` is the only pair documented for the protocol, and it is shared
+/// by the iVIZION, UBA, TBV, and iPRO families, so its family is [ProductFamily::Unknown], and
+/// detected from the USB product string instead. Family-specific PIDs are not published, so
+/// none are listed: add a pair here, with its [ProductFamily], once confirmed against hardware.
+///
+/// Devices enumerated with other pairs can still be opened with
+/// [find_usb_with](super::UsbDeviceHandle::find_usb_with).
+pub const PRODUCT_IDS: &[ProductId] =
+    &[ProductId::create(JCM_VID, JCM_PID, ProductFamily::Unknown)];
+
+/// Represents a USB VID:PID pair, and the [ProductFamily] of the devices using it.
+///
+/// Pairs shared by more than one family use [ProductFamily::Unknown], and the family is detected
+/// from the USB product string when the device is opened.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ProductId {
+    vendor_id: u16,
+    product_id: u16,
+    family: ProductFamily,
+}
+
+impl ProductId {
+    /// Creates a new [ProductId].
+    pub const fn new() -> Self {
+        Self {
+            vendor_id: JCM_VID,
+            product_id: JCM_PID,
+            family: ProductFamily::Unknown,
+        }
+    }
+
+    /// Creates a new [ProductId] from the provided parameters.
+    pub const fn create(vendor_id: u16, product_id: u16, family: ProductFamily) -> Self {
+        Self {
+            vendor_id,
+            product_id,
+            family,
+        }
+    }
+
+    /// Gets the USB vendor ID.
+    pub const fn vendor_id(&self) -> u16 {
+        self.vendor_id
+    }
+
+    /// Gets the USB product ID.
+    pub const fn product_id(&self) -> u16 {
+        self.product_id
+    }
+
+    /// Gets the [ProductFamily] of the devices using the VID:PID pair.
+    pub const fn family(&self) -> ProductFamily {
+        self.family
+    }
+
+    /// Gets whether the [ProductId] matches the VID:PID pair.
+    pub const fn matches(&self, vendor_id: u16, product_id: u16) -> bool {
+        self.vendor_id == vendor_id && self.product_id == product_id
+    }
+
+    /// Finds the [ProductId] matching the VID:PID pair in [PRODUCT_IDS].
+    pub fn find(vendor_id: u16, product_id: u16) -> Option<Self> {
+        PRODUCT_IDS
+            .iter()
+            .find(|id| id.matches(vendor_id, product_id))
+            .copied()
+    }
+
+    /// Lists the VID:PID pairs in [PRODUCT_IDS], e.g. for error messages.
+    pub fn supported_pairs() -> String {
+        PRODUCT_IDS
+            .iter()
+            .map(|id| format!("{:04x}:{:04x}", id.vendor_id, id.product_id))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    /// Detects the [ProductFamily] of a device from its VID:PID pair and USB product string.
+    pub fn detect_family(vendor_id: u16, product_id: u16, product: Option<&str>) -> ProductFamily {
+        match Self::find(vendor_id, product_id).map(|id| id.family) {
+            Some(family) if family.is_known() => family,
+            _ => product
+                .map(ProductFamily::from_model_name)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl Default for ProductId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for ProductId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""vendor_id": "{:04x}", "#, self.vendor_id)?;
+        write!(f, r#""product_id": "{:04x}", "#, self.product_id)?;
+        write!(f, r#""family": {}"#, self.family)?;
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_product_id() {
+        assert_eq!(ProductId::find(JCM_VID, JCM_PID), Some(ProductId::new()));
+        assert_eq!(ProductId::find(JCM_VID, 0xffff), None);
+        assert_eq!(ProductId::supported_pairs(), "2475:0105");
+
+        assert_eq!(
+            ProductId::detect_family(JCM_VID, JCM_PID, Some("UBA-14")),
+            ProductFamily::Uba
+        );
+        assert_eq!(
+            ProductId::detect_family(JCM_VID, JCM_PID, None),
+            ProductFamily::Unknown
+        );
+
+        let custom = ProductId::create(0x1234, 0x5678, ProductFamily::Tbv);
+        assert!(custom.matches(0x1234, 0x5678));
+        assert_eq!(
+            custom.to_string(),
+            r#"{"vendor_id": "1234", "product_id": "5678", "family": "TBV"}"#
+        );
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time;
+
+use crate::{
+    DeviceFrame, DeviceTransport, EventCode, EventType, Message, MessageCode, MessageData,
+    MessageType, ResponseCode, Result, TransportErrorKind, DEFAULT_TRANSPORT_TIMEOUT,
+};
+
+use super::transport_error;
+
+/// Builds the device frames written in reply to a host request.
+pub(crate) type Responder = dyn Fn(&Message) -> Result<Vec<Vec<u8>>> + Send + Sync;
+
+#[derive(Default)]
+struct SharedState {
+    frames: Mutex<VecDeque<(time::Instant, Vec<u8>)>>,
+    reads: AtomicUsize,
+    ops: Mutex<Vec<String>>,
+}
+
+/// Scriptable [DeviceTransport] shared by the polling tests.
+///
+/// Reads pop queued device frames. Every written request is answered by the responder, by
+/// default with an `ACK` echo of the request, after the configured delay. Clones share the same
+/// device, so tests keep a handle after moving the transport into a poller.
+#[derive(Clone)]
+pub(crate) struct TestTransport {
+    state: Arc<SharedState>,
+    responder: Arc<Responder>,
+    event_every: Option<usize>,
+    delay: time::Duration,
+    timeout: time::Duration,
+}
+
+impl TestTransport {
+    /// Creates a new [TestTransport] answering every request with an `ACK`.
+    pub fn new() -> Self {
+        Self::with_responder(|msg| Ok(vec![ack(msg).into()]))
+    }
+
+    /// Creates a new [TestTransport] answering requests with the provided [Responder].
+    pub fn with_responder<F>(responder: F) -> Self
+    where
+        F: Fn(&Message) -> Result<Vec<Vec<u8>>> + Send + Sync + 'static,
+    {
+        Self {
+            state: Arc::new(SharedState::default()),
+            responder: Arc::new(responder),
+            event_every: None,
+            delay: time::Duration::ZERO,
+            timeout: DEFAULT_TRANSPORT_TIMEOUT,
+        }
+    }
+
+    /// Builder function that interleaves an `Idle` event before every `n`-th read.
+    pub fn with_event_every(mut self, n: usize) -> Self {
+        self.event_every = Some(n);
+        self
+    }
+
+    /// Builder function that delays every response by `delay`.
+    pub fn with_delay(mut self, delay: time::Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Builder function that sets the transport timeout.
+    pub fn with_timeout(mut self, timeout: time::Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Queues a device-sent [Message], e.g. an event, for the next read.
+    pub fn push(&self, msg: Message) {
+        self.state
+            .frames
+            .lock()
+            .unwrap()
+            .push_back((time::Instant::now(), msg.into()));
+    }
+
+    /// Queues a raw device frame, e.g. a vendor-specific event, for the next read.
+    pub fn push_frame(&self, frame: Vec<u8>) {
+        self.state
+            .frames
+            .lock()
+            .unwrap()
+            .push_back((time::Instant::now(), frame));
+    }
+
+    /// Gets the number of reads, including reads that timed out.
+    pub fn reads(&self) -> usize {
+        self.state.reads.load(Ordering::SeqCst)
+    }
+
+    /// Gets the recorded successful reads and event responses, in order.
+    pub fn ops(&self) -> Vec<String> {
+        self.state.ops.lock().unwrap().clone()
+    }
+
+    /// Gets the number of event responses written by the host.
+    pub fn event_responses(&self) -> usize {
+        self.ops().iter().filter(|op| op.starts_with("ack")).count()
+    }
+}
+
+impl DeviceTransport for TestTransport {
+    fn write_message(&self, message: &Message) -> Result<()> {
+        let ready = time::Instant::now() + self.delay;
+        let frames = (self.responder)(message)?;
+
+        self.state
+            .frames
+            .lock()
+            .unwrap()
+            .extend(frames.into_iter().map(|frame| (ready, frame)));
+
+        Ok(())
+    }
+
+    fn read_message(&self) -> Result<Message> {
+        match self.read_frame()? {
+            DeviceFrame::Message(msg) => Ok(msg),
+            DeviceFrame::Undecoded((_, err)) => Err(err),
+        }
+    }
+
+    fn read_frame(&self) -> Result<DeviceFrame> {
+        let reads = self.state.reads.fetch_add(1, Ordering::SeqCst);
+
+        let msg = match self.event_every {
+            Some(n) if reads.is_multiple_of(n) => idle_event(),
+            _ => {
+                let mut frames = self.state.frames.lock().unwrap();
+                match frames.front() {
+                    Some((ready, _)) if *ready <= time::Instant::now() => {
+                        let (_, frame) = frames.pop_front().unwrap();
+                        match Message::try_from(frame.as_slice()) {
+                            Ok(msg) => msg,
+                            Err(err) => return Ok(DeviceFrame::Undecoded((frame, err))),
+                        }
+                    }
+                    _ => {
+                        return Err(transport_error(
+                            TransportErrorKind::Timeout,
+                            "no message available",
+                        ))
+                    }
+                }
+            }
+        };
+
+        self.state.ops.lock().unwrap().push("read".into());
+
+        Ok(DeviceFrame::Message(msg))
+    }
+
+    fn write_event_response(&self, message: &Message) -> Result<()> {
+        let code = message.data().message_code().event_code()?;
+        self.state.ops.lock().unwrap().push(format!("ack {code}"));
+        Ok(())
+    }
+
+    fn write_frame(&self, frame: &[u8]) -> Result<()> {
+        self.state
+            .ops
+            .lock()
+            .unwrap()
+            .push(format!("frame {frame:02x?}"));
+        Ok(())
+    }
+
+    fn timeout(&self) -> time::Duration {
+        self.timeout
+    }
+}
+
+/// Gets the `ACK` response to a request.
+pub(crate) fn ack(request: &Message) -> Message {
+    Message::new().with_data(
+        request
+            .data()
+            .clone()
+            .with_additional(&[u8::from(ResponseCode::Ack)]),
+    )
+}
+
+/// Gets a device `Idle` event.
+pub(crate) fn idle_event() -> Message {
+    Message::new().with_data(
+        MessageData::new()
+            .with_message_type(MessageType::Event(EventType::Sequence0))
+            .with_message_code(MessageCode::Event(EventCode::Idle)),
+    )
+}
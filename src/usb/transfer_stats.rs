@@ -0,0 +1,192 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use nusb::transfer::TransferError;
+
+/// Represents a snapshot of the transport-level counters of a
+/// [UsbDeviceHandle](super::UsbDeviceHandle).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct TransferStats {
+    transfers_out: u64,
+    transfers_in: u64,
+    bytes_out: u64,
+    bytes_in: u64,
+    errors: u64,
+    timeouts: u64,
+    stalls: u64,
+}
+
+impl TransferStats {
+    /// Creates a new [TransferStats].
+    pub const fn new() -> Self {
+        Self {
+            transfers_out: 0,
+            transfers_in: 0,
+            bytes_out: 0,
+            bytes_in: 0,
+            errors: 0,
+            timeouts: 0,
+            stalls: 0,
+        }
+    }
+
+    /// Gets the number of completed bulk OUT transfers.
+    pub const fn transfers_out(&self) -> u64 {
+        self.transfers_out
+    }
+
+    /// Gets the number of completed bulk IN transfers.
+    pub const fn transfers_in(&self) -> u64 {
+        self.transfers_in
+    }
+
+    /// Gets the number of bytes written to the device.
+    pub const fn bytes_out(&self) -> u64 {
+        self.bytes_out
+    }
+
+    /// Gets the number of bytes read from the device.
+    pub const fn bytes_in(&self) -> u64 {
+        self.bytes_in
+    }
+
+    /// Gets the number of failed bulk transfers, including stalls.
+    pub const fn errors(&self) -> u64 {
+        self.errors
+    }
+
+    /// Gets the number of bulk transfers that timed out.
+    pub const fn timeouts(&self) -> u64 {
+        self.timeouts
+    }
+
+    /// Gets the number of bulk transfers that failed with an endpoint stall.
+    pub const fn stalls(&self) -> u64 {
+        self.stalls
+    }
+}
+
+impl fmt::Display for TransferStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""transfers_out": {}, "#, self.transfers_out)?;
+        write!(f, r#""transfers_in": {}, "#, self.transfers_in)?;
+        write!(f, r#""bytes_out": {}, "#, self.bytes_out)?;
+        write!(f, r#""bytes_in": {}, "#, self.bytes_in)?;
+        write!(f, r#""errors": {}, "#, self.errors)?;
+        write!(f, r#""timeouts": {}, "#, self.timeouts)?;
+        write!(f, r#""stalls": {}"#, self.stalls)?;
+        write!(f, "}}")
+    }
+}
+
+/// Accumulates the transport-level counters shared by the polling threads.
+#[derive(Debug, Default)]
+pub(crate) struct TransferCounters {
+    transfers_out: AtomicU64,
+    transfers_in: AtomicU64,
+    bytes_out: AtomicU64,
+    bytes_in: AtomicU64,
+    errors: AtomicU64,
+    timeouts: AtomicU64,
+    stalls: AtomicU64,
+}
+
+impl TransferCounters {
+    /// Creates a new [TransferCounters].
+    pub const fn new() -> Self {
+        Self {
+            transfers_out: AtomicU64::new(0),
+            transfers_in: AtomicU64::new(0),
+            bytes_out: AtomicU64::new(0),
+            bytes_in: AtomicU64::new(0),
+            errors: AtomicU64::new(0),
+            timeouts: AtomicU64::new(0),
+            stalls: AtomicU64::new(0),
+        }
+    }
+
+    /// Records a completed bulk OUT transfer.
+    pub fn record_out(&self, len: usize) {
+        self.transfers_out.fetch_add(1, Ordering::Relaxed);
+        self.bytes_out.fetch_add(len as u64, Ordering::Relaxed);
+    }
+
+    /// Records a completed bulk IN transfer.
+    pub fn record_in(&self, len: usize) {
+        self.transfers_in.fetch_add(1, Ordering::Relaxed);
+        self.bytes_in.fetch_add(len as u64, Ordering::Relaxed);
+    }
+
+    /// Records a failed bulk transfer.
+    pub fn record_error(&self, err: &TransferError) {
+        self.errors.fetch_add(1, Ordering::Relaxed);
+        if matches!(err, TransferError::Stall) {
+            self.stalls.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Records a bulk transfer that timed out.
+    pub fn record_timeout(&self) {
+        self.timeouts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Gets a snapshot of the counters.
+    pub fn stats(&self) -> TransferStats {
+        self.collect(|counter| counter.load(Ordering::Relaxed))
+    }
+
+    /// Gets a snapshot of the counters, and resets them.
+    ///
+    /// Each counter is swapped individually, so no transfer is lost or counted twice between
+    /// consecutive calls.
+    pub fn take(&self) -> TransferStats {
+        self.collect(|counter| counter.swap(0, Ordering::Relaxed))
+    }
+
+    fn collect(&self, read: impl Fn(&AtomicU64) -> u64) -> TransferStats {
+        TransferStats {
+            transfers_out: read(&self.transfers_out),
+            transfers_in: read(&self.transfers_in),
+            bytes_out: read(&self.bytes_out),
+            bytes_in: read(&self.bytes_in),
+            errors: read(&self.errors),
+            timeouts: read(&self.timeouts),
+            stalls: read(&self.stalls),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_counters() {
+        let counters = TransferCounters::new();
+
+        counters.record_out(7);
+        counters.record_in(64);
+        counters.record_in(3);
+        counters.record_error(&TransferError::Stall);
+        counters.record_error(&TransferError::Fault);
+        counters.record_timeout();
+
+        let stats = counters.stats();
+        assert_eq!(stats.transfers_out(), 1);
+        assert_eq!(stats.transfers_in(), 2);
+        assert_eq!(stats.bytes_out(), 7);
+        assert_eq!(stats.bytes_in(), 67);
+        assert_eq!(stats.errors(), 2);
+        assert_eq!(stats.stalls(), 1);
+        assert_eq!(stats.timeouts(), 1);
+
+        assert_eq!(counters.take(), stats);
+        assert_eq!(counters.stats(), TransferStats::new());
+        assert_eq!(
+            stats.to_string(),
+            r#"{"transfers_out": 1, "transfers_in": 2, "bytes_out": 7, "bytes_in": 67, "errors": 2, "timeouts": 1, "stalls": 1}"#
+        );
+    }
+}
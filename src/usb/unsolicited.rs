@@ -0,0 +1,116 @@
+use std::fmt;
+
+use crate::{redact, Message, PollObserver};
+
+/// Represents how an [UnsolicitedSink] handles responses that match no outstanding request.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum UnsolicitedPolicy {
+    /// Silently drop unsolicited responses.
+    Drop,
+    /// Log unsolicited responses as warnings, then drop them.
+    #[default]
+    Log,
+    /// Log unsolicited responses, and deliver them on a dedicated channel.
+    Deliver,
+}
+
+impl From<UnsolicitedPolicy> for &'static str {
+    fn from(val: UnsolicitedPolicy) -> Self {
+        match val {
+            UnsolicitedPolicy::Drop => "drop",
+            UnsolicitedPolicy::Log => "log",
+            UnsolicitedPolicy::Deliver => "deliver",
+        }
+    }
+}
+
+impl From<&UnsolicitedPolicy> for &'static str {
+    fn from(val: &UnsolicitedPolicy) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for UnsolicitedPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
+
+/// Handles unsolicited responses seen while polling requests.
+///
+/// Pass the [UnsolicitedSink] as the observer to
+/// [poll_request_observed](crate::usb::poll_request_observed), so late responses are never
+/// misattributed to the next request with the same code.
+#[derive(Clone, Debug, Default)]
+pub struct UnsolicitedSink {
+    policy: UnsolicitedPolicy,
+    sender: Option<crossbeam::channel::Sender<Message>>,
+}
+
+impl UnsolicitedSink {
+    /// Creates a new [UnsolicitedSink] with the provided [UnsolicitedPolicy].
+    ///
+    /// A [UnsolicitedPolicy::Deliver] sink without a channel only logs responses. Use
+    /// [deliver](Self::deliver) to create a delivering sink.
+    pub const fn new(policy: UnsolicitedPolicy) -> Self {
+        Self {
+            policy,
+            sender: None,
+        }
+    }
+
+    /// Creates a new [UnsolicitedSink] that delivers unsolicited responses on the channel.
+    pub const fn deliver(sender: crossbeam::channel::Sender<Message>) -> Self {
+        Self {
+            policy: UnsolicitedPolicy::Deliver,
+            sender: Some(sender),
+        }
+    }
+
+    /// Gets the [UnsolicitedPolicy] of the [UnsolicitedSink].
+    pub const fn policy(&self) -> UnsolicitedPolicy {
+        self.policy
+    }
+}
+
+impl PollObserver for UnsolicitedSink {
+    fn on_unsolicited(&self, response: &Message) {
+        match self.policy {
+            UnsolicitedPolicy::Drop => (),
+            UnsolicitedPolicy::Log => {
+                log::warn!("dropping unsolicited response: {}", redact(response))
+            }
+            UnsolicitedPolicy::Deliver => {
+                log::warn!("unsolicited response: {}", redact(response));
+
+                if let Some(sender) = self.sender.as_ref() {
+                    if let Err(err) = sender.send(response.clone()) {
+                        log::warn!("error delivering unsolicited response: {err}");
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unsolicited_sink() {
+        let (send, recv) = crossbeam::channel::unbounded();
+        let msg = Message::new();
+
+        UnsolicitedSink::new(UnsolicitedPolicy::Drop).on_unsolicited(&msg);
+        UnsolicitedSink::new(UnsolicitedPolicy::Deliver).on_unsolicited(&msg);
+
+        let sink = UnsolicitedSink::deliver(send);
+        assert_eq!(sink.policy(), UnsolicitedPolicy::Deliver);
+
+        sink.on_unsolicited(&msg);
+        assert_eq!(recv.try_recv(), Ok(msg));
+        assert!(recv.try_recv().is_err());
+    }
+}
@@ -0,0 +1,364 @@
+use std::fmt;
+
+use crate::{
+    redact, Credit, CreditJournal, CreditLedger, EscrowData, EventCode, Message, ResponseCode,
+    Result, VendValidEvent,
+};
+
+/// Creates the `ACK` response [Message] to a device event [Message].
+pub fn event_ack(event: &Message) -> Message {
+    Message::new().with_data(
+        event
+            .data()
+            .clone()
+            .with_additional(&[ResponseCode::Ack.into()]),
+    )
+}
+
+/// Represents when the host acknowledges a `Vend Valid` event.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum VendValidAckMode {
+    /// Acknowledge as soon as the [Credit] is journaled.
+    Immediate,
+    /// Withhold the acknowledgement until the application confirms it recorded the [Credit].
+    ///
+    /// The device keeps the note in its `Vend Valid` state, and resends the event, until the
+    /// host acknowledges it.
+    #[default]
+    Confirmed,
+}
+
+impl From<VendValidAckMode> for &'static str {
+    fn from(val: VendValidAckMode) -> Self {
+        match val {
+            VendValidAckMode::Immediate => "immediate",
+            VendValidAckMode::Confirmed => "confirmed",
+        }
+    }
+}
+
+impl From<&VendValidAckMode> for &'static str {
+    fn from(val: &VendValidAckMode) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for VendValidAckMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&str>::from(self))
+    }
+}
+
+/// Holds the acknowledgement of a `Vend Valid` event until the host decides to send it.
+///
+/// The guard must be resolved explicitly: [confirm](Self::confirm) releases the `ACK` once the
+/// credit is durably recorded, and [defer](Self::defer) withholds it, so the device resends the
+/// event. With [VendValidAckMode::Immediate], [auto_ack](Self::auto_ack) releases the `ACK`
+/// without confirmation.
+///
+/// A guard dropped without being resolved is treated as deferred, and logged as a warning.
+///
+/// # Example
+///
+/// ```
+/// use jcm::{EventType, Message, VendValidAckMode, VendValidEvent, VendValidGuard};
+///
+/// # pub fn main() -> jcm::Result<()> {
+/// let event = Message::from(VendValidEvent::create(EventType::Sequence1));
+///
+/// let guard = VendValidGuard::create(&event, VendValidAckMode::Confirmed)?;
+/// let guard = guard.auto_ack().unwrap_err();
+///
+/// // record the credit, then release the ACK
+/// let ack = guard.confirm();
+/// assert_eq!(ack.data().message_code(), event.data().message_code());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+#[must_use = "a Vend Valid event must be confirmed or deferred"]
+pub struct VendValidGuard {
+    event: VendValidEvent,
+    mode: VendValidAckMode,
+    response: Message,
+    resolved: bool,
+}
+
+impl VendValidGuard {
+    /// Creates a new [VendValidGuard] from a `Vend Valid` event [Message].
+    ///
+    /// Fails if the [Message] is not a `Vend Valid` event.
+    pub fn create(event: &Message, mode: VendValidAckMode) -> Result<Self> {
+        Ok(Self {
+            event: event.try_into()?,
+            mode,
+            response: event_ack(event),
+            resolved: false,
+        })
+    }
+
+    /// Builder function that attaches the [EscrowData] from the preceding `Escrow` event.
+    pub fn with_escrow(mut self, escrow: EscrowData) -> Self {
+        self.event.set_escrow(escrow);
+        self
+    }
+
+    /// Gets a reference to the [VendValidEvent].
+    pub const fn event(&self) -> &VendValidEvent {
+        &self.event
+    }
+
+    /// Gets the [VendValidAckMode] of the [VendValidGuard].
+    pub const fn mode(&self) -> VendValidAckMode {
+        self.mode
+    }
+
+    /// Releases the `ACK` if the [VendValidAckMode] does not require confirmation.
+    ///
+    /// Returns the `ACK` response [Message] with [VendValidAckMode::Immediate], and the
+    /// [VendValidGuard] back with [VendValidAckMode::Confirmed].
+    pub fn auto_ack(self) -> std::result::Result<Message, Self> {
+        match self.mode {
+            VendValidAckMode::Immediate => Ok(self.confirm()),
+            VendValidAckMode::Confirmed => Err(self),
+        }
+    }
+
+    /// Confirms the host recorded the credit, and returns the `ACK` response [Message] to send
+    /// to the device.
+    pub fn confirm(mut self) -> Message {
+        self.resolved = true;
+        std::mem::take(&mut self.response)
+    }
+
+    /// Withholds the `ACK`, so the device resends the `Vend Valid` event.
+    pub fn defer(mut self) {
+        log::debug!(
+            "deferring Vend Valid ACK, event type: {}",
+            self.event.event_type()
+        );
+        self.resolved = true;
+    }
+}
+
+impl Drop for VendValidGuard {
+    fn drop(&mut self) {
+        if !self.resolved {
+            log::warn!("Vend Valid guard dropped without confirm or defer, ACK withheld");
+        }
+    }
+}
+
+/// Represents the host handling of a device event.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventAck {
+    credit: Option<Credit>,
+    response: Option<Message>,
+}
+
+impl EventAck {
+    /// Gets the new [Credit] delivered by the event, if any.
+    pub const fn credit(&self) -> Option<&Credit> {
+        self.credit.as_ref()
+    }
+
+    /// Gets the response [Message] to send to the device now.
+    ///
+    /// Returns `None` if the acknowledgement is withheld until the [Credit] is confirmed.
+    pub const fn response(&self) -> Option<&Message> {
+        self.response.as_ref()
+    }
+
+    /// Gets whether the acknowledgement is withheld until the [Credit] is confirmed.
+    pub const fn is_withheld(&self) -> bool {
+        self.response.is_none()
+    }
+}
+
+/// Acknowledges device events, tying the `Vend Valid` acknowledgement to credit confirmation.
+///
+/// With [VendValidAckMode::Confirmed], the `ACK` to a `Vend Valid` event is only released by
+/// [confirm](Self::confirm), after the application has durably recorded the [Credit]. A host
+/// crash before confirmation leaves the device retrying the event, instead of losing the credit.
+///
+/// Each `Vend Valid` event is held in a [VendValidGuard], so the acknowledger releases and
+/// withholds the `ACK` the same way as a host resolving the guard directly.
+#[derive(Debug)]
+pub struct CreditAcknowledger<J: CreditJournal> {
+    ledger: CreditLedger<J>,
+    mode: VendValidAckMode,
+    withheld: Option<(u64, VendValidGuard)>,
+}
+
+impl<J: CreditJournal> CreditAcknowledger<J> {
+    /// Creates a new [CreditAcknowledger] from the provided [CreditLedger].
+    pub fn new(ledger: CreditLedger<J>) -> Self {
+        Self {
+            ledger,
+            mode: VendValidAckMode::default(),
+            withheld: None,
+        }
+    }
+
+    /// Gets the [VendValidAckMode].
+    pub const fn mode(&self) -> VendValidAckMode {
+        self.mode
+    }
+
+    /// Sets the [VendValidAckMode].
+    pub fn set_mode(&mut self, mode: VendValidAckMode) {
+        self.mode = mode;
+    }
+
+    /// Builder function that sets the [VendValidAckMode].
+    pub fn with_mode(mut self, mode: VendValidAckMode) -> Self {
+        self.set_mode(mode);
+        self
+    }
+
+    /// Gets a reference to the [CreditLedger].
+    pub const fn ledger(&self) -> &CreditLedger<J> {
+        &self.ledger
+    }
+
+    /// Gets the ID of the [Credit] awaiting confirmation, if any.
+    pub fn withheld(&self) -> Option<u64> {
+        self.withheld.as_ref().map(|(id, _)| *id)
+    }
+
+    /// Processes a device event [Message].
+    ///
+    /// A resent `Vend Valid` event is not acknowledged while its [Credit] awaits confirmation.
+    pub fn on_event(&mut self, event: &Message) -> Result<EventAck> {
+        match self.ledger.on_event(event)? {
+            Some(credit) => match VendValidGuard::create(event, self.mode)?.auto_ack() {
+                Ok(response) => Ok(EventAck {
+                    credit: Some(credit),
+                    response: Some(response),
+                }),
+                Err(guard) => {
+                    log::debug!("withholding Vend Valid ACK for credit: {}", redact(&credit));
+
+                    if let Some((_, previous)) = self.withheld.replace((credit.id(), guard)) {
+                        previous.defer();
+                    }
+
+                    Ok(EventAck {
+                        credit: Some(credit),
+                        response: None,
+                    })
+                }
+            },
+            None if self.withheld.is_some()
+                && matches!(
+                    event.data().message_code().event_code(),
+                    Ok(EventCode::VendValid)
+                ) =>
+            {
+                Ok(EventAck {
+                    credit: None,
+                    response: None,
+                })
+            }
+            None => Ok(EventAck {
+                credit: None,
+                response: Some(event_ack(event)),
+            }),
+        }
+    }
+
+    /// Confirms the application durably recorded the [Credit] with the provided ID.
+    ///
+    /// The [Credit] is acknowledged in the [CreditLedger] in both [VendValidAckMode]s.
+    ///
+    /// Returns the withheld `ACK` response [Message] to send to the device, or `None` if no
+    /// acknowledgement was withheld for the [Credit].
+    pub fn confirm(&mut self, id: u64) -> Result<Option<Message>> {
+        self.ledger.acknowledge(id)?;
+
+        match self.withheld.take() {
+            Some((withheld, guard)) if withheld == id => Ok(Some(guard.confirm())),
+            withheld => {
+                self.withheld = withheld;
+                Ok(None)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Currency, CurrencyCode, Denomination, EventType, MemoryJournal, MessageCode, MessageData,
+        MessageType,
+    };
+
+    fn event(event_type: EventType, code: EventCode) -> Message {
+        Message::new().with_data(
+            MessageData::new()
+                .with_message_type(MessageType::Event(event_type))
+                .with_message_code(MessageCode::Event(code)),
+        )
+    }
+
+    #[test]
+    fn test_credit_acknowledger() -> Result<()> {
+        let mut acker = CreditAcknowledger::new(CreditLedger::open(MemoryJournal::new())?);
+        let vend = event(EventType::Sequence1, EventCode::VendValid);
+
+        let ack = acker.on_event(&vend)?;
+        let credit = ack.credit().cloned().unwrap();
+        assert!(ack.is_withheld());
+        assert_eq!(acker.withheld(), Some(credit.id()));
+
+        // the device retries the event until it is acknowledged
+        assert!(acker.on_event(&vend)?.is_withheld());
+
+        assert_eq!(acker.confirm(credit.id() + 1)?, None);
+        assert_eq!(acker.confirm(credit.id())?, Some(event_ack(&vend)));
+        assert_eq!(acker.ledger().pending().count(), 0);
+
+        let idle = event(EventType::Sequence2, EventCode::Idle);
+        assert_eq!(acker.on_event(&idle)?.response(), Some(&event_ack(&idle)));
+
+        let mut acker = acker.with_mode(VendValidAckMode::Immediate);
+        let vend = event(EventType::Sequence3, EventCode::VendValid);
+        let ack = acker.on_event(&vend)?;
+        assert!(ack.credit().is_some());
+        assert_eq!(ack.response(), Some(&event_ack(&vend)));
+        assert_eq!(acker.confirm(ack.credit().unwrap().id())?, None);
+        assert_eq!(acker.ledger().pending().count(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_vend_valid_guard() -> Result<()> {
+        let vend = event(EventType::Sequence1, EventCode::VendValid);
+        let escrow = EscrowData::new_currency(
+            Currency::new()
+                .with_code(CurrencyCode::USD)
+                .with_denomination(Denomination::from_value(5)),
+        );
+
+        let guard =
+            VendValidGuard::create(&vend, VendValidAckMode::Confirmed)?.with_escrow(escrow.clone());
+        assert_eq!(guard.event().escrow(), Some(&escrow));
+        assert_eq!(guard.event().amount().map(|a| a.minor_value()), Some(500));
+
+        let guard = guard.auto_ack().unwrap_err();
+        assert_eq!(guard.confirm(), event_ack(&vend));
+
+        VendValidGuard::create(&vend, VendValidAckMode::Confirmed)?.defer();
+
+        let guard = VendValidGuard::create(&vend, VendValidAckMode::Immediate)?;
+        assert_eq!(guard.auto_ack().ok(), Some(event_ack(&vend)));
+
+        let idle = event(EventType::Sequence2, EventCode::Idle);
+        assert!(VendValidGuard::create(&idle, VendValidAckMode::Immediate).is_err());
+
+        Ok(())
+    }
+}
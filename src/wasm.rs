@@ -0,0 +1,128 @@
+//! Frame decoding for browser-based tooling.
+//!
+//! Build the codec without USB support for `wasm32-unknown-unknown`:
+//!
+//! ```bash
+//! cargo build --target wasm32-unknown-unknown --no-default-features --features wasm
+//! ```
+//!
+//! On `wasm32` targets, the module also exports a minimal C ABI, so tools can decode captured
+//! frames without additional bindings:
+//!
+//! - `jcm_alloc(len) -> *mut u8`: allocates an input buffer for a frame
+//! - `jcm_free(ptr, len)`: frees an input buffer
+//! - `jcm_describe_frame(ptr, len) -> *mut c_char`: decodes a frame to a NUL-terminated string
+//! - `jcm_free_string(ptr)`: frees a string returned by `jcm_describe_frame`
+
+use crate::{Message, Result};
+
+/// Decodes a captured frame into a [Message], using the same parsing logic as the driver.
+pub fn decode_frame(frame: &[u8]) -> Result<Message> {
+    Message::try_from(frame)
+}
+
+/// Decodes a captured frame into a JSON-formatted description.
+///
+/// Decoding errors are described in an `error` field, so tooling can always display a result.
+pub fn describe_frame(frame: &[u8]) -> String {
+    match decode_frame(frame) {
+        Ok(msg) => format!(r#"{{"message": {msg}}}"#),
+        Err(err) => format!(r#"{{"error": "{}"}}"#, json_escape(&err.to_string())),
+    }
+}
+
+// escapes a string for embedding in a JSON string literal
+fn json_escape(val: &str) -> String {
+    let mut out = String::with_capacity(val.len());
+
+    for c in val.chars() {
+        match c {
+            '"' => out.push_str(r#"\""#),
+            '\\' => out.push_str(r"\\"),
+            '\n' => out.push_str(r"\n"),
+            '\r' => out.push_str(r"\r"),
+            '\t' => out.push_str(r"\t"),
+            c if c.is_control() => out.push_str(format!(r"\u{:04x}", c as u32).as_str()),
+            c => out.push(c),
+        }
+    }
+
+    out
+}
+
+#[cfg(target_arch = "wasm32")]
+mod abi {
+    use std::ffi::{c_char, CString};
+
+    #[no_mangle]
+    pub extern "C" fn jcm_alloc(len: usize) -> *mut u8 {
+        let mut buf = Vec::<u8>::with_capacity(len);
+        let ptr = buf.as_mut_ptr();
+        std::mem::forget(buf);
+        ptr
+    }
+
+    /// # Safety
+    ///
+    /// `ptr` and `len` must come from a previous call to [jcm_alloc].
+    #[no_mangle]
+    pub unsafe extern "C" fn jcm_free(ptr: *mut u8, len: usize) {
+        if !ptr.is_null() {
+            drop(Vec::from_raw_parts(ptr, 0, len));
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `ptr` must point to `len` initialized bytes.
+    #[no_mangle]
+    pub unsafe extern "C" fn jcm_describe_frame(ptr: *const u8, len: usize) -> *mut c_char {
+        let frame = if ptr.is_null() {
+            &[]
+        } else {
+            std::slice::from_raw_parts(ptr, len)
+        };
+
+        CString::new(super::describe_frame(frame))
+            .unwrap_or_default()
+            .into_raw()
+    }
+
+    /// # Safety
+    ///
+    /// `ptr` must come from a previous call to [jcm_describe_frame].
+    #[no_mangle]
+    pub unsafe extern "C" fn jcm_free_string(ptr: *mut c_char) {
+        if !ptr.is_null() {
+            drop(CString::from_raw(ptr));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventCode, EventType, MessageCode, MessageData, MessageType};
+
+    #[test]
+    fn test_describe_frame() -> Result<()> {
+        let msg = Message::new().with_data(
+            MessageData::new()
+                .with_message_type(MessageType::Event(EventType::Sequence0))
+                .with_message_code(MessageCode::Event(EventCode::Idle)),
+        );
+        let mut frame = vec![0u8; msg.len()];
+        msg.to_bytes(&mut frame)?;
+
+        assert_eq!(decode_frame(&frame)?, msg);
+        assert_eq!(describe_frame(&frame), format!(r#"{{"message": {msg}}}"#));
+        assert!(describe_frame(&[]).starts_with(r#"{"error": "#));
+
+        assert_eq!(
+            json_escape("unexpected \"code\"\\\n\u{1}"),
+            r#"unexpected \"code\"\\\n\u0001"#
+        );
+
+        Ok(())
+    }
+}
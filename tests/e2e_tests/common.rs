@@ -1,4 +1,4 @@
-use jcm::{Error, Result};
+use jcm::{Error, Result, TransportError, TransportErrorKind};
 use std::sync::{Mutex, MutexGuard};
 
 static INIT: Mutex<()> = Mutex::new(());
@@ -8,7 +8,10 @@ pub fn init() -> Result<MutexGuard<'static, ()>> {
         .format_timestamp_millis()
         .try_init()
         .ok();
-    Ok(INIT
-        .lock()
-        .map_err(|err| Error::Usb(format!("unable to lock e2e-test mutex: {err}")))?)
+    INIT.lock().map_err(|err| {
+        Error::from(TransportError::new(
+            TransportErrorKind::Other,
+            &format!("unable to lock e2e-test mutex: {err}"),
+        ))
+    })
 }
@@ -1,40 +1,14 @@
+// exercises the `Mutex` polling API on hardware until it is removed
+#![allow(deprecated)]
+
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::{thread, time};
 
-use jcm::{Error, Result};
+use jcm::Result;
 
 use super::common;
 
-/// Dummy event responder that sends `ACK` responses to all device-sent events.
-fn ack_event_responder(
-    stop: Arc<AtomicBool>,
-    event_recv: crossbeam::channel::Receiver<jcm::Message>,
-    event_res_send: crossbeam::channel::Sender<jcm::Message>,
-) -> Result<()> {
-    thread::spawn(move || -> Result<()> {
-        while !stop.load(Ordering::Relaxed) {
-            if let Ok(event) = event_recv.try_recv() {
-                event_res_send
-                    .try_send(
-                        jcm::Message::new().with_data(
-                            event
-                                .data()
-                                .clone()
-                                .with_additional(&[jcm::ResponseCode::Ack.into()]),
-                        ),
-                    )
-                    .map_err(|err| Error::Usb(format!("error sending event response: {err}")))?;
-            }
-
-            thread::sleep(time::Duration::from_millis(100));
-        }
-        Ok(())
-    });
-
-    Ok(())
-}
-
 fn common_startup(
     usb: &Arc<Mutex<jcm::usb::UsbDeviceHandle>>,
     response_recv: &crossbeam::channel::Receiver<jcm::Message>,
@@ -78,6 +52,39 @@ fn common_startup(
     Ok(())
 }
 
+#[test]
+fn test_device_present() -> Result<()> {
+    let _lock = common::init()?;
+
+    assert!(jcm::usb::is_device_present());
+    assert!(!jcm::usb::list_present().is_empty());
+
+    // presence checks leave the device available to the driver
+    jcm::usb::UsbDeviceHandle::find_usb()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_idle_read_buffer_reuse() -> Result<()> {
+    let _lock = common::init()?;
+
+    let usb = jcm::usb::UsbDeviceHandle::find_usb()?;
+
+    // reads on an idle device time out, and return their buffers to the pool
+    for _ in 0..16 {
+        usb.read_event().ok();
+    }
+
+    let allocations = usb.buffer_pool().allocations();
+    assert!(
+        allocations <= 1,
+        "idle reads allocated {allocations} buffers"
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_device_status() -> Result<()> {
     let _lock = common::init()?;
@@ -99,7 +106,12 @@ fn test_device_status() -> Result<()> {
 
     jcm::usb::wait_for_power_up(&event_recv, &event_res_send).ok();
 
-    ack_event_responder(Arc::clone(&stop), event_recv, event_res_send)?;
+    jcm::usb::spawn_event_acknowledger(
+        Arc::clone(&stop),
+        event_recv,
+        event_res_send,
+        jcm::usb::EventAckPolicy::AckAll,
+    );
 
     let req: jcm::Message = jcm::MessageData::from(jcm::UidRequest::new_set(0x1)).into();
     let res = jcm::usb::poll_request(Arc::clone(&usb), &req, &response_recv, 3)?;
@@ -143,7 +155,12 @@ fn test_full_startup() -> Result<()> {
 
     jcm::usb::wait_for_power_up(&event_recv, &event_res_send).ok();
 
-    ack_event_responder(Arc::clone(&stop), event_recv, event_res_send)?;
+    jcm::usb::spawn_event_acknowledger(
+        Arc::clone(&stop),
+        event_recv,
+        event_res_send,
+        jcm::usb::EventAckPolicy::AckAll,
+    );
 
     common_startup(&usb, &response_recv)?;
 
@@ -182,7 +199,12 @@ fn test_denomination_disable() -> Result<()> {
 
     jcm::usb::wait_for_power_up(&event_recv, &event_res_send).ok();
 
-    ack_event_responder(Arc::clone(&stop), event_recv, event_res_send)?;
+    jcm::usb::spawn_event_acknowledger(
+        Arc::clone(&stop),
+        event_recv,
+        event_res_send,
+        jcm::usb::EventAckPolicy::AckAll,
+    );
 
     let req: jcm::Message = jcm::MessageData::from(jcm::UidRequest::new_set(0x1)).into();
     let res = jcm::usb::poll_request(Arc::clone(&usb), &req, &response_recv, 3)?;
@@ -281,37 +303,23 @@ fn test_direction_disable() -> Result<()> {
 
     jcm::usb::wait_for_power_up(&event_recv, &event_res_send).ok();
 
-    ack_event_responder(Arc::clone(&stop), event_recv, event_res_send)?;
+    jcm::usb::spawn_event_acknowledger(
+        Arc::clone(&stop),
+        event_recv,
+        event_res_send,
+        jcm::usb::EventAckPolicy::AckAll,
+    );
 
     common_startup(&usb, &response_recv)?;
 
     thread::sleep(time::Duration::from_millis(5000));
 
-    let req: jcm::Message = jcm::MessageData::from(jcm::DirectionDisableRequest::new())
-        .with_uid(1)
-        .into();
-    let res: jcm::DirectionDisableResponse =
-        jcm::usb::poll_request(Arc::clone(&usb), &req, &response_recv, 3)?.try_into()?;
-
-    log::info!("Direction disable (get) response: {res}");
-
-    let dir_req = jcm::DirectionDisableRequest::new()
-        .with_mode(jcm::DirectionDisableMode::Set)
-        .with_direction(jcm::InhibitDirection::create(0xf));
-
-    let req: jcm::Message = jcm::MessageData::from(dir_req).with_uid(1).into();
-    let res: jcm::DirectionDisableResponse =
-        jcm::usb::poll_request(Arc::clone(&usb), &req, &response_recv, 3)?.try_into()?;
-
-    log::info!("Direction disable (set) response: {res}");
-
-    let req: jcm::Message = jcm::MessageData::from(jcm::DirectionDisableRequest::new())
-        .with_uid(1)
-        .into();
-    let res: jcm::DirectionDisableResponse =
-        jcm::usb::poll_request(Arc::clone(&usb), &req, &response_recv, 3)?.try_into()?;
+    let delta =
+        jcm::usb::modify_direction_disable(Arc::clone(&usb), &response_recv, 3, 1, |dirs| {
+            dirs.set(0xf);
+        })?;
 
-    log::info!("Direction disable (get) response: {res}");
+    log::info!("Direction disable delta: {delta}");
 
     let req: jcm::Message = jcm::MessageData::from(jcm::IdleRequest::new())
         .with_uid(1)
@@ -346,7 +354,12 @@ fn test_currency_assign() -> Result<()> {
 
     jcm::usb::wait_for_power_up(&event_recv, &event_res_send).ok();
 
-    ack_event_responder(Arc::clone(&stop), event_recv, event_res_send)?;
+    jcm::usb::spawn_event_acknowledger(
+        Arc::clone(&stop),
+        event_recv,
+        event_res_send,
+        jcm::usb::EventAckPolicy::AckAll,
+    );
 
     let req: jcm::Message = jcm::MessageData::from(jcm::CurrencyAssignRequest::new())
         .with_uid(1)
@@ -393,7 +406,12 @@ fn test_program_signature() -> Result<()> {
 
     jcm::usb::wait_for_power_up(&event_recv, &event_res_send).ok();
 
-    ack_event_responder(Arc::clone(&stop), event_recv, event_res_send)?;
+    jcm::usb::spawn_event_acknowledger(
+        Arc::clone(&stop),
+        event_recv,
+        event_res_send,
+        jcm::usb::EventAckPolicy::AckAll,
+    );
 
     let req: jcm::Message = jcm::MessageData::from(jcm::ProgramSignatureRequest::new())
         .with_uid(1)
@@ -460,7 +478,12 @@ fn test_model_name() -> Result<()> {
 
     jcm::usb::wait_for_power_up(&event_recv, &event_res_send).ok();
 
-    ack_event_responder(Arc::clone(&stop), event_recv, event_res_send)?;
+    jcm::usb::spawn_event_acknowledger(
+        Arc::clone(&stop),
+        event_recv,
+        event_res_send,
+        jcm::usb::EventAckPolicy::AckAll,
+    );
 
     let req: jcm::Message = jcm::MessageData::from(jcm::ModelNameRequest::new())
         .with_uid(1)
@@ -507,7 +530,12 @@ fn test_near_full() -> Result<()> {
 
     jcm::usb::wait_for_power_up(&event_recv, &event_res_send).ok();
 
-    ack_event_responder(Arc::clone(&stop), event_recv, event_res_send)?;
+    jcm::usb::spawn_event_acknowledger(
+        Arc::clone(&stop),
+        event_recv,
+        event_res_send,
+        jcm::usb::EventAckPolicy::AckAll,
+    );
 
     let req: jcm::Message = jcm::MessageData::from(jcm::NearFullRequest::new())
         .with_uid(1)
@@ -554,7 +582,12 @@ fn test_serial_number() -> Result<()> {
 
     jcm::usb::wait_for_power_up(&event_recv, &event_res_send).ok();
 
-    ack_event_responder(Arc::clone(&stop), event_recv, event_res_send)?;
+    jcm::usb::spawn_event_acknowledger(
+        Arc::clone(&stop),
+        event_recv,
+        event_res_send,
+        jcm::usb::EventAckPolicy::AckAll,
+    );
 
     common_startup(&usb, &response_recv)?;
 
@@ -615,7 +648,12 @@ fn test_note_image() -> Result<()> {
 
     jcm::usb::wait_for_power_up(&event_recv, &event_res_send).ok();
 
-    ack_event_responder(Arc::clone(&stop), event_recv, event_res_send)?;
+    jcm::usb::spawn_event_acknowledger(
+        Arc::clone(&stop),
+        event_recv,
+        event_res_send,
+        jcm::usb::EventAckPolicy::AckAll,
+    );
 
     common_startup(&usb, &response_recv)?;
 
@@ -628,27 +666,12 @@ fn test_note_image() -> Result<()> {
 
     log::info!("Idle response: {res}");
 
-    let req: jcm::Message = jcm::MessageData::from(jcm::NoteImageRequest::new())
-        .with_uid(1)
-        .into();
-    let res: jcm::NoteImageSizeResponse =
-        jcm::usb::poll_request(Arc::clone(&usb), &req, &response_recv, 3)?.try_into()?;
-
-    log::info!("Note Image size response: {res}");
+    let cancel = jcm::CancelToken::new();
+    let image = jcm::usb::fetch_note_image(Arc::clone(&usb), &response_recv, 3, &cancel, |p| {
+        log::info!("Note Image progress: {p}");
+    })?;
 
-    if res.is_supported() {
-        for block in (1..res.size_total().total_blocks() as u8).map(jcm::ImageBlockNumber::from) {
-            let req: jcm::Message =
-                jcm::MessageData::from(jcm::NoteImageRequest::new().with_block_number(block))
-                    .with_uid(1)
-                    .into();
-
-            let res: jcm::NoteImageBlockResponse =
-                jcm::usb::poll_request(Arc::clone(&usb), &req, &response_recv, 3)?.try_into()?;
-
-            log::info!("Note Image block[{block}] response: {res}");
-        }
-    }
+    log::info!("Note Image: {image}");
 
     stop.store(true, Ordering::SeqCst);
 
@@ -0,0 +1,162 @@
+//! Examples of the high-level host APIs, run against the simulated [MockDevice].
+
+#![cfg(feature = "std")]
+
+use jcm::mock::MockDevice;
+use jcm::{
+    Currency, CurrencyCode, DebugMonitor, Denomination, EscrowEvent, EscrowPolicy, EscrowRule,
+    EventCode, FirmwareVersion, Message, PollObserver, Result, StatusRequest, UidManager,
+};
+
+/// Acknowledges the next pending event, returning it.
+fn next_event(device: &MockDevice) -> Result<Message> {
+    let event = device.pending_event().expect("no pending event");
+    device.acknowledge_event(&jcm::event_ack(&event))?;
+    Ok(event)
+}
+
+/// Performs the startup sequence: `Power Up`, UID negotiation, and enabling the device.
+fn startup(device: &MockDevice, uids: &UidManager) -> Result<u8> {
+    let power_up = next_event(device)?;
+    assert_eq!(
+        power_up.data().message_code().event_code(),
+        Ok(EventCode::PowerUp)
+    );
+
+    let uid = uids.negotiate("mock", |req| device.handle_request(req))?;
+
+    let mut poll = uids.stamped("mock", |req| device.handle_request(req));
+    poll(&jcm::ResetRequest::new().into())?;
+    next_event(device)?;
+    poll(&jcm::IdleRequest::new().into())?;
+    next_event(device)?;
+
+    Ok(uid)
+}
+
+#[cfg(feature = "usb")]
+#[test]
+fn device_open() -> Result<()> {
+    use std::time;
+
+    let (event_send, event_recv) = crossbeam::channel::unbounded();
+    let (event_res_send, event_res_recv) = crossbeam::channel::unbounded();
+
+    let io = jcm::usb::DeviceIo::spawn(MockDevice::new(), event_send, event_res_recv);
+    let handle = io.handle();
+
+    let routine = jcm::PowerUpRoutine::new().with_grace_period(time::Duration::from_millis(300));
+    let report = handle.power_up(&event_recv, &event_res_send, &routine)?;
+    assert_eq!(report.events(), [EventCode::PowerUp]);
+
+    let uids = UidManager::new();
+    let uid = handle.negotiate_uid(&uids, "mock")?;
+    let res = handle.request(&jcm::UidRequest::new_get().into())?;
+    assert_eq!(jcm::UidResponse::try_from(&res)?.uid(), uid);
+
+    io.stop()
+}
+
+#[test]
+fn deposit() -> Result<()> {
+    let device = MockDevice::new();
+    let uids = UidManager::new();
+    startup(&device, &uids)?;
+
+    let policy = EscrowPolicy::new()
+        .with_rule(EscrowRule::AcceptCurrency(CurrencyCode::USD))
+        .with_rule(EscrowRule::RejectTickets);
+    let mut ledger = jcm::CreditLedger::open(jcm::MemoryJournal::new())?;
+
+    let note = Currency::new()
+        .with_code(CurrencyCode::USD)
+        .with_denomination(Denomination::from_value(50));
+    assert!(device.insert_note(note));
+
+    let escrow = next_event(&device)?;
+    ledger.on_event(&escrow)?;
+
+    let record = policy.on_escrow(&EscrowEvent::try_from(&escrow)?);
+    assert!(record.accepted());
+
+    let mut poll = uids.stamped("mock", |req| device.handle_request(req));
+    poll(&record.evaluation().to_request(jcm::UnitNumber::default()))?;
+
+    let credit = ledger
+        .on_event(&next_event(&device)?)?
+        .expect("missing credit");
+    ledger.acknowledge(credit.id())?;
+    assert_eq!(ledger.pending().count(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn config_apply() -> Result<()> {
+    let device = MockDevice::new();
+    next_event(&device)?;
+
+    let pipeline = jcm::ConfigPipeline::new()
+        .with_step("uid", jcm::UidRequest::new_set(7))
+        .with_step("enable", jcm::IdleRequest::new());
+
+    pipeline.run(|req| device.handle_request(req))?;
+    assert_eq!(device.uid(), 7);
+    assert_eq!(
+        device.status().major_minor_status(),
+        jcm::MajorMinorStatus::NormalIdle
+    );
+
+    Ok(())
+}
+
+#[test]
+fn snapshot() -> Result<()> {
+    let device = MockDevice::new();
+    let monitor = DebugMonitor::new();
+
+    let power_up = next_event(&device)?;
+    monitor.on_event(&power_up);
+
+    let status = Message::from(StatusRequest::new());
+    monitor.on_request_sent(&status, 0);
+    let res = device.handle_request(&status)?;
+    monitor.on_response(&status, &res, 0);
+
+    let state = monitor.snapshot(&[("events", 0)]);
+    assert_eq!(state.state(), jcm::BillAcceptorState::Initializing);
+    assert!(state.outstanding().is_empty());
+    assert_eq!(state.last_event(EventCode::PowerUp), Some(&power_up));
+
+    Ok(())
+}
+
+#[test]
+fn firmware_check() -> Result<()> {
+    let device = MockDevice::new();
+    let firmware = FirmwareVersion::new()
+        .with_firmware_name("iVIZION")
+        .with_interface_number("ID-008")
+        .with_version("V1.10")
+        .with_date("01JAN24");
+
+    // the mock answers `Version` requests through a wrapper, like a device-specific quirk layer
+    let poll = |req: &Message| -> Result<Message> {
+        match req.data().message_code().request_code() {
+            Ok(jcm::RequestCode::Version) => {
+                let mut additional = vec![jcm::ResponseCode::Ack.into()];
+                additional.extend(firmware.into_bytes());
+                Ok(req.data().clone().with_additional(&additional).into())
+            }
+            _ => device.handle_request(req),
+        }
+    };
+
+    let res = jcm::VersionResponse::try_from(&jcm::Response::try_from(&poll(
+        &jcm::VersionRequest::new().into(),
+    )?)?)?;
+    assert_eq!(res.firmware_version(), &firmware);
+    assert_eq!(res.firmware_version().version(), "V1.10");
+
+    Ok(())
+}